@@ -873,6 +873,7 @@ fn extension_only_provides_context_server(manifest: &ExtensionManifest) -> bool
         && manifest.indexed_docs_providers.is_empty()
         && manifest.snippets.is_none()
         && manifest.debug_locators.is_empty()
+        && manifest.debug_visualizers.is_empty()
 }
 
 pub(crate) fn resolve_extension_for_context_server(
@@ -443,6 +443,42 @@ impl Server {
             .add_request_handler(forward_mutating_project_request::<proto::SetIndexText>)
             .add_request_handler(forward_mutating_project_request::<proto::ToggleBreakpoint>)
             .add_message_handler(broadcast_project_message_from_host::<proto::BreakpointsForFile>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::UpdateDebugSession>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::DebugSessionEvent>)
+            .add_message_handler(broadcast_project_message_from_host::<proto::DebugSessionShutdown>)
+            .add_request_handler(forward_read_only_project_request::<proto::DapThreadsRequest>)
+            .add_request_handler(forward_read_only_project_request::<proto::DapStackTraceRequest>)
+            .add_request_handler(forward_read_only_project_request::<proto::DapScopesRequest>)
+            .add_request_handler(forward_read_only_project_request::<proto::VariablesRequest>)
+            .add_request_handler(forward_read_only_project_request::<proto::DapModulesRequest>)
+            .add_request_handler(
+                forward_read_only_project_request::<proto::DapLoadedSourcesRequest>,
+            )
+            .add_request_handler(forward_read_only_project_request::<proto::DapCompletionRequest>)
+            .add_request_handler(forward_read_only_project_request::<proto::DapLocationsRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapNextRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapStepInRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapStepOutRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapStepBackRequest>)
+            .add_request_handler(
+                forward_mutating_project_request::<proto::DapReverseContinueRequest>,
+            )
+            .add_request_handler(forward_mutating_project_request::<proto::DapContinueRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapPauseRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapDisconnectRequest>)
+            .add_request_handler(
+                forward_mutating_project_request::<proto::DapTerminateThreadsRequest>,
+            )
+            .add_request_handler(forward_mutating_project_request::<proto::DapTerminateRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapRestartRequest>)
+            .add_request_handler(
+                forward_mutating_project_request::<proto::DapRestartStackFrameRequest>,
+            )
+            .add_request_handler(
+                forward_mutating_project_request::<proto::DapSetVariableValueRequest>,
+            )
+            .add_request_handler(forward_mutating_project_request::<proto::DapSetExpressionRequest>)
+            .add_request_handler(forward_mutating_project_request::<proto::DapEvaluateRequest>)
             .add_request_handler(forward_mutating_project_request::<proto::OpenCommitMessageBuffer>)
             .add_request_handler(forward_mutating_project_request::<proto::GitDiff>)
             .add_request_handler(forward_mutating_project_request::<proto::GitCreateBranch>)
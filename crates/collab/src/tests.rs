@@ -9,6 +9,7 @@ mod channel_guest_tests;
 mod channel_message_tests;
 mod channel_tests;
 // mod debug_panel_tests;
+mod debug_session_tests;
 mod editor_tests;
 mod following_tests;
 mod git_tests;
@@ -0,0 +1,218 @@
+use crate::tests::TestServer;
+use call::ActiveCall;
+use dap::{
+    SteppingGranularity,
+    adapters::{DebugAdapterName, DebugTaskDefinition},
+    requests::{Next, Threads, Variables},
+};
+use gpui::{Entity, TestAppContext};
+use project::debugger::{
+    dap_store::DapStoreEvent,
+    session::{Session, ThreadId, ThreadStatus},
+};
+use serde_json::json;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use task::TaskContext;
+
+#[gpui::test]
+async fn test_guest_drives_host_debug_session(
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+    cx_c: &mut TestAppContext,
+) {
+    let executor = cx_a.executor();
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    let client_c = server.create_client(cx_c, "user_c").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b), (&client_c, cx_c)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+    cx_a.update(dap_adapters::init);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "main.rs": "fn main() {}" }))
+        .await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+
+    let remote_sessions = Rc::new(RefCell::new(Vec::<Entity<Session>>::new()));
+    let dap_store_b = project_b.read_with(cx_b, |project, _| project.dap_store());
+    let _subscription = cx_b.update(|cx| {
+        let remote_sessions = remote_sessions.clone();
+        cx.subscribe(&dap_store_b, move |_, event, _| {
+            if let DapStoreEvent::RemoteSessionAdded(session) = event {
+                remote_sessions.borrow_mut().push(session.clone());
+            }
+        })
+    });
+
+    let stepped = Arc::new(AtomicBool::new(false));
+    let _intercept = project::debugger::test::intercept_debug_sessions(cx_a, {
+        let stepped = stepped.clone();
+        move |client| {
+            client.on_request::<Threads, _>(move |_, _| {
+                Ok(dap::ThreadsResponse {
+                    threads: vec![dap::Thread {
+                        id: 1,
+                        name: "Thread 1".into(),
+                    }],
+                })
+            });
+            client.on_request::<Variables, _>(move |_, args| {
+                assert_eq!(args.variables_reference, 2);
+                Ok(dap::VariablesResponse {
+                    variables: vec![dap::Variable {
+                        name: "x".into(),
+                        value: "1".into(),
+                        type_: None,
+                        presentation_hint: None,
+                        evaluate_name: None,
+                        variables_reference: 0,
+                        named_variables: None,
+                        indexed_variables: None,
+                        memory_reference: None,
+                        declaration_location_reference: None,
+                        value_location_reference: None,
+                    }],
+                })
+            });
+            client.on_request::<Next, _>({
+                let stepped = stepped.clone();
+                move |_, args| {
+                    assert_eq!(args.thread_id, 1);
+                    stepped.store(true, Ordering::SeqCst);
+                    Ok(())
+                }
+            });
+        }
+    });
+
+    // The host starts a session, which the guest mirrors.
+    let dap_store_a = project_a.read_with(cx_a, |project, _| project.dap_store());
+    let worktree_a = project_a.read_with(cx_a, |project, cx| project.worktrees(cx).next().unwrap());
+    let session_a = dap_store_a.update(cx_a, |dap_store, cx| {
+        dap_store.new_session(
+            "test".into(),
+            DebugAdapterName("fake-adapter".into()),
+            TaskContext::default(),
+            None,
+            cx,
+        )
+    });
+    dap_store_a
+        .update(cx_a, |dap_store, cx| {
+            dap_store.boot_session(
+                session_a.clone(),
+                DebugTaskDefinition {
+                    adapter: "fake-adapter".into(),
+                    label: "test".into(),
+                    config: json!({ "request": "launch" }),
+                    tcp_connection: None,
+                },
+                worktree_a,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    let session_b = {
+        let remote_sessions = remote_sessions.borrow();
+        assert_eq!(remote_sessions.len(), 1);
+        remote_sessions[0].clone()
+    };
+    session_b.read_with(cx_b, |session, _| {
+        assert!(session.is_remote());
+        assert_eq!(session.label().as_ref(), "test");
+        assert_eq!(
+            session.session_id(),
+            session_a.read_with(cx_a, |session, _| session.session_id())
+        );
+    });
+
+    // The adapter's events reach the guest.
+    let client = session_a.read_with(cx_a, |session, _| session.adapter_client().unwrap());
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    executor.run_until_parked();
+
+    // A guest joining after the session started gets it, along with its stopped thread.
+    let project_c = client_c.join_remote_project(project_id, cx_c).await;
+    executor.run_until_parked();
+    let dap_store_c = project_c.read_with(cx_c, |project, _| project.dap_store());
+    let session_c = dap_store_c.read_with(cx_c, |dap_store, _| {
+        assert_eq!(dap_store.sessions().count(), 1);
+        dap_store.sessions().next().unwrap().clone()
+    });
+    session_c.read_with(cx_c, |session, _| {
+        assert_eq!(session.label().as_ref(), "test");
+        assert_eq!(session.thread_status(ThreadId(1)), ThreadStatus::Stopped);
+    });
+
+    // The guest's requests are answered by the host's adapter.
+    session_b.update(cx_b, |session, cx| {
+        session.threads(cx);
+        session.variables(2, cx);
+    });
+    executor.run_until_parked();
+    session_b.update(cx_b, |session, cx| {
+        assert_eq!(
+            session
+                .threads(cx)
+                .into_iter()
+                .map(|(thread, _)| thread.id)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(
+            session
+                .variables(2, cx)
+                .into_iter()
+                .map(|variable| (variable.name, variable.value))
+                .collect::<Vec<_>>(),
+            vec![("x".to_string(), "1".to_string())]
+        );
+    });
+
+    session_b.update(cx_b, |session, cx| {
+        session.step_over(ThreadId(1), SteppingGranularity::Line, cx);
+    });
+    executor.run_until_parked();
+    assert!(stepped.load(Ordering::SeqCst));
+
+    // Shutting the session down on the host removes it from the guest.
+    dap_store_a
+        .update(cx_a, |dap_store, cx| {
+            dap_store.shutdown_session(session_a.read(cx).session_id(), cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    dap_store_b.read_with(cx_b, |dap_store, _| {
+        assert_eq!(dap_store.sessions().count(), 0);
+    });
+}
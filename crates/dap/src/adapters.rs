@@ -5,13 +5,16 @@ use async_trait::async_trait;
 use collections::HashMap;
 pub use dap_types::{StartDebuggingRequestArguments, StartDebuggingRequestArgumentsRequest};
 use fs::Fs;
-use futures::io::BufReader;
+use futures::{StreamExt as _, io::BufReader};
 use gpui::{AsyncApp, SharedString};
-pub use http_client::{HttpClient, github::latest_github_release};
+pub use http_client::{
+    HttpClient,
+    github::{GithubRelease, get_release_by_tag_name, latest_github_release},
+};
 use language::{LanguageName, LanguageToolchainStore};
 use node_runtime::NodeRuntime;
 use serde::{Deserialize, Serialize};
-use settings::WorktreeId;
+use settings::{Settings as _, WorktreeId};
 use smol::fs::File;
 use std::{
     borrow::Borrow,
@@ -25,6 +28,8 @@ use std::{
 use task::{DebugScenario, TcpArgumentsTemplate, ZedDebugConfig};
 use util::archive::extract_zip;
 
+use crate::debugger_settings::DebuggerSettings;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DapStatus {
     None,
@@ -153,6 +158,7 @@ impl DebugTaskDefinition {
             build: None,
             tcp_connection: self.tcp_connection.clone(),
             config: self.config.clone(),
+            wait_for: None,
         }
     }
 
@@ -333,6 +339,69 @@ pub async fn download_adapter_from_github(
     Ok(version_path)
 }
 
+/// Fetches the release of a downloaded adapter that should be installed: `tag` when the adapter is
+/// pinned to a version, and the latest release otherwise.
+pub async fn fetch_adapter_release(
+    repo_name_with_owner: &str,
+    tag: Option<&str>,
+    require_assets: bool,
+    http: Arc<dyn HttpClient>,
+) -> Result<GithubRelease> {
+    match tag {
+        Some(tag) => get_release_by_tag_name(repo_name_with_owner, tag, http).await,
+        None => latest_github_release(repo_name_with_owner, require_assets, false, http).await,
+    }
+}
+
+/// The directory a downloaded version of an adapter is installed in.
+pub fn adapter_version_dir(adapter_name: &DebugAdapterName, tag: &str) -> PathBuf {
+    paths::debug_adapters_dir()
+        .join(adapter_name.as_ref())
+        .join(format!("{adapter_name}_{tag}"))
+}
+
+/// The versions of an adapter that are downloaded, sorted by name.
+pub async fn installed_adapter_versions(
+    adapter_name: &DebugAdapterName,
+    fs: &dyn Fs,
+) -> Vec<String> {
+    let prefix = format!("{adapter_name}_");
+    let mut versions = Vec::new();
+    let Ok(mut entries) = fs
+        .read_dir(&paths::debug_adapters_dir().join(adapter_name.as_ref()))
+        .await
+    else {
+        return versions;
+    };
+    while let Some(entry) = entries.next().await {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if let Some(version) = entry
+            .file_name()
+            .and_then(|file_name| file_name.to_str()?.strip_prefix(&prefix))
+            .filter(|version| !version.ends_with(".zip"))
+        {
+            versions.push(version.to_string());
+        }
+    }
+    versions.sort();
+    versions
+}
+
+/// The version an adapter is pinned to in the `debugger.adapter_versions` setting.
+pub fn pinned_adapter_version(
+    adapter_name: &DebugAdapterName,
+    cx: &AsyncApp,
+) -> Result<Option<String>> {
+    cx.update(|cx| {
+        DebuggerSettings::get_global(cx)
+            .adapter_versions
+            .get(adapter_name.as_ref())
+            .cloned()
+    })
+}
+
 #[async_trait(?Send)]
 pub trait DebugAdapter: 'static + Send + Sync {
     fn name(&self) -> DebugAdapterName;
@@ -373,6 +442,27 @@ pub trait DebugAdapter: 'static + Send + Sync {
     fn label_for_child_session(&self, _args: &StartDebuggingRequestArguments) -> Option<String> {
         None
     }
+
+    /// Custom requests that reload the debuggee's code without ending the session, for adapters
+    /// that support them.
+    fn hot_reload_requests(&self) -> HotReloadRequests {
+        HotReloadRequests::default()
+    }
+
+    /// Whether Zed downloads the adapter's releases, which lets it be pinned to a version with the
+    /// `debugger.adapter_versions` setting.
+    fn downloads_releases(&self) -> bool {
+        false
+    }
+}
+
+/// Custom requests an adapter reloads the debuggee's code with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HotReloadRequests {
+    /// Applies code changes to the running program, like Flutter's `hotReload`.
+    pub reload: Option<String>,
+    /// Restarts the program in place, keeping the session, like Flutter's `hotRestart`.
+    pub restart: Option<String>,
 }
 
 #[cfg(any(test, feature = "test-support"))]
@@ -417,6 +507,13 @@ impl DebugAdapter for FakeAdapter {
         None
     }
 
+    fn hot_reload_requests(&self) -> HotReloadRequests {
+        HotReloadRequests {
+            reload: Some("hotReload".into()),
+            restart: None,
+        }
+    }
+
     async fn config_from_zed_format(&self, zed_scenario: ZedDebugConfig) -> Result<DebugScenario> {
         let config = serde_json::to_value(zed_scenario.request).unwrap();
 
@@ -426,6 +523,7 @@ impl DebugAdapter for FakeAdapter {
             build: None,
             config,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -1,6 +1,6 @@
 use crate::{
     adapters::DebugAdapterBinary,
-    transport::{IoKind, LogKind, TransportDelegate},
+    transport::{IoHandler, IoKind, LogKind, TransportDelegate},
 };
 use anyhow::Result;
 use dap_types::{
@@ -39,13 +39,16 @@ pub struct DebugAdapterClient {
 pub type DapMessageHandler = Box<dyn FnMut(Message) + 'static + Send + Sync>;
 
 impl DebugAdapterClient {
+    /// Starts the adapter, passing its output (e.g. stderr) to `adapter_log_handler` from the moment
+    /// it's spawned.
     pub async fn start(
         id: SessionId,
         binary: DebugAdapterBinary,
         message_handler: DapMessageHandler,
+        adapter_log_handler: Option<IoHandler>,
         cx: &mut AsyncApp,
     ) -> Result<Self> {
-        let transport_delegate = TransportDelegate::start(&binary, cx).await?;
+        let transport_delegate = TransportDelegate::start(&binary, adapter_log_handler, cx).await?;
         let this = Self {
             id,
             binary,
@@ -75,6 +78,7 @@ impl DebugAdapterClient {
         session_id: SessionId,
         binary: DebugAdapterBinary,
         message_handler: DapMessageHandler,
+        adapter_log_handler: Option<IoHandler>,
         cx: &mut AsyncApp,
     ) -> Result<Self> {
         let binary = if let Some(connection) = self.transport_delegate.tcp_arguments() {
@@ -90,22 +94,45 @@ impl DebugAdapterClient {
             self.binary.clone()
         };
 
-        Self::start(session_id, binary, message_handler, cx).await
+        Self::start(session_id, binary, message_handler, adapter_log_handler, cx).await
     }
 
     /// Send a request to an adapter and get a response back
     /// Note: This function will block until a response is sent back from the adapter
     pub async fn request<R: Request>(&self, arguments: R::Arguments) -> Result<R::Response> {
         let serialized_arguments = serde_json::to_value(arguments)?;
+        let body = self
+            .custom_request(R::COMMAND, Some(serialized_arguments))
+            .await?;
+
+        if let Some(json) = body {
+            Ok(serde_json::from_value(json)?)
+        // Note: dap types configure themselves to return `None` when an empty object is received,
+        // which then fails here...
+        } else if let Ok(result) =
+            serde_json::from_value(serde_json::Value::Object(Default::default()))
+        {
+            Ok(result)
+        } else {
+            Ok(serde_json::from_value(Default::default())?)
+        }
+    }
 
+    /// Send a request that isn't part of the protocol, like the ones some adapters add for
+    /// features specific to them, and get the body of its response back.
+    pub async fn custom_request(
+        &self,
+        command: &str,
+        arguments: Option<serde_json::Value>,
+    ) -> Result<Option<serde_json::Value>> {
         let (callback_tx, callback_rx) = oneshot::channel::<Result<Response>>();
 
         let sequence_id = self.next_sequence_id();
 
         let request = crate::messages::Request {
             seq: sequence_id,
-            command: R::COMMAND.to_string(),
-            arguments: Some(serialized_arguments),
+            command: command.to_string(),
+            arguments,
         };
         self.transport_delegate
             .add_pending_request(sequence_id, callback_tx);
@@ -113,14 +140,12 @@ impl DebugAdapterClient {
         log::debug!(
             "Client {} send `{}` request with sequence_id: {}",
             self.id.0,
-            R::COMMAND,
+            command,
             sequence_id
         );
 
         self.send_message(Message::Request(request)).await?;
 
-        let command = R::COMMAND.to_string();
-
         let response = callback_rx.await??;
         log::debug!(
             "Client {} received response for: `{}` sequence_id: {}",
@@ -129,19 +154,7 @@ impl DebugAdapterClient {
             sequence_id
         );
         match response.success {
-            true => {
-                if let Some(json) = response.body {
-                    Ok(serde_json::from_value(json)?)
-                // Note: dap types configure themselves to return `None` when an empty object is received,
-                // which then fails here...
-                } else if let Ok(result) =
-                    serde_json::from_value(serde_json::Value::Object(Default::default()))
-                {
-                    Ok(result)
-                } else {
-                    Ok(serde_json::from_value(Default::default())?)
-                }
-            }
+            true => Ok(response.body),
             false => anyhow::bail!("Request failed: {}", response.message.unwrap_or_default()),
         }
     }
@@ -269,6 +282,7 @@ mod tests {
                 },
             },
             Box::new(|_| panic!("Did not expect to hit this code path")),
+            None,
             &mut cx.to_async(),
         )
         .await
@@ -351,6 +365,7 @@ mod tests {
                     );
                 }
             }),
+            None,
             &mut cx.to_async(),
         )
         .await
@@ -409,6 +424,7 @@ mod tests {
                     );
                 }
             }),
+            None,
             &mut cx.to_async(),
         )
         .await
@@ -0,0 +1,234 @@
+use std::{ffi::OsStr, path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use collections::HashMap;
+use gpui::{App, AsyncApp};
+use serde_json::json;
+use settings::{Settings as _, SettingsStore};
+use task::{DebugRequest, DebugScenario, ZedDebugConfig};
+
+use crate::{
+    DapRegistry, StartDebuggingRequestArguments,
+    adapters::{
+        DapDelegate, DebugAdapter, DebugAdapterBinary, DebugAdapterName, DebugTaskDefinition,
+        TcpArguments,
+    },
+    configure_tcp_connection,
+    debugger_settings::{CustomAdapterSettings, DebuggerSettings},
+};
+
+/// A debug adapter declared in the `debugger.custom_adapters` setting.
+pub struct CustomDebugAdapter {
+    name: DebugAdapterName,
+    settings: CustomAdapterSettings,
+}
+
+impl CustomDebugAdapter {
+    pub fn new(name: DebugAdapterName, settings: CustomAdapterSettings) -> Self {
+        Self { name, settings }
+    }
+}
+
+#[async_trait(?Send)]
+impl DebugAdapter for CustomDebugAdapter {
+    fn name(&self) -> DebugAdapterName {
+        self.name.clone()
+    }
+
+    async fn config_from_zed_format(&self, zed_scenario: ZedDebugConfig) -> Result<DebugScenario> {
+        let mut config = serde_json::Map::default();
+        match &zed_scenario.request {
+            DebugRequest::Attach(attach) => {
+                config.insert("request".into(), "attach".into());
+                if let Some(process_id) = attach.process_id {
+                    config.insert("processId".into(), process_id.into());
+                }
+            }
+            DebugRequest::Launch(launch) => {
+                config.insert("request".into(), "launch".into());
+                config.insert("program".into(), launch.program.clone().into());
+                if !launch.args.is_empty() {
+                    config.insert("args".into(), launch.args.clone().into());
+                }
+                if !launch.env.is_empty() {
+                    config.insert("env".into(), launch.env_json());
+                }
+                if let Some(cwd) = &launch.cwd {
+                    config.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
+                }
+                if let Some(stop_on_entry) = zed_scenario.stop_on_entry {
+                    config.insert("stopOnEntry".into(), stop_on_entry.into());
+                }
+            }
+        }
+
+        Ok(DebugScenario {
+            adapter: zed_scenario.adapter,
+            label: zed_scenario.label,
+            build: None,
+            config: serde_json::Value::Object(config),
+            tcp_connection: None,
+            wait_for: None,
+        })
+    }
+
+    fn dap_schema(&self) -> serde_json::Value {
+        self.settings.schema.clone().unwrap_or_else(|| {
+            json!({
+                "type": "object",
+                "required": ["request"],
+                "properties": {
+                    "request": {
+                        "type": "string",
+                        "enum": ["launch", "attach"],
+                        "description": "Whether to launch a new program or attach to a running one"
+                    }
+                }
+            })
+        })
+    }
+
+    async fn get_binary(
+        &self,
+        delegate: &Arc<dyn DapDelegate>,
+        config: &DebugTaskDefinition,
+        user_installed_path: Option<PathBuf>,
+        _: &mut AsyncApp,
+    ) -> Result<DebugAdapterBinary> {
+        let command = match user_installed_path {
+            Some(path) => path.to_string_lossy().into_owned(),
+            None => delegate
+                .which(OsStr::new(&self.settings.command))
+                .await
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.settings.command.clone()),
+        };
+
+        let mut arguments = self.settings.args.clone();
+        let tcp_connection = config
+            .tcp_connection
+            .clone()
+            .or_else(|| self.settings.tcp_connection.clone());
+        let connection = if let Some(tcp_connection) = tcp_connection {
+            let (host, port, timeout) = configure_tcp_connection(tcp_connection).await?;
+            for argument in &mut arguments {
+                *argument = argument.replace("${port}", &port.to_string());
+            }
+            Some(TcpArguments {
+                host,
+                port,
+                timeout,
+            })
+        } else {
+            None
+        };
+
+        let mut configuration = config.config.clone();
+        if let Some(configuration) = configuration.as_object_mut() {
+            configuration
+                .entry("cwd")
+                .or_insert_with(|| delegate.worktree_root_path().to_string_lossy().into());
+        }
+
+        Ok(DebugAdapterBinary {
+            command: Some(command),
+            arguments,
+            envs: self.settings.env.clone(),
+            cwd: Some(delegate.worktree_root_path().to_path_buf()),
+            connection,
+            request_args: StartDebuggingRequestArguments {
+                request: self.request_kind(&config.config).await?,
+                configuration,
+            },
+        })
+    }
+}
+
+/// Keeps the adapters declared in the `debugger.custom_adapters` setting registered in the
+/// [`DapRegistry`] as the setting changes.
+pub fn init(cx: &mut App) {
+    let mut registered = HashMap::default();
+    register_custom_adapters(&mut registered, cx);
+    cx.observe_global::<SettingsStore>(move |cx| register_custom_adapters(&mut registered, cx))
+        .detach();
+}
+
+fn register_custom_adapters(registered: &mut HashMap<String, CustomAdapterSettings>, cx: &mut App) {
+    let adapters = DebuggerSettings::get_global(cx).custom_adapters.clone();
+    if adapters == *registered {
+        return;
+    }
+
+    let registry = DapRegistry::global(cx);
+    for name in registered.keys() {
+        if !adapters.contains_key(name) {
+            registry.remove_adapter(name);
+        }
+    }
+    let mut added = HashMap::default();
+    for (name, settings) in adapters {
+        if !registered.contains_key(&name) && registry.adapter(&name).is_some() {
+            log::warn!("custom debug adapter {name} has the same name as an existing adapter");
+            continue;
+        }
+        if registered.get(&name) != Some(&settings) {
+            registry.add_adapter(Arc::new(CustomDebugAdapter::new(
+                DebugAdapterName(name.clone().into()),
+                settings.clone(),
+            )));
+        }
+        added.insert(name, settings);
+    }
+    *registered = added;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn test_custom_adapters_follow_settings(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            DebuggerSettings::register(cx);
+            init(cx);
+        });
+
+        let set_custom_adapters = |adapters: HashMap<String, CustomAdapterSettings>,
+                                   cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                cx.update_global::<SettingsStore, _>(|store, cx| {
+                    store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                        settings.custom_adapters = adapters;
+                    });
+                });
+            });
+        };
+        let adapter =
+            |cx: &mut TestAppContext| cx.update(|cx| DapRegistry::global(cx).adapter("my-adapter"));
+
+        set_custom_adapters(
+            HashMap::from_iter([(
+                "my-adapter".to_string(),
+                CustomAdapterSettings {
+                    command: "my-dap".into(),
+                    args: vec!["--port=${port}".into()],
+                    env: HashMap::default(),
+                    tcp_connection: None,
+                    schema: None,
+                },
+            )]),
+            cx,
+        );
+        assert_eq!(
+            adapter(cx).map(|adapter| adapter.name()),
+            Some(DebugAdapterName("my-adapter".into()))
+        );
+
+        set_custom_adapters(HashMap::default(), cx);
+        assert!(adapter(cx).is_none());
+    }
+}
@@ -1,10 +1,12 @@
 pub mod adapters;
 pub mod client;
+pub mod custom_adapter;
 pub mod debugger_settings;
 pub mod inline_value;
 pub mod proto_conversions;
 mod registry;
 pub mod transport;
+pub mod visualizer;
 
 use std::net::Ipv4Addr;
 
@@ -45,6 +47,7 @@ pub enum TelemetrySpawnLocation {
     Gutter,
     ScenarioList,
     Custom,
+    TestUnderCursor,
 }
 
 pub fn send_telemetry(scenario: &DebugScenario, location: TelemetrySpawnLocation, cx: &App) {
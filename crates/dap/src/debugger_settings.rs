@@ -1,8 +1,11 @@
+use collections::HashMap;
 use dap_types::SteppingGranularity;
 use gpui::{App, Global};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
+use std::path::{Path, PathBuf};
+use task::TcpArgumentsTemplate;
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -12,7 +15,155 @@ pub enum DebugPanelDockPosition {
     Right,
 }
 
-#[derive(Serialize, Deserialize, JsonSchema, Clone, Copy)]
+/// What clicking on a breakpoint in the editor gutter does.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GutterClickAction {
+    /// Set or remove a breakpoint.
+    Toggle,
+    /// Enable or disable the breakpoint, creating a disabled breakpoint on lines without one.
+    ToggleEnabled,
+    /// Open the log message prompt, turning the breakpoint into a logpoint.
+    LogBreakpoint,
+    /// Open the condition prompt.
+    ConditionBreakpoint,
+    /// Open the hit condition prompt.
+    HitConditionBreakpoint,
+}
+
+/// What the lines of the debug console are prefixed with.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsoleTimestamps {
+    /// No timestamps.
+    #[default]
+    Off,
+    /// The local time the output was received at.
+    WallClock,
+    /// The time elapsed since the debug session started.
+    SessionRelative,
+}
+
+/// Where the values of variables are shown in the editor while a session is stopped.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineValues {
+    /// Values aren't shown in the editor.
+    Off,
+    /// After each variable they belong to.
+    NextToVariables,
+    /// At the end of each line, listing the values of the variables on it.
+    #[default]
+    EndOfLine,
+}
+
+/// Gutter click actions bound to modifier keys.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct GutterClickGestures {
+    /// Action performed when clicking with shift held down.
+    ///
+    /// Default: log_breakpoint
+    pub shift: GutterClickAction,
+    /// Action performed when clicking with alt held down.
+    ///
+    /// Default: toggle_enabled
+    pub alt: GutterClickAction,
+    /// Action performed when clicking with cmd (macOS) or ctrl (Linux/Windows) held down.
+    ///
+    /// Default: toggle_enabled
+    pub secondary: GutterClickAction,
+}
+
+impl Default for GutterClickGestures {
+    fn default() -> Self {
+        Self {
+            shift: GutterClickAction::LogBreakpoint,
+            alt: GutterClickAction::ToggleEnabled,
+            secondary: GutterClickAction::ToggleEnabled,
+        }
+    }
+}
+
+/// The corner of the editor the floating debug controls are docked to.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FloatingControlsPosition {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+/// A toolbar with the continue, step and stop buttons, drawn over the active editor while a
+/// session is running.
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(default)]
+pub struct FloatingControls {
+    /// Whether to show the floating controls.
+    ///
+    /// Default: false
+    pub enabled: bool,
+    /// Which corner of the editor the controls are docked to.
+    ///
+    /// Default: top_right
+    pub position: FloatingControlsPosition,
+}
+
+/// Maps the source paths a debug adapter reports onto local paths, e.g. for programs built in a
+/// container, on CI or in a different checkout.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct SourcePathMapping {
+    /// The path prefix used by the debug adapter.
+    pub remote: String,
+    /// The local path prefix it corresponds to.
+    pub local: String,
+}
+
+/// A debug adapter declared in settings, which Zed starts with a command rather than getting from
+/// an extension.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct CustomAdapterSettings {
+    /// The command that starts the debug adapter.
+    pub command: String,
+    /// Arguments passed to the command. When the adapter is connected to over TCP, `${port}` is
+    /// replaced with the port.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Environment variables set for the command.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Connect to the adapter over TCP instead of talking to it over stdin and stdout. A free port
+    /// is picked when no port is given.
+    #[serde(default)]
+    pub tcp_connection: Option<TcpArgumentsTemplate>,
+    /// A JSON schema for the adapter's launch and attach configurations, used to validate and
+    /// complete them in `debug.json`.
+    #[serde(default)]
+    pub schema: Option<serde_json::Value>,
+}
+
+/// Settings for one of the debug adapters Zed knows about.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct AdapterSettings {
+    /// Values merged into the configuration of every debug scenario that uses this adapter, e.g.
+    /// `{"justMyCode": false}`. Values set by a scenario take precedence, and objects like `env`
+    /// are merged key by key.
+    #[serde(default)]
+    pub defaults: serde_json::Map<String, serde_json::Value>,
+}
+
+fn replace_path_prefix(path: &str, prefix: &str, replacement: &str) -> Option<PathBuf> {
+    let suffix = Path::new(path).strip_prefix(prefix).ok()?;
+    Some(if suffix.as_os_str().is_empty() {
+        PathBuf::from(replacement)
+    } else {
+        Path::new(replacement).join(suffix)
+    })
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Clone)]
 #[serde(default)]
 pub struct DebuggerSettings {
     /// Determines the stepping granularity.
@@ -23,10 +174,90 @@ pub struct DebuggerSettings {
     ///
     /// Default: true
     pub save_breakpoints: bool,
+    /// Whether breakpoints should be scoped to the current git branch.
+    /// Switching branches swaps in the breakpoints that were set the last time
+    /// that branch was checked out.
+    ///
+    /// Default: false
+    pub breakpoints_per_branch: bool,
+    /// What modifier-clicks on a breakpoint in the gutter do.
+    pub gutter_click_gestures: GutterClickGestures,
     /// Whether to show the debug button in the status bar.
     ///
     /// Default: true
     pub button: bool,
+    /// A toolbar with the debugger's controls, drawn over the active editor while a session is
+    /// running.
+    pub floating_controls: FloatingControls,
+    /// How many levels of nested variables Copy Scope as JSON follows. Variables nested deeper
+    /// are copied as their displayed value.
+    ///
+    /// Default: 3
+    pub copy_as_json_depth: usize,
+    /// Where the values of the active stack frame's variables are shown in the editor while a
+    /// session is stopped.
+    ///
+    /// Default: end_of_line
+    pub inline_values: InlineValues,
+    /// What the lines of the debug console are prefixed with.
+    ///
+    /// Default: off
+    pub console_timestamps: ConsoleTimestamps,
+    /// How many lines of output the debug console keeps. Older lines are dropped as new output
+    /// arrives.
+    ///
+    /// Default: 10000
+    pub console_max_lines: usize,
+    /// Whether to clear the debug console when a session restarts.
+    ///
+    /// Default: false
+    pub clear_console_on_restart: bool,
+    /// How many minutes after a session ends, because its debuggee exited or it was detached, it
+    /// is closed and removed from the debug panel. Ended sessions are kept until closed when unset.
+    ///
+    /// Default: null
+    pub close_ended_sessions_after_minutes: Option<u64>,
+    /// Whether to collapse consecutive stack frames the debug adapter marks as subtle into a single
+    /// expandable entry in the Frames pane. Otherwise they're shown dimmed.
+    ///
+    /// Default: true
+    pub collapse_subtle_frames: bool,
+    /// Whether to collapse stack frames from dependencies and the standard library in the
+    /// Frames pane.
+    ///
+    /// Default: true
+    pub hide_library_frames: bool,
+    /// Globs matching the source paths of stack frames that are collapsed when
+    /// `hide_library_frames` is enabled, in addition to the frames the debug adapter marks as
+    /// library code.
+    ///
+    /// Default: ["**/node_modules/**", "**/site-packages/**", "**/.cargo/registry/**", "**/rustlib/src/**"]
+    pub library_frame_paths: Vec<String>,
+    /// Prefix remappings applied to the source paths of stack frames and breakpoints, for
+    /// programs whose sources were built somewhere else.
+    ///
+    /// Default: []
+    pub source_path_mappings: Vec<SourcePathMapping>,
+    /// Versions that downloaded debug adapters are pinned to, by adapter name, e.g.
+    /// `{"CodeLLDB": "v1.11.4"}`. Adapters that aren't pinned are updated to their latest release.
+    ///
+    /// Default: {}
+    pub adapter_versions: HashMap<String, String>,
+    /// Debug adapters declared in settings, by name. They can be used in debug scenarios like the
+    /// adapters Zed provides.
+    ///
+    /// Default: {}
+    pub custom_adapters: HashMap<String, CustomAdapterSettings>,
+    /// Settings for debug adapters, by adapter name.
+    ///
+    /// Default: {}
+    pub adapters: HashMap<String, AdapterSettings>,
+    /// Whether to launch programs that debug adapters ask to run in a terminal in the operating
+    /// system's terminal app, rather than in the debug panel's terminal. Adapters can also request
+    /// this per launch.
+    ///
+    /// Default: false
+    pub run_in_external_terminal: bool,
     /// Time in milliseconds until timeout error when connecting to a TCP debug adapter
     ///
     /// Default: 2000ms
@@ -49,7 +280,29 @@ impl Default for DebuggerSettings {
     fn default() -> Self {
         Self {
             button: true,
+            floating_controls: FloatingControls::default(),
+            copy_as_json_depth: 3,
+            inline_values: InlineValues::EndOfLine,
+            console_timestamps: ConsoleTimestamps::Off,
+            console_max_lines: 10_000,
+            clear_console_on_restart: false,
+            close_ended_sessions_after_minutes: None,
+            collapse_subtle_frames: true,
+            hide_library_frames: true,
+            library_frame_paths: vec![
+                "**/node_modules/**".to_string(),
+                "**/site-packages/**".to_string(),
+                "**/.cargo/registry/**".to_string(),
+                "**/rustlib/src/**".to_string(),
+            ],
+            source_path_mappings: Vec::new(),
+            run_in_external_terminal: false,
+            adapter_versions: HashMap::default(),
+            custom_adapters: HashMap::default(),
+            adapters: HashMap::default(),
             save_breakpoints: true,
+            breakpoints_per_branch: false,
+            gutter_click_gestures: GutterClickGestures::default(),
             stepping_granularity: SteppingGranularity::Line,
             timeout: 2000,
             log_dap_communications: true,
@@ -59,6 +312,51 @@ impl Default for DebuggerSettings {
     }
 }
 
+impl DebuggerSettings {
+    /// Resolves a source path reported by the debug adapter to a local path, using the first
+    /// mapping whose remote prefix matches it.
+    pub fn local_source_path(&self, path: &str) -> PathBuf {
+        self.source_path_mappings
+            .iter()
+            .find_map(|mapping| replace_path_prefix(path, &mapping.remote, &mapping.local))
+            .unwrap_or_else(|| PathBuf::from(path))
+    }
+
+    /// The inverse of [`Self::local_source_path`], used for paths sent to the debug adapter.
+    pub fn remote_source_path(&self, path: &Path) -> PathBuf {
+        let path = path.to_string_lossy();
+        self.source_path_mappings
+            .iter()
+            .find_map(|mapping| replace_path_prefix(&path, &mapping.local, &mapping.remote))
+            .unwrap_or_else(|| PathBuf::from(path.as_ref()))
+    }
+}
+
+/// Fills in the values from an adapter's [`AdapterSettings::defaults`] that `config` doesn't set.
+/// Objects, like `env`, are merged key by key.
+pub fn merge_adapter_defaults(config: &mut serde_json::Value, defaults: &serde_json::Value) {
+    let serde_json::Value::Object(defaults) = defaults else {
+        return;
+    };
+    if config.is_null() {
+        *config = serde_json::Value::Object(Default::default());
+    }
+    let serde_json::Value::Object(config) = config else {
+        return;
+    };
+    for (key, default) in defaults {
+        match config.get_mut(key) {
+            None => {
+                config.insert(key.clone(), default.clone());
+            }
+            Some(value) if value.is_object() && default.is_object() => {
+                merge_adapter_defaults(value, default);
+            }
+            Some(_) => {}
+        }
+    }
+}
+
 impl Settings for DebuggerSettings {
     const KEY: Option<&'static str> = Some("debugger");
 
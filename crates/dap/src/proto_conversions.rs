@@ -355,6 +355,25 @@ impl ProtoConversion for dap_types::SteppingGranularity {
     }
 }
 
+impl ProtoConversion for dap_types::VariablesArgumentsFilter {
+    type ProtoType = proto::VariablesArgumentsFilter;
+    type Output = Self;
+
+    fn to_proto(&self) -> Self::ProtoType {
+        match self {
+            Self::Indexed => proto::VariablesArgumentsFilter::Indexed,
+            Self::Named => proto::VariablesArgumentsFilter::Named,
+        }
+    }
+
+    fn from_proto(payload: Self::ProtoType) -> Self {
+        match payload {
+            proto::VariablesArgumentsFilter::Indexed => Self::Indexed,
+            proto::VariablesArgumentsFilter::Named => Self::Named,
+        }
+    }
+}
+
 impl ProtoConversion for dap_types::OutputEventCategory {
     type ProtoType = proto::DapOutputCategory;
     type Output = Self;
@@ -365,6 +384,7 @@ impl ProtoConversion for dap_types::OutputEventCategory {
             Self::Important => proto::DapOutputCategory::Important,
             Self::Stdout => proto::DapOutputCategory::Stdout,
             Self::Stderr => proto::DapOutputCategory::Stderr,
+            Self::Telemetry => proto::DapOutputCategory::Telemetry,
             _ => proto::DapOutputCategory::Unknown,
         }
     }
@@ -375,6 +395,7 @@ impl ProtoConversion for dap_types::OutputEventCategory {
             proto::DapOutputCategory::Important => Self::Important,
             proto::DapOutputCategory::Stdout => Self::Stdout,
             proto::DapOutputCategory::Stderr => Self::Stderr,
+            proto::DapOutputCategory::Telemetry => Self::Telemetry,
             proto::DapOutputCategory::Unknown => Self::Unknown,
         }
     }
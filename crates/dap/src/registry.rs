@@ -11,6 +11,7 @@ use task::{
 use crate::{
     adapters::{DebugAdapter, DebugAdapterName},
     inline_value::InlineValueProvider,
+    visualizer::DebugVisualizer,
 };
 use std::{collections::BTreeMap, sync::Arc};
 
@@ -34,6 +35,7 @@ struct DapRegistryState {
     adapters: BTreeMap<DebugAdapterName, Arc<dyn DebugAdapter>>,
     locators: FxHashMap<SharedString, Arc<dyn DapLocator>>,
     inline_value_providers: FxHashMap<String, Arc<dyn InlineValueProvider>>,
+    visualizers: FxHashMap<SharedString, Arc<dyn DebugVisualizer>>,
 }
 
 #[derive(Clone, Default)]
@@ -62,6 +64,21 @@ impl DapRegistry {
         self.0.write().locators.remove(locator);
     }
 
+    pub fn add_visualizer(&self, visualizer: Arc<dyn DebugVisualizer>) {
+        self.0
+            .write()
+            .visualizers
+            .insert(visualizer.name(), visualizer);
+    }
+
+    pub fn remove_visualizer(&self, name: &str) {
+        self.0.write().visualizers.remove(name);
+    }
+
+    pub fn visualizers(&self) -> Vec<Arc<dyn DebugVisualizer>> {
+        self.0.read().visualizers.values().cloned().collect()
+    }
+
     pub fn adapter_language(&self, adapter_name: &str) -> Option<LanguageName> {
         self.adapter(adapter_name)
             .and_then(|adapter| adapter.adapter_language_name())
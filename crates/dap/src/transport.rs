@@ -100,8 +100,17 @@ pub(crate) struct TransportDelegate {
 }
 
 impl TransportDelegate {
-    pub(crate) async fn start(binary: &DebugAdapterBinary, cx: &mut AsyncApp) -> Result<Self> {
+    pub(crate) async fn start(
+        binary: &DebugAdapterBinary,
+        adapter_log_handler: Option<IoHandler>,
+        cx: &mut AsyncApp,
+    ) -> Result<Self> {
         let log_handlers: LogHandlers = Default::default();
+        // Registered before the adapter is spawned, so that output it writes while starting up
+        // (or failing to) isn't missed.
+        if let Some(handler) = adapter_log_handler {
+            log_handlers.lock().push((LogKind::Adapter, handler));
+        }
         let transport = start(binary, log_handlers.clone(), cx).await?;
         Ok(Self {
             transport: Mutex::new(transport),
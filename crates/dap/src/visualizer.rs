@@ -0,0 +1,130 @@
+use gpui::SharedString;
+
+use crate::adapters::DebugAdapterName;
+
+/// Renders variables of matching types in a friendlier form than the adapter's raw value, e.g. a
+/// `Duration` in human units.
+pub trait DebugVisualizer: Send + Sync {
+    fn name(&self) -> SharedString;
+
+    /// Whether this visualizer applies to `variable` as reported by `adapter`.
+    fn matches(&self, adapter: &DebugAdapterName, variable: &dap_types::Variable) -> bool;
+
+    /// Returns the text to show in place of the variable's value, or `None` to fall back to the
+    /// value reported by the adapter. `children` holds the variable's members, if it has any.
+    fn visualize(
+        &self,
+        variable: &dap_types::Variable,
+        children: &[dap_types::Variable],
+    ) -> Option<String>;
+}
+
+/// A visualizer declared without code, as extensions do in their manifest.
+///
+/// Types match either exactly or, when the pattern ends with `*`, by prefix. The template may
+/// reference `{name}`, `{value}` and `{type}` of the variable, as well as the value of any of its
+/// members by name, e.g. `{secs}s {nanos}ns`.
+pub struct TemplateVisualizer {
+    pub name: SharedString,
+    pub adapters: Vec<SharedString>,
+    pub types: Vec<String>,
+    pub template: String,
+}
+
+impl DebugVisualizer for TemplateVisualizer {
+    fn name(&self) -> SharedString {
+        self.name.clone()
+    }
+
+    fn matches(&self, adapter: &DebugAdapterName, variable: &dap_types::Variable) -> bool {
+        if !self.adapters.is_empty() && !self.adapters.iter().any(|name| name == &adapter.0) {
+            return false;
+        }
+        let Some(type_) = variable.type_.as_deref() else {
+            return false;
+        };
+        self.types.iter().any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => type_.starts_with(prefix),
+            None => type_ == pattern,
+        })
+    }
+
+    fn visualize(
+        &self,
+        variable: &dap_types::Variable,
+        children: &[dap_types::Variable],
+    ) -> Option<String> {
+        let mut output = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            output.push_str(&rest[..start]);
+            let end = rest[start..].find('}')? + start;
+            let placeholder = &rest[start + 1..end];
+            let replacement = match placeholder {
+                "name" => variable.name.as_str(),
+                "value" => variable.value.as_str(),
+                "type" => variable.type_.as_deref()?,
+                member => children
+                    .iter()
+                    .find(|child| child.name == member)?
+                    .value
+                    .as_str(),
+            };
+            output.push_str(replacement);
+            rest = &rest[end + 1..];
+        }
+        output.push_str(rest);
+        Some(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variable(name: &str, value: &str, type_: Option<&str>) -> dap_types::Variable {
+        dap_types::Variable {
+            name: name.into(),
+            value: value.into(),
+            type_: type_.map(Into::into),
+            presentation_hint: None,
+            evaluate_name: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            declaration_location_reference: None,
+            value_location_reference: None,
+        }
+    }
+
+    #[test]
+    fn test_template_visualizer() {
+        let visualizer = TemplateVisualizer {
+            name: "duration".into(),
+            adapters: vec!["CodeLLDB".into()],
+            types: vec!["core::time::Duration".into(), "std::time::*".into()],
+            template: "{secs}s {nanos}ns".into(),
+        };
+        let duration = variable("timeout", "{...}", Some("core::time::Duration"));
+        let children = [
+            variable("secs", "5", Some("u64")),
+            variable("nanos", "250", Some("u32")),
+        ];
+
+        let codelldb = DebugAdapterName("CodeLLDB".into());
+        assert!(visualizer.matches(&codelldb, &duration));
+        assert!(visualizer.matches(
+            &codelldb,
+            &variable("now", "{...}", Some("std::time::Instant"))
+        ));
+        assert!(!visualizer.matches(&codelldb, &variable("secs", "5", Some("u64"))));
+        assert!(!visualizer.matches(&DebugAdapterName("GDB".into()), &duration));
+
+        assert_eq!(
+            Some("5s 250ns".to_string()),
+            visualizer.visualize(&duration, &children)
+        );
+        assert_eq!(None, visualizer.visualize(&duration, &children[..1]));
+    }
+}
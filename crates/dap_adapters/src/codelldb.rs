@@ -1,8 +1,12 @@
-use std::{collections::HashMap, path::PathBuf, sync::OnceLock};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 use anyhow::{Context as _, Result};
 use async_trait::async_trait;
-use dap::adapters::{DebugTaskDefinition, latest_github_release};
+use dap::adapters::DebugTaskDefinition;
 use futures::StreamExt;
 use gpui::AsyncApp;
 use serde_json::Value;
@@ -45,12 +49,14 @@ impl CodeLldbDebugAdapter {
         })
     }
 
-    async fn fetch_latest_adapter_version(
+    async fn fetch_adapter_version(
         &self,
+        tag: Option<&str>,
         delegate: &Arc<dyn DapDelegate>,
     ) -> Result<AdapterVersion> {
         let release =
-            latest_github_release("vadimcn/codelldb", true, false, delegate.http_client()).await?;
+            adapters::fetch_adapter_release("vadimcn/codelldb", tag, true, delegate.http_client())
+                .await?;
 
         let arch = match std::env::consts::ARCH {
             "aarch64" => "arm64",
@@ -130,6 +136,7 @@ impl DebugAdapter for CodeLldbDebugAdapter {
             config: configuration,
             build: None,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -324,22 +331,48 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 
+    fn downloads_releases(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
         config: &DebugTaskDefinition,
         user_installed_path: Option<PathBuf>,
-        _: &mut AsyncApp,
+        cx: &mut AsyncApp,
     ) -> Result<DebugAdapterBinary> {
+        let pinned_version = adapters::pinned_adapter_version(&self.name(), cx)?;
+        // The cached path is of the latest version, which a pinned version may have replaced.
         let mut command = user_installed_path
             .map(|p| p.to_string_lossy().to_string())
-            .or(self.path_to_codelldb.get().cloned());
+            .or_else(|| {
+                self.path_to_codelldb
+                    .get()
+                    .filter(|path| pinned_version.is_none() && Path::new(path).exists())
+                    .cloned()
+            });
 
         if command.is_none() {
-            delegate.output_to_console(format!("Checking latest version of {}...", self.name()));
             let adapter_path = paths::debug_adapters_dir().join(&Self::ADAPTER_NAME);
-            let version_path =
-                if let Ok(version) = self.fetch_latest_adapter_version(delegate).await {
+            let version_path = if let Some(tag) = &pinned_version {
+                let version_path = adapters::adapter_version_dir(&self.name(), tag);
+                if !version_path.exists() {
+                    delegate.output_to_console(format!("Downloading {} {tag}...", self.name()));
+                    let version = self.fetch_adapter_version(Some(tag), delegate).await?;
+                    adapters::download_adapter_from_github(
+                        self.name(),
+                        version,
+                        adapters::DownloadedFileType::Vsix,
+                        delegate.as_ref(),
+                    )
+                    .await?;
+                }
+                version_path
+            } else {
+                delegate
+                    .output_to_console(format!("Checking latest version of {}...", self.name()));
+                if let Ok(version) = self.fetch_adapter_version(None, delegate).await {
                     adapters::download_adapter_from_github(
                         self.name(),
                         version.clone(),
@@ -354,10 +387,13 @@ impl DebugAdapter for CodeLldbDebugAdapter {
                 } else {
                     let mut paths = delegate.fs().read_dir(&adapter_path).await?;
                     paths.next().await.context("No adapter found")??
-                };
+                }
+            };
             let adapter_dir = version_path.join("extension").join("adapter");
             let path = adapter_dir.join("codelldb").to_string_lossy().to_string();
-            self.path_to_codelldb.set(path.clone()).ok();
+            if pinned_version.is_none() {
+                self.path_to_codelldb.set(path.clone()).ok();
+            }
             command = Some(path);
         };
 
@@ -391,6 +391,7 @@ impl DebugAdapter for GoDebugAdapter {
             build: None,
             config: args,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -1,4 +1,3 @@
-use adapters::latest_github_release;
 use anyhow::Context as _;
 use dap::{StartDebuggingRequestArguments, adapters::DebugTaskDefinition};
 use gpui::AsyncApp;
@@ -19,14 +18,15 @@ impl JsDebugAdapter {
     const ADAPTER_NPM_NAME: &'static str = "vscode-js-debug";
     const ADAPTER_PATH: &'static str = "js-debug/src/dapDebugServer.js";
 
-    async fn fetch_latest_adapter_version(
+    async fn fetch_adapter_version(
         &self,
+        tag: Option<&str>,
         delegate: &Arc<dyn DapDelegate>,
     ) -> Result<AdapterVersion> {
-        let release = latest_github_release(
+        let release = adapters::fetch_adapter_release(
             &format!("microsoft/{}", Self::ADAPTER_NPM_NAME),
+            tag,
             true,
-            false,
             delegate.http_client(),
         )
         .await?;
@@ -98,7 +98,7 @@ impl JsDebugAdapter {
             configuration.entry("type").and_modify(normalize_task_type);
             configuration
                 .entry("console")
-                .or_insert("externalTerminal".into());
+                .or_insert("integratedTerminal".into());
 
             configuration.entry("sourceMaps").or_insert(true.into());
             configuration
@@ -190,6 +190,7 @@ impl DebugAdapter for JsDebugAdapter {
             build: None,
             config: args,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -304,7 +305,7 @@ impl DebugAdapter for JsDebugAdapter {
                                 },
                                 "console": {
                                     "type": "string",
-                                    "enum": ["internalConsole", "integratedTerminal"],
+                                    "enum": ["internalConsole", "integratedTerminal", "externalTerminal"],
                                     "description": "Where to launch the debug target",
                                     "default": "internalConsole"
                                 },
@@ -459,6 +460,10 @@ impl DebugAdapter for JsDebugAdapter {
         })
     }
 
+    fn downloads_releases(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -466,9 +471,21 @@ impl DebugAdapter for JsDebugAdapter {
         user_installed_path: Option<PathBuf>,
         cx: &mut AsyncApp,
     ) -> Result<DebugAdapterBinary> {
-        if self.checked.set(()).is_ok() {
+        if let Some(tag) = adapters::pinned_adapter_version(&self.name(), cx)? {
+            if !adapters::adapter_version_dir(&self.name(), &tag).exists() {
+                delegate.output_to_console(format!("Downloading {} {tag}...", self.name()));
+                let version = self.fetch_adapter_version(Some(&tag), delegate).await?;
+                adapters::download_adapter_from_github(
+                    self.name(),
+                    version,
+                    adapters::DownloadedFileType::GzipTar,
+                    delegate.as_ref(),
+                )
+                .await?;
+            }
+        } else if self.checked.set(()).is_ok() {
             delegate.output_to_console(format!("Checking latest version of {}...", self.name()));
-            if let Some(version) = self.fetch_latest_adapter_version(delegate).await.log_err() {
+            if let Some(version) = self.fetch_adapter_version(None, delegate).await.log_err() {
                 adapters::download_adapter_from_github(
                     self.name(),
                     version,
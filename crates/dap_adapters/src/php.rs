@@ -1,4 +1,3 @@
-use adapters::latest_github_release;
 use anyhow::Context as _;
 use anyhow::bail;
 use dap::StartDebuggingRequestArguments;
@@ -21,14 +20,15 @@ impl PhpDebugAdapter {
     const ADAPTER_PACKAGE_NAME: &'static str = "vscode-php-debug";
     const ADAPTER_PATH: &'static str = "extension/out/phpDebug.js";
 
-    async fn fetch_latest_adapter_version(
+    async fn fetch_adapter_version(
         &self,
+        tag: Option<&str>,
         delegate: &Arc<dyn DapDelegate>,
     ) -> Result<AdapterVersion> {
-        let release = latest_github_release(
+        let release = adapters::fetch_adapter_release(
             &format!("{}/{}", "xdebug", Self::ADAPTER_PACKAGE_NAME),
+            tag,
             true,
-            false,
             delegate.http_client(),
         )
         .await?;
@@ -318,9 +318,14 @@ impl DebugAdapter for PhpDebugAdapter {
             build: None,
             config: obj,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
+    fn downloads_releases(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -328,9 +333,21 @@ impl DebugAdapter for PhpDebugAdapter {
         user_installed_path: Option<PathBuf>,
         cx: &mut AsyncApp,
     ) -> Result<DebugAdapterBinary> {
-        if self.checked.set(()).is_ok() {
+        if let Some(tag) = adapters::pinned_adapter_version(&self.name(), cx)? {
+            if !adapters::adapter_version_dir(&self.name(), &tag).exists() {
+                delegate.output_to_console(format!("Downloading {} {tag}...", self.name()));
+                let version = self.fetch_adapter_version(Some(&tag), delegate).await?;
+                adapters::download_adapter_from_github(
+                    self.name(),
+                    version,
+                    adapters::DownloadedFileType::Vsix,
+                    delegate.as_ref(),
+                )
+                .await?;
+            }
+        } else if self.checked.set(()).is_ok() {
             delegate.output_to_console(format!("Checking latest version of {}...", self.name()));
-            if let Some(version) = self.fetch_latest_adapter_version(delegate).await.log_err() {
+            if let Some(version) = self.fetch_adapter_version(None, delegate).await.log_err() {
                 adapters::download_adapter_from_github(
                     self.name(),
                     version,
@@ -1,6 +1,5 @@
 use crate::*;
 use anyhow::Context as _;
-use dap::adapters::latest_github_release;
 use dap::{DebugRequest, StartDebuggingRequestArguments, adapters::DebugTaskDefinition};
 use gpui::{AppContext, AsyncApp, SharedString};
 use json_dotpath::DotPaths;
@@ -106,8 +105,9 @@ impl PythonDebugAdapter {
             request,
         })
     }
-    async fn fetch_latest_adapter_version(
+    async fn fetch_adapter_version(
         &self,
+        tag: Option<&str>,
         delegate: &Arc<dyn DapDelegate>,
     ) -> Result<AdapterVersion> {
         let github_repo = GithubRepo {
@@ -115,7 +115,7 @@ impl PythonDebugAdapter {
             repo_owner: "microsoft".into(),
         };
 
-        fetch_latest_adapter_version_from_github(github_repo, delegate.as_ref()).await
+        fetch_adapter_version_from_github(github_repo, tag, delegate.as_ref()).await
     }
 
     async fn install_binary(
@@ -254,6 +254,7 @@ impl DebugAdapter for PythonDebugAdapter {
             config: args,
             build: None,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -590,6 +591,10 @@ impl DebugAdapter for PythonDebugAdapter {
         })
     }
 
+    fn downloads_releases(&self) -> bool {
+        true
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -638,9 +643,17 @@ impl DebugAdapter for PythonDebugAdapter {
             }
         }
 
-        if self.checked.set(()).is_ok() {
+        if let Some(tag) = adapters::pinned_adapter_version(&self.name(), cx)? {
+            if !adapters::adapter_version_dir(&self.name(), &tag).exists() {
+                delegate.output_to_console(format!("Downloading {} {tag}...", self.name()));
+                let version = self.fetch_adapter_version(Some(&tag), delegate).await?;
+                cx.background_spawn(Self::install_binary(self.name(), version, delegate.clone()))
+                    .await
+                    .context("Failed to install debugpy")?;
+            }
+        } else if self.checked.set(()).is_ok() {
             delegate.output_to_console(format!("Checking latest version of {}...", self.name()));
-            if let Some(version) = self.fetch_latest_adapter_version(delegate).await.log_err() {
+            if let Some(version) = self.fetch_adapter_version(None, delegate).await.log_err() {
                 cx.background_spawn(Self::install_binary(self.name(), version, delegate.clone()))
                     .await
                     .context("Failed to install debugpy")?;
@@ -652,13 +665,14 @@ impl DebugAdapter for PythonDebugAdapter {
     }
 }
 
-async fn fetch_latest_adapter_version_from_github(
+async fn fetch_adapter_version_from_github(
     github_repo: GithubRepo,
+    tag: Option<&str>,
     delegate: &dyn DapDelegate,
 ) -> Result<AdapterVersion> {
-    let release = latest_github_release(
+    let release = adapters::fetch_adapter_release(
         &format!("{}/{}", github_repo.repo_owner, github_repo.repo_name),
-        false,
+        tag,
         false,
         delegate.http_client(),
     )
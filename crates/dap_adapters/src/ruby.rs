@@ -106,6 +106,7 @@ impl DebugAdapter for RubyDebugAdapter {
                     config,
                     tcp_connection: None,
                     build: None,
+                    wait_for: None,
                 })
             }
             DebugRequest::Attach(_) => {
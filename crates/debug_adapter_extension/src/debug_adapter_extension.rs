@@ -3,8 +3,11 @@ mod extension_locator_adapter;
 
 use std::{path::Path, sync::Arc};
 
-use dap::DapRegistry;
-use extension::{ExtensionDebugAdapterProviderProxy, ExtensionHostProxy};
+use dap::{DapRegistry, visualizer::TemplateVisualizer};
+use extension::{
+    DebugAdapterManifestEntry, DebugVisualizerManifestEntry, ExtensionDebugAdapterProviderProxy,
+    ExtensionHostProxy,
+};
 use extension_dap_adapter::ExtensionDapAdapter;
 use gpui::App;
 use util::ResultExt;
@@ -35,9 +38,11 @@ impl ExtensionDebugAdapterProviderProxy for DebugAdapterRegistryProxy {
         extension: Arc<dyn extension::Extension>,
         debug_adapter_name: Arc<str>,
         schema_path: &Path,
+        manifest_entry: &DebugAdapterManifestEntry,
     ) {
         if let Some(adapter) =
-            ExtensionDapAdapter::new(extension, debug_adapter_name, schema_path).log_err()
+            ExtensionDapAdapter::new(extension, debug_adapter_name, schema_path, manifest_entry)
+                .log_err()
         {
             self.debug_adapter_registry.add_adapter(Arc::new(adapter));
         }
@@ -55,6 +60,24 @@ impl ExtensionDebugAdapterProviderProxy for DebugAdapterRegistryProxy {
             )));
     }
 
+    fn register_debug_visualizer(
+        &self,
+        visualizer_name: Arc<str>,
+        visualizer: &DebugVisualizerManifestEntry,
+    ) {
+        self.debug_adapter_registry
+            .add_visualizer(Arc::new(TemplateVisualizer {
+                name: visualizer_name.into(),
+                adapters: visualizer
+                    .adapters
+                    .iter()
+                    .map(|adapter| adapter.clone().into())
+                    .collect(),
+                types: visualizer.types.clone(),
+                template: visualizer.template.clone(),
+            }));
+    }
+
     fn unregister_debug_adapter(&self, debug_adapter_name: Arc<str>) {
         self.debug_adapter_registry
             .remove_adapter(&debug_adapter_name);
@@ -63,4 +86,9 @@ impl ExtensionDebugAdapterProviderProxy for DebugAdapterRegistryProxy {
     fn unregister_debug_locator(&self, locator_name: Arc<str>) {
         self.debug_adapter_registry.remove_locator(&locator_name);
     }
+
+    fn unregister_debug_visualizer(&self, visualizer_name: Arc<str>) {
+        self.debug_adapter_registry
+            .remove_visualizer(&visualizer_name);
+    }
 }
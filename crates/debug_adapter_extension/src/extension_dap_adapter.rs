@@ -10,9 +10,10 @@ use dap::{
     StartDebuggingRequestArgumentsRequest,
     adapters::{
         DapDelegate, DebugAdapter, DebugAdapterBinary, DebugAdapterName, DebugTaskDefinition,
+        HotReloadRequests,
     },
 };
-use extension::{Extension, WorktreeDelegate};
+use extension::{DebugAdapterManifestEntry, Extension, WorktreeDelegate};
 use gpui::AsyncApp;
 use task::{DebugScenario, ZedDebugConfig};
 
@@ -20,6 +21,7 @@ pub(crate) struct ExtensionDapAdapter {
     extension: Arc<dyn Extension>,
     debug_adapter_name: Arc<str>,
     schema: serde_json::Value,
+    hot_reload_requests: HotReloadRequests,
 }
 
 impl ExtensionDapAdapter {
@@ -27,6 +29,7 @@ impl ExtensionDapAdapter {
         extension: Arc<dyn extension::Extension>,
         debug_adapter_name: Arc<str>,
         schema_path: &Path,
+        manifest_entry: &DebugAdapterManifestEntry,
     ) -> Result<Self> {
         let schema = std::fs::read_to_string(&schema_path).with_context(|| {
             format!(
@@ -40,6 +43,10 @@ impl ExtensionDapAdapter {
             extension,
             debug_adapter_name,
             schema,
+            hot_reload_requests: HotReloadRequests {
+                reload: manifest_entry.hot_reload_request.clone(),
+                restart: manifest_entry.hot_restart_request.clone(),
+            },
         })
     }
 }
@@ -83,6 +90,10 @@ impl DebugAdapter for ExtensionDapAdapter {
         self.schema.clone()
     }
 
+    fn hot_reload_requests(&self) -> HotReloadRequests {
+        self.hot_reload_requests.clone()
+    }
+
     async fn get_binary(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -0,0 +1,372 @@
+use std::sync::Arc;
+
+use dap::{
+    DapRegistry,
+    adapters::{self, DebugAdapterName},
+    debugger_settings::DebuggerSettings,
+};
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task};
+use project::{Fs, Project, debugger::dap_store::DapStore};
+use settings::{Settings as _, SettingsStore};
+use ui::{Divider, Tooltip, prelude::*};
+use workspace::{ModalView, Workspace};
+
+/// A debug adapter and the versions of it that are downloaded.
+struct AdapterEntry {
+    name: DebugAdapterName,
+    downloads_releases: bool,
+    installed_versions: Vec<String>,
+}
+
+/// Lists the registered debug adapters with their downloaded versions, lets downloaded adapters
+/// be pinned to a release, and shows which sessions run which adapter binary.
+pub(crate) struct AdapterVersionsModal {
+    fs: Arc<dyn Fs>,
+    dap_store: Entity<DapStore>,
+    adapters: Vec<AdapterEntry>,
+    /// The adapter whose version is being typed into `version_editor`.
+    editing_version: Option<DebugAdapterName>,
+    version_editor: Entity<Editor>,
+    focus_handle: FocusHandle,
+    _load_versions: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl AdapterVersionsModal {
+    pub(crate) fn show(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        workspace.toggle_modal(window, cx, |window, cx| Self::new(&project, window, cx));
+    }
+
+    fn new(project: &Entity<Project>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let fs = project.read(cx).fs().clone();
+        let dap_store = project.read(cx).dap_store();
+
+        let registry = DapRegistry::global(cx);
+        let mut names = registry.enumerate_adapters();
+        names.sort_by(|a, b| a.0.cmp(&b.0));
+        let adapters = names
+            .into_iter()
+            .filter_map(|name| {
+                let adapter = registry.adapter(&name)?;
+                Some(AdapterEntry {
+                    downloads_releases: adapter.downloads_releases(),
+                    name,
+                    installed_versions: Vec::new(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let _load_versions = cx.spawn({
+            let fs = fs.clone();
+            let names = adapters
+                .iter()
+                .filter(|adapter| adapter.downloads_releases)
+                .map(|adapter| adapter.name.clone())
+                .collect::<Vec<_>>();
+            async move |this, cx| {
+                for name in names {
+                    let versions = adapters::installed_adapter_versions(&name, fs.as_ref()).await;
+                    this.update(cx, |this, cx| {
+                        if let Some(adapter) = this
+                            .adapters
+                            .iter_mut()
+                            .find(|adapter| adapter.name == name)
+                        {
+                            adapter.installed_versions = versions;
+                            cx.notify();
+                        }
+                    })
+                    .ok();
+                }
+            }
+        });
+
+        let version_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Release tag, e.g. v1.11.4", cx);
+            editor
+        });
+
+        let _subscriptions = vec![
+            cx.observe(&dap_store, |_, _, cx| cx.notify()),
+            cx.observe_global::<SettingsStore>(|_, cx| cx.notify()),
+        ];
+
+        Self {
+            fs,
+            dap_store,
+            adapters,
+            editing_version: None,
+            version_editor,
+            focus_handle: cx.focus_handle(),
+            _load_versions,
+            _subscriptions,
+        }
+    }
+
+    fn set_pinned_version(&self, name: DebugAdapterName, version: Option<String>, cx: &mut App) {
+        settings::update_settings_file::<DebuggerSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| match version {
+                Some(version) => {
+                    settings.adapter_versions.insert(name.to_string(), version);
+                }
+                None => {
+                    settings.adapter_versions.remove(name.as_ref());
+                }
+            },
+        );
+    }
+
+    fn edit_version(
+        &mut self,
+        name: DebugAdapterName,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let pinned_version = DebuggerSettings::get_global(cx)
+            .adapter_versions
+            .get(name.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        self.version_editor.update(cx, |editor, cx| {
+            editor.set_text(pinned_version, window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+        });
+        self.editing_version = Some(name);
+        window.focus(&self.version_editor.focus_handle(cx));
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(name) = self.editing_version.take() else {
+            return;
+        };
+        let version = self.version_editor.read(cx).text(cx).trim().to_string();
+        if !version.is_empty() {
+            self.set_pinned_version(name, Some(version), cx);
+        }
+        window.focus(&self.focus_handle);
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.editing_version.take().is_some() {
+            window.focus(&self.focus_handle);
+            cx.notify();
+        } else {
+            cx.emit(DismissEvent);
+        }
+    }
+
+    fn render_adapter(
+        &self,
+        ix: usize,
+        adapter: &AdapterEntry,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let pinned_version = DebuggerSettings::get_global(cx)
+            .adapter_versions
+            .get(adapter.name.as_ref())
+            .cloned();
+        let status = version_status(
+            adapter.downloads_releases,
+            &adapter.installed_versions,
+            pinned_version.as_deref(),
+        );
+        let sessions = self
+            .dap_store
+            .read(cx)
+            .sessions()
+            .filter_map(|session| {
+                let session = session.read(cx);
+                if session.adapter() != adapter.name || session.is_terminated() {
+                    return None;
+                }
+                let command = session
+                    .binary()
+                    .and_then(|binary| binary.command.clone())
+                    .unwrap_or_else(|| "remote binary".to_string());
+                Some(SharedString::from(format!(
+                    "{}: {command}",
+                    session.label()
+                )))
+            })
+            .collect::<Vec<_>>();
+        let is_editing = self.editing_version.as_ref() == Some(&adapter.name);
+
+        v_flex()
+            .gap_1()
+            .when(ix > 0, |this| this.child(Divider::horizontal()))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(Label::new(adapter.name.0.clone()))
+                    .child(
+                        Label::new(status)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    )
+                    .child(div().flex_1())
+                    .when(adapter.downloads_releases, |this| {
+                        let name = adapter.name.clone();
+                        let installed_version = adapter.installed_versions.last().cloned();
+                        this.when_some(pinned_version.clone(), |this, _| {
+                            let name = name.clone();
+                            this.child(
+                                Button::new(("unpin-adapter", ix), "Unpin")
+                                    .label_size(LabelSize::Small)
+                                    .tooltip(Tooltip::text(
+                                        "Update to the latest release when next used",
+                                    ))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.set_pinned_version(name.clone(), None, cx)
+                                    })),
+                            )
+                        })
+                        .when_some(
+                            installed_version.filter(|_| pinned_version.is_none()),
+                            |this, installed_version| {
+                                let name = name.clone();
+                                this.child(
+                                    Button::new(("pin-adapter", ix), "Pin")
+                                        .label_size(LabelSize::Small)
+                                        .tooltip(Tooltip::text(format!(
+                                            "Keep using {installed_version}"
+                                        )))
+                                        .on_click(cx.listener(move |this, _, _, cx| {
+                                            this.set_pinned_version(
+                                                name.clone(),
+                                                Some(installed_version.clone()),
+                                                cx,
+                                            )
+                                        })),
+                                )
+                            },
+                        )
+                        .child(
+                            Button::new(("pin-adapter-version", ix), "Pin Version…")
+                                .label_size(LabelSize::Small)
+                                .tooltip(Tooltip::text(
+                                    "Update or downgrade to a release when next used",
+                                ))
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.edit_version(name.clone(), window, cx)
+                                })),
+                        )
+                    }),
+            )
+            .when(is_editing, |this| {
+                this.child(
+                    div()
+                        .px_1()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .rounded_sm()
+                        .child(self.version_editor.clone()),
+                )
+            })
+            .children(sessions.into_iter().map(|session| {
+                Label::new(session)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .truncate()
+            }))
+    }
+}
+
+/// Which version of an adapter is installed, and what happens to it when the adapter is next used.
+fn version_status(
+    downloads_releases: bool,
+    installed_versions: &[String],
+    pinned_version: Option<&str>,
+) -> SharedString {
+    if !downloads_releases {
+        return "Installed separately".into();
+    }
+    let installed_version = installed_versions.last();
+    match (installed_version, pinned_version) {
+        (None, None) => "Not downloaded".into(),
+        (None, Some(pinned)) => format!("Pinned to {pinned}, downloaded when next used").into(),
+        (Some(installed), None) => format!("{installed}, updated when next used").into(),
+        (Some(installed), Some(pinned)) if installed == pinned => {
+            format!("{installed}, pinned").into()
+        }
+        (Some(installed), Some(pinned)) => {
+            format!("{installed}, pinned to {pinned} when next used").into()
+        }
+    }
+}
+
+impl EventEmitter<DismissEvent> for AdapterVersionsModal {}
+
+impl Focusable for AdapterVersionsModal {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for AdapterVersionsModal {}
+
+impl Render for AdapterVersionsModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let adapters = self
+            .adapters
+            .iter()
+            .enumerate()
+            .map(|(ix, adapter)| self.render_adapter(ix, adapter, cx).into_any_element())
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .id("adapter-versions")
+            .key_context("AdapterVersionsModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .max_h(rems(32.))
+            .overflow_y_scroll()
+            .p_2()
+            .gap_2()
+            .child(Label::new("Debug Adapters"))
+            .children(adapters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_status() {
+        let installed = vec!["v1.10.0".to_string()];
+
+        assert_eq!(version_status(false, &[], None), "Installed separately");
+        assert_eq!(version_status(true, &[], None), "Not downloaded");
+        assert_eq!(
+            version_status(true, &[], Some("v1.9.0")),
+            "Pinned to v1.9.0, downloaded when next used"
+        );
+        assert_eq!(
+            version_status(true, &installed, None),
+            "v1.10.0, updated when next used"
+        );
+        assert_eq!(
+            version_status(true, &installed, Some("v1.10.0")),
+            "v1.10.0, pinned"
+        );
+        assert_eq!(
+            version_status(true, &installed, Some("v1.9.0")),
+            "v1.10.0, pinned to v1.9.0 when next used"
+        );
+    }
+}
@@ -1,13 +1,19 @@
-use dap::{DapRegistry, DebugRequest};
+use dap::{
+    DapRegistry, DebugRequest,
+    debugger_settings::{DebuggerSettings, SourcePathMapping},
+};
+use futures::channel::oneshot;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use gpui::{AppContext, DismissEvent, Entity, EventEmitter, Focusable, Render};
 use gpui::{Subscription, WeakEntity};
 use picker::{Picker, PickerDelegate};
+use project::debugger::docker::{self, CONTAINER_CONFIG_KEY, DockerContainer};
+use settings::Settings as _;
 use task::ZedDebugConfig;
-use util::debug_panic;
+use util::{ResultExt as _, debug_panic};
 
 use std::sync::Arc;
-use sysinfo::System;
+use sysinfo::{System, Users};
 use ui::{Context, Tooltip, prelude::*};
 use ui::{ListItem, ListItemSpacing};
 use workspace::{ModalView, Workspace};
@@ -19,6 +25,7 @@ pub(super) struct Candidate {
     pub(super) pid: u32,
     pub(super) name: SharedString,
     pub(super) command: Vec<String>,
+    pub(super) user: Option<SharedString>,
 }
 
 pub(crate) struct AttachModalDelegate {
@@ -28,6 +35,10 @@ pub(crate) struct AttachModalDelegate {
     pub(crate) definition: ZedDebugConfig,
     workspace: WeakEntity<Workspace>,
     candidates: Arc<[Candidate]>,
+    /// When set, the picked pid is sent here instead of starting a session with `definition`.
+    pid_sender: Option<oneshot::Sender<u32>>,
+    /// The container whose processes are listed, or `None` for the host's.
+    container: Option<DockerContainer>,
 }
 
 impl AttachModalDelegate {
@@ -40,6 +51,8 @@ impl AttachModalDelegate {
             workspace,
             definition,
             candidates,
+            pid_sender: None,
+            container: None,
             selected_index: 0,
             matches: Vec::default(),
             placeholder_text: Arc::from("Select the process you want to attach the debugger to"),
@@ -60,25 +73,29 @@ impl AttachModal {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
-        let mut processes: Box<[_]> = System::new_all()
-            .processes()
-            .values()
-            .map(|process| {
-                let name = process.name().to_string_lossy().into_owned();
-                Candidate {
-                    name: name.into(),
-                    pid: process.pid().as_u32(),
-                    command: process
-                        .cmd()
-                        .iter()
-                        .map(|s| s.to_string_lossy().to_string())
-                        .collect::<Vec<_>>(),
-                }
-            })
-            .collect();
-        processes.sort_by_key(|k| k.name.clone());
-        let processes = processes.into_iter().collect();
-        Self::with_processes(workspace, definition, processes, modal, window, cx)
+        Self::with_processes(
+            workspace,
+            definition,
+            running_processes(),
+            modal,
+            window,
+            cx,
+        )
+    }
+
+    /// Lets the user pick a process for a scenario that refers to it through `${pid}`.
+    pub(crate) fn pick_pid(
+        definition: ZedDebugConfig,
+        workspace: WeakEntity<Workspace>,
+        pid_sender: oneshot::Sender<u32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let this = Self::new(definition, workspace, true, window, cx);
+        this.picker.update(cx, |picker, _| {
+            picker.delegate.pid_sender = Some(pid_sender);
+        });
+        this
     }
 
     pub(super) fn with_processes(
@@ -104,6 +121,29 @@ impl AttachModal {
             picker,
         }
     }
+
+    /// Lists the processes of `container` instead of the host's, attaching to them with an
+    /// adapter started inside of the container.
+    pub(crate) fn set_container(
+        &mut self,
+        container: Option<DockerContainer>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let picker = self.picker.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let processes = match &container {
+                Some(container) => container_processes(&container.id).await?,
+                None => running_processes(),
+            };
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.container = container;
+                picker.delegate.candidates = processes;
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
 }
 
 impl Render for AttachModal {
@@ -172,10 +212,11 @@ impl PickerDelegate for AttachModalDelegate {
                         StringMatchCandidate::new(
                             id,
                             format!(
-                                "{} {} {}",
+                                "{} {} {} {}",
                                 candidate.command.join(" "),
                                 candidate.pid,
-                                candidate.name
+                                candidate.name,
+                                candidate.user.as_deref().unwrap_or_default()
                             )
                             .as_str(),
                         )
@@ -219,6 +260,11 @@ impl PickerDelegate for AttachModalDelegate {
             return cx.emit(DismissEvent);
         };
 
+        if let Some(pid_sender) = self.pid_sender.take() {
+            pid_sender.send(candidate.pid).ok();
+            return cx.emit(DismissEvent);
+        }
+
         match &mut self.definition.request {
             DebugRequest::Attach(config) => {
                 config.process_id = Some(candidate.pid);
@@ -237,10 +283,20 @@ impl PickerDelegate for AttachModalDelegate {
 
         let workspace = self.workspace.clone();
         let definition = self.definition.clone();
+        let container = self.container.clone();
         cx.spawn_in(window, async move |this, cx| {
-            let Ok(scenario) = adapter.config_from_zed_format(definition).await else {
+            let Ok(mut scenario) = adapter.config_from_zed_format(definition).await else {
                 return;
             };
+            if let Some(container) = container {
+                map_container_sources(&container, &workspace, cx).await;
+                if let Some(config) = scenario.config.as_object_mut() {
+                    config.insert(
+                        CONTAINER_CONFIG_KEY.into(),
+                        container.name.to_string().into(),
+                    );
+                }
+            }
 
             let panel = workspace
                 .update(cx, |workspace, cx| workspace.panel::<DebugPanel>(cx))
@@ -282,6 +338,9 @@ impl PickerDelegate for AttachModalDelegate {
                 .inset(true)
                 .spacing(ListItemSpacing::Sparse)
                 .toggle_state(selected)
+                .when_some(candidate.user.clone(), |this, user| {
+                    this.end_slot(Label::new(user).size(LabelSize::Small).color(Color::Muted))
+                })
                 .child(
                     v_flex()
                         .items_start()
@@ -318,6 +377,95 @@ impl PickerDelegate for AttachModalDelegate {
     }
 }
 
+fn running_processes() -> Arc<[Candidate]> {
+    let users = Users::new_with_refreshed_list();
+    let mut processes: Box<[_]> = System::new_all()
+        .processes()
+        .values()
+        .map(|process| {
+            let name = process.name().to_string_lossy().into_owned();
+            Candidate {
+                name: name.into(),
+                pid: process.pid().as_u32(),
+                command: process
+                    .cmd()
+                    .iter()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .collect::<Vec<_>>(),
+                user: process
+                    .user_id()
+                    .and_then(|user_id| users.get_user_by_id(user_id))
+                    .map(|user| user.name().to_string().into()),
+            }
+        })
+        .collect();
+    processes.sort_by_key(|k| k.name.clone());
+    processes.into_iter().collect()
+}
+
+async fn container_processes(container: &str) -> anyhow::Result<Arc<[Candidate]>> {
+    Ok(docker::container_processes(container)
+        .await?
+        .into_iter()
+        .map(|process| Candidate {
+            pid: process.pid,
+            name: process
+                .command
+                .first()
+                .map(|program| program.rsplit('/').next().unwrap_or(program).to_string())
+                .unwrap_or_default()
+                .into(),
+            command: process.command,
+            user: Some(process.user.into()),
+        })
+        .collect())
+}
+
+/// Adds `source_path_mappings` for the container's bind mounts of the workspace's worktrees, so
+/// that frames reported from inside the container open the local sources.
+async fn map_container_sources(
+    container: &DockerContainer,
+    workspace: &WeakEntity<Workspace>,
+    cx: &mut gpui::AsyncWindowContext,
+) {
+    let Some(mounts) = docker::container_mounts(&container.id).await.log_err() else {
+        return;
+    };
+    workspace
+        .update(cx, |workspace, cx| {
+            let worktree_roots = workspace
+                .project()
+                .read(cx)
+                .visible_worktrees(cx)
+                .map(|worktree| worktree.read(cx).abs_path())
+                .collect::<Vec<_>>();
+            let worktree_roots = worktree_roots
+                .iter()
+                .map(|root| root.as_ref())
+                .collect::<Vec<_>>();
+            let mappings = docker::source_path_mappings(&mounts, &worktree_roots);
+            let existing = &DebuggerSettings::get_global(cx).source_path_mappings;
+            let new_mappings = mappings
+                .into_iter()
+                .filter(|mapping| !existing.contains(mapping))
+                .collect::<Vec<SourcePathMapping>>();
+            if new_mappings.is_empty() {
+                return;
+            }
+            settings::update_settings_file::<DebuggerSettings>(
+                workspace.app_state().fs.clone(),
+                cx,
+                move |settings, _| {
+                    settings.source_path_mappings.retain(|mapping| {
+                        !new_mappings.iter().any(|new| new.remote == mapping.remote)
+                    });
+                    settings.source_path_mappings.extend(new_mappings);
+                },
+            );
+        })
+        .ok();
+}
+
 #[cfg(any(test, feature = "test-support"))]
 pub(crate) fn _process_names(modal: &AttachModal, cx: &mut Context<AttachModal>) -> Vec<String> {
     modal.picker.read_with(cx, |picker, _| {
@@ -0,0 +1,206 @@
+use dap::{DapRegistry, adapters::DebugAdapterName};
+use gpui::{DismissEvent, EventEmitter, FocusHandle, Focusable, Task};
+use project::debugger::dap_store::{AdapterCheck, AdapterCheckStage};
+use ui::{Divider, prelude::*};
+use workspace::{ModalView, Workspace};
+
+/// A debug adapter and how far checking it has gotten.
+struct AdapterCheckEntry {
+    name: DebugAdapterName,
+    check: Option<AdapterCheck>,
+}
+
+/// Checks that every registered debug adapter can be located, launched and completes the
+/// `initialize` handshake, and shows what went wrong for the ones that can't.
+pub(crate) struct CheckAdaptersModal {
+    adapters: Vec<AdapterCheckEntry>,
+    /// Why no adapters could be checked.
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    _checks: Vec<Task<()>>,
+}
+
+impl CheckAdaptersModal {
+    pub(crate) fn show(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let project = workspace.project().clone();
+        workspace.toggle_modal(window, cx, |_, cx| {
+            let mut names = DapRegistry::global(cx).enumerate_adapters();
+            names.sort_by(|a, b| a.0.cmp(&b.0));
+            let adapters = names
+                .into_iter()
+                .map(|name| AdapterCheckEntry { name, check: None })
+                .collect::<Vec<_>>();
+
+            let worktree = project.read(cx).visible_worktrees(cx).next();
+            let Some(worktree) = worktree else {
+                return Self {
+                    adapters: Vec::new(),
+                    error: Some("Open a folder to check debug adapters.".into()),
+                    focus_handle: cx.focus_handle(),
+                    _checks: Vec::new(),
+                };
+            };
+
+            let dap_store = project.read(cx).dap_store();
+            let _checks = adapters
+                .iter()
+                .map(|adapter| {
+                    let name = adapter.name.clone();
+                    let check = dap_store.update(cx, |dap_store, cx| {
+                        dap_store.check_adapter(name.clone(), &worktree, cx)
+                    });
+                    cx.spawn(async move |this, cx| {
+                        let check = check.await;
+                        this.update(cx, |this: &mut Self, cx| {
+                            if let Some(adapter) = this
+                                .adapters
+                                .iter_mut()
+                                .find(|adapter| adapter.name == name)
+                            {
+                                adapter.check = Some(check);
+                                cx.notify();
+                            }
+                        })
+                        .ok();
+                    })
+                })
+                .collect();
+
+            Self {
+                adapters,
+                error: None,
+                focus_handle: cx.focus_handle(),
+                _checks,
+            }
+        });
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_adapter(&self, ix: usize, adapter: &AdapterCheckEntry) -> impl IntoElement {
+        let (icon, status) = match &adapter.check {
+            None => (
+                Icon::new(IconName::ArrowCircle).color(Color::Muted),
+                Label::new("Checking…").color(Color::Muted),
+            ),
+            Some(AdapterCheck::Passed { command }) => (
+                Icon::new(IconName::Check).color(Color::Success),
+                Label::new(
+                    command
+                        .clone()
+                        .unwrap_or_else(|| "Started remotely".to_string()),
+                )
+                .color(Color::Muted),
+            ),
+            Some(AdapterCheck::Failed { stage, .. }) => (
+                Icon::new(IconName::XCircle).color(Color::Error),
+                Label::new(stage_label(*stage)).color(Color::Error),
+            ),
+        };
+
+        v_flex()
+            .gap_1()
+            .when(ix > 0, |this| this.child(Divider::horizontal()))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(icon.size(IconSize::Small))
+                    .child(Label::new(adapter.name.0.clone()))
+                    .child(status.size(LabelSize::Small).truncate()),
+            )
+            .when_some(
+                match &adapter.check {
+                    Some(AdapterCheck::Failed { stage, error }) => Some((*stage, error.clone())),
+                    _ => None,
+                },
+                |this, (stage, error)| {
+                    this.child(Label::new(error).size(LabelSize::Small).color(Color::Muted))
+                        .child(
+                            Label::new(failure_hint(&adapter.name, stage)).size(LabelSize::Small),
+                        )
+                },
+            )
+    }
+}
+
+fn stage_label(stage: AdapterCheckStage) -> &'static str {
+    match stage {
+        AdapterCheckStage::Locate => "Couldn't be located",
+        AdapterCheckStage::Launch => "Couldn't be launched",
+        AdapterCheckStage::Initialize => "Didn't complete initialization",
+    }
+}
+
+/// What to try next when an adapter fails at `stage`.
+fn failure_hint(adapter: &DebugAdapterName, stage: AdapterCheckStage) -> String {
+    match stage {
+        AdapterCheckStage::Locate => format!(
+            "Install {adapter}, or set `dap.{adapter}.binary` in your settings to the path of its binary."
+        ),
+        AdapterCheckStage::Launch => format!(
+            "Check that {adapter}'s command runs from a terminal. Its output is in `dev: open debug adapter logs`."
+        ),
+        AdapterCheckStage::Initialize => format!(
+            "{adapter} started but didn't answer the initialize request. Its messages are in `dev: open debug adapter logs`."
+        ),
+    }
+}
+
+impl EventEmitter<DismissEvent> for CheckAdaptersModal {}
+
+impl Focusable for CheckAdaptersModal {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for CheckAdaptersModal {}
+
+impl Render for CheckAdaptersModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("check-adapters")
+            .key_context("CheckAdaptersModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .max_h(rems(32.))
+            .overflow_y_scroll()
+            .p_2()
+            .gap_2()
+            .child(Label::new("Check Debug Adapters"))
+            .when_some(self.error.clone(), |this, error| {
+                this.child(Label::new(error).size(LabelSize::Small).color(Color::Muted))
+            })
+            .children(
+                self.adapters
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, adapter)| self.render_adapter(ix, adapter)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_hints() {
+        let adapter = DebugAdapterName("CodeLLDB".into());
+        assert_eq!(
+            failure_hint(&adapter, AdapterCheckStage::Locate),
+            "Install CodeLLDB, or set `dap.CodeLLDB.binary` in your settings to the path of its binary."
+        );
+        assert!(
+            failure_hint(&adapter, AdapterCheckStage::Initialize).contains("debug adapter logs")
+        );
+    }
+}
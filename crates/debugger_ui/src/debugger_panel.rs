@@ -1,43 +1,60 @@
+use crate::dropdown_menus::ThreadPickerDelegate;
 use crate::persistence::DebuggerPaneItem;
-use crate::session::DebugSession;
 use crate::session::running::RunningState;
 use crate::session::running::breakpoint_list::BreakpointList;
+use crate::session::{DebugSession, LaunchGroup, SessionTreeNode};
+use crate::session_history::{MAX_SESSION_HISTORY, SessionHistoryEntry};
 use crate::{
-    ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
-    FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, Restart, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, CycleSteppingGranularity, Detach,
+    FocusBreakpointList, FocusConsole, FocusFrames, FocusLoadedSources, FocusModules,
+    FocusRegisters, FocusTerminal, FocusVariables, HotReload, NewProcessModal, NewProcessMode,
+    Pause, Restart, ReverseContinue, ShowDisassembly, StepBack, StepInto, StepOut, StepOver, Stop,
+    ToggleExpandItem, ToggleSessionPicker, ToggleSessionRecording, ToggleThreadPicker, persistence,
+    spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use dap::adapters::DebugAdapterName;
 use dap::debugger_settings::DebugPanelDockPosition;
 use dap::{
-    ContinuedEvent, LoadedSourceEvent, ModuleEvent, OutputEvent, StoppedEvent, ThreadEvent,
-    client::SessionId, debugger_settings::DebuggerSettings,
+    ContinuedEvent, LoadedSourceEvent, ModuleEvent, OutputEvent, SteppingGranularity, StoppedEvent,
+    ThreadEvent, client::SessionId, debugger_settings::DebuggerSettings,
 };
 use dap::{DapRegistry, StartDebuggingRequestArguments};
 use gpui::{
-    Action, App, AsyncWindowContext, ClipboardItem, Context, DismissEvent, Entity, EntityId,
-    EventEmitter, FocusHandle, Focusable, MouseButton, MouseDownEvent, Point, Subscription, Task,
-    WeakEntity, anchored, deferred,
+    Action, Animation, AnimationExt as _, App, AsyncWindowContext, ClickEvent, ClipboardItem,
+    Context, DismissEvent, Entity, EntityId, EventEmitter, FocusHandle, Focusable, MouseButton,
+    MouseDownEvent, PathPromptOptions, Point, Subscription, Task, Transformation, WeakEntity,
+    anchored, deferred, percentage,
 };
 
+use collections::HashMap;
 use itertools::Itertools as _;
 use language::Buffer;
-use project::debugger::session::{Session, SessionStateEvent};
-use project::{Fs, ProjectPath, WorktreeId};
+use picker::Picker;
+use project::debugger::dap_store::DapStoreEvent;
+use project::debugger::launch_targets;
+use project::debugger::recording::DapRecording;
+use project::debugger::session::{
+    HotReloadKind, Session, SessionEvent, SessionStateEvent, StopMode,
+};
+use project::{Fs, ProjectPath, Worktree, WorktreeId};
 use project::{Project, debugger::session::ThreadStatus};
 use rpc::proto::{self};
 use settings::Settings;
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use task::{DebugScenario, TaskContext};
 use tree_sitter::{Query, StreamingIterator as _};
-use ui::{ContextMenu, Divider, PopoverMenuHandle, Tooltip, prelude::*};
+use ui::{
+    ContextMenu, ContextMenuEntry, Divider, PopoverMenu, PopoverMenuHandle, Tab, TabBar,
+    TabPosition, Tooltip, prelude::*,
+};
 use util::maybe;
 use workspace::SplitDirection;
 use workspace::{
     Pane, Workspace,
     dock::{DockPosition, Panel, PanelEvent},
+    notifications::DetachAndPromptErr,
 };
 use zed_actions::ToggleFocus;
 
@@ -67,11 +84,17 @@ pub struct DebugPanel {
     focus_handle: FocusHandle,
     context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
     debug_scenario_scheduled_last: bool,
-    pub(crate) thread_picker_menu_handle: PopoverMenuHandle<ContextMenu>,
+    pub(crate) thread_picker_menu_handle: PopoverMenuHandle<Picker<ThreadPickerDelegate>>,
     pub(crate) session_picker_menu_handle: PopoverMenuHandle<ContextMenu>,
     fs: Arc<dyn Fs>,
     is_zoomed: bool,
-    _subscriptions: [Subscription; 1],
+    /// Sessions started from a scenario in this workspace, from oldest to newest.
+    session_history: Vec<SessionHistoryEntry>,
+    /// Scenarios starred by the user, which are listed first when starting a session.
+    pinned_scenarios: Vec<DebugScenario>,
+    launch_groups: HashMap<SessionId, LaunchGroup>,
+    next_launch_group_id: usize,
+    _subscriptions: [Subscription; 2],
     breakpoint_list: Entity<BreakpointList>,
 }
 
@@ -94,6 +117,19 @@ impl DebugPanel {
                     this.focus_active_item(window, cx);
                 },
             );
+            let dap_store_subscription = cx.subscribe_in(
+                &project.read(cx).dap_store(),
+                window,
+                |_, _, event: &DapStoreEvent, window, cx| {
+                    if let DapStoreEvent::RemoteSessionAdded(session) = event {
+                        let session = session.clone();
+                        cx.spawn_in(window, async move |this, cx| {
+                            Self::register_session(this, session, false, cx).await
+                        })
+                        .detach_and_log_err(cx);
+                    }
+                },
+            );
 
             Self {
                 size: px(300.),
@@ -108,7 +144,11 @@ impl DebugPanel {
                 thread_picker_menu_handle,
                 session_picker_menu_handle,
                 is_zoomed: false,
-                _subscriptions: [focus_subscription],
+                session_history: Vec::new(),
+                pinned_scenarios: Vec::new(),
+                launch_groups: HashMap::default(),
+                next_launch_group_id: 0,
+                _subscriptions: [focus_subscription, dap_store_subscription],
                 debug_scenario_scheduled_last: true,
             }
         })
@@ -133,6 +173,24 @@ impl DebugPanel {
         self.sessions.clone()
     }
 
+    /// The sessions with where each sits in the tree of parent and child sessions.
+    pub(crate) fn session_tree(&self, cx: &App) -> Vec<(Entity<DebugSession>, SessionTreeNode)> {
+        let ids = self
+            .sessions
+            .iter()
+            .map(|session| {
+                let session = session.read(cx).session(cx);
+                let session = session.read(cx);
+                (session.session_id(), session.parent_id(cx))
+            })
+            .collect::<Vec<_>>();
+        self.sessions
+            .iter()
+            .cloned()
+            .zip(crate::session::session_tree(&ids, &self.launch_groups))
+            .collect()
+    }
+
     pub fn active_session(&self) -> Option<Entity<DebugSession>> {
         self.active_session.clone()
     }
@@ -147,8 +205,26 @@ impl DebugPanel {
         cx: &mut AsyncWindowContext,
     ) -> Task<Result<Entity<Self>>> {
         cx.spawn(async move |cx| {
+            let workspace_id = workspace.read_with(cx, |workspace, _| workspace.database_id())?;
+            let (session_history, pinned_scenarios) = match workspace_id {
+                Some(workspace_id) => {
+                    cx.background_spawn(async move {
+                        (
+                            persistence::get_session_history(workspace_id),
+                            persistence::get_pinned_scenarios(workspace_id),
+                        )
+                    })
+                    .await
+                }
+                None => (Vec::new(), Vec::new()),
+            };
+
             workspace.update_in(cx, |workspace, window, cx| {
                 let debug_panel = DebugPanel::new(workspace, window, cx);
+                debug_panel.update(cx, |debug_panel, _| {
+                    debug_panel.session_history = session_history;
+                    debug_panel.pinned_scenarios = pinned_scenarios;
+                });
 
                 workspace.register_action(|workspace, _: &ClearAllBreakpoints, _, cx| {
                     workspace.project().read(cx).breakpoint_store().update(
@@ -175,6 +251,270 @@ impl DebugPanel {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if scenario
+            .config
+            .get(launch_targets::COUPLED_CONFIG_KEY)
+            .is_some()
+        {
+            self.start_coupled_sessions(
+                scenario,
+                task_context,
+                active_buffer,
+                worktree_id,
+                window,
+                cx,
+            );
+        } else if scenario
+            .config
+            .get(launch_targets::PROGRAMS_CONFIG_KEY)
+            .is_some()
+        {
+            self.start_launch_group(
+                scenario,
+                task_context,
+                active_buffer,
+                worktree_id,
+                window,
+                cx,
+            );
+        } else {
+            self.start_target_session(
+                scenario,
+                task_context,
+                active_buffer,
+                worktree_id,
+                window,
+                cx,
+            );
+        }
+    }
+
+    fn worktree_for_session(
+        &self,
+        worktree_id: Option<WorktreeId>,
+        active_buffer: Option<&Entity<Buffer>>,
+        cx: &App,
+    ) -> Option<Entity<Worktree>> {
+        let worktree = worktree_id.or_else(|| {
+            active_buffer
+                .and_then(|buffer| buffer.read(cx).file())
+                .map(|f| f.worktree_id(cx))
+        });
+        worktree
+            .and_then(|id| self.project.read(cx).worktree_for_id(id, cx))
+            .or_else(|| self.project.read(cx).visible_worktrees(cx).next())
+    }
+
+    /// Starts one session per program of a scenario with several targets, listed together in
+    /// the panel.
+    fn start_launch_group(
+        &mut self,
+        scenario: DebugScenario,
+        task_context: TaskContext,
+        active_buffer: Option<Entity<Buffer>>,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(worktree) = self.worktree_for_session(worktree_id, active_buffer.as_ref(), cx)
+        else {
+            log::debug!("Could not find a worktree to spawn the debug session in");
+            return;
+        };
+        let root = worktree.read(cx).abs_path();
+        let is_local = self.project.read(cx).is_local();
+        let fs = self.fs.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            anyhow::ensure!(
+                is_local,
+                "Scenarios with several programs can only be started in local projects"
+            );
+            let targets = launch_targets::expand_targets(&scenario, &root, fs.as_ref()).await?;
+            this.update_in(cx, |this, window, cx| {
+                let group = LaunchGroup {
+                    id: this.next_launch_group_id,
+                    label: scenario.label.clone(),
+                    coupled: false,
+                };
+                this.next_launch_group_id += 1;
+                for target in targets {
+                    if let Some(session_id) = this.start_target_session(
+                        target,
+                        task_context.clone(),
+                        active_buffer.clone(),
+                        worktree_id,
+                        window,
+                        cx,
+                    ) {
+                        this.launch_groups.insert(session_id, group.clone());
+                    }
+                }
+
+                // Rerunning the last session starts all of the targets again.
+                this.scenario_scheduled(scenario, cx);
+            })
+        })
+        .detach_and_prompt_err(
+            "Failed to start debug sessions",
+            window,
+            cx,
+            |error, _, _| Some(error.to_string()),
+        );
+    }
+
+    /// Starts the session of a mixed-mode scenario, and the sessions coupled to it that debug the
+    /// same program with other adapters, listed together in the panel. Coupled sessions that
+    /// refer to `${pid}` are started once the first session's adapter reports the debuggee's
+    /// process id, which replaces it.
+    fn start_coupled_sessions(
+        &mut self,
+        scenario: DebugScenario,
+        task_context: TaskContext,
+        active_buffer: Option<Entity<Buffer>>,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.spawn_in(window, async move |this, cx| {
+            let (primary, coupled) = launch_targets::split_coupled(&scenario)?;
+            this.update_in(cx, |this, window, cx| {
+                let group = LaunchGroup {
+                    id: this.next_launch_group_id,
+                    label: scenario.label.clone(),
+                    coupled: true,
+                };
+                this.next_launch_group_id += 1;
+                let session_id = this
+                    .start_target_session(
+                        primary,
+                        task_context.clone(),
+                        active_buffer.clone(),
+                        worktree_id,
+                        window,
+                        cx,
+                    )
+                    .context("Could not find a worktree to start the debug sessions in")?;
+                this.launch_groups.insert(session_id, group.clone());
+
+                let (attaching, independent): (Vec<_>, Vec<_>) = coupled
+                    .into_iter()
+                    .partition(|coupled| RunningState::config_refers_to_pid(&coupled.config));
+                for coupled in independent {
+                    if let Some(session_id) = this.start_target_session(
+                        coupled,
+                        task_context.clone(),
+                        active_buffer.clone(),
+                        worktree_id,
+                        window,
+                        cx,
+                    ) {
+                        this.launch_groups.insert(session_id, group.clone());
+                    }
+                }
+                this.scenario_scheduled(scenario.clone(), cx);
+
+                let session = this
+                    .project
+                    .read(cx)
+                    .dap_store()
+                    .read(cx)
+                    .session_by_id(session_id);
+                let Some(session) = session else {
+                    return anyhow::Ok(());
+                };
+                let mut attaching = Some(attaching).filter(|attaching| !attaching.is_empty());
+                cx.subscribe_in(&session, window, move |this, session, event, window, cx| {
+                    if !matches!(event, SessionEvent::DebuggeeProcess) {
+                        return;
+                    }
+                    let Some(pid) = session.read(cx).debuggee_pid() else {
+                        return;
+                    };
+                    let Some(attaching) = attaching.take() else {
+                        return;
+                    };
+                    for mut coupled in attaching {
+                        RunningState::substitute_pid_in_config(&mut coupled.config, pid);
+                        if let Some(session_id) = this.start_target_session(
+                            coupled,
+                            task_context.clone(),
+                            active_buffer.clone(),
+                            worktree_id,
+                            window,
+                            cx,
+                        ) {
+                            this.launch_groups.insert(session_id, group.clone());
+                        }
+                    }
+                    this.scenario_scheduled(scenario.clone(), cx);
+                })
+                .detach();
+                anyhow::Ok(())
+            })?
+        })
+        .detach_and_prompt_err(
+            "Failed to start debug sessions",
+            window,
+            cx,
+            |error, _, _| Some(error.to_string()),
+        );
+    }
+
+    /// Makes rerunning the last session start `scenario` again.
+    fn scenario_scheduled(&self, scenario: DebugScenario, cx: &mut App) {
+        if let Some(inventory) = self
+            .project
+            .read(cx)
+            .task_store()
+            .read(cx)
+            .task_inventory()
+            .cloned()
+        {
+            inventory.update(cx, |inventory, _| {
+                inventory.scenario_scheduled(scenario);
+            })
+        }
+    }
+
+    /// Stops the sessions coupled to `session_id`'s along with it.
+    pub(crate) fn stop_coupled_sessions(&mut self, session_id: SessionId, cx: &mut Context<Self>) {
+        let Some(group_id) = self
+            .launch_groups
+            .get(&session_id)
+            .filter(|group| group.coupled)
+            .map(|group| group.id)
+        else {
+            return;
+        };
+        for session in &self.sessions {
+            let session_id = session.read(cx).session_id(cx);
+            if self
+                .launch_groups
+                .get(&session_id)
+                .is_none_or(|group| group.id != group_id)
+            {
+                continue;
+            }
+            let running_state = session.read(cx).running_state().clone();
+            running_state.update(cx, |running_state, cx| {
+                let session = running_state.session().read(cx);
+                if !session.is_terminated() {
+                    let mode = session.default_stop_mode();
+                    running_state.stop_with_mode(mode, cx);
+                }
+            });
+        }
+    }
+
+    fn start_target_session(
+        &mut self,
+        scenario: DebugScenario,
+        task_context: TaskContext,
+        active_buffer: Option<Entity<Buffer>>,
+        worktree_id: Option<WorktreeId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<SessionId> {
         let dap_store = self.project.read(cx).dap_store();
         let session = dap_store.update(cx, |dap_store, cx| {
             dap_store.new_session(
@@ -185,18 +525,11 @@ impl DebugPanel {
                 cx,
             )
         });
-        let worktree = worktree_id.or_else(|| {
-            active_buffer
-                .as_ref()
-                .and_then(|buffer| buffer.read(cx).file())
-                .map(|f| f.worktree_id(cx))
-        });
-        let Some(worktree) = worktree
-            .and_then(|id| self.project.read(cx).worktree_for_id(id, cx))
-            .or_else(|| self.project.read(cx).visible_worktrees(cx).next())
+        let session_id = session.read(cx).session_id();
+        let Some(worktree) = self.worktree_for_session(worktree_id, active_buffer.as_ref(), cx)
         else {
             log::debug!("Could not find a worktree to spawn the debug session in");
-            return;
+            return None;
         };
         self.debug_scenario_scheduled_last = true;
         if let Some(inventory) = self
@@ -211,6 +544,7 @@ impl DebugPanel {
                 inventory.scenario_scheduled(scenario.clone());
             })
         }
+        self.record_session_start(&session, scenario.clone(), cx);
         let task = cx.spawn_in(window, {
             let session = session.clone();
             async move |this, cx| {
@@ -254,6 +588,7 @@ impl DebugPanel {
             anyhow::Ok(())
         })
         .detach_and_log_err(cx);
+        Some(session_id)
     }
 
     pub(crate) fn rerun_last_session(
@@ -266,8 +601,8 @@ impl DebugPanel {
         let Some(task_inventory) = task_store.read(cx).task_inventory() else {
             return;
         };
-        let workspace = self.workspace.clone();
         let Some(scenario) = task_inventory.read(cx).last_scheduled_scenario().cloned() else {
+            let workspace = self.workspace.clone();
             window.defer(cx, move |window, cx| {
                 workspace
                     .update(cx, |workspace, cx| {
@@ -278,6 +613,17 @@ impl DebugPanel {
             return;
         };
 
+        self.rerun_scenario(scenario, window, cx);
+    }
+
+    /// Starts `scenario` again in the context of the active editor.
+    pub(crate) fn rerun_scenario(
+        &mut self,
+        scenario: DebugScenario,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let workspace = self.workspace.clone();
         cx.spawn_in(window, async move |this, cx| {
             let task_contexts = workspace
                 .update_in(cx, |workspace, window, cx| {
@@ -302,6 +648,191 @@ impl DebugPanel {
         .detach();
     }
 
+    /// Like [`Self::rerun_last_session`], but lets the user edit the scenario first.
+    pub(crate) fn edit_and_rerun_last_session(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let task_store = workspace.project().read(cx).task_store().clone();
+        let Some(task_inventory) = task_store.read(cx).task_inventory() else {
+            return;
+        };
+        let workspace = self.workspace.clone();
+        let scenario = task_inventory.read(cx).last_scheduled_scenario().cloned();
+        window.defer(cx, move |window, cx| {
+            workspace
+                .update(cx, |workspace, cx| match scenario {
+                    Some(scenario) => {
+                        NewProcessModal::show_for_rerun(workspace, window, scenario, cx)
+                    }
+                    None => {
+                        NewProcessModal::show(workspace, window, NewProcessMode::Debug, None, cx)
+                    }
+                })
+                .ok();
+        });
+    }
+
+    /// Starts recording the DAP traffic of the active session to a file the user picks, or stops
+    /// the recording that's in progress.
+    pub(crate) fn toggle_recording(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(session) = self
+            .active_session()
+            .map(|session| session.read(cx).session(cx))
+        else {
+            return;
+        };
+        if session.read(cx).recording_path().is_some() {
+            session.update(cx, |session, cx| session.stop_recording(cx));
+            return;
+        }
+
+        let directory = self
+            .project
+            .read(cx)
+            .visible_worktrees(cx)
+            .find_map(|worktree| Some(worktree.read(cx).as_local()?.abs_path().to_path_buf()))
+            .or_else(std::env::home_dir)
+            .unwrap_or_default();
+        let path = cx.prompt_for_new_path(&directory);
+        cx.spawn_in(window, async move |_, cx| {
+            let Some(path) = path.await?? else {
+                return Ok(());
+            };
+            session.update(cx, |session, cx| session.start_recording(path, cx))?
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Opens a recording of a debug session and replays it into a new, read-only session.
+    pub(crate) fn replay_recording(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        });
+        let fs = self.project.read(cx).fs().clone();
+        let dap_store = self.project.read(cx).dap_store();
+        cx.spawn_in(window, async move |this, cx| {
+            let Some(path) = paths.await??.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let contents = fs.load(&path).await?;
+            let recording = DapRecording::parse(&contents)
+                .with_context(|| format!("Reading debug session recording {path:?}"))?;
+            let session =
+                dap_store.update(cx, |dap_store, cx| dap_store.replay_session(recording, cx))?;
+            Self::register_session(this, session, true, cx).await?;
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn session_history(&self) -> &[SessionHistoryEntry] {
+        &self.session_history
+    }
+
+    pub(crate) fn pinned_scenarios(&self) -> &[DebugScenario] {
+        &self.pinned_scenarios
+    }
+
+    /// Scenarios are pinned by label, so that edits to a pinned scenario's definition apply to it.
+    pub(crate) fn is_scenario_pinned(&self, scenario: &DebugScenario) -> bool {
+        self.pinned_scenarios
+            .iter()
+            .any(|pinned| pinned.label == scenario.label)
+    }
+
+    pub(crate) fn toggle_pinned_scenario(
+        &mut self,
+        scenario: &DebugScenario,
+        cx: &mut Context<Self>,
+    ) {
+        if self.is_scenario_pinned(scenario) {
+            self.pinned_scenarios
+                .retain(|pinned| pinned.label != scenario.label);
+        } else {
+            self.pinned_scenarios.push(scenario.clone());
+        }
+        cx.notify();
+
+        let Some(workspace_id) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        cx.background_spawn(persistence::serialize_pinned_scenarios(
+            workspace_id,
+            self.pinned_scenarios.clone(),
+        ))
+        .detach_and_log_err(cx);
+    }
+
+    fn record_session_start(
+        &mut self,
+        session: &Entity<Session>,
+        scenario: DebugScenario,
+        cx: &mut Context<Self>,
+    ) {
+        let session_id = session.read(cx).session_id();
+        self.session_history
+            .push(SessionHistoryEntry::new(scenario, session_id));
+        if self.session_history.len() > MAX_SESSION_HISTORY {
+            self.session_history.remove(0);
+        }
+        self.save_session_history(cx);
+
+        cx.subscribe(session, move |this, _, event: &SessionEvent, _| {
+            if let SessionEvent::Exited(event) = event {
+                if let Some(entry) = this.session_history_entry_mut(session_id) {
+                    entry.exit_code = i64::try_from(event.exit_code).ok();
+                }
+            }
+        })
+        .detach();
+        cx.subscribe(session, move |this, _, event: &SessionStateEvent, cx| {
+            if let SessionStateEvent::Shutdown = event {
+                if let Some(entry) = this.session_history_entry_mut(session_id) {
+                    entry.ended_at = Some(chrono::Utc::now());
+                    entry.session_id = None;
+                    this.save_session_history(cx);
+                }
+            }
+        })
+        .detach();
+    }
+
+    fn session_history_entry_mut(
+        &mut self,
+        session_id: SessionId,
+    ) -> Option<&mut SessionHistoryEntry> {
+        self.session_history
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.session_id == Some(session_id))
+    }
+
+    fn save_session_history(&self, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        cx.background_spawn(persistence::serialize_session_history(
+            workspace_id,
+            self.session_history.clone(),
+        ))
+        .detach_and_log_err(cx);
+    }
+
     pub(crate) async fn register_session(
         this: WeakEntity<Self>,
         session: Entity<Session>,
@@ -344,12 +875,16 @@ impl DebugPanel {
         let binary = curr_session.read(cx).binary().cloned().unwrap();
         let task = curr_session.update(cx, |session, cx| session.shutdown(cx));
         let task_context = curr_session.read(cx).task_context().clone();
+        let auto_restart_count = curr_session.read(cx).auto_restart_count();
 
         cx.spawn_in(window, async move |this, cx| {
             task.await;
 
             let (session, task) = dap_store_handle.update(cx, |dap_store, cx| {
                 let session = dap_store.new_session(label, adapter, task_context, None, cx);
+                session.update(cx, |session, _| {
+                    session.set_auto_restart_count(auto_restart_count)
+                });
 
                 let task = session.update(cx, |session, cx| {
                     session.boot(binary, worktree, dap_store_handle.downgrade(), cx)
@@ -426,6 +961,39 @@ impl DebugPanel {
         .detach_and_log_err(cx);
     }
 
+    /// Closes a session that ended once `close_ended_sessions_after_minutes` have passed.
+    fn close_ended_session_later(
+        &mut self,
+        session: &Entity<Session>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(minutes) = DebuggerSettings::get_global(cx).close_ended_sessions_after_minutes
+        else {
+            return;
+        };
+        let session_id = session.read(cx).session_id();
+        cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor()
+                .timer(Duration::from_secs(minutes * 60))
+                .await;
+            this.update_in(cx, |this, window, cx| {
+                // Sessions restarted in the meantime are running again.
+                let Some(debug_session) = this.sessions.iter().find(|session| {
+                    let session = session.read(cx).session(cx);
+                    let session = session.read(cx);
+                    session.session_id() == session_id && session.is_terminated()
+                }) else {
+                    return;
+                };
+                let entity_id = debug_session.entity_id();
+                this.close_session(entity_id, window, cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     pub(crate) fn close_session(
         &mut self,
         entity_id: EntityId,
@@ -528,15 +1096,65 @@ impl DebugPanel {
                 menu
             });
 
-            window.focus(&context_menu.focus_handle(cx));
-            let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
-                this.context_menu.take();
-                cx.notify();
-            });
-            self.context_menu = Some((context_menu, position, subscription));
+            self.set_context_menu(context_menu, position, window, cx);
         }
     }
 
+    /// Lists the ways the active session can be stopped, to pick what happens to the debuggee.
+    pub(crate) fn deploy_stop_menu(
+        &mut self,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(running_state) = self
+            .active_session
+            .as_ref()
+            .map(|session| session.read(cx).running_state().clone())
+        else {
+            return;
+        };
+        let session = running_state.read(cx).session().read(cx);
+        let stop_modes = [StopMode::Terminate, StopMode::Disconnect, StopMode::Suspend]
+            .map(|mode| (mode, session.supports_stop_mode(mode)));
+        let running_state = running_state.downgrade();
+
+        let context_menu = ContextMenu::build(window, cx, |mut menu, _window, _cx| {
+            for (mode, is_supported) in stop_modes {
+                let running_state = running_state.clone();
+                menu = menu.item(
+                    ContextMenuEntry::new(mode.menu_label())
+                        .disabled(!is_supported)
+                        .handler(move |_, cx| {
+                            running_state
+                                .update(cx, |running_state, cx| {
+                                    running_state.stop_with_mode(mode, cx);
+                                })
+                                .ok();
+                        }),
+                );
+            }
+            menu
+        });
+        self.set_context_menu(context_menu, position, window, cx);
+    }
+
+    fn set_context_menu(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        window.focus(&context_menu.focus_handle(cx));
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
     fn copy_debug_adapter_arguments(
         &mut self,
         _: &CopyDebugAdapterArguments,
@@ -567,12 +1185,20 @@ impl DebugPanel {
         let is_side = self.position(window, cx).axis() == gpui::Axis::Horizontal;
         let div = if is_side { v_flex() } else { h_flex() };
 
+        let coupled_session_id = active_session
+            .as_ref()
+            .map(|session| session.read(cx).session_id(cx))
+            .filter(|session_id| {
+                self.launch_groups
+                    .get(session_id)
+                    .is_some_and(|group| group.coupled)
+            });
+
+        let pinned_scenarios = self.pinned_scenarios.clone();
+        let this = cx.weak_entity();
         let new_session_button = || {
-            IconButton::new("debug-new-session", IconName::Plus)
+            let button = IconButton::new("debug-new-session", IconName::Plus)
                 .icon_size(IconSize::Small)
-                .on_click({
-                    move |_, window, cx| window.dispatch_action(crate::Start.boxed_clone(), cx)
-                })
                 .tooltip({
                     let focus_handle = focus_handle.clone();
                     move |window, cx| {
@@ -584,7 +1210,41 @@ impl DebugPanel {
                             cx,
                         )
                     }
+                });
+            if pinned_scenarios.is_empty() {
+                return button
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(crate::Start.boxed_clone(), cx)
+                    })
+                    .into_any_element();
+            }
+
+            let pinned_scenarios = pinned_scenarios.clone();
+            let this = this.clone();
+            PopoverMenu::new("debug-new-session-menu")
+                .trigger(button)
+                .menu(move |window, cx| {
+                    let pinned_scenarios = pinned_scenarios.clone();
+                    let this = this.clone();
+                    Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        menu = menu
+                            .action("New Session…", crate::Start.boxed_clone())
+                            .separator()
+                            .header("Favorites");
+                        for scenario in pinned_scenarios {
+                            let this = this.clone();
+                            menu = menu.entry(scenario.label.clone(), None, move |window, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.rerun_scenario(scenario.clone(), window, cx);
+                                })
+                                .ok();
+                            });
+                        }
+                        menu
+                    }))
                 })
+                .attach(gpui::Corner::BottomLeft)
+                .into_any_element()
         };
         let documentation_button = || {
             IconButton::new("debug-open-documentation", IconName::CircleHelp)
@@ -613,8 +1273,27 @@ impl DebugPanel {
                                             project::debugger::session::ThreadStatus::Exited,
                                         );
                                     let capabilities = running_state.read(cx).capabilities(cx);
+                                    let supports_step_back =
+                                        capabilities.supports_step_back.unwrap_or_default();
+                                    let supports_stepping_granularity = capabilities
+                                        .supports_stepping_granularity
+                                        .unwrap_or_default();
                                     let supports_detach =
                                         running_state.read(cx).session().read(cx).is_attached();
+                                    let is_replay =
+                                        running_state.read(cx).session().read(cx).is_replay();
+                                    let is_recording = running_state
+                                        .read(cx)
+                                        .session()
+                                        .read(cx)
+                                        .recording_path()
+                                        .is_some();
+                                    let supports_hot_reload = running_state
+                                        .read(cx)
+                                        .session()
+                                        .read(cx)
+                                        .hot_reload_request(HotReloadKind::Reload, cx)
+                                        .is_some();
                                     this.map(|this| {
                                         if thread_status == ThreadStatus::Running {
                                             this.child(
@@ -746,7 +1425,95 @@ impl DebugPanel {
                                             }
                                         }),
                                     )
+                                    .when(supports_stepping_granularity, |div| {
+                                        div.child(stepping_granularity_menu(
+                                            &running_state,
+                                            &focus_handle,
+                                            cx,
+                                        ))
+                                    })
+                                    .when(supports_step_back, |div| {
+                                        div.child(
+                                            IconButton::new(
+                                                "debug-step-back",
+                                                IconName::DebugStepBack,
+                                            )
+                                            .icon_size(IconSize::XSmall)
+                                            .shape(ui::IconButtonShape::Square)
+                                            .on_click(window.listener_for(
+                                                &running_state,
+                                                |this, _, _window, cx| {
+                                                    this.step_back(cx);
+                                                },
+                                            ))
+                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .tooltip({
+                                                let focus_handle = focus_handle.clone();
+                                                move |window, cx| {
+                                                    Tooltip::for_action_in(
+                                                        "Step back",
+                                                        &StepBack,
+                                                        &focus_handle,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                }
+                                            }),
+                                        )
+                                        .child(
+                                            IconButton::new(
+                                                "debug-reverse-continue",
+                                                IconName::RotateCcw,
+                                            )
+                                            .icon_size(IconSize::XSmall)
+                                            .shape(ui::IconButtonShape::Square)
+                                            .on_click(window.listener_for(
+                                                &running_state,
+                                                |this, _, _window, cx| {
+                                                    this.reverse_continue(cx);
+                                                },
+                                            ))
+                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .tooltip({
+                                                let focus_handle = focus_handle.clone();
+                                                move |window, cx| {
+                                                    Tooltip::for_action_in(
+                                                        "Reverse continue",
+                                                        &ReverseContinue,
+                                                        &focus_handle,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                }
+                                            }),
+                                        )
+                                    })
                                     .child(Divider::vertical())
+                                    .when(supports_hot_reload, |div| {
+                                        div.child(
+                                            IconButton::new("debug-hot-reload", IconName::Bolt)
+                                                .icon_size(IconSize::XSmall)
+                                                .on_click(window.listener_for(
+                                                    &running_state,
+                                                    |this, _, _window, cx| {
+                                                        this.hot_reload(HotReloadKind::Reload, cx);
+                                                    },
+                                                ))
+                                                .disabled(thread_status == ThreadStatus::Exited)
+                                                .tooltip({
+                                                    let focus_handle = focus_handle.clone();
+                                                    move |window, cx| {
+                                                        Tooltip::for_action_in(
+                                                            "Hot Reload",
+                                                            &HotReload,
+                                                            &focus_handle,
+                                                            window,
+                                                            cx,
+                                                        )
+                                                    }
+                                                }),
+                                        )
+                                    })
                                     .child(
                                         IconButton::new("debug-restart", IconName::DebugRestart)
                                             .icon_size(IconSize::XSmall)
@@ -774,8 +1541,17 @@ impl DebugPanel {
                                             .icon_size(IconSize::XSmall)
                                             .on_click(window.listener_for(
                                                 &running_state,
-                                                |this, _, _window, cx| {
-                                                    this.stop_thread(cx);
+                                                |this, _, window, cx| {
+                                                    this.stop_thread(window, cx);
+                                                },
+                                            ))
+                                            .on_right_click(cx.listener(
+                                                |this, event: &ClickEvent, window, cx| {
+                                                    this.deploy_stop_menu(
+                                                        event.down.position,
+                                                        window,
+                                                        cx,
+                                                    );
                                                 },
                                             ))
                                             .disabled(
@@ -790,7 +1566,7 @@ impl DebugPanel {
                                                 {
                                                     "Terminate Thread"
                                                 } else {
-                                                    "Terminate All Threads"
+                                                    "Stop Debugging"
                                                 };
                                                 move |window, cx| {
                                                     Tooltip::for_action_in(
@@ -803,40 +1579,86 @@ impl DebugPanel {
                                                 }
                                             }),
                                     )
-                                    .when(
-                                        supports_detach,
-                                        |div| {
-                                            div.child(
-                                                IconButton::new(
-                                                    "debug-disconnect",
-                                                    IconName::DebugDetach,
-                                                )
-                                                .disabled(
-                                                    thread_status != ThreadStatus::Stopped
-                                                        && thread_status != ThreadStatus::Running,
-                                                )
+                                    .when_some(coupled_session_id, |div, session_id| {
+                                        div.child(
+                                            IconButton::new("debug-stop-coupled", IconName::Stop)
                                                 .icon_size(IconSize::XSmall)
-                                                .on_click(window.listener_for(
-                                                    &running_state,
-                                                    |this, _, _, cx| {
-                                                        this.detach_client(cx);
-                                                    },
-                                                ))
+                                                .on_click(cx.listener(move |this, _, _, cx| {
+                                                    this.stop_coupled_sessions(session_id, cx);
+                                                }))
+                                                .tooltip(Tooltip::text("Stop Coupled Sessions")),
+                                        )
+                                    })
+                                    .when(supports_detach, |div| {
+                                        div.child(
+                                            IconButton::new(
+                                                "debug-disconnect",
+                                                IconName::DebugDetach,
+                                            )
+                                            .disabled(
+                                                thread_status != ThreadStatus::Stopped
+                                                    && thread_status != ThreadStatus::Running,
+                                            )
+                                            .icon_size(IconSize::XSmall)
+                                            .on_click(window.listener_for(
+                                                &running_state,
+                                                |this, _, _, cx| {
+                                                    this.detach_client(cx);
+                                                },
+                                            ))
+                                            .on_right_click(cx.listener(
+                                                |this, event: &ClickEvent, window, cx| {
+                                                    this.deploy_stop_menu(
+                                                        event.down.position,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                },
+                                            ))
+                                            .tooltip({
+                                                let focus_handle = focus_handle.clone();
+                                                move |window, cx| {
+                                                    Tooltip::for_action_in(
+                                                        "Detach",
+                                                        &Detach,
+                                                        &focus_handle,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                }
+                                            }),
+                                        )
+                                    })
+                                    .when(!is_replay, |div| {
+                                        div.child(
+                                            IconButton::new("debug-record", IconName::Circle)
+                                                .icon_size(IconSize::XSmall)
+                                                .toggle_state(is_recording)
+                                                .when(is_recording, |button| {
+                                                    button.icon_color(Color::Error)
+                                                })
+                                                .on_click(cx.listener(|this, _, window, cx| {
+                                                    this.toggle_recording(window, cx);
+                                                }))
                                                 .tooltip({
                                                     let focus_handle = focus_handle.clone();
+                                                    let label = if is_recording {
+                                                        "Stop Recording"
+                                                    } else {
+                                                        "Record DAP Traffic"
+                                                    };
                                                     move |window, cx| {
                                                         Tooltip::for_action_in(
-                                                            "Detach",
-                                                            &Detach,
+                                                            label,
+                                                            &ToggleSessionRecording,
                                                             &focus_handle,
                                                             window,
                                                             cx,
                                                         )
                                                     }
                                                 }),
-                                            )
-                                        },
-                                    )
+                                        )
+                                    })
                                 },
                             ),
                         )
@@ -878,6 +1700,7 @@ impl DebugPanel {
                                             )
                                         })
                                     })
+                                    .children(session_progress(&running_state, cx))
                                     .when(!is_side, |this| this.gap_2().child(Divider::vertical()))
                                 },
                             ),
@@ -899,6 +1722,73 @@ impl DebugPanel {
         )
     }
 
+    /// One tab per session, so switching between parallel sessions is a single click. Child
+    /// sessions follow their parent, marked with a tree guide.
+    fn render_session_tabs(&self, cx: &mut Context<Self>) -> Option<TabBar> {
+        if self.sessions.len() < 2 {
+            return None;
+        }
+
+        let active_ix = self
+            .sessions
+            .iter()
+            .position(|session| Some(session) == self.active_session.as_ref())
+            .unwrap_or_default();
+        let last_ix = self.sessions.len() - 1;
+
+        let tabs = self
+            .session_tree(cx)
+            .into_iter()
+            .enumerate()
+            .map(|(ix, (session, node))| {
+                let entity_id = session.entity_id();
+                let debug_session = session.read(cx);
+                let is_terminated = debug_session
+                    .running_state()
+                    .read(cx)
+                    .session()
+                    .read(cx)
+                    .is_terminated();
+
+                Tab::new(("debug-session-tab", ix))
+                    .position(if ix == 0 {
+                        TabPosition::First
+                    } else if ix == last_ix {
+                        TabPosition::Last
+                    } else {
+                        TabPosition::Middle(ix.cmp(&active_ix))
+                    })
+                    .toggle_state(ix == active_ix)
+                    .start_slot(
+                        h_flex()
+                            .gap_1()
+                            .children(node.guide())
+                            .child(debug_session.status_icon(cx)),
+                    )
+                    .end_slot(
+                        IconButton::new(("close-debug-session-tab", ix), IconName::Close)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Close Session"))
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.close_session(entity_id, window, cx);
+                            })),
+                    )
+                    .on_click(cx.listener({
+                        let session = session.clone();
+                        move |this, _, window, cx| {
+                            this.activate_session(session.clone(), window, cx);
+                        }
+                    }))
+                    .child(
+                        Label::new(debug_session.label(cx))
+                            .size(LabelSize::Small)
+                            .when(is_terminated, |this| this.strikethrough()),
+                    )
+            });
+
+        Some(TabBar::new("debug-session-tabs").children(tabs))
+    }
+
     pub(crate) fn activate_pane_in_direction(
         &mut self,
         direction: SplitDirection,
@@ -929,6 +1819,22 @@ impl DebugPanel {
         }
     }
 
+    /// Opens the disassembly of the active session, adding its pane if it was closed.
+    fn show_disassembly(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(running_state) = self
+            .active_session()
+            .map(|session| session.read(cx).running_state().clone())
+        else {
+            return;
+        };
+        running_state.update(cx, |state, cx| {
+            if DebuggerPaneItem::Disassembly.is_supported(&state.capabilities(cx)) {
+                state.ensure_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+                state.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+            }
+        });
+    }
+
     pub(crate) fn activate_session_by_id(
         &mut self,
         session_id: SessionId,
@@ -1115,6 +2021,9 @@ async fn register_session_inner(
                 SessionStateEvent::SpawnChildSession { request } => {
                     this.handle_start_debugging_request(request, session.clone(), window, cx);
                 }
+                SessionStateEvent::Shutdown => {
+                    this.close_ended_session_later(session, window, cx);
+                }
                 _ => {}
             },
         )
@@ -1172,6 +2081,140 @@ async fn register_session_inner(
     Ok(debug_session)
 }
 
+fn stepping_granularity_label(granularity: SteppingGranularity) -> &'static str {
+    match granularity {
+        SteppingGranularity::Statement => "Statement",
+        SteppingGranularity::Line => "Line",
+        SteppingGranularity::Instruction => "Instruction",
+    }
+}
+
+/// The operation the adapter most recently reported progress for, e.g. "Loading symbols… 40%".
+fn session_progress(running_state: &Entity<RunningState>, cx: &App) -> Option<impl IntoElement> {
+    let session = running_state.read(cx).session().clone();
+    let (label, message, other_count, cancellable_progress_id) = {
+        let session = session.read(cx);
+        if session.is_terminated() {
+            return None;
+        }
+        let progress = session.progress().last()?;
+        let mut label = format!("{}…", progress.title);
+        if let Some(percentage) = &progress.percentage {
+            label.push_str(&format!(" {percentage}%"));
+        }
+        (
+            label,
+            progress.message.clone(),
+            session.progress().count() - 1,
+            session
+                .can_cancel_progress(&progress.progress_id)
+                .then(|| progress.progress_id.clone()),
+        )
+    };
+
+    Some(
+        h_flex()
+            .id("debug-session-progress")
+            .gap_1()
+            .child(
+                Icon::new(IconName::ArrowCircle)
+                    .size(IconSize::XSmall)
+                    .color(Color::Muted)
+                    .with_animation(
+                        "debug-session-progress-spinner",
+                        Animation::new(Duration::from_secs(2)).repeat(),
+                        |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+                    ),
+            )
+            .child(
+                Label::new(label)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .truncate(),
+            )
+            .when(other_count > 0, |this| {
+                this.child(
+                    Label::new(format!("+{other_count}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .when_some(message, |this, message| {
+                this.tooltip(Tooltip::text(message))
+            })
+            .when_some(cancellable_progress_id, |this, progress_id| {
+                this.child(
+                    IconButton::new("debug-cancel-progress", IconName::Close)
+                        .icon_size(IconSize::XSmall)
+                        .tooltip(Tooltip::text("Cancel"))
+                        .on_click(move |_, _, cx| {
+                            session.update(cx, |session, cx| {
+                                session.cancel_progress(progress_id.clone(), cx)
+                            });
+                        }),
+                )
+            }),
+    )
+}
+
+/// Picks how far Step Over, Step Into and Step Out go in the active session.
+fn stepping_granularity_menu(
+    running_state: &Entity<RunningState>,
+    focus_handle: &FocusHandle,
+    cx: &App,
+) -> impl IntoElement {
+    let current = running_state.read(cx).stepping_granularity(cx);
+    let running_state = running_state.downgrade();
+    PopoverMenu::new("debug-stepping-granularity")
+        .trigger_with_tooltip(
+            Button::new(
+                "debug-stepping-granularity-trigger",
+                stepping_granularity_label(current),
+            )
+            .label_size(LabelSize::Small)
+            .color(Color::Muted),
+            {
+                let focus_handle = focus_handle.clone();
+                move |window, cx| {
+                    Tooltip::for_action_in(
+                        "Stepping Granularity",
+                        &CycleSteppingGranularity,
+                        &focus_handle,
+                        window,
+                        cx,
+                    )
+                }
+            },
+        )
+        .menu(move |window, cx| {
+            let running_state = running_state.clone();
+            Some(ContextMenu::build(window, cx, move |mut menu, _, _| {
+                for granularity in [
+                    SteppingGranularity::Statement,
+                    SteppingGranularity::Line,
+                    SteppingGranularity::Instruction,
+                ] {
+                    let running_state = running_state.clone();
+                    menu = menu.toggleable_entry(
+                        stepping_granularity_label(granularity),
+                        granularity == current,
+                        IconPosition::End,
+                        None,
+                        move |_, cx| {
+                            running_state
+                                .update(cx, |state, cx| {
+                                    state.set_stepping_granularity(granularity, cx)
+                                })
+                                .ok();
+                        },
+                    );
+                }
+                menu
+            }))
+        })
+        .attach(gpui::Corner::BottomLeft)
+}
+
 impl EventEmitter<PanelEvent> for DebugPanel {}
 impl EventEmitter<DebugPanelEvent> for DebugPanel {}
 
@@ -1295,6 +2338,7 @@ impl Render for DebugPanel {
             .size_full()
             .key_context("DebugPanel")
             .child(h_flex().children(self.top_controls_strip(window, cx)))
+            .children(self.render_session_tabs(cx))
             .track_focus(&self.focus_handle(cx))
             .on_action({
                 let this = this.clone();
@@ -1386,6 +2430,24 @@ impl Render for DebugPanel {
                     .ok();
                 }
             })
+            .on_action({
+                let this = this.clone();
+                move |_: &FocusRegisters, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.activate_item(DebuggerPaneItem::Registers, window, cx);
+                    })
+                    .ok();
+                }
+            })
+            .on_action({
+                let this = this.clone();
+                move |_: &ShowDisassembly, window, cx| {
+                    this.update(cx, |this, cx| {
+                        this.show_disassembly(window, cx);
+                    })
+                    .ok();
+                }
+            })
             .on_action({
                 let this = this.clone();
                 move |_: &FocusTerminal, window, cx| {
@@ -1,29 +1,46 @@
-use std::any::TypeId;
+use std::{any::TypeId, ops::Range};
 
-use dap::debugger_settings::DebuggerSettings;
+use adapter_versions_modal::AdapterVersionsModal;
+use check_adapters_modal::CheckAdaptersModal;
+use dap::{EvaluateArgumentsContext, debugger_settings::DebuggerSettings};
 use debugger_panel::DebugPanel;
 use editor::Editor;
+use floating_controls::FloatingControls;
 use gpui::{App, DispatchPhase, EntityInputHandler, actions};
+use language::{BufferSnapshot, ToPoint as _};
 use new_process_modal::{NewProcessModal, NewProcessMode};
 use onboarding_modal::DebuggerOnboardingModal;
-use project::debugger::{self, breakpoint_store::SourceBreakpoint, session::ThreadStatus};
+use project::debugger::{
+    self,
+    breakpoint_store::SourceBreakpoint,
+    session::{HotReloadKind, ThreadStatus},
+};
 use session::DebugSession;
+use session::running::instruction_breakpoint_modal::InstructionBreakpointModal;
+use session::running::quick_watch::QuickWatchModal;
+use session_history::SessionHistoryModal;
+use session_info::{CapabilitiesModal, SessionInfoModal};
 use settings::Settings;
 use stack_trace_view::StackTraceView;
 use tasks_ui::{Spawn, TaskOverrides};
 use ui::{FluentBuilder, InteractiveElement};
 use util::maybe;
-use workspace::{ItemHandle, ShutdownDebugAdapters, Workspace};
+use workspace::{ItemHandle, ShutdownDebugAdapters, Workspace, notifications::DetachAndPromptErr};
 use zed_actions::ToggleFocus;
 use zed_actions::debugger::OpenOnboardingModal;
 
+mod adapter_versions_modal;
 pub mod attach_modal;
+mod check_adapters_modal;
 pub mod debugger_panel;
 mod dropdown_menus;
+mod floating_controls;
 mod new_process_modal;
 mod onboarding_modal;
 mod persistence;
 pub(crate) mod session;
+mod session_history;
+mod session_info;
 mod stack_trace_view;
 
 #[cfg(any(test, feature = "test-support"))]
@@ -37,10 +54,14 @@ actions!(
         Detach,
         Pause,
         Restart,
+        HotReload,
+        HotRestart,
         StepInto,
         StepOver,
         StepOut,
         StepBack,
+        ReverseContinue,
+        CycleSteppingGranularity,
         Stop,
         ToggleIgnoreBreakpoints,
         ClearAllBreakpoints,
@@ -50,12 +71,29 @@ actions!(
         FocusFrames,
         FocusModules,
         FocusLoadedSources,
+        FocusRegisters,
+        ShowDisassembly,
+        AddInstructionBreakpoint,
         FocusTerminal,
         ShowStackTrace,
         ToggleThreadPicker,
         ToggleSessionPicker,
         RerunLastSession,
+        EditAndRerunLastSession,
+        OpenSessionHistory,
+        ShowSessionInfo,
+        ShowCapabilities,
+        ManageAdapters,
+        CheckAdapters,
+        ToggleSessionRecording,
+        ReplaySessionRecording,
         ToggleExpandItem,
+        QuickWatch,
+        ClearConsole,
+        FrameUp,
+        FrameDown,
+        FreezeThread,
+        ThawThread,
     ]
 );
 
@@ -85,6 +123,56 @@ pub fn init(cx: &mut App) {
                     })
                 },
             )
+            .register_action(
+                |workspace: &mut Workspace, _: &EditAndRerunLastSession, window, cx| {
+                    let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
+                        return;
+                    };
+
+                    debug_panel.update(cx, |debug_panel, cx| {
+                        debug_panel.edit_and_rerun_last_session(workspace, window, cx);
+                    })
+                },
+            )
+            .register_action(
+                |workspace: &mut Workspace, _: &OpenSessionHistory, window, cx| {
+                    SessionHistoryModal::show(workspace, window, cx);
+                },
+            )
+            .register_action(|workspace: &mut Workspace, _: &ShowSessionInfo, window, cx| {
+                SessionInfoModal::show(workspace, window, cx);
+            })
+            .register_action(|workspace: &mut Workspace, _: &ShowCapabilities, window, cx| {
+                CapabilitiesModal::show(workspace, window, cx);
+            })
+            .register_action(|workspace: &mut Workspace, _: &ManageAdapters, window, cx| {
+                AdapterVersionsModal::show(workspace, window, cx);
+            })
+            .register_action(|workspace: &mut Workspace, _: &CheckAdapters, window, cx| {
+                CheckAdaptersModal::show(workspace, window, cx);
+            })
+            .register_action(
+                |workspace: &mut Workspace, _: &ToggleSessionRecording, window, cx| {
+                    let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
+                        return;
+                    };
+
+                    debug_panel.update(cx, |debug_panel, cx| {
+                        debug_panel.toggle_recording(window, cx);
+                    })
+                },
+            )
+            .register_action(
+                |workspace: &mut Workspace, _: &ReplaySessionRecording, window, cx| {
+                    let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
+                        return;
+                    };
+
+                    debug_panel.update(cx, |debug_panel, cx| {
+                        debug_panel.replay_recording(window, cx);
+                    })
+                },
+            )
             .register_action(
                 |workspace: &mut Workspace, _: &ShutdownDebugAdapters, _window, cx| {
                     workspace.project().update(cx, |project, cx| {
@@ -97,6 +185,51 @@ pub fn init(cx: &mut App) {
             .register_action(|workspace, _: &OpenOnboardingModal, window, cx| {
                 DebuggerOnboardingModal::toggle(workspace, window, cx)
             })
+            .register_action(|workspace: &mut Workspace, _: &ClearConsole, window, cx| {
+                let Some(console) = workspace
+                    .panel::<DebugPanel>(cx)
+                    .and_then(|panel| panel.read(cx).active_session())
+                    .map(|session| session.read(cx).running_state().read(cx).console().clone())
+                else {
+                    return;
+                };
+                console.update(cx, |console, cx| console.clear(window, cx));
+            })
+            .register_action(
+                |workspace: &mut Workspace, _: &AddInstructionBreakpoint, window, cx| {
+                    let Some(session) = workspace
+                        .panel::<DebugPanel>(cx)
+                        .and_then(|panel| panel.read(cx).active_session())
+                        .map(|session| session.read(cx).session(cx))
+                    else {
+                        return;
+                    };
+                    if !session.read(cx).supports_instruction_breakpoints() {
+                        return;
+                    }
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        InstructionBreakpointModal::new(session, window, cx)
+                    });
+                },
+            )
+            .register_action(|workspace: &mut Workspace, _: &QuickWatch, window, cx| {
+                let Some(running_state) = workspace
+                    .panel::<DebugPanel>(cx)
+                    .and_then(|panel| panel.read(cx).active_session())
+                    .map(|session| session.read(cx).running_state().clone())
+                else {
+                    return;
+                };
+                let running_state = running_state.read(cx);
+                let session = running_state.session().clone();
+                let stack_frame_id = running_state.selected_stack_frame_id(cx);
+                let expression = workspace.active_item_as::<Editor>(cx).and_then(|editor| {
+                    editor.update(cx, |editor, cx| watch_expression_for_editor(editor, cx))
+                });
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    QuickWatchModal::new(session, stack_frame_id, expression, window, cx)
+                });
+            })
             .register_action_renderer(|div, workspace, _, cx| {
                 let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
                     return div;
@@ -115,7 +248,21 @@ pub fn init(cx: &mut App) {
 
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
+                let supports_stepping_granularity =
+                    caps.supports_stepping_granularity.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
+                let supports_freezing_threads =
+                    running_state.session().read(cx).supports_freezing_threads();
+                let supports_hot_reload = running_state
+                    .session()
+                    .read(cx)
+                    .hot_reload_request(HotReloadKind::Reload, cx)
+                    .is_some();
+                let supports_hot_restart = running_state
+                    .session()
+                    .read(cx)
+                    .hot_reload_request(HotReloadKind::Restart, cx)
+                    .is_some();
                 let status = running_state.thread_status(cx);
 
                 let active_item = active_item.downgrade();
@@ -146,10 +293,42 @@ pub fn init(cx: &mut App) {
                             active_item.update(cx, |item, cx| item.step_out(cx)).ok();
                         }
                     })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &FrameUp, window, cx| {
+                            active_item
+                                .update(cx, |item, cx| {
+                                    item.stack_frame_list().update(cx, |list, cx| {
+                                        list.select_adjacent_frame(true, window, cx)
+                                    })
+                                })
+                                .ok();
+                        }
+                    })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &FrameDown, window, cx| {
+                            active_item
+                                .update(cx, |item, cx| {
+                                    item.stack_frame_list().update(cx, |list, cx| {
+                                        list.select_adjacent_frame(false, window, cx)
+                                    })
+                                })
+                                .ok();
+                        }
+                    })
                     .when(supports_step_back, |div| {
                         let active_item = active_item.clone();
-                        div.on_action(move |_: &StepBack, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepBack, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                            }
+                        })
+                        .on_action(move |_: &ReverseContinue, _, cx| {
+                            active_item
+                                .update(cx, |item, cx| item.reverse_continue(cx))
+                                .ok();
                         })
                     })
                     .on_action({
@@ -194,6 +373,28 @@ pub fn init(cx: &mut App) {
                         },
                     ))
                 })
+                .when(supports_freezing_threads, |div| {
+                    div.on_action({
+                        let active_item = active_item.clone();
+                        move |_: &FreezeThread, _, cx| {
+                            active_item.update(cx, |item, cx| item.freeze_thread(cx)).ok();
+                        }
+                    })
+                    .on_action({
+                        let active_item = active_item.clone();
+                        move |_: &ThawThread, _, cx| {
+                            active_item.update(cx, |item, cx| item.thaw_thread(cx)).ok();
+                        }
+                    })
+                })
+                .when(supports_stepping_granularity, |div| {
+                    let active_item = active_item.clone();
+                    div.on_action(move |_: &CycleSteppingGranularity, _, cx| {
+                        active_item
+                            .update(cx, |item, cx| item.cycle_stepping_granularity(cx))
+                            .ok();
+                    })
+                })
                 .when(supports_detach, |div| {
                     let active_item = active_item.clone();
                     div.on_action(move |_: &Detach, _, cx| {
@@ -202,6 +403,22 @@ pub fn init(cx: &mut App) {
                             .ok();
                     })
                 })
+                .when(supports_hot_reload, |div| {
+                    let active_item = active_item.clone();
+                    div.on_action(move |_: &HotReload, _, cx| {
+                        active_item
+                            .update(cx, |item, cx| item.hot_reload(HotReloadKind::Reload, cx))
+                            .ok();
+                    })
+                })
+                .when(supports_hot_restart, |div| {
+                    let active_item = active_item.clone();
+                    div.on_action(move |_: &HotRestart, _, cx| {
+                        active_item
+                            .update(cx, |item, cx| item.hot_reload(HotReloadKind::Restart, cx))
+                            .ok();
+                    })
+                })
                 .on_action({
                     let active_item = active_item.clone();
                     move |_: &Restart, _, cx| {
@@ -212,8 +429,10 @@ pub fn init(cx: &mut App) {
                 })
                 .on_action({
                     let active_item = active_item.clone();
-                    move |_: &Stop, _, cx| {
-                        active_item.update(cx, |item, cx| item.stop_thread(cx)).ok();
+                    move |_: &Stop, window, cx| {
+                        active_item
+                            .update(cx, |item, cx| item.stop_thread(window, cx))
+                            .ok();
                     }
                 })
                 .on_action({
@@ -229,7 +448,11 @@ pub fn init(cx: &mut App) {
     .detach();
 
     cx.observe_new({
-        move |editor: &mut Editor, _, _| {
+        move |editor: &mut Editor, _, cx| {
+            if editor.mode().is_full() {
+                let floating_controls = FloatingControls::new(editor, cx);
+                editor.register_addon(floating_controls);
+            }
             editor
                 .register_action_renderer(move |editor, window, cx| {
                     let Some(workspace) = editor.workspace() else {
@@ -276,6 +499,7 @@ pub fn init(cx: &mut App) {
                                     message: None,
                                     condition: None,
                                     hit_condition: None,
+                                    note: None,
                                     state: debugger::breakpoint_store::BreakpointState::Enabled,
                                 };
 
@@ -298,30 +522,211 @@ pub fn init(cx: &mut App) {
                         }
                     });
 
+                    let supports_goto = active_session
+                        .read(cx)
+                        .session(cx)
+                        .read(cx)
+                        .supports_goto();
+                    if supports_goto {
+                        window.on_action(TypeId::of::<editor::actions::JumpToCursor>(), {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, window, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (buffer, position, _) = editor
+                                        .update(cx, |editor, cx| {
+                                            let cursor_point: language::Point =
+                                                editor.selections.newest(cx).head();
+
+                                            editor
+                                                .buffer()
+                                                .read(cx)
+                                                .point_to_buffer_point(cursor_point, cx)
+                                        })
+                                        .ok()??;
+
+                                    let path =
+                                    debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
+                                        &buffer, cx,
+                                    )?;
+
+                                    let task = active_session.update(cx, |session, cx| {
+                                        session.running_state().update(cx, |state, cx| {
+                                            let thread_id = state.selected_thread_id()?;
+                                            Some(state.session().update(cx, |session, cx| {
+                                                session.jump_to_position(
+                                                    &path,
+                                                    position.row,
+                                                    thread_id,
+                                                    cx,
+                                                )
+                                            }))
+                                        })
+                                    })?;
+                                    task.detach_and_prompt_err(
+                                        "Failed to jump to line",
+                                        window,
+                                        cx,
+                                        |error, _, _| Some(error.to_string()),
+                                    );
+
+                                    Some(())
+                                });
+                            }
+                        });
+                    }
+
+                    let supports_disassemble = active_session
+                        .read(cx)
+                        .session(cx)
+                        .read(cx)
+                        .capabilities()
+                        .supports_disassemble_request
+                        .unwrap_or_default();
+                    if supports_disassemble {
+                        window.on_action(TypeId::of::<editor::actions::OpenDisassembly>(), {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, window, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (buffer, position, _) = editor
+                                        .update(cx, |editor, cx| {
+                                            let cursor_point: language::Point =
+                                                editor.selections.newest(cx).head();
+
+                                            editor
+                                                .buffer()
+                                                .read(cx)
+                                                .point_to_buffer_point(cursor_point, cx)
+                                        })
+                                        .ok()??;
+
+                                    let path =
+                                    debugger::breakpoint_store::BreakpointStore::abs_path_from_buffer(
+                                        &buffer, cx,
+                                    )?;
+                                    let rows = enclosing_function_rows(
+                                        &buffer.read(cx).snapshot(),
+                                        position,
+                                    );
+
+                                    let task = active_session.update(cx, |session, cx| {
+                                        session.running_state().update(cx, |state, cx| {
+                                            state.open_disassembly_at(&path, rows, window, cx)
+                                        })
+                                    });
+                                    task.detach_and_prompt_err(
+                                        "Failed to open disassembly",
+                                        window,
+                                        cx,
+                                        |error, _, _| Some(error.to_string()),
+                                    );
+
+                                    Some(())
+                                });
+                            }
+                        });
+                    }
+
+                    window.on_action(TypeId::of::<editor::actions::AddToWatch>(), {
+                        let editor = editor.clone();
+                        let active_session = active_session.clone();
+                        move |_, phase, window, cx| {
+                            if phase != DispatchPhase::Bubble {
+                                return;
+                            }
+                            maybe!({
+                                let expression = editor
+                                    .update(cx, |editor, cx| {
+                                        watch_expression_for_editor(editor, cx)
+                                    })
+                                    .ok()??;
+
+                                active_session.update(cx, |session, cx| {
+                                    session.running_state().update(cx, |state, cx| {
+                                        state.add_watch(expression, window, cx);
+                                    });
+                                });
+
+                                Some(())
+                            });
+                        }
+                    });
+
+                    window.on_action(
+                        TypeId::of::<editor::actions::EvaluateSelectedTextInline>(),
+                        {
+                            let editor = editor.clone();
+                            let active_session = active_session.clone();
+                            move |_, phase, _, cx| {
+                                if phase != DispatchPhase::Bubble {
+                                    return;
+                                }
+                                maybe!({
+                                    let (expression, position) = editor
+                                        .update(cx, |editor, cx| {
+                                            let position = editor.selections.newest_anchor().end;
+                                            let expression =
+                                                watch_expression_for_editor(editor, cx)?;
+                                            Some((expression, position))
+                                        })
+                                        .ok()??;
+
+                                    let running_state =
+                                        active_session.read(cx).running_state().read(cx);
+                                    let evaluation =
+                                        running_state.session().read(cx).evaluate_silently(
+                                            expression,
+                                            Some(EvaluateArgumentsContext::Watch),
+                                            running_state.selected_stack_frame_id(cx),
+                                        );
+                                    let editor = editor.clone();
+                                    cx.spawn(async move |cx| {
+                                        let (value, is_error) = match evaluation.await {
+                                            Ok(response) => (response.result, false),
+                                            Err(error) => (error.to_string(), true),
+                                        };
+                                        editor
+                                            .update(cx, |editor, cx| {
+                                                editor.show_evaluation_result(
+                                                    position,
+                                                    value.into(),
+                                                    is_error,
+                                                    cx,
+                                                )
+                                            })
+                                            .ok();
+                                    })
+                                    .detach();
+
+                                    Some(())
+                                });
+                            }
+                        },
+                    );
+
                     window.on_action(
                         TypeId::of::<editor::actions::EvaluateSelectedText>(),
-                        move |_, _, window, cx| {
+                        move |_, phase, window, cx| {
+                            if phase != DispatchPhase::Bubble {
+                                return;
+                            }
                             maybe!({
-                                let text = editor
+                                let expression = editor
                                     .update(cx, |editor, cx| {
-                                        editor.text_for_range(
-                                            editor.selections.newest(cx).range(),
-                                            &mut None,
-                                            window,
-                                            cx,
-                                        )
+                                        watch_expression_for_editor(editor, cx)
                                     })
                                     .ok()??;
 
                                 active_session.update(cx, |session, cx| {
                                     session.running_state().update(cx, |state, cx| {
-                                        let stack_id = state.selected_stack_frame_id(cx);
-
-                                        state.session().update(cx, |session, cx| {
-                                            session
-                                                .evaluate(text, None, stack_id, None, cx)
-                                                .detach();
-                                        });
+                                        state.evaluate_in_console(expression, window, cx);
                                     });
                                 });
 
@@ -336,6 +741,70 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+/// The selected text, or the expression under the cursor when nothing is selected.
+fn watch_expression_for_editor(
+    editor: &mut Editor,
+    cx: &mut ui::Context<Editor>,
+) -> Option<String> {
+    let selection = editor.selections.newest::<usize>(cx);
+    let buffer = editor.buffer().read(cx);
+    if !selection.is_empty() {
+        return Some(
+            buffer
+                .snapshot(cx)
+                .text_for_range(selection.range())
+                .collect(),
+        );
+    }
+    let (buffer, offset) = buffer.point_to_buffer_offset(selection.head(), cx)?;
+    watch_expression_at(&buffer.read(cx).snapshot(), offset)
+}
+
+/// Finds the identifier at `offset` using the buffer's syntax tree, along with the member
+/// accesses leading up to it, so that the cursor on `config` in `self.config.name` yields
+/// `self.config`.
+pub(crate) fn watch_expression_at(buffer: &BufferSnapshot, offset: usize) -> Option<String> {
+    let mut node = buffer.syntax_ancestor(offset..offset)?;
+    let kind = node.kind();
+    if !kind.ends_with("identifier") && !matches!(kind, "self" | "this") {
+        return None;
+    }
+    while let Some(parent) = node.parent() {
+        let is_member_access = matches!(
+            parent.kind(),
+            "field_expression"
+                | "member_expression"
+                | "attribute"
+                | "selector_expression"
+                | "scoped_identifier"
+                | "qualified_identifier"
+        );
+        if !is_member_access || parent.end_byte() != node.end_byte() {
+            break;
+        }
+        node = parent;
+    }
+    Some(buffer.text_for_range(node.byte_range()).collect())
+}
+
+/// The rows of the innermost outline item, such as a function, containing `position`, or just
+/// its row when there's none.
+pub(crate) fn enclosing_function_rows(
+    buffer: &BufferSnapshot,
+    position: language::Point,
+) -> Range<u32> {
+    buffer
+        .outline_items_containing(position..position, false, None)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| item.range.start.to_point(buffer)..item.range.end.to_point(buffer))
+        .filter(|range| range.start <= position && position <= range.end)
+        .min_by_key(|range| range.end.row - range.start.row)
+        .map_or(position.row..position.row + 1, |range| {
+            range.start.row..range.end.row + 1
+        })
+}
+
 fn spawn_task_or_modal(
     workspace: &mut Workspace,
     action: &Spawn,
@@ -1,11 +1,19 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
-use collections::HashMap;
-use gpui::{Animation, AnimationExt as _, Entity, Transformation, percentage};
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{
+    Action as _, Animation, AnimationExt as _, Corner, DismissEvent, Entity, Task, Transformation,
+    percentage,
+};
+use picker::{Picker, PickerDelegate};
 use project::debugger::session::{ThreadId, ThreadStatus};
-use ui::{ContextMenu, DropdownMenu, DropdownStyle, Indicator, prelude::*};
+use ui::{
+    ContextMenu, DropdownMenu, DropdownStyle, HighlightedLabel, Indicator, ListItem,
+    ListItemSpacing, PopoverMenu, Tooltip, prelude::*,
+};
 
 use crate::{
+    ShowCapabilities, ShowSessionInfo,
     debugger_panel::DebugPanel,
     session::{DebugSession, running::RunningState},
 };
@@ -23,7 +31,7 @@ impl DebugPanel {
         cx: &mut Context<Self>,
     ) -> Option<impl IntoElement> {
         if let Some(running_state) = running_state {
-            let sessions = self.sessions().clone();
+            let session_tree = self.session_tree(cx);
             let weak = cx.weak_entity();
             let running_state = running_state.read(cx);
             let label = if let Some(active_session) = active_session.clone() {
@@ -73,21 +81,13 @@ impl DebugPanel {
                     trigger,
                     ContextMenu::build(window, cx, move |mut this, _, cx| {
                         let context_menu = cx.weak_entity();
-                        let mut session_depths = HashMap::default();
-                        for session in sessions.into_iter() {
+                        for (session, node) in session_tree.into_iter() {
+                            if let Some(group_label) = node.group_label.clone() {
+                                this = this.header(group_label);
+                            }
                             let weak_session = session.downgrade();
                             let weak_session_id = weak_session.entity_id();
                             let session_id = session.read(cx).session_id(cx);
-                            let parent_depth = session
-                                .read(cx)
-                                .session(cx)
-                                .read(cx)
-                                .parent_id(cx)
-                                .and_then(|parent_id| session_depths.get(&parent_id).cloned());
-                            let self_depth =
-                                *session_depths.entry(session_id).or_insert_with(|| {
-                                    parent_depth.map(|depth| depth + 1).unwrap_or(0usize)
-                                });
                             this = this.custom_entry(
                                 {
                                     let weak = weak.clone();
@@ -105,7 +105,7 @@ impl DebugPanel {
                                                     .w_full()
                                                     .group(id.clone())
                                                     .justify_between()
-                                                    .child(session.label_element(self_depth, cx))
+                                                    .child(session.label_element(&node, cx))
                                                     .child(
                                                         IconButton::new(
                                                             "close-debug-session",
@@ -152,7 +152,9 @@ impl DebugPanel {
                                 },
                             );
                         }
-                        this
+                        this.separator()
+                            .action("Session Info", ShowSessionInfo.boxed_clone())
+                            .action("Capabilities", ShowCapabilities.boxed_clone())
                     }),
                 )
                 .style(DropdownStyle::Ghost)
@@ -167,9 +169,9 @@ impl DebugPanel {
         &self,
         running_state: &Entity<RunningState>,
         threads: Vec<(dap::Thread, ThreadStatus)>,
-        window: &mut Window,
+        _window: &mut Window,
         cx: &mut Context<Self>,
-    ) -> Option<DropdownMenu> {
+    ) -> Option<PopoverMenu<Picker<ThreadPickerDelegate>>> {
         let running_state = running_state.clone();
         let running_state_read = running_state.read(cx);
         let thread_id = running_state_read.thread_id();
@@ -179,45 +181,306 @@ impl DebugPanel {
         let selected_thread_name = threads
             .iter()
             .find(|(thread, _)| thread_id.map(|id| id.0) == Some(thread.id))
-            .map(|(thread, _)| {
-                thread
-                    .name
-                    .is_empty()
-                    .then(|| format!("Tid: {}", thread.id))
-                    .unwrap_or_else(|| thread.name.clone())
-            });
-
-        if let Some(selected_thread_name) = selected_thread_name {
-            let trigger = DebugPanel::dropdown_label(selected_thread_name).into_any_element();
-            Some(
-                DropdownMenu::new_with_element(
-                    ("thread-list", session_id.0),
-                    trigger,
-                    ContextMenu::build(window, cx, move |mut this, _, _| {
-                        for (thread, _) in threads {
-                            let running_state = running_state.clone();
-                            let thread_id = thread.id;
-                            let entry_name = thread
-                                .name
-                                .is_empty()
-                                .then(|| format!("Tid: {}", thread.id))
-                                .unwrap_or_else(|| thread.name);
-
-                            this = this.entry(entry_name, None, move |window, cx| {
-                                running_state.update(cx, |running_state, cx| {
-                                    running_state.select_thread(ThreadId(thread_id), window, cx);
-                                });
-                            });
-                        }
-                        this
-                    }),
+            .map(|(thread, _)| thread_name(thread))?;
+
+        Some(
+            PopoverMenu::new(("thread-list", session_id.0))
+                .menu(move |window, cx| {
+                    let delegate =
+                        ThreadPickerDelegate::new(running_state.clone(), threads.clone());
+                    let session = running_state.read(cx).session().clone();
+                    Some(cx.new(|cx| {
+                        // Top frames of the listed threads are fetched as they're rendered.
+                        cx.observe(&session, |_, _, cx| cx.notify()).detach();
+                        Picker::uniform_list(delegate, window, cx)
+                            .width(rems(28.))
+                            .modal(false)
+                    }))
+                })
+                .trigger(
+                    Button::new("thread-list-trigger", selected_thread_name)
+                        .style(ButtonStyle::Subtle)
+                        .label_size(LabelSize::Small)
+                        .icon(IconName::ChevronDown)
+                        .icon_position(IconPosition::End)
+                        .icon_size(IconSize::XSmall)
+                        .icon_color(Color::Muted)
+                        .disabled(session_terminated),
                 )
-                .disabled(session_terminated)
-                .style(DropdownStyle::Ghost)
-                .handle(self.thread_picker_menu_handle.clone()),
-            )
-        } else {
-            None
+                .attach(Corner::BottomLeft)
+                .with_handle(self.thread_picker_menu_handle.clone()),
+        )
+    }
+}
+
+fn thread_name(thread: &dap::Thread) -> String {
+    thread
+        .name
+        .is_empty()
+        .then(|| format!("Tid: {}", thread.id))
+        .unwrap_or_else(|| thread.name.clone())
+}
+
+fn stop_reason_label(event: &dap::StoppedEvent) -> SharedString {
+    if let Some(description) = &event.description {
+        return description.clone().into();
+    }
+    match &event.reason {
+        dap::StoppedEventReason::Step => "Step".into(),
+        dap::StoppedEventReason::Breakpoint => "Breakpoint".into(),
+        dap::StoppedEventReason::Exception => "Exception".into(),
+        dap::StoppedEventReason::Pause => "Pause".into(),
+        dap::StoppedEventReason::Entry => "Entry".into(),
+        dap::StoppedEventReason::Goto => "Goto".into(),
+        _ => "Stopped".into(),
+    }
+}
+
+/// Formats where the frame is as `file:line`, using the source's name or the last component of its path.
+fn frame_location(frame: &project::debugger::session::StackFrame) -> Option<String> {
+    let source = frame.dap.source.as_ref()?;
+    let file_name = source.name.clone().or_else(|| {
+        let path = source.path.as_ref()?;
+        Some(
+            std::path::Path::new(path)
+                .file_name()?
+                .to_string_lossy()
+                .into_owned(),
+        )
+    })?;
+    Some(format!("{}:{}", file_name, frame.dap.line))
+}
+
+/// Lists the threads of a session, fuzzy-filtered by their name and id.
+pub(crate) struct ThreadPickerDelegate {
+    running_state: Entity<RunningState>,
+    threads: Vec<(dap::Thread, ThreadStatus)>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl ThreadPickerDelegate {
+    fn new(running_state: Entity<RunningState>, threads: Vec<(dap::Thread, ThreadStatus)>) -> Self {
+        Self {
+            running_state,
+            threads,
+            matches: Vec::new(),
+            selected_index: 0,
         }
     }
 }
+
+impl PickerDelegate for ThreadPickerDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Select a thread…".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .threads
+            .iter()
+            .enumerate()
+            .map(|(ix, (thread, _))| {
+                StringMatchCandidate::new(ix, &format!("{} {}", thread_name(thread), thread.id))
+            })
+            .collect::<Vec<_>>();
+        let selected_thread_id = self.running_state.read(cx).thread_id();
+
+        cx.spawn(async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.0,
+                        positions: Vec::new(),
+                        string: candidate.string,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+
+            this.update(cx, |this, _| {
+                let delegate = &mut this.delegate;
+                delegate.selected_index = matches
+                    .iter()
+                    .position(|hit| {
+                        delegate
+                            .threads
+                            .get(hit.candidate_id)
+                            .map(|(thread, _)| thread.id)
+                            == selected_thread_id.map(|id| id.0)
+                    })
+                    .filter(|_| query.is_empty())
+                    .unwrap_or(0);
+                delegate.matches = matches;
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some((thread, _)) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|hit| self.threads.get(hit.candidate_id))
+        else {
+            return;
+        };
+        let thread_id = ThreadId(thread.id);
+        self.running_state.update(cx, |running_state, cx| {
+            running_state.select_thread(thread_id, window, cx);
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let (thread, status) = self.threads.get(hit.candidate_id)?;
+        let name = thread_name(thread);
+        let thread_id = ThreadId(thread.id);
+        let session = self.running_state.read(cx).session().clone();
+        let (supports_freezing_threads, is_frozen, stop_reason) = {
+            let session = session.read(cx);
+            (
+                session.supports_freezing_threads(),
+                session.is_thread_frozen(thread_id),
+                session.stopped_event(thread_id).map(stop_reason_label),
+            )
+        };
+        let location = (*status == ThreadStatus::Stopped)
+            .then(|| session.update(cx, |session, cx| session.stack_frames(thread_id, cx)))
+            .and_then(|stack_frames| stack_frames.ok()?.first().and_then(frame_location));
+        // The id was appended to the name when matching, so positions past the name belong to it.
+        let name_positions = hit
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < name.len())
+            .collect();
+
+        Some(
+            ListItem::new(("thread-picker-entry", ix))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    h_flex()
+                        .w_full()
+                        .gap_2()
+                        .justify_between()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .child(HighlightedLabel::new(name, name_positions))
+                                .child(
+                                    Label::new(format!("Tid: {}", thread.id))
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted),
+                                )
+                                .when_some(location, |this, location| {
+                                    this.child(
+                                        Label::new(location)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .truncate(),
+                                    )
+                                }),
+                        )
+                        .child(
+                            h_flex()
+                                .gap_1()
+                                .when_some(stop_reason, |this, stop_reason| {
+                                    this.child(
+                                        Label::new(stop_reason)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Warning),
+                                    )
+                                })
+                                .child(Label::new(status.label()).size(LabelSize::Small).color(
+                                    match status {
+                                        ThreadStatus::Stopped => Color::Conflict,
+                                        ThreadStatus::Running | ThreadStatus::Stepping => {
+                                            Color::Success
+                                        }
+                                        ThreadStatus::Exited | ThreadStatus::Ended => Color::Muted,
+                                    },
+                                )),
+                        ),
+                )
+                .when(supports_freezing_threads, |this| {
+                    this.end_slot(
+                        IconButton::new(("thread-picker-freeze", ix), IconName::LockOutlined)
+                            .icon_size(IconSize::XSmall)
+                            .icon_color(if is_frozen {
+                                Color::Accent
+                            } else {
+                                Color::Muted
+                            })
+                            .toggle_state(is_frozen)
+                            .tooltip(Tooltip::text(if is_frozen {
+                                "Thaw Thread"
+                            } else {
+                                "Freeze Thread"
+                            }))
+                            .on_click(cx.listener(move |picker, _, _, cx| {
+                                let session =
+                                    picker.delegate.running_state.read(cx).session().clone();
+                                session.update(cx, |session, cx| {
+                                    if session.is_thread_frozen(thread_id) {
+                                        session.thaw_thread(thread_id, cx);
+                                    } else {
+                                        session.freeze_thread(thread_id, cx);
+                                    }
+                                });
+                                cx.notify();
+                            })),
+                    )
+                }),
+        )
+    }
+}
@@ -0,0 +1,163 @@
+use dap::debugger_settings::{DebuggerSettings, FloatingControlsPosition};
+use editor::{Addon, Editor};
+use gpui::{AnyElement, Corner, Entity, Subscription, WeakEntity};
+use project::debugger::{dap_store::DapStoreEvent, session::ThreadStatus};
+use settings::Settings as _;
+use ui::{Tooltip, prelude::*};
+
+use crate::{
+    Continue, Pause, StepInto, StepOut, StepOver, Stop, debugger_panel::DebugPanel,
+    session::running::RunningState,
+};
+
+/// The continue, step and stop buttons, drawn in a corner of the active editor while a session is
+/// running, when `debugger.floating_controls` is enabled.
+pub(crate) struct FloatingControls {
+    editor: WeakEntity<Editor>,
+    _subscription: Option<Subscription>,
+}
+
+impl FloatingControls {
+    pub(crate) fn new(editor: &Editor, cx: &mut Context<Editor>) -> Self {
+        // Redraw the editor as sessions start, stop and continue, since it doesn't otherwise
+        // observe them.
+        let _subscription = editor.project.as_ref().map(|project| {
+            cx.subscribe(&project.read(cx).dap_store(), |_, _, event, cx| {
+                if matches!(
+                    event,
+                    DapStoreEvent::DebugClientEvent { .. } | DapStoreEvent::DebugClientShutdown(_)
+                ) && DebuggerSettings::get_global(cx).floating_controls.enabled
+                {
+                    cx.notify();
+                }
+            })
+        });
+        Self {
+            editor: cx.weak_entity(),
+            _subscription,
+        }
+    }
+
+    /// The running state of the debug panel's active session, if this is the active editor.
+    fn running_state(&self, cx: &App) -> Option<Entity<RunningState>> {
+        let editor = self.editor.upgrade()?;
+        let workspace = editor.read(cx).workspace()?;
+        let workspace = workspace.read(cx);
+        if workspace.active_item_as::<Editor>(cx)? != editor {
+            return None;
+        }
+        let session = workspace
+            .panel::<DebugPanel>(cx)?
+            .read(cx)
+            .active_session()?;
+        Some(session.read(cx).running_state().clone())
+    }
+}
+
+fn corner(position: FloatingControlsPosition) -> Corner {
+    match position {
+        FloatingControlsPosition::TopRight => Corner::TopRight,
+        FloatingControlsPosition::TopLeft => Corner::TopLeft,
+        FloatingControlsPosition::BottomRight => Corner::BottomRight,
+        FloatingControlsPosition::BottomLeft => Corner::BottomLeft,
+    }
+}
+
+impl Addon for FloatingControls {
+    fn render_overlay(&self, _: &Window, cx: &App) -> Option<(Corner, AnyElement)> {
+        let settings = DebuggerSettings::get_global(cx).floating_controls;
+        if !settings.enabled {
+            return None;
+        }
+        let running_state = self.running_state(cx)?;
+        let thread_status = running_state.read(cx).thread_status(cx)?;
+        if !matches!(
+            thread_status,
+            ThreadStatus::Running | ThreadStatus::Stopped | ThreadStatus::Stepping
+        ) {
+            return None;
+        }
+        let is_stopped = thread_status == ThreadStatus::Stopped;
+
+        let controls = h_flex()
+            .p_0p5()
+            .gap_0p5()
+            .elevation_2(cx)
+            .map(|this| {
+                if thread_status == ThreadStatus::Running {
+                    this.child(
+                        IconButton::new("floating-debug-pause", IconName::DebugPause)
+                            .icon_size(IconSize::XSmall)
+                            .on_click({
+                                let running_state = running_state.clone();
+                                move |_, _, cx| {
+                                    running_state.update(cx, |this, cx| this.pause_thread(cx))
+                                }
+                            })
+                            .tooltip(|window, cx| {
+                                Tooltip::for_action("Pause program", &Pause, window, cx)
+                            }),
+                    )
+                } else {
+                    this.child(
+                        IconButton::new("floating-debug-continue", IconName::DebugContinue)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!is_stopped)
+                            .on_click({
+                                let running_state = running_state.clone();
+                                move |_, _, cx| {
+                                    running_state.update(cx, |this, cx| this.continue_thread(cx))
+                                }
+                            })
+                            .tooltip(|window, cx| {
+                                Tooltip::for_action("Continue program", &Continue, window, cx)
+                            }),
+                    )
+                }
+            })
+            .child(
+                IconButton::new("floating-debug-step-over", IconName::ArrowRight)
+                    .icon_size(IconSize::XSmall)
+                    .disabled(!is_stopped)
+                    .on_click({
+                        let running_state = running_state.clone();
+                        move |_, _, cx| running_state.update(cx, |this, cx| this.step_over(cx))
+                    })
+                    .tooltip(|window, cx| Tooltip::for_action("Step over", &StepOver, window, cx)),
+            )
+            .child(
+                IconButton::new("floating-debug-step-out", IconName::ArrowUpRight)
+                    .icon_size(IconSize::XSmall)
+                    .disabled(!is_stopped)
+                    .on_click({
+                        let running_state = running_state.clone();
+                        move |_, _, cx| running_state.update(cx, |this, cx| this.step_out(cx))
+                    })
+                    .tooltip(|window, cx| Tooltip::for_action("Step out", &StepOut, window, cx)),
+            )
+            .child(
+                IconButton::new("floating-debug-step-into", IconName::ArrowDownRight)
+                    .icon_size(IconSize::XSmall)
+                    .disabled(!is_stopped)
+                    .on_click({
+                        let running_state = running_state.clone();
+                        move |_, _, cx| running_state.update(cx, |this, cx| this.step_in(cx))
+                    })
+                    .tooltip(|window, cx| Tooltip::for_action("Step in", &StepInto, window, cx)),
+            )
+            .child(
+                IconButton::new("floating-debug-stop", IconName::Power)
+                    .icon_size(IconSize::XSmall)
+                    .on_click(move |_, window, cx| {
+                        running_state.update(cx, |this, cx| this.stop_thread(window, cx))
+                    })
+                    .tooltip(|window, cx| Tooltip::for_action("Stop debugging", &Stop, window, cx)),
+            );
+
+        Some((corner(settings.position), controls.into_any_element()))
+    }
+
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
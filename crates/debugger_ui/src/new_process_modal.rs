@@ -23,7 +23,11 @@ use gpui::{
 };
 use itertools::Itertools as _;
 use picker::{Picker, PickerDelegate, highlighted_match_with_paths::HighlightedMatch};
-use project::{ProjectPath, TaskContexts, TaskSourceKind, task_store::TaskStore};
+use project::{
+    ProjectPath, TaskContexts, TaskSourceKind,
+    debugger::docker::{self, DockerContainer},
+    task_store::TaskStore,
+};
 use settings::{Settings, initial_local_debug_tasks_content};
 use task::{DebugScenario, RevealTarget, ZedDebugConfig};
 use theme::ThemeSettings;
@@ -55,6 +59,8 @@ pub(super) struct NewProcessModal {
     task_mode: TaskMode,
     debugger: Option<DebugAdapterName>,
     save_scenario_state: Option<SaveScenarioState>,
+    /// The scenario being edited before it's rerun, whose config the edits are applied over.
+    rerun_scenario: Option<DebugScenario>,
     _subscriptions: [Subscription; 3],
 }
 
@@ -83,6 +89,35 @@ impl NewProcessModal {
         mode: NewProcessMode,
         reveal_target: Option<RevealTarget>,
         cx: &mut Context<Workspace>,
+    ) {
+        Self::open(workspace, window, mode, reveal_target, None, cx);
+    }
+
+    /// Opens the modal with the scenario's program, arguments and environment filled in, so they
+    /// can be tweaked before it runs again.
+    pub(super) fn show_for_rerun(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        scenario: DebugScenario,
+        cx: &mut Context<Workspace>,
+    ) {
+        Self::open(
+            workspace,
+            window,
+            NewProcessMode::Launch,
+            None,
+            Some(scenario),
+            cx,
+        );
+    }
+
+    fn open(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        mode: NewProcessMode,
+        reveal_target: Option<RevealTarget>,
+        rerun_scenario: Option<DebugScenario>,
+        cx: &mut Context<Workspace>,
     ) {
         let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
             return;
@@ -106,6 +141,11 @@ impl NewProcessModal {
                     });
 
                     let configure_mode = ConfigureMode::new(window, cx);
+                    if let Some(scenario) = &rerun_scenario {
+                        configure_mode.update(cx, |configure_mode, cx| {
+                            configure_mode.load_scenario(&scenario.config, window, cx);
+                        });
+                    }
 
                     let task_overrides = Some(TaskOverrides { reveal_target });
 
@@ -261,11 +301,14 @@ impl NewProcessModal {
                         attach_mode,
                         configure_mode,
                         task_mode,
-                        debugger: None,
+                        debugger: rerun_scenario
+                            .as_ref()
+                            .map(|scenario| DebugAdapterName(scenario.adapter.clone())),
                         mode,
                         debug_panel: debug_panel.downgrade(),
                         workspace: workspace_handle,
                         save_scenario_state: None,
+                        rerun_scenario,
                         _subscriptions,
                     }
                 });
@@ -370,13 +413,22 @@ impl NewProcessModal {
         let task_context = task_contexts.active_context().cloned().unwrap_or_default();
         let worktree_id = task_contexts.worktree();
         let mode = self.mode;
+        let rerun_scenario = self
+            .rerun_scenario
+            .clone()
+            .filter(|_| matches!(mode, NewProcessMode::Launch));
         cx.spawn_in(window, async move |this, cx| {
-            let Some(config) = this
+            let Some(mut config) = this
                 .update(cx, |this, cx| this.debug_scenario(&debugger, cx))?
                 .await
             else {
                 bail!("debug config not found in mode: {mode}");
             };
+            if let Some(rerun_scenario) =
+                rerun_scenario.filter(|scenario| scenario.adapter == config.adapter)
+            {
+                config = apply_edits_to_scenario(rerun_scenario, config);
+            }
 
             debug_panel.update_in(cx, |debug_panel, window, cx| {
                 send_telemetry(&config, TelemetrySpawnLocation::Custom, cx);
@@ -607,6 +659,27 @@ impl NewProcessModal {
     }
 }
 
+/// Config fields that the launch form edits, which are dropped from a rerun scenario's config in
+/// favor of the edited ones.
+const EDITABLE_CONFIG_FIELDS: [&str; 5] = ["program", "args", "env", "cwd", "stopOnEntry"];
+
+pub(crate) fn apply_edits_to_scenario(
+    scenario: DebugScenario,
+    edited: DebugScenario,
+) -> DebugScenario {
+    let config = match (scenario.config, edited.config) {
+        (serde_json::Value::Object(mut config), serde_json::Value::Object(edited)) => {
+            for field in EDITABLE_CONFIG_FIELDS {
+                config.remove(field);
+            }
+            config.extend(edited);
+            serde_json::Value::Object(config)
+        }
+        (_, edited) => edited,
+    };
+    DebugScenario { config, ..scenario }
+}
+
 static SELECT_DEBUGGER_LABEL: SharedString = SharedString::new_static("Select Debugger");
 
 #[derive(Clone, Copy)]
@@ -861,7 +934,16 @@ impl Render for NewProcessModal {
                     ),
                     NewProcessMode::Attach => el.child(
                         container
-                            .child(div().child(self.adapter_drop_down_menu(window, cx)))
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(self.adapter_drop_down_menu(window, cx))
+                                    .children(AttachMode::container_drop_down_menu(
+                                        &self.attach_mode,
+                                        window,
+                                        cx,
+                                    )),
+                            )
                             .child(
                                 Button::new("debugger-spawn", "Start")
                                     .on_click(cx.listener(|this, _, window, cx| {
@@ -935,6 +1017,45 @@ impl ConfigureMode {
         })
     }
 
+    fn load_scenario(&mut self, config: &serde_json::Value, window: &mut Window, cx: &mut App) {
+        let env = config
+            .get("env")
+            .and_then(|env| env.as_object())
+            .into_iter()
+            .flatten()
+            .filter_map(|(key, value)| Some(format!("{key}={}", value.as_str()?)));
+        let program = config
+            .get("program")
+            .and_then(|program| program.as_str())
+            .map(ToOwned::to_owned);
+        let args = config
+            .get("args")
+            .and_then(|args| args.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|arg| arg.as_str().map(ToOwned::to_owned));
+        let command = env
+            .chain(program)
+            .chain(args)
+            .filter_map(|part| shlex::try_quote(&part).ok().map(|part| part.into_owned()))
+            .join(" ");
+
+        self.program.update(cx, |editor, cx| {
+            editor.set_text(command, window, cx);
+        });
+        if let Some(cwd) = config.get("cwd").and_then(|cwd| cwd.as_str()) {
+            self.cwd.update(cx, |editor, cx| {
+                editor.set_text(cwd, window, cx);
+            });
+        }
+        if let Some(stop_on_entry) = config
+            .get("stopOnEntry")
+            .and_then(|stop_on_entry| stop_on_entry.as_bool())
+        {
+            self.stop_on_entry = stop_on_entry.into();
+        }
+    }
+
     fn load(&mut self, cwd: PathBuf, window: &mut Window, cx: &mut App) {
         self.cwd.update(cx, |editor, cx| {
             if editor.is_empty(cx) {
@@ -1071,6 +1192,9 @@ impl ConfigureMode {
 pub(super) struct AttachMode {
     pub(super) definition: ZedDebugConfig,
     pub(super) attach_picker: Entity<AttachModal>,
+    /// Running Docker containers whose processes can be attached to instead of the host's.
+    containers: Vec<DockerContainer>,
+    container: Option<DockerContainer>,
 }
 
 impl AttachMode {
@@ -1093,14 +1217,74 @@ impl AttachMode {
             modal
         });
 
-        cx.new(|_| Self {
-            definition,
-            attach_picker,
+        cx.new(|cx| {
+            cx.spawn(async move |this, cx| {
+                let containers = docker::running_containers().await.unwrap_or_default();
+                this.update(cx, |this, cx| {
+                    this.containers = containers;
+                    cx.notify();
+                })
+            })
+            .detach_and_log_err(cx);
+
+            Self {
+                definition,
+                attach_picker,
+                containers: Vec::new(),
+                container: None,
+            }
         })
     }
+
     pub(super) fn debug_request(&self) -> task::AttachRequest {
         task::AttachRequest { process_id: None }
     }
+
+    fn container_drop_down_menu(
+        this: &Entity<Self>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<DropdownMenu> {
+        let attach_mode = this.read(cx);
+        if attach_mode.containers.is_empty() {
+            return None;
+        }
+        let label = attach_mode
+            .container
+            .as_ref()
+            .map(|container| container.name.clone())
+            .unwrap_or_else(|| "Host".into());
+        let containers = attach_mode.containers.clone();
+        let weak = this.downgrade();
+
+        Some(DropdownMenu::new(
+            "attach-container-picker",
+            label,
+            ContextMenu::build(window, cx, move |mut menu, _, _| {
+                let setter_for_container = |container: Option<DockerContainer>| {
+                    let weak = weak.clone();
+                    move |window: &mut Window, cx: &mut App| {
+                        weak.update(cx, |this, cx| {
+                            this.container = container.clone();
+                            this.attach_picker.update(cx, |attach_picker, cx| {
+                                attach_picker.set_container(container.clone(), window, cx);
+                            });
+                            cx.notify();
+                        })
+                        .ok();
+                    }
+                };
+
+                menu = menu.entry("Host", None, setter_for_container(None));
+                for container in containers.into_iter() {
+                    let label = format!("{} ({})", container.name, container.image);
+                    menu = menu.entry(label, None, setter_for_container(Some(container)));
+                }
+
+                menu
+            }),
+        ))
+    }
 }
 
 #[derive(Clone)]
@@ -1118,6 +1302,8 @@ pub(super) struct DebugDelegate {
     task_contexts: Option<Arc<TaskContexts>>,
     divider_index: Option<usize>,
     last_used_candidate_index: Option<usize>,
+    pinned_divider_index: Option<usize>,
+    pinned_candidate_count: usize,
 }
 
 impl DebugDelegate {
@@ -1132,6 +1318,8 @@ impl DebugDelegate {
             task_contexts: None,
             divider_index: None,
             last_used_candidate_index: None,
+            pinned_divider_index: None,
+            pinned_candidate_count: 0,
         }
     }
 
@@ -1192,10 +1380,6 @@ impl DebugDelegate {
             };
 
             this.update(cx, |this, cx| {
-                if !recent.is_empty() {
-                    this.delegate.last_used_candidate_index = Some(recent.len() - 1);
-                }
-
                 let dap_registry = cx.global::<DapRegistry>();
                 let hide_vscode = scenarios.iter().any(|(kind, _)| match kind {
                     TaskSourceKind::Worktree {
@@ -1206,33 +1390,88 @@ impl DebugDelegate {
                     _ => false,
                 });
 
-                this.delegate.candidates = recent
+                let recent = recent
                     .into_iter()
                     .map(|scenario| Self::get_scenario_kind(&languages, &dap_registry, scenario))
-                    .chain(
-                        scenarios
-                            .into_iter()
-                            .filter(|(kind, _)| match kind {
-                                TaskSourceKind::Worktree {
-                                    id: _,
-                                    directory_in_worktree: dir,
-                                    id_base: _,
-                                } => !(hide_vscode && dir.ends_with(".vscode")),
-                                _ => true,
-                            })
-                            .map(|(kind, scenario)| {
-                                let (language, scenario) =
-                                    Self::get_scenario_kind(&languages, &dap_registry, scenario);
-                                (language.or(Some(kind)), scenario)
-                            }),
-                    )
                     .collect();
+                let scenarios = scenarios
+                    .into_iter()
+                    .filter(|(kind, _)| match kind {
+                        TaskSourceKind::Worktree {
+                            id: _,
+                            directory_in_worktree: dir,
+                            id_base: _,
+                        } => !(hide_vscode && dir.ends_with(".vscode")),
+                        _ => true,
+                    })
+                    .map(|(kind, scenario)| {
+                        let (language, scenario) =
+                            Self::get_scenario_kind(&languages, &dap_registry, scenario);
+                        (language.or(Some(kind)), scenario)
+                    })
+                    .collect();
+                let pinned = this
+                    .delegate
+                    .debug_panel
+                    .read_with(cx, |debug_panel, _| debug_panel.pinned_scenarios().to_vec())
+                    .unwrap_or_default();
+
+                let (candidates, pinned_count, recent_count) =
+                    pin_candidates(&pinned, recent, scenarios);
+                this.delegate.candidates = candidates;
+                this.delegate.pinned_candidate_count = pinned_count;
+                this.delegate.last_used_candidate_index =
+                    (recent_count > 0).then(|| pinned_count + recent_count - 1);
             })
             .ok();
         })
     }
 }
 
+type Candidate = (Option<TaskSourceKind>, DebugScenario);
+
+/// Moves the pinned scenarios in front of the recently used and the other ones, in the order they
+/// were pinned. Returns the candidates with the number of pinned and recent ones among them.
+pub(crate) fn pin_candidates(
+    pinned: &[DebugScenario],
+    recent: Vec<Candidate>,
+    scenarios: Vec<Candidate>,
+) -> (Vec<Candidate>, usize, usize) {
+    let mut pinned_candidates = vec![None; pinned.len()];
+    let mut unpinned = |candidates: Vec<Candidate>| {
+        candidates
+            .into_iter()
+            .filter_map(|candidate| {
+                let Some(ix) = pinned
+                    .iter()
+                    .position(|pinned| pinned.label == candidate.1.label)
+                else {
+                    return Some(candidate);
+                };
+                pinned_candidates[ix].get_or_insert(candidate);
+                None
+            })
+            .collect::<Vec<_>>()
+    };
+    let recent = unpinned(recent);
+    let scenarios = unpinned(scenarios);
+
+    // Pinned scenarios that aren't listed anymore, e.g. one-off commands that are no longer among
+    // the recently used ones, are started as they were pinned.
+    let pinned_candidates = pinned_candidates
+        .into_iter()
+        .zip(pinned)
+        .map(|(candidate, pinned)| candidate.unwrap_or_else(|| (None, pinned.clone())))
+        .collect::<Vec<_>>();
+    let (pinned_count, recent_count) = (pinned_candidates.len(), recent.len());
+    let candidates = pinned_candidates
+        .into_iter()
+        .chain(recent)
+        .chain(scenarios)
+        .collect();
+    (candidates, pinned_count, recent_count)
+}
+
 impl PickerDelegate for DebugDelegate {
     type ListItem = ui::ListItem;
 
@@ -1292,12 +1531,20 @@ impl PickerDelegate for DebugDelegate {
                     delegate.matches = matches;
                     delegate.prompt = query;
 
-                    delegate.divider_index = delegate.last_used_candidate_index.and_then(|index| {
-                        let index = delegate
-                            .matches
+                    let matches = &delegate.matches;
+                    let divider_after = |index: usize| {
+                        let index = matches
                             .partition_point(|matching_task| matching_task.candidate_id <= index);
-                        Some(index).and_then(|index| (index != 0).then(|| index - 1))
-                    });
+                        (index != 0).then(|| index - 1)
+                    };
+                    let divider_index = delegate.last_used_candidate_index.and_then(divider_after);
+                    let pinned_divider_index = delegate
+                        .pinned_candidate_count
+                        .checked_sub(1)
+                        .and_then(divider_after)
+                        .filter(|&index| Some(index) != divider_index);
+                    delegate.divider_index = divider_index;
+                    delegate.pinned_divider_index = pinned_divider_index;
 
                     if delegate.matches.is_empty() {
                         delegate.selected_index = 0;
@@ -1311,11 +1558,10 @@ impl PickerDelegate for DebugDelegate {
     }
 
     fn separators_after_indices(&self) -> Vec<usize> {
-        if let Some(i) = self.divider_index {
-            vec![i]
-        } else {
-            Vec::new()
-        }
+        self.pinned_divider_index
+            .into_iter()
+            .chain(self.divider_index)
+            .collect()
     }
 
     fn confirm_input(
@@ -1495,8 +1741,9 @@ impl PickerDelegate for DebugDelegate {
                 } else {
                     this.children(KeyBinding::for_action(&menu::Confirm, window, cx).map(
                         |keybind| {
-                            let is_recent_selected =
-                                self.divider_index >= Some(self.selected_index);
+                            let is_recent_selected = self.divider_index
+                                >= Some(self.selected_index)
+                                && self.pinned_divider_index < Some(self.selected_index);
                             let run_entry_label =
                                 if is_recent_selected { "Rerun" } else { "Spawn" };
 
@@ -1557,13 +1804,53 @@ impl PickerDelegate for DebugDelegate {
                 .indicator_border_color(Some(cx.theme().colors().border_transparent))
         });
 
+        let scenario = self.candidates[hit.candidate_id].1.clone();
+        let is_pinned = self
+            .debug_panel
+            .read_with(cx, |debug_panel, _| {
+                debug_panel.is_scenario_pinned(&scenario)
+            })
+            .unwrap_or_default();
+        let pin_button = IconButton::new(
+            SharedString::from(format!("debug-scenario-pin-{ix}")),
+            if is_pinned {
+                IconName::StarFilled
+            } else {
+                IconName::Star
+            },
+        )
+        .icon_size(IconSize::Small)
+        .icon_color(if is_pinned {
+            Color::Accent
+        } else {
+            Color::Muted
+        })
+        .tooltip(Tooltip::text(if is_pinned { "Unpin" } else { "Pin" }))
+        .on_click(cx.listener(move |picker, _, _, cx| {
+            picker
+                .delegate
+                .debug_panel
+                .update(cx, |debug_panel, cx| {
+                    debug_panel.toggle_pinned_scenario(&scenario, cx)
+                })
+                .ok();
+            cx.notify();
+        }));
+
         Some(
             ListItem::new(SharedString::from(format!("debug-scenario-selection-{ix}")))
                 .inset(true)
                 .start_slot::<IconWithIndicator>(icon)
                 .spacing(ListItemSpacing::Sparse)
                 .toggle_state(selected)
-                .child(highlighted_location.render(window, cx)),
+                .child(highlighted_location.render(window, cx))
+                .map(|item| {
+                    if is_pinned {
+                        item.end_slot(pin_button)
+                    } else {
+                        item.end_hover_slot(pin_button)
+                    }
+                }),
         )
     }
 }
@@ -5,15 +5,19 @@ use db::kvp::KEY_VALUE_STORE;
 use gpui::{Axis, Context, Entity, EntityId, Focusable, Subscription, WeakEntity, Window};
 use project::Project;
 use serde::{Deserialize, Serialize};
+use task::DebugScenario;
 use ui::{App, SharedString};
 use util::ResultExt;
-use workspace::{Member, Pane, PaneAxis, Workspace};
+use workspace::{Member, Pane, PaneAxis, Workspace, WorkspaceId};
 
 use crate::session::running::{
-    self, DebugTerminal, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
-    loaded_source_list::LoadedSourceList, module_list::ModuleList,
-    stack_frame_list::StackFrameList, variable_list::VariableList,
+    self, DebugTerminal, RunningState, SubView, adapter_log::AdapterLog,
+    breakpoint_list::BreakpointList, collection_table::CollectionTable, console::Console,
+    disassembly_view::DisassemblyView, loaded_source_list::LoadedSourceList,
+    module_list::ModuleList, register_list::RegisterList, stack_frame_list::StackFrameList,
+    variable_list::VariableList, variable_snapshots::VariableSnapshots,
 };
+use crate::session_history::SessionHistoryEntry;
 
 #[derive(Clone, Hash, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) enum DebuggerPaneItem {
@@ -24,6 +28,11 @@ pub(crate) enum DebuggerPaneItem {
     Modules,
     LoadedSources,
     Terminal,
+    Registers,
+    Disassembly,
+    CollectionTable,
+    VariableSnapshots,
+    AdapterLog,
 }
 
 impl DebuggerPaneItem {
@@ -36,6 +45,11 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Modules,
             DebuggerPaneItem::LoadedSources,
             DebuggerPaneItem::Terminal,
+            DebuggerPaneItem::Registers,
+            DebuggerPaneItem::Disassembly,
+            DebuggerPaneItem::CollectionTable,
+            DebuggerPaneItem::VariableSnapshots,
+            DebuggerPaneItem::AdapterLog,
         ];
         VARIANTS
     }
@@ -46,6 +60,9 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::LoadedSources => capabilities
                 .supports_loaded_sources_request
                 .unwrap_or_default(),
+            DebuggerPaneItem::Disassembly => capabilities
+                .supports_disassemble_request
+                .unwrap_or_default(),
             _ => true,
         }
     }
@@ -59,6 +76,11 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Modules => SharedString::new_static("Modules"),
             DebuggerPaneItem::LoadedSources => SharedString::new_static("Sources"),
             DebuggerPaneItem::Terminal => SharedString::new_static("Terminal"),
+            DebuggerPaneItem::Registers => SharedString::new_static("Registers"),
+            DebuggerPaneItem::Disassembly => SharedString::new_static("Disassembly"),
+            DebuggerPaneItem::CollectionTable => SharedString::new_static("Table"),
+            DebuggerPaneItem::VariableSnapshots => SharedString::new_static("Snapshots"),
+            DebuggerPaneItem::AdapterLog => SharedString::new_static("Adapter Log"),
         }
     }
     pub(crate) fn tab_tooltip(self) -> SharedString {
@@ -80,6 +102,19 @@ impl DebuggerPaneItem {
             DebuggerPaneItem::Terminal => {
                 "Provides an interactive terminal session within the debugging environment."
             }
+            DebuggerPaneItem::Registers => "Shows the CPU registers of the current stack frame.",
+            DebuggerPaneItem::Disassembly => {
+                "Shows the machine instructions around the current stack frame's instruction pointer."
+            }
+            DebuggerPaneItem::CollectionTable => {
+                "Shows the elements of a collection variable as a sortable table."
+            }
+            DebuggerPaneItem::VariableSnapshots => {
+                "Compares captured snapshots of variables with each other or the current values."
+            }
+            DebuggerPaneItem::AdapterLog => {
+                "Shows what the debug adapter wrote to stderr, and why it failed to start."
+            }
         };
         SharedString::new_static(tooltip)
     }
@@ -192,6 +227,100 @@ pub(crate) async fn get_serialized_layout(
         .and_then(|value| serde_json::from_str::<SerializedLayout>(&value).ok())
 }
 
+const DEBUGGER_CONSOLE_HISTORY_PREFIX: &str = "debugger_console_history";
+
+fn console_history_key(workspace_id: WorkspaceId) -> String {
+    format!("{DEBUGGER_CONSOLE_HISTORY_PREFIX}-{workspace_id:?}")
+}
+
+/// Held while a console history is read and written back, so that expressions saved at the same
+/// time don't overwrite each other.
+static CONSOLE_HISTORY_LOCK: smol::lock::Mutex<()> = smol::lock::Mutex::new(());
+
+/// Appends an evaluated expression to the console history of a workspace.
+///
+/// Every console of the workspace shares the stored history, so the expression is added to what
+/// is currently stored rather than overwriting it with a single console's view of the history.
+pub(crate) async fn add_to_console_history(
+    workspace_id: WorkspaceId,
+    expression: String,
+    max_len: usize,
+) -> anyhow::Result<()> {
+    let _guard = CONSOLE_HISTORY_LOCK.lock().await;
+    let mut history = get_console_history(workspace_id);
+    history.push(expression);
+    history.drain(..history.len().saturating_sub(max_len));
+    let history =
+        serde_json::to_string(&history).context("Serializing console history as a string")?;
+    KEY_VALUE_STORE
+        .write_kvp(console_history_key(workspace_id), history)
+        .await
+}
+
+/// The expressions evaluated in the debug consoles of a workspace, from oldest to newest.
+pub(crate) fn get_console_history(workspace_id: WorkspaceId) -> Vec<String> {
+    KEY_VALUE_STORE
+        .read_kvp(&console_history_key(workspace_id))
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str(&value).log_err())
+        .unwrap_or_default()
+}
+
+const DEBUGGER_SESSION_HISTORY_PREFIX: &str = "debugger_session_history";
+
+fn session_history_key(workspace_id: WorkspaceId) -> String {
+    format!("{DEBUGGER_SESSION_HISTORY_PREFIX}-{workspace_id:?}")
+}
+
+pub(crate) async fn serialize_session_history(
+    workspace_id: WorkspaceId,
+    history: Vec<SessionHistoryEntry>,
+) -> anyhow::Result<()> {
+    let history =
+        serde_json::to_string(&history).context("Serializing session history as a string")?;
+    KEY_VALUE_STORE
+        .write_kvp(session_history_key(workspace_id), history)
+        .await
+}
+
+/// The debug sessions started in a workspace, from oldest to newest.
+pub(crate) fn get_session_history(workspace_id: WorkspaceId) -> Vec<SessionHistoryEntry> {
+    KEY_VALUE_STORE
+        .read_kvp(&session_history_key(workspace_id))
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str(&value).log_err())
+        .unwrap_or_default()
+}
+
+const DEBUGGER_PINNED_SCENARIOS_PREFIX: &str = "debugger_pinned_scenarios";
+
+fn pinned_scenarios_key(workspace_id: WorkspaceId) -> String {
+    format!("{DEBUGGER_PINNED_SCENARIOS_PREFIX}-{workspace_id:?}")
+}
+
+pub(crate) async fn serialize_pinned_scenarios(
+    workspace_id: WorkspaceId,
+    scenarios: Vec<DebugScenario>,
+) -> anyhow::Result<()> {
+    let scenarios =
+        serde_json::to_string(&scenarios).context("Serializing pinned scenarios as a string")?;
+    KEY_VALUE_STORE
+        .write_kvp(pinned_scenarios_key(workspace_id), scenarios)
+        .await
+}
+
+/// The scenarios pinned in a workspace, in the order they were pinned.
+pub(crate) fn get_pinned_scenarios(workspace_id: WorkspaceId) -> Vec<DebugScenario> {
+    KEY_VALUE_STORE
+        .read_kvp(&pinned_scenarios_key(workspace_id))
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str(&value).log_err())
+        .unwrap_or_default()
+}
+
 pub(crate) fn deserialize_pane_layout(
     serialized: SerializedPaneLayout,
     should_invert: bool,
@@ -203,6 +332,11 @@ pub(crate) fn deserialize_pane_layout(
     console: &Entity<Console>,
     breakpoint_list: &Entity<BreakpointList>,
     loaded_sources: &Entity<LoadedSourceList>,
+    register_list: &Entity<RegisterList>,
+    disassembly_view: &Entity<DisassemblyView>,
+    collection_table: &Entity<CollectionTable>,
+    variable_snapshots: &Entity<VariableSnapshots>,
+    adapter_log: &Entity<AdapterLog>,
     terminal: &Entity<DebugTerminal>,
     subscriptions: &mut HashMap<EntityId, Subscription>,
     window: &mut Window,
@@ -227,6 +361,11 @@ pub(crate) fn deserialize_pane_layout(
                     console,
                     breakpoint_list,
                     loaded_sources,
+                    register_list,
+                    disassembly_view,
+                    collection_table,
+                    variable_snapshots,
+                    adapter_log,
                     terminal,
                     subscriptions,
                     window,
@@ -317,6 +456,41 @@ pub(crate) fn deserialize_pane_layout(
                         None,
                         cx,
                     )),
+                    DebuggerPaneItem::Registers => Box::new(SubView::new(
+                        register_list.focus_handle(cx),
+                        register_list.clone().into(),
+                        DebuggerPaneItem::Registers,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                        disassembly_view.focus_handle(cx),
+                        disassembly_view.clone().into(),
+                        DebuggerPaneItem::Disassembly,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::CollectionTable => Box::new(SubView::new(
+                        collection_table.focus_handle(cx),
+                        collection_table.clone().into(),
+                        DebuggerPaneItem::CollectionTable,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::VariableSnapshots => Box::new(SubView::new(
+                        variable_snapshots.focus_handle(cx),
+                        variable_snapshots.clone().into(),
+                        DebuggerPaneItem::VariableSnapshots,
+                        None,
+                        cx,
+                    )),
+                    DebuggerPaneItem::AdapterLog => Box::new(SubView::new(
+                        adapter_log.focus_handle(cx),
+                        adapter_log.clone().into(),
+                        DebuggerPaneItem::AdapterLog,
+                        None,
+                        cx,
+                    )),
                 })
                 .collect();
 
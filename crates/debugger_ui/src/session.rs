@@ -1,17 +1,18 @@
 pub mod running;
 
 use crate::{StackTraceView, persistence::SerializedLayout, session::running::DebugTerminal};
+use collections::HashMap;
 use dap::client::SessionId;
 use gpui::{
     App, Axis, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task, WeakEntity,
 };
 use project::Project;
-use project::debugger::session::Session;
+use project::debugger::session::{Session, ThreadStatus};
 use project::worktree_store::WorktreeStore;
 use rpc::proto;
 use running::RunningState;
 use std::{cell::OnceCell, sync::OnceLock};
-use ui::{Indicator, prelude::*};
+use ui::prelude::*;
 use workspace::{
     CollaboratorId, FollowableItem, ViewId, Workspace,
     item::{self, Item},
@@ -27,6 +28,87 @@ pub struct DebugSession {
     _subscriptions: [Subscription; 1],
 }
 
+/// Sessions started together, one per target of a scenario with several programs, or one per
+/// adapter of a mixed-mode scenario.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LaunchGroup {
+    pub(crate) id: usize,
+    pub(crate) label: SharedString,
+    /// Whether the sessions debug the same program with different adapters, and are stopped
+    /// together.
+    pub(crate) coupled: bool,
+}
+
+/// Where a session sits in the tree of parent and child sessions listed by the debug panel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SessionTreeNode {
+    pub(crate) depth: usize,
+    /// Whether no later session shares the parent of this one.
+    pub(crate) is_last_child: bool,
+    /// Set on the first session of a launch group, which is listed under the group's label.
+    pub(crate) group_label: Option<SharedString>,
+}
+
+impl SessionTreeNode {
+    /// The tree line connecting a child session to its parent.
+    pub(crate) fn guide(&self) -> Option<Label> {
+        (self.depth > 0).then(|| {
+            Label::new(if self.is_last_child { "└" } else { "├" })
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+        })
+    }
+}
+
+/// Lays out sessions, given as their ids and their parents' ids, as a tree. Parents have to come
+/// before their children, as they do in the debug panel. Sessions without a parent that belong to
+/// a launch group are listed as children of the group.
+pub(crate) fn session_tree(
+    sessions: &[(SessionId, Option<SessionId>)],
+    groups: &HashMap<SessionId, LaunchGroup>,
+) -> Vec<SessionTreeNode> {
+    let mut depths = HashMap::<SessionId, usize>::default();
+    let group_of = |ix: usize| {
+        let (session_id, parent_id) = &sessions[ix];
+        parent_id
+            .is_none()
+            .then(|| groups.get(session_id).map(|group| group.id))
+            .flatten()
+    };
+    let mut previous_root_group = None;
+    sessions
+        .iter()
+        .enumerate()
+        .map(|(ix, (session_id, parent_id))| {
+            let group = group_of(ix);
+            let depth = match parent_id {
+                Some(parent_id) => depths.get(parent_id).map_or(0, |depth| depth + 1),
+                None => usize::from(group.is_some()),
+            };
+            depths.insert(*session_id, depth);
+
+            let is_last_child = match group {
+                Some(group) => !(ix + 1..sessions.len()).any(|ix| group_of(ix) == Some(group)),
+                None => !sessions[ix + 1..]
+                    .iter()
+                    .any(|(_, other_parent_id)| other_parent_id == parent_id),
+            };
+            let mut group_label = None;
+            if parent_id.is_none() {
+                if group.is_some() && group != previous_root_group {
+                    group_label = groups.get(session_id).map(|group| group.label.clone());
+                }
+                previous_root_group = group;
+            }
+            SessionTreeNode {
+                depth,
+                is_last_child,
+                group_label,
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 pub enum DebugPanelItemEvent {
     Close,
@@ -125,7 +207,25 @@ impl DebugSession {
         &self.running_state
     }
 
-    pub(crate) fn label_element(&self, depth: usize, cx: &App) -> AnyElement {
+    /// An icon for whether the session is starting, running, stopped or terminated.
+    pub(crate) fn status_icon(&self, cx: &App) -> Icon {
+        let running_state = self.running_state.read(cx);
+        let session = running_state.session().read(cx);
+        let (icon, color) = if session.is_terminated() {
+            (IconName::DebugStop, Color::Muted)
+        } else if !session.is_started() {
+            (IconName::ArrowCircle, Color::Muted)
+        } else {
+            match running_state.thread_status(cx).unwrap_or_default() {
+                ThreadStatus::Stopped => (IconName::DebugPause, Color::Conflict),
+                _ => (IconName::DebugContinue, Color::Success),
+            }
+        };
+        Icon::new(icon).size(IconSize::XSmall).color(color)
+    }
+
+    /// The session's label, indented under its parent session with a tree guide.
+    pub(crate) fn label_element(&self, node: &SessionTreeNode, cx: &App) -> AnyElement {
         let label = self.label(cx);
 
         let is_terminated = self
@@ -134,29 +234,12 @@ impl DebugSession {
             .session()
             .read(cx)
             .is_terminated();
-        let icon = {
-            if is_terminated {
-                Some(Indicator::dot().color(Color::Error))
-            } else {
-                match self
-                    .running_state
-                    .read(cx)
-                    .thread_status(cx)
-                    .unwrap_or_default()
-                {
-                    project::debugger::session::ThreadStatus::Stopped => {
-                        Some(Indicator::dot().color(Color::Conflict))
-                    }
-                    _ => Some(Indicator::dot().color(Color::Success)),
-                }
-            }
-        };
 
         h_flex()
-            .ml(depth * px(16.0))
-            .gap_2()
-            .when_some(icon, |this, indicator| this.child(indicator))
-            .justify_between()
+            .gap_1()
+            .when(node.depth > 0, |this| this.ml((node.depth - 1) * px(16.0)))
+            .children(node.guide())
+            .child(self.status_icon(cx))
             .child(
                 Label::new(label)
                     .size(LabelSize::Small)
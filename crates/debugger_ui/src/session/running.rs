@@ -1,64 +1,110 @@
+pub(crate) mod adapter_log;
 pub(crate) mod breakpoint_list;
+pub(crate) mod collection_table;
 pub(crate) mod console;
+pub(crate) mod disassembly_view;
+pub(crate) mod exception_popover;
+pub(crate) mod external_terminal;
+pub(crate) mod image_visualizer;
+pub(crate) mod instruction_breakpoint_modal;
 pub(crate) mod loaded_source_list;
+pub(crate) mod memory_editor;
 pub(crate) mod module_list;
+pub(crate) mod quick_watch;
+pub(crate) mod register_list;
+pub(crate) mod source_path_mapping;
 pub mod stack_frame_list;
 pub mod variable_list;
-
-use std::{any::Any, ops::ControlFlow, path::PathBuf, sync::Arc, time::Duration};
+pub(crate) mod variable_snapshots;
+pub(crate) mod wait_for;
+
+use std::{
+    any::Any,
+    ops::{ControlFlow, Range},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use crate::{
     ToggleExpandItem,
+    attach_modal::AttachModal,
     new_process_modal::resolve_path,
     persistence::{self, DebuggerPaneItem, SerializedLayout},
 };
 
 use super::DebugPanelItemEvent;
+use adapter_log::AdapterLog;
 use anyhow::{Context as _, Result, anyhow};
 use breakpoint_list::BreakpointList;
+use collection_table::CollectionTable;
 use collections::{HashMap, IndexMap};
 use console::Console;
 use dap::{
-    Capabilities, DapRegistry, RunInTerminalRequestArguments, Thread,
+    Capabilities, DapRegistry, RunInTerminalRequestArguments, RunInTerminalRequestArgumentsKind,
+    SteppingGranularity, Thread,
     adapters::{DebugAdapterName, DebugTaskDefinition},
     client::SessionId,
-    debugger_settings::DebuggerSettings,
+    debugger_settings::{DebuggerSettings, merge_adapter_defaults},
+};
+use disassembly_view::DisassemblyView;
+use exception_popover::ExceptionPopover;
+use editor::{Editor, MultiBuffer};
+use futures::{
+    SinkExt,
+    channel::{mpsc, oneshot},
 };
-use futures::{SinkExt, channel::mpsc};
 use gpui::{
     Action as _, AnyView, AppContext, Axis, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
-    NoAction, Pixels, Point, Subscription, Task, WeakEntity,
+    NoAction, Pixels, Point, PromptLevel, Subscription, Task, WeakEntity,
 };
 use language::Buffer;
 use loaded_source_list::LoadedSourceList;
 use module_list::ModuleList;
 use project::{
     Project, WorktreeId,
-    debugger::session::{Session, SessionEvent, ThreadId, ThreadStatus},
+    debugger::session::{HotReloadKind, Session, SessionEvent, StopMode, ThreadId, ThreadStatus},
     terminals::TerminalKind,
 };
+use register_list::RegisterList;
 use rpc::proto::ViewId;
 use serde_json::Value;
 use settings::Settings;
 use stack_frame_list::StackFrameList;
 use task::{
-    BuildTaskDefinition, DebugScenario, ShellBuilder, SpawnInTerminal, TaskContext, ZedDebugConfig,
-    substitute_variables_in_str,
+    BuildTaskDefinition, DebugScenario, ShellBuilder, SpawnInTerminal, TaskContext, WaitForTarget,
+    ZedDebugConfig, substitute_variables_in_str,
 };
 use terminal_view::TerminalView;
 use ui::{
-    ActiveTheme, AnyElement, App, ButtonCommon as _, Clickable as _, Context, FluentBuilder,
-    IconButton, IconName, IconSize, InteractiveElement, IntoElement, Label, LabelCommon as _,
-    ParentElement, Render, SharedString, StatefulInteractiveElement, Styled, Tab, Tooltip,
-    VisibleOnHover, VisualContext, Window, div, h_flex, v_flex,
+    ActiveTheme, AnyElement, App, Button, ButtonCommon as _, Clickable as _, Context,
+    FluentBuilder, IconButton, IconName, IconSize, InteractiveElement, IntoElement, Label,
+    LabelCommon as _, LabelSize, ParentElement, Render, SharedString, StatefulInteractiveElement,
+    Styled, Tab, Tooltip, VisibleOnHover, VisualContext, Window, div, h_flex, v_flex,
 };
 use util::ResultExt;
-use variable_list::VariableList;
+use variable_list::{VariableList, VariableListEvent};
+use variable_snapshots::VariableSnapshots;
 use workspace::{
     ActivePaneDecorator, DraggedTab, Item, ItemHandle, Member, Pane, PaneGroup, SplitDirection,
     Workspace, item::TabContentParams, move_item, pane::Event,
 };
 
+/// Placeholder in a scenario's config for a process the user picks when the scenario starts.
+const PID_VARIABLE: &str = "${pid}";
+/// How long to wait before restarting a session whose debuggee exited unexpectedly.
+const RESTART_COUNTDOWN_SECONDS: u64 = 3;
+
+/// A restart scheduled after the debuggee exited with a non-zero code, for scenarios with
+/// `restartOnExit` set. Dropping it cancels the restart.
+struct PendingRestart {
+    exit_code: String,
+    seconds_left: u64,
+    attempt: u32,
+    max_restarts: Option<u32>,
+    _task: Task<()>,
+}
+
 pub struct RunningState {
     session: Entity<Session>,
     thread_id: Option<ThreadId>,
@@ -72,12 +118,21 @@ pub struct RunningState {
     loaded_sources_list: Entity<LoadedSourceList>,
     pub debug_terminal: Entity<DebugTerminal>,
     module_list: Entity<module_list::ModuleList>,
+    register_list: Entity<RegisterList>,
+    disassembly_view: Entity<DisassemblyView>,
+    collection_table: Entity<CollectionTable>,
+    variable_snapshots: Entity<VariableSnapshots>,
+    adapter_log: Entity<AdapterLog>,
     console: Entity<Console>,
     breakpoint_list: Entity<BreakpointList>,
+    _exception_popover: Entity<ExceptionPopover>,
     panes: PaneGroup,
     active_pane: Entity<Pane>,
     pane_close_subscriptions: HashMap<EntityId, Subscription>,
     dock_axis: Axis,
+    pending_restart: Option<PendingRestart>,
+    /// Overrides the `stepping_granularity` setting for this session.
+    stepping_granularity: Option<SteppingGranularity>,
     _schedule_serialize: Option<Task<()>>,
 }
 
@@ -126,6 +181,7 @@ impl Render for RunningState {
             .size_full()
             .key_context("DebugSessionItem")
             .track_focus(&self.focus_handle(cx))
+            .children(self.render_pending_restart(cx))
             .child(h_flex().flex_1().child(pane))
     }
 }
@@ -571,6 +627,71 @@ impl RunningState {
         }
     }
 
+    pub(crate) fn config_refers_to_pid(config: &serde_json::Value) -> bool {
+        match config {
+            serde_json::Value::Object(obj) => obj.values().any(Self::config_refers_to_pid),
+            serde_json::Value::Array(array) => array.iter().any(Self::config_refers_to_pid),
+            serde_json::Value::String(s) => s.contains(PID_VARIABLE),
+            _ => false,
+        }
+    }
+
+    /// Replaces `${pid}` with the picked process id, as a number when it makes up the whole
+    /// value, as adapters expect for fields like `processId`.
+    pub(crate) fn substitute_pid_in_config(config: &mut serde_json::Value, pid: u32) {
+        if config.as_str() == Some(PID_VARIABLE) {
+            *config = pid.into();
+            return;
+        }
+        match config {
+            serde_json::Value::Object(obj) => {
+                obj.values_mut()
+                    .for_each(|value| Self::substitute_pid_in_config(value, pid));
+            }
+            serde_json::Value::Array(array) => {
+                array
+                    .iter_mut()
+                    .for_each(|value| Self::substitute_pid_in_config(value, pid));
+            }
+            serde_json::Value::String(s) => {
+                *s = s.replace(PID_VARIABLE, &pid.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn pick_pid(
+        &self,
+        adapter: SharedString,
+        label: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<u32> {
+        let (pid_sender, pid_receiver) = oneshot::channel();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let weak_workspace = cx.weak_entity();
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    AttachModal::pick_pid(
+                        ZedDebugConfig {
+                            adapter,
+                            label,
+                            request: dap::DebugRequest::Attach(task::AttachRequest {
+                                process_id: None,
+                            }),
+                            stop_on_entry: None,
+                        },
+                        weak_workspace,
+                        pid_sender,
+                        window,
+                        cx,
+                    )
+                });
+            })
+            .ok();
+        pid_receiver
+    }
+
     pub(crate) fn relativize_paths(
         key: Option<&str>,
         config: &mut serde_json::Value,
@@ -628,11 +749,27 @@ impl RunningState {
 
         let loaded_source_list = cx.new(|cx| LoadedSourceList::new(session.clone(), cx));
 
+        let register_list =
+            cx.new(|cx| RegisterList::new(session.clone(), stack_frame_list.clone(), cx));
+
+        let disassembly_view = cx.new({
+            let weak_state = cx.weak_entity();
+            |cx| DisassemblyView::new(session.clone(), weak_state, stack_frame_list.clone(), cx)
+        });
+
+        let collection_table = cx.new(|cx| CollectionTable::new(session.clone(), cx));
+
+        let variable_snapshots =
+            cx.new(|cx| VariableSnapshots::new(variable_list.clone(), window, cx));
+
+        let adapter_log = cx.new(|cx| AdapterLog::new(session.clone(), cx));
+
         let console = cx.new(|cx| {
             Console::new(
                 session.clone(),
                 stack_frame_list.clone(),
                 variable_list.clone(),
+                workspace.clone(),
                 window,
                 cx,
             )
@@ -641,8 +778,35 @@ impl RunningState {
         let breakpoint_list =
             BreakpointList::new(Some(session.clone()), workspace.clone(), &project, cx);
 
+        let exception_popover = cx.new(|cx| {
+            ExceptionPopover::new(
+                session.clone(),
+                workspace.clone(),
+                project.read(cx).breakpoint_store(),
+                cx,
+            )
+        });
+
         let _subscriptions = vec![
             cx.observe(&module_list, |_, _, cx| cx.notify()),
+            cx.subscribe_in(&variable_list, window, |this, _, event, window, cx| match event {
+                VariableListEvent::ViewAsTable(variable) => {
+                    this.collection_table.update(cx, |table, cx| table.show(variable.clone(), cx));
+                    this.ensure_pane_item(DebuggerPaneItem::CollectionTable, window, cx);
+                    this.activate_item(DebuggerPaneItem::CollectionTable, window, cx);
+                }
+                VariableListEvent::CaptureSnapshot => {
+                    this.variable_snapshots
+                        .update(cx, |snapshots, cx| snapshots.capture(window, cx));
+                    this.ensure_pane_item(DebuggerPaneItem::VariableSnapshots, window, cx);
+                    this.activate_item(DebuggerPaneItem::VariableSnapshots, window, cx);
+                }
+                VariableListEvent::ViewFullText {
+                    title,
+                    text,
+                    is_json,
+                } => this.open_full_text(title.clone(), text.clone(), *is_json, window, cx),
+            }),
             cx.subscribe_in(&session, window, |this, _, event, window, cx| {
                 match event {
                     SessionEvent::Stopped(thread_id) => {
@@ -686,6 +850,13 @@ impl RunningState {
                     SessionEvent::RunInTerminal { request, sender } => this
                         .handle_run_in_terminal(request, sender.clone(), window, cx)
                         .detach_and_log_err(cx),
+                    SessionEvent::Exited(event) if event.exit_code != 0 => {
+                        this.schedule_restart(event.exit_code.to_string(), window, cx);
+                    }
+                    SessionEvent::AdapterFailed => {
+                        this.ensure_pane_item(DebuggerPaneItem::AdapterLog, window, cx);
+                        this.activate_item(DebuggerPaneItem::AdapterLog, window, cx);
+                    }
 
                     _ => {}
                 }
@@ -709,6 +880,11 @@ impl RunningState {
                 &console,
                 &breakpoint_list,
                 &loaded_source_list,
+                &register_list,
+                &disassembly_view,
+                &collection_table,
+                &variable_snapshots,
+                &adapter_log,
                 &debug_terminal,
                 &mut pane_close_subscriptions,
                 window,
@@ -750,16 +926,123 @@ impl RunningState {
             panes,
             active_pane,
             module_list,
+            register_list,
+            disassembly_view,
+            collection_table,
+            variable_snapshots,
+            adapter_log,
             console,
             breakpoint_list,
+            _exception_popover: exception_popover,
             loaded_sources_list: loaded_source_list,
             pane_close_subscriptions,
             debug_terminal,
             dock_axis,
+            pending_restart: None,
+            stepping_granularity: None,
             _schedule_serialize: None,
         }
     }
 
+    pub(crate) fn add_watch(
+        &mut self,
+        expression: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.variable_list
+            .update(cx, |list, cx| list.add_watch(expression, cx));
+        self.ensure_pane_item(DebuggerPaneItem::Variables, window, cx);
+        self.activate_item(DebuggerPaneItem::Variables, window, cx);
+    }
+
+    /// Evaluates `expression` in the selected stack frame as if it was entered in the console,
+    /// and focuses the console to show the result.
+    pub(crate) fn evaluate_in_console(
+        &mut self,
+        expression: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let stack_frame_id = self.selected_stack_frame_id(cx);
+        self.session.update(cx, |session, cx| {
+            session
+                .evaluate(
+                    expression,
+                    Some(dap::EvaluateArgumentsContext::Repl),
+                    stack_frame_id,
+                    None,
+                    cx,
+                )
+                .detach();
+        });
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_panel::<crate::DebugPanel>(window, cx);
+            })
+            .log_err();
+        self.ensure_pane_item(DebuggerPaneItem::Console, window, cx);
+        self.activate_item(DebuggerPaneItem::Console, window, cx);
+        window.focus(&self.console.focus_handle(cx));
+    }
+
+    /// Opens `text` in a read-only editor tab, highlighted as JSON when `is_json` is set.
+    fn open_full_text(
+        &self,
+        title: SharedString,
+        text: String,
+        is_json: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let language = is_json.then(|| {
+            workspace
+                .read(cx)
+                .app_state()
+                .languages
+                .language_for_name("JSON")
+        });
+
+        window
+            .spawn(cx, async move |cx| {
+                let language = match language {
+                    Some(language) => language.await.log_err(),
+                    None => None,
+                };
+                workspace.update_in(cx, |workspace, window, cx| {
+                    let project = workspace.project().clone();
+                    if !project.read(cx).is_local() {
+                        anyhow::bail!("Viewing full values is not supported in remote projects");
+                    }
+
+                    let buffer = project.update(cx, |project, cx| {
+                        project.create_local_buffer(&text, language, cx)
+                    });
+                    let buffer = cx
+                        .new(|cx| MultiBuffer::singleton(buffer, cx).with_title(title.to_string()));
+                    workspace.add_item_to_active_pane(
+                        Box::new(cx.new(|cx| {
+                            let mut editor =
+                                Editor::for_multibuffer(buffer, Some(project), window, cx);
+                            editor.set_read_only(true);
+                            editor.set_breadcrumb_header(title.to_string());
+                            editor
+                        })),
+                        None,
+                        true,
+                        window,
+                        cx,
+                    );
+                    anyhow::Ok(())
+                })??;
+                anyhow::Ok(())
+            })
+            .detach_and_log_err(cx);
+    }
+
     pub(crate) fn remove_pane_item(
         &mut self,
         item_kind: DebuggerPaneItem,
@@ -806,6 +1089,10 @@ impl RunningState {
         let weak_project = project.downgrade();
         let weak_workspace = workspace.downgrade();
         let is_local = project.read(cx).is_local();
+        let adapter_defaults = DebuggerSettings::get_global(cx)
+            .adapters
+            .get(scenario.adapter.as_ref())
+            .map(|adapter| Value::Object(adapter.defaults.clone()));
         cx.spawn_in(window, async move |this, cx| {
             let DebugScenario {
                 adapter,
@@ -813,10 +1100,52 @@ impl RunningState {
                 build,
                 mut config,
                 tcp_connection,
+                wait_for,
             } = scenario;
             Self::relativize_paths(None, &mut config, &task_context);
             Self::substitute_variables_in_config(&mut config, &task_context);
 
+            if let Some(mut wait_for) = wait_for {
+                anyhow::ensure!(is_local, "`wait_for` is only supported in local projects");
+                if let WaitForTarget::PidFile(path) = &mut wait_for.target {
+                    if let Some(substituted) =
+                        substitute_variables_in_str(&path.to_string_lossy(), &task_context)
+                    {
+                        *path = PathBuf::from(substituted);
+                    }
+                }
+                this.update(cx, |this, cx| {
+                    this.session.update(cx, |session, cx| {
+                        session
+                            .console_output(cx)
+                            .unbounded_send(format!(
+                                "Waiting for {}…",
+                                wait_for::describe(&wait_for.target)
+                            ))
+                            .ok();
+                    })
+                })?;
+                let pid = wait_for::wait_for(
+                    &wait_for,
+                    task_context.cwd.as_deref(),
+                    cx.background_executor().clone(),
+                )
+                .await?;
+                if let Some(pid) = pid {
+                    Self::substitute_pid_in_config(&mut config, pid);
+                }
+            }
+
+            if Self::config_refers_to_pid(&config) {
+                let pid = this
+                    .update_in(cx, |this, window, cx| {
+                        this.pick_pid(adapter.clone(), label.clone(), window, cx)
+                    })?
+                    .await
+                    .context("No process was picked to attach to")?;
+                Self::substitute_pid_in_config(&mut config, pid);
+            }
+
             let request_type = match dap_registry
                 .adapter(&adapter)
                 .with_context(|| format!("{}: is not a valid adapter name", &adapter)) {
@@ -973,6 +1302,12 @@ impl RunningState {
                 anyhow::bail!("Zed cannot determine how to run this debug scenario. `build` field was not provided and Debug Adapter won't accept provided configuration because: {e}");
             };
 
+            if let Some(mut defaults) = adapter_defaults {
+                Self::relativize_paths(None, &mut defaults, &task_context);
+                Self::substitute_variables_in_config(&mut defaults, &task_context);
+                merge_adapter_defaults(&mut config, &defaults);
+            }
+
             Ok(DebugTaskDefinition {
                 label,
                 adapter: DebugAdapterName(adapter),
@@ -985,7 +1320,7 @@ impl RunningState {
     fn handle_run_in_terminal(
         &self,
         request: &RunInTerminalRequestArguments,
-        mut sender: mpsc::Sender<Result<u32>>,
+        mut sender: mpsc::Sender<Result<Option<u32>>>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
@@ -1005,17 +1340,6 @@ impl RunningState {
 
         let mut args = request.args.clone();
 
-        // Handle special case for NodeJS debug adapter
-        // If only the Node binary path is provided, we set the command to None
-        // This prevents the NodeJS REPL from appearing, which is not the desired behavior
-        // The expected usage is for users to provide their own Node command, e.g., `node test.js`
-        // This allows the NodeJS debug client to attach correctly
-        let command = if args.len() > 1 {
-            Some(args.remove(0))
-        } else {
-            None
-        };
-
         let mut envs: HashMap<String, String> =
             self.session.read(cx).task_context().project_env.clone();
         if let Some(Value::Object(env)) = &request.env {
@@ -1029,6 +1353,27 @@ impl RunningState {
             }
         }
 
+        let external = matches!(request.kind, Some(RunInTerminalRequestArgumentsKind::External))
+            || DebuggerSettings::get_global(cx).run_in_external_terminal;
+        if external && !args.is_empty() {
+            let title = request.title.clone().unwrap_or_else(|| args[0].clone());
+            // The terminal app doesn't report the debuggee's process id, so none is sent back.
+            let result =
+                external_terminal::run_in_external_terminal(title, args, cwd, envs).map(|()| None);
+            return cx.background_spawn(async move { anyhow::Ok(sender.send(result).await?) });
+        }
+
+        // Handle special case for NodeJS debug adapter
+        // If only the Node binary path is provided, we set the command to None
+        // This prevents the NodeJS REPL from appearing, which is not the desired behavior
+        // The expected usage is for users to provide their own Node command, e.g., `node test.js`
+        // This allows the NodeJS debug client to attach correctly
+        let command = if args.len() > 1 {
+            Some(args.remove(0))
+        } else {
+            None
+        };
+
         let shell = project.read(cx).terminal_settings(&cwd, cx).shell.clone();
         let kind = if let Some(command) = command {
             let title = request.title.clone().unwrap_or(command.clone());
@@ -1080,7 +1425,7 @@ impl RunningState {
                 terminal
                     .pty_info
                     .pid()
-                    .map(|pid| pid.as_u32())
+                    .map(|pid| Some(pid.as_u32()))
                     .context("Terminal was spawned but PID was not available")
             })?
         });
@@ -1152,6 +1497,41 @@ impl RunningState {
                 None,
                 cx,
             )),
+            DebuggerPaneItem::Registers => Box::new(SubView::new(
+                self.register_list.focus_handle(cx),
+                self.register_list.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::Disassembly => Box::new(SubView::new(
+                self.disassembly_view.focus_handle(cx),
+                self.disassembly_view.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::CollectionTable => Box::new(SubView::new(
+                self.collection_table.focus_handle(cx),
+                self.collection_table.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::VariableSnapshots => Box::new(SubView::new(
+                self.variable_snapshots.focus_handle(cx),
+                self.variable_snapshots.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
+            DebuggerPaneItem::AdapterLog => Box::new(SubView::new(
+                self.adapter_log.focus_handle(cx),
+                self.adapter_log.clone().into(),
+                item_kind,
+                None,
+                cx,
+            )),
         }
     }
 
@@ -1326,16 +1706,72 @@ impl RunningState {
         &self.stack_frame_list
     }
 
+    /// Opens the disassembly at the frame on the stack that's in `rows` of `abs_path`, e.g. in
+    /// the function the cursor is in, selecting that frame.
+    pub(crate) fn open_disassembly_at(
+        &mut self,
+        abs_path: &Path,
+        rows: Range<u32>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !DebuggerPaneItem::Disassembly.is_supported(&self.capabilities(cx)) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support disassembling"
+            )));
+        }
+        let Some(stack_frame_id) = self
+            .stack_frame_list
+            .read(cx)
+            .frame_in_rows(abs_path, rows, cx)
+        else {
+            return Task::ready(Err(anyhow!(
+                "None of the stack frames are in the function at the cursor"
+            )));
+        };
+
+        self.ensure_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+        self.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        self.stack_frame_list.update(cx, |stack_frame_list, cx| {
+            stack_frame_list.go_to_stack_frame(stack_frame_id, window, cx)
+        })
+    }
+
     #[cfg(test)]
     pub fn console(&self) -> &Entity<Console> {
         &self.console
     }
 
+    #[cfg(test)]
+    pub(crate) fn breakpoint_list(&self) -> &Entity<BreakpointList> {
+        &self.breakpoint_list
+    }
+
     #[cfg(test)]
     pub(crate) fn module_list(&self) -> &Entity<ModuleList> {
         &self.module_list
     }
 
+    #[cfg(test)]
+    pub(crate) fn register_list(&self) -> &Entity<RegisterList> {
+        &self.register_list
+    }
+
+    #[cfg(test)]
+    pub(crate) fn disassembly_view(&self) -> &Entity<DisassemblyView> {
+        &self.disassembly_view
+    }
+
+    #[cfg(test)]
+    pub(crate) fn collection_table(&self) -> &Entity<CollectionTable> {
+        &self.collection_table
+    }
+
+    #[cfg(test)]
+    pub(crate) fn variable_snapshots(&self) -> &Entity<VariableSnapshots> {
+        &self.variable_snapshots
+    }
+
     pub(crate) fn activate_item(&self, item: DebuggerPaneItem, window: &mut Window, cx: &mut App) {
         let (variable_list_position, pane) = self
             .panes
@@ -1422,12 +1858,36 @@ impl RunningState {
         });
     }
 
+    /// The granularity Step Over, Step Into, Step Out and Step Back use.
+    pub(crate) fn stepping_granularity(&self, cx: &App) -> SteppingGranularity {
+        self.stepping_granularity
+            .unwrap_or_else(|| DebuggerSettings::get_global(cx).stepping_granularity)
+    }
+
+    pub(crate) fn set_stepping_granularity(
+        &mut self,
+        granularity: SteppingGranularity,
+        cx: &mut Context<Self>,
+    ) {
+        self.stepping_granularity = Some(granularity);
+        cx.notify();
+    }
+
+    pub(crate) fn cycle_stepping_granularity(&mut self, cx: &mut Context<Self>) {
+        let granularity = match self.stepping_granularity(cx) {
+            SteppingGranularity::Statement => SteppingGranularity::Line,
+            SteppingGranularity::Line => SteppingGranularity::Instruction,
+            SteppingGranularity::Instruction => SteppingGranularity::Statement,
+        };
+        self.set_stepping_granularity(granularity, cx);
+    }
+
     pub fn step_over(&mut self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
         };
 
-        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+        let granularity = self.stepping_granularity(cx);
 
         self.session().update(cx, |state, cx| {
             state.step_over(thread_id, granularity, cx);
@@ -1439,7 +1899,7 @@ impl RunningState {
             return;
         };
 
-        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+        let granularity = self.stepping_granularity(cx);
 
         self.session().update(cx, |state, cx| {
             state.step_in(thread_id, granularity, cx);
@@ -1451,7 +1911,7 @@ impl RunningState {
             return;
         };
 
-        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+        let granularity = self.stepping_granularity(cx);
 
         self.session().update(cx, |state, cx| {
             state.step_out(thread_id, granularity, cx);
@@ -1463,19 +1923,155 @@ impl RunningState {
             return;
         };
 
-        let granularity = DebuggerSettings::get_global(cx).stepping_granularity;
+        let granularity = self.stepping_granularity(cx);
 
         self.session().update(cx, |state, cx| {
             state.step_back(thread_id, granularity, cx);
         });
     }
 
-    pub fn restart_session(&self, cx: &mut Context<Self>) {
+    pub(crate) fn reverse_continue(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
         self.session().update(cx, |state, cx| {
+            state.reverse_continue(thread_id, cx);
+        });
+    }
+
+    pub fn restart_session(&mut self, cx: &mut Context<Self>) {
+        self.pending_restart = None;
+        self.session().update(cx, |state, cx| {
+            state.set_auto_restart_count(0);
             state.restart(None, cx);
         });
     }
 
+    /// Restarts the session after a countdown if its scenario asks for it with `restartOnExit`,
+    /// up to `maxRestarts` times in a row.
+    fn schedule_restart(&mut self, exit_code: String, window: &mut Window, cx: &mut Context<Self>) {
+        let session = self.session.read(cx);
+        // The user stopped the session, so the exit was expected.
+        if session.is_terminated() {
+            return;
+        }
+        let Some(configuration) = session
+            .binary()
+            .map(|binary| &binary.request_args.configuration)
+        else {
+            return;
+        };
+        if !configuration
+            .get("restartOnExit")
+            .and_then(Value::as_bool)
+            .unwrap_or_default()
+        {
+            return;
+        }
+        let max_restarts = configuration
+            .get("maxRestarts")
+            .and_then(Value::as_u64)
+            .map(|max_restarts| max_restarts.min(u32::MAX as u64) as u32);
+        let attempt = session.auto_restart_count() + 1;
+        if max_restarts.is_some_and(|max_restarts| attempt > max_restarts) {
+            return;
+        }
+
+        let task = cx.spawn_in(window, async move |this, cx| {
+            for seconds_left in (1..=RESTART_COUNTDOWN_SECONDS).rev() {
+                this.update(cx, |this, cx| {
+                    if let Some(pending_restart) = &mut this.pending_restart {
+                        pending_restart.seconds_left = seconds_left;
+                        cx.notify();
+                    }
+                })
+                .ok();
+                cx.background_executor().timer(Duration::from_secs(1)).await;
+            }
+
+            this.update(cx, |this, cx| {
+                this.pending_restart = None;
+                this.session.update(cx, |session, cx| {
+                    session.set_auto_restart_count(attempt);
+                    session.restart(None, cx);
+                });
+                cx.notify();
+            })
+            .ok();
+        });
+        self.pending_restart = Some(PendingRestart {
+            exit_code,
+            seconds_left: RESTART_COUNTDOWN_SECONDS,
+            attempt,
+            max_restarts,
+            _task: task,
+        });
+        cx.notify();
+    }
+
+    pub(crate) fn cancel_pending_restart(&mut self, cx: &mut Context<Self>) {
+        if self.pending_restart.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    fn render_pending_restart(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let pending_restart = self.pending_restart.as_ref()?;
+        let attempt = pending_restart
+            .max_restarts
+            .map(|max_restarts| {
+                format!(" (attempt {} of {})", pending_restart.attempt, max_restarts)
+            })
+            .unwrap_or_default();
+
+        Some(
+            h_flex()
+                .w_full()
+                .px_2()
+                .py_1()
+                .gap_2()
+                .justify_between()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .bg(cx.theme().status().warning_background)
+                .child(
+                    Label::new(format!(
+                        "Debuggee exited with code {}. Restarting in {}s{}",
+                        pending_restart.exit_code, pending_restart.seconds_left, attempt
+                    ))
+                    .size(LabelSize::Small),
+                )
+                .child(
+                    Button::new("cancel-pending-restart", "Cancel Restart")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.cancel_pending_restart(cx);
+                        })),
+                ),
+        )
+    }
+
+    pub(crate) fn freeze_thread(&self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.freeze_thread(thread_id, cx);
+        });
+    }
+
+    pub(crate) fn thaw_thread(&self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.thaw_thread(thread_id, cx);
+        });
+    }
+
     pub fn pause_thread(&self, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
@@ -1504,10 +2100,20 @@ impl RunningState {
         })
     }
 
-    pub fn stop_thread(&self, cx: &mut Context<Self>) {
+    pub fn stop_thread(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         let Some(thread_id) = self.thread_id else {
             return;
         };
+        if !self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_terminate_threads_request
+            .unwrap_or_default()
+        {
+            self.stop_session(window, cx);
+            return;
+        }
 
         self.workspace
             .update(cx, |workspace, cx| {
@@ -1526,6 +2132,73 @@ impl RunningState {
         });
     }
 
+    /// Stops the session, asking the user what should happen to the debuggee when the adapter
+    /// supports more than one way of leaving it.
+    pub(crate) fn stop_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let session = self.session.read(cx);
+        let mut stop_modes = [StopMode::Terminate, StopMode::Disconnect, StopMode::Suspend]
+            .into_iter()
+            .filter(|mode| session.supports_stop_mode(*mode))
+            .collect::<Vec<_>>();
+        let default_stop_mode = session.default_stop_mode();
+        if stop_modes.len() <= 1 {
+            let mode = stop_modes.pop().unwrap_or(default_stop_mode);
+            self.stop_with_mode(mode, cx);
+            return;
+        }
+
+        // The first answer is the default one.
+        stop_modes.sort_by_key(|mode| *mode != default_stop_mode);
+        let answers = stop_modes
+            .iter()
+            .map(StopMode::label)
+            .chain(["Cancel"])
+            .collect::<Vec<_>>();
+        let answer = window.prompt(
+            PromptLevel::Info,
+            "Stop debugging?",
+            Some("Choose what happens to the program being debugged."),
+            &answers,
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            let Some(mode) = answer
+                .await
+                .ok()
+                .and_then(|ix| stop_modes.get(ix).copied())
+            else {
+                return;
+            };
+            this.update(cx, |this, cx| this.stop_with_mode(mode, cx))
+                .ok();
+        })
+        .detach();
+    }
+
+    pub(crate) fn stop_with_mode(&mut self, mode: StopMode, cx: &mut Context<Self>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .project()
+                    .read(cx)
+                    .breakpoint_store()
+                    .update(cx, |store, cx| {
+                        store.remove_active_position(Some(self.session_id), cx)
+                    })
+            })
+            .log_err();
+
+        self.session.update(cx, |session, cx| {
+            session.stop(mode, cx).detach();
+        })
+    }
+
+    pub(crate) fn hot_reload(&self, kind: HotReloadKind, cx: &mut Context<Self>) {
+        self.session
+            .update(cx, |session, cx| session.hot_reload(kind, cx))
+            .detach_and_log_err(cx);
+    }
+
     pub fn detach_client(&self, cx: &mut Context<Self>) {
         self.session().update(cx, |state, cx| {
             state.disconnect_client(cx);
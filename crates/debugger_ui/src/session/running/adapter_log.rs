@@ -0,0 +1,98 @@
+use gpui::{AnyElement, Empty, Entity, FocusHandle, Focusable, ListState, Subscription, list};
+use project::debugger::session::{Session, SessionEvent};
+use settings::Settings as _;
+use theme::ThemeSettings;
+use ui::prelude::*;
+
+/// Shows what a session's debug adapter wrote to stderr, and why it failed to start, if it did.
+pub(crate) struct AdapterLog {
+    list: ListState,
+    invalidate: bool,
+    focus_handle: FocusHandle,
+    _subscription: Subscription,
+    session: Entity<Session>,
+}
+
+impl AdapterLog {
+    pub fn new(session: Entity<Session>, cx: &mut Context<Self>) -> Self {
+        let weak_entity = cx.weak_entity();
+        let focus_handle = cx.focus_handle();
+
+        let list = ListState::new(
+            0,
+            gpui::ListAlignment::Bottom,
+            px(1000.),
+            move |ix, _window, cx| {
+                weak_entity
+                    .upgrade()
+                    .map(|adapter_log| adapter_log.update(cx, |this, cx| this.render_entry(ix, cx)))
+                    .unwrap_or(div().into_any())
+            },
+        );
+
+        let _subscription = cx.subscribe(&session, |this, _, event, cx| {
+            if let SessionEvent::AdapterLog = event {
+                this.invalidate = true;
+                cx.notify();
+            }
+        });
+
+        Self {
+            list,
+            session,
+            focus_handle,
+            _subscription,
+            invalidate: true,
+        }
+    }
+
+    fn render_entry(&mut self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let Some(line) = self.session.read(cx).adapter_log().nth(ix).cloned() else {
+            return Empty.into_any();
+        };
+
+        let color = if line.starts_with("error: ") {
+            Color::Error
+        } else {
+            Color::Default
+        };
+        div()
+            .w_full()
+            .px_1()
+            .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+            .child(Label::new(line).size(LabelSize::Small).color(color))
+            .into_any()
+    }
+}
+
+impl Focusable for AdapterLog {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for AdapterLog {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let len = self.session.read(cx).adapter_log().count();
+        if self.invalidate {
+            self.list.reset(len);
+            self.invalidate = false;
+        }
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .map(|this| {
+                if len == 0 {
+                    this.child(
+                        Label::new("The debug adapter hasn't written anything to stderr.")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                } else {
+                    this.child(list(self.list.clone()).size_full())
+                }
+            })
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeSet,
     ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
@@ -8,28 +9,42 @@ use std::{
 use dap::ExceptionBreakpointsFilter;
 use editor::Editor;
 use gpui::{
-    AppContext, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful, Task,
-    UniformListScrollHandle, WeakEntity, uniform_list,
+    AppContext, ClickEvent, Entity, FocusHandle, Focusable, Modifiers, MouseButton, ScrollStrategy,
+    Stateful, Subscription, Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
 };
 use language::Point;
 use project::{
     Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
-        session::Session,
+        breakpoint_store::{
+            BreakpointEditAction, BreakpointSessionState, BreakpointState, BreakpointStore,
+            SourceBreakpoint,
+        },
+        session::{Session, SessionEvent},
     },
     worktree_store::WorktreeStore,
 };
 use ui::{
-    App, ButtonCommon, Clickable, Color, Context, Div, FluentBuilder as _, Icon, IconButton,
-    IconName, Indicator, InteractiveElement, IntoElement, Label, LabelCommon, LabelSize, ListItem,
-    ParentElement, Render, Scrollbar, ScrollbarState, SharedString, StatefulInteractiveElement,
-    Styled, Toggleable, Tooltip, Window, div, h_flex, px, v_flex,
+    ActiveTheme, App, Button, ButtonCommon, Clickable, Color, Context, Div, FluentBuilder as _,
+    Icon, IconButton, IconName, Indicator, InteractiveElement, IntoElement, Label, LabelCommon,
+    LabelSize, ListItem, ParentElement, Render, Scrollbar, ScrollbarState, SharedString,
+    StatefulInteractiveElement, Styled, Toggleable, Tooltip, Window, div, h_flex, px, v_flex,
 };
 use util::ResultExt;
 use workspace::Workspace;
 use zed_actions::{ToggleEnableBreakpoint, UnsetBreakpoint};
 
+use crate::AddInstructionBreakpoint;
+
+actions!(
+    breakpoint_list,
+    [
+        EnableSelectedBreakpoints,
+        DisableSelectedBreakpoints,
+        SelectAllBreakpointsInFile
+    ]
+);
+
 pub(crate) struct BreakpointList {
     workspace: WeakEntity<Workspace>,
     breakpoint_store: Entity<BreakpointStore>,
@@ -42,6 +57,9 @@ pub(crate) struct BreakpointList {
     focus_handle: FocusHandle,
     scroll_handle: UniformListScrollHandle,
     selected_ix: Option<usize>,
+    /// Entries picked with shift/secondary-click; bulk actions apply to these when non-empty.
+    multi_selection: BTreeSet<usize>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl Focusable for BreakpointList {
@@ -64,18 +82,31 @@ impl BreakpointList {
         let scroll_handle = UniformListScrollHandle::new();
         let scrollbar_state = ScrollbarState::new(scroll_handle.clone());
 
-        cx.new(|_| Self {
-            breakpoint_store,
-            worktree_store,
-            scrollbar_state,
-            breakpoints: Default::default(),
-            hide_scrollbar_task: None,
-            show_scrollbar: false,
-            workspace,
-            session,
-            focus_handle,
-            scroll_handle,
-            selected_ix: None,
+        cx.new(|cx| {
+            let mut _subscriptions = vec![cx.observe(&breakpoint_store, |_, _, cx| cx.notify())];
+            if let Some(session) = &session {
+                _subscriptions.push(cx.subscribe(session, |_, _, event, cx| {
+                    if let SessionEvent::InstructionBreakpoints = event {
+                        cx.notify();
+                    }
+                }));
+            }
+
+            Self {
+                breakpoint_store,
+                worktree_store,
+                scrollbar_state,
+                breakpoints: Default::default(),
+                hide_scrollbar_task: None,
+                show_scrollbar: false,
+                workspace,
+                session,
+                focus_handle,
+                scroll_handle,
+                selected_ix: None,
+                multi_selection: BTreeSet::new(),
+                _subscriptions,
+            }
         })
     }
 
@@ -129,6 +160,7 @@ impl BreakpointList {
 
     fn select_ix(&mut self, ix: Option<usize>, cx: &mut Context<Self>) {
         self.selected_ix = ix;
+        self.multi_selection.clear();
         if let Some(ix) = ix {
             self.scroll_handle
                 .scroll_to_item(ix, ScrollStrategy::Center);
@@ -136,6 +168,127 @@ impl BreakpointList {
         cx.notify();
     }
 
+    fn click_ix(&mut self, ix: usize, modifiers: &Modifiers, cx: &mut Context<Self>) {
+        if modifiers.shift {
+            let anchor = self.selected_ix.unwrap_or(ix);
+            self.multi_selection = (anchor.min(ix)..=anchor.max(ix)).collect();
+            self.selected_ix = Some(anchor);
+        } else if modifiers.secondary() {
+            if self.multi_selection.is_empty() {
+                self.multi_selection.extend(self.selected_ix);
+            }
+            if !self.multi_selection.remove(&ix) {
+                self.multi_selection.insert(ix);
+            }
+            self.selected_ix = Some(ix);
+        } else {
+            self.select_ix(Some(ix), cx);
+            return;
+        }
+        cx.notify();
+    }
+
+    fn selected_line_breakpoints(&self) -> Vec<(Arc<Path>, u32, BreakpointState)> {
+        let selection = if self.multi_selection.is_empty() {
+            self.selected_ix.into_iter().collect()
+        } else {
+            self.multi_selection.clone()
+        };
+
+        selection
+            .into_iter()
+            .filter_map(|ix| match &self.breakpoints.get(ix)?.kind {
+                BreakpointEntryKind::LineBreakpoint(line_breakpoint) => Some((
+                    line_breakpoint.breakpoint.path.clone(),
+                    line_breakpoint.breakpoint.row,
+                    line_breakpoint.breakpoint.state,
+                )),
+                BreakpointEntryKind::ExceptionBreakpoint(_)
+                | BreakpointEntryKind::InstructionBreakpointsHeader
+                | BreakpointEntryKind::InstructionBreakpoint(_) => None,
+            })
+            .collect()
+    }
+
+    fn line_breakpoint_positions(&self) -> Vec<(Arc<Path>, u32)> {
+        self.breakpoints
+            .iter()
+            .filter_map(|entry| match &entry.kind {
+                BreakpointEntryKind::LineBreakpoint(line_breakpoint) => Some((
+                    line_breakpoint.breakpoint.path.clone(),
+                    line_breakpoint.breakpoint.row,
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn selected_breakpoint_rows(&self) -> Vec<(Arc<Path>, u32)> {
+        self.selected_line_breakpoints()
+            .into_iter()
+            .map(|(path, row, _)| (path, row))
+            .collect()
+    }
+
+    fn set_selected_breakpoints_state(&mut self, state: BreakpointState, cx: &mut Context<Self>) {
+        for (path, row, current_state) in self.selected_line_breakpoints() {
+            if current_state != state {
+                self.edit_line_breakpoint(path, row, BreakpointEditAction::InvertState, cx);
+            }
+        }
+        cx.notify();
+    }
+
+    fn enable_selected_breakpoints(
+        &mut self,
+        _: &EnableSelectedBreakpoints,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_selected_breakpoints_state(BreakpointState::Enabled, cx);
+    }
+
+    fn disable_selected_breakpoints(
+        &mut self,
+        _: &DisableSelectedBreakpoints,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_selected_breakpoints_state(BreakpointState::Disabled, cx);
+    }
+
+    fn select_all_breakpoints_in_file(
+        &mut self,
+        _: &SelectAllBreakpointsInFile,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(BreakpointEntryKind::LineBreakpoint(selected)) = self
+            .selected_ix
+            .and_then(|ix| self.breakpoints.get(ix))
+            .map(|entry| &entry.kind)
+        else {
+            return;
+        };
+        let path = selected.breakpoint.path.clone();
+
+        self.multi_selection = self
+            .breakpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, entry)| match &entry.kind {
+                BreakpointEntryKind::LineBreakpoint(line_breakpoint)
+                    if line_breakpoint.breakpoint.path == path =>
+                {
+                    Some(ix)
+                }
+                _ => None,
+            })
+            .collect();
+        cx.notify();
+    }
+
     fn select_next(&mut self, _: &menu::SelectNext, _window: &mut Window, cx: &mut Context<Self>) {
         let ix = match self.selected_ix {
             _ if self.breakpoints.len() == 0 => None,
@@ -205,7 +358,11 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
-            BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+            BreakpointEntryKind::InstructionBreakpointsHeader => {
+                window.dispatch_action(Box::new(AddInstructionBreakpoint), cx);
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(_)
+            | BreakpointEntryKind::InstructionBreakpoint(_) => {}
         }
     }
 
@@ -215,6 +372,20 @@ impl BreakpointList {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if !self.multi_selection.is_empty() {
+            let all_enabled = self
+                .selected_line_breakpoints()
+                .iter()
+                .all(|(_, _, state)| state.is_enabled());
+            let state = if all_enabled {
+                BreakpointState::Disabled
+            } else {
+                BreakpointState::Enabled
+            };
+            self.set_selected_breakpoints_state(state, cx);
+            return;
+        }
+
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -233,6 +404,15 @@ impl BreakpointList {
                     });
                 }
             }
+            BreakpointEntryKind::InstructionBreakpoint(instruction_breakpoint) => {
+                if let Some(session) = &self.session {
+                    let address = instruction_breakpoint.address.clone();
+                    session.update(cx, |session, cx| {
+                        session.toggle_instruction_breakpoint_enabled(&address, cx);
+                    });
+                }
+            }
+            BreakpointEntryKind::InstructionBreakpointsHeader => {}
         }
         cx.notify();
     }
@@ -243,6 +423,14 @@ impl BreakpointList {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if !self.multi_selection.is_empty() {
+            for (path, row, _) in self.selected_line_breakpoints() {
+                self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+            }
+            self.select_ix(None, cx);
+            return;
+        }
+
         let Some(entry) = self.selected_ix.and_then(|ix| self.breakpoints.get_mut(ix)) else {
             return;
         };
@@ -253,7 +441,16 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
             }
-            BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+            BreakpointEntryKind::InstructionBreakpoint(instruction_breakpoint) => {
+                if let Some(session) = &self.session {
+                    let address = instruction_breakpoint.address.clone();
+                    session.update(cx, |session, cx| {
+                        session.toggle_instruction_breakpoint(address, cx);
+                    });
+                }
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(_)
+            | BreakpointEntryKind::InstructionBreakpointsHeader => {}
         }
         cx.notify();
     }
@@ -275,6 +472,7 @@ impl BreakpointList {
 
     fn render_list(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let selected_ix = self.selected_ix;
+        let multi_selection = self.multi_selection.clone();
         let focus_handle = self.focus_handle.clone();
         uniform_list(
             "breakpoint-list",
@@ -286,7 +484,9 @@ impl BreakpointList {
                     .map(|(ix, breakpoint)| {
                         breakpoint
                             .render(ix, focus_handle.clone(), window, cx)
-                            .toggle_state(Some(ix) == selected_ix)
+                            .toggle_state(
+                                Some(ix) == selected_ix || multi_selection.contains(&ix),
+                            )
                             .into_any_element()
                     })
                     .collect()
@@ -296,6 +496,54 @@ impl BreakpointList {
         .flex_grow()
     }
 
+    fn render_bulk_actions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .px_2()
+            .py_1()
+            .gap_1()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                Label::new(format!("{} selected", self.multi_selection.len()))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("breakpoint-list-enable-selected", "Enable")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.enable_selected_breakpoints(
+                                    &EnableSelectedBreakpoints,
+                                    window,
+                                    cx,
+                                )
+                            })),
+                    )
+                    .child(
+                        Button::new("breakpoint-list-disable-selected", "Disable")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.disable_selected_breakpoints(
+                                    &DisableSelectedBreakpoints,
+                                    window,
+                                    cx,
+                                )
+                            })),
+                    )
+                    .child(
+                        Button::new("breakpoint-list-remove-selected", "Remove")
+                            .label_size(LabelSize::Small)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.unset_breakpoint(&UnsetBreakpoint, window, cx)
+                            })),
+                    ),
+            )
+    }
+
     fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Option<Stateful<Div>> {
         if !(self.show_scrollbar || self.scrollbar_state.is_dragging()) {
             return None;
@@ -338,6 +586,11 @@ impl Render for BreakpointList {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl ui::IntoElement {
         // let old_len = self.breakpoints.len();
         let breakpoints = self.breakpoint_store.read(cx).all_source_breakpoints(cx);
+        let session_id = self
+            .session
+            .as_ref()
+            .map(|session| session.read(cx).session_id());
+        let previous_line_breakpoints = self.line_breakpoint_positions();
         self.breakpoints.clear();
         let weak = cx.weak_entity();
         let breakpoints = breakpoints.into_iter().flat_map(|(path, mut breakpoints)| {
@@ -352,8 +605,22 @@ impl Render for BreakpointList {
                         .then(|| Path::new(worktree.read(cx).root_name()).join(relative_path))
                 });
             breakpoints.sort_by_key(|breakpoint| breakpoint.row);
+            let breakpoints = breakpoints
+                .into_iter()
+                .map(|breakpoint| {
+                    let session_state = session_id.and_then(|session_id| {
+                        self.breakpoint_store.read(cx).session_state_at_row(
+                            &path,
+                            breakpoint.row,
+                            session_id,
+                            cx,
+                        )
+                    });
+                    (breakpoint, session_state)
+                })
+                .collect::<Vec<_>>();
             let weak = weak.clone();
-            breakpoints.into_iter().filter_map(move |breakpoint| {
+            breakpoints.into_iter().filter_map(move |(breakpoint, session_state)| {
                 debug_assert_eq!(&path, &breakpoint.path);
                 let file_name = breakpoint.path.file_name()?;
 
@@ -379,6 +646,7 @@ impl Render for BreakpointList {
                         dir,
                         line,
                         breakpoint,
+                        session_state,
                     }),
                     weak,
                 })
@@ -397,8 +665,40 @@ impl Render for BreakpointList {
                     weak: weak.clone(),
                 })
         });
-        self.breakpoints
-            .extend(breakpoints.chain(exception_breakpoints));
+        let instruction_breakpoints = self
+            .session
+            .as_ref()
+            .filter(|session| session.read(cx).supports_instruction_breakpoints())
+            .into_iter()
+            .flat_map(|session| {
+                let header = BreakpointEntry {
+                    kind: BreakpointEntryKind::InstructionBreakpointsHeader,
+                    weak: weak.clone(),
+                };
+                let breakpoints = session
+                    .read(cx)
+                    .instruction_breakpoints()
+                    .map(|(address, is_enabled)| BreakpointEntry {
+                        kind: BreakpointEntryKind::InstructionBreakpoint(InstructionBreakpoint {
+                            address: address.clone(),
+                            is_enabled: *is_enabled,
+                        }),
+                        weak: weak.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                std::iter::once(header).chain(breakpoints)
+            });
+        self.breakpoints.extend(
+            breakpoints
+                .chain(exception_breakpoints)
+                .chain(instruction_breakpoints),
+        );
+        // The multi-selection holds entry indices, which point at other breakpoints once one is
+        // added or removed, e.g. from the gutter.
+        if self.line_breakpoint_positions() != previous_line_breakpoints {
+            self.multi_selection.clear();
+        }
+        let bulk_actions = (self.multi_selection.len() > 1).then(|| self.render_bulk_actions(cx));
         v_flex()
             .id("breakpoint-list")
             .key_context("BreakpointList")
@@ -419,8 +719,12 @@ impl Render for BreakpointList {
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::toggle_enable_breakpoint))
             .on_action(cx.listener(Self::unset_breakpoint))
+            .on_action(cx.listener(Self::enable_selected_breakpoints))
+            .on_action(cx.listener(Self::disable_selected_breakpoints))
+            .on_action(cx.listener(Self::select_all_breakpoints_in_file))
             .size_full()
             .m_0p5()
+            .children(bulk_actions)
             .child(self.render_list(window, cx))
             .children(self.render_vertical_scrollbar(cx))
     }
@@ -431,6 +735,7 @@ struct LineBreakpoint {
     dir: Option<SharedString>,
     line: u32,
     breakpoint: SourceBreakpoint,
+    session_state: Option<BreakpointSessionState>,
 }
 
 impl LineBreakpoint {
@@ -448,6 +753,19 @@ impl LineBreakpoint {
         let path = self.breakpoint.path.clone();
         let row = self.breakpoint.row;
         let is_enabled = self.breakpoint.state.is_enabled();
+        let is_verified = self
+            .session_state
+            .as_ref()
+            .is_none_or(|state| state.verified);
+        let unverified_message = (!is_verified).then(|| {
+            self.session_state
+                .as_ref()
+                .and_then(|state| state.message.clone())
+                .map(SharedString::from)
+                .unwrap_or_else(|| {
+                    SharedString::from("No executable code is associated with this line.")
+                })
+        });
         let indicator = div()
             .id(SharedString::from(format!(
                 "breakpoint-ui-toggle-{:?}/{}:{}",
@@ -485,7 +803,11 @@ impl LineBreakpoint {
                     .ok();
                 }
             })
-            .child(Indicator::icon(Icon::new(icon_name)).color(Color::Debugger))
+            .child(Indicator::icon(Icon::new(icon_name)).color(if is_verified {
+                Color::Debugger
+            } else {
+                Color::Disabled
+            }))
             .on_mouse_down(MouseButton::Left, move |_, _, _| {});
         ListItem::new(SharedString::from(format!(
             "breakpoint-ui-item-{:?}/{}:{}",
@@ -493,9 +815,9 @@ impl LineBreakpoint {
         )))
         .on_click({
             let weak = weak.clone();
-            move |_, _, cx| {
+            move |event: &ClickEvent, _, cx| {
                 weak.update(cx, |breakpoint_list, cx| {
-                    breakpoint_list.select_ix(Some(ix), cx);
+                    breakpoint_list.click_ix(ix, &event.modifiers(), cx);
                 })
                 .ok();
             }
@@ -553,20 +875,29 @@ impl LineBreakpoint {
                     "breakpoint-ui-on-click-go-to-line-{:?}/{}:{}",
                     self.dir, self.name, self.line
                 )))
-                .on_click(move |_, window, cx| {
+                .on_click(move |event: &ClickEvent, window, cx| {
                     weak.update(cx, |breakpoint_list, cx| {
-                        breakpoint_list.select_ix(Some(ix), cx);
-                        breakpoint_list.go_to_line_breakpoint(path.clone(), row, window, cx);
+                        let modifiers = event.modifiers();
+                        if modifiers.shift || modifiers.secondary() {
+                            breakpoint_list.click_ix(ix, &modifiers, cx);
+                        } else {
+                            breakpoint_list.select_ix(Some(ix), cx);
+                            breakpoint_list.go_to_line_breakpoint(path.clone(), row, window, cx);
+                        }
                     })
                     .ok();
                 })
                 .cursor_pointer()
+                .when_some(unverified_message, |this, message| {
+                    this.tooltip(Tooltip::text(message))
+                })
                 .child(
                     h_flex()
                         .gap_1()
                         .child(
                             Label::new(format!("{}:{}", self.name, self.line))
                                 .size(LabelSize::Small)
+                                .when(!is_verified, |this| this.color(Color::Disabled))
                                 .line_height_style(ui::LineHeightStyle::UiLabel),
                         )
                         .children(self.dir.clone().map(|dir| {
@@ -574,6 +905,14 @@ impl LineBreakpoint {
                                 .color(Color::Muted)
                                 .size(LabelSize::Small)
                                 .line_height_style(ui::LineHeightStyle::UiLabel)
+                        }))
+                        .children(self.breakpoint.note.clone().map(|note| {
+                            Label::new(note)
+                                .color(Color::Muted)
+                                .size(LabelSize::Small)
+                                .italic()
+                                .truncate()
+                                .line_height_style(ui::LineHeightStyle::UiLabel)
                         })),
                 ),
         )
@@ -670,10 +1009,176 @@ impl ExceptionBreakpoint {
         )
     }
 }
+#[derive(Clone, Debug)]
+struct InstructionBreakpoint {
+    address: String,
+    is_enabled: bool,
+}
+
+impl InstructionBreakpoint {
+    fn render(
+        &mut self,
+        ix: usize,
+        focus_handle: FocusHandle,
+        list: WeakEntity<BreakpointList>,
+        cx: &App,
+    ) -> ListItem {
+        let is_enabled = self.is_enabled;
+        let icon_name = if is_enabled {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+
+        ListItem::new(SharedString::from(format!(
+            "instruction-breakpoint-ui-item-{}",
+            self.address
+        )))
+        .on_click({
+            let list = list.clone();
+            move |event: &ClickEvent, _, cx| {
+                list.update(cx, |list, cx| list.click_ix(ix, &event.modifiers(), cx))
+                    .ok();
+            }
+        })
+        .rounded()
+        .on_secondary_mouse_down(|_, _, cx| {
+            cx.stop_propagation();
+        })
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "instruction-breakpoint-ui-toggle-{}",
+                    self.address
+                )))
+                .tooltip({
+                    let focus_handle = focus_handle.clone();
+                    move |window, cx| {
+                        Tooltip::for_action_in(
+                            if is_enabled {
+                                "Disable Instruction Breakpoint"
+                            } else {
+                                "Enable Instruction Breakpoint"
+                            },
+                            &ToggleEnableBreakpoint,
+                            &focus_handle,
+                            window,
+                            cx,
+                        )
+                    }
+                })
+                .on_click({
+                    let list = list.clone();
+                    let address = self.address.clone();
+                    move |_, _, cx| {
+                        list.update(cx, |this, cx| {
+                            if let Some(session) = &this.session {
+                                session.update(cx, |session, cx| {
+                                    session.toggle_instruction_breakpoint_enabled(&address, cx);
+                                });
+                            }
+                        })
+                        .ok();
+                    }
+                })
+                .cursor_pointer()
+                .child(Indicator::icon(Icon::new(icon_name)).color(Color::Debugger)),
+        )
+        .end_hover_slot(
+            h_flex()
+                .child(
+                    IconButton::new(
+                        SharedString::from(format!(
+                            "instruction-breakpoint-ui-remove-{}",
+                            self.address
+                        )),
+                        IconName::Close,
+                    )
+                    .on_click({
+                        let address = self.address.clone();
+                        move |_, _, cx| {
+                            list.update(cx, |this, cx| {
+                                if let Some(session) = &this.session {
+                                    session.update(cx, |session, cx| {
+                                        session.toggle_instruction_breakpoint(address.clone(), cx);
+                                    });
+                                }
+                            })
+                            .ok();
+                        }
+                    })
+                    .tooltip(move |window, cx| {
+                        Tooltip::for_action_in(
+                            "Unset Breakpoint",
+                            &UnsetBreakpoint,
+                            &focus_handle,
+                            window,
+                            cx,
+                        )
+                    })
+                    .icon_size(ui::IconSize::XSmall),
+                )
+                .right_4(),
+        )
+        .child(
+            h_flex().py_1().min_h(px(26.)).child(
+                Label::new(self.address.clone())
+                    .size(LabelSize::Small)
+                    .buffer_font(cx)
+                    .line_height_style(ui::LineHeightStyle::UiLabel),
+            ),
+        )
+    }
+}
+
+fn render_instruction_breakpoints_header(
+    ix: usize,
+    focus_handle: FocusHandle,
+    list: WeakEntity<BreakpointList>,
+) -> ListItem {
+    ListItem::new(("instruction-breakpoints-header", ix))
+        .on_click(move |_, _, cx| {
+            list.update(cx, |list, cx| list.select_ix(Some(ix), cx))
+                .ok();
+        })
+        .rounded()
+        .child(
+            h_flex()
+                .py_1()
+                .min_h(px(26.))
+                .justify_between()
+                .child(
+                    Label::new("Instruction Breakpoints")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .line_height_style(ui::LineHeightStyle::UiLabel),
+                )
+                .child(
+                    IconButton::new("add-instruction-breakpoint", IconName::Plus)
+                        .icon_size(ui::IconSize::XSmall)
+                        .tooltip(move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Add Instruction Breakpoint",
+                                &AddInstructionBreakpoint,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        })
+                        .on_click(|_, window, cx| {
+                            window.dispatch_action(Box::new(AddInstructionBreakpoint), cx);
+                        }),
+                ),
+        )
+}
+
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
     ExceptionBreakpoint(ExceptionBreakpoint),
+    /// Heads the instruction breakpoints, with a button for adding one by address.
+    InstructionBreakpointsHeader,
+    InstructionBreakpoint(InstructionBreakpoint),
 }
 
 #[derive(Clone, Debug)]
@@ -688,7 +1193,7 @@ impl BreakpointEntry {
         ix: usize,
         focus_handle: FocusHandle,
         _: &mut Window,
-        _: &mut App,
+        cx: &mut App,
     ) -> ListItem {
         match &mut self.kind {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
@@ -697,6 +1202,12 @@ impl BreakpointEntry {
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 exception_breakpoint.render(ix, focus_handle, self.weak.clone())
             }
+            BreakpointEntryKind::InstructionBreakpointsHeader => {
+                render_instruction_breakpoints_header(ix, focus_handle, self.weak.clone())
+            }
+            BreakpointEntryKind::InstructionBreakpoint(instruction_breakpoint) => {
+                instruction_breakpoint.render(ix, focus_handle, self.weak.clone(), cx)
+            }
         }
     }
 }
@@ -0,0 +1,429 @@
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription, Task,
+    UniformListScrollHandle, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent};
+use settings::Settings as _;
+use std::{cmp::Ordering, ops::Range};
+use theme::ThemeSettings;
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::ResultExt;
+
+/// Number of elements requested from the adapter at a time.
+const PAGE_SIZE: u64 = 256;
+/// Numeric collections longer than this are averaged into buckets when plotted.
+const MAX_PLOT_BARS: usize = 200;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum TableColumn {
+    Name,
+    Value,
+    Type,
+}
+
+impl TableColumn {
+    const ALL: [Self; 3] = [Self::Name, Self::Value, Self::Type];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Value => "Value",
+            Self::Type => "Type",
+        }
+    }
+
+    fn cell<'a>(&self, variable: &'a dap::Variable) -> &'a str {
+        match self {
+            Self::Name => &variable.name,
+            Self::Value => &variable.value,
+            Self::Type => variable.type_.as_deref().unwrap_or_default(),
+        }
+    }
+
+    fn compare(&self, a: &dap::Variable, b: &dap::Variable) -> Ordering {
+        let (a, b) = (self.cell(a), self.cell(b));
+        match (numeric_value(a), numeric_value(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            _ => a.cmp(b),
+        }
+    }
+}
+
+/// Parses numeric cells, including array indices such as `[12]`, so they sort by magnitude.
+fn numeric_value(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix('[')
+        .and_then(|text| text.strip_suffix(']'))
+        .unwrap_or(text);
+    text.parse().ok()
+}
+
+/// Shows the elements of an array or map variable as a sortable table, paging them in from the
+/// adapter so that large collections stay responsive.
+pub(crate) struct CollectionTable {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    collection: Option<dap::Variable>,
+    rows: Vec<dap::Variable>,
+    /// Indices into `rows` in display order.
+    sorted_rows: Vec<usize>,
+    sort: Option<(TableColumn, bool)>,
+    show_plot: bool,
+    loading: bool,
+    _fetch_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CollectionTable {
+    pub(crate) fn new(session: Entity<Session>, cx: &mut Context<Self>) -> Self {
+        let _subscriptions = vec![cx.subscribe(&session, |this, _, event, cx| {
+            // Variable references from an earlier stop are no longer valid.
+            if let SessionEvent::Stopped(_) = event {
+                this.clear(cx);
+            }
+        })];
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            focus_handle: cx.focus_handle(),
+            collection: None,
+            rows: Vec::new(),
+            sorted_rows: Vec::new(),
+            sort: None,
+            show_plot: false,
+            loading: false,
+            _fetch_task: Task::ready(()),
+            _subscriptions,
+        }
+    }
+
+    pub(crate) fn show(&mut self, collection: dap::Variable, cx: &mut Context<Self>) {
+        let variables_reference = collection.variables_reference;
+        let indexed_count = collection.indexed_variables;
+        self.collection = Some(collection);
+        self.rows.clear();
+        self.sorted_rows.clear();
+        self.show_plot = false;
+        self.loading = true;
+
+        self._fetch_task = cx.spawn(async move |this, cx| {
+            // Only indexed children can be paged; maps and structs are fetched all at once.
+            let pages: Vec<Option<Range<u64>>> = match indexed_count {
+                Some(total) => (0..total)
+                    .step_by(PAGE_SIZE as usize)
+                    .map(|start| Some(start..total.min(start + PAGE_SIZE)))
+                    .collect(),
+                None => vec![None],
+            };
+            for range in pages {
+                let Ok(page) = this.update(cx, |this, cx| {
+                    this.session
+                        .read(cx)
+                        .fetch_variables(variables_reference, range)
+                }) else {
+                    return;
+                };
+                let Some(page) = page.await.log_err() else {
+                    break;
+                };
+                if this
+                    .update(cx, |this, cx| this.append_rows(page, cx))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn clear(&mut self, cx: &mut Context<Self>) {
+        self.collection = None;
+        self.rows.clear();
+        self.sorted_rows.clear();
+        self.loading = false;
+        self._fetch_task = Task::ready(());
+        cx.notify();
+    }
+
+    fn append_rows(&mut self, rows: Vec<dap::Variable>, cx: &mut Context<Self>) {
+        self.rows.extend(rows);
+        self.sort_rows();
+        cx.notify();
+    }
+
+    fn sort_rows(&mut self) {
+        self.sorted_rows = (0..self.rows.len()).collect();
+        if let Some((column, ascending)) = self.sort {
+            self.sorted_rows.sort_by(|a, b| {
+                let ordering = column.compare(&self.rows[*a], &self.rows[*b]);
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+    }
+
+    /// Sorts by `column`, flipping the direction when it is already the sort column.
+    pub(crate) fn sort_by(&mut self, column: TableColumn, cx: &mut Context<Self>) {
+        self.sort = match self.sort {
+            Some((sorted_column, ascending)) if sorted_column == column => {
+                Some((column, !ascending))
+            }
+            _ => Some((column, true)),
+        };
+        self.sort_rows();
+        cx.notify();
+    }
+
+    fn toggle_plot(&mut self, cx: &mut Context<Self>) {
+        self.show_plot = !self.show_plot;
+        cx.notify();
+    }
+
+    /// The values of the collection in element order, if every one of them is a number.
+    fn numeric_values(&self) -> Option<Vec<f64>> {
+        if self.rows.is_empty() {
+            return None;
+        }
+        self.rows
+            .iter()
+            .map(|row| numeric_value(&row.value))
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn visual_rows(&self) -> Vec<String> {
+        self.sorted_rows
+            .iter()
+            .map(|ix| {
+                let row = &self.rows[*ix];
+                format!("{} = {}", row.name, row.value)
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .w_full()
+            .px_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .children(TableColumn::ALL.into_iter().map(|column| {
+                let indicator = match self.sort {
+                    Some((sorted_column, true)) if sorted_column == column => {
+                        Some(IconName::ArrowUp)
+                    }
+                    Some((sorted_column, false)) if sorted_column == column => {
+                        Some(IconName::ArrowDown)
+                    }
+                    _ => None,
+                };
+                self.column_cell(
+                    column,
+                    h_flex()
+                        .id(column.label())
+                        .gap_1()
+                        .cursor_pointer()
+                        .child(
+                            Label::new(column.label())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .children(indicator.map(|icon| {
+                            Icon::new(icon).size(IconSize::XSmall).color(Color::Muted)
+                        }))
+                        .on_click(cx.listener(move |this, _, _, cx| this.sort_by(column, cx))),
+                )
+            }))
+    }
+
+    fn column_cell(&self, column: TableColumn, content: impl IntoElement) -> Div {
+        let cell = div().overflow_hidden().child(content);
+        match column {
+            TableColumn::Value => cell.flex_1(),
+            TableColumn::Name | TableColumn::Type => cell.w(relative(0.25)),
+        }
+    }
+
+    fn render_row(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let row = &self.rows[self.sorted_rows[ix]];
+        h_flex()
+            .id(("collection-row", ix))
+            .w_full()
+            .px_1()
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+            .children(TableColumn::ALL.into_iter().map(|column| {
+                self.column_cell(
+                    column,
+                    Label::new(SharedString::from(column.cell(row).to_string()))
+                        .size(LabelSize::Small)
+                        .color(match column {
+                            TableColumn::Value => Color::Default,
+                            TableColumn::Name | TableColumn::Type => Color::Muted,
+                        })
+                        .single_line(),
+                )
+            }))
+            .into_any_element()
+    }
+
+    fn render_plot(&self, values: &[f64], cx: &mut Context<Self>) -> impl IntoElement {
+        let bucket_size = values.len().div_ceil(MAX_PLOT_BARS).max(1);
+        let bars = values
+            .chunks(bucket_size)
+            .map(|bucket| bucket.iter().sum::<f64>() / bucket.len() as f64)
+            .collect::<Vec<_>>();
+        let min = bars.iter().copied().fold(f64::INFINITY, f64::min).min(0.);
+        let max = bars.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let span = if max > min { max - min } else { 1. };
+        let color = cx.theme().colors().text_accent;
+
+        h_flex()
+            .size_full()
+            .items_end()
+            .gap_px()
+            .children(bars.into_iter().map(|value| {
+                div()
+                    .flex_1()
+                    .h(relative(((value - min) / span) as f32))
+                    .min_h_px()
+                    .bg(color)
+            }))
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("collection-table-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+impl Focusable for CollectionTable {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CollectionTable {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(collection) = self.collection.as_ref() else {
+            return v_flex()
+                .track_focus(&self.focus_handle)
+                .size_full()
+                .p_1()
+                .child(
+                    Label::new("Choose View as Table on a collection in the Variables pane")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                );
+        };
+
+        let element_count = match collection.indexed_variables {
+            Some(total) => format!("{} of {total} elements", self.rows.len()),
+            None => format!("{} elements", self.rows.len()),
+        };
+        let numeric_values = self.numeric_values();
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .pb_1()
+                    .child(Label::new(collection.name.clone()).size(LabelSize::Small))
+                    .child(
+                        Label::new(element_count)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .when(self.loading, |this| {
+                        this.child(
+                            Label::new("Loading…")
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        )
+                    })
+                    .child(div().flex_1())
+                    .when(numeric_values.is_some(), |this| {
+                        this.child(
+                            Button::new("toggle-collection-plot", "Plot")
+                                .label_size(LabelSize::Small)
+                                .toggle_state(self.show_plot)
+                                .tooltip(Tooltip::text("Plot the numeric values of the collection"))
+                                .on_click(cx.listener(|this, _, _, cx| this.toggle_plot(cx))),
+                        )
+                    }),
+            )
+            .map(|this| match numeric_values.filter(|_| self.show_plot) {
+                Some(values) => this.child(self.render_plot(&values, cx)),
+                None => this.child(self.render_header(cx)).child(
+                    div()
+                        .relative()
+                        .size_full()
+                        .child(
+                            uniform_list(
+                                "collection-table",
+                                self.sorted_rows.len(),
+                                cx.processor(|this, range: Range<usize>, _window, cx| {
+                                    range.map(|ix| this.render_row(ix, cx)).collect()
+                                }),
+                            )
+                            .track_scroll(self.scroll_handle.clone())
+                            .size_full(),
+                        )
+                        .child(self.render_vertical_scrollbar(cx)),
+                ),
+            })
+    }
+}
@@ -2,26 +2,313 @@ use super::{
     stack_frame_list::{StackFrameList, StackFrameListEvent},
     variable_list::VariableList,
 };
+use crate::{ClearConsole, persistence};
 use alacritty_terminal::vte::ansi;
 use anyhow::Result;
-use collections::HashMap;
-use dap::OutputEvent;
-use editor::{Bias, CompletionProvider, Editor, EditorElement, EditorStyle, ExcerptId};
+use collections::{HashMap, HashSet};
+use dap::{
+    OutputEvent, OutputEventCategory, StackFrameId, VariableReference,
+    debugger_settings::{ConsoleTimestamps, DebuggerSettings},
+};
+use editor::{
+    Anchor, Bias, CompletionProvider, Editor, EditorElement, EditorEvent, EditorStyle, ExcerptId,
+    FoldPlaceholder,
+    actions::{MoveDown, MoveUp},
+    display_map::{
+        BlockContext, BlockId, BlockPlacement, BlockProperties, BlockStyle, Crease, CustomBlockId,
+        RenderBlock,
+    },
+    scroll::Autoscroll,
+};
 use fuzzy::StringMatchCandidate;
 use gpui::{
-    Context, Entity, FocusHandle, Focusable, HighlightStyle, Hsla, Render, Subscription, Task,
-    TextStyle, WeakEntity,
+    Context, Entity, FocusHandle, Focusable, FontStyle, FontWeight, HighlightStyle, Hsla,
+    MouseButton, Render, StrikethroughStyle, Subscription, Task, TextStyle, UnderlineStyle,
+    WeakEntity, actions,
 };
-use language::{Buffer, CodeLabel, ToOffset};
+use language::{Buffer, CodeLabel, Point, ToOffset};
 use menu::Confirm;
 use project::{
     Completion, CompletionResponse,
-    debugger::session::{CompletionsQuery, OutputToken, Session, SessionEvent},
+    debugger::session::{
+        CompletionsQuery, OutputToken, Session, SessionEvent, ThreadId, ThreadStatus,
+    },
+    search_history::{QueryInsertionBehavior, SearchHistory, SearchHistoryCursor},
 };
 use settings::Settings;
-use std::{cell::RefCell, ops::Range, rc::Rc, usize};
+use std::{cell::RefCell, ops::Range, path::PathBuf, rc::Rc, sync::Arc, time::SystemTime, usize};
 use theme::{Theme, ThemeSettings};
-use ui::{Divider, prelude::*};
+use ui::{ContextMenu, Divider, PopoverMenu, Tooltip, prelude::*};
+use workspace::{OpenOptions, OpenVisible, Workspace};
+
+const MAX_CONSOLE_HISTORY_LEN: usize = 100;
+
+actions!(
+    console,
+    [ToggleSearch, SelectNextMatch, SelectPreviousMatch]
+);
+
+struct ConsoleAnsiHighlight;
+struct ConsoleCategoryHighlight;
+struct ConsoleLocationHighlight;
+struct ConsoleSearchHighlight;
+
+/// A location referenced by console output, either as a `path:line:column` in its text or
+/// through the `source` and `line` of its output event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ConsoleLocation {
+    pub(crate) path: PathBuf,
+    /// 1-based, as printed.
+    pub(crate) line: u32,
+    pub(crate) column: Option<u32>,
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '/' | '\\' | '.' | '_' | '-' | '~' | '@' | '+')
+}
+
+fn parse_number(text: &str, start: usize) -> Option<(u32, usize)> {
+    let len = text[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len() - start);
+    let number = text[start..start + len].parse().ok()?;
+    Some((number, start + len))
+}
+
+/// Finds `path:line` and `path:line:column` references in console output, such as the ones in
+/// stack traces and compiler diagnostics. Paths need a file extension, so that URLs and
+/// `host:port` pairs aren't mistaken for them.
+pub(crate) fn find_locations(text: &str) -> Vec<(Range<usize>, ConsoleLocation)> {
+    let mut locations = Vec::new();
+    let mut search_start = 0;
+    while let Some(colon) = text[search_start..].find(':').map(|ix| ix + search_start) {
+        search_start = colon + 1;
+        let Some((line, mut end)) = parse_number(text, colon + 1) else {
+            continue;
+        };
+        let mut start = text[..colon]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_path_char(*c))
+            .last()
+            .map_or(colon, |(ix, _)| ix);
+        // Windows drive letters, e.g. `C:\src\main.rs:3`.
+        let before = &text[..start];
+        if before.ends_with(':')
+            && before[..before.len() - 1]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphabetic())
+            && before[..before.len() - 2]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !is_path_char(c))
+            && text[start..].starts_with(['\\', '/'])
+        {
+            start -= 2;
+        } else if before.ends_with(':') {
+            // The rest of a URL, e.g. `//example.com:8080`.
+            continue;
+        }
+
+        let path = &text[start..colon];
+        let has_extension = path
+            .rsplit(['/', '\\'])
+            .next()
+            .and_then(|file_name| file_name.rsplit_once('.'))
+            .is_some_and(|(stem, extension)| {
+                !stem.is_empty() && extension.starts_with(|c: char| c.is_ascii_alphabetic())
+            });
+        if line == 0 || !has_extension {
+            continue;
+        }
+
+        let column = text[end..]
+            .strip_prefix(':')
+            .and_then(|_| parse_number(text, end + 1))
+            .map(|(column, column_end)| {
+                end = column_end;
+                column
+            });
+        locations.push((
+            start..end,
+            ConsoleLocation {
+                path: PathBuf::from(path),
+                line,
+                column,
+            },
+        ));
+        search_start = end;
+    }
+    locations
+}
+
+/// Formats the time output was received at as a prefix for its lines, e.g. `[14:03:27.512]` or
+/// `[+12.345s]`.
+pub(crate) fn format_timestamp(
+    timestamps: ConsoleTimestamps,
+    received_at: SystemTime,
+    started_at: SystemTime,
+) -> Option<String> {
+    match timestamps {
+        ConsoleTimestamps::Off => None,
+        ConsoleTimestamps::WallClock => Some(format!(
+            "[{}]",
+            chrono::DateTime::<chrono::Local>::from(received_at).format("%H:%M:%S%.3f")
+        )),
+        ConsoleTimestamps::SessionRelative => {
+            let elapsed = received_at.duration_since(started_at).unwrap_or_default();
+            Some(format!(
+                "[+{}.{:03}s]",
+                elapsed.as_secs(),
+                elapsed.subsec_millis()
+            ))
+        }
+    }
+}
+
+/// Finds the occurrences of `query` in `text`, ignoring ASCII case.
+pub(crate) fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let text = text.to_ascii_lowercase();
+    let query = query.to_ascii_lowercase();
+    text.match_indices(&query)
+        .map(|(start, _)| start..start + query.len())
+        .collect()
+}
+
+/// The categories of output the console can show or hide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum OutputCategory {
+    Stdout,
+    Stderr,
+    Console,
+    Telemetry,
+}
+
+impl OutputCategory {
+    const ALL: [Self; 4] = [Self::Stdout, Self::Stderr, Self::Console, Self::Telemetry];
+
+    fn of(event: &OutputEvent) -> Self {
+        match event.category {
+            Some(OutputEventCategory::Stdout) => Self::Stdout,
+            Some(OutputEventCategory::Stderr) => Self::Stderr,
+            Some(OutputEventCategory::Telemetry) => Self::Telemetry,
+            // Adapters are expected to treat missing and unknown categories as `console`.
+            _ => Self::Console,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+            Self::Console => "console",
+            Self::Telemetry => "telemetry",
+        }
+    }
+
+    /// The color of this category's text, or `None` to use the console's own text color.
+    fn color(&self, theme: &Theme) -> Option<Hsla> {
+        match self {
+            Self::Stdout => Some(theme.colors().text),
+            Self::Stderr => Some(theme.status().error),
+            Self::Console => None,
+            Self::Telemetry => Some(theme.status().hint),
+        }
+    }
+}
+
+/// A run of consecutive events with the same output, shown as its first line followed by a
+/// "×N" counter.
+struct RepeatedOutput {
+    category: OutputCategory,
+    output: String,
+    /// The end of the first line of the run, where the repeats are folded from.
+    start: Anchor,
+    count: usize,
+    /// Whether the user unfolded the run, in which case further repeats are shown as well.
+    expanded: bool,
+}
+
+fn repeated_output_placeholder(console: WeakEntity<Console>, count: usize) -> FoldPlaceholder {
+    FoldPlaceholder {
+        render: Arc::new(move |fold_id, fold_range, cx| {
+            let console = console.clone();
+            div()
+                .id(fold_id)
+                .ml_1()
+                .px_1()
+                .rounded_xs()
+                .bg(cx.theme().colors().element_background)
+                .hover(|style| style.bg(cx.theme().colors().element_hover))
+                .cursor_pointer()
+                .child(
+                    Label::new(format!("×{count}"))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                .on_click(move |_, _, cx| {
+                    console
+                        .update(cx, |console, cx| {
+                            console.expand_repeated_output(fold_range.clone(), cx)
+                        })
+                        .ok();
+                })
+                .into_any()
+        }),
+        constrain_width: false,
+        merge_adjacent: false,
+        type_tag: None,
+    }
+}
+
+/// Output that references variables, like an object logged with `console.log` in JavaScript. Its
+/// lines are replaced by a block that can be expanded to show the variables.
+struct StructuredOutput {
+    label: SharedString,
+    variables_reference: VariableReference,
+    /// The end of the output's text, to tell when it was trimmed.
+    end: Anchor,
+    /// The paths of the expanded variables, where the empty path is the output itself.
+    expanded: HashSet<Vec<SharedString>>,
+    /// The number of rows the block currently takes up.
+    height: u32,
+}
+
+/// A row of an expanded structured output. `path` holds the names leading to the variable.
+struct StructuredOutputEntry {
+    path: Vec<SharedString>,
+    variable: dap::Variable,
+}
+
+fn render_structured_output_block(console: WeakEntity<Console>) -> RenderBlock {
+    Arc::new(move |cx: &mut BlockContext| {
+        let BlockId::Custom(block_id) = cx.block_id else {
+            return gpui::Empty.into_any_element();
+        };
+        let line_height = cx.line_height;
+        console
+            .update(cx.app, |console, cx| {
+                console.render_structured_output(block_id, line_height, cx)
+            })
+            .unwrap_or_else(|_| gpui::Empty.into_any_element())
+    })
+}
+
+/// A thread and frame picked to evaluate expressions in, rather than following the selected
+/// stack frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EvaluationContext {
+    thread_id: ThreadId,
+    /// The frame's index in the thread's stack, so that the pick survives the thread stopping
+    /// somewhere else.
+    frame_index: usize,
+    label: SharedString,
+}
 
 pub struct Console {
     console: Entity<Editor>,
@@ -32,7 +319,27 @@ pub struct Console {
     stack_frame_list: Entity<StackFrameList>,
     last_token: OutputToken,
     update_output_task: Task<()>,
+    update_language_task: Task<()>,
     focus_handle: FocusHandle,
+    hidden_categories: HashSet<OutputCategory>,
+    workspace: WeakEntity<Workspace>,
+    locations: Vec<(Range<Anchor>, ConsoleLocation)>,
+    history: SearchHistory,
+    history_cursor: SearchHistoryCursor,
+    search_editor: Entity<Editor>,
+    show_search: bool,
+    search_matches: Vec<Range<Anchor>>,
+    active_match: Option<usize>,
+    timestamps: ConsoleTimestamps,
+    session_started_at: SystemTime,
+    /// How many bytes of output were dropped from the start of the console.
+    trimmed_len: usize,
+    repeated_output: Option<RepeatedOutput>,
+    evaluation_context: Option<EvaluationContext>,
+    structured_outputs: HashMap<CustomBlockId, StructuredOutput>,
+    /// Output received before this token was cleared, and isn't shown again when the output is
+    /// rebuilt.
+    cleared_token: OutputToken,
 }
 
 impl Console {
@@ -40,6 +347,7 @@ impl Console {
         session: Entity<Session>,
         stack_frame_list: Entity<StackFrameList>,
         variable_list: Entity<VariableList>,
+        workspace: WeakEntity<Workspace>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
@@ -79,13 +387,33 @@ impl Console {
             editor
         });
 
+        let search_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Search console output", cx);
+            editor
+        });
+
         let _subscriptions = vec![
             cx.subscribe(&stack_frame_list, Self::handle_stack_frame_list_events),
-            cx.subscribe_in(&session, window, |this, _, event, window, cx| {
-                if let SessionEvent::ConsoleOutput = event {
-                    this.update_output(window, cx)
+            cx.subscribe_in(&search_editor, window, |this, _, event, window, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_search_matches(window, cx);
+                    this.select_match(0, window, cx);
                 }
             }),
+            cx.subscribe_in(&session, window, |this, _, event, window, cx| match event {
+                SessionEvent::ConsoleOutput => this.update_output(window, cx),
+                SessionEvent::Restarted
+                    if DebuggerSettings::get_global(cx).clear_console_on_restart =>
+                {
+                    this.clear(window, cx)
+                }
+                _ => {}
+            }),
+            // Variables of structured output are fetched lazily, so its blocks grow as they load.
+            cx.observe(&session, |this, _, cx| {
+                this.update_structured_output_heights(cx)
+            }),
             cx.on_focus(&focus_handle, window, |console, window, cx| {
                 if console.is_running(cx) {
                     console.query_bar.focus_handle(cx).focus(window);
@@ -93,7 +421,8 @@ impl Console {
             }),
         ];
 
-        Self {
+        let session_started_at = session.read(cx).started_at();
+        let mut this = Self {
             session,
             console,
             query_bar,
@@ -101,9 +430,81 @@ impl Console {
             _subscriptions,
             stack_frame_list,
             update_output_task: Task::ready(()),
+            update_language_task: Task::ready(()),
             last_token: OutputToken(0),
             focus_handle,
-        }
+            hidden_categories: HashSet::from_iter([OutputCategory::Telemetry]),
+            workspace,
+            locations: Vec::new(),
+            history: SearchHistory::new(
+                Some(MAX_CONSOLE_HISTORY_LEN),
+                QueryInsertionBehavior::AlwaysInsert,
+            ),
+            history_cursor: SearchHistoryCursor::default(),
+            search_editor,
+            show_search: false,
+            search_matches: Vec::new(),
+            active_match: None,
+            timestamps: DebuggerSettings::get_global(cx).console_timestamps,
+            session_started_at,
+            trimmed_len: 0,
+            repeated_output: None,
+            evaluation_context: None,
+            structured_outputs: HashMap::default(),
+            cleared_token: OutputToken(0),
+        };
+        this.load_history(cx);
+        this
+    }
+
+    #[cfg(test)]
+    pub(crate) fn query_bar(&self) -> &Entity<Editor> {
+        &self.query_bar
+    }
+
+    fn load_history(&mut self, cx: &mut Context<Self>) {
+        let workspace = self.workspace.clone();
+        cx.spawn(async move |this, cx| {
+            let Some(workspace_id) =
+                workspace.read_with(cx, |workspace, _| workspace.database_id())?
+            else {
+                return Ok(());
+            };
+            let persisted = cx
+                .background_spawn(async move { persistence::get_console_history(workspace_id) })
+                .await;
+            this.update(cx, |this, _| {
+                // Anything evaluated while the history was loading is newer than what was persisted.
+                let evaluated = this
+                    .history
+                    .queries()
+                    .map(str::to_string)
+                    .collect::<Vec<_>>();
+                let mut cursor = SearchHistoryCursor::default();
+                for expression in persisted.into_iter().chain(evaluated) {
+                    this.history.add(&mut cursor, expression);
+                }
+                this.history_cursor.reset();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn save_history(&self, expression: String, cx: &mut Context<Self>) {
+        let Some(workspace_id) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        cx.background_spawn(persistence::add_to_console_history(
+            workspace_id,
+            expression,
+            MAX_CONSOLE_HISTORY_LEN,
+        ))
+        .detach_and_log_err(cx);
     }
 
     #[cfg(test)]
@@ -122,26 +523,80 @@ impl Console {
         cx: &mut Context<Self>,
     ) {
         match event {
-            StackFrameListEvent::SelectedStackFrameChanged(_) => cx.notify(),
+            StackFrameListEvent::SelectedStackFrameChanged(_) => {
+                self.update_query_bar_language(cx);
+                cx.notify();
+            }
             StackFrameListEvent::BuiltEntries => {}
         }
     }
 
+    /// Highlights expressions in the language of the selected frame's source, or as plain text when
+    /// the language isn't known.
+    fn update_query_bar_language(&mut self, cx: &mut Context<Self>) {
+        let Some(buffer) = self.query_bar.read(cx).buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let Ok(languages) = self.workspace.read_with(cx, |workspace, cx| {
+            workspace.project().read(cx).languages().clone()
+        }) else {
+            return;
+        };
+        let path = self
+            .stack_frame_list
+            .read(cx)
+            .opened_stack_frame_abs_path(cx);
+
+        self.update_language_task = cx.spawn(async move |_, cx| {
+            let language = match path {
+                Some(path) => languages.language_for_file_path(&path).await.ok(),
+                None => None,
+            };
+            buffer
+                .update(cx, |buffer, cx| buffer.set_language(language, cx))
+                .ok();
+        });
+    }
+
     pub(crate) fn show_indicator(&self, cx: &App) -> bool {
         self.session.read(cx).has_new_output(self.last_token)
     }
 
     pub fn add_messages<'a>(
         &mut self,
-        events: impl Iterator<Item = &'a OutputEvent>,
+        events: impl Iterator<Item = (SystemTime, &'a OutputEvent)>,
         window: &mut Window,
-        cx: &mut App,
+        cx: &mut Context<Self>,
     ) {
-        self.console.update(cx, |console, cx| {
+        let hidden_categories = self.hidden_categories.clone();
+        let timestamps = self.timestamps;
+        let session_started_at = self.session_started_at;
+        // Highlights are keyed by their offset from the start of all the output, including any
+        // that was trimmed, so that keys stay unique.
+        let key_offset = self.trimmed_len;
+        let this = cx.weak_entity();
+        let mut repeated_output = self.repeated_output.take();
+        let session = self.session.clone();
+        let (locations, structured_outputs) = self.console.update(cx, |console, cx| {
+            let mut locations = Vec::new();
+            let mut structured_outputs = Vec::new();
             console.set_read_only(false);
 
-            for event in events {
-                let to_insert = format!("{}\n", event.output.trim_end());
+            for (received_at, event) in events {
+                let category = OutputCategory::of(event);
+                if hidden_categories.contains(&category) {
+                    continue;
+                }
+                let to_insert = match format_timestamp(timestamps, received_at, session_started_at)
+                {
+                    Some(timestamp) => event
+                        .output
+                        .trim_end()
+                        .lines()
+                        .map(|line| format!("{timestamp} {line}\n"))
+                        .collect(),
+                    None => format!("{}\n", event.output.trim_end()),
+                };
 
                 let mut ansi_handler = ConsoleHandler::default();
                 let mut ansi_processor = ansi::Processor::<ansi::StdSyncHandler>::default();
@@ -154,7 +609,7 @@ impl Console {
                 if ansi_handler.current_range_start < output.len() {
                     spans.push((
                         ansi_handler.current_range_start..output.len(),
-                        ansi_handler.current_color,
+                        ansi_handler.current_style,
                     ));
                 }
                 if ansi_handler.current_background_range_start < output.len() {
@@ -167,22 +622,65 @@ impl Console {
                 console.insert(&output, window, cx);
                 let buffer = console.buffer().read(cx).snapshot(cx);
 
-                struct ConsoleAnsiHighlight;
+                let mut event_locations = find_locations(&output);
+                if event_locations.is_empty() {
+                    let source_location = event
+                        .source
+                        .as_ref()
+                        .and_then(|source| source.path.as_ref())
+                        .zip(event.line)
+                        .map(|(path, line)| ConsoleLocation {
+                            path: session.read(cx).local_source_path(path, cx),
+                            line: line as u32,
+                            column: event.column.map(|column| column as u32),
+                        });
+                    event_locations.extend(
+                        source_location.map(|location| (0..output.trim_end().len(), location)),
+                    );
+                }
+                for (range, location) in event_locations {
+                    let start_offset = len + range.start;
+                    let range =
+                        buffer.anchor_after(start_offset)..buffer.anchor_before(len + range.end);
+                    console.highlight_text_key::<ConsoleLocationHighlight>(
+                        key_offset + start_offset,
+                        vec![range.clone()],
+                        HighlightStyle {
+                            underline: Some(UnderlineStyle {
+                                color: None,
+                                thickness: px(1.),
+                                wavy: false,
+                            }),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                    locations.push((range, location));
+                }
 
-                for (range, color) in spans {
-                    let Some(color) = color else { continue };
+                if let Some(color) = category.color(cx.theme()) {
+                    let range = buffer.anchor_after(len)..buffer.anchor_before(len + output.len());
+                    console.highlight_text_key::<ConsoleCategoryHighlight>(
+                        key_offset + len,
+                        vec![range],
+                        HighlightStyle {
+                            color: Some(color),
+                            ..Default::default()
+                        },
+                        cx,
+                    );
+                }
+
+                for (range, style) in spans {
+                    if range.is_empty() || style == AnsiStyle::default() {
+                        continue;
+                    }
                     let start_offset = len + range.start;
                     let range = start_offset..len + range.end;
                     let range = buffer.anchor_after(range.start)..buffer.anchor_before(range.end);
-                    let style = HighlightStyle {
-                        color: Some(terminal_view::terminal_element::convert_color(
-                            &color,
-                            cx.theme(),
-                        )),
-                        ..Default::default()
-                    };
+                    let style = style.to_highlight_style(cx.theme());
                     console.highlight_text_key::<ConsoleAnsiHighlight>(
-                        start_offset,
+                        key_offset + start_offset,
                         vec![range],
                         style,
                         cx,
@@ -316,19 +814,651 @@ impl Console {
                     };
 
                     console.highlight_background_key::<ConsoleAnsiHighlight>(
-                        start_offset,
+                        key_offset + start_offset,
                         &[range],
                         color_fetcher,
                         cx,
                     );
                 }
+
+                let text = event.output.trim_end();
+                let end = len + output.trim_end_matches('\n').len();
+                if let Some(variables_reference) = event
+                    .variables_reference
+                    .filter(|reference| *reference != 0)
+                {
+                    repeated_output = None;
+                    let start_row = buffer.offset_to_point(len).row;
+                    let end_row = buffer.offset_to_point(end).row;
+                    let block_ids = console.insert_blocks(
+                        [BlockProperties {
+                            placement: BlockPlacement::Replace(
+                                buffer.anchor_after(Point::new(start_row, 0))
+                                    ..=buffer.anchor_after(Point::new(end_row, 0)),
+                            ),
+                            height: Some(1),
+                            style: BlockStyle::Flex,
+                            render: render_structured_output_block(this.clone()),
+                            priority: 0,
+                            render_in_minimap: false,
+                        }],
+                        None,
+                        cx,
+                    );
+                    structured_outputs.extend(block_ids.into_iter().map(|block_id| {
+                        (
+                            block_id,
+                            StructuredOutput {
+                                label: output.trim_end().to_string().into(),
+                                variables_reference,
+                                end: buffer.anchor_before(end),
+                                expanded: HashSet::default(),
+                                height: 1,
+                            },
+                        )
+                    }));
+                    continue;
+                }
+                match repeated_output.as_mut() {
+                    Some(run) if run.category == category && run.output == text => {
+                        run.count += 1;
+                        if !run.expanded {
+                            let range = run.start.to_offset(&buffer)..end;
+                            console.unfold_ranges(&[range.clone()], true, false, cx);
+                            console.fold_creases(
+                                vec![Crease::simple(
+                                    range,
+                                    repeated_output_placeholder(this.clone(), run.count),
+                                )],
+                                false,
+                                window,
+                                cx,
+                            );
+                        }
+                    }
+                    _ => {
+                        repeated_output = Some(RepeatedOutput {
+                            category,
+                            output: text.to_string(),
+                            start: buffer.anchor_before(end),
+                            count: 1,
+                            expanded: false,
+                        });
+                    }
+                }
             }
 
             console.set_read_only(true);
             cx.notify();
+            (locations, structured_outputs)
+        });
+        self.locations.extend(locations);
+        self.structured_outputs.extend(structured_outputs);
+        self.repeated_output = repeated_output;
+        self.trim_output(cx);
+        if self.show_search {
+            self.update_search_matches(window, cx);
+        }
+    }
+
+    /// Drops the oldest lines once the console holds more than `console_max_lines` of them.
+    fn trim_output(&mut self, cx: &mut Context<Self>) {
+        let max_lines = DebuggerSettings::get_global(cx).console_max_lines.max(1);
+        let trimmed_len = self.console.update(cx, |console, cx| {
+            let snapshot = console.buffer().read(cx).snapshot(cx);
+            // The output ends with a newline, so the last row is always empty.
+            let line_count = snapshot.max_point().row as usize;
+            if line_count <= max_lines {
+                return 0;
+            }
+            let end = snapshot.point_to_offset(Point::new((line_count - max_lines) as u32, 0));
+            console.set_read_only(false);
+            console.edit([(0..end, "")], cx);
+            console.set_read_only(true);
+            end
+        });
+        if trimmed_len == 0 {
+            return;
+        }
+
+        let trimmed_keys = self.trimmed_len..self.trimmed_len + trimmed_len;
+        self.trimmed_len += trimmed_len;
+        let snapshot = self.console.update(cx, |console, cx| {
+            console.clear_highlights_with_keys::<ConsoleAnsiHighlight>(trimmed_keys.clone(), cx);
+            console
+                .clear_highlights_with_keys::<ConsoleCategoryHighlight>(trimmed_keys.clone(), cx);
+            console.clear_highlights_with_keys::<ConsoleLocationHighlight>(trimmed_keys, cx);
+            console.buffer().read(cx).snapshot(cx)
+        });
+        self.locations
+            .retain(|(range, _)| range.end.to_offset(&snapshot) > 0);
+        let mut trimmed_blocks = HashSet::default();
+        self.structured_outputs.retain(|block_id, output| {
+            let is_trimmed = output.end.to_offset(&snapshot) == 0;
+            if is_trimmed {
+                trimmed_blocks.insert(*block_id);
+            }
+            !is_trimmed
+        });
+        self.console.update(cx, |console, cx| {
+            console.remove_blocks(trimmed_blocks, None, cx);
+        });
+    }
+
+    /// The variables shown under a structured output, in display order.
+    fn structured_output_entries(
+        &self,
+        block_id: CustomBlockId,
+        cx: &mut Context<Self>,
+    ) -> Vec<StructuredOutputEntry> {
+        let Some(output) = self.structured_outputs.get(&block_id) else {
+            return Vec::new();
+        };
+        if !output.expanded.contains(&Vec::new()) {
+            return Vec::new();
+        }
+
+        let variables_reference = output.variables_reference;
+        let mut entries = Vec::new();
+        let mut stack = self
+            .session
+            .update(cx, |session, cx| session.variables(variables_reference, cx))
+            .into_iter()
+            .rev()
+            .map(|variable| StructuredOutputEntry {
+                path: vec![variable.name.clone().into()],
+                variable,
+            })
+            .collect::<Vec<_>>();
+        while let Some(entry) = stack.pop() {
+            let variables_reference = entry.variable.variables_reference;
+            if variables_reference != 0 && output.expanded.contains(&entry.path) {
+                let children = self
+                    .session
+                    .update(cx, |session, cx| session.variables(variables_reference, cx));
+                stack.extend(children.into_iter().rev().map(|variable| {
+                    let mut path = entry.path.clone();
+                    path.push(variable.name.clone().into());
+                    StructuredOutputEntry { path, variable }
+                }));
+            }
+            entries.push(entry);
+        }
+        entries
+    }
+
+    fn toggle_structured_output(
+        &mut self,
+        block_id: CustomBlockId,
+        path: Vec<SharedString>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(output) = self.structured_outputs.get_mut(&block_id) else {
+            return;
+        };
+        if !output.expanded.remove(&path) {
+            output.expanded.insert(path);
+        }
+        self.update_structured_output_heights(cx);
+    }
+
+    fn update_structured_output_heights(&mut self, cx: &mut Context<Self>) {
+        let expanded_blocks = self
+            .structured_outputs
+            .iter()
+            .filter(|(_, output)| !output.expanded.is_empty() || output.height != 1)
+            .map(|(block_id, _)| *block_id)
+            .collect::<Vec<_>>();
+        if expanded_blocks.is_empty() {
+            return;
+        }
+
+        let mut heights = HashMap::default();
+        for block_id in expanded_blocks {
+            let height = 1 + self.structured_output_entries(block_id, cx).len() as u32;
+            if let Some(output) = self.structured_outputs.get_mut(&block_id) {
+                if output.height != height {
+                    output.height = height;
+                    heights.insert(block_id, height);
+                }
+            }
+        }
+        self.console.update(cx, |console, cx| {
+            if heights.is_empty() {
+                cx.notify();
+            } else {
+                console.resize_blocks(heights, None, cx);
+            }
         });
     }
 
+    fn render_structured_output(
+        &mut self,
+        block_id: CustomBlockId,
+        line_height: Pixels,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let Some(output) = self.structured_outputs.get(&block_id) else {
+            return gpui::Empty.into_any_element();
+        };
+        let label = output.label.clone();
+        let is_expanded = output.expanded.contains(&Vec::new());
+        let entries = self.structured_output_entries(block_id, cx);
+        let expanded = self
+            .structured_outputs
+            .get(&block_id)
+            .map(|output| output.expanded.clone())
+            .unwrap_or_default();
+
+        let row = |id: usize, depth: usize, path: Vec<SharedString>, is_expandable: bool| {
+            h_flex()
+                .id(("structured-output-row", id))
+                .h(line_height)
+                .pl(px(depth as f32 * 12.))
+                .gap_1()
+                .when(is_expandable, |row| {
+                    row.cursor_pointer()
+                        .hover(|style| style.bg(cx.theme().colors().element_hover))
+                        .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.toggle_structured_output(block_id, path.clone(), cx)
+                        }))
+                })
+        };
+        let chevron = |is_expandable: bool, is_expanded: bool| {
+            let icon = if is_expanded {
+                IconName::ChevronDown
+            } else {
+                IconName::ChevronRight
+            };
+            div().w_3().when(is_expandable, |this| {
+                this.child(Icon::new(icon).size(IconSize::XSmall).color(Color::Muted))
+            })
+        };
+
+        v_flex()
+            .w_full()
+            .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+            .child(
+                row(0, 0, Vec::new(), true)
+                    .child(chevron(true, is_expanded))
+                    .child(Label::new(label).single_line()),
+            )
+            .children(entries.into_iter().enumerate().map(|(ix, entry)| {
+                let is_expandable = entry.variable.variables_reference != 0;
+                let is_expanded = expanded.contains(&entry.path);
+                row(ix + 1, entry.path.len(), entry.path.clone(), is_expandable)
+                    .child(chevron(is_expandable, is_expanded))
+                    .child(Label::new(entry.variable.name))
+                    .child(Label::new("=").color(Color::Muted))
+                    .child(
+                        Label::new(entry.variable.value)
+                            .color(Color::Muted)
+                            .single_line(),
+                    )
+            }))
+            .into_any_element()
+    }
+
+    /// Formats the rows of each structured output like `assert_visual_entries` does for the
+    /// variable list.
+    #[cfg(test)]
+    pub(crate) fn structured_output_visual_entries(&self, cx: &mut Context<Self>) -> Vec<String> {
+        let mut block_ids = self.structured_outputs.keys().copied().collect::<Vec<_>>();
+        block_ids.sort();
+        let mut visual_entries = Vec::new();
+        for block_id in block_ids {
+            let Some(output) = self.structured_outputs.get(&block_id) else {
+                continue;
+            };
+            let marker = if output.expanded.contains(&Vec::new()) {
+                'v'
+            } else {
+                '>'
+            };
+            visual_entries.push(format!("{marker} {}", output.label));
+            for entry in self.structured_output_entries(block_id, cx) {
+                let marker = if entry.variable.variables_reference == 0 {
+                    ' '
+                } else if output.expanded.contains(&entry.path) {
+                    'v'
+                } else {
+                    '>'
+                };
+                visual_entries.push(format!(
+                    "{}{marker} {} = {}",
+                    "    ".repeat(entry.path.len()),
+                    entry.variable.name,
+                    entry.variable.value
+                ));
+            }
+        }
+        visual_entries
+    }
+
+    #[cfg(test)]
+    pub(crate) fn toggle_structured_output_path(&mut self, path: &[&str], cx: &mut Context<Self>) {
+        let Some(block_id) = self.structured_outputs.keys().min().copied() else {
+            return;
+        };
+        self.toggle_structured_output(
+            block_id,
+            path.iter().map(|name| name.to_string().into()).collect(),
+            cx,
+        );
+    }
+
+    fn expand_repeated_output(&mut self, range: Range<Anchor>, cx: &mut Context<Self>) {
+        let snapshot = self.console.read(cx).buffer().read(cx).snapshot(cx);
+        if let Some(run) = self
+            .repeated_output
+            .as_mut()
+            .filter(|run| run.start.to_offset(&snapshot) == range.start.to_offset(&snapshot))
+        {
+            run.expanded = true;
+        }
+        self.console.update(cx, |console, cx| {
+            console.unfold_ranges(&[range], true, false, cx);
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn expand_last_repeated_output(&mut self, cx: &mut Context<Self>) {
+        let Some(start) = self.repeated_output.as_ref().map(|run| run.start) else {
+            return;
+        };
+        let snapshot = self.console.read(cx).buffer().read(cx).snapshot(cx);
+        self.expand_repeated_output(start..snapshot.anchor_before(snapshot.len()), cx);
+    }
+
+    fn toggle_search(&mut self, _: &ToggleSearch, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_search = !self.show_search;
+        if self.show_search {
+            self.search_editor.update(cx, |editor, cx| {
+                editor.select_all(&editor::actions::SelectAll, window, cx);
+            });
+            window.focus(&self.search_editor.focus_handle(cx));
+            self.update_search_matches(window, cx);
+        } else {
+            self.search_matches.clear();
+            self.active_match = None;
+            self.console.update(cx, |console, cx| {
+                console.clear_background_highlights::<ConsoleSearchHighlight>(cx);
+            });
+            window.focus(&self.console.focus_handle(cx));
+        }
+        cx.notify();
+    }
+
+    fn update_search_matches(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx);
+        let active_match = self
+            .active_match
+            .and_then(|ix| self.search_matches.get(ix))
+            .cloned();
+        let (matches, active_match) = self.console.update(cx, |console, cx| {
+            let snapshot = console.buffer().read(cx).snapshot(cx);
+            let offsets = find_matches(&snapshot.text(), &query);
+            // Trimming output shifts match indices, so look the active match up by its position.
+            let active_match = active_match
+                .map(|range| range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot))
+                .filter(|range| !range.is_empty())
+                .and_then(|active| offsets.iter().position(|range| *range == active));
+            let matches = offsets
+                .into_iter()
+                .map(|range| snapshot.anchor_after(range.start)..snapshot.anchor_before(range.end))
+                .collect::<Vec<_>>();
+            console.highlight_background::<ConsoleSearchHighlight>(
+                &matches,
+                |theme| theme.colors().search_match_background,
+                cx,
+            );
+            (matches, active_match)
+        });
+        self.search_matches = matches;
+        self.active_match = active_match;
+        cx.notify();
+    }
+
+    fn select_match(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(range) = self.search_matches.get(ix).cloned() else {
+            self.active_match = None;
+            cx.notify();
+            return;
+        };
+        self.active_match = Some(ix);
+        self.console.update(cx, |console, cx| {
+            console.change_selections(Some(Autoscroll::center()), window, cx, |selections| {
+                selections.select_ranges([range])
+            });
+        });
+        cx.notify();
+    }
+
+    fn select_next_match(
+        &mut self,
+        _: &SelectNextMatch,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let match_count = self.search_matches.len();
+        if match_count == 0 {
+            return;
+        }
+        let ix = self.active_match.map_or(0, |ix| (ix + 1) % match_count);
+        self.select_match(ix, window, cx);
+    }
+
+    fn select_previous_match(
+        &mut self,
+        _: &SelectPreviousMatch,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let match_count = self.search_matches.len();
+        if match_count == 0 {
+            return;
+        }
+        let ix = self
+            .active_match
+            .map_or(match_count - 1, |ix| (ix + match_count - 1) % match_count);
+        self.select_match(ix, window, cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn search_matches(&self) -> (usize, Option<usize>) {
+        (self.search_matches.len(), self.active_match)
+    }
+
+    fn open_location_at_cursor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (selection, snapshot) = self.console.update(cx, |console, cx| {
+            (
+                console.selections.newest::<usize>(cx),
+                console.buffer().read(cx).snapshot(cx),
+            )
+        });
+        if !selection.is_empty() {
+            return;
+        }
+        let offset = selection.head();
+        let Some(location) = self.locations.iter().find_map(|(range, location)| {
+            (range.start.to_offset(&snapshot)..range.end.to_offset(&snapshot))
+                .contains(&offset)
+                .then(|| location.clone())
+        }) else {
+            return;
+        };
+        self.open_location(location, window, cx);
+    }
+
+    fn open_location(
+        &self,
+        location: ConsoleLocation,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let open_task = workspace.update(cx, |workspace, cx| {
+            let project_path = workspace
+                .project()
+                .read(cx)
+                .find_project_path(&location.path, cx);
+            match project_path {
+                Some(project_path) => {
+                    Some(workspace.open_path(project_path, None, true, window, cx))
+                }
+                None if location.path.is_absolute() => Some(workspace.open_abs_path(
+                    location.path.clone(),
+                    OpenOptions {
+                        visible: Some(OpenVisible::None),
+                        ..Default::default()
+                    },
+                    window,
+                    cx,
+                )),
+                None => None,
+            }
+        });
+        let Some(open_task) = open_task else {
+            return;
+        };
+        cx.spawn_in(window, async move |_, cx| {
+            let item = open_task.await?;
+            if let Some(editor) = item.downcast::<Editor>() {
+                editor.update_in(cx, |editor, window, cx| {
+                    let point = Point::new(
+                        location.line.saturating_sub(1),
+                        location.column.unwrap_or(1).saturating_sub(1),
+                    );
+                    editor.go_to_singleton_buffer_point(point, window, cx);
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub(crate) fn toggle_category(
+        &mut self,
+        category: OutputCategory,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.hidden_categories.remove(&category) {
+            self.hidden_categories.insert(category);
+        }
+        self.rebuild_output(window, cx);
+    }
+
+    fn toggle_timestamps(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.timestamps = match self.timestamps {
+            ConsoleTimestamps::Off => match DebuggerSettings::get_global(cx).console_timestamps {
+                ConsoleTimestamps::Off => ConsoleTimestamps::WallClock,
+                timestamps => timestamps,
+            },
+            _ => ConsoleTimestamps::Off,
+        };
+        self.rebuild_output(window, cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_timestamps(
+        &mut self,
+        timestamps: ConsoleTimestamps,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.timestamps = timestamps;
+        self.rebuild_output(window, cx);
+    }
+
+    fn rebuild_output(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Rebuild the output from the events the session still holds, so that hidden output
+        // can be shown again and timestamps can be added to earlier output.
+        let (events, last_token) = {
+            let (events, last_token) = self.session.read(cx).output(self.cleared_token);
+            (
+                events
+                    .map(|(received_at, event)| (received_at, event.clone()))
+                    .collect::<Vec<_>>(),
+                last_token,
+            )
+        };
+        self.console.update(cx, |console, cx| {
+            let len = console.buffer().read(cx).len(cx);
+            console.unfold_ranges(&[0..len], true, false, cx);
+            console.remove_blocks(
+                self.structured_outputs.drain().map(|(id, _)| id).collect(),
+                None,
+                cx,
+            );
+            console.set_read_only(false);
+            console.clear(window, cx);
+            console.set_read_only(true);
+            console.clear_highlights_with_keys::<ConsoleAnsiHighlight>(0..usize::MAX, cx);
+            console.clear_highlights_with_keys::<ConsoleCategoryHighlight>(0..usize::MAX, cx);
+            console.clear_highlights_with_keys::<ConsoleLocationHighlight>(0..usize::MAX, cx);
+        });
+        self.trimmed_len = 0;
+        self.repeated_output = None;
+        self.locations.clear();
+        self.search_matches.clear();
+        self.active_match = None;
+        self.add_messages(
+            events
+                .iter()
+                .map(|(received_at, event)| (*received_at, event)),
+            window,
+            cx,
+        );
+        self.last_token = last_token;
+        cx.notify();
+    }
+
+    /// Removes the output received so far from the console.
+    pub(crate) fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.cleared_token = self.last_token;
+        self.rebuild_output(window, cx);
+    }
+
+    fn render_category_toggles(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .px_1()
+            .gap_1()
+            .children(OutputCategory::ALL.into_iter().map(|category| {
+                let label = category.label();
+                Button::new(label, label)
+                    .label_size(LabelSize::Small)
+                    .toggle_state(!self.hidden_categories.contains(&category))
+                    .tooltip(Tooltip::text(format!("Show {label} output")))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.toggle_category(category, window, cx)
+                    }))
+            }))
+            .child(Divider::vertical())
+            .child(
+                Button::new("console-timestamps", "Timestamps")
+                    .label_size(LabelSize::Small)
+                    .toggle_state(self.timestamps != ConsoleTimestamps::Off)
+                    .tooltip(Tooltip::text(
+                        "Prefix output with the time it was received at",
+                    ))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.toggle_timestamps(window, cx)),
+                    ),
+            )
+            .child(div().flex_1())
+            .child(self.render_evaluation_context_picker(cx))
+            .child(
+                IconButton::new("console-clear", IconName::ListX)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::for_action_title("Clear Console", &ClearConsole))
+                    .on_click(cx.listener(|this, _, window, cx| this.clear(window, cx))),
+            )
+    }
+
     pub fn evaluate(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
         let expression = self.query_bar.update(cx, |editor, cx| {
             let expression = editor.text(cx);
@@ -338,13 +1468,20 @@ impl Console {
 
             expression
         });
+        if !expression.trim().is_empty() {
+            self.history
+                .add(&mut self.history_cursor, expression.clone());
+            self.history_cursor.reset();
+            self.save_history(expression.clone(), cx);
+        }
 
+        let frame_id = self.evaluation_frame_id(cx);
         self.session.update(cx, |session, cx| {
             session
                 .evaluate(
                     expression,
                     Some(dap::EvaluateArgumentsContext::Repl),
-                    self.stack_frame_list.read(cx).opened_stack_frame_id(),
+                    frame_id,
                     None,
                     cx,
                 )
@@ -352,8 +1489,210 @@ impl Console {
         });
     }
 
+    /// The frame to evaluate expressions in. When the picked thread's stack isn't known, this
+    /// evaluates in the global scope rather than in an unrelated frame.
+    fn evaluation_frame_id(&self, cx: &mut Context<Self>) -> Option<StackFrameId> {
+        let Some(context) = self.evaluation_context.as_ref() else {
+            return self.stack_frame_list.read(cx).opened_stack_frame_id();
+        };
+        let thread_id = context.thread_id;
+        self.session
+            .update(cx, |session, cx| session.stack_frames(thread_id, cx))
+            .ok()?
+            .get(context.frame_index)
+            .map(|stack_frame| stack_frame.dap.id)
+    }
+
+    fn set_evaluation_context(
+        &mut self,
+        evaluation_context: Option<EvaluationContext>,
+        cx: &mut Context<Self>,
+    ) {
+        self.evaluation_context = evaluation_context;
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn evaluate_in_thread(
+        &mut self,
+        thread_id: ThreadId,
+        frame_index: usize,
+        cx: &mut Context<Self>,
+    ) {
+        self.set_evaluation_context(
+            Some(EvaluationContext {
+                thread_id,
+                frame_index,
+                label: SharedString::default(),
+            }),
+            cx,
+        );
+    }
+
+    fn render_evaluation_context_picker(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let this = cx.entity().downgrade();
+        let session = self.session.clone();
+        let selected = self.evaluation_context.clone();
+        let label = selected
+            .as_ref()
+            .map_or_else(|| "Selected Frame".into(), |context| context.label.clone());
+
+        PopoverMenu::new("console-evaluation-context")
+            .trigger_with_tooltip(
+                Button::new("console-evaluation-context-button", label)
+                    .label_size(LabelSize::Small)
+                    .icon(IconName::ChevronDown)
+                    .icon_position(IconPosition::End)
+                    .icon_size(IconSize::XSmall)
+                    .icon_color(Color::Muted),
+                Tooltip::text("Thread and frame to evaluate expressions in"),
+            )
+            .anchor(gpui::Corner::TopRight)
+            .menu(move |window, cx| {
+                // Listing a thread's frames fetches them, so only do it once the menu is opened.
+                let threads = session.update(cx, |session, cx| {
+                    session
+                        .threads(cx)
+                        .into_iter()
+                        .filter(|(_, status)| *status == ThreadStatus::Stopped)
+                        .map(|(thread, _)| {
+                            let thread_id = ThreadId(thread.id);
+                            let frames = session
+                                .stack_frames(thread_id, cx)
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|stack_frame| SharedString::from(stack_frame.dap.name))
+                                .collect::<Vec<_>>();
+                            let name = if thread.name.is_empty() {
+                                format!("Tid: {}", thread.id)
+                            } else {
+                                thread.name
+                            };
+                            (thread_id, name, frames)
+                        })
+                        .collect::<Vec<_>>()
+                });
+                let this = this.clone();
+                let selected = selected.clone();
+                Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                    let select = |context: Option<EvaluationContext>| {
+                        let this = this.clone();
+                        move |_: &mut Window, cx: &mut App| {
+                            this.update(cx, |this, cx| {
+                                this.set_evaluation_context(context.clone(), cx)
+                            })
+                            .ok();
+                        }
+                    };
+                    let mut menu = menu.toggleable_entry(
+                        "Selected Frame",
+                        selected.is_none(),
+                        IconPosition::Start,
+                        None,
+                        select(None),
+                    );
+                    for (thread_id, name, frames) in threads {
+                        menu = menu.header(name.clone());
+                        // The stack may still be loading, in which case the top frame is offered.
+                        let frames = if frames.is_empty() {
+                            vec![SharedString::from("Top Frame")]
+                        } else {
+                            frames
+                        };
+                        for (frame_index, frame) in frames.into_iter().enumerate() {
+                            let context = EvaluationContext {
+                                thread_id,
+                                frame_index,
+                                label: format!("{name} › {frame}").into(),
+                            };
+                            let is_selected = selected.as_ref().is_some_and(|selected| {
+                                selected.thread_id == thread_id
+                                    && selected.frame_index == frame_index
+                            });
+                            menu = menu.toggleable_entry(
+                                frame,
+                                is_selected,
+                                IconPosition::Start,
+                                None,
+                                select(Some(context)),
+                            );
+                        }
+                    }
+                    menu
+                }))
+            })
+    }
+
+    fn previous_history_expression(
+        &mut self,
+        _: &MoveUp,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.query_bar.focus_handle(cx).is_focused(window) {
+            cx.propagate();
+            return;
+        }
+        if self.query_bar.read(cx).text(cx).is_empty() {
+            if let Some(expression) = self
+                .history
+                .current(&self.history_cursor)
+                .map(str::to_string)
+            {
+                self.set_query(&expression, window, cx);
+                return;
+            }
+        }
+
+        if let Some(expression) = self
+            .history
+            .previous(&mut self.history_cursor)
+            .map(str::to_string)
+        {
+            self.set_query(&expression, window, cx);
+        }
+    }
+
+    fn next_history_expression(
+        &mut self,
+        _: &MoveDown,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.query_bar.focus_handle(cx).is_focused(window) {
+            cx.propagate();
+            return;
+        }
+        if let Some(expression) = self
+            .history
+            .next(&mut self.history_cursor)
+            .map(str::to_string)
+        {
+            self.set_query(&expression, window, cx);
+        } else {
+            self.history_cursor.reset();
+            self.set_query("", window, cx);
+        }
+    }
+
+    fn set_query(&mut self, query: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.query_bar.update(cx, |editor, cx| {
+            editor.set_text(query, window, cx);
+            editor.move_to_end(&editor::actions::MoveToEnd, window, cx);
+        });
+    }
+
     fn render_console(&self, cx: &Context<Self>) -> impl IntoElement {
-        EditorElement::new(&self.console, Self::editor_style(&self.console, cx))
+        div()
+            .size_full()
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, window, cx| this.open_location_at_cursor(window, cx)),
+            )
+            .child(EditorElement::new(
+                &self.console,
+                Self::editor_style(&self.console, cx),
+            ))
     }
 
     fn editor_style(editor: &Entity<Editor>, cx: &Context<Self>) -> EditorStyle {
@@ -381,6 +1720,49 @@ impl Console {
         }
     }
 
+    fn render_search_bar(&self, cx: &Context<Self>) -> impl IntoElement {
+        let match_label = match (self.active_match, self.search_matches.len()) {
+            (_, 0) => "No matches".to_string(),
+            (Some(ix), count) => format!("{} of {count}", ix + 1),
+            (None, count) => format!("{count} matches"),
+        };
+
+        h_flex()
+            .key_context("ConsoleSearch")
+            .px_1()
+            .gap_1()
+            .child(
+                div()
+                    .flex_1()
+                    .px_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .rounded_sm()
+                    .child(self.search_editor.clone()),
+            )
+            .child(
+                Label::new(match_label)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                IconButton::new("console-search-previous", IconName::ChevronLeft)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Previous Match"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.select_previous_match(&SelectPreviousMatch, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("console-search-next", IconName::ChevronRight)
+                    .icon_size(IconSize::Small)
+                    .tooltip(Tooltip::text("Next Match"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.select_next_match(&SelectNextMatch, window, cx)
+                    })),
+            )
+    }
+
     fn render_query_bar(&self, cx: &Context<Self>) -> impl IntoElement {
         EditorElement::new(&self.query_bar, Self::editor_style(&self.query_bar, cx))
     }
@@ -412,7 +1794,18 @@ impl Render for Console {
             .track_focus(&self.focus_handle)
             .key_context("DebugConsole")
             .on_action(cx.listener(Self::evaluate))
+            .on_action(cx.listener(Self::previous_history_expression))
+            .on_action(cx.listener(Self::next_history_expression))
+            .on_action(cx.listener(Self::toggle_search))
+            .on_action(cx.listener(Self::select_next_match))
+            .on_action(cx.listener(Self::select_previous_match))
             .size_full()
+            .child(self.render_category_toggles(cx))
+            .when(self.show_search, |this| {
+                this.child(Divider::horizontal())
+                    .child(self.render_search_bar(cx))
+            })
+            .child(Divider::horizontal())
             .child(self.render_console(cx))
             .when(self.is_running(cx), |this| {
                 this.child(Divider::horizontal())
@@ -571,9 +1964,8 @@ impl ConsoleQueryBarCompletionProvider {
         cx: &mut Context<Editor>,
     ) -> Task<Result<Vec<CompletionResponse>>> {
         let completion_task = console.update(cx, |console, cx| {
+            let frame_id = console.evaluation_frame_id(cx);
             console.session.update(cx, |state, cx| {
-                let frame_id = console.stack_frame_list.read(cx).opened_stack_frame_id();
-
                 state.completions(
                     CompletionsQuery::new(buffer.read(cx), buffer_position, frame_id),
                     cx,
@@ -593,29 +1985,42 @@ impl ConsoleQueryBarCompletionProvider {
                         .unwrap_or(&completion.label)
                         .to_owned();
                     let buffer_text = snapshot.text();
-                    let buffer_bytes = buffer_text.as_bytes();
-                    let new_bytes = new_text.as_bytes();
-
-                    let mut prefix_len = 0;
-                    for i in (0..new_bytes.len()).rev() {
-                        if buffer_bytes.ends_with(&new_bytes[0..i]) {
-                            prefix_len = i;
-                            break;
+                    let buffer_offset = buffer_position.to_offset(&snapshot);
+
+                    let replace_range = if let Some(start) = completion.start {
+                        let end = start + completion.length.unwrap_or_default();
+                        let start = utf16_column_to_offset(&buffer_text, start);
+                        let end = utf16_column_to_offset(&buffer_text, end);
+                        snapshot.anchor_before(start)..snapshot.anchor_after(end)
+                    } else {
+                        // Without a start position, replace the end of the query that the
+                        // completion begins with.
+                        let buffer_bytes = buffer_text.as_bytes();
+                        let new_bytes = new_text.as_bytes();
+
+                        let mut prefix_len = 0;
+                        for i in (0..new_bytes.len()).rev() {
+                            if buffer_bytes.ends_with(&new_bytes[0..i]) {
+                                prefix_len = i;
+                                break;
+                            }
                         }
-                    }
 
-                    let buffer_offset = buffer_position.to_offset(&snapshot);
-                    let start = buffer_offset - prefix_len;
-                    let start = snapshot.clip_offset(start, Bias::Left);
-                    let start = snapshot.anchor_before(start);
-                    let replace_range = start..buffer_position;
+                        let start = buffer_offset - prefix_len;
+                        let start = snapshot.clip_offset(start, Bias::Left);
+                        snapshot.anchor_before(start)..buffer_position
+                    };
+                    let label = match completion.detail {
+                        Some(detail) => format!("{} {detail}", completion.label),
+                        None => completion.label.clone(),
+                    };
 
                     project::Completion {
                         replace_range,
                         new_text,
                         label: CodeLabel {
                             filter_range: 0..completion.label.len(),
-                            text: completion.label,
+                            text: label,
                             runs: Vec::new(),
                         },
                         icon_path: None,
@@ -638,25 +2043,74 @@ impl ConsoleQueryBarCompletionProvider {
     }
 }
 
+/// Converts a 1-based UTF-16 column of a single-line query, as adapters report them, to a byte
+/// offset into it.
+pub(crate) fn utf16_column_to_offset(text: &str, column: u64) -> usize {
+    let mut utf16_len = 0;
+    for (offset, ch) in text.char_indices() {
+        if utf16_len + 1 >= column {
+            return offset;
+        }
+        utf16_len += ch.len_utf16() as u64;
+    }
+    text.len()
+}
+
+/// The text style set by SGR escape sequences.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct AnsiStyle {
+    color: Option<ansi::Color>,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+}
+
+impl AnsiStyle {
+    fn to_highlight_style(&self, theme: &Theme) -> HighlightStyle {
+        let color = self
+            .color
+            .map(|color| terminal_view::terminal_element::convert_color(&color, theme));
+        HighlightStyle {
+            color,
+            font_weight: self.bold.then_some(FontWeight::BOLD),
+            font_style: self.italic.then_some(FontStyle::Italic),
+            underline: self.underline.then(|| UnderlineStyle {
+                color,
+                thickness: px(1.),
+                wavy: false,
+            }),
+            strikethrough: self.strikethrough.then(|| StrikethroughStyle {
+                color,
+                thickness: px(1.),
+            }),
+            // Matches how the terminal renders dim text.
+            fade_out: self.dim.then_some(0.3),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Default)]
 struct ConsoleHandler {
     output: String,
-    spans: Vec<(Range<usize>, Option<ansi::Color>)>,
+    spans: Vec<(Range<usize>, AnsiStyle)>,
     background_spans: Vec<(Range<usize>, Option<ansi::Color>)>,
     current_range_start: usize,
     current_background_range_start: usize,
-    current_color: Option<ansi::Color>,
+    current_style: AnsiStyle,
     current_background_color: Option<ansi::Color>,
     pos: usize,
 }
 
 impl ConsoleHandler {
-    fn break_span(&mut self, color: Option<ansi::Color>) {
+    fn break_span(&mut self, update: impl FnOnce(&mut AnsiStyle)) {
         self.spans.push((
             self.current_range_start..self.output.len(),
-            self.current_color,
+            self.current_style,
         ));
-        self.current_color = color;
+        update(&mut self.current_style);
         self.current_range_start = self.pos;
     }
 
@@ -689,14 +2143,29 @@ impl ansi::Handler for ConsoleHandler {
 
     fn terminal_attribute(&mut self, attr: ansi::Attr) {
         match attr {
-            ansi::Attr::Foreground(color) => {
-                self.break_span(Some(color));
-            }
+            ansi::Attr::Foreground(color) => self.break_span(|style| style.color = Some(color)),
             ansi::Attr::Background(color) => {
                 self.break_background_span(Some(color));
             }
+            ansi::Attr::Bold => self.break_span(|style| style.bold = true),
+            ansi::Attr::Dim => self.break_span(|style| style.dim = true),
+            ansi::Attr::Italic => self.break_span(|style| style.italic = true),
+            ansi::Attr::Underline
+            | ansi::Attr::DoubleUnderline
+            | ansi::Attr::Undercurl
+            | ansi::Attr::DottedUnderline
+            | ansi::Attr::DashedUnderline => self.break_span(|style| style.underline = true),
+            ansi::Attr::Strike => self.break_span(|style| style.strikethrough = true),
+            ansi::Attr::CancelBold => self.break_span(|style| style.bold = false),
+            ansi::Attr::CancelBoldDim => self.break_span(|style| {
+                style.bold = false;
+                style.dim = false;
+            }),
+            ansi::Attr::CancelItalic => self.break_span(|style| style.italic = false),
+            ansi::Attr::CancelUnderline => self.break_span(|style| style.underline = false),
+            ansi::Attr::CancelStrike => self.break_span(|style| style.strikethrough = false),
             ansi::Attr::Reset => {
-                self.break_span(None);
+                self.break_span(|style| *style = AnsiStyle::default());
                 self.break_background_span(None);
             }
             _ => {}
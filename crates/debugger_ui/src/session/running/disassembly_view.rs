@@ -0,0 +1,423 @@
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+use crate::{StepInto, StepOut, StepOver};
+use dap::{DisassembledInstruction, StackFrameId, SteppingGranularity};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent, ThreadId};
+use settings::Settings as _;
+use std::ops::Range;
+use theme::ThemeSettings;
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+
+/// How many instructions are shown before and after the one the selected frame is at.
+const INSTRUCTIONS_AROUND_PC: u64 = 64;
+
+#[derive(Debug, Clone, PartialEq)]
+enum DisassemblyEntry {
+    /// The function the following instructions belong to.
+    Symbol(SharedString),
+    Instruction {
+        address: SharedString,
+        bytes: Option<SharedString>,
+        instruction: SharedString,
+        is_pc: bool,
+    },
+}
+
+/// The instructions around the current instruction pointer of the selected stack frame.
+pub(crate) struct DisassemblyView {
+    session: Entity<Session>,
+    state: WeakEntity<RunningState>,
+    stack_frame_list: Entity<StackFrameList>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    selected_stack_frame_id: Option<StackFrameId>,
+    entries: Vec<DisassemblyEntry>,
+    error: Option<SharedString>,
+    _fetch_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        state: WeakEntity<RunningState>,
+        stack_frame_list: Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
+                    this.selected_stack_frame_id = Some(*stack_frame_id);
+                    if this._fetch_task.is_some() {
+                        this.schedule_fetch(cx);
+                    }
+                }
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) => {
+                    this.selected_stack_frame_id.take();
+                    cx.notify();
+                }
+                SessionEvent::InstructionBreakpoints => cx.notify(),
+                _ => {}
+            }),
+        ];
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            state,
+            stack_frame_list,
+            focus_handle: cx.focus_handle(),
+            selected_stack_frame_id: None,
+            entries: Vec::new(),
+            error: None,
+            _fetch_task: None,
+            _subscriptions,
+        }
+    }
+
+    fn schedule_fetch(&mut self, cx: &mut Context<Self>) {
+        let Some(stack_frame_id) = self.selected_stack_frame_id else {
+            self._fetch_task = Some(Task::ready(()));
+            return;
+        };
+        let instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .flatten_entries(true, true)
+            .into_iter()
+            .find(|stack_frame| stack_frame.id == stack_frame_id)
+            .and_then(|stack_frame| stack_frame.instruction_pointer_reference);
+        let Some(instruction_pointer) = instruction_pointer else {
+            self.entries.clear();
+            self.error =
+                Some("The debug adapter reports no instruction pointer for this frame".into());
+            self._fetch_task = Some(Task::ready(()));
+            cx.notify();
+            return;
+        };
+
+        let instructions = self.session.read(cx).disassemble(
+            instruction_pointer.clone(),
+            -(INSTRUCTIONS_AROUND_PC as i64),
+            INSTRUCTIONS_AROUND_PC * 2 + 1,
+        );
+        self._fetch_task = Some(cx.spawn(async move |this, cx| {
+            let instructions = instructions.await;
+            this.update(cx, |this, cx| {
+                match instructions {
+                    Ok(instructions) => {
+                        this.entries = disassembly_entries(instructions, &instruction_pointer);
+                        this.error = None;
+                        if let Some(pc_ix) = this.entries.iter().position(|entry| {
+                            matches!(entry, DisassemblyEntry::Instruction { is_pc: true, .. })
+                        }) {
+                            this.scroll_handle
+                                .scroll_to_item(pc_ix, ScrollStrategy::Center);
+                        }
+                    }
+                    Err(error) => {
+                        this.entries.clear();
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn thread_id(&self, cx: &App) -> Option<ThreadId> {
+        self.state
+            .read_with(cx, |state, _| state.thread_id())
+            .ok()
+            .flatten()
+    }
+
+    /// Steps by single instructions while the disassembly is focused, for adapters that can.
+    fn step(
+        &mut self,
+        step: fn(&mut Session, ThreadId, SteppingGranularity, &mut Context<Session>),
+        cx: &mut Context<Self>,
+    ) {
+        let supports_instruction_stepping = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_stepping_granularity
+            .unwrap_or_default();
+        let Some(thread_id) = self.thread_id(cx).filter(|_| supports_instruction_stepping) else {
+            cx.propagate();
+            return;
+        };
+        self.session.update(cx, |session, cx| {
+            step(session, thread_id, SteppingGranularity::Instruction, cx)
+        });
+    }
+
+    fn has_instruction_breakpoint(&self, address: &str, cx: &App) -> bool {
+        self.session
+            .read(cx)
+            .instruction_breakpoints()
+            .any(|(breakpoint, _)| addresses_match(breakpoint, address))
+    }
+
+    fn toggle_instruction_breakpoint(&mut self, address: &str, cx: &mut Context<Self>) {
+        let address = self
+            .session
+            .read(cx)
+            .instruction_breakpoints()
+            .find(|(breakpoint, _)| addresses_match(breakpoint, address))
+            .map_or_else(|| address.to_string(), |(breakpoint, _)| breakpoint.clone());
+        self.session.update(cx, |session, cx| {
+            session.toggle_instruction_breakpoint(address, cx)
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn toggle_instruction_breakpoint_at(&mut self, ix: usize, cx: &mut Context<Self>) {
+        if let Some(DisassemblyEntry::Instruction { address, .. }) = self.entries.get(ix).cloned() {
+            self.toggle_instruction_breakpoint(&address, cx);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn visual_entries(&self, cx: &App) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                DisassemblyEntry::Symbol(symbol) => format!("{symbol}:"),
+                DisassemblyEntry::Instruction {
+                    address,
+                    instruction,
+                    is_pc,
+                    ..
+                } => {
+                    let marker = if *is_pc {
+                        ">"
+                    } else if self.has_instruction_breakpoint(address, cx) {
+                        "*"
+                    } else {
+                        " "
+                    };
+                    format!("{marker} {address} {instruction}")
+                }
+            })
+            .collect()
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            DisassemblyEntry::Symbol(symbol) => h_flex()
+                .id(("disassembly-symbol", ix))
+                .px_1()
+                .pt_1()
+                .child(
+                    Label::new(symbol.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+            DisassemblyEntry::Instruction {
+                address,
+                bytes,
+                instruction,
+                is_pc,
+            } => {
+                let has_breakpoint = self.has_instruction_breakpoint(address, cx);
+                let supports_breakpoints = self.session.read(cx).supports_instruction_breakpoints();
+                h_flex()
+                    .id(("disassembly-instruction", ix))
+                    .px_1()
+                    .gap_2()
+                    .when(*is_pc, |this| {
+                        this.bg(cx.theme().colors().editor_debugger_active_line_background)
+                    })
+                    .hover(|style| style.bg(cx.theme().colors().element_hover))
+                    .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+                    .child(
+                        div()
+                            .id(("disassembly-breakpoint", ix))
+                            .w_3()
+                            .flex_none()
+                            .map(|this| {
+                                if *is_pc {
+                                    this.child(
+                                        Icon::new(IconName::DebugStepOver)
+                                            .size(IconSize::XSmall)
+                                            .color(Color::Warning),
+                                    )
+                                } else if has_breakpoint {
+                                    this.child(
+                                        Icon::new(IconName::DebugBreakpoint)
+                                            .size(IconSize::XSmall)
+                                            .color(Color::Debugger),
+                                    )
+                                } else {
+                                    this
+                                }
+                            })
+                            .when(supports_breakpoints, |this| {
+                                let address = address.clone();
+                                this.cursor_pointer()
+                                    .tooltip(Tooltip::text(if has_breakpoint {
+                                        "Remove Instruction Breakpoint"
+                                    } else {
+                                        "Set Instruction Breakpoint"
+                                    }))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.toggle_instruction_breakpoint(&address, cx);
+                                    }))
+                            }),
+                    )
+                    .child(
+                        Label::new(address.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .when_some(bytes.clone(), |this, bytes| {
+                        this.child(
+                            Label::new(bytes)
+                                .size(LabelSize::Small)
+                                .color(Color::Disabled),
+                        )
+                    })
+                    .child(Label::new(instruction.clone()).size(LabelSize::Small))
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("disassembly-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+/// Lists the instructions under the symbols they belong to, marking the one at `instruction_pointer`.
+fn disassembly_entries(
+    instructions: Vec<DisassembledInstruction>,
+    instruction_pointer: &str,
+) -> Vec<DisassemblyEntry> {
+    let mut entries = Vec::with_capacity(instructions.len());
+    let mut symbol = None;
+    for instruction in instructions {
+        if instruction.symbol.is_some() && instruction.symbol != symbol {
+            symbol = instruction.symbol.clone();
+            entries.extend(
+                symbol
+                    .clone()
+                    .map(|symbol| DisassemblyEntry::Symbol(symbol.into())),
+            );
+        }
+        entries.push(DisassemblyEntry::Instruction {
+            is_pc: addresses_match(&instruction.address, instruction_pointer),
+            address: instruction.address.into(),
+            bytes: instruction.instruction_bytes.map(Into::into),
+            instruction: instruction.instruction.into(),
+        });
+    }
+    entries
+}
+
+/// Adapters format addresses differently, e.g. with or without leading zeros.
+fn addresses_match(address: &str, other: &str) -> bool {
+    fn parse(address: &str) -> Option<u64> {
+        let address = address.trim();
+        let hex = address
+            .strip_prefix("0x")
+            .or_else(|| address.strip_prefix("0X"))?;
+        u64::from_str_radix(hex, 16).ok()
+    }
+    match (parse(address), parse(other)) {
+        (Some(address), Some(other)) => address == other,
+        _ => address == other,
+    }
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self._fetch_task.is_none() {
+            self.schedule_fetch(cx);
+        }
+
+        div()
+            .track_focus(&self.focus_handle)
+            .key_context("DisassemblyView")
+            .on_action(cx.listener(|this, _: &StepOver, _, cx| this.step(Session::step_over, cx)))
+            .on_action(cx.listener(|this, _: &StepInto, _, cx| this.step(Session::step_in, cx)))
+            .on_action(cx.listener(|this, _: &StepOut, _, cx| this.step(Session::step_out, cx)))
+            .size_full()
+            .p_1()
+            .map(|this| {
+                if let Some(error) = self.error.clone() {
+                    this.child(Label::new(error).size(LabelSize::Small).color(Color::Muted))
+                } else if self.entries.is_empty() {
+                    this.child(
+                        Label::new("Select a stack frame to see its instructions")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            "disassembly-view",
+                            self.entries.len(),
+                            cx.processor(|this, range: Range<usize>, _window, cx| {
+                                range.map(|ix| this.render_entry(ix, cx)).collect()
+                            }),
+                        )
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full(),
+                    )
+                }
+            })
+            .child(self.render_vertical_scrollbar(cx))
+    }
+}
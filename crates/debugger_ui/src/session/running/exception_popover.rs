@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use collections::HashSet;
+use dap::{ExceptionDetails, ExceptionInfoResponse};
+use editor::{
+    Editor,
+    display_map::{
+        BlockContext, BlockPlacement, BlockProperties, BlockStyle, CustomBlockId, RenderBlock,
+    },
+};
+use gpui::{Entity, Subscription, WeakEntity};
+use project::debugger::{
+    breakpoint_store::{ActiveStackFrame, BreakpointStore, BreakpointStoreEvent},
+    session::{Session, SessionEvent},
+};
+use settings::Settings as _;
+use theme::ThemeSettings;
+use ui::{Tooltip, prelude::*};
+use workspace::Workspace;
+
+/// How many lines of the stack trace and inner exceptions are shown below the throwing line.
+const MAX_DETAIL_LINES: usize = 8;
+
+/// The exception's type and message, e.g. `ValueError: invalid literal for int()`.
+pub(crate) fn exception_summary(exception_info: &ExceptionInfoResponse) -> SharedString {
+    let details = exception_info.details.as_ref();
+    let type_name = details
+        .and_then(|details| details.type_name.clone())
+        .unwrap_or_else(|| exception_info.exception_id.clone());
+    let message = exception_info
+        .description
+        .clone()
+        .or_else(|| details.and_then(|details| details.message.clone()));
+    match message {
+        Some(message) => format!("{type_name}: {message}").into(),
+        None => type_name.into(),
+    }
+}
+
+/// The exception's stack trace followed by its inner exceptions, one line each.
+pub(crate) fn exception_detail_lines(exception_info: &ExceptionInfoResponse) -> Vec<SharedString> {
+    fn push_details(details: &ExceptionDetails, lines: &mut Vec<SharedString>) {
+        if let Some(stack_trace) = &details.stack_trace {
+            lines.extend(
+                stack_trace
+                    .lines()
+                    .map(str::trim_end)
+                    .filter(|line| !line.is_empty())
+                    .map(|line| SharedString::from(line.to_string())),
+            );
+        }
+        for inner in details.inner_exception.iter().flatten() {
+            let type_name = inner
+                .type_name
+                .clone()
+                .unwrap_or_else(|| "Exception".to_string());
+            lines.push(match &inner.message {
+                Some(message) => format!("Caused by {type_name}: {message}").into(),
+                None => format!("Caused by {type_name}").into(),
+            });
+            push_details(inner, lines);
+        }
+    }
+
+    let mut lines = Vec::new();
+    if let Some(details) = &exception_info.details {
+        push_details(details, &mut lines);
+    }
+    lines
+}
+
+/// Shows the exception a thread stopped on at the end of the line that threw it, with its stack
+/// trace and inner exceptions in a dismissable block below that line.
+pub(crate) struct ExceptionPopover {
+    session: Entity<Session>,
+    workspace: WeakEntity<Workspace>,
+    breakpoint_store: Entity<BreakpointStore>,
+    /// The first position opened after the session stopped, which is where the exception was thrown.
+    throw_site: Option<ActiveStackFrame>,
+    blocks: Vec<(WeakEntity<Editor>, CustomBlockId)>,
+    annotated_editors: Vec<WeakEntity<Editor>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ExceptionPopover {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        workspace: WeakEntity<Workspace>,
+        breakpoint_store: Entity<BreakpointStore>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) => {
+                    this.throw_site = None;
+                    this.refresh(cx);
+                }
+                SessionEvent::ExceptionInfo => this.refresh(cx),
+                _ => {}
+            }),
+            cx.subscribe(&breakpoint_store, |this, breakpoint_store, event, cx| {
+                let BreakpointStoreEvent::SetDebugLine = event else {
+                    return;
+                };
+                if this.throw_site.is_some() {
+                    return;
+                }
+                let session_id = this.session.read(cx).session_id();
+                this.throw_site = breakpoint_store
+                    .read(cx)
+                    .active_position()
+                    .filter(|position| position.session_id == session_id)
+                    .cloned();
+                this.refresh(cx);
+            }),
+        ];
+
+        Self {
+            session,
+            workspace,
+            breakpoint_store,
+            throw_site: None,
+            blocks: Vec::new(),
+            annotated_editors: Vec::new(),
+            _subscriptions,
+        }
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        for (editor, block_id) in self.blocks.drain(..) {
+            editor
+                .update(cx, |editor, cx| {
+                    editor.remove_blocks(HashSet::from_iter([block_id]), None, cx)
+                })
+                .ok();
+        }
+        for editor in self.annotated_editors.drain(..) {
+            editor
+                .update(cx, |editor, cx| editor.set_exception_annotation(None, cx))
+                .ok();
+        }
+
+        let Some(throw_site) = self.throw_site.clone() else {
+            return;
+        };
+        // The session may have stopped somewhere else without a frame being opened yet.
+        if self
+            .breakpoint_store
+            .read(cx)
+            .active_position()
+            .is_none_or(|position| position.session_id != throw_site.session_id)
+        {
+            return;
+        }
+        let Some(exception_info) = self.session.read(cx).exception_info(throw_site.thread_id)
+        else {
+            return;
+        };
+        let summary = exception_summary(exception_info);
+        let mut detail_lines = exception_detail_lines(exception_info);
+        if detail_lines.len() > MAX_DETAIL_LINES {
+            detail_lines.truncate(MAX_DETAIL_LINES);
+            detail_lines.push("…".into());
+        }
+        let Some(buffer_id) = throw_site.position.buffer_id else {
+            return;
+        };
+
+        let Ok(editors) = self.workspace.read_with(cx, |workspace, cx| {
+            workspace.items_of_type::<Editor>(cx).collect::<Vec<_>>()
+        }) else {
+            return;
+        };
+        for editor in editors {
+            editor.update(cx, |editor, cx| {
+                editor.set_exception_annotation(Some((throw_site.position, summary.clone())), cx)
+            });
+            self.annotated_editors.push(editor.downgrade());
+            if detail_lines.is_empty() {
+                continue;
+            }
+
+            let block_ids = editor.update(cx, |editor, cx| {
+                let multibuffer = editor.buffer().read(cx);
+                let snapshot = multibuffer.snapshot(cx);
+                let Some(anchor) = multibuffer
+                    .excerpts_for_buffer(buffer_id, cx)
+                    .into_iter()
+                    .find_map(|(excerpt_id, _)| {
+                        snapshot.anchor_in_excerpt(excerpt_id, throw_site.position)
+                    })
+                else {
+                    return Vec::new();
+                };
+                editor.insert_blocks(
+                    [BlockProperties {
+                        placement: BlockPlacement::Below(anchor),
+                        height: Some(detail_lines.len() as u32 + 2),
+                        style: BlockStyle::Flex,
+                        render: render_exception_block(
+                            self.session.downgrade(),
+                            detail_lines.clone(),
+                        ),
+                        priority: 0,
+                        render_in_minimap: false,
+                    }],
+                    None,
+                    cx,
+                )
+            });
+            self.blocks.extend(
+                block_ids
+                    .into_iter()
+                    .map(|block_id| (editor.downgrade(), block_id)),
+            );
+        }
+    }
+}
+
+fn render_exception_block(
+    session: WeakEntity<Session>,
+    detail_lines: Vec<SharedString>,
+) -> RenderBlock {
+    Arc::new(move |cx: &mut BlockContext| {
+        let colors = cx.app.theme().status();
+        let buffer_font_family = ThemeSettings::get_global(cx.app).buffer_font.family.clone();
+        let session = session.clone();
+        v_flex()
+            .id(cx.block_id)
+            .ml(cx.margins.gutter.full_width())
+            .mr_4()
+            .px_2()
+            .py_0p5()
+            .border_l_2()
+            .border_color(colors.error_border)
+            .bg(colors.error_background)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Icon::new(IconName::XCircle)
+                            .size(IconSize::Small)
+                            .color(Color::Error),
+                    )
+                    .child(
+                        Label::new("Exception details")
+                            .size(LabelSize::Small)
+                            .color(Color::Error),
+                    )
+                    .child(div().flex_1())
+                    .child(
+                        IconButton::new("dismiss-exception-info", IconName::Close)
+                            .icon_size(IconSize::XSmall)
+                            .tooltip(Tooltip::text("Dismiss"))
+                            .on_click(move |_, _, cx| {
+                                session
+                                    .update(cx, |session, cx| session.dismiss_exception_info(cx))
+                                    .ok();
+                            }),
+                    ),
+            )
+            .children(detail_lines.iter().map(|line| {
+                div().font_family(buffer_font_family.clone()).child(
+                    Label::new(line.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .truncate(),
+                )
+            }))
+            .into_any_element()
+    })
+}
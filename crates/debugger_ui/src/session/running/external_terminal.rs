@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+
+/// Terminal emulators tried, in order, when `$TERMINAL` isn't set.
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const LINUX_TERMINALS: &[&str] = &["x-terminal-emulator", "gnome-terminal", "konsole", "xterm"];
+
+/// A shell command line that runs `args` in `cwd` with `env` set, then waits for Enter so that
+/// the program's last output stays visible after it exits.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn shell_script(
+    args: &[String],
+    cwd: Option<&Path>,
+    env: &HashMap<String, String>,
+) -> Result<String> {
+    let mut script = String::new();
+    if let Some(cwd) = cwd {
+        script.push_str(&format!(
+            "cd {} && ",
+            shlex::try_quote(&cwd.to_string_lossy())?
+        ));
+    }
+    if !env.is_empty() {
+        let mut env = env.iter().collect::<Vec<_>>();
+        env.sort();
+        script.push_str("env ");
+        for (key, value) in env {
+            script.push_str(&shlex::try_quote(&format!("{key}={value}"))?);
+            script.push(' ');
+        }
+    }
+    script.push_str(&shlex::try_join(args.iter().map(String::as_str))?);
+    script.push_str("; echo; printf 'Press Enter to close this window...'; read _");
+    Ok(script)
+}
+
+/// Launches `args` in the operating system's terminal app rather than Zed's integrated terminal.
+/// The terminal isn't waited on, since the program runs in it for as long as it's debugged.
+pub(crate) fn run_in_external_terminal(
+    title: String,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    env: HashMap<String, String>,
+) -> Result<()> {
+    let mut command = external_terminal_command(&title, &args, cwd.as_deref(), &env)?;
+    if let Some(cwd) = &cwd {
+        command.current_dir(cwd);
+    }
+    command
+        .envs(env)
+        .spawn()
+        .context("failed to launch the external terminal")?;
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn external_terminal_command(
+    _title: &str,
+    args: &[String],
+    cwd: Option<&Path>,
+    env: &HashMap<String, String>,
+) -> Result<smol::process::Command> {
+    let script = shell_script(args, cwd, env)?
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+    let mut command = util::command::new_smol_command("osascript");
+    command
+        .arg("-e")
+        .arg(format!(
+            "tell application \"Terminal\" to do script \"{script}\""
+        ))
+        .arg("-e")
+        .arg("tell application \"Terminal\" to activate");
+    Ok(command)
+}
+
+#[cfg(target_os = "windows")]
+fn external_terminal_command(
+    title: &str,
+    args: &[String],
+    _cwd: Option<&Path>,
+    _env: &HashMap<String, String>,
+) -> Result<smol::process::Command> {
+    use smol::process::windows::CommandExt as _;
+
+    // `start` takes its first quoted argument as the window's title, and `/K` keeps the window
+    // open after the program exits. The command line is passed as is, since `cmd` doesn't
+    // follow the quoting rules `Command` escapes arguments with.
+    let mut command = util::command::new_smol_command("cmd");
+    command.raw_arg(format!("/C start {} cmd /K", quote_windows_arg(title)));
+    for arg in args {
+        command.raw_arg(quote_windows_arg(arg));
+    }
+    Ok(command)
+}
+
+/// Quotes `arg` so that `cmd` passes characters such as spaces, `&` or `|` on to the program,
+/// which unescapes quotes and the backslashes before them.
+#[cfg(target_os = "windows")]
+fn quote_windows_arg(arg: &str) -> String {
+    let mut quoted = String::from('"');
+    let mut backslashes = 0;
+    for char in arg.chars() {
+        if char == '"' {
+            quoted.extend(std::iter::repeat_n('\\', backslashes + 1));
+        }
+        backslashes = if char == '\\' { backslashes + 1 } else { 0 };
+        quoted.push(char);
+    }
+    quoted.extend(std::iter::repeat_n('\\', backslashes));
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn external_terminal_command(
+    _title: &str,
+    args: &[String],
+    cwd: Option<&Path>,
+    env: &HashMap<String, String>,
+) -> Result<smol::process::Command> {
+    let terminal = std::env::var("TERMINAL")
+        .ok()
+        .filter(|terminal| !terminal.is_empty())
+        .or_else(|| {
+            let paths = std::env::var_os("PATH")?;
+            LINUX_TERMINALS
+                .iter()
+                .find(|terminal| {
+                    std::env::split_paths(&paths).any(|path| path.join(terminal).is_file())
+                })
+                .map(|terminal| terminal.to_string())
+        })
+        .context("no terminal emulator found; set the TERMINAL environment variable")?;
+    let script = shell_script(args, cwd, env)?;
+    let mut command = util::command::new_smol_command(&terminal);
+    // gnome-terminal deprecated `-e` in favor of `--`.
+    if Path::new(&terminal).ends_with("gnome-terminal") {
+        command.arg("--");
+    } else {
+        command.arg("-e");
+    }
+    command.args(["sh", "-c", &script]);
+    Ok(command)
+}
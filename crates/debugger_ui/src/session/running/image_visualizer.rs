@@ -0,0 +1,385 @@
+use anyhow::{Context as _, Result, anyhow};
+use dap::VariableReference;
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, RenderImage, Task, img};
+use project::debugger::session::{Session, SessionEvent};
+use std::sync::Arc;
+use ui::prelude::*;
+
+/// Images larger than this are refused rather than read from the debuggee.
+const MAX_IMAGE_BYTES: u64 = 64 * 1024 * 1024;
+/// Longest side of the rendered image, in pixels.
+const MAX_PREVIEW_SIZE: f32 = 384.;
+
+/// How the bytes of a buffer map onto pixels.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum PixelFormat {
+    #[default]
+    Rgba8,
+    Bgra8,
+    Rgb8,
+    Gray8,
+}
+
+impl PixelFormat {
+    const ALL: [Self; 4] = [Self::Rgba8, Self::Bgra8, Self::Rgb8, Self::Gray8];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Rgba8 => "RGBA8",
+            Self::Bgra8 => "BGRA8",
+            Self::Rgb8 => "RGB8",
+            Self::Gray8 => "Gray8",
+        }
+    }
+
+    fn bytes_per_pixel(&self) -> u64 {
+        match self {
+            Self::Rgba8 | Self::Bgra8 => 4,
+            Self::Rgb8 => 3,
+            Self::Gray8 => 1,
+        }
+    }
+}
+
+/// Converts the first `width * height` pixels of `bytes` to BGRA, the layout GPUI renders.
+pub(crate) fn pixels_to_bgra(
+    bytes: &[u8],
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+) -> Result<Vec<u8>> {
+    let pixel_count = width as usize * height as usize;
+    let needed = pixel_count * format.bytes_per_pixel() as usize;
+    anyhow::ensure!(
+        bytes.len() >= needed,
+        "expected {needed} bytes for a {width}×{height} {} image, got {}",
+        format.label(),
+        bytes.len()
+    );
+
+    let mut bgra = Vec::with_capacity(pixel_count * 4);
+    let bytes = &bytes[..needed];
+    match format {
+        PixelFormat::Bgra8 => bgra.extend_from_slice(bytes),
+        PixelFormat::Rgba8 => {
+            for pixel in bytes.chunks_exact(4) {
+                bgra.extend_from_slice(&[pixel[2], pixel[1], pixel[0], pixel[3]]);
+            }
+        }
+        PixelFormat::Rgb8 => {
+            for pixel in bytes.chunks_exact(3) {
+                bgra.extend_from_slice(&[pixel[2], pixel[1], pixel[0], u8::MAX]);
+            }
+        }
+        PixelFormat::Gray8 => {
+            for &value in bytes {
+                bgra.extend_from_slice(&[value, value, value, u8::MAX]);
+            }
+        }
+    }
+    Ok(bgra)
+}
+
+/// Parses an element of a byte array as adapters display it, e.g. `255`, `0xff` or `65 'A'`.
+pub(crate) fn parse_byte(value: &str) -> Option<u8> {
+    let value = value.split_whitespace().next()?;
+    let value = value.strip_prefix('+').unwrap_or(value);
+    if let Some(hex) = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        return u8::from_str_radix(hex, 16).ok();
+    }
+    // Signed byte types display values above 127 as negative numbers.
+    match value.parse::<i16>().ok()? {
+        value @ 0..=255 => Some(value as u8),
+        value @ -128..=-1 => Some(value as i8 as u8),
+        _ => None,
+    }
+}
+
+/// Where the pixels of a visualized variable are read from.
+#[derive(Debug, Clone)]
+pub(crate) enum ImageSource {
+    /// Memory the variable points to, read with a `readMemory` request.
+    Memory(String),
+    /// The indexed elements of a byte array.
+    Elements(VariableReference),
+}
+
+impl ImageSource {
+    pub(crate) fn for_variable(variable: &dap::Variable) -> Option<Self> {
+        if let Some(memory_reference) = variable.memory_reference.clone() {
+            Some(Self::Memory(memory_reference))
+        } else if variable.variables_reference != 0 && variable.indexed_variables.is_some() {
+            Some(Self::Elements(variable.variables_reference))
+        } else {
+            None
+        }
+    }
+}
+
+/// A popover that renders a byte buffer as an image, using the dimensions and pixel format
+/// entered by the user.
+pub(crate) struct ImageVisualizer {
+    session: Entity<Session>,
+    name: SharedString,
+    source: ImageSource,
+    focus_handle: FocusHandle,
+    width_editor: Entity<Editor>,
+    height_editor: Entity<Editor>,
+    format: PixelFormat,
+    image: Option<(Arc<RenderImage>, u32, u32)>,
+    error: Option<SharedString>,
+    loading: bool,
+    _load_task: Task<()>,
+    _subscription: gpui::Subscription,
+}
+
+impl ImageVisualizer {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        name: SharedString,
+        source: ImageSource,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let width_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Width", cx);
+            editor
+        });
+        let height_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Height", cx);
+            editor
+        });
+        // The buffer may have moved or been freed once the debuggee resumes.
+        let _subscription = cx.subscribe(&session, |_, _, event, cx| {
+            if let SessionEvent::Stopped(_) = event {
+                cx.emit(DismissEvent);
+            }
+        });
+
+        Self {
+            session,
+            name,
+            source,
+            focus_handle: cx.focus_handle(),
+            width_editor,
+            height_editor,
+            format: PixelFormat::default(),
+            image: None,
+            error: None,
+            loading: false,
+            _load_task: Task::ready(()),
+            _subscription,
+        }
+    }
+
+    pub(crate) fn focus_width_editor(&self, window: &mut Window, cx: &mut App) {
+        window.focus(&self.width_editor.focus_handle(cx));
+    }
+
+    fn set_format(&mut self, format: PixelFormat, cx: &mut Context<Self>) {
+        self.format = format;
+        cx.notify();
+    }
+
+    fn dimension(editor: &Entity<Editor>, cx: &App) -> Option<u32> {
+        editor
+            .read(cx)
+            .text(cx)
+            .trim()
+            .parse()
+            .ok()
+            .filter(|value| *value > 0)
+    }
+
+    fn render_image(&mut self, cx: &mut Context<Self>) {
+        let (Some(width), Some(height)) = (
+            Self::dimension(&self.width_editor, cx),
+            Self::dimension(&self.height_editor, cx),
+        ) else {
+            self.error = Some("Width and height must be positive integers".into());
+            cx.notify();
+            return;
+        };
+        let format = self.format;
+        let byte_count = width as u64 * height as u64 * format.bytes_per_pixel();
+        if byte_count > MAX_IMAGE_BYTES {
+            self.error = Some(format!("{byte_count} bytes is too large to visualize").into());
+            cx.notify();
+            return;
+        }
+
+        let session = self.session.read(cx);
+        let bytes = match &self.source {
+            ImageSource::Memory(memory_reference) => {
+                session.read_memory(memory_reference.clone(), 0, byte_count)
+            }
+            ImageSource::Elements(variables_reference) => {
+                let elements = session.fetch_variables(*variables_reference, Some(0..byte_count));
+                cx.background_spawn(async move {
+                    elements
+                        .await?
+                        .iter()
+                        .map(|element| {
+                            parse_byte(&element.value).ok_or_else(|| {
+                                anyhow!("{} = {} is not a byte", element.name, element.value)
+                            })
+                        })
+                        .collect::<Result<Vec<u8>>>()
+                })
+            }
+        };
+
+        self.loading = true;
+        self.error = None;
+        self._load_task = cx.spawn(async move |this, cx| {
+            let image = async {
+                let bgra = pixels_to_bgra(&bytes.await?, width, height, format)?;
+                let buffer = image::RgbaImage::from_raw(width, height, bgra)
+                    .context("creating image buffer")?;
+                anyhow::Ok(Arc::new(RenderImage::new(vec![image::Frame::new(buffer)])))
+            }
+            .await;
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match image {
+                    Ok(image) => this.image = Some((image, width, height)),
+                    Err(error) => {
+                        this.image = None;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        self.render_image(cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn rendered_size(&self) -> Option<(u32, u32)> {
+        self.image
+            .as_ref()
+            .map(|(_, width, height)| (*width, *height))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn error(&self) -> Option<&SharedString> {
+        self.error.as_ref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_dimensions(
+        &mut self,
+        width: u32,
+        height: u32,
+        format: PixelFormat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.width_editor.update(cx, |editor, cx| {
+            editor.set_text(width.to_string(), window, cx)
+        });
+        self.height_editor.update(cx, |editor, cx| {
+            editor.set_text(height.to_string(), window, cx)
+        });
+        self.format = format;
+        self.render_image(cx);
+    }
+}
+
+impl EventEmitter<DismissEvent> for ImageVisualizer {}
+
+impl Focusable for ImageVisualizer {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for ImageVisualizer {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let editor_field = |editor: &Entity<Editor>| {
+            div()
+                .w_16()
+                .px_1()
+                .border_1()
+                .border_color(cx.theme().colors().border_variant)
+                .rounded_sm()
+                .child(editor.clone())
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("ImageVisualizer")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_mouse_down_out(cx.listener(|_, _, _, cx| cx.emit(DismissEvent)))
+            .elevation_2(cx)
+            .p_2()
+            .gap_2()
+            .min_w_64()
+            .child(
+                Label::new(format!("View {} as Image", self.name))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(editor_field(&self.width_editor))
+                    .child(Label::new("×").size(LabelSize::Small).color(Color::Muted))
+                    .child(editor_field(&self.height_editor))
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("render-image", "Render")
+                            .label_size(LabelSize::Small)
+                            .disabled(self.loading)
+                            .on_click(cx.listener(|this, _, _, cx| this.render_image(cx))),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .children(PixelFormat::ALL.into_iter().map(|format| {
+                        Button::new(format.label(), format.label())
+                            .label_size(LabelSize::Small)
+                            .toggle_state(self.format == format)
+                            .on_click(
+                                cx.listener(move |this, _, _, cx| this.set_format(format, cx)),
+                            )
+                    })),
+            )
+            .when(self.loading, |this| {
+                this.child(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .children(
+                self.error
+                    .clone()
+                    .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+            )
+            .children(self.image.clone().map(|(image, width, height)| {
+                let scale = MAX_PREVIEW_SIZE / width.max(height) as f32;
+                div()
+                    .w(px(width as f32 * scale))
+                    .h(px(height as f32 * scale))
+                    .child(img(image).size_full())
+            }))
+    }
+}
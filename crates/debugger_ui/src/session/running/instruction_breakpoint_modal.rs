@@ -0,0 +1,96 @@
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable};
+use project::debugger::session::Session;
+use ui::prelude::*;
+use workspace::ModalView;
+
+/// A modal for setting a breakpoint on an instruction address that's typed in.
+pub(crate) struct InstructionBreakpointModal {
+    session: Entity<Session>,
+    address_editor: Entity<Editor>,
+}
+
+impl InstructionBreakpointModal {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let address_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Instruction address, e.g. 0x100003f70", cx);
+            editor
+        });
+
+        Self {
+            session,
+            address_editor,
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let address = self.address_editor.read(cx).text(cx).trim().to_string();
+        if address.is_empty() {
+            return;
+        }
+
+        self.session.update(cx, |session, cx| {
+            let is_set = session
+                .instruction_breakpoints()
+                .any(|(breakpoint, _)| *breakpoint == address);
+            if !is_set {
+                session.toggle_instruction_breakpoint(address, cx);
+            }
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for InstructionBreakpointModal {}
+
+impl Focusable for InstructionBreakpointModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.address_editor.focus_handle(cx)
+    }
+}
+
+impl ModalView for InstructionBreakpointModal {}
+
+impl Render for InstructionBreakpointModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("InstructionBreakpointModal")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(34.))
+            .p_2()
+            .gap_2()
+            .child(
+                Label::new("Add Instruction Breakpoint")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                div()
+                    .px_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .rounded_sm()
+                    .child(self.address_editor.clone()),
+            )
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("add-instruction-breakpoint", "Add")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.confirm(&menu::Confirm, window, cx)
+                        })),
+                ),
+            )
+    }
+}
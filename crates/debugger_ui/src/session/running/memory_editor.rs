@@ -0,0 +1,421 @@
+use editor::{Editor, actions::SelectAll};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task};
+use project::debugger::session::{Session, SessionEvent};
+use std::collections::BTreeMap;
+use ui::prelude::*;
+
+/// How many bytes past the variable's memory reference the editor shows.
+const MEMORY_EDITOR_BYTE_COUNT: u64 = 256;
+const BYTES_PER_ROW: usize = 16;
+
+/// Parses whitespace-separated bytes in hex, e.g. `de ad 0xBE ef`.
+pub(crate) fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    text.split_whitespace()
+        .map(|byte| {
+            let byte = byte
+                .strip_prefix("0x")
+                .or_else(|| byte.strip_prefix("0X"))
+                .unwrap_or(byte);
+            u8::from_str_radix(byte, 16).ok()
+        })
+        .collect()
+}
+
+/// Groups edited bytes into runs of adjacent offsets, so each run is written with one request.
+pub(crate) fn pending_runs(pending: &BTreeMap<usize, u8>) -> Vec<(usize, Vec<u8>)> {
+    let mut runs: Vec<(usize, Vec<u8>)> = Vec::new();
+    for (&offset, &byte) in pending {
+        match runs.last_mut() {
+            Some((start, bytes)) if *start + bytes.len() == offset => bytes.push(byte),
+            _ => runs.push((offset, vec![byte])),
+        }
+    }
+    runs
+}
+
+/// A popover that shows the memory a variable refers to, and lets the user edit its bytes and
+/// write them back with `writeMemory`.
+pub(crate) struct MemoryEditor {
+    session: Entity<Session>,
+    name: SharedString,
+    memory_reference: String,
+    focus_handle: FocusHandle,
+    byte_editor: Entity<Editor>,
+    bytes: Vec<u8>,
+    selected_offset: usize,
+    /// Edited bytes that haven't been written yet, keyed by offset.
+    pending: BTreeMap<usize, u8>,
+    /// The bytes each edit replaced, as they were pending before it, so it can be undone.
+    undo_stack: Vec<Vec<(usize, Option<u8>)>>,
+    error: Option<SharedString>,
+    loading: bool,
+    _task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl MemoryEditor {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        name: SharedString,
+        memory_reference: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let byte_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bytes in hex, e.g. de ad be ef", cx);
+            editor
+        });
+        // By the time the debuggee stops again, it may have changed or freed the memory.
+        let _subscription = cx.subscribe(&session, |_, _, event, cx| {
+            if let SessionEvent::Stopped(_) = event {
+                cx.emit(DismissEvent);
+            }
+        });
+
+        let mut this = Self {
+            session,
+            name,
+            memory_reference,
+            focus_handle: cx.focus_handle(),
+            byte_editor,
+            bytes: Vec::new(),
+            selected_offset: 0,
+            pending: BTreeMap::new(),
+            undo_stack: Vec::new(),
+            error: None,
+            loading: false,
+            _task: Task::ready(()),
+            _subscription,
+        };
+        this.load(cx);
+        this
+    }
+
+    pub(crate) fn focus_byte_editor(&self, window: &mut Window, cx: &mut App) {
+        window.focus(&self.byte_editor.focus_handle(cx));
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let bytes = self.session.read(cx).read_memory(
+            self.memory_reference.clone(),
+            0,
+            MEMORY_EDITOR_BYTE_COUNT,
+        );
+        self.loading = true;
+        self._task = cx.spawn(async move |this, cx| {
+            let bytes = bytes.await;
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match bytes {
+                    Ok(bytes) => this.bytes = bytes,
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn byte_at(&self, offset: usize) -> Option<u8> {
+        self.pending
+            .get(&offset)
+            .or_else(|| self.bytes.get(offset))
+            .copied()
+    }
+
+    fn select_offset(&mut self, offset: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selected_offset = offset;
+        let text = self
+            .byte_at(offset)
+            .map(|byte| format!("{byte:02x}"))
+            .unwrap_or_default();
+        self.byte_editor.update(cx, |editor, cx| {
+            editor.set_text(text, window, cx);
+            editor.select_all(&SelectAll, window, cx);
+        });
+        self.focus_byte_editor(window, cx);
+        cx.notify();
+    }
+
+    /// Stages the bytes entered in the byte editor, starting at the selected offset.
+    fn edit_bytes(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        // Edits can't be made until the bytes they're compared against are read, or once a write
+        // that would drop them is in flight.
+        if self.loading {
+            return;
+        }
+        let text = self.byte_editor.read(cx).text(cx);
+        let Some(edited) = parse_hex_bytes(&text).filter(|bytes| !bytes.is_empty()) else {
+            self.error = Some("Enter bytes in hex, e.g. de ad be ef".into());
+            cx.notify();
+            return;
+        };
+        if self.selected_offset + edited.len() > self.bytes.len() {
+            self.error = Some("The edit runs past the end of the readable memory".into());
+            cx.notify();
+            return;
+        }
+
+        let mut replaced = Vec::with_capacity(edited.len());
+        for (offset, byte) in (self.selected_offset..).zip(edited) {
+            replaced.push((offset, self.pending.get(&offset).copied()));
+            if self.bytes[offset] == byte {
+                self.pending.remove(&offset);
+            } else {
+                self.pending.insert(offset, byte);
+            }
+        }
+        let next_offset = (self.selected_offset + replaced.len()).min(self.bytes.len() - 1);
+        self.undo_stack.push(replaced);
+        self.error = None;
+        self.select_offset(next_offset, window, cx);
+    }
+
+    fn undo_edit(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.loading {
+            return;
+        }
+        let Some(replaced) = self.undo_stack.pop() else {
+            return;
+        };
+        let first_offset = replaced.first().map(|(offset, _)| *offset);
+        for (offset, byte) in replaced.into_iter().rev() {
+            match byte {
+                Some(byte) => self.pending.insert(offset, byte),
+                None => self.pending.remove(&offset),
+            };
+        }
+        if let Some(offset) = first_offset {
+            self.select_offset(offset, window, cx);
+        }
+    }
+
+    fn discard_edits(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.loading {
+            return;
+        }
+        self.pending.clear();
+        self.undo_stack.clear();
+        self.select_offset(self.selected_offset, window, cx);
+    }
+
+    fn write_edits(&mut self, cx: &mut Context<Self>) {
+        let runs = pending_runs(&self.pending);
+        if runs.is_empty() || self.loading {
+            return;
+        }
+        let writes = self.session.update(cx, |session, cx| {
+            runs.into_iter()
+                .map(|(offset, bytes)| {
+                    session.write_memory(self.memory_reference.clone(), offset as i64, bytes, cx)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        self.loading = true;
+        self.error = None;
+        self._task = cx.spawn(async move |this, cx| {
+            let written = futures::future::try_join_all(writes).await;
+            this.update(cx, |this, cx| {
+                match written {
+                    Ok(_) => {
+                        this.pending.clear();
+                        this.undo_stack.clear();
+                        // Read the memory back, as the debuggee may not keep what was written.
+                        this.load(cx);
+                    }
+                    Err(error) => {
+                        this.loading = false;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.edit_bytes(window, cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    #[cfg(test)]
+    pub(crate) fn pending(&self) -> &BTreeMap<usize, u8> {
+        &self.pending
+    }
+
+    #[cfg(test)]
+    pub(crate) fn error(&self) -> Option<&SharedString> {
+        self.error.as_ref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn enter_bytes(
+        &mut self,
+        offset: usize,
+        text: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.select_offset(offset, window, cx);
+        self.byte_editor
+            .update(cx, |editor, cx| editor.set_text(text, window, cx));
+        self.edit_bytes(window, cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn undo(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.undo_edit(window, cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn write(&mut self, cx: &mut Context<Self>) {
+        self.write_edits(cx);
+    }
+
+    fn render_row(&self, row: usize, cx: &mut Context<Self>) -> impl IntoElement {
+        let start = row * BYTES_PER_ROW;
+        let end = (start + BYTES_PER_ROW).min(self.bytes.len());
+        h_flex()
+            .gap_1()
+            .child(
+                Label::new(format!("+{start:04x}"))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .children((start..end).map(|offset| {
+                let is_pending = self.pending.contains_key(&offset);
+                let byte = self.byte_at(offset).unwrap_or_default();
+                div()
+                    .id(("memory-byte", offset))
+                    .px_0p5()
+                    .rounded_xs()
+                    .border_1()
+                    .border_color(gpui::transparent_black())
+                    .cursor_pointer()
+                    .when(offset % 8 == 0 && offset % BYTES_PER_ROW != 0, |this| {
+                        this.ml_1()
+                    })
+                    .when(is_pending, |this| {
+                        this.bg(cx.theme().status().modified_background)
+                    })
+                    .when(offset == self.selected_offset, |this| {
+                        this.border_color(cx.theme().colors().border_focused)
+                    })
+                    .child(
+                        Label::new(format!("{byte:02x}"))
+                            .size(LabelSize::Small)
+                            .color(if is_pending {
+                                Color::Modified
+                            } else {
+                                Color::Default
+                            })
+                            .buffer_font(cx),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_offset(offset, window, cx)
+                    }))
+            }))
+    }
+}
+
+impl EventEmitter<DismissEvent> for MemoryEditor {}
+
+impl Focusable for MemoryEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let row_count = self.bytes.len().div_ceil(BYTES_PER_ROW);
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .key_context("MemoryEditor")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_mouse_down_out(cx.listener(|_, _, _, cx| cx.emit(DismissEvent)))
+            .elevation_2(cx)
+            .p_2()
+            .gap_2()
+            .child(
+                Label::new(format!("Edit Memory of {}", self.name))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(v_flex().children((0..row_count).map(|row| self.render_row(row, cx))))
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Label::new(format!("+{:04x}", self.selected_offset))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_1()
+                            .border_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .rounded_sm()
+                            .child(self.byte_editor.clone()),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .justify_end()
+                    .child(
+                        Button::new("undo-memory-edit", "Undo")
+                            .label_size(LabelSize::Small)
+                            .disabled(self.undo_stack.is_empty() || self.loading)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.undo_edit(window, cx)),
+                            ),
+                    )
+                    .child(
+                        Button::new("discard-memory-edits", "Discard")
+                            .label_size(LabelSize::Small)
+                            .disabled(self.pending.is_empty() || self.loading)
+                            .on_click(
+                                cx.listener(|this, _, window, cx| this.discard_edits(window, cx)),
+                            ),
+                    )
+                    .child(
+                        Button::new("write-memory", "Write")
+                            .label_size(LabelSize::Small)
+                            .disabled(self.pending.is_empty() || self.loading)
+                            .on_click(cx.listener(|this, _, _, cx| this.write_edits(cx))),
+                    ),
+            )
+            .when(self.loading, |this| {
+                this.child(
+                    Label::new("Loading…")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+            })
+            .children(
+                self.error
+                    .clone()
+                    .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+            )
+    }
+}
@@ -0,0 +1,269 @@
+use super::variable_list::evaluated_variable;
+use collections::HashSet;
+use dap::{EvaluateArgumentsContext, StackFrameId, VariableReference};
+use editor::Editor;
+use gpui::{
+    AnyElement, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Subscription, Task,
+    uniform_list,
+};
+use project::debugger::session::Session;
+use settings::Settings as _;
+use std::ops::Range;
+use theme::ThemeSettings;
+use ui::{ListItem, prelude::*};
+use workspace::ModalView;
+
+/// A row of the result tree. `path` holds the names leading from the result to the variable.
+#[derive(Clone)]
+struct QuickWatchEntry {
+    path: Vec<SharedString>,
+    variable: dap::Variable,
+}
+
+/// A modal for evaluating a one-off expression in the selected stack frame, without echoing it
+/// to the console or adding a watch for it.
+pub(crate) struct QuickWatchModal {
+    session: Entity<Session>,
+    stack_frame_id: Option<StackFrameId>,
+    expression_editor: Entity<Editor>,
+    result: Option<dap::Variable>,
+    expanded: HashSet<Vec<SharedString>>,
+    _evaluate_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl QuickWatchModal {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_id: Option<StackFrameId>,
+        expression: Option<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let expression_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Expression to evaluate", cx);
+            if let Some(expression) = expression.as_ref() {
+                editor.set_text(expression.as_str(), window, cx);
+                editor.select_all(&editor::actions::SelectAll, window, cx);
+            }
+            editor
+        });
+        // Children are fetched lazily, so the tree fills in as the session loads them.
+        let _subscription = cx.observe(&session, |_, _, cx| cx.notify());
+
+        let mut this = Self {
+            session,
+            stack_frame_id,
+            expression_editor,
+            result: None,
+            expanded: HashSet::default(),
+            _evaluate_task: Task::ready(()),
+            _subscription,
+        };
+        if expression.is_some() {
+            this.evaluate(cx);
+        }
+        this
+    }
+
+    fn evaluate(&mut self, cx: &mut Context<Self>) {
+        let expression = self.expression_editor.read(cx).text(cx).trim().to_string();
+        if expression.is_empty() {
+            return;
+        }
+        let evaluation = self.session.read(cx).evaluate_silently(
+            expression.clone(),
+            Some(EvaluateArgumentsContext::Watch),
+            self.stack_frame_id,
+        );
+        self._evaluate_task = cx.spawn(async move |this, cx| {
+            let response = evaluation.await;
+            this.update(cx, |this, cx| {
+                this.result = Some(evaluated_variable(expression, response));
+                this.expanded.clear();
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn toggle(&mut self, path: Vec<SharedString>, cx: &mut Context<Self>) {
+        if !self.expanded.remove(&path) {
+            self.expanded.insert(path);
+        }
+        cx.notify();
+    }
+
+    fn entries(&self, cx: &mut Context<Self>) -> Vec<QuickWatchEntry> {
+        let Some(result) = self.result.clone() else {
+            return Vec::new();
+        };
+        let mut entries = Vec::new();
+        let mut stack = vec![QuickWatchEntry {
+            path: vec![result.name.clone().into()],
+            variable: result,
+        }];
+        while let Some(entry) = stack.pop() {
+            let variables_reference: VariableReference = entry.variable.variables_reference;
+            if variables_reference != 0 && self.expanded.contains(&entry.path) {
+                let children = self
+                    .session
+                    .update(cx, |session, cx| session.variables(variables_reference, cx));
+                stack.extend(children.into_iter().rev().map(|variable| {
+                    let mut path = entry.path.clone();
+                    path.push(variable.name.clone().into());
+                    QuickWatchEntry { path, variable }
+                }));
+            }
+            entries.push(entry);
+        }
+        entries
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_expression(
+        &mut self,
+        expression: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.expression_editor
+            .update(cx, |editor, cx| editor.set_text(expression, window, cx));
+        self.evaluate(cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn toggle_path(&mut self, path: &[&str], cx: &mut Context<Self>) {
+        self.toggle(
+            path.iter().map(|name| name.to_string().into()).collect(),
+            cx,
+        );
+    }
+
+    /// Formats the result tree like `assert_visual_entries` does for the variable list.
+    #[cfg(test)]
+    pub(crate) fn visual_entries(&self, cx: &mut Context<Self>) -> Vec<String> {
+        self.entries(cx)
+            .into_iter()
+            .map(|entry| {
+                let marker = if entry.variable.variables_reference == 0 {
+                    ' '
+                } else if self.expanded.contains(&entry.path) {
+                    'v'
+                } else {
+                    '>'
+                };
+                format!(
+                    "{}{marker} {} = {}",
+                    "    ".repeat(entry.path.len() - 1),
+                    entry.variable.name,
+                    entry.variable.value
+                )
+            })
+            .collect()
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        self.evaluate(cx);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_entry(
+        &self,
+        ix: usize,
+        entry: QuickWatchEntry,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let is_expandable = entry.variable.variables_reference != 0;
+        let is_expanded = self.expanded.contains(&entry.path);
+        let path = entry.path.clone();
+
+        ListItem::new(ix)
+            .indent_level(entry.path.len() - 1)
+            .indent_step_size(px(10.))
+            .selectable(false)
+            .always_show_disclosure_icon(true)
+            .when(is_expandable, |list_item| {
+                list_item
+                    .toggle(is_expanded)
+                    .on_toggle(cx.listener(move |this, _, _, cx| this.toggle(path.clone(), cx)))
+            })
+            .child(
+                h_flex()
+                    .gap_1()
+                    .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+                    .child(Label::new(entry.variable.name).size(LabelSize::Small))
+                    .child(Label::new("=").size(LabelSize::Small).color(Color::Muted))
+                    .child(
+                        Label::new(entry.variable.value)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .single_line(),
+                    ),
+            )
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<DismissEvent> for QuickWatchModal {}
+
+impl Focusable for QuickWatchModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.expression_editor.focus_handle(cx)
+    }
+}
+
+impl ModalView for QuickWatchModal {}
+
+impl Render for QuickWatchModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let entries = self.entries(cx);
+
+        v_flex()
+            .key_context("QuickWatchModal")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(34.))
+            .p_2()
+            .gap_2()
+            .child(
+                Label::new("Quick Watch")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                div()
+                    .px_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .rounded_sm()
+                    .child(self.expression_editor.clone()),
+            )
+            .when(self.stack_frame_id.is_none(), |this| {
+                this.child(
+                    Label::new("No stack frame is selected; evaluating in the global scope")
+                        .size(LabelSize::Small)
+                        .color(Color::Warning),
+                )
+            })
+            .when(!entries.is_empty(), |this| {
+                this.child(
+                    uniform_list(
+                        "quick-watch-result",
+                        entries.len(),
+                        cx.processor(move |this, range: Range<usize>, _window, cx| {
+                            range
+                                .map(|ix| this.render_entry(ix, entries[ix].clone(), cx))
+                                .collect()
+                        }),
+                    )
+                    .h(rems(16.)),
+                )
+            })
+    }
+}
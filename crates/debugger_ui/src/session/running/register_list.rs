@@ -0,0 +1,321 @@
+use super::{
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+    variable_list::NumericFormat,
+};
+use collections::HashMap;
+use dap::{ScopePresentationHint, StackFrameId};
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, Stateful, Subscription, Task,
+    UniformListScrollHandle, uniform_list,
+};
+use project::debugger::session::{Session, SessionEvent};
+use settings::Settings as _;
+use std::ops::Range;
+use theme::ThemeSettings;
+use ui::{Scrollbar, ScrollbarState, prelude::*};
+
+#[derive(Debug, Clone, PartialEq)]
+enum RegisterEntry {
+    Group(SharedString),
+    Register {
+        /// Group and register name, used to compare values across stops.
+        key: SharedString,
+        name: SharedString,
+        value: String,
+        changed: bool,
+    },
+}
+
+pub(crate) struct RegisterList {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    selected_stack_frame_id: Option<StackFrameId>,
+    entries: Vec<RegisterEntry>,
+    previous_values: HashMap<SharedString, String>,
+    _rebuild_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl RegisterList {
+    pub(crate) fn new(
+        session: Entity<Session>,
+        stack_frame_list: Entity<StackFrameList>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
+                    this.selected_stack_frame_id = Some(*stack_frame_id);
+                    if this._rebuild_task.is_some() {
+                        this.schedule_rebuild(cx);
+                    }
+                }
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+            cx.subscribe(&session, |this, _, event, cx| match event {
+                SessionEvent::Stopped(_) => {
+                    this.previous_values = this
+                        .entries
+                        .iter()
+                        .filter_map(|entry| match entry {
+                            RegisterEntry::Register { key, value, .. } => {
+                                Some((key.clone(), value.clone()))
+                            }
+                            RegisterEntry::Group(_) => None,
+                        })
+                        .collect();
+                    this.selected_stack_frame_id.take();
+                }
+                SessionEvent::Variables => {
+                    if this._rebuild_task.is_some() {
+                        this.schedule_rebuild(cx);
+                    }
+                }
+                _ => {}
+            }),
+        ];
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            focus_handle: cx.focus_handle(),
+            selected_stack_frame_id: None,
+            entries: Vec::new(),
+            previous_values: HashMap::default(),
+            _rebuild_task: None,
+            _subscriptions,
+        }
+    }
+
+    fn schedule_rebuild(&mut self, cx: &mut Context<Self>) {
+        self._rebuild_task = Some(cx.spawn(async move |this, cx| {
+            this.update(cx, |this, cx| this.build_entries(cx)).ok();
+        }));
+    }
+
+    fn build_entries(&mut self, cx: &mut Context<Self>) {
+        let Some(stack_frame_id) = self.selected_stack_frame_id else {
+            return;
+        };
+
+        let mut entries = self.session.update(cx, |session, cx| {
+            let Some(registers) = session
+                .scopes(stack_frame_id, cx)
+                .iter()
+                .find(|scope| is_registers_scope(scope))
+                .cloned()
+            else {
+                return Vec::new();
+            };
+
+            // Adapters either list registers directly or nest them under one variable per
+            // register class, e.g. "General Purpose Registers".
+            let (ungrouped, groups): (Vec<_>, Vec<_>) = session
+                .variables(registers.variables_reference, cx)
+                .into_iter()
+                .partition(|variable| variable.variables_reference == 0);
+
+            let mut entries = ungrouped
+                .into_iter()
+                .map(|register| RegisterEntry::register(None, register))
+                .collect::<Vec<_>>();
+            for group in groups {
+                let group_name = SharedString::from(group.name);
+                entries.push(RegisterEntry::Group(group_name.clone()));
+                entries.extend(
+                    session
+                        .variables(group.variables_reference, cx)
+                        .into_iter()
+                        .map(|register| RegisterEntry::register(Some(&group_name), register)),
+                );
+            }
+            entries
+        });
+
+        for entry in &mut entries {
+            if let RegisterEntry::Register {
+                key,
+                value,
+                changed,
+                ..
+            } = entry
+            {
+                *changed = self
+                    .previous_values
+                    .get(key)
+                    .is_some_and(|previous_value| previous_value != value);
+            }
+        }
+
+        self.entries = entries;
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn changed_registers(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                RegisterEntry::Register {
+                    name,
+                    changed: true,
+                    ..
+                } => Some(name.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn visual_entries(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .map(|entry| match entry {
+                RegisterEntry::Group(name) => format!("{name}:"),
+                RegisterEntry::Register { name, value, .. } => format!("    {name} = {value}"),
+            })
+            .collect()
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            RegisterEntry::Group(name) => h_flex()
+                .id(("register-group", ix))
+                .px_1()
+                .pt_1()
+                .child(
+                    Label::new(name.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element(),
+            RegisterEntry::Register {
+                name,
+                value,
+                changed,
+                ..
+            } => h_flex()
+                .id(("register", ix))
+                .px_1()
+                .pl_3()
+                .gap_2()
+                .hover(|style| style.bg(cx.theme().colors().element_hover))
+                .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+                .child(Label::new(name.clone()).size(LabelSize::Small))
+                .child(
+                    Label::new(value.clone())
+                        .size(LabelSize::Small)
+                        .color(if *changed {
+                            Color::Modified
+                        } else {
+                            Color::Muted
+                        }),
+                )
+                .into_any_element(),
+        }
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("register-list-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+impl RegisterEntry {
+    fn register(group: Option<&SharedString>, register: dap::Variable) -> Self {
+        let key = match group {
+            Some(group) => format!("{group}/{}", register.name),
+            None => register.name.clone(),
+        };
+        Self::Register {
+            key: key.into(),
+            value: NumericFormat::Hex
+                .format(&register.value)
+                .unwrap_or(register.value),
+            name: register.name.into(),
+            changed: false,
+        }
+    }
+}
+
+fn is_registers_scope(scope: &dap::Scope) -> bool {
+    scope
+        .presentation_hint
+        .as_ref()
+        .map(|hint| *hint == ScopePresentationHint::Registers)
+        .unwrap_or(scope.name.to_lowercase().contains("register"))
+}
+
+impl Focusable for RegisterList {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RegisterList {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self._rebuild_task.is_none() {
+            self.schedule_rebuild(cx);
+        }
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(
+                        Label::new("The debug adapter reports no registers for this frame")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            "register-list",
+                            self.entries.len(),
+                            cx.processor(|this, range: Range<usize>, _window, cx| {
+                                range.map(|ix| this.render_entry(ix, cx)).collect()
+                            }),
+                        )
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full(),
+                    )
+                }
+            })
+            .child(self.render_vertical_scrollbar(cx))
+    }
+}
@@ -0,0 +1,153 @@
+use dap::debugger_settings::{DebuggerSettings, SourcePathMapping};
+use editor::Editor;
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable};
+use project::Fs;
+use std::{path::Path, sync::Arc};
+use ui::prelude::*;
+use workspace::ModalView;
+
+/// A modal for mapping the source path of a stack frame that doesn't exist locally onto a local
+/// directory. The mapping is saved to the `source_path_mappings` setting, where it can be edited
+/// later.
+pub(crate) struct SourcePathMappingModal {
+    fs: Arc<dyn Fs>,
+    missing_path: Arc<Path>,
+    remote_editor: Entity<Editor>,
+    local_editor: Entity<Editor>,
+}
+
+impl SourcePathMappingModal {
+    pub(crate) fn new(
+        fs: Arc<dyn Fs>,
+        missing_path: Arc<Path>,
+        local_root: Option<Arc<Path>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = |placeholder: &str,
+                      text: Option<&Path>,
+                      window: &mut Window,
+                      cx: &mut Context<Editor>| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text(placeholder, cx);
+            if let Some(text) = text {
+                editor.set_text(text.to_string_lossy(), window, cx);
+            }
+            editor
+        };
+        let remote_editor = cx.new(|cx| {
+            editor(
+                "Path prefix reported by the debug adapter",
+                missing_path.parent(),
+                window,
+                cx,
+            )
+        });
+        let local_editor =
+            cx.new(|cx| editor("Local path prefix", local_root.as_deref(), window, cx));
+
+        Self {
+            fs,
+            missing_path,
+            remote_editor,
+            local_editor,
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let remote = self.remote_editor.read(cx).text(cx).trim().to_string();
+        let local = self.local_editor.read(cx).text(cx).trim().to_string();
+        if remote.is_empty() || local.is_empty() {
+            return;
+        }
+
+        settings::update_settings_file::<DebuggerSettings>(self.fs.clone(), cx, |settings, _| {
+            settings
+                .source_path_mappings
+                .retain(|mapping| mapping.remote != remote);
+            settings
+                .source_path_mappings
+                .push(SourcePathMapping { remote, local });
+        });
+        cx.emit(DismissEvent);
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_prefixes(
+        &mut self,
+        remote: &str,
+        local: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.remote_editor
+            .update(cx, |editor, cx| editor.set_text(remote, window, cx));
+        self.local_editor
+            .update(cx, |editor, cx| editor.set_text(local, window, cx));
+    }
+}
+
+impl EventEmitter<DismissEvent> for SourcePathMappingModal {}
+
+impl Focusable for SourcePathMappingModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.local_editor.focus_handle(cx)
+    }
+}
+
+impl ModalView for SourcePathMappingModal {}
+
+impl Render for SourcePathMappingModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let field = |label: &'static str, editor: &Entity<Editor>| {
+            v_flex()
+                .gap_1()
+                .child(Label::new(label).size(LabelSize::Small))
+                .child(
+                    div()
+                        .px_1()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .rounded_sm()
+                        .child(editor.clone()),
+                )
+        };
+
+        v_flex()
+            .key_context("SourcePathMappingModal")
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(34.))
+            .p_2()
+            .gap_2()
+            .child(
+                Label::new("Map Source Path")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                Label::new(format!(
+                    "{} doesn't exist locally",
+                    self.missing_path.display()
+                ))
+                .size(LabelSize::Small)
+                .color(Color::Warning),
+            )
+            .child(field("Replace", &self.remote_editor))
+            .child(field("With", &self.local_editor))
+            .child(
+                h_flex().justify_end().child(
+                    Button::new("map-source-path", "Map")
+                        .label_size(LabelSize::Small)
+                        .on_click(cx.listener(|this, _, window, cx| {
+                            this.confirm(&menu::Confirm, window, cx)
+                        })),
+                ),
+            )
+    }
+}
@@ -1,24 +1,31 @@
+use std::ops::Range;
 use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context as _, Result, anyhow};
+use collections::{HashMap, HashSet};
 use dap::StackFrameId;
+use dap::debugger_settings::DebuggerSettings;
+use editor::{Editor, EditorEvent, MultiBuffer, scroll::Autoscroll};
 use gpui::{
     AnyElement, Entity, EventEmitter, FocusHandle, Focusable, FontWeight, ListState, MouseButton,
     Stateful, Subscription, Task, WeakEntity, list,
 };
-use util::debug_panic;
+use settings::{Settings as _, SettingsStore};
+use util::{ResultExt as _, debug_panic, paths::PathMatcher};
 
 use crate::StackTraceView;
-use language::PointUtf16;
+use language::{Buffer, Capability, Point, PointUtf16};
 use project::debugger::breakpoint_store::ActiveStackFrame;
-use project::debugger::session::{Session, SessionEvent, StackFrame};
+use project::debugger::session::{Session, SessionEvent, StackFrame, ThreadId};
 use project::{ProjectItem, ProjectPath};
-use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use ui::{Divider, Scrollbar, ScrollbarState, Tooltip, prelude::*};
 use workspace::{ItemHandle, Workspace};
 
 use super::RunningState;
+use super::exception_popover::{exception_detail_lines, exception_summary};
+use super::source_path_mapping::SourcePathMappingModal;
 
 #[derive(Debug)]
 pub enum StackFrameListEvent {
@@ -28,7 +35,7 @@ pub enum StackFrameListEvent {
 
 pub struct StackFrameList {
     focus_handle: FocusHandle,
-    _subscription: Subscription,
+    _subscriptions: Vec<Subscription>,
     session: Entity<Session>,
     state: WeakEntity<RunningState>,
     entries: Vec<StackFrameEntry>,
@@ -38,6 +45,22 @@ pub struct StackFrameList {
     scrollbar_state: ScrollbarState,
     list_state: ListState,
     error: Option<SharedString>,
+    /// The settings the entries were last built with.
+    collapsing_settings: CollapsingSettings,
+    /// The source path of the opened stack frame, when it doesn't exist locally.
+    unresolved_source_path: Option<Arc<Path>>,
+    load_more_task: Option<Task<()>>,
+    filter_editor: Entity<Editor>,
+    /// Whether every thread is listed with its frames, instead of only the selected thread.
+    show_all_threads: bool,
+    /// Threads whose expansion the user toggled, relative to only the selected thread being expanded.
+    toggled_threads: HashSet<ThreadId>,
+    /// The threads of listed frames that don't belong to the selected thread.
+    frame_threads: HashMap<StackFrameId, ThreadId>,
+    /// A frame of another thread to open once that thread has been selected.
+    pending_stack_frame_id: Option<StackFrameId>,
+    /// Read-only buffers holding sources fetched from the adapter, by their `sourceReference`.
+    source_reference_buffers: HashMap<u64, Entity<Buffer>>,
     _refresh_task: Task<()>,
 }
 
@@ -47,6 +70,33 @@ pub enum StackFrameEntry {
     /// Used to indicate that the frame is artificial and is a visual label or separator
     Label(dap::StackFrame),
     Collapsed(Vec<dap::StackFrame>),
+    /// The adapter only returned part of the stack, e.g. for long async stack traces.
+    LoadMore,
+    /// The header of a thread's frames when all threads are shown.
+    Thread {
+        thread_id: ThreadId,
+        name: String,
+        is_expanded: bool,
+    },
+}
+
+/// The settings that decide which frames are collapsed.
+#[derive(PartialEq)]
+struct CollapsingSettings {
+    collapse_subtle_frames: bool,
+    hide_library_frames: bool,
+    library_frame_paths: Vec<String>,
+}
+
+impl CollapsingSettings {
+    fn get(cx: &App) -> Self {
+        let settings = DebuggerSettings::get_global(cx);
+        Self {
+            collapse_subtle_frames: settings.collapse_subtle_frames,
+            hide_library_frames: settings.hide_library_frames,
+            library_frame_paths: settings.library_frame_paths.clone(),
+        }
+    }
 }
 
 impl StackFrameList {
@@ -59,7 +109,18 @@ impl StackFrameList {
     ) -> Self {
         let focus_handle = cx.focus_handle();
 
-        let _subscription =
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter frames", cx);
+            editor
+        });
+
+        let _subscriptions = vec![
+            cx.subscribe_in(&filter_editor, window, |this, _, event, window, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.build_entries(false, window, cx);
+                }
+            }),
             cx.subscribe_in(&session, window, |this, _, event, window, cx| match event {
                 SessionEvent::Threads => {
                     this.schedule_refresh(false, window, cx);
@@ -67,8 +128,24 @@ impl StackFrameList {
                 SessionEvent::Stopped(..) | SessionEvent::StackTrace => {
                     this.schedule_refresh(true, window, cx);
                 }
+                SessionEvent::ExceptionInfo => cx.notify(),
                 _ => {}
-            });
+            }),
+            cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+                let collapsing_settings = CollapsingSettings::get(cx);
+                if collapsing_settings != this.collapsing_settings {
+                    this.schedule_refresh(false, window, cx);
+                }
+                // Reopen the frame in case a source path mapping now resolves it.
+                if this.unresolved_source_path.is_none() {
+                    return;
+                }
+                if let Some(stack_frame_id) = this.opened_stack_frame_id {
+                    this.go_to_stack_frame(stack_frame_id, window, cx)
+                        .detach_and_log_err(cx);
+                }
+            }),
+        ];
 
         let list_state = ListState::new(0, gpui::ListAlignment::Top, px(1000.), {
             let this = cx.weak_entity();
@@ -84,9 +161,18 @@ impl StackFrameList {
             workspace,
             focus_handle,
             state,
-            _subscription,
+            _subscriptions,
             entries: Default::default(),
             error: None,
+            collapsing_settings: CollapsingSettings::get(cx),
+            unresolved_source_path: None,
+            load_more_task: None,
+            filter_editor,
+            show_all_threads: false,
+            toggled_threads: HashSet::default(),
+            frame_threads: HashMap::default(),
+            pending_stack_frame_id: None,
+            source_reference_buffers: HashMap::default(),
             selected_ix: None,
             opened_stack_frame_id: None,
             list_state,
@@ -118,8 +204,15 @@ impl StackFrameList {
             .collect::<Vec<_>>()
     }
 
+    fn thread_id(&self, cx: &App) -> Option<ThreadId> {
+        self.state
+            .read_with(cx, |state, _| state.thread_id)
+            .ok()
+            .flatten()
+    }
+
     fn stack_frames(&self, cx: &mut App) -> Result<Vec<StackFrame>> {
-        if let Ok(Some(thread_id)) = self.state.read_with(cx, |state, _| state.thread_id) {
+        if let Some(thread_id) = self.thread_id(cx) {
             self.session
                 .update(cx, |this, cx| this.stack_frames(thread_id, cx))
         } else {
@@ -136,6 +229,41 @@ impl StackFrameList {
             .collect()
     }
 
+    /// The absolute path of the source of the opened stack frame, if it has one.
+    pub(crate) fn opened_stack_frame_abs_path(&self, cx: &App) -> Option<Arc<Path>> {
+        let stack_frame_id = self.opened_stack_frame_id?;
+        self.flatten_entries(true, true)
+            .iter()
+            .find(|stack_frame| stack_frame.id == stack_frame_id)
+            .and_then(|stack_frame| self.abs_path_from_stack_frame(stack_frame, cx))
+    }
+
+    /// A frame whose source is `abs_path` and whose line is one of `rows`, e.g. the function the
+    /// cursor is in, preferring the opened frame and then the innermost one.
+    pub(crate) fn frame_in_rows(
+        &self,
+        abs_path: &Path,
+        rows: Range<u32>,
+        cx: &App,
+    ) -> Option<StackFrameId> {
+        let frames = self
+            .flatten_entries(true, true)
+            .into_iter()
+            .filter(|stack_frame| {
+                rows.contains(&(stack_frame.line.saturating_sub(1) as u32))
+                    && self
+                        .abs_path_from_stack_frame(stack_frame, cx)
+                        .is_some_and(|path| path.as_ref() == abs_path)
+            })
+            .map(|stack_frame| stack_frame.id)
+            .collect::<Vec<_>>();
+        frames
+            .iter()
+            .find(|stack_frame_id| Some(**stack_frame_id) == self.opened_stack_frame_id)
+            .or(frames.first())
+            .copied()
+    }
+
     pub fn opened_stack_frame_id(&self) -> Option<StackFrameId> {
         self.opened_stack_frame_id
     }
@@ -179,13 +307,26 @@ impl StackFrameList {
             .and_then(|ix| self.entries.get(ix))
             .and_then(|entry| match entry {
                 StackFrameEntry::Normal(stack_frame) => Some(stack_frame.id),
-                StackFrameEntry::Collapsed(_) | StackFrameEntry::Label(_) => None,
+                StackFrameEntry::Collapsed(_)
+                | StackFrameEntry::Label(_)
+                | StackFrameEntry::LoadMore
+                | StackFrameEntry::Thread { .. } => None,
             });
         let mut entries = Vec::new();
         let mut collapsed_entries = Vec::new();
         let mut first_stack_frame = None;
         let mut first_stack_frame_with_path = None;
 
+        self.collapsing_settings = CollapsingSettings::get(cx);
+        let collapse_subtle_frames = self.collapsing_settings.collapse_subtle_frames;
+        let library_frame_paths = self
+            .collapsing_settings
+            .hide_library_frames
+            .then(|| PathMatcher::new(&self.collapsing_settings.library_frame_paths).log_err())
+            .flatten();
+
+        let filter = self.filter_editor.read(cx).text(cx).to_lowercase();
+
         let stack_frames = match self.stack_frames(cx) {
             Ok(stack_frames) => stack_frames,
             Err(e) => {
@@ -199,9 +340,29 @@ impl StackFrameList {
             }
         };
         for stack_frame in &stack_frames {
+            // Collapsing and label frames would only get in the way of the frames being searched for.
+            if !filter.is_empty() {
+                if stack_frame.dap.presentation_hint != Some(dap::StackFramePresentationHint::Label)
+                    && Self::matches_filter(&stack_frame.dap, &filter)
+                {
+                    entries.push(StackFrameEntry::Normal(stack_frame.dap.clone()));
+                }
+                continue;
+            }
             match stack_frame.dap.presentation_hint {
-                Some(dap::StackFramePresentationHint::Deemphasize)
-                | Some(dap::StackFramePresentationHint::Subtle) => {
+                Some(dap::StackFramePresentationHint::Deemphasize) => {
+                    collapsed_entries.push(stack_frame.dap.clone());
+                }
+                Some(dap::StackFramePresentationHint::Subtle) if collapse_subtle_frames => {
+                    collapsed_entries.push(stack_frame.dap.clone());
+                }
+                Some(dap::StackFramePresentationHint::Normal) | None
+                    if library_frame_paths
+                        .as_ref()
+                        .is_some_and(|library_frame_paths| {
+                            Self::is_library_frame(&stack_frame.dap, library_frame_paths)
+                        }) =>
+                {
                     collapsed_entries.push(stack_frame.dap.clone());
                 }
                 Some(dap::StackFramePresentationHint::Label) => {
@@ -213,15 +374,19 @@ impl StackFrameList {
                         entries.push(StackFrameEntry::Collapsed(collapsed_entries.clone()));
                     }
 
-                    first_stack_frame.get_or_insert(entries.len());
+                    let is_subtle = stack_frame.dap.presentation_hint
+                        == Some(dap::StackFramePresentationHint::Subtle);
+                    if !is_subtle {
+                        first_stack_frame.get_or_insert(entries.len());
 
-                    if stack_frame
-                        .dap
-                        .source
-                        .as_ref()
-                        .is_some_and(|source| source.path.is_some())
-                    {
-                        first_stack_frame_with_path.get_or_insert(entries.len());
+                        if stack_frame
+                            .dap
+                            .source
+                            .as_ref()
+                            .is_some_and(|source| source.path.is_some())
+                        {
+                            first_stack_frame_with_path.get_or_insert(entries.len());
+                        }
                     }
                     entries.push(StackFrameEntry::Normal(stack_frame.dap.clone()));
                 }
@@ -232,20 +397,44 @@ impl StackFrameList {
         if !collapsed_entries.is_empty() {
             entries.push(StackFrameEntry::Collapsed(collapsed_entries.clone()));
         }
+        if self
+            .thread_id(cx)
+            .is_some_and(|thread_id| self.session.read(cx).has_more_stack_frames(thread_id))
+        {
+            entries.push(StackFrameEntry::LoadMore);
+        }
+
+        self.frame_threads.clear();
+        if self.show_all_threads {
+            let offset;
+            (entries, offset) = self.build_all_threads_entries(entries, &filter, cx);
+            first_stack_frame = first_stack_frame.map(|ix| ix + offset);
+            first_stack_frame_with_path = first_stack_frame_with_path.map(|ix| ix + offset);
+        }
         self.entries = entries;
 
-        if let Some(ix) = first_stack_frame_with_path
+        let frame_ix = |entries: &[StackFrameEntry], stack_frame_id| {
+            entries.iter().position(|entry| match entry {
+                StackFrameEntry::Normal(frame) => frame.id == stack_frame_id,
+                _ => false,
+            })
+        };
+        if let Some(ix) = self
+            .pending_stack_frame_id
+            .take()
+            .and_then(|stack_frame_id| frame_ix(&self.entries, stack_frame_id))
+            .filter(|_| open_first_stack_frame)
+        {
+            self.select_ix(Some(ix), cx);
+            self.activate_selected_entry(window, cx);
+        } else if let Some(ix) = first_stack_frame_with_path
             .or(first_stack_frame)
             .filter(|_| open_first_stack_frame)
         {
             self.select_ix(Some(ix), cx);
             self.activate_selected_entry(window, cx);
         } else if let Some(old_selected_frame_id) = old_selected_frame_id {
-            let ix = self.entries.iter().position(|entry| match entry {
-                StackFrameEntry::Normal(frame) => frame.id == old_selected_frame_id,
-                StackFrameEntry::Collapsed(_) | StackFrameEntry::Label(_) => false,
-            });
-            self.selected_ix = ix;
+            self.selected_ix = frame_ix(&self.entries, old_selected_frame_id);
         }
 
         self.list_state.reset(self.entries.len());
@@ -253,6 +442,116 @@ impl StackFrameList {
         cx.notify();
     }
 
+    /// Lists every thread of the session under a header, with the selected thread's
+    /// `selected_thread_entries` and the frames of the other expanded threads. Returns the entries
+    /// along with the index the selected thread's entries start at.
+    fn build_all_threads_entries(
+        &mut self,
+        mut selected_thread_entries: Vec<StackFrameEntry>,
+        filter: &str,
+        cx: &mut Context<Self>,
+    ) -> (Vec<StackFrameEntry>, usize) {
+        let selected_thread_id = self.thread_id(cx);
+        let threads = self.session.update(cx, |session, cx| session.threads(cx));
+
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        for (thread, _) in threads {
+            let thread_id = ThreadId(thread.id);
+            let is_selected = Some(thread_id) == selected_thread_id;
+            let is_expanded = is_selected != self.toggled_threads.contains(&thread_id);
+            entries.push(StackFrameEntry::Thread {
+                thread_id,
+                name: thread.name,
+                is_expanded,
+            });
+            if !is_expanded {
+                continue;
+            }
+            if is_selected {
+                offset = entries.len();
+                entries.append(&mut selected_thread_entries);
+                continue;
+            }
+
+            let stack_frames = self
+                .session
+                .update(cx, |session, cx| session.stack_frames(thread_id, cx))
+                .unwrap_or_default();
+            for stack_frame in stack_frames {
+                if stack_frame.dap.presentation_hint == Some(dap::StackFramePresentationHint::Label)
+                    || (!filter.is_empty() && !Self::matches_filter(&stack_frame.dap, filter))
+                {
+                    continue;
+                }
+                self.frame_threads.insert(stack_frame.dap.id, thread_id);
+                entries.push(StackFrameEntry::Normal(stack_frame.dap));
+            }
+        }
+        (entries, offset)
+    }
+
+    pub(crate) fn toggle_all_threads(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_all_threads = !self.show_all_threads;
+        self.build_entries(false, window, cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn activate_entry(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_ix = Some(ix);
+        self.activate_selected_entry(window, cx);
+    }
+
+    fn toggle_thread_expansion(
+        &mut self,
+        thread_id: ThreadId,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.toggled_threads.remove(&thread_id) {
+            self.toggled_threads.insert(thread_id);
+        }
+        self.build_entries(false, window, cx);
+    }
+
+    /// Whether the frame's function name or source path contains the lowercased `filter`.
+    fn matches_filter(stack_frame: &dap::StackFrame, filter: &str) -> bool {
+        stack_frame.name.to_lowercase().contains(filter)
+            || stack_frame
+                .source
+                .as_ref()
+                .and_then(|source| source.path.as_ref().or(source.name.as_ref()))
+                .is_some_and(|path| path.to_lowercase().contains(filter))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_filter(&mut self, filter: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.filter_editor.update(cx, |editor, cx| {
+            editor.set_text(filter, window, cx);
+        });
+    }
+
+    /// Whether the frame belongs to a dependency or the standard library, going by the hints of
+    /// its source and the `library_frame_paths` setting.
+    fn is_library_frame(stack_frame: &dap::StackFrame, library_frame_paths: &PathMatcher) -> bool {
+        let Some(source) = stack_frame.source.as_ref() else {
+            return false;
+        };
+        if source.presentation_hint == Some(dap::SourcePresentationHint::Deemphasize) {
+            return true;
+        }
+        match source.path.as_deref() {
+            Some(path) => library_frame_paths.is_match(Path::new(path)),
+            // Sources that only the adapter can provide, e.g. Node's `node:internal` modules.
+            None => source.origin.is_some(),
+        }
+    }
+
     pub fn go_to_stack_frame(
         &mut self,
         stack_frame_id: StackFrameId,
@@ -266,6 +565,7 @@ impl StackFrameList {
                 StackFrameEntry::Label(stack_frame) => std::slice::from_ref(stack_frame),
                 StackFrameEntry::Normal(stack_frame) => std::slice::from_ref(stack_frame),
                 StackFrameEntry::Collapsed(stack_frames) => stack_frames.as_slice(),
+                StackFrameEntry::LoadMore | StackFrameEntry::Thread { .. } => Default::default(),
             })
             .find(|stack_frame| stack_frame.id == stack_frame_id)
             .cloned()
@@ -283,42 +583,69 @@ impl StackFrameList {
     ) -> Task<Result<()>> {
         let stack_frame_id = stack_frame.id;
         self.opened_stack_frame_id = Some(stack_frame_id);
-        let Some(abs_path) = Self::abs_path_from_stack_frame(&stack_frame) else {
+        let row = stack_frame.line.saturating_sub(1) as u32;
+        let Some(abs_path) = self.abs_path_from_stack_frame(&stack_frame, cx) else {
+            if let Some(source) = stack_frame.source.filter(|source| {
+                source
+                    .source_reference
+                    .is_some_and(|reference| reference > 0)
+            }) {
+                cx.emit(StackFrameListEvent::SelectedStackFrameChanged(
+                    stack_frame_id,
+                ));
+                return self.go_to_source_reference(source, row, window, cx);
+            }
             return Task::ready(Err(anyhow!("Project path not found")));
         };
-        let row = stack_frame.line.saturating_sub(1) as u32;
         cx.emit(StackFrameListEvent::SelectedStackFrameChanged(
             stack_frame_id,
         ));
+        let top_frame = self
+            .stack_frames(cx)
+            .ok()
+            .and_then(|stack_frames| stack_frames.into_iter().next())
+            .filter(|top_frame| top_frame.dap.id != stack_frame_id)
+            .and_then(|top_frame| {
+                let abs_path = self.abs_path_from_stack_frame(&top_frame.dap, cx)?;
+                Some((abs_path, top_frame.dap.line.saturating_sub(1) as u32))
+            });
         cx.spawn_in(window, async move |this, cx| {
-            let (worktree, relative_path) = this
-                .update(cx, |this, cx| {
-                    this.workspace.update(cx, |workspace, cx| {
-                        workspace.project().update(cx, |this, cx| {
-                            this.find_or_create_worktree(&abs_path, false, cx)
-                        })
-                    })
-                })??
-                .await?;
+            let fs = this.update(cx, |this, cx| {
+                this.workspace.read_with(cx, |workspace, cx| {
+                    let project = workspace.project().read(cx);
+                    project.is_local().then(|| project.fs().clone())
+                })
+            })??;
+            if let Some(fs) = fs {
+                let exists = fs.is_file(&abs_path).await;
+                this.update(cx, |this, cx| {
+                    this.unresolved_source_path = (!exists).then(|| abs_path.clone());
+                    cx.notify();
+                })?;
+                if !exists {
+                    return Ok(());
+                }
+            }
+
             let buffer = this
-                .update(cx, |this, cx| {
-                    this.workspace.update(cx, |this, cx| {
-                        this.project().update(cx, |this, cx| {
-                            let worktree_id = worktree.read(cx).id();
-                            this.open_buffer(
-                                ProjectPath {
-                                    worktree_id,
-                                    path: relative_path.into(),
-                                },
-                                cx,
-                            )
-                        })
-                    })
-                })??
+                .update(cx, |this, cx| this.open_source_buffer(abs_path.clone(), cx))?
                 .await?;
             let position = buffer.read_with(cx, |this, _| {
                 this.snapshot().anchor_after(PointUtf16::new(row, 0))
             })?;
+            let top_frame_position = match top_frame {
+                Some((abs_path, row)) => this
+                    .update(cx, |this, cx| this.open_source_buffer(abs_path, cx))?
+                    .await
+                    .ok()
+                    .map(|buffer| {
+                        buffer.read_with(cx, |buffer, _| {
+                            buffer.snapshot().anchor_after(PointUtf16::new(row, 0))
+                        })
+                    })
+                    .transpose()?,
+                None => None,
+            };
             this.update_in(cx, |this, window, cx| {
                 this.workspace.update(cx, |workspace, cx| {
                     let project_path = buffer
@@ -364,6 +691,7 @@ impl StackFrameList {
                                 stack_frame_id,
                                 path: abs_path,
                                 position,
+                                top_frame_position,
                             },
                             cx,
                         );
@@ -373,45 +701,219 @@ impl StackFrameList {
         })
     }
 
-    pub(crate) fn abs_path_from_stack_frame(stack_frame: &dap::StackFrame) -> Option<Arc<Path>> {
+    /// Opens the buffer of a frame's source, adding a worktree for it if needed.
+    fn open_source_buffer(
+        &self,
+        abs_path: Arc<Path>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Buffer>>> {
+        let project = match self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.project().clone())
+        {
+            Ok(project) => project,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        cx.spawn(async move |_, cx| {
+            let (worktree, relative_path) = project
+                .update(cx, |project, cx| {
+                    project.find_or_create_worktree(&abs_path, false, cx)
+                })?
+                .await?;
+            project
+                .update(cx, |project, cx| {
+                    let worktree_id = worktree.read(cx).id();
+                    project.open_buffer(
+                        ProjectPath {
+                            worktree_id,
+                            path: relative_path.into(),
+                        },
+                        cx,
+                    )
+                })?
+                .await
+        })
+    }
+
+    /// Opens a source the adapter provides through its `sourceReference` in a read-only editor.
+    fn go_to_source_reference(
+        &mut self,
+        source: dap::Source,
+        row: u32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.unresolved_source_path = None;
+        let title = source
+            .name
+            .clone()
+            .or_else(|| source.path.clone())
+            .unwrap_or_else(|| "Adapter Source".to_string());
+        let buffer = self.source_reference_buffer(source, title.clone(), cx);
+
+        cx.spawn_in(window, async move |this, cx| {
+            let buffer = buffer.await?;
+            this.update_in(cx, |this, window, cx| {
+                this.workspace.update(cx, |workspace, cx| {
+                    let existing_editor = workspace.items_of_type::<Editor>(cx).find(|editor| {
+                        editor.read(cx).buffer().read(cx).as_singleton().as_ref() == Some(&buffer)
+                    });
+                    let editor = match existing_editor {
+                        Some(editor) => {
+                            workspace.activate_item(&editor, true, true, window, cx);
+                            editor
+                        }
+                        None => {
+                            let project = workspace.project().clone();
+                            let buffer = cx.new(|cx| {
+                                MultiBuffer::singleton(buffer, cx).with_title(title.clone())
+                            });
+                            let editor = cx.new(|cx| {
+                                let mut editor =
+                                    Editor::for_multibuffer(buffer, Some(project), window, cx);
+                                editor.set_read_only(true);
+                                editor.set_breadcrumb_header(title);
+                                editor
+                            });
+                            workspace.add_item_to_active_pane(
+                                Box::new(editor.clone()),
+                                None,
+                                true,
+                                window,
+                                cx,
+                            );
+                            editor
+                        }
+                    };
+                    editor.update(cx, |editor, cx| {
+                        let point = Point::new(row, 0);
+                        editor.change_selections(
+                            Some(Autoscroll::center()),
+                            window,
+                            cx,
+                            |selections| selections.select_ranges([point..point]),
+                        );
+                    });
+                })
+            })?
+        })
+    }
+
+    fn source_reference_buffer(
+        &mut self,
+        source: dap::Source,
+        title: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Entity<Buffer>>> {
+        let Some(source_reference) = source.source_reference else {
+            return Task::ready(Err(anyhow!("Source has no source reference")));
+        };
+        if let Some(buffer) = self.source_reference_buffers.get(&source_reference) {
+            return Task::ready(Ok(buffer.clone()));
+        }
+        let Some(project) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.project().clone())
+            .ok()
+        else {
+            return Task::ready(Err(anyhow!("Workspace was dropped")));
+        };
+        let languages = project.read(cx).languages().clone();
+        let content = self.session.read(cx).source_content(source);
+
+        cx.spawn(async move |this, cx| {
+            let response = content.await?;
+            let language = languages
+                .language_for_file_path(Path::new(&title))
+                .await
+                .ok();
+            this.update(cx, |this, cx| {
+                if !project.read(cx).is_local() {
+                    anyhow::bail!(
+                        "Viewing adapter-provided sources is not supported in remote projects"
+                    );
+                }
+                let buffer = project.update(cx, |project, cx| {
+                    project.create_local_buffer(&response.content, language, cx)
+                });
+                buffer.update(cx, |buffer, cx| {
+                    buffer.set_capability(Capability::ReadOnly, cx);
+                });
+                this.source_reference_buffers
+                    .insert(source_reference, buffer.clone());
+                Ok(buffer)
+            })?
+        })
+    }
+
+    /// The local path of the frame's source, after applying the `source_path_mappings` setting.
+    pub(crate) fn abs_path_from_stack_frame(
+        &self,
+        stack_frame: &dap::StackFrame,
+        cx: &App,
+    ) -> Option<Arc<Path>> {
+        let session = self.session.read(cx);
         stack_frame.source.as_ref().and_then(|s| {
             s.path
                 .as_deref()
-                .map(|path| Arc::<Path>::from(Path::new(path)))
+                .map(|path| Arc::<Path>::from(session.local_source_path(path, cx)))
                 .filter(|path| path.is_absolute())
         })
     }
 
+    #[cfg(test)]
+    pub(crate) fn unresolved_source_path(&self) -> Option<&Arc<Path>> {
+        self.unresolved_source_path.as_ref()
+    }
+
+    pub(crate) fn map_source_path(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(missing_path) = self.unresolved_source_path.clone() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                let project = workspace.project().read(cx);
+                let fs = project.fs().clone();
+                let local_root = project
+                    .visible_worktrees(cx)
+                    .next()
+                    .map(|worktree| worktree.read(cx).abs_path());
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    SourcePathMappingModal::new(fs, missing_path, local_root, window, cx)
+                });
+            })
+            .log_err();
+    }
+
     pub fn restart_stack_frame(&mut self, stack_frame_id: u64, cx: &mut Context<Self>) {
         self.session.update(cx, |state, cx| {
             state.restart_stack_frame(stack_frame_id, cx)
         });
     }
 
+    /// Label frames, e.g. the `await` points of async stack traces, separate the frames around them.
     fn render_label_entry(
         &self,
         stack_frame: &dap::StackFrame,
         _cx: &mut Context<Self>,
     ) -> AnyElement {
         h_flex()
-            .rounded_md()
-            .justify_between()
             .w_full()
-            .group("")
+            .gap_2()
             .id(("label-stack-frame", stack_frame.id))
             .p_1()
             .on_any_mouse_down(|_, _, cx| {
                 cx.stop_propagation();
             })
+            .child(div().flex_1().child(Divider::horizontal()))
             .child(
-                v_flex().justify_center().gap_0p5().child(
-                    Label::new(stack_frame.name.clone())
-                        .size(LabelSize::Small)
-                        .weight(FontWeight::BOLD)
-                        .truncate()
-                        .color(Color::Info),
-                ),
+                Label::new(stack_frame.name.clone())
+                    .size(LabelSize::XSmall)
+                    .weight(FontWeight::BOLD)
+                    .truncate()
+                    .color(Color::Muted),
             )
+            .child(div().flex_1().child(Divider::horizontal()))
             .into_any()
     }
 
@@ -565,8 +1067,9 @@ impl StackFrameList {
                     .truncate()
                     .text_color(cx.theme().colors().text_muted)
                     .child(format!(
-                        "Show {} more{}",
+                        "Show {} hidden frame{}{}",
                         stack_frames.len(),
+                        if stack_frames.len() == 1 { "" } else { "s" },
                         first_stack_frame
                             .source
                             .as_ref()
@@ -577,6 +1080,95 @@ impl StackFrameList {
             .into_any()
     }
 
+    pub(crate) fn load_more_stack_frames(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.load_more_task.is_some() {
+            return;
+        }
+        let Some(thread_id) = self.thread_id(cx) else {
+            return;
+        };
+        let task = self.session.update(cx, |session, cx| {
+            session.load_more_stack_frames(thread_id, cx)
+        });
+        self.load_more_task = Some(cx.spawn_in(window, async move |this, cx| {
+            task.await;
+            this.update_in(cx, |this, window, cx| {
+                this.load_more_task = None;
+                this.build_entries(false, window, cx);
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn render_load_more_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let is_selected = Some(ix) == self.selected_ix;
+        let label = if self.load_more_task.is_some() {
+            "Loading Stack Frames…"
+        } else {
+            "Load More Stack Frames"
+        };
+
+        h_flex()
+            .rounded_md()
+            .w_full()
+            .id("load-more-stack-frames")
+            .p_1()
+            .when(is_selected, |this| {
+                this.bg(cx.theme().colors().element_hover)
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_ix = Some(ix);
+                this.activate_selected_entry(window, cx);
+            }))
+            .hover(|style| style.bg(cx.theme().colors().element_hover).cursor_pointer())
+            .child(
+                Label::new(label)
+                    .size(LabelSize::Small)
+                    .color(Color::Accent),
+            )
+            .into_any()
+    }
+
+    fn render_thread_entry(
+        &self,
+        ix: usize,
+        name: &str,
+        is_expanded: bool,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let is_selected = Some(ix) == self.selected_ix;
+
+        h_flex()
+            .rounded_md()
+            .w_full()
+            .gap_1()
+            .id(("thread-entry", ix))
+            .p_1()
+            .when(is_selected, |this| {
+                this.bg(cx.theme().colors().element_hover)
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.selected_ix = Some(ix);
+                this.activate_selected_entry(window, cx);
+            }))
+            .hover(|style| style.bg(cx.theme().colors().element_hover).cursor_pointer())
+            .child(Disclosure::new(("thread-disclosure", ix), is_expanded))
+            .child(
+                Label::new(name.to_string())
+                    .size(LabelSize::Small)
+                    .weight(FontWeight::SEMIBOLD)
+                    .truncate(),
+            )
+            .into_any()
+    }
+
     fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
         match &self.entries[ix] {
             StackFrameEntry::Label(stack_frame) => self.render_label_entry(stack_frame, cx),
@@ -584,6 +1176,10 @@ impl StackFrameList {
             StackFrameEntry::Collapsed(stack_frames) => {
                 self.render_collapsed_entry(ix, stack_frames, cx)
             }
+            StackFrameEntry::LoadMore => self.render_load_more_entry(ix, cx),
+            StackFrameEntry::Thread {
+                name, is_expanded, ..
+            } => self.render_thread_entry(ix, name, *is_expanded, cx),
         }
     }
 
@@ -693,21 +1289,145 @@ impl StackFrameList {
         match entry {
             StackFrameEntry::Normal(stack_frame) => {
                 let stack_frame = stack_frame.clone();
-                self.go_to_stack_frame_inner(stack_frame, window, cx)
-                    .detach_and_log_err(cx)
+                if let Some(thread_id) = self.frame_threads.get(&stack_frame.id).copied() {
+                    // Selecting the thread rebuilds the entries, which then opens the pending frame.
+                    self.pending_stack_frame_id = Some(stack_frame.id);
+                    // Keep both threads expanded or collapsed as they are, since expansion is
+                    // tracked relative to the selected thread.
+                    for thread_id in self.thread_id(cx).into_iter().chain([thread_id]) {
+                        if !self.toggled_threads.remove(&thread_id) {
+                            self.toggled_threads.insert(thread_id);
+                        }
+                    }
+                    let state = self.state.clone();
+                    window.defer(cx, move |window, cx| {
+                        state
+                            .update(cx, |state, cx| state.select_thread(thread_id, window, cx))
+                            .ok();
+                    });
+                } else {
+                    self.go_to_stack_frame_inner(stack_frame, window, cx)
+                        .detach_and_log_err(cx)
+                }
             }
             StackFrameEntry::Label(_) => {
                 debug_panic!("You should not be able to select a label stack frame")
             }
             StackFrameEntry::Collapsed(_) => self.expand_collapsed_entry(ix, cx),
+            StackFrameEntry::LoadMore => self.load_more_stack_frames(window, cx),
+            StackFrameEntry::Thread { thread_id, .. } => {
+                let thread_id = *thread_id;
+                self.toggle_thread_expansion(thread_id, window, cx)
+            }
         }
         cx.notify();
     }
 
+    /// Open the frame that called the opened one when `up` is true, and the one it called
+    /// otherwise, like gdb's `up` and `down` commands.
+    pub(crate) fn select_adjacent_frame(
+        &mut self,
+        up: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let opened_ix = self.opened_stack_frame_id.and_then(|stack_frame_id| {
+            self.entries.iter().position(|entry| match entry {
+                StackFrameEntry::Normal(stack_frame) => stack_frame.id == stack_frame_id,
+                _ => false,
+            })
+        });
+        let is_frame = |ix: &usize| match &self.entries[*ix] {
+            StackFrameEntry::Normal(stack_frame) => {
+                !self.frame_threads.contains_key(&stack_frame.id)
+            }
+            _ => false,
+        };
+        let ix = match opened_ix {
+            Some(opened_ix) if up => (opened_ix + 1..self.entries.len()).find(is_frame),
+            Some(opened_ix) => (0..opened_ix).rev().find(is_frame),
+            None => (0..self.entries.len()).find(is_frame),
+        };
+        let Some(ix) = ix else {
+            return;
+        };
+        self.select_ix(Some(ix), cx);
+        self.list_state.scroll_to_reveal_item(ix);
+        self.activate_selected_entry(window, cx);
+    }
+
     fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
         self.activate_selected_entry(window, cx);
     }
 
+    /// The exception the selected thread stopped on, with its details in a tooltip.
+    fn render_exception_info(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let thread_id = self.thread_id(cx)?;
+        let exception_info = self.session.read(cx).exception_info(thread_id)?;
+        let summary = exception_summary(exception_info);
+        let details = exception_detail_lines(exception_info);
+        let tooltip = details.iter().fold(summary.to_string(), |tooltip, line| {
+            format!("{tooltip}\n{line}")
+        });
+
+        Some(
+            h_flex()
+                .id("exception-info")
+                .bg(cx.theme().status().error_background)
+                .border_b_1()
+                .border_color(cx.theme().status().error_border)
+                .pl_1()
+                .gap_2()
+                .child(Icon::new(IconName::XCircle).color(Color::Error))
+                .child(
+                    Label::new(summary)
+                        .size(LabelSize::Small)
+                        .color(Color::Error)
+                        .truncate(),
+                )
+                .tooltip(Tooltip::text(tooltip))
+                .child(div().flex_1())
+                .child(
+                    IconButton::new("dismiss-exception-info", IconName::Close)
+                        .icon_size(IconSize::XSmall)
+                        .tooltip(Tooltip::text("Dismiss"))
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.session
+                                .update(cx, |session, cx| session.dismiss_exception_info(cx));
+                        })),
+                ),
+        )
+    }
+
+    fn render_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .p_1()
+            .gap_1()
+            .child(
+                Icon::new(IconName::MagnifyingGlass)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .px_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .rounded_sm()
+                    .child(self.filter_editor.clone()),
+            )
+            .child(
+                IconButton::new("show-all-threads", IconName::ListTree)
+                    .icon_size(IconSize::Small)
+                    .toggle_state(self.show_all_threads)
+                    .tooltip(Tooltip::text("Show All Threads"))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_all_threads(window, cx);
+                    })),
+            )
+    }
+
     fn render_list(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .p_1()
@@ -742,6 +1462,33 @@ impl Render for StackFrameList {
                         ),
                 )
             })
+            .when_some(self.unresolved_source_path.clone(), |el, path| {
+                el.child(
+                    h_flex()
+                        .bg(cx.theme().status().warning_background)
+                        .border_b_1()
+                        .border_color(cx.theme().status().warning_border)
+                        .pl_1()
+                        .gap_2()
+                        .child(Icon::new(IconName::Warning).color(Color::Warning))
+                        .child(
+                            Label::new(format!("{} not found", path.display()))
+                                .size(LabelSize::Small)
+                                .color(Color::Warning)
+                                .truncate(),
+                        )
+                        .child(div().flex_1())
+                        .child(
+                            Button::new("map-source-path", "Map Source Path…")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.map_source_path(window, cx)
+                                })),
+                        ),
+                )
+            })
+            .children(self.render_exception_info(cx))
+            .child(self.render_filter(cx))
             .child(self.render_list(window, cx))
             .child(self.render_vertical_scrollbar(cx))
     }
@@ -1,28 +1,127 @@
-use super::stack_frame_list::{StackFrameList, StackFrameListEvent};
-use dap::{ScopePresentationHint, StackFrameId, VariablePresentationHintKind, VariableReference};
-use editor::Editor;
+use super::{
+    image_visualizer::{ImageSource, ImageVisualizer},
+    memory_editor::MemoryEditor,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+    variable_snapshots::SnapshotValues,
+};
+use anyhow::Result;
+use dap::{
+    DapRegistry, EvaluateArgumentsContext, ScopePresentationHint, StackFrameId,
+    VariablePresentationHintKind, VariableReference, adapters::DebugAdapterName,
+    debugger_settings::DebuggerSettings, visualizer::DebugVisualizer,
+};
+use editor::{Editor, EditorEvent};
+use futures::{FutureExt as _, future::LocalBoxFuture};
 use gpui::{
-    Action, AnyElement, ClickEvent, ClipboardItem, Context, DismissEvent, Entity, FocusHandle,
-    Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful, Subscription,
-    TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred, uniform_list,
+    Action, AnyElement, AsyncApp, ClickEvent, ClipboardItem, Context, DismissEvent, Entity,
+    EventEmitter, FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, ScrollStrategy,
+    Stateful, Subscription, Task, TextStyleRefinement, UniformListScrollHandle, actions, anchored,
+    deferred, uniform_list,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
 use project::debugger::session::{Session, SessionEvent};
-use std::{collections::HashMap, ops::Range, sync::Arc};
-use ui::{ContextMenu, ListItem, Scrollbar, ScrollbarState, prelude::*};
-use util::debug_panic;
+use settings::Settings as _;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::Arc,
+    time::Duration,
+};
+use ui::{ContextMenu, ListItem, PopoverMenu, Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::{ResultExt, debug_panic};
 
 actions!(
     variable_list,
     [
         ExpandSelectedEntry,
         CollapseSelectedEntry,
+        ExpandSelectedEntryRecursively,
+        SelectParentEntry,
+        SelectFirstSibling,
+        SelectLastSibling,
         CopyVariableName,
         CopyVariableValue,
-        EditVariable
+        CopyVariableExpression,
+        CopyAsHexDump,
+        CopyScopeAsJson,
+        EditVariable,
+        TogglePinnedVariable,
+        ViewVariableAsTable,
+        ViewFullText,
+        FollowPointer,
+        RemoveWatch,
+        ToggleTypeColumn,
+        CaptureSnapshot
     ]
 );
 
+const PINNED_SECTION_NAME: &str = "Pinned";
+const WATCHES_SECTION_NAME: &str = "Watches";
+const RETURN_VALUE_NAME: &str = "Return value";
+const CHANGED_VALUE_HIGHLIGHT_DURATION: Duration = Duration::from_secs(3);
+/// How many levels below the selection Expand Recursively opens, as self-referential structures
+/// would otherwise expand forever.
+const MAX_RECURSIVE_EXPAND_DEPTH: usize = 8;
+/// Collections with more indexed children than this are shown as expandable ranges of this many
+/// children, which are only fetched once a range is expanded.
+const VARIABLES_PAGE_SIZE: u64 = 100;
+/// How many bytes Copy as Hex Dump reads from the memory a variable refers to.
+const HEX_DUMP_BYTE_COUNT: u64 = 256;
+/// How many elements of a collection Copy Scope as JSON includes.
+const MAX_JSON_ELEMENTS: u64 = 1000;
+/// Strings longer than this don't fit in a row, so they can be opened in an editor instead.
+const MAX_INLINE_STRING_LEN: usize = 80;
+
+/// Radix used to display integer variable values.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum NumericFormat {
+    #[default]
+    Decimal,
+    Hex,
+    Binary,
+    Octal,
+}
+
+impl NumericFormat {
+    const ALL: [Self; 4] = [Self::Decimal, Self::Hex, Self::Binary, Self::Octal];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Decimal => "Decimal",
+            Self::Hex => "Hexadecimal",
+            Self::Binary => "Binary",
+            Self::Octal => "Octal",
+        }
+    }
+
+    /// Re-formats `value` if it is an integer literal, returning `None` for anything else.
+    pub(crate) fn format(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        let (sign, digits) = match value.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", value),
+        };
+        let (radix, digits) = match digits.get(..2) {
+            Some("0x" | "0X") => (16, &digits[2..]),
+            Some("0b" | "0B") => (2, &digits[2..]),
+            Some("0o" | "0O") => (8, &digits[2..]),
+            _ => (10, digits),
+        };
+        let digits = digits.replace('_', "");
+        if digits.is_empty() {
+            return None;
+        }
+        let magnitude = u128::from_str_radix(&digits, radix).ok()?;
+
+        Some(match self {
+            Self::Decimal => format!("{sign}{magnitude}"),
+            Self::Hex => format!("{sign}{magnitude:#x}"),
+            Self::Binary => format!("{sign}{magnitude:#b}"),
+            Self::Octal => format!("{sign}{magnitude:#o}"),
+        })
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) struct EntryState {
     depth: usize,
@@ -35,6 +134,8 @@ pub(crate) struct EntryState {
 pub(crate) struct EntryPath {
     pub leaf_name: Option<SharedString>,
     pub indices: Arc<[SharedString]>,
+    /// Whether this entry lives in the pinned section rather than under its own scope.
+    pub pinned: bool,
 }
 
 impl EntryPath {
@@ -42,6 +143,7 @@ impl EntryPath {
         Self {
             leaf_name: Some(scope_name.into()),
             indices: Arc::new([]),
+            pinned: false,
         }
     }
 
@@ -49,6 +151,33 @@ impl EntryPath {
         Self {
             leaf_name: Some(name),
             indices: self.indices.clone(),
+            pinned: self.pinned,
+        }
+    }
+
+    /// Builds an evaluatable expression such as `foo.bar[3].baz` from the names leading to this
+    /// variable, for adapters that don't report an `evaluateName`.
+    pub(crate) fn to_expression(&self) -> String {
+        use std::fmt::Write;
+        let mut names = self.indices.iter().skip(1);
+        let mut expression = names.next().map(ToString::to_string).unwrap_or_default();
+        for name in names.filter(|name| !is_page_name(name)) {
+            if name.starts_with('[') {
+                expression.push_str(name);
+            } else if !name.is_empty() && name.chars().all(|c| c.is_ascii_digit()) {
+                _ = write!(expression, "[{name}]");
+            } else {
+                _ = write!(expression, ".{name}");
+            }
+        }
+        expression
+    }
+
+    fn with_pinned(&self, pinned: bool) -> Self {
+        Self {
+            leaf_name: self.leaf_name.clone(),
+            indices: self.indices.clone(),
+            pinned,
         }
     }
 
@@ -62,6 +191,7 @@ impl EntryPath {
                 .cloned()
                 .chain(std::iter::once(name))
                 .collect(),
+            pinned: self.pinned,
         }
     }
 }
@@ -87,7 +217,6 @@ impl EntryKind {
         }
     }
 
-    #[allow(dead_code)]
     fn name(&self) -> &str {
         match self {
             EntryKind::Variable(dap) => &dap.name,
@@ -96,6 +225,58 @@ impl EntryKind {
     }
 }
 
+/// How deep the filter searches below a scope for matches among already loaded variables.
+const MAX_FILTER_DEPTH: usize = 16;
+
+/// Entries kept visible by the filter query: the matches themselves and all of their ancestors,
+/// which get expanded whenever the query changes.
+#[derive(Default)]
+struct FilterMatches {
+    visible: HashSet<EntryPath>,
+    expanded: HashSet<EntryPath>,
+}
+
+impl FilterMatches {
+    fn collect(
+        &mut self,
+        session: &Session,
+        variables_reference: VariableReference,
+        parent_path: &EntryPath,
+        query: &str,
+        depth: usize,
+        visited: &mut HashSet<VariableReference>,
+    ) -> bool {
+        let mut any_match = false;
+        for variable in session.loaded_variables(variables_reference) {
+            let path = parent_path
+                .with_child(variable.name.clone().into())
+                .with_name(variable.name.clone().into());
+            let is_match = variable.name.to_lowercase().contains(query)
+                || variable.value.to_lowercase().contains(query);
+            let has_matching_descendant = variable.variables_reference != 0
+                && depth < MAX_FILTER_DEPTH
+                && visited.insert(variable.variables_reference)
+                && self.collect(
+                    session,
+                    variable.variables_reference,
+                    &path,
+                    query,
+                    depth + 1,
+                    visited,
+                );
+
+            if has_matching_descendant {
+                self.expanded.insert(path.clone());
+            }
+            if is_match || has_matching_descendant {
+                self.visible.insert(path);
+                any_match = true;
+            }
+        }
+        any_match
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct ListEntry {
     dap_kind: EntryKind,
@@ -117,6 +298,9 @@ impl ListEntry {
             EntryKind::Variable(dap) => format!("variable-{}", dap.name),
             EntryKind::Scope(dap) => format!("scope-{}", dap.name),
         };
+        if self.path.pinned {
+            id.insert_str(0, "pinned-");
+        }
         for name in self.path.indices.iter() {
             _ = write!(id, "-{}", name);
         }
@@ -129,6 +313,9 @@ impl ListEntry {
             EntryKind::Variable(dap) => format!("variable-{}", dap.name),
             EntryKind::Scope(dap) => format!("scope-{}", dap.name),
         };
+        if self.path.pinned {
+            id.insert_str(0, "pinned-");
+        }
         for name in self.path.indices.iter() {
             _ = write!(id, "-{}", name);
         }
@@ -137,6 +324,275 @@ impl ListEntry {
     }
 }
 
+pub enum VariableListEvent {
+    ViewAsTable(dap::Variable),
+    CaptureSnapshot,
+    ViewFullText {
+        title: SharedString,
+        text: String,
+        is_json: bool,
+    },
+}
+
+/// Removes the quotes and escapes that adapters wrap string values in, returning `None` for
+/// values that aren't quoted.
+fn unquote_string(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.len() < 2 {
+        return None;
+    }
+    if value.starts_with('"') && value.ends_with('"') {
+        return Some(
+            serde_json::from_str(value).unwrap_or_else(|_| value[1..value.len() - 1].to_string()),
+        );
+    }
+    if value.starts_with('\'') && value.ends_with('\'') {
+        return Some(
+            value[1..value.len() - 1]
+                .replace("\\n", "\n")
+                .replace("\\t", "\t")
+                .replace("\\'", "'"),
+        );
+    }
+    None
+}
+
+fn is_long_string(variable: &dap::Variable) -> bool {
+    let is_string = variable.value.trim_start().starts_with(['"', '\''])
+        || variable
+            .type_
+            .as_deref()
+            .is_some_and(|type_| type_.to_lowercase().contains("string"));
+    is_string && variable.value.chars().count() > MAX_INLINE_STRING_LEN
+}
+
+/// The text View Full Text opens for a string value, and whether it holds JSON, in which case it
+/// is pretty-printed.
+pub(crate) fn full_text(value: &str) -> (String, bool) {
+    let text = unquote_string(value).unwrap_or_else(|| value.to_string());
+    let pretty_json = text
+        .trim_start()
+        .starts_with(['{', '['])
+        .then(|| serde_json::from_str::<serde_json::Value>(&text).ok())
+        .flatten()
+        .and_then(|json| serde_json::to_string_pretty(&json).ok());
+    match pretty_json {
+        Some(pretty_json) => (pretty_json, true),
+        None => (text, false),
+    }
+}
+
+/// Formats `bytes` read from `start_address` like `hexdump -C` does: an address column, then
+/// sixteen bytes per line in hex and as ASCII.
+pub(crate) fn hex_dump(start_address: u64, bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut dump = String::new();
+    for (line_ix, line) in bytes.chunks(16).enumerate() {
+        _ = write!(dump, "{:08x} ", start_address + line_ix as u64 * 16);
+        for column in 0..16 {
+            if column == 8 {
+                dump.push(' ');
+            }
+            match line.get(column) {
+                Some(byte) => _ = write!(dump, " {byte:02x}"),
+                None => dump.push_str("   "),
+            }
+        }
+        dump.push_str("  |");
+        dump.extend(line.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        dump.push_str("|\n");
+    }
+    dump
+}
+
+/// Converts a displayed value to the JSON value it looks like, keeping it as a string otherwise.
+fn json_value(value: &str) -> serde_json::Value {
+    if let Some(string) = unquote_string(value) {
+        return serde_json::Value::String(string);
+    }
+    serde_json::from_str::<serde_json::Value>(value.trim())
+        .ok()
+        .filter(|json| !json.is_object() && !json.is_array())
+        .unwrap_or_else(|| serde_json::Value::String(value.to_string()))
+}
+
+/// Fetches the children of `variables_reference` and serializes them as a JSON object, or as an
+/// array when they are all indexed. Children are followed `depth` levels deep, below which they
+/// are represented by their displayed value.
+fn variables_to_json(
+    session: Entity<Session>,
+    variables_reference: VariableReference,
+    indexed_variables: Option<u64>,
+    depth: usize,
+    cx: AsyncApp,
+) -> LocalBoxFuture<'static, Result<serde_json::Value>> {
+    async move {
+        let range = indexed_variables
+            .filter(|count| *count > MAX_JSON_ELEMENTS)
+            .map(|_| 0..MAX_JSON_ELEMENTS);
+        let variables = session
+            .read_with(&cx, |session, _| {
+                session.fetch_variables(variables_reference, range)
+            })?
+            .await?;
+
+        let is_array = !variables.is_empty()
+            && variables
+                .iter()
+                .all(|variable| variable.name.starts_with('[') && variable.name.ends_with(']'));
+        let mut values = Vec::with_capacity(variables.len());
+        for variable in variables {
+            let value = if variable.variables_reference != 0 && depth > 0 {
+                variables_to_json(
+                    session.clone(),
+                    variable.variables_reference,
+                    variable.indexed_variables,
+                    depth - 1,
+                    cx.clone(),
+                )
+                .await?
+            } else {
+                json_value(&variable.value)
+            };
+            values.push((variable.name, value));
+        }
+
+        Ok(if is_array {
+            serde_json::Value::Array(values.into_iter().map(|(_, value)| value).collect())
+        } else {
+            serde_json::Value::Object(values.into_iter().collect())
+        })
+    }
+    .boxed_local()
+}
+
+/// Whether `variable` holds an address that can be dereferenced, judging by its type or, for
+/// adapters that don't report types, by a memory reference next to an address-like value.
+fn is_pointer(variable: &dap::Variable) -> bool {
+    match variable.type_.as_deref().map(str::trim) {
+        Some(type_) if !type_.is_empty() => {
+            type_.ends_with('*') || type_.starts_with('*') || type_.starts_with('&')
+        }
+        _ => {
+            variable.memory_reference.is_some()
+                && variable.variables_reference == 0
+                && variable.value.trim_start().starts_with("0x")
+        }
+    }
+}
+
+/// An entry standing in for `range` of a large collection's indexed children.
+fn page_variable(range: &Range<u64>, variables_reference: VariableReference) -> dap::Variable {
+    dap::Variable {
+        name: format!("[{}..{}]", range.start, range.end.saturating_sub(1)),
+        value: String::new(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: Some(range.end - range.start),
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    }
+}
+
+fn is_locals_scope(scope: &dap::Scope) -> bool {
+    scope
+        .presentation_hint
+        .as_ref()
+        .map(|hint| *hint == ScopePresentationHint::Locals)
+        .unwrap_or(scope.name.to_lowercase().starts_with("local"))
+}
+
+/// Whether `variable` holds the value returned by the call the debuggee just stepped over or out
+/// of, which adapters list among the locals under names like debugpy's `(return) compute`.
+fn is_return_value(variable: &dap::Variable) -> bool {
+    let name = variable.name.to_lowercase();
+    name.starts_with("(return)")
+        || matches!(
+            name.as_str(),
+            "[return value]" | "<return value>" | "$returnvalue" | "return value"
+        )
+}
+
+/// Moves the return value reported after stepping over or out of a call to the top of the
+/// locals, under a name that doesn't depend on the adapter.
+fn hoist_return_value(locals: &mut Vec<dap::Variable>) {
+    if let Some(ix) = locals.iter().position(is_return_value) {
+        let mut return_value = locals.remove(ix);
+        return_value.name = RETURN_VALUE_NAME.into();
+        locals.insert(0, return_value);
+    }
+}
+
+fn is_page_name(name: &str) -> bool {
+    name.strip_prefix('[')
+        .and_then(|name| name.strip_suffix(']'))
+        .and_then(|name| name.split_once(".."))
+        .is_some_and(|(start, end)| start.parse::<u64>().is_ok() && end.parse::<u64>().is_ok())
+}
+
+fn unevaluated_variable(expression: String) -> dap::Variable {
+    dap::Variable {
+        name: expression.clone(),
+        value: String::new(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: Some(expression),
+        variables_reference: 0,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    }
+}
+
+/// An entry for an expression evaluated by the variable list itself, such as a watch or the
+/// target of a followed pointer.
+pub(crate) fn evaluated_variable(
+    expression: String,
+    response: Result<dap::EvaluateResponse>,
+) -> dap::Variable {
+    let mut variable = unevaluated_variable(expression);
+    match response {
+        Ok(response) => {
+            variable.value = response.result;
+            variable.type_ = response.type_;
+            variable.presentation_hint = response.presentation_hint;
+            variable.variables_reference = response.variables_reference;
+            variable.named_variables = response.named_variables;
+            variable.indexed_variables = response.indexed_variables;
+            variable.memory_reference = response.memory_reference;
+        }
+        Err(error) => variable.value = format!("<{error}>"),
+    }
+    variable
+}
+
+/// An expression re-evaluated in the selected stack frame whenever the debuggee stops.
+struct Watch {
+    expression: SharedString,
+    variable: dap::Variable,
+}
+
+impl Watch {
+    fn new(expression: SharedString) -> Self {
+        Self {
+            variable: unevaluated_variable(expression.to_string()),
+            expression,
+        }
+    }
+}
+
 pub struct VariableList {
     entries: Vec<ListEntry>,
     entry_states: HashMap<EntryPath, EntryState>,
@@ -146,8 +602,29 @@ pub struct VariableList {
     session: Entity<Session>,
     selection: Option<EntryPath>,
     open_context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    image_visualizer: Option<(Entity<ImageVisualizer>, Point<Pixels>, Subscription)>,
+    memory_editor: Option<(Entity<MemoryEditor>, Point<Pixels>, Subscription)>,
     focus_handle: FocusHandle,
     edited_path: Option<(EntryPath, Entity<Editor>)>,
+    filter_editor: Entity<Editor>,
+    filter_query: String,
+    pinned_paths: Vec<EntryPath>,
+    /// Values of the entries that were visible when the debuggee last resumed.
+    previous_values: HashMap<EntryPath, String>,
+    changed_paths: HashSet<EntryPath>,
+    visualized_values: HashMap<EntryPath, String>,
+    /// Dereferenced values of pointers, keyed by the path of the pointer they are shown under.
+    followed_pointers: HashMap<EntryPath, dap::Variable>,
+    /// Entries whose descendants are expanded as they load.
+    recursively_expanded: HashSet<EntryPath>,
+    watches: Vec<Watch>,
+    _evaluate_watches_task: Task<()>,
+    highlight_changes: bool,
+    show_changed_only: bool,
+    show_types: bool,
+    _highlight_changes_task: Task<()>,
+    session_format: NumericFormat,
+    variable_formats: HashMap<EntryPath, NumericFormat>,
     disabled: bool,
     _subscriptions: Vec<Subscription>,
 }
@@ -160,6 +637,11 @@ impl VariableList {
         cx: &mut Context<Self>,
     ) -> Self {
         let focus_handle = cx.focus_handle();
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter variables", cx);
+            editor
+        });
 
         let _subscriptions = vec![
             cx.subscribe(&stack_frame_list, Self::handle_stack_frame_list_events),
@@ -168,12 +650,20 @@ impl VariableList {
                     this.selection.take();
                     this.edited_path.take();
                     this.selected_stack_frame_id.take();
+                    this.followed_pointers.clear();
+                    this.recursively_expanded.clear();
+                    this.remember_values_before_stop(cx);
                 }
                 SessionEvent::Variables => {
                     this.build_entries(cx);
                 }
                 _ => {}
             }),
+            cx.subscribe(&filter_editor, |this, _, event: &EditorEvent, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.build_entries(cx);
+                }
+            }),
             cx.on_focus_out(&focus_handle, window, |this, _, _, cx| {
                 this.edited_path.take();
                 cx.notify();
@@ -191,8 +681,26 @@ impl VariableList {
             selected_stack_frame_id: None,
             selection: None,
             open_context_menu: None,
+            image_visualizer: None,
+            memory_editor: None,
             disabled: false,
             edited_path: None,
+            filter_editor,
+            filter_query: String::new(),
+            pinned_paths: Vec::new(),
+            previous_values: Default::default(),
+            changed_paths: Default::default(),
+            visualized_values: Default::default(),
+            followed_pointers: Default::default(),
+            recursively_expanded: Default::default(),
+            watches: Vec::new(),
+            _evaluate_watches_task: Task::ready(()),
+            highlight_changes: false,
+            show_changed_only: false,
+            show_types: false,
+            _highlight_changes_task: Task::ready(()),
+            session_format: NumericFormat::default(),
+            variable_formats: Default::default(),
             entries: Default::default(),
             entry_states: Default::default(),
         }
@@ -220,18 +728,14 @@ impl VariableList {
             session.scopes(stack_frame_id, cx).iter().cloned().collect()
         });
 
+        let pinned_variables = self.resolve_pinned_variables(&scopes, cx);
         let mut contains_local_scope = false;
 
         let mut stack = scopes
             .into_iter()
             .rev()
             .filter(|scope| {
-                if scope
-                    .presentation_hint
-                    .as_ref()
-                    .map(|hint| *hint == ScopePresentationHint::Locals)
-                    .unwrap_or(scope.name.to_lowercase().starts_with("local"))
-                {
+                if is_locals_scope(scope) {
                     contains_local_scope = true;
                 }
 
@@ -250,44 +754,178 @@ impl VariableList {
             .collect::<Vec<_>>();
 
         let scopes_count = stack.len();
+        let visualizers = DapRegistry::global(cx).visualizers();
+        let adapter = self.session.read(cx).adapter();
+        let mut visualized_values = HashMap::default();
+
+        let query = self.filter_editor.read(cx).text(cx).trim().to_lowercase();
+        let filter = (!query.is_empty()).then(|| {
+            let session = self.session.read(cx);
+            let mut matches = FilterMatches::default();
+            for (_, variables_reference, path, dap_kind) in &stack {
+                let path = path.with_child(dap_kind.name().to_owned().into());
+                let mut visited = HashSet::default();
+                if matches.collect(session, *variables_reference, &path, &query, 0, &mut visited) {
+                    matches.visible.insert(path.clone());
+                    matches.expanded.insert(path);
+                }
+            }
+            matches
+        });
+        let query_changed = query != self.filter_query;
+        let mut pages = HashMap::default();
+
+        if !pinned_variables.is_empty() {
+            let header_path = EntryPath::for_scope(PINNED_SECTION_NAME).with_pinned(true);
+            let header_state = self
+                .entry_states
+                .entry(header_path.with_child(PINNED_SECTION_NAME.into()))
+                .or_insert(EntryState {
+                    depth: 1,
+                    is_expanded: true,
+                    has_children: false,
+                    parent_reference: 0,
+                });
+            if header_state.is_expanded {
+                stack.extend(pinned_variables.into_iter().rev().map(
+                    |(container_reference, variable, path)| {
+                        (
+                            container_reference,
+                            variable.variables_reference,
+                            path.with_pinned(true),
+                            EntryKind::Variable(variable),
+                        )
+                    },
+                ));
+            }
+            let header = section_scope(PINNED_SECTION_NAME);
+            stack.push((0, 0, header_path, EntryKind::Scope(header)));
+        }
+
+        if !self.watches.is_empty() {
+            let header_path = EntryPath::for_scope(WATCHES_SECTION_NAME);
+            let watches_path = header_path.with_child(WATCHES_SECTION_NAME.into());
+            let header_state = self
+                .entry_states
+                .entry(watches_path.clone())
+                .or_insert(EntryState {
+                    depth: 1,
+                    is_expanded: true,
+                    has_children: false,
+                    parent_reference: 0,
+                });
+            if header_state.is_expanded {
+                stack.extend(self.watches.iter().rev().map(|watch| {
+                    (
+                        0,
+                        watch.variable.variables_reference,
+                        watches_path.with_child(watch.expression.clone()),
+                        EntryKind::Variable(watch.variable.clone()),
+                    )
+                }));
+            }
+            let header = section_scope(WATCHES_SECTION_NAME);
+            stack.push((0, 0, header_path, EntryKind::Scope(header)));
+        }
 
         while let Some((container_reference, variables_reference, mut path, dap_kind)) = stack.pop()
         {
+            let page: Option<Range<u64>> = pages.remove(&path);
             match &dap_kind {
                 EntryKind::Variable(dap) => path = path.with_name(dap.name.clone().into()),
                 EntryKind::Scope(dap) => path = path.with_child(dap.name.clone().into()),
             }
 
+            if let EntryKind::Variable(dap) = &dap_kind {
+                if self
+                    .previous_values
+                    .get(&path)
+                    .is_some_and(|previous_value| *previous_value != dap.value)
+                {
+                    self.changed_paths.insert(path.clone());
+                }
+                if let Some(visualized) = self.visualize(&visualizers, &adapter, dap, cx) {
+                    visualized_values.insert(path.clone(), visualized);
+                }
+            }
+
+            let pointee = self.followed_pointers.get(&path).cloned();
+            let has_children = variables_reference != 0 || pointee.is_some();
+            let expand_recursively = has_children && self.is_recursively_expanded(&path);
             let var_state = self
                 .entry_states
                 .entry(path.clone())
                 .and_modify(|state| {
                     state.parent_reference = container_reference;
-                    state.has_children = variables_reference != 0;
+                    state.has_children = has_children;
                 })
                 .or_insert(EntryState {
                     depth: path.indices.len(),
-                    is_expanded: dap_kind.as_scope().is_some_and(|scope| {
-                        (scopes_count == 1 && !contains_local_scope)
-                            || scope
-                                .presentation_hint
-                                .as_ref()
-                                .map(|hint| *hint == ScopePresentationHint::Locals)
-                                .unwrap_or(scope.name.to_lowercase().starts_with("local"))
-                    }),
+                    is_expanded: expand_recursively
+                        || dap_kind.as_scope().is_some_and(|scope| {
+                            (scopes_count == 1 && !contains_local_scope) || is_locals_scope(scope)
+                        }),
                     parent_reference: container_reference,
-                    has_children: variables_reference != 0,
+                    has_children,
                 });
 
+            if !path.pinned
+                && !is_watch_path(&path)
+                && filter
+                    .as_ref()
+                    .is_some_and(|filter| !filter.visible.contains(&path))
+            {
+                continue;
+            }
+
+            if query_changed
+                && filter
+                    .as_ref()
+                    .is_some_and(|filter| filter.expanded.contains(&path))
+            {
+                var_state.is_expanded = true;
+            }
+
             entries.push(ListEntry {
                 dap_kind,
                 path: path.clone(),
             });
 
-            if var_state.is_expanded {
-                let children = self
-                    .session
-                    .update(cx, |session, cx| session.variables(variables_reference, cx));
+            if var_state.is_expanded && variables_reference != 0 {
+                let indexed_count = dap_kind
+                    .as_variable()
+                    .filter(|_| page.is_none())
+                    .and_then(|variable| variable.indexed_variables)
+                    .filter(|count| *count > VARIABLES_PAGE_SIZE);
+                let mut children = self.session.update(cx, |session, cx| {
+                    if page.is_some() {
+                        session.variables_page(variables_reference, page, cx)
+                    } else if indexed_count.is_some() {
+                        session.variables_page(variables_reference, None, cx)
+                    } else {
+                        session.variables(variables_reference, cx)
+                    }
+                });
+                if dap_kind.as_scope().is_some_and(is_locals_scope)
+                    && self.session.read(cx).stepped_over_call()
+                {
+                    hoist_return_value(&mut children);
+                }
+                if let Some(count) = indexed_count {
+                    let page_starts = (0..count).step_by(VARIABLES_PAGE_SIZE as usize);
+                    for start in page_starts.rev() {
+                        let range = start..(start + VARIABLES_PAGE_SIZE).min(count);
+                        let variable = page_variable(&range, variables_reference);
+                        let page_path = path.with_child(variable.name.clone().into());
+                        pages.insert(page_path.clone(), range);
+                        stack.push((
+                            variables_reference,
+                            variables_reference,
+                            page_path,
+                            EntryKind::Variable(variable),
+                        ));
+                    }
+                }
                 stack.extend(children.into_iter().rev().map(|child| {
                     (
                         variables_reference,
@@ -297,12 +935,115 @@ impl VariableList {
                     )
                 }));
             }
+            if let Some(pointee) = pointee.filter(|_| var_state.is_expanded) {
+                stack.push((
+                    0,
+                    pointee.variables_reference,
+                    path.with_child(pointee.name.clone().into()),
+                    EntryKind::Variable(pointee),
+                ));
+            }
+        }
+
+        if self.show_changed_only {
+            entries.retain(|entry| {
+                entry.path.pinned
+                    || self.changed_paths.iter().any(|changed| {
+                        !changed.pinned && changed.indices.starts_with(&entry.path.indices)
+                    })
+            });
         }
 
         self.entries = entries;
+        self.visualized_values = visualized_values;
+        self.filter_query = query;
+        cx.notify();
+    }
+
+    fn remember_values_before_stop(&mut self, cx: &mut Context<Self>) {
+        self.previous_values = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry.path.clone(), entry.as_variable()?.value.clone())))
+            .collect();
+        self.changed_paths.clear();
+        self.highlight_changes = true;
+        self._highlight_changes_task = cx.spawn(async move |this, cx| {
+            cx.background_executor()
+                .timer(CHANGED_VALUE_HIGHLIGHT_DURATION)
+                .await;
+            this.update(cx, |this, cx| {
+                this.highlight_changes = false;
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    pub(crate) fn toggle_changed_values_only(&mut self, cx: &mut Context<Self>) {
+        self.show_changed_only = !self.show_changed_only;
+        self.build_entries(cx);
+    }
+
+    fn toggle_type_column(
+        &mut self,
+        _: &ToggleTypeColumn,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_types = !self.show_types;
         cx.notify();
     }
 
+    /// Looks pinned variables up again by their path, as their containers' references change
+    /// every time the debuggee stops.
+    fn resolve_pinned_variables(
+        &self,
+        scopes: &[dap::Scope],
+        cx: &mut Context<Self>,
+    ) -> Vec<(VariableReference, dap::Variable, EntryPath)> {
+        self.pinned_paths
+            .iter()
+            .filter_map(|path| {
+                let (scope_name, names) = path.indices.split_first()?;
+                let (name, parent_names) = names.split_last()?;
+                let scope = scopes.iter().find(|scope| *scope_name == scope.name)?;
+
+                self.session.update(cx, |session, cx| {
+                    let mut container_reference = scope.variables_reference;
+                    for parent_name in parent_names {
+                        container_reference = session
+                            .variables(container_reference, cx)
+                            .into_iter()
+                            .find(|variable| *parent_name == variable.name)
+                            .map(|variable| variable.variables_reference)
+                            .filter(|reference| *reference != 0)?;
+                    }
+                    let variable = session
+                        .variables(container_reference, cx)
+                        .into_iter()
+                        .find(|variable| *name == variable.name)?;
+                    Some((container_reference, variable, path.clone()))
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn is_pinned(&self, path: &EntryPath) -> bool {
+        let path = path.with_pinned(false);
+        self.pinned_paths.contains(&path)
+    }
+
+    pub(crate) fn toggle_pinned(&mut self, path: &EntryPath, cx: &mut Context<Self>) {
+        let path = path.with_pinned(false);
+        if let Some(ix) = self.pinned_paths.iter().position(|pinned| pinned == &path) {
+            self.pinned_paths.remove(ix);
+        } else {
+            self.pinned_paths.push(path);
+        }
+        self.build_entries(cx);
+    }
+
     fn handle_stack_frame_list_events(
         &mut self,
         _: Entity<StackFrameList>,
@@ -312,12 +1053,88 @@ impl VariableList {
         match event {
             StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
                 self.selected_stack_frame_id = Some(*stack_frame_id);
+                self.evaluate_watches(cx);
                 self.build_entries(cx);
             }
             StackFrameListEvent::BuiltEntries => {}
         }
     }
 
+    /// Adds `expression` to the watches, unless it is already being watched.
+    pub(crate) fn add_watch(&mut self, expression: String, cx: &mut Context<Self>) {
+        let expression = SharedString::from(expression.trim().to_string());
+        if expression.is_empty()
+            || self
+                .watches
+                .iter()
+                .any(|watch| watch.expression == expression)
+        {
+            return;
+        }
+        self.watches.push(Watch::new(expression));
+        self.evaluate_watches(cx);
+        self.build_entries(cx);
+    }
+
+    fn remove_watch(&mut self, _: &RemoveWatch, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(expression) = self
+            .selection
+            .as_ref()
+            .filter(|path| is_watch_path(path) && path.indices.len() == 2)
+            .and_then(|path| path.indices.get(1))
+        else {
+            return;
+        };
+        self.watches.retain(|watch| &watch.expression != expression);
+        self.selection.take();
+        self.build_entries(cx);
+    }
+
+    fn evaluate_watches(&mut self, cx: &mut Context<Self>) {
+        let Some(frame_id) = self.selected_stack_frame_id else {
+            return;
+        };
+        let session = self.session.read(cx);
+        let evaluations = self
+            .watches
+            .iter()
+            .map(|watch| {
+                session.evaluate_silently(
+                    watch.expression.to_string(),
+                    Some(EvaluateArgumentsContext::Watch),
+                    Some(frame_id),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        self._evaluate_watches_task = cx.spawn(async move |this, cx| {
+            let responses = futures::future::join_all(evaluations).await;
+            this.update(cx, |this, cx| {
+                for (watch, response) in this.watches.iter_mut().zip(responses) {
+                    watch.variable = evaluated_variable(watch.expression.to_string(), response);
+                }
+                this.build_entries(cx);
+            })
+            .ok();
+        });
+    }
+
+    #[cfg(test)]
+    pub(crate) fn watch_expressions(&self) -> Vec<SharedString> {
+        self.watches
+            .iter()
+            .map(|watch| watch.expression.clone())
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn watch_values(&self) -> Vec<String> {
+        self.watches
+            .iter()
+            .map(|watch| watch.variable.value.clone())
+            .collect()
+    }
+
     pub fn completion_variables(&self, _cx: &mut Context<Self>) -> Vec<dap::Variable> {
         self.entries
             .iter()
@@ -359,19 +1176,27 @@ impl VariableList {
         self.build_entries(cx);
     }
 
+    /// Selecting doesn't change the shape of the tree, so only scroll the selection into view
+    /// instead of rebuilding every entry of a potentially huge scope.
+    fn select_path(&mut self, path: EntryPath, cx: &mut Context<Self>) {
+        if let Some(ix) = self.entries.iter().position(|entry| entry.path == path) {
+            self.list_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+        self.selection = Some(path);
+        cx.notify();
+    }
+
     fn select_first(&mut self, _: &SelectFirst, window: &mut Window, cx: &mut Context<Self>) {
         self.cancel(&Default::default(), window, cx);
         if let Some(variable) = self.entries.first() {
-            self.selection = Some(variable.path.clone());
-            self.build_entries(cx);
+            self.select_path(variable.path.clone(), cx);
         }
     }
 
     fn select_last(&mut self, _: &SelectLast, window: &mut Window, cx: &mut Context<Self>) {
         self.cancel(&Default::default(), window, cx);
         if let Some(variable) = self.entries.last() {
-            self.selection = Some(variable.path.clone());
-            self.build_entries(cx);
+            self.select_path(variable.path.clone(), cx);
         }
     }
 
@@ -389,8 +1214,7 @@ impl VariableList {
             if let Some(new_selection) =
                 index.and_then(|ix| self.entries.get(ix).map(|var| var.path.clone()))
             {
-                self.selection = Some(new_selection);
-                self.build_entries(cx);
+                self.select_path(new_selection, cx);
             } else {
                 self.select_last(&SelectLast, window, cx);
             }
@@ -413,8 +1237,7 @@ impl VariableList {
             if let Some(new_selection) =
                 index.and_then(|ix| self.entries.get(ix).map(|var| var.path.clone()))
             {
-                self.selection = Some(new_selection);
-                self.build_entries(cx);
+                self.select_path(new_selection, cx);
             } else {
                 self.select_first(&SelectFirst, window, cx);
             }
@@ -429,20 +1252,62 @@ impl VariableList {
         cx.notify();
     }
 
+    /// Whether the adapter lets us assign to `variable`, either through `setVariable` or, for
+    /// adapters that only support `setExpression`, through the variable's evaluate name.
+    fn can_edit_variable(&self, variable: &dap::Variable, cx: &App) -> bool {
+        let capabilities = self.session.read(cx).capabilities();
+        capabilities.supports_set_variable.unwrap_or_default()
+            || (capabilities.supports_set_expression.unwrap_or_default()
+                && variable.evaluate_name.is_some())
+    }
+
     fn confirm(&mut self, _: &menu::Confirm, _window: &mut Window, cx: &mut Context<Self>) {
         if let Some((var_path, editor)) = self.edited_path.take() {
             let Some(state) = self.entry_states.get(&var_path) else {
                 return;
             };
             let variables_reference = state.parent_reference;
-            let Some(name) = var_path.leaf_name else {
+            let Some(name) = var_path.leaf_name.clone() else {
                 return;
             };
+            let evaluate_name = self
+                .entries
+                .iter()
+                .find(|entry| entry.path == var_path)
+                .and_then(|entry| entry.as_variable())
+                .and_then(|variable| variable.evaluate_name.clone());
             let value = editor.read(cx).text(cx);
-
-            self.session.update(cx, |session, cx| {
-                session.set_variable_value(variables_reference, name.into(), value, cx)
+            let frame_id = self.selected_stack_frame_id;
+
+            let set_value = self.session.update(cx, |session, cx| {
+                let capabilities = session.capabilities();
+                let supports_set_variable = capabilities.supports_set_variable.unwrap_or_default();
+                let supports_set_expression =
+                    capabilities.supports_set_expression.unwrap_or_default();
+
+                // Followed pointees have no container, so they can only be set by expression.
+                if supports_set_variable && variables_reference != 0 {
+                    let task =
+                        session.set_variable_value(variables_reference, name.into(), value, cx);
+                    Some(cx.background_spawn(async move { task.await.is_some() }))
+                } else if let Some(expression) = evaluate_name.filter(|_| supports_set_expression)
+                {
+                    let task = session.set_expression(expression, value, frame_id, cx);
+                    Some(cx.background_spawn(async move { task.await.is_some() }))
+                } else {
+                    None
+                }
             });
+            let Some(set_value) = set_value else {
+                return;
+            };
+            // Watches may depend on the value that was just set, or be the edited entry itself.
+            cx.spawn(async move |this, cx| {
+                if set_value.await {
+                    this.update(cx, |this, cx| this.evaluate_watches(cx)).ok();
+                }
+            })
+            .detach();
         }
     }
 
@@ -467,41 +1332,354 @@ impl VariableList {
         }
     }
 
-    fn expand_selected_entry(
+    fn expand_selected_entry(
+        &mut self,
+        _: &ExpandSelectedEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(selected_entry) = &self.selection {
+            let Some(entry_state) = self.entry_states.get_mut(selected_entry) else {
+                debug_panic!("Trying to toggle variable in variable list that has an no state");
+                return;
+            };
+
+            if entry_state.is_expanded || !entry_state.has_children {
+                self.select_next(&SelectNext, window, cx);
+            } else {
+                entry_state.is_expanded = true;
+                self.build_entries(cx);
+            }
+        }
+    }
+
+    fn is_recursively_expanded(&self, path: &EntryPath) -> bool {
+        self.recursively_expanded.iter().any(|root| {
+            root.pinned == path.pinned
+                && path.indices.starts_with(&root.indices)
+                && path.indices.len() - root.indices.len() <= MAX_RECURSIVE_EXPAND_DEPTH
+        })
+    }
+
+    fn expand_selected_entry_recursively(
+        &mut self,
+        _: &ExpandSelectedEntryRecursively,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.clone() else {
+            return;
+        };
+        self.recursively_expanded.insert(selection);
+        // Descendants that haven't loaded yet are expanded by `build_entries` once they arrive.
+        let paths = self
+            .entry_states
+            .iter()
+            .filter(|(path, state)| state.has_children && self.is_recursively_expanded(path))
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+        for path in paths {
+            if let Some(state) = self.entry_states.get_mut(&path) {
+                state.is_expanded = true;
+            }
+        }
+        self.build_entries(cx);
+    }
+
+    fn selected_index(&self) -> Option<(usize, usize)> {
+        let selection = self.selection.as_ref()?;
+        let ix = self
+            .entries
+            .iter()
+            .position(|entry| &entry.path == selection)?;
+        let depth = self.entry_states.get(selection)?.depth;
+        Some((ix, depth))
+    }
+
+    fn entry_depth(&self, ix: usize) -> Option<usize> {
+        let entry = self.entries.get(ix)?;
+        Some(self.entry_states.get(&entry.path)?.depth)
+    }
+
+    fn select_parent_entry(
+        &mut self,
+        _: &SelectParentEntry,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((ix, depth)) = self.selected_index() else {
+            return;
+        };
+        let parent = (0..ix)
+            .rev()
+            .find(|ix| self.entry_depth(*ix).is_some_and(|parent| parent < depth));
+        if let Some(parent) = parent {
+            let path = self.entries[parent].path.clone();
+            self.select_path(path, cx);
+        }
+    }
+
+    fn select_first_sibling(
+        &mut self,
+        _: &SelectFirstSibling,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((ix, depth)) = self.selected_index() else {
+            return;
+        };
+        let mut first_sibling = ix;
+        for ix in (0..ix).rev() {
+            match self.entry_depth(ix) {
+                Some(sibling_depth) if sibling_depth == depth => first_sibling = ix,
+                Some(descendant_depth) if descendant_depth > depth => {}
+                _ => break,
+            }
+        }
+        let path = self.entries[first_sibling].path.clone();
+        self.select_path(path, cx);
+    }
+
+    fn select_last_sibling(
+        &mut self,
+        _: &SelectLastSibling,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((ix, depth)) = self.selected_index() else {
+            return;
+        };
+        let mut last_sibling = ix;
+        for ix in ix + 1..self.entries.len() {
+            match self.entry_depth(ix) {
+                Some(sibling_depth) if sibling_depth == depth => last_sibling = ix,
+                Some(descendant_depth) if descendant_depth > depth => {}
+                _ => break,
+            }
+        }
+        let path = self.entries[last_sibling].path.clone();
+        self.select_path(path, cx);
+    }
+
+    fn set_session_format(&mut self, format: NumericFormat, cx: &mut Context<Self>) {
+        self.session_format = format;
+        self.session.update(cx, |session, cx| {
+            session.set_hex_values(format == NumericFormat::Hex, cx)
+        });
+        cx.notify();
+    }
+
+    fn set_variable_format(
+        &mut self,
+        path: EntryPath,
+        format: NumericFormat,
+        cx: &mut Context<Self>,
+    ) {
+        if format == self.session_format {
+            self.variable_formats.remove(&path);
+        } else {
+            self.variable_formats.insert(path, format);
+        }
+        cx.notify();
+    }
+
+    fn visualize(
+        &self,
+        visualizers: &[Arc<dyn DebugVisualizer>],
+        adapter: &DebugAdapterName,
+        variable: &dap::Variable,
+        cx: &mut Context<Self>,
+    ) -> Option<String> {
+        let visualizer = visualizers
+            .iter()
+            .find(|visualizer| visualizer.matches(adapter, variable))?;
+        let is_paged = variable
+            .indexed_variables
+            .is_some_and(|count| count > VARIABLES_PAGE_SIZE);
+        let children = if variable.variables_reference != 0 {
+            self.session.update(cx, |session, cx| {
+                if is_paged {
+                    session.variables_page(variable.variables_reference, None, cx)
+                } else {
+                    session.variables(variable.variables_reference, cx)
+                }
+            })
+        } else {
+            Vec::new()
+        };
+        visualizer.visualize(variable, &children)
+    }
+
+    fn display_value(&self, entry: &ListEntry, value: &str) -> String {
+        if let Some(visualized) = self.visualized_values.get(&entry.path) {
+            return visualized.clone();
+        }
+        let format = match self.variable_formats.get(&entry.path) {
+            Some(format) => *format,
+            None if self.session_format != NumericFormat::Decimal => self.session_format,
+            None => return value.to_string(),
+        };
+        format.format(value).unwrap_or_else(|| value.to_string())
+    }
+
+    fn deploy_variable_context_menu(
         &mut self,
-        _: &ExpandSelectedEntry,
+        variable: ListEntry,
+        position: Point<Pixels>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        if let Some(selected_entry) = &self.selection {
-            let Some(entry_state) = self.entry_states.get_mut(selected_entry) else {
-                debug_panic!("Trying to toggle variable in variable list that has an no state");
-                return;
-            };
-
-            if entry_state.is_expanded || !entry_state.has_children {
-                self.select_next(&SelectNext, window, cx);
-            } else {
-                entry_state.is_expanded = true;
-                self.build_entries(cx);
-            }
-        }
+        let this = cx.weak_entity();
+        let editable_memory = variable
+            .as_variable()
+            .filter(|_| self.session.read(cx).supports_write_memory())
+            .and_then(|variable| {
+                let memory_reference = variable.memory_reference.clone()?;
+                Some((SharedString::from(variable.name.clone()), memory_reference))
+            });
+        let session_format = self.session_format;
+        let variable_format = self
+            .variable_formats
+            .get(&variable.path)
+            .copied()
+            .unwrap_or(session_format);
+        let is_long_string = variable.as_variable().is_some_and(is_long_string);
+        let is_collection = variable
+            .as_variable()
+            .is_some_and(|variable| variable.variables_reference != 0);
+        let follow_pointer_label = variable
+            .as_variable()
+            .filter(|variable| is_pointer(variable))
+            .map(|_| {
+                if self.followed_pointers.contains_key(&variable.path) {
+                    "Unfollow Pointer"
+                } else {
+                    "Follow Pointer"
+                }
+            });
+        let is_watch = is_watch_path(&variable.path) && variable.path.indices.len() == 2;
+        let has_memory_reference = variable
+            .as_variable()
+            .is_some_and(|variable| variable.memory_reference.is_some());
+        let image_source = variable.as_variable().and_then(|variable| {
+            let source = ImageSource::for_variable(variable)?;
+            Some((SharedString::from(variable.name.clone()), source))
+        });
+        let pin_label = if self.is_pinned(&variable.path) {
+            "Unpin Variable"
+        } else {
+            "Pin Variable"
+        };
+        let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
+            let menu = menu
+                .action("Copy Name", CopyVariableName.boxed_clone())
+                .action("Copy Value", CopyVariableValue.boxed_clone())
+                .action("Copy as Expression", CopyVariableExpression.boxed_clone())
+                .when(has_memory_reference, |menu| {
+                    menu.action("Copy as Hex Dump", CopyAsHexDump.boxed_clone())
+                })
+                .action("Edit Value", EditVariable.boxed_clone())
+                .when_some(editable_memory, |menu, (name, memory_reference)| {
+                    let this = this.clone();
+                    menu.entry("Edit Memory…", None, move |window, cx| {
+                        this.update(cx, |this, cx| {
+                            this.open_memory_editor(
+                                name.clone(),
+                                memory_reference.clone(),
+                                position,
+                                window,
+                                cx,
+                            )
+                        })
+                        .ok();
+                    })
+                })
+                .action(pin_label, TogglePinnedVariable.boxed_clone())
+                .when(is_watch, |menu| {
+                    menu.action("Remove Watch", RemoveWatch.boxed_clone())
+                })
+                .when(is_long_string, |menu| {
+                    menu.action("View Full Text", ViewFullText.boxed_clone())
+                })
+                .when_some(follow_pointer_label, |menu, label| {
+                    menu.action(label, FollowPointer.boxed_clone())
+                })
+                .when(is_collection, |menu| {
+                    menu.action("View as Table", ViewVariableAsTable.boxed_clone())
+                })
+                .when_some(image_source, |menu, (name, source)| {
+                    let this = this.clone();
+                    menu.entry("View as Image…", None, move |window, cx| {
+                        this.update(cx, |this, cx| {
+                            this.open_image_visualizer(
+                                name.clone(),
+                                source.clone(),
+                                position,
+                                window,
+                                cx,
+                            )
+                        })
+                        .ok();
+                    })
+                })
+                .separator()
+                .header("Format Value As");
+            let menu = NumericFormat::ALL.into_iter().fold(menu, |menu, format| {
+                let this = this.clone();
+                let path = variable.path.clone();
+                menu.toggleable_entry(
+                    format.label(),
+                    variable_format == format,
+                    IconPosition::Start,
+                    None,
+                    move |_, cx| {
+                        this.update(cx, |this, cx| {
+                            this.set_variable_format(path.clone(), format, cx)
+                        })
+                        .ok();
+                    },
+                )
+            });
+            let menu = menu.separator().header("Format All Values As");
+            NumericFormat::ALL
+                .into_iter()
+                .fold(menu, |menu, format| {
+                    let this = this.clone();
+                    menu.toggleable_entry(
+                        format.label(),
+                        session_format == format,
+                        IconPosition::Start,
+                        None,
+                        move |_, cx| {
+                            this.update(cx, |this, cx| this.set_session_format(format, cx)).ok();
+                        },
+                    )
+                })
+                .context(self.focus_handle.clone())
+        });
+        self.show_context_menu(context_menu, position, window, cx);
     }
 
-    fn deploy_variable_context_menu(
+    fn deploy_scope_context_menu(
         &mut self,
-        _variable: ListEntry,
         position: Point<Pixels>,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
-            menu.action("Copy Name", CopyVariableName.boxed_clone())
-                .action("Copy Value", CopyVariableValue.boxed_clone())
-                .action("Edit Value", EditVariable.boxed_clone())
+            menu.action("Copy Scope as JSON", CopyScopeAsJson.boxed_clone())
                 .context(self.focus_handle.clone())
         });
+        self.show_context_menu(context_menu, position, window, cx);
+    }
 
+    fn show_context_menu(
+        &mut self,
+        context_menu: Entity<ContextMenu>,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         cx.focus_view(&context_menu, window);
         let subscription = cx.subscribe_in(
             &context_menu,
@@ -520,6 +1698,75 @@ impl VariableList {
         self.open_context_menu = Some((context_menu, position, subscription));
     }
 
+    pub(crate) fn open_image_visualizer(
+        &mut self,
+        name: SharedString,
+        source: ImageSource,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let session = self.session.clone();
+        let visualizer = cx.new(|cx| ImageVisualizer::new(session, name, source, window, cx));
+        visualizer.update(cx, |visualizer, cx| visualizer.focus_width_editor(window, cx));
+        let subscription = cx.subscribe_in(
+            &visualizer,
+            window,
+            |this, _, _: &DismissEvent, window, cx| {
+                if this.image_visualizer.as_ref().is_some_and(|visualizer| {
+                    visualizer.0.focus_handle(cx).contains_focused(window, cx)
+                }) {
+                    cx.focus_self(window);
+                }
+                this.image_visualizer.take();
+                cx.notify();
+            },
+        );
+
+        self.image_visualizer = Some((visualizer, position, subscription));
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn image_visualizer(&self) -> Option<&Entity<ImageVisualizer>> {
+        self.image_visualizer
+            .as_ref()
+            .map(|(visualizer, _, _)| visualizer)
+    }
+
+    pub(crate) fn open_memory_editor(
+        &mut self,
+        name: SharedString,
+        memory_reference: String,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let session = self.session.clone();
+        let editor = cx.new(|cx| MemoryEditor::new(session, name, memory_reference, window, cx));
+        editor.update(cx, |editor, cx| editor.focus_byte_editor(window, cx));
+        let subscription =
+            cx.subscribe_in(&editor, window, |this, _, _: &DismissEvent, window, cx| {
+                if this
+                    .memory_editor
+                    .as_ref()
+                    .is_some_and(|editor| editor.0.focus_handle(cx).contains_focused(window, cx))
+                {
+                    cx.focus_self(window);
+                }
+                this.memory_editor.take();
+                cx.notify();
+            });
+
+        self.memory_editor = Some((editor, position, subscription));
+        cx.notify();
+    }
+
+    #[cfg(test)]
+    pub(crate) fn memory_editor(&self) -> Option<&Entity<MemoryEditor>> {
+        self.memory_editor.as_ref().map(|(editor, _, _)| editor)
+    }
+
     fn copy_variable_name(
         &mut self,
         _: &CopyVariableName,
@@ -556,6 +1803,232 @@ impl VariableList {
         cx.write_to_clipboard(ClipboardItem::new_string(variable.value.clone()));
     }
 
+    fn copy_as_hex_dump(
+        &mut self,
+        _: &CopyAsHexDump,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+        let Some(entry) = self.entries.iter().find(|entry| &entry.path == selection) else {
+            return;
+        };
+        let Some(memory_reference) = entry
+            .as_variable()
+            .and_then(|variable| variable.memory_reference.clone())
+        else {
+            return;
+        };
+        // Memory references are opaque, but most adapters use the address they point to.
+        let start_address = memory_reference
+            .strip_prefix("0x")
+            .and_then(|address| u64::from_str_radix(address, 16).ok())
+            .unwrap_or_default();
+        let session = self.session.read(cx);
+        let bytes = session.read_memory(memory_reference.clone(), 0, HEX_DUMP_BYTE_COUNT);
+        cx.spawn(async move |_, cx| {
+            let bytes = bytes.await?;
+            anyhow::ensure!(
+                !bytes.is_empty(),
+                "no readable memory at {memory_reference}"
+            );
+            cx.update(|cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(hex_dump(start_address, &bytes)))
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn copy_scope_as_json(
+        &mut self,
+        _: &CopyScopeAsJson,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+        let Some(scope) = self
+            .entries
+            .iter()
+            .find(|entry| &entry.path == selection)
+            .and_then(|entry| entry.as_scope())
+            .filter(|scope| scope.variables_reference != 0)
+        else {
+            return;
+        };
+        let json = variables_to_json(
+            self.session.clone(),
+            scope.variables_reference,
+            scope.indexed_variables,
+            DebuggerSettings::get_global(cx).copy_as_json_depth,
+            cx.to_async(),
+        );
+        cx.spawn(async move |_, cx| {
+            let json = serde_json::to_string_pretty(&json.await?)?;
+            cx.update(|cx| cx.write_to_clipboard(ClipboardItem::new_string(json)))
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn copy_variable_expression(
+        &mut self,
+        _: &CopyVariableExpression,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+        let Some(entry) = self.entries.iter().find(|entry| &entry.path == selection) else {
+            return;
+        };
+        let Some(variable) = entry.as_variable() else {
+            return;
+        };
+        let expression = variable
+            .evaluate_name
+            .clone()
+            .unwrap_or_else(|| entry.path.to_expression());
+        cx.write_to_clipboard(ClipboardItem::new_string(expression));
+    }
+
+    fn toggle_pinned_variable(
+        &mut self,
+        _: &TogglePinnedVariable,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.clone() else {
+            return;
+        };
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.path == selection && entry.as_variable().is_some())
+        {
+            self.toggle_pinned(&selection, cx);
+        }
+    }
+
+    fn view_variable_as_table(
+        &mut self,
+        _: &ViewVariableAsTable,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+        let Some(entry) = self.entries.iter().find(|entry| &entry.path == selection) else {
+            return;
+        };
+        let Some(variable) = entry.as_variable() else {
+            return;
+        };
+        if variable.variables_reference != 0 {
+            cx.emit(VariableListEvent::ViewAsTable(variable.clone()));
+        }
+    }
+
+    fn view_full_text(&mut self, _: &ViewFullText, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+        let Some(entry) = self.entries.iter().find(|entry| &entry.path == selection) else {
+            return;
+        };
+        let Some(variable) = entry.as_variable().filter(|variable| is_long_string(variable)) else {
+            return;
+        };
+
+        // Adapters may shorten long strings in `variables` responses, but return them in full
+        // when evaluated for the clipboard.
+        let session = self.session.read(cx);
+        let full_value = variable
+            .evaluate_name
+            .clone()
+            .filter(|_| {
+                session
+                    .capabilities()
+                    .supports_clipboard_context
+                    .unwrap_or_default()
+            })
+            .map(|expression| {
+                session.evaluate_silently(
+                    expression,
+                    Some(EvaluateArgumentsContext::Clipboard),
+                    self.selected_stack_frame_id,
+                )
+            });
+        let title = SharedString::from(variable.name.clone());
+        let value = variable.value.clone();
+
+        cx.spawn(async move |this, cx| {
+            let value = match full_value {
+                Some(task) => task
+                    .await
+                    .log_err()
+                    .map(|response| response.result)
+                    .unwrap_or(value),
+                None => value,
+            };
+            let (text, is_json) = full_text(&value);
+            this.update(cx, |_, cx| {
+                cx.emit(VariableListEvent::ViewFullText {
+                    title,
+                    text,
+                    is_json,
+                })
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Shows the value `variable` points to below it, or hides it again if it is already shown.
+    fn follow_pointer(&mut self, _: &FollowPointer, _window: &mut Window, cx: &mut Context<Self>) {
+        let Some(selection) = self.selection.clone() else {
+            return;
+        };
+        if self.followed_pointers.remove(&selection).is_some() {
+            self.build_entries(cx);
+            return;
+        }
+        let Some(entry) = self.entries.iter().find(|entry| entry.path == selection) else {
+            return;
+        };
+        let Some(variable) = entry.as_variable().filter(|variable| is_pointer(variable)) else {
+            return;
+        };
+
+        let pointer = variable
+            .evaluate_name
+            .clone()
+            .unwrap_or_else(|| entry.path.to_expression());
+        let expression = format!("*{pointer}");
+        let pointee = self.session.read(cx).evaluate_silently(
+            expression.clone(),
+            Some(EvaluateArgumentsContext::Watch),
+            self.selected_stack_frame_id,
+        );
+
+        cx.spawn(async move |this, cx| {
+            let pointee = evaluated_variable(expression, pointee.await);
+            this.update(cx, |this, cx| {
+                this.followed_pointers.insert(selection.clone(), pointee);
+                if let Some(state) = this.entry_states.get_mut(&selection) {
+                    state.is_expanded = true;
+                }
+                this.build_entries(cx);
+            })
+            .ok();
+        })
+        .detach();
+    }
+
     fn edit_variable(&mut self, _: &EditVariable, window: &mut Window, cx: &mut Context<Self>) {
         let Some(selection) = self.selection.as_ref() else {
             return;
@@ -566,6 +2039,9 @@ impl VariableList {
         let Some(variable) = entry.as_variable() else {
             return;
         };
+        if !self.can_edit_variable(variable, cx) {
+            return;
+        }
 
         let editor = Self::create_variable_editor(&variable.value, window, cx);
         self.edited_path = Some((entry.path.clone(), editor));
@@ -573,6 +2049,51 @@ impl VariableList {
         cx.notify();
     }
 
+    /// The values of the loaded variables outside of the pinned section, which only repeats them.
+    pub(crate) fn snapshot_values(&self) -> SnapshotValues {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.path.pinned)
+            .filter_map(|entry| {
+                let variable = entry
+                    .as_variable()
+                    .filter(|variable| !is_page_name(&variable.name))?;
+                let scope = entry.path.indices.first()?;
+                let key = format!("{scope} › {}", entry.path.to_expression());
+                Some((key.into(), variable.value.clone().into()))
+            })
+            .collect()
+    }
+
+    fn capture_snapshot(
+        &mut self,
+        _: &CaptureSnapshot,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.emit(VariableListEvent::CaptureSnapshot);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn changed_variable_names(&self) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|entry| self.changed_paths.contains(&entry.path))
+            .filter_map(|entry| Some(entry.as_variable()?.name.clone()))
+            .collect()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_filter_query(
+        &mut self,
+        query: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.filter_editor
+            .update(cx, |editor, cx| editor.set_text(query, window, cx));
+    }
+
     #[track_caller]
     #[cfg(test)]
     pub(crate) fn assert_visual_entries(&self, expected: Vec<&str>) {
@@ -586,11 +2107,16 @@ impl VariableList {
                 .get(&entry.path)
                 .expect("If there's a variable entry there has to be a state that goes with it");
 
+            let type_ = entry
+                .as_variable()
+                .and_then(|variable| variable.type_.as_deref())
+                .filter(|type_| self.show_types && !type_.is_empty());
             visual_entries.push(format!(
-                "{}{} {}{}",
+                "{}{} {}{}{}",
                 INDENT.repeat(state.depth - 1),
                 if state.is_expanded { "v" } else { ">" },
                 entry.dap_kind.name(),
+                type_.map(|type_| format!(": {type_}")).unwrap_or_default(),
                 if self.selection.as_ref() == Some(&entry.path) {
                     " <=== selected"
                 } else {
@@ -731,6 +2257,16 @@ impl VariableList {
                         let var_path = entry.path.clone();
                         cx.listener(move |this, _, _, cx| this.toggle_entry(&var_path, cx))
                     })
+                    .when(var_ref != 0, |list_item| {
+                        let path = entry.path.clone();
+                        list_item.on_secondary_mouse_down(cx.listener(
+                            move |this, event: &MouseDownEvent, window, cx| {
+                                this.selection = Some(path.clone());
+                                this.deploy_scope_context_menu(event.position, window, cx);
+                                cx.stop_propagation();
+                            },
+                        ))
+                    })
                     .child(
                         div()
                             .text_ui(cx)
@@ -777,9 +2313,11 @@ impl VariableList {
                 VariablePresentationHintKind::Unknown | _ => syntax_color_for("variable"),
             }
         };
+        let is_changed = self.highlight_changes && self.changed_paths.contains(&variable.path);
         let variable_color = self
             .disabled
             .then(|| Color::Disabled.color(cx))
+            .or_else(|| is_changed.then(|| Color::Modified.color(cx)))
             .or_else(|| syntax_color_for("variable.special"));
 
         let var_ref = dap.variables_reference;
@@ -799,6 +2337,10 @@ impl VariableList {
         } else {
             colors.default
         };
+        let type_ = dap
+            .type_
+            .as_deref()
+            .filter(|type_| self.show_types && !type_.is_empty());
         let path = variable.path.clone();
         div()
             .id(variable.item_id())
@@ -829,13 +2371,7 @@ impl VariableList {
                 .when(var_ref > 0, |list_item| {
                     list_item.toggle(state.is_expanded).on_toggle(cx.listener({
                         let var_path = variable.path.clone();
-                        move |this, _, _, cx| {
-                            this.session.update(cx, |session, cx| {
-                                session.variables(var_ref, cx);
-                            });
-
-                            this.toggle_entry(&var_path, cx);
-                        }
+                        move |this, _, _, cx| this.toggle_entry(&var_path, cx)
                     }))
                 })
                 .on_secondary_mouse_down(cx.listener({
@@ -872,13 +2408,7 @@ impl VariableList {
                                 } else {
                                     this.text_color(cx.theme().colors().text_muted)
                                         .when(
-                                            !self.disabled
-                                                && self
-                                                    .session
-                                                    .read(cx)
-                                                    .capabilities()
-                                                    .supports_set_variable
-                                                    .unwrap_or_default(),
+                                            !self.disabled && self.can_edit_variable(dap, cx),
                                             |this| {
                                                 let path = variable.path.clone();
                                                 let variable_value = dap.value.clone();
@@ -901,7 +2431,10 @@ impl VariableList {
                                             },
                                         )
                                         .child(
-                                            Label::new(format!("=  {}", &dap.value))
+                                            Label::new(format!(
+                                                "=  {}",
+                                                self.display_value(variable, &dap.value)
+                                            ))
                                                 .single_line()
                                                 .truncate()
                                                 .size(LabelSize::Small)
@@ -912,12 +2445,73 @@ impl VariableList {
                                         )
                                 }
                             }))
+                        })
+                        .when_some(type_, |this, type_| {
+                            this.child(
+                                div().flex_none().pr_2().child(
+                                    Label::new(type_.to_string())
+                                        .single_line()
+                                        .size(LabelSize::Small)
+                                        .color(Color::Placeholder),
+                                ),
+                            )
                         }),
                 ),
             )
             .into_any()
     }
 
+    fn render_options_menu(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let this = cx.entity().downgrade();
+        let focus_handle = self.focus_handle.clone();
+        let show_types = self.show_types;
+        let show_changed_only = self.show_changed_only;
+        PopoverMenu::new("variable-list-options")
+            .trigger_with_tooltip(
+                IconButton::new("variable-list-options-button", IconName::Ellipsis)
+                    .icon_size(IconSize::Small),
+                Tooltip::text("Variables Options"),
+            )
+            .anchor(gpui::Corner::TopRight)
+            .menu(move |window, cx| {
+                let this = this.clone();
+                let focus_handle = focus_handle.clone();
+                Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                    menu.context(focus_handle)
+                        .toggleable_entry(
+                            "Show Types",
+                            show_types,
+                            IconPosition::Start,
+                            Some(ToggleTypeColumn.boxed_clone()),
+                            {
+                                let this = this.clone();
+                                move |window, cx| {
+                                    this.update(cx, |this, cx| {
+                                        this.toggle_type_column(&ToggleTypeColumn, window, cx)
+                                    })
+                                    .ok();
+                                }
+                            },
+                        )
+                        .toggleable_entry(
+                            "Show Changed Values Only",
+                            show_changed_only,
+                            IconPosition::Start,
+                            None,
+                            {
+                                let this = this.clone();
+                                move |_, cx| {
+                                    this.update(cx, |this, cx| this.toggle_changed_values_only(cx))
+                                        .ok();
+                                }
+                            },
+                        )
+                        .separator()
+                        .action("Capture Snapshot", CaptureSnapshot.boxed_clone())
+                }))
+            })
+    }
+
     fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
         div()
             .occlude()
@@ -952,6 +2546,8 @@ impl VariableList {
     }
 }
 
+impl EventEmitter<VariableListEvent> for VariableList {}
+
 impl Focusable for VariableList {
     fn focus_handle(&self, _: &App) -> gpui::FocusHandle {
         self.focus_handle.clone()
@@ -975,9 +2571,42 @@ impl Render for VariableList {
             .on_action(cx.listener(Self::confirm))
             .on_action(cx.listener(Self::expand_selected_entry))
             .on_action(cx.listener(Self::collapse_selected_entry))
+            .on_action(cx.listener(Self::expand_selected_entry_recursively))
+            .on_action(cx.listener(Self::select_parent_entry))
+            .on_action(cx.listener(Self::select_first_sibling))
+            .on_action(cx.listener(Self::select_last_sibling))
             .on_action(cx.listener(Self::copy_variable_name))
             .on_action(cx.listener(Self::copy_variable_value))
+            .on_action(cx.listener(Self::copy_variable_expression))
+            .on_action(cx.listener(Self::copy_as_hex_dump))
+            .on_action(cx.listener(Self::copy_scope_as_json))
             .on_action(cx.listener(Self::edit_variable))
+            .on_action(cx.listener(Self::toggle_pinned_variable))
+            .on_action(cx.listener(Self::view_variable_as_table))
+            .on_action(cx.listener(Self::view_full_text))
+            .on_action(cx.listener(Self::follow_pointer))
+            .on_action(cx.listener(Self::remove_watch))
+            .on_action(cx.listener(Self::toggle_type_column))
+            .on_action(cx.listener(Self::capture_snapshot))
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .gap_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(div().flex_1().child(self.filter_editor.clone()))
+                    .child(
+                        IconButton::new("variable-list-changed-only", IconName::Diff)
+                            .icon_size(IconSize::Small)
+                            .toggle_state(self.show_changed_only)
+                            .tooltip(Tooltip::text("Show Changed Values Only"))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.toggle_changed_values_only(cx)
+                            })),
+                    )
+                    .child(self.render_options_menu(cx)),
+            )
             .child(
                 uniform_list(
                     "variable-list",
@@ -1000,10 +2629,58 @@ impl Render for VariableList {
                 )
                 .with_priority(1)
             }))
+            .children(
+                self.image_visualizer
+                    .as_ref()
+                    .map(|(visualizer, position, _)| {
+                        deferred(
+                            anchored()
+                                .position(*position)
+                                .anchor(gpui::Corner::TopLeft)
+                                .snap_to_window_with_margin(px(8.))
+                                .child(visualizer.clone()),
+                        )
+                        .with_priority(1)
+                    }),
+            )
+            .children(self.memory_editor.as_ref().map(|(editor, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Corner::TopLeft)
+                        .snap_to_window_with_margin(px(8.))
+                        .child(editor.clone()),
+                )
+                .with_priority(1)
+            }))
             .child(self.render_vertical_scrollbar(cx))
     }
 }
 
+fn section_scope(name: &str) -> dap::Scope {
+    dap::Scope {
+        name: name.into(),
+        presentation_hint: None,
+        variables_reference: 0,
+        named_variables: None,
+        indexed_variables: None,
+        expensive: false,
+        source: None,
+        line: None,
+        column: None,
+        end_line: None,
+        end_column: None,
+    }
+}
+
+fn is_watch_path(path: &EntryPath) -> bool {
+    !path.pinned
+        && path
+            .indices
+            .first()
+            .is_some_and(|name| name == WATCHES_SECTION_NAME)
+}
+
 struct EntryColors {
     default: Hsla,
     hover: Hsla,
@@ -0,0 +1,363 @@
+use super::variable_list::VariableList;
+use collections::IndexMap;
+use editor::Editor;
+use gpui::{AnyElement, Entity, FocusHandle, Focusable, Subscription, uniform_list};
+use settings::Settings as _;
+use std::ops::Range;
+use theme::ThemeSettings;
+use ui::{ContextMenu, DropdownMenu, Tooltip, prelude::*};
+
+/// Values of the loaded variables, keyed by the scope and expression they were found under, e.g.
+/// `Locals › point.x`.
+pub(crate) type SnapshotValues = IndexMap<SharedString, SharedString>;
+
+pub(crate) struct VariableSnapshot {
+    name: SharedString,
+    values: SnapshotValues,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SnapshotChange {
+    Added,
+    Removed,
+    Changed { old_value: SharedString },
+    Unchanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SnapshotDiffEntry {
+    pub(crate) key: SharedString,
+    /// The newer value, or the last known one for removed entries.
+    pub(crate) value: SharedString,
+    pub(crate) change: SnapshotChange,
+}
+
+/// Compares `new` against `old`, listing entries in the order of `new` followed by the ones that
+/// only `old` has.
+pub(crate) fn diff_snapshots(old: &SnapshotValues, new: &SnapshotValues) -> Vec<SnapshotDiffEntry> {
+    let mut diff = new
+        .iter()
+        .map(|(key, value)| SnapshotDiffEntry {
+            key: key.clone(),
+            value: value.clone(),
+            change: match old.get(key) {
+                None => SnapshotChange::Added,
+                Some(old_value) if old_value != value => SnapshotChange::Changed {
+                    old_value: old_value.clone(),
+                },
+                Some(_) => SnapshotChange::Unchanged,
+            },
+        })
+        .collect::<Vec<_>>();
+    diff.extend(
+        old.iter()
+            .filter(|(key, _)| !new.contains_key(*key))
+            .map(|(key, value)| SnapshotDiffEntry {
+                key: key.clone(),
+                value: value.clone(),
+                change: SnapshotChange::Removed,
+            }),
+    );
+    diff
+}
+
+/// Named captures of the variables loaded in the Variables pane, which can be compared with each
+/// other or with the current values, e.g. to see what changed between two loop iterations.
+pub(crate) struct VariableSnapshots {
+    variable_list: Entity<VariableList>,
+    focus_handle: FocusHandle,
+    name_editor: Entity<Editor>,
+    snapshots: Vec<VariableSnapshot>,
+    base: Option<usize>,
+    /// The snapshot compared with `base`, or `None` to compare with the current values.
+    target: Option<usize>,
+    diff: Vec<SnapshotDiffEntry>,
+    show_unchanged: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl VariableSnapshots {
+    pub(crate) fn new(
+        variable_list: Entity<VariableList>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Snapshot name", cx);
+            editor
+        });
+        let _subscriptions = vec![cx.observe(&variable_list, |this, _, cx| {
+            if this.target.is_none() {
+                this.update_diff(cx);
+            }
+        })];
+
+        Self {
+            variable_list,
+            focus_handle: cx.focus_handle(),
+            name_editor,
+            snapshots: Vec::new(),
+            base: None,
+            target: None,
+            diff: Vec::new(),
+            show_unchanged: false,
+            _subscriptions,
+        }
+    }
+
+    /// Captures the values currently loaded in the Variables pane, and compares the current values
+    /// with them from then on.
+    pub(crate) fn capture(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        let name = if name.is_empty() {
+            format!("Snapshot {}", self.snapshots.len() + 1)
+        } else {
+            name
+        };
+        self.name_editor
+            .update(cx, |editor, cx| editor.set_text("", window, cx));
+
+        let values = self.variable_list.read(cx).snapshot_values();
+        self.snapshots.push(VariableSnapshot {
+            name: name.into(),
+            values,
+        });
+        self.base = Some(self.snapshots.len() - 1);
+        self.target = None;
+        self.update_diff(cx);
+    }
+
+    fn set_base(&mut self, base: usize, cx: &mut Context<Self>) {
+        self.base = Some(base);
+        self.update_diff(cx);
+    }
+
+    fn set_target(&mut self, target: Option<usize>, cx: &mut Context<Self>) {
+        self.target = target;
+        self.update_diff(cx);
+    }
+
+    fn toggle_unchanged(&mut self, cx: &mut Context<Self>) {
+        self.show_unchanged = !self.show_unchanged;
+        cx.notify();
+    }
+
+    fn update_diff(&mut self, cx: &mut Context<Self>) {
+        let Some(base) = self.base.and_then(|base| self.snapshots.get(base)) else {
+            self.diff.clear();
+            cx.notify();
+            return;
+        };
+        self.diff = match self.target.and_then(|target| self.snapshots.get(target)) {
+            Some(target) => diff_snapshots(&base.values, &target.values),
+            None => diff_snapshots(&base.values, &self.variable_list.read(cx).snapshot_values()),
+        };
+        cx.notify();
+    }
+
+    fn visible_diff(&self) -> impl Iterator<Item = &SnapshotDiffEntry> {
+        self.diff
+            .iter()
+            .filter(|entry| self.show_unchanged || entry.change != SnapshotChange::Unchanged)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn visual_diff(&self) -> Vec<String> {
+        self.visible_diff()
+            .map(|entry| match &entry.change {
+                SnapshotChange::Added => format!("+ {} = {}", entry.key, entry.value),
+                SnapshotChange::Removed => format!("- {} = {}", entry.key, entry.value),
+                SnapshotChange::Changed { old_value } => {
+                    format!("~ {} = {old_value} → {}", entry.key, entry.value)
+                }
+                SnapshotChange::Unchanged => format!("  {} = {}", entry.key, entry.value),
+            })
+            .collect()
+    }
+
+    fn snapshot_name(&self, ix: Option<usize>) -> SharedString {
+        match ix.and_then(|ix| self.snapshots.get(ix)) {
+            Some(snapshot) => snapshot.name.clone(),
+            None => "Current Values".into(),
+        }
+    }
+
+    fn render_snapshot_picker(
+        &self,
+        id: &'static str,
+        selected: Option<usize>,
+        include_current: bool,
+        on_select: impl Fn(&mut Self, Option<usize>, &mut Context<Self>) + Clone + 'static,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let this = cx.entity().downgrade();
+        let names = self
+            .snapshots
+            .iter()
+            .enumerate()
+            .map(|(ix, snapshot)| (Some(ix), snapshot.name.clone()))
+            .chain(include_current.then(|| (None, self.snapshot_name(None))))
+            .collect::<Vec<_>>();
+        let menu = ContextMenu::build(window, cx, move |menu, _, _| {
+            names.into_iter().fold(menu, |menu, (ix, name)| {
+                let this = this.clone();
+                let on_select = on_select.clone();
+                menu.toggleable_entry(
+                    name,
+                    ix == selected,
+                    IconPosition::Start,
+                    None,
+                    move |_, cx| {
+                        this.update(cx, |this, cx| on_select(this, ix, cx)).ok();
+                    },
+                )
+            })
+        });
+        DropdownMenu::new(id, self.snapshot_name(selected), menu)
+    }
+
+    fn render_entry(&self, entry: &SnapshotDiffEntry, cx: &mut Context<Self>) -> AnyElement {
+        let (marker, color) = match entry.change {
+            SnapshotChange::Added => ("+", Color::Created),
+            SnapshotChange::Removed => ("−", Color::Deleted),
+            SnapshotChange::Changed { .. } => ("~", Color::Modified),
+            SnapshotChange::Unchanged => (" ", Color::Muted),
+        };
+        let value = match &entry.change {
+            SnapshotChange::Changed { old_value } => format!("{old_value} → {}", entry.value),
+            _ => entry.value.to_string(),
+        };
+
+        h_flex()
+            .id(entry.key.clone())
+            .w_full()
+            .px_1()
+            .gap_2()
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .font_family(ThemeSettings::get_global(cx).buffer_font.family.clone())
+            .child(Label::new(marker).size(LabelSize::Small).color(color))
+            .child(
+                div().w(relative(0.4)).overflow_hidden().child(
+                    Label::new(entry.key.clone())
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .single_line(),
+                ),
+            )
+            .child(
+                div().flex_1().overflow_hidden().child(
+                    Label::new(value)
+                        .size(LabelSize::Small)
+                        .color(color)
+                        .single_line(),
+                ),
+            )
+            .into_any_element()
+    }
+}
+
+impl Focusable for VariableSnapshots {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for VariableSnapshots {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let capture_bar = h_flex()
+            .w_full()
+            .gap_2()
+            .pb_1()
+            .child(
+                div()
+                    .flex_1()
+                    .px_1()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .rounded_sm()
+                    .child(self.name_editor.clone()),
+            )
+            .child(
+                Button::new("capture-variable-snapshot", "Capture")
+                    .label_size(LabelSize::Small)
+                    .tooltip(Tooltip::text(
+                        "Capture the variables loaded in the Variables pane",
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| this.capture(window, cx))),
+            );
+
+        if self.snapshots.is_empty() {
+            return v_flex()
+                .track_focus(&self.focus_handle)
+                .size_full()
+                .p_1()
+                .child(capture_bar)
+                .child(
+                    Label::new("Capture a snapshot to compare variables across stops")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                );
+        }
+
+        let entries = self.visible_diff().cloned().collect::<Vec<_>>();
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_1()
+            .child(capture_bar)
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .pb_1()
+                    .child(self.render_snapshot_picker(
+                        "variable-snapshot-base",
+                        self.base,
+                        false,
+                        |this, base, cx| {
+                            if let Some(base) = base {
+                                this.set_base(base, cx)
+                            }
+                        },
+                        window,
+                        cx,
+                    ))
+                    .child(
+                        Label::new("with")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(self.render_snapshot_picker(
+                        "variable-snapshot-target",
+                        self.target,
+                        true,
+                        |this, target, cx| this.set_target(target, cx),
+                        window,
+                        cx,
+                    ))
+                    .child(div().flex_1())
+                    .child(
+                        Button::new("toggle-unchanged-snapshot-values", "Unchanged")
+                            .label_size(LabelSize::Small)
+                            .toggle_state(self.show_unchanged)
+                            .tooltip(Tooltip::text("Show values that didn't change"))
+                            .on_click(cx.listener(|this, _, _, cx| this.toggle_unchanged(cx))),
+                    ),
+            )
+            .child(
+                uniform_list(
+                    "variable-snapshot-diff",
+                    entries.len(),
+                    cx.processor(move |this, range: Range<usize>, _window, cx| {
+                        entries[range]
+                            .iter()
+                            .map(|entry| this.render_entry(entry, cx))
+                            .collect()
+                    }),
+                )
+                .size_full(),
+            )
+    }
+}
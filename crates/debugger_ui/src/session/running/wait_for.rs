@@ -0,0 +1,114 @@
+use std::{
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::Result;
+use gpui::{BackgroundExecutor, Task};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use task::{WaitFor, WaitForTarget};
+
+/// How long to wait when a scenario's `wait_for` doesn't set a timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// How often to check whether the target is ready.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What is being waited for, as shown in the session's console.
+pub(crate) fn describe(target: &WaitForTarget) -> String {
+    match target {
+        WaitForTarget::Port(port) => format!("port {port}"),
+        WaitForTarget::Process(name) => format!("process `{name}`"),
+        WaitForTarget::PidFile(path) => format!("pid file {}", path.display()),
+    }
+}
+
+/// Polls until `wait_for`'s target is ready, returning the id of the process it names, if any.
+/// Polling runs on the background executor, since refreshing the process list is slow.
+///
+/// A relative pid file path is resolved against `cwd`.
+pub(crate) fn wait_for(
+    wait_for: &WaitFor,
+    cwd: Option<&Path>,
+    executor: BackgroundExecutor,
+) -> Task<Result<Option<u32>>> {
+    let timeout = wait_for
+        .timeout
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let target = match &wait_for.target {
+        WaitForTarget::PidFile(path) if path.is_relative() => WaitForTarget::PidFile(
+            cwd.map(|cwd| cwd.join(path))
+                .unwrap_or_else(|| PathBuf::from(path)),
+        ),
+        target => target.clone(),
+    };
+
+    executor.clone().spawn(async move {
+        let mut system = System::new();
+        let mut waited = Duration::ZERO;
+        loop {
+            if let Some(pid) = poll(&target, &mut system).await {
+                return Ok(pid);
+            }
+            anyhow::ensure!(
+                waited < timeout,
+                "timed out after {}ms waiting for {}",
+                timeout.as_millis(),
+                describe(&target)
+            );
+            executor.timer(POLL_INTERVAL).await;
+            waited += POLL_INTERVAL;
+        }
+    })
+}
+
+/// `Some` once `target` is ready, holding the id of the process it names, if any.
+async fn poll(target: &WaitForTarget, system: &mut System) -> Option<Option<u32>> {
+    match target {
+        WaitForTarget::Port(port) => smol::net::TcpStream::connect((Ipv4Addr::LOCALHOST, *port))
+            .await
+            .ok()
+            .map(|_| None),
+        WaitForTarget::Process(name) => {
+            system.refresh_processes_specifics(ProcessesToUpdate::All, ProcessRefreshKind::new());
+            system
+                .processes()
+                .values()
+                .find(|process| process.name().to_string_lossy() == name.as_str())
+                .map(|process| Some(process.pid().as_u32()))
+        }
+        // The file may exist before the program has finished writing its pid to it.
+        WaitForTarget::PidFile(path) => smol::fs::read_to_string(path)
+            .await
+            .ok()?
+            .trim()
+            .parse()
+            .ok()
+            .map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    async fn test_wait_for_times_out(cx: &mut TestAppContext) {
+        let error = wait_for(
+            &WaitFor {
+                target: WaitForTarget::Process("zed-wait-for-missing-process".into()),
+                timeout: Some(0),
+            },
+            None,
+            cx.executor(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "timed out after 0ms waiting for process `zed-wait-for-missing-process`"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use chrono::{DateTime, Local, Utc};
+use dap::client::SessionId;
+use fuzzy::{StringMatch, StringMatchCandidate};
+use gpui::{DismissEvent, Entity, EventEmitter, Focusable, Render, Subscription, WeakEntity};
+use picker::{Picker, PickerDelegate};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use task::DebugScenario;
+use ui::{Context, ListItem, ListItemSpacing, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::debugger_panel::DebugPanel;
+
+/// How many finished and running sessions are remembered per workspace.
+pub(crate) const MAX_SESSION_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SessionHistoryEntry {
+    pub(crate) scenario: DebugScenario,
+    pub(crate) started_at: DateTime<Utc>,
+    pub(crate) ended_at: Option<DateTime<Utc>>,
+    pub(crate) exit_code: Option<i64>,
+    /// The session this entry was recorded for, while it is still running in this window.
+    #[serde(skip)]
+    pub(crate) session_id: Option<SessionId>,
+}
+
+impl SessionHistoryEntry {
+    pub(crate) fn new(scenario: DebugScenario, session_id: SessionId) -> Self {
+        Self {
+            scenario,
+            started_at: Utc::now(),
+            ended_at: None,
+            exit_code: None,
+            session_id: Some(session_id),
+        }
+    }
+
+    fn status_label(&self) -> (SharedString, Color) {
+        match (self.exit_code, self.ended_at) {
+            (Some(0), _) => ("Exited with 0".into(), Color::Success),
+            (Some(code), _) => (format!("Exited with {code}").into(), Color::Error),
+            (None, Some(_)) => ("Ended".into(), Color::Muted),
+            (None, None) if self.session_id.is_some() => ("Running".into(), Color::Accent),
+            (None, None) => ("Unfinished".into(), Color::Muted),
+        }
+    }
+
+    fn details(&self) -> String {
+        let started_at = DateTime::<Local>::from(self.started_at).format("%Y-%m-%d %H:%M:%S");
+        let mut details = format!("{} · {started_at}", self.scenario.adapter);
+        if let Some(ended_at) = self.ended_at {
+            let seconds = (ended_at - self.started_at).num_seconds().max(0);
+            details.push_str(&format!(" · {}m {:02}s", seconds / 60, seconds % 60));
+        }
+        details
+    }
+}
+
+pub(crate) struct SessionHistoryDelegate {
+    debug_panel: WeakEntity<DebugPanel>,
+    /// Newest entries first.
+    entries: Vec<SessionHistoryEntry>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+pub(crate) struct SessionHistoryModal {
+    _subscription: Subscription,
+    pub(crate) picker: Entity<Picker<SessionHistoryDelegate>>,
+}
+
+impl SessionHistoryModal {
+    pub(crate) fn show(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(debug_panel) = workspace.panel::<DebugPanel>(cx) else {
+            return;
+        };
+        let entries = debug_panel.read(cx).session_history().to_vec();
+        let debug_panel = debug_panel.downgrade();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(debug_panel, entries, window, cx)
+        });
+    }
+
+    pub(crate) fn new(
+        debug_panel: WeakEntity<DebugPanel>,
+        entries: Vec<SessionHistoryEntry>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = SessionHistoryDelegate {
+            debug_panel,
+            entries: entries.into_iter().rev().collect(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        Self {
+            _subscription: cx.subscribe(&picker, |_, _, _, cx| {
+                cx.emit(DismissEvent);
+            }),
+            picker,
+        }
+    }
+}
+
+impl Render for SessionHistoryModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("SessionHistoryModal")
+            .track_focus(&self.focus_handle(cx))
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl EventEmitter<DismissEvent> for SessionHistoryModal {}
+
+impl Focusable for SessionHistoryModal {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.read(cx).focus_handle(cx)
+    }
+}
+
+impl ModalView for SessionHistoryModal {}
+
+impl PickerDelegate for SessionHistoryDelegate {
+    type ListItem = ListItem;
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search past debug sessions…".into()
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> gpui::Task<()> {
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| {
+                StringMatchCandidate::new(
+                    id,
+                    &format!("{} {}", entry.scenario.label, entry.scenario.adapter),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn(async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        score: 0.,
+                        positions: Vec::new(),
+                        string: candidate.string,
+                    })
+                    .collect()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    MAX_SESSION_HISTORY,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+            };
+
+            this.update(cx, |this, _| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+            })
+            .ok();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|hit| self.entries.get(hit.candidate_id))
+        else {
+            return cx.emit(DismissEvent);
+        };
+
+        let scenario = entry.scenario.clone();
+        self.debug_panel
+            .update(cx, |debug_panel, cx| {
+                debug_panel.rerun_scenario(scenario, window, cx);
+            })
+            .ok();
+        cx.emit(DismissEvent);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let hit = self.matches.get(ix)?;
+        let entry = self.entries.get(hit.candidate_id)?;
+        let (status, status_color) = entry.status_label();
+
+        Some(
+            ListItem::new(SharedString::from(format!("session-history-entry-{ix}")))
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .end_slot(
+                    Label::new(status)
+                        .size(LabelSize::Small)
+                        .color(status_color),
+                )
+                .child(
+                    v_flex()
+                        .items_start()
+                        .child(Label::new(entry.scenario.label.clone()))
+                        .child(
+                            Label::new(entry.details())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
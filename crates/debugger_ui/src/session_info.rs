@@ -0,0 +1,545 @@
+use dap::{Capabilities, StartDebuggingRequestArgumentsRequest, adapters::DebugAdapterBinary};
+use gpui::{DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render};
+use project::debugger::session::Session;
+use serde_json::Value;
+use settings::Settings as _;
+use theme::ThemeSettings;
+use ui::{Divider, prelude::*};
+use workspace::{ModalView, Workspace};
+
+use crate::debugger_panel::DebugPanel;
+
+/// A titled group of `(name, value)` rows.
+#[derive(Debug, PartialEq)]
+struct InfoSection {
+    title: &'static str,
+    rows: Vec<(SharedString, SharedString)>,
+}
+
+/// A read-only summary of what a session was started with: the program the adapter was asked
+/// to debug, and how the adapter itself was started.
+pub(crate) struct SessionInfoModal {
+    label: SharedString,
+    sections: Vec<InfoSection>,
+    focus_handle: FocusHandle,
+}
+
+impl SessionInfoModal {
+    pub(crate) fn show(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(mut session) = workspace
+            .panel::<DebugPanel>(cx)
+            .and_then(|panel| panel.read(cx).active_session())
+            .map(|session| session.read(cx).session(cx))
+        else {
+            return;
+        };
+        // Child sessions are started by their parent's adapter, which is what the user configured.
+        while let Some(parent) = session.read(cx).parent_session().cloned() {
+            session = parent;
+        }
+
+        workspace.toggle_modal(window, cx, |_, cx| Self::new(&session, cx));
+    }
+
+    fn new(session: &Entity<Session>, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+        let session = session.read(cx);
+        let sections = match session.binary() {
+            Some(binary) => info_sections(binary),
+            None => vec![InfoSection {
+                title: "Debug Adapter",
+                rows: vec![("Name".into(), session.adapter().0.clone())],
+            }],
+        };
+        Self {
+            label: session.label(),
+            sections,
+            focus_handle,
+        }
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+fn render_section(section: &InfoSection, name_width: Rems, cx: &App) -> Div {
+    let buffer_font = ThemeSettings::get_global(cx).buffer_font.family.clone();
+    v_flex()
+        .gap_1()
+        .child(
+            Label::new(section.title)
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+        )
+        .children(section.rows.iter().map(|(name, value)| {
+            h_flex()
+                .gap_2()
+                .items_start()
+                .child(
+                    div()
+                        .w(name_width)
+                        .flex_none()
+                        .child(Label::new(name.clone()).size(LabelSize::Small)),
+                )
+                .child(
+                    div().min_w_0().font_family(buffer_font.clone()).child(
+                        Label::new(value.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+                )
+        }))
+}
+
+fn render_sections(sections: &[InfoSection], name_width: Rems, cx: &App) -> Vec<Div> {
+    sections
+        .iter()
+        .enumerate()
+        .map(|(ix, section)| {
+            v_flex()
+                .gap_2()
+                .when(ix > 0, |this| this.child(Divider::horizontal()))
+                .child(render_section(section, name_width, cx))
+        })
+        .collect()
+}
+
+fn string_value(value: &Value) -> SharedString {
+    match value {
+        Value::String(string) => string.clone().into(),
+        value => value.to_string().into(),
+    }
+}
+
+fn info_sections(binary: &DebugAdapterBinary) -> Vec<InfoSection> {
+    let configuration = &binary.request_args.configuration;
+    let mut program = vec![(
+        "Request".into(),
+        match binary.request_args.request {
+            StartDebuggingRequestArgumentsRequest::Launch => "launch".into(),
+            StartDebuggingRequestArgumentsRequest::Attach => "attach".into(),
+        },
+    )];
+    for (key, name) in [
+        ("program", "Program"),
+        ("processId", "Process ID"),
+        ("cwd", "Working Directory"),
+    ] {
+        if let Some(value) = configuration.get(key) {
+            program.push((name.into(), string_value(value)));
+        }
+    }
+    match configuration.get("args") {
+        Some(Value::Array(args)) => program.push((
+            "Arguments".into(),
+            shlex::try_join(args.iter().filter_map(Value::as_str))
+                .unwrap_or_default()
+                .into(),
+        )),
+        Some(args) => program.push(("Arguments".into(), string_value(args))),
+        None => {}
+    }
+
+    let mut program_env = match configuration.get("env") {
+        Some(Value::Object(env)) => env
+            .iter()
+            .map(|(key, value)| (key.clone().into(), string_value(value)))
+            .collect(),
+        _ => Vec::new(),
+    };
+    program_env.sort();
+
+    let mut adapter = Vec::new();
+    if let Some(command) = &binary.command {
+        adapter.push((
+            "Command".into(),
+            shlex::try_join(
+                std::iter::once(command.as_str())
+                    .chain(binary.arguments.iter().map(String::as_str)),
+            )
+            .unwrap_or_default()
+            .into(),
+        ));
+    }
+    if let Some(cwd) = &binary.cwd {
+        adapter.push((
+            "Working Directory".into(),
+            cwd.to_string_lossy().into_owned().into(),
+        ));
+    }
+    if let Some(connection) = &binary.connection {
+        adapter.push((
+            "Connection".into(),
+            format!("tcp://{}:{}", connection.host, connection.port).into(),
+        ));
+    }
+
+    let mut adapter_env = binary
+        .envs
+        .iter()
+        .map(|(key, value)| (key.clone().into(), value.clone().into()))
+        .collect::<Vec<_>>();
+    adapter_env.sort();
+
+    [
+        InfoSection {
+            title: "Program",
+            rows: program,
+        },
+        InfoSection {
+            title: "Program Environment",
+            rows: program_env,
+        },
+        InfoSection {
+            title: "Debug Adapter",
+            rows: adapter,
+        },
+        InfoSection {
+            title: "Debug Adapter Environment",
+            rows: adapter_env,
+        },
+    ]
+    .into_iter()
+    .filter(|section| !section.rows.is_empty())
+    .collect()
+}
+
+impl EventEmitter<DismissEvent> for SessionInfoModal {}
+
+impl Focusable for SessionInfoModal {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for SessionInfoModal {}
+
+impl Render for SessionInfoModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("session-info")
+            .key_context("SessionInfoModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .max_h(rems(32.))
+            .overflow_y_scroll()
+            .p_2()
+            .gap_2()
+            .child(Label::new(format!("Session Info: {}", self.label)))
+            .children(render_sections(&self.sections, rems(8.), cx))
+    }
+}
+
+/// Capabilities from the `initialize` response that turn on parts of the debugger UI, with the
+/// names they're shown under.
+const BOOLEAN_CAPABILITIES: &[(&str, &str)] = &[
+    ("supportsConfigurationDoneRequest", "Configuration Done"),
+    ("supportsFunctionBreakpoints", "Function Breakpoints"),
+    ("supportsConditionalBreakpoints", "Conditional Breakpoints"),
+    ("supportsHitConditionalBreakpoints", "Hit Count Breakpoints"),
+    ("supportsLogPoints", "Log Points"),
+    ("supportsDataBreakpoints", "Data Breakpoints"),
+    ("supportsDataBreakpointBytes", "Data Breakpoint Bytes"),
+    ("supportsInstructionBreakpoints", "Instruction Breakpoints"),
+    ("supportsBreakpointLocationsRequest", "Breakpoint Locations"),
+    ("supportsExceptionOptions", "Exception Options"),
+    ("supportsExceptionFilterOptions", "Exception Filter Options"),
+    ("supportsExceptionInfoRequest", "Exception Info"),
+    ("supportsEvaluateForHovers", "Evaluate for Hovers"),
+    ("supportsStepBack", "Step Back"),
+    ("supportsSteppingGranularity", "Stepping Granularity"),
+    ("supportsStepInTargetsRequest", "Step In Targets"),
+    ("supportsGotoTargetsRequest", "Go To Targets"),
+    ("supportsRestartFrame", "Restart Frame"),
+    ("supportsRestartRequest", "Restart"),
+    ("supportsTerminateRequest", "Terminate"),
+    (
+        "supportTerminateDebuggee",
+        "Terminate Debuggee on Disconnect",
+    ),
+    ("supportSuspendDebuggee", "Suspend Debuggee on Disconnect"),
+    ("supportsTerminateThreadsRequest", "Terminate Threads"),
+    (
+        "supportsSingleThreadExecutionRequests",
+        "Single Thread Execution",
+    ),
+    ("supportsCancelRequest", "Cancel"),
+    ("supportsSetVariable", "Set Variable"),
+    ("supportsSetExpression", "Set Expression"),
+    ("supportsValueFormattingOptions", "Value Formatting"),
+    ("supportsCompletionsRequest", "Completions"),
+    ("supportsClipboardContext", "Clipboard Context"),
+    ("supportsModulesRequest", "Modules"),
+    ("supportsLoadedSourcesRequest", "Loaded Sources"),
+    (
+        "supportsDelayedStackTraceLoading",
+        "Delayed Stack Trace Loading",
+    ),
+    ("supportsReadMemoryRequest", "Read Memory"),
+    ("supportsWriteMemoryRequest", "Write Memory"),
+    ("supportsDisassembleRequest", "Disassemble"),
+    ("supportsANSIStyling", "ANSI Styling"),
+];
+
+/// Capabilities that list values rather than turning a feature on or off.
+const LIST_CAPABILITIES: &[(&str, &str)] = &[
+    ("exceptionBreakpointFilters", "Exception Filters"),
+    (
+        "completionTriggerCharacters",
+        "Completion Trigger Characters",
+    ),
+    ("additionalModuleColumns", "Module Columns"),
+    ("supportedChecksumAlgorithms", "Checksum Algorithms"),
+    ("breakpointModes", "Breakpoint Modes"),
+];
+
+/// Everything a session's debug adapter reported it can do in its `initialize` response, which
+/// decides which debugger actions are offered for that session.
+pub(crate) struct CapabilitiesModal {
+    label: SharedString,
+    sections: Vec<InfoSection>,
+    focus_handle: FocusHandle,
+}
+
+impl CapabilitiesModal {
+    pub(crate) fn show(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(session) = workspace
+            .panel::<DebugPanel>(cx)
+            .and_then(|panel| panel.read(cx).active_session())
+            .map(|session| session.read(cx).session(cx))
+        else {
+            return;
+        };
+
+        workspace.toggle_modal(window, cx, |_, cx| {
+            let session = session.read(cx);
+            Self {
+                label: session.label(),
+                sections: capability_sections(session.capabilities()),
+                focus_handle: cx.focus_handle(),
+            }
+        });
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+}
+
+fn capability_sections(capabilities: &Capabilities) -> Vec<InfoSection> {
+    let mut capabilities = match serde_json::to_value(capabilities) {
+        Ok(Value::Object(capabilities)) => capabilities,
+        _ => Default::default(),
+    };
+
+    let mut supported = Vec::new();
+    let mut unsupported = Vec::new();
+    for (key, name) in BOOLEAN_CAPABILITIES {
+        let row = (SharedString::from(*name), SharedString::from(*key));
+        match capabilities.remove(*key) {
+            Some(Value::Bool(true)) => supported.push(row),
+            _ => unsupported.push(row),
+        }
+    }
+
+    let mut sections = vec![
+        InfoSection {
+            title: "Supported",
+            rows: supported,
+        },
+        InfoSection {
+            title: "Not Supported",
+            rows: unsupported,
+        },
+    ];
+
+    let lists = LIST_CAPABILITIES
+        .iter()
+        .filter_map(|(key, name)| {
+            let values = match capabilities.remove(*key)? {
+                Value::Array(values) => values,
+                _ => return None,
+            };
+            let values = values
+                .iter()
+                .map(|value| match value {
+                    Value::Object(object) => object
+                        .get("label")
+                        .map(string_value)
+                        .unwrap_or_else(|| string_value(value))
+                        .to_string(),
+                    value => string_value(value).to_string(),
+                })
+                .collect::<Vec<_>>();
+            (!values.is_empty()).then(|| ((*name).into(), values.join(", ").into()))
+        })
+        .collect::<Vec<_>>();
+    sections.push(InfoSection {
+        title: "Values",
+        rows: lists,
+    });
+
+    // Anything the adapter reports that isn't listed above, e.g. from a newer protocol version.
+    let other = capabilities
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| (key.into(), string_value(&value)))
+        .collect();
+    sections.push(InfoSection {
+        title: "Other",
+        rows: other,
+    });
+
+    sections.retain(|section| !section.rows.is_empty());
+    sections
+}
+
+impl EventEmitter<DismissEvent> for CapabilitiesModal {}
+
+impl Focusable for CapabilitiesModal {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl ModalView for CapabilitiesModal {}
+
+impl Render for CapabilitiesModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("session-capabilities")
+            .key_context("CapabilitiesModal")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .elevation_3(cx)
+            .w(rems(40.))
+            .max_h(rems(32.))
+            .overflow_y_scroll()
+            .p_2()
+            .gap_2()
+            .child(Label::new(format!("Capabilities: {}", self.label)))
+            .children(render_sections(&self.sections, rems(16.), cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use collections::HashMap;
+    use dap::{StartDebuggingRequestArguments, adapters::TcpArguments};
+    use std::{net::Ipv4Addr, path::PathBuf};
+
+    #[test]
+    fn test_session_info_sections() {
+        let binary = DebugAdapterBinary {
+            command: Some("node".into()),
+            arguments: vec!["adapter.js".into(), "--port=8123".into()],
+            envs: HashMap::from_iter([("NODE_OPTIONS".into(), "--inspect".into())]),
+            cwd: Some(PathBuf::from("/adapters/js")),
+            connection: Some(TcpArguments {
+                host: Ipv4Addr::LOCALHOST,
+                port: 8123,
+                timeout: None,
+            }),
+            request_args: StartDebuggingRequestArguments {
+                request: StartDebuggingRequestArgumentsRequest::Launch,
+                configuration: serde_json::json!({
+                    "program": "server.js",
+                    "args": ["--name", "my app"],
+                    "cwd": "/project",
+                    "env": { "PORT": "3000", "DEBUG": "1" },
+                }),
+            },
+        };
+
+        let rows = |title| {
+            info_sections(&binary)
+                .into_iter()
+                .find(|section| section.title == title)
+                .map(|section| section.rows)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, value)| format!("{name}: {value}"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            rows("Program"),
+            vec![
+                "Request: launch",
+                "Program: server.js",
+                "Working Directory: /project",
+                "Arguments: --name 'my app'",
+            ]
+        );
+        assert_eq!(rows("Program Environment"), vec!["DEBUG: 1", "PORT: 3000"]);
+        assert_eq!(
+            rows("Debug Adapter"),
+            vec![
+                "Command: node adapter.js --port=8123",
+                "Working Directory: /adapters/js",
+                "Connection: tcp://127.0.0.1:8123",
+            ]
+        );
+        assert_eq!(
+            rows("Debug Adapter Environment"),
+            vec!["NODE_OPTIONS: --inspect"]
+        );
+    }
+
+    #[test]
+    fn test_capability_sections() {
+        let capabilities: Capabilities = serde_json::from_value(serde_json::json!({
+            "supportsConfigurationDoneRequest": true,
+            "supportsStepBack": false,
+            "exceptionBreakpointFilters": [
+                { "filter": "raised", "label": "Raised Exceptions" },
+                { "filter": "uncaught", "label": "Uncaught Exceptions" },
+            ],
+        }))
+        .unwrap();
+        let sections = capability_sections(&capabilities);
+
+        let rows = |title| {
+            sections
+                .iter()
+                .find(|section| section.title == title)
+                .map(|section| {
+                    section
+                        .rows
+                        .iter()
+                        .map(|(name, value)| format!("{name}: {value}"))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        assert_eq!(
+            rows("Supported"),
+            vec!["Configuration Done: supportsConfigurationDoneRequest"]
+        );
+        let unsupported = rows("Not Supported");
+        assert_eq!(unsupported.len(), BOOLEAN_CAPABILITIES.len() - 1);
+        assert!(unsupported.contains(&"Step Back: supportsStepBack".to_string()));
+        assert!(
+            unsupported.contains(
+                &"Terminate Debuggee on Disconnect: supportTerminateDebuggee".to_string()
+            )
+        );
+        assert_eq!(
+            rows("Values"),
+            vec!["Exception Filters: Raised Exceptions, Uncaught Exceptions"]
+        );
+    }
+}
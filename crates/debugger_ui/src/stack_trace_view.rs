@@ -146,17 +146,15 @@ impl StackTraceView {
             editor.clear_highlights::<DebugStackFrameLine>(cx)
         });
 
-        let stack_frames = self
-            .stack_frame_list
-            .read_with(cx, |list, _| list.flatten_entries(false, false));
-
-        let frames_to_open: Vec<_> = stack_frames
+        let stack_frame_list = self.stack_frame_list.read(cx);
+        let frames_to_open: Vec<_> = stack_frame_list
+            .flatten_entries(false, false)
             .into_iter()
             .filter_map(|frame| {
                 Some((
                     frame.id,
                     frame.line as u32 - 1,
-                    StackFrameList::abs_path_from_stack_frame(&frame)?,
+                    stack_frame_list.abs_path_from_stack_frame(&frame, cx)?,
                 ))
             })
             .collect();
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::{Context as _, Result};
+use dap::Variable;
 use dap::adapters::DebugTaskDefinition;
 use dap::client::DebugAdapterClient;
 use gpui::{Entity, TestAppContext, WindowHandle};
@@ -15,23 +16,41 @@ use crate::{debugger_panel::DebugPanel, session::DebugSession};
 #[cfg(test)]
 mod attach_modal;
 #[cfg(test)]
+mod breakpoint_list;
+#[cfg(test)]
+mod collection_table;
+#[cfg(test)]
 mod console;
 #[cfg(test)]
 mod dap_logger;
 #[cfg(test)]
 mod debugger_panel;
 #[cfg(test)]
+mod disassembly_view;
+#[cfg(test)]
+mod floating_controls;
+#[cfg(test)]
+mod image_visualizer;
+#[cfg(test)]
 mod inline_values;
 #[cfg(test)]
+mod memory_editor;
+#[cfg(test)]
 mod module_list;
 #[cfg(test)]
 mod new_process_modal;
 #[cfg(test)]
 mod persistence;
 #[cfg(test)]
+mod quick_watch;
+#[cfg(test)]
+mod register_list;
+#[cfg(test)]
 mod stack_frame_list;
 #[cfg(test)]
 mod variable_list;
+#[cfg(test)]
+mod variable_snapshots;
 
 pub fn init_test(cx: &mut gpui::TestAppContext) {
     #[cfg(test)]
@@ -153,3 +172,19 @@ pub fn start_debug_session<T: Fn(&Arc<DebugAdapterClient>) + 'static>(
         configure,
     )
 }
+
+pub fn variable(name: &str, value: &str, variables_reference: u64) -> Variable {
+    Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    }
+}
@@ -117,16 +117,19 @@ async fn test_show_attach_modal_and_select_process(
                             pid: 0,
                             name: "fake-binary-1".into(),
                             command: vec![],
+                            user: None,
                         },
                         Candidate {
                             pid: 3,
                             name: "real-binary-1".into(),
                             command: vec![],
+                            user: None,
                         },
                         Candidate {
                             pid: 1,
                             name: "fake-binary-2".into(),
                             command: vec![],
+                            user: None,
                         },
                     ]
                     .into_iter()
@@ -179,3 +182,75 @@ async fn test_show_attach_modal_and_select_process(
         })
         .unwrap();
 }
+
+#[gpui::test]
+async fn test_filter_processes_by_user(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let attach_modal = workspace
+        .update(cx, |workspace, window, cx| {
+            let workspace_handle = cx.weak_entity();
+            workspace.toggle_modal(window, cx, |window, cx| {
+                AttachModal::with_processes(
+                    workspace_handle,
+                    task::ZedDebugConfig {
+                        adapter: FakeAdapter::ADAPTER_NAME.into(),
+                        request: dap::DebugRequest::Attach(AttachRequest::default()),
+                        label: "attach example".into(),
+                        stop_on_entry: None,
+                    },
+                    vec![
+                        Candidate {
+                            pid: 1,
+                            name: "server".into(),
+                            command: vec![],
+                            user: Some("alice".into()),
+                        },
+                        Candidate {
+                            pid: 2,
+                            name: "server".into(),
+                            command: vec![],
+                            user: Some("bob".into()),
+                        },
+                    ]
+                    .into_iter()
+                    .collect(),
+                    true,
+                    window,
+                    cx,
+                )
+            });
+
+            workspace.active_modal::<AttachModal>(cx).unwrap()
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    workspace
+        .update(cx, |_, window, cx| {
+            attach_modal.update(cx, |this, cx| {
+                this.picker.update(cx, |this, cx| {
+                    this.set_query("bob", window, cx);
+                })
+            })
+        })
+        .unwrap();
+    cx.run_until_parked();
+
+    workspace
+        .update(cx, |_, _, cx| {
+            let names =
+                attach_modal.update(cx, |modal, cx| attach_modal::_process_names(&modal, cx));
+            assert_eq!(names, vec![" 2 server bob".to_string()]);
+        })
+        .unwrap();
+}
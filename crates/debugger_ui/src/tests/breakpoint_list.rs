@@ -0,0 +1,174 @@
+use crate::{
+    persistence::DebuggerPaneItem,
+    session::running::breakpoint_list::{
+        DisableSelectedBreakpoints, EnableSelectedBreakpoints, SelectAllBreakpointsInFile,
+    },
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::requests::SetBreakpoints;
+use gpui::{BackgroundExecutor, Focusable, TestAppContext, VisualTestContext};
+use language::Point;
+use project::{
+    FakeFs, Project,
+    debugger::breakpoint_store::{Breakpoint, BreakpointEditAction, BreakpointWithPosition},
+};
+use serde_json::json;
+use std::path::Path;
+use util::path;
+use zed_actions::UnsetBreakpoint;
+
+#[gpui::test]
+async fn test_bulk_actions_on_selected_breakpoints(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "a.rs": "one\ntwo\nthree\nfour\nfive",
+            "b.rs": "one\ntwo",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+    client.on_request::<SetBreakpoints, _>(move |_, _| {
+        Ok(dap::SetBreakpointsResponse {
+            breakpoints: Vec::default(),
+        })
+    });
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    let a_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let b_buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/b.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let toggle_breakpoint =
+        |buffer: &gpui::Entity<language::Buffer>, row: u32, cx: &mut VisualTestContext| {
+            breakpoint_store.update(cx, |store, cx| {
+                let position = buffer.read(cx).anchor_before(Point::new(row, 0));
+                store.toggle_breakpoint(
+                    buffer.clone(),
+                    BreakpointWithPosition {
+                        position,
+                        bp: Breakpoint::new_standard(),
+                    },
+                    BreakpointEditAction::Toggle,
+                    cx,
+                );
+            });
+        };
+    for row in [1, 2, 3] {
+        toggle_breakpoint(&a_buffer, row, cx);
+    }
+    toggle_breakpoint(&b_buffer, 0, cx);
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .update(cx, |item, _| item.running_state().clone());
+    running_state.update_in(cx, |this, window, cx| {
+        this.ensure_pane_item(DebuggerPaneItem::BreakpointList, window, cx);
+        this.activate_item(DebuggerPaneItem::BreakpointList, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    let breakpoint_list = running_state.update(cx, |state, _| state.breakpoint_list().clone());
+    breakpoint_list.update_in(cx, |breakpoint_list, window, cx| {
+        window.focus(&breakpoint_list.focus_handle(cx));
+    });
+    cx.run_until_parked();
+
+    let a_path = Path::new(path!("/project/a.rs"));
+    let b_path = Path::new(path!("/project/b.rs"));
+    let selected_rows = |cx: &mut VisualTestContext| {
+        breakpoint_list.read_with(cx, |breakpoint_list, _| {
+            breakpoint_list.selected_breakpoint_rows()
+        })
+    };
+    let breakpoint_states = |cx: &mut VisualTestContext| {
+        breakpoint_store.read_with(cx, |store, cx| {
+            store
+                .all_source_breakpoints(cx)
+                .into_values()
+                .flatten()
+                .map(|breakpoint| {
+                    (
+                        breakpoint.path.clone(),
+                        breakpoint.row,
+                        breakpoint.state.is_enabled(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    cx.dispatch_action(menu::SelectFirst);
+    cx.dispatch_action(menu::SelectNext);
+    cx.dispatch_action(SelectAllBreakpointsInFile);
+    cx.run_until_parked();
+    assert_eq!(
+        selected_rows(cx),
+        vec![(a_path.into(), 1), (a_path.into(), 2), (a_path.into(), 3)]
+    );
+
+    cx.dispatch_action(DisableSelectedBreakpoints);
+    cx.run_until_parked();
+    assert_eq!(
+        breakpoint_states(cx),
+        vec![
+            (a_path.into(), 1, false),
+            (a_path.into(), 2, false),
+            (a_path.into(), 3, false),
+            (b_path.into(), 0, true),
+        ]
+    );
+
+    cx.dispatch_action(EnableSelectedBreakpoints);
+    cx.run_until_parked();
+    assert!(
+        breakpoint_states(cx)
+            .iter()
+            .all(|(_, _, is_enabled)| *is_enabled)
+    );
+
+    // Adding a breakpoint from elsewhere shifts the entries, so the selection is dropped rather
+    // than left pointing at other breakpoints.
+    toggle_breakpoint(&a_buffer, 0, cx);
+    cx.run_until_parked();
+    assert_eq!(selected_rows(cx), vec![(a_path.into(), 1)]);
+
+    cx.dispatch_action(DisableSelectedBreakpoints);
+    cx.run_until_parked();
+    assert_eq!(
+        breakpoint_states(cx),
+        vec![
+            (a_path.into(), 0, true),
+            (a_path.into(), 1, false),
+            (a_path.into(), 2, true),
+            (a_path.into(), 3, true),
+            (b_path.into(), 0, true),
+        ]
+    );
+
+    cx.dispatch_action(SelectAllBreakpointsInFile);
+    cx.dispatch_action(UnsetBreakpoint);
+    cx.run_until_parked();
+    assert_eq!(breakpoint_states(cx), vec![(b_path.into(), 0, true)]);
+}
@@ -0,0 +1,181 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    session::running::{collection_table::TableColumn, variable_list::VariableListEvent},
+    tests::{
+        active_debug_session_panel, init_test, init_test_workspace, start_debug_session, variable,
+    },
+};
+use dap::{
+    Scope, StackFrame, StoppedEvent, Variable, VariablesArgumentsFilter,
+    requests::{Scopes, StackTrace, Variables},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use util::path;
+
+#[gpui::test]
+async fn test_view_as_table_pages_and_sorts_elements(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Locals".into(),
+                presentation_hint: None,
+                variables_reference: 10,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let numbers = Variable {
+        indexed_variables: Some(600),
+        ..variable("numbers", "len=600", 20)
+    };
+
+    let requested_pages = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Variables, _>({
+        let numbers = numbers.clone();
+        let requested_pages = requested_pages.clone();
+        move |_, args| {
+            let variables = match args.variables_reference {
+                10 => vec![numbers.clone()],
+                20 => {
+                    assert!(matches!(
+                        args.filter,
+                        Some(VariablesArgumentsFilter::Indexed)
+                    ));
+                    let start = args.start.unwrap();
+                    let count = args.count.unwrap();
+                    requested_pages.lock().unwrap().push((start, count));
+                    (start..start + count)
+                        .map(|ix| variable(&format!("[{ix}]"), &(600 - ix).to_string(), 0))
+                        .collect()
+                }
+                _ => unreachable!(),
+            };
+            Ok(dap::VariablesResponse { variables })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+    cx.run_until_parked();
+
+    let variable_list = running_state.update(cx, |state, _| state.variable_list().clone());
+    variable_list.update(cx, |_, cx| {
+        cx.emit(VariableListEvent::ViewAsTable(numbers.clone()));
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        vec![(0, 256), (256, 256), (512, 88)],
+        *requested_pages.lock().unwrap()
+    );
+
+    let collection_table = running_state.update(cx, |state, _| state.collection_table().clone());
+    collection_table.update(cx, |table, cx| {
+        assert!(!table.is_loading());
+        let rows = table.visual_rows();
+        assert_eq!(600, rows.len());
+        assert_eq!("[0] = 600", rows[0]);
+        assert_eq!("[599] = 1", rows[599]);
+
+        table.sort_by(TableColumn::Value, cx);
+        assert_eq!("[599] = 1", table.visual_rows()[0]);
+
+        table.sort_by(TableColumn::Value, cx);
+        assert_eq!("[0] = 600", table.visual_rows()[0]);
+
+        table.sort_by(TableColumn::Name, cx);
+        let rows = table.visual_rows();
+        assert_eq!("[0] = 600", rows[0]);
+        assert_eq!("[10] = 590", rows[10]);
+    });
+}
@@ -1,12 +1,23 @@
 use crate::{
-    tests::{active_debug_session_panel, start_debug_session},
+    ClearConsole,
+    session::running::console::{
+        OutputCategory, SelectNextMatch, SelectPreviousMatch, ToggleSearch,
+    },
+    tests::{active_debug_session_panel, inline_values::rust_lang, start_debug_session},
     *,
 };
-use dap::requests::StackTrace;
-use editor::{DisplayPoint, display_map::DisplayRow};
-use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
-use project::{FakeFs, Project};
+use dap::debugger_settings::DebuggerSettings;
+use dap::requests::{Evaluate, StackTrace, Variables};
+use editor::{
+    DisplayPoint, Editor,
+    actions::{MoveDown, MoveUp},
+    display_map::DisplayRow,
+};
+use gpui::{BackgroundExecutor, Focusable, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger::session::ThreadId};
 use serde_json::json;
+use settings::SettingsStore;
+use std::sync::{Arc, Mutex};
 use tests::{init_test, init_test_workspace};
 use util::path;
 
@@ -341,6 +352,20 @@ async fn test_escape_code_processing(executor: BackgroundExecutor, cx: &mut Test
                 ]
             );
 
+            let bold_highlights = editor.update(cx, |editor, cx| {
+                let mut bold_highlights = editor.all_text_highlights(window, cx).into_iter().filter(|(style, _)| style.font_weight == Some(gpui::FontWeight::BOLD)).flat_map(|(_, ranges)| ranges).collect::<Vec<_>>();
+                bold_highlights.sort_by(|a, b| a.start.cmp(&b.start));
+                bold_highlights
+            });
+            pretty_assertions::assert_eq!(
+                bold_highlights,
+                [
+                    DisplayPoint::new(DisplayRow(1), 3)..DisplayPoint::new(DisplayRow(1), 21),
+                    DisplayPoint::new(DisplayRow(5), 1)..DisplayPoint::new(DisplayRow(5), 4),
+                    DisplayPoint::new(DisplayRow(7), 1)..DisplayPoint::new(DisplayRow(7), 4),
+                ]
+            );
+
             let background_highlights = editor.update(cx, |editor, cx| {
                 editor.all_text_background_highlights(window, cx).into_iter().map(|(range, _)| range).collect::<Vec<_>>()
             });
@@ -1090,3 +1115,1096 @@ async fn test_escape_code_processing(executor: BackgroundExecutor, cx: &mut Test
 
 //     shutdown_session.await.unwrap();
 // }
+
+#[gpui::test]
+async fn test_output_categories_can_be_hidden(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    for (category, output) in [
+        (Some(dap::OutputEventCategory::Stdout), "out"),
+        (Some(dap::OutputEventCategory::Stderr), "err"),
+        (None, "console"),
+        (Some(dap::OutputEventCategory::Telemetry), "telemetry"),
+    ] {
+        client
+            .fake_event(dap::messages::Events::Output(dap::OutputEvent {
+                category,
+                output: output.to_string(),
+                data: None,
+                variables_reference: None,
+                source: None,
+                line: None,
+                column: None,
+                group: None,
+                location_reference: None,
+            }))
+            .await;
+    }
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    let console_text = |cx: &mut VisualTestContext| {
+        console.read_with(cx, |console, cx| console.editor().read(cx).text(cx))
+    };
+
+    assert_eq!(
+        "out\nerr\nconsole\n",
+        console_text(cx),
+        "Telemetry should be hidden by default"
+    );
+
+    console.update_in(cx, |console, window, cx| {
+        console.toggle_category(OutputCategory::Stderr, window, cx);
+    });
+    assert_eq!("out\nconsole\n", console_text(cx));
+
+    console.update_in(cx, |console, window, cx| {
+        console.toggle_category(OutputCategory::Telemetry, window, cx);
+        console.toggle_category(OutputCategory::Stderr, window, cx);
+    });
+    assert_eq!("out\nerr\nconsole\ntelemetry\n", console_text(cx));
+}
+
+#[gpui::test]
+async fn test_console_drops_lines_over_the_limit(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.console_max_lines = 3;
+            });
+        });
+    });
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    let output = |output: &str| {
+        dap::messages::Events::Output(dap::OutputEvent {
+            category: None,
+            output: output.to_string(),
+            data: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            group: None,
+            location_reference: None,
+        })
+    };
+    client.fake_event(output("first\nsecond")).await;
+    cx.run_until_parked();
+    client.fake_event(output("third")).await;
+    client.fake_event(output("fourth\nfifth")).await;
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    console.read_with(cx, |console, cx| {
+        assert_eq!(
+            "third\nfourth\nfifth\n",
+            console.editor().read(cx).text(cx),
+            "Only the newest lines should be kept"
+        );
+    });
+
+    console.update_in(cx, |console, window, cx| {
+        console.toggle_category(OutputCategory::Stderr, window, cx);
+    });
+    console.read_with(cx, |console, cx| {
+        assert_eq!(
+            "third\nfourth\nfifth\n",
+            console.editor().read(cx).text(cx),
+            "Rebuilding the output should apply the limit too"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_console_collapses_repeated_output(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    let output = |output: &str| {
+        dap::messages::Events::Output(dap::OutputEvent {
+            category: None,
+            output: output.to_string(),
+            data: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            group: None,
+            location_reference: None,
+        })
+    };
+    client.fake_event(output("retrying")).await;
+    client.fake_event(output("retrying")).await;
+    cx.run_until_parked();
+    client.fake_event(output("retrying")).await;
+    client.fake_event(output("connected")).await;
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    console.update(cx, |console, cx| {
+        let editor = console.editor().clone();
+        assert_eq!(
+            "retrying\nretrying\nretrying\nconnected\n",
+            editor.read(cx).text(cx),
+            "Repeated output should still be in the console"
+        );
+        assert_eq!(
+            "retrying⋯\nconnected\n",
+            editor.update(cx, |editor, cx| editor.display_text(cx)),
+            "Repeats should be folded into the first line"
+        );
+    });
+
+    client.fake_event(output("connected")).await;
+    cx.run_until_parked();
+    console.update(cx, |console, cx| {
+        console.expand_last_repeated_output(cx);
+    });
+    client.fake_event(output("connected")).await;
+    cx.run_until_parked();
+    console.update(cx, |console, cx| {
+        assert_eq!(
+            "retrying⋯\nconnected\nconnected\nconnected\n",
+            console
+                .editor()
+                .update(cx, |editor, cx| editor.display_text(cx)),
+            "Expanded runs should stay expanded as they grow"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_clear_console(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.clear_console_on_restart = true;
+            });
+        });
+    });
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_restart_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<dap::requests::Restart, _>(move |_, _| Ok(()));
+
+    let output = |output: &str| {
+        dap::messages::Events::Output(dap::OutputEvent {
+            category: None,
+            output: output.to_string(),
+            data: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            group: None,
+            location_reference: None,
+        })
+    };
+    client.fake_event(output("first run")).await;
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .update(cx, |item, _| item.running_state().clone());
+    let console = running_state.read_with(cx, |state, _| state.console().clone());
+    let text = |cx: &mut VisualTestContext| {
+        console.read_with(cx, |console, cx| console.editor().read(cx).text(cx))
+    };
+    assert_eq!("first run\n", text(cx));
+
+    cx.dispatch_action(ClearConsole);
+    cx.run_until_parked();
+    assert_eq!("", text(cx));
+
+    client.fake_event(output("after clearing")).await;
+    cx.run_until_parked();
+    console.update_in(cx, |console, window, cx| {
+        console.toggle_category(OutputCategory::Stderr, window, cx);
+    });
+    assert_eq!(
+        "after clearing\n",
+        text(cx),
+        "Cleared output should stay cleared when the output is rebuilt"
+    );
+
+    running_state.update(cx, |state, cx| state.restart_session(cx));
+    cx.run_until_parked();
+    client.fake_event(output("second run")).await;
+    cx.run_until_parked();
+    assert_eq!(
+        "second run\n",
+        text(cx),
+        "The console should be cleared when the session restarts"
+    );
+}
+
+#[gpui::test]
+async fn test_console_history_navigation(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<Evaluate, _>(move |_, args| {
+        Ok(dap::EvaluateResponse {
+            result: args.expression,
+            type_: None,
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    for expression in ["first", "second"] {
+        console.update_in(cx, |console, window, cx| {
+            console.query_bar().update(cx, |query_bar, cx| {
+                query_bar.set_text(expression, window, cx)
+            });
+            console.evaluate(&menu::Confirm, window, cx);
+        });
+        cx.run_until_parked();
+    }
+
+    console.update_in(cx, |console, window, cx| {
+        window.focus(&console.query_bar().focus_handle(cx));
+    });
+    let query = |cx: &mut VisualTestContext| {
+        console.read_with(cx, |console, cx| console.query_bar().read(cx).text(cx))
+    };
+
+    cx.dispatch_action(MoveUp);
+    assert_eq!("second", query(cx));
+    cx.dispatch_action(MoveUp);
+    assert_eq!("first", query(cx));
+    cx.dispatch_action(MoveUp);
+    assert_eq!(
+        "first",
+        query(cx),
+        "The oldest expression should stay selected"
+    );
+    cx.dispatch_action(MoveDown);
+    assert_eq!("second", query(cx));
+    cx.dispatch_action(MoveDown);
+    assert_eq!(
+        "",
+        query(cx),
+        "Moving past the newest expression should clear the query"
+    );
+}
+
+#[gpui::test]
+async fn test_search_console_output(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    let output = |output: &str| {
+        dap::messages::Events::Output(dap::OutputEvent {
+            category: None,
+            output: output.to_string(),
+            data: None,
+            variables_reference: None,
+            source: None,
+            line: None,
+            column: None,
+            group: None,
+            location_reference: None,
+        })
+    };
+    for line in ["foo bar", "Foo baz", "qux"] {
+        client.fake_event(output(line)).await;
+    }
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    let selected_range = |cx: &mut VisualTestContext| {
+        console.update(cx, |console, cx| {
+            console.editor().update(cx, |editor, cx| {
+                editor.selections.newest::<usize>(cx).range()
+            })
+        })
+    };
+
+    console.update_in(cx, |console, window, cx| {
+        window.focus(&console.editor().focus_handle(cx));
+    });
+    cx.dispatch_action(ToggleSearch);
+    cx.simulate_input("foo");
+    cx.run_until_parked();
+    console.read_with(cx, |console, _| {
+        assert_eq!((2, Some(0)), console.search_matches());
+    });
+    assert_eq!(0..3, selected_range(cx));
+
+    cx.dispatch_action(SelectNextMatch);
+    console.read_with(cx, |console, _| {
+        assert_eq!((2, Some(1)), console.search_matches());
+    });
+    assert_eq!(8..11, selected_range(cx));
+
+    cx.dispatch_action(SelectNextMatch);
+    console.read_with(cx, |console, _| {
+        assert_eq!((2, Some(0)), console.search_matches(), "Should wrap around");
+    });
+    cx.dispatch_action(SelectPreviousMatch);
+    console.read_with(cx, |console, _| {
+        assert_eq!((2, Some(1)), console.search_matches());
+    });
+
+    client.fake_event(output("food")).await;
+    cx.run_until_parked();
+    console.read_with(cx, |console, _| {
+        assert_eq!(
+            (3, Some(1)),
+            console.search_matches(),
+            "New output should be searched without moving the active match"
+        );
+    });
+
+    cx.dispatch_action(ToggleSearch);
+    console.read_with(cx, |console, _| {
+        assert_eq!((0, None), console.search_matches());
+    });
+}
+
+#[test]
+fn test_format_timestamp() {
+    use crate::session::running::console::format_timestamp;
+    use dap::debugger_settings::ConsoleTimestamps;
+    use std::time::{Duration, SystemTime};
+
+    let started_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+    let received_at = started_at + Duration::from_millis(75_125);
+
+    assert_eq!(
+        None,
+        format_timestamp(ConsoleTimestamps::Off, received_at, started_at)
+    );
+    assert_eq!(
+        Some("[+75.125s]".to_string()),
+        format_timestamp(ConsoleTimestamps::SessionRelative, received_at, started_at)
+    );
+    assert_eq!(
+        Some("[+0.000s]".to_string()),
+        format_timestamp(ConsoleTimestamps::SessionRelative, started_at, received_at),
+        "Output received before the session started shouldn't underflow"
+    );
+    let wall_clock = format_timestamp(ConsoleTimestamps::WallClock, received_at, started_at)
+        .expect("wall clock timestamps are always formatted");
+    assert_eq!("[hh:mm:ss.mmm]".len(), wall_clock.len());
+}
+
+#[test]
+fn test_find_matches() {
+    use crate::session::running::console::find_matches;
+
+    assert_eq!(vec![0..3, 8..11], find_matches("foo bar\nFoo baz", "foo"));
+    assert_eq!(vec![0..2, 2..4], find_matches("aaaa", "aa"));
+    assert!(find_matches("foo", "").is_empty());
+    assert!(find_matches("foo", "bar").is_empty());
+}
+
+#[test]
+fn test_utf16_column_to_offset() {
+    use crate::session::running::console::utf16_column_to_offset;
+
+    assert_eq!(0, utf16_column_to_offset("self.fo", 1));
+    assert_eq!(5, utf16_column_to_offset("self.fo", 6));
+    assert_eq!(7, utf16_column_to_offset("self.fo", 8));
+    assert_eq!(7, utf16_column_to_offset("self.fo", 20));
+    // `é` is two bytes but a single UTF-16 code unit, `😀` is four bytes and two code units.
+    assert_eq!(3, utf16_column_to_offset("é.x", 3));
+    assert_eq!(5, utf16_column_to_offset("😀.x", 4));
+}
+
+#[gpui::test]
+async fn test_evaluate_selection_in_console(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({ "main.rs": "fn main() {\n    let total = a + b;\n}" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<Evaluate, _>(move |_, args| {
+        assert_eq!("a + b", args.expression);
+        assert_eq!(Some(dap::EvaluateArgumentsContext::Repl), args.context);
+        Ok(dap::EvaluateResponse {
+            result: "3".into(),
+            type_: None,
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+    cx.run_until_parked();
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_path((worktree_id, "main.rs"), None, true, window, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(None, window, cx, |selections| {
+            selections.select_ranges([28..33])
+        });
+    });
+
+    cx.dispatch_action(editor::actions::EvaluateSelectedText);
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    console.update_in(cx, |console, window, cx| {
+        assert_eq!(
+            "> a + b\n< 3\n",
+            console.editor().read(cx).text(cx),
+            "The selection should be evaluated in the console"
+        );
+        assert!(
+            console.focus_handle(cx).contains_focused(window, cx),
+            "The console should be focused to show the result"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_evaluate_selection_inline(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({ "main.rs": "fn main() {\n    let total = a + b;\n}" }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<Evaluate, _>(move |_, args| {
+        assert_eq!("a + b", args.expression);
+        Ok(dap::EvaluateResponse {
+            result: "3".into(),
+            type_: None,
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+    cx.run_until_parked();
+
+    let editor = workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.open_path((worktree_id, "main.rs"), None, true, window, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(None, window, cx, |selections| {
+            selections.select_ranges([28..33])
+        });
+    });
+
+    cx.dispatch_action(editor::actions::EvaluateSelectedTextInline);
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        assert_eq!(Some("3".into()), editor.evaluation_result());
+        assert!(
+            editor.focus_handle(cx).is_focused(window),
+            "The editor should keep focus"
+        );
+    });
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    console.update(cx, |console, cx| {
+        assert_eq!(
+            "",
+            console.editor().read(cx).text(cx),
+            "The evaluation shouldn't be echoed to the console"
+        );
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.change_selections(None, window, cx, |selections| {
+            selections.select_ranges([0..0])
+        });
+        assert_eq!(
+            None,
+            editor.evaluation_result(),
+            "Changing the selection should dismiss the result"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_evaluate_in_picked_thread(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    let stack_frame = |id: u64| dap::StackFrame {
+        id,
+        name: format!("Stack Frame {id}"),
+        source: None,
+        line: 1,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference: None,
+        module_id: None,
+        presentation_hint: None,
+    };
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![
+                dap::Thread {
+                    id: 1,
+                    name: "Thread 1".into(),
+                },
+                dap::Thread {
+                    id: 2,
+                    name: "Thread 2".into(),
+                },
+            ],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, args| {
+        let stack_frames = match args.thread_id {
+            1 => vec![stack_frame(11), stack_frame(12)],
+            _ => vec![stack_frame(21), stack_frame(22)],
+        };
+        Ok(dap::StackTraceResponse {
+            stack_frames,
+            total_frames: None,
+        })
+    });
+    client.on_request::<dap::requests::Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse { scopes: vec![] })
+    });
+    let evaluated_frames = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<Evaluate, _>({
+        let evaluated_frames = evaluated_frames.clone();
+        move |_, args| {
+            evaluated_frames.lock().unwrap().push(args.frame_id);
+            Ok(dap::EvaluateResponse {
+                result: args.expression,
+                type_: None,
+                presentation_hint: None,
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                value_location_reference: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    let evaluate = |cx: &mut VisualTestContext| {
+        console.update_in(cx, |console, window, cx| {
+            console
+                .query_bar()
+                .update(cx, |query_bar, cx| query_bar.set_text("value", window, cx));
+            console.evaluate(&menu::Confirm, window, cx);
+        });
+        cx.run_until_parked();
+    };
+
+    evaluate(cx);
+    // Load the other thread's stack, as opening the picker does.
+    session.update(cx, |session, cx| {
+        session.stack_frames(ThreadId(2), cx).unwrap();
+    });
+    cx.run_until_parked();
+    console.update(cx, |console, cx| {
+        console.evaluate_in_thread(ThreadId(2), 1, cx);
+    });
+    evaluate(cx);
+
+    assert_eq!(
+        vec![Some(11), Some(22)],
+        *evaluated_frames.lock().unwrap(),
+        "Expressions should run in the picked frame instead of the selected one"
+    );
+}
+
+#[gpui::test]
+async fn test_expand_structured_output(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.js": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+    let variable = |name: &str, value: &str, variables_reference: u64| dap::Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+    client.on_request::<Variables, _>(move |_, args| {
+        let variables = match args.variables_reference {
+            5 => vec![variable("a", "1", 0), variable("b", "{c: 2}", 6)],
+            6 => vec![variable("c", "2", 0)],
+            _ => Vec::new(),
+        };
+        Ok(dap::VariablesResponse { variables })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Output(dap::OutputEvent {
+            category: None,
+            output: "{a: 1, b: {…}}".to_string(),
+            data: None,
+            variables_reference: Some(5),
+            source: None,
+            line: None,
+            column: None,
+            group: None,
+            location_reference: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let console = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state().read(cx).console().clone()
+    });
+    console.update(cx, |console, cx| {
+        assert_eq!(
+            "{a: 1, b: {…}}\n",
+            console.editor().read(cx).text(cx),
+            "Structured output should still be in the console's text"
+        );
+        assert_eq!(
+            vec!["> {a: 1, b: {…}}"],
+            console.structured_output_visual_entries(cx)
+        );
+        console.toggle_structured_output_path(&[], cx);
+    });
+    cx.run_until_parked();
+    console.update(cx, |console, cx| {
+        assert_eq!(
+            vec!["v {a: 1, b: {…}}", "      a = 1", "    > b = {c: 2}"],
+            console.structured_output_visual_entries(cx)
+        );
+        console.toggle_structured_output_path(&["b"], cx);
+    });
+    cx.run_until_parked();
+    console.update(cx, |console, cx| {
+        assert_eq!(
+            vec![
+                "v {a: 1, b: {…}}",
+                "      a = 1",
+                "    v b = {c: 2}",
+                "          c = 2"
+            ],
+            console.structured_output_visual_entries(cx)
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_query_bar_uses_language_of_selected_frame(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {}",
+            "script.unknown": "",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    project.update(cx, |project, _| {
+        project.languages().add(Arc::new(rust_lang()));
+    });
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.read_with(cx, |session, _| session.adapter_client().unwrap());
+
+    let stack_frame = |id: u64, path: &str| dap::StackFrame {
+        id,
+        name: format!("Stack Frame {id}"),
+        source: Some(dap::Source {
+            name: None,
+            path: Some(path.into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line: 1,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference: None,
+        module_id: None,
+        presentation_hint: None,
+    };
+    let stack_frames = vec![
+        stack_frame(1, path!("/project/main.rs")),
+        stack_frame(2, path!("/project/script.unknown")),
+    ];
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: stack_frames.clone(),
+            total_frames: None,
+        })
+    });
+    client.on_request::<dap::requests::Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse { scopes: vec![] })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .update(cx, |item, _| item.running_state().clone());
+    let query_bar_language = |cx: &mut VisualTestContext| {
+        running_state.update(cx, |state, cx| {
+            let query_bar = state.console().read(cx).query_bar().clone();
+            let buffer = query_bar.read(cx).buffer().read(cx).as_singleton().unwrap();
+            buffer
+                .read(cx)
+                .language()
+                .map(|language| language.name().to_string())
+        })
+    };
+    assert_eq!(Some("Rust".to_string()), query_bar_language(cx));
+
+    running_state
+        .update_in(cx, |state, window, cx| {
+            state
+                .stack_frame_list()
+                .update(cx, |list, cx| list.go_to_stack_frame(2, window, cx))
+        })
+        .await
+        .ok();
+    cx.run_until_parked();
+    assert_eq!(
+        None,
+        query_bar_language(cx),
+        "Sources in unknown languages should fall back to plain text"
+    );
+}
+
+#[test]
+fn test_find_locations() {
+    use crate::session::running::console::{ConsoleLocation, find_locations};
+    use std::path::PathBuf;
+
+    let location = |path: &str, line, column| ConsoleLocation {
+        path: PathBuf::from(path),
+        line,
+        column,
+    };
+
+    assert_eq!(
+        vec![(6..22, location("src/main.rs", 12, Some(4)))],
+        find_locations("error src/main.rs:12:4: expected `;`")
+    );
+    assert_eq!(
+        vec![(8..27, location("/Users/x/main.js", 10, None))],
+        find_locations("thrown: /Users/x/main.js:10")
+    );
+    assert_eq!(
+        vec![
+            (8..19, location("/a/b.js", 1, Some(2))),
+            (23..35, location("/a/c.ts", 30, Some(9))),
+        ],
+        find_locations("at foo (/a/b.js:1:2), (/a/c.ts:30:9)")
+    );
+    assert_eq!(
+        vec![(0..16, location("C:\\src\\main.rs", 3, None))],
+        find_locations("C:\\src\\main.rs:3")
+    );
+    assert!(find_locations("listening on http://localhost:3000").is_empty());
+    assert!(find_locations("https://example.com:8080/index.html").is_empty());
+    assert!(find_locations("connected to 192.168.1.144:3000").is_empty());
+    assert!(find_locations("Error: 3 tests failed").is_empty());
+}
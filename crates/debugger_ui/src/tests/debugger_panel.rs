@@ -9,8 +9,9 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        BreakpointLocations, Cancel, Continue, Disconnect, Goto, GotoTargets, Initialize, Launch,
+        Next, RunInTerminal, SetBreakpoints, StackTrace, StartDebugging, StepBack, StepIn, StepOut,
+        Threads,
     },
 };
 use editor::{
@@ -20,15 +21,19 @@ use editor::{
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use project::{
     FakeFs, Project,
-    debugger::session::{ThreadId, ThreadStatus},
+    debugger::{
+        breakpoint_store::{Breakpoint, BreakpointEditAction, BreakpointWithPosition},
+        session::{HotReloadKind, StopMode, ThreadId, ThreadStatus},
+    },
 };
 use serde_json::json;
 use std::{
     path::Path,
     sync::{
         Arc,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
+    time::Duration,
 };
 use terminal_view::terminal_panel::TerminalPanel;
 use tests::{active_debug_session_panel, init_test, init_test_workspace};
@@ -738,6 +743,20 @@ async fn test_shutdown_children_when_parent_session_shutdown(
 
     cx.run_until_parked();
 
+    // both children are listed under the parent session
+    let debug_panel = workspace
+        .update(cx, |workspace, _, cx| workspace.panel::<DebugPanel>(cx))
+        .unwrap()
+        .unwrap();
+    let session_tree = debug_panel.read_with(cx, |debug_panel, cx| {
+        debug_panel
+            .session_tree(cx)
+            .into_iter()
+            .map(|(_, node)| (node.depth, node.is_last_child))
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(session_tree, vec![(0, true), (1, false), (1, true)]);
+
     // shutdown parent session
     dap_store
         .update(cx, |dap_store, cx| {
@@ -1008,6 +1027,20 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
         })
     });
 
+    client.on_request::<dap::requests::ReverseContinue, _>(move |_, _| {
+        Err(ErrorResponse {
+            error: Some(dap::Message {
+                id: 1,
+                format: "error".into(),
+                variables: None,
+                send_telemetry: None,
+                show_user: None,
+                url: None,
+                url_label: None,
+            }),
+        })
+    });
+
     client
         .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
             reason: dap::StoppedEventReason::Pause,
@@ -1032,6 +1065,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
         "step_over",
         "continue_thread",
         "step_back",
+        "reverse_continue",
         "step_in",
         "step_out",
     ] {
@@ -1039,6 +1073,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
             "step_over" => running_state.step_over(cx),
             "continue_thread" => running_state.continue_thread(cx),
             "step_back" => running_state.step_back(cx),
+            "reverse_continue" => running_state.reverse_continue(cx),
             "step_in" => running_state.step_in(cx),
             "step_out" => running_state.step_out(cx),
             _ => unreachable!(),
@@ -1051,7 +1086,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
                     .thread_status(cx)
                     .expect("There should be an active thread selected"),
                 match *operation {
-                    "continue_thread" => ThreadStatus::Running,
+                    "continue_thread" | "reverse_continue" => ThreadStatus::Running,
                     _ => ThreadStatus::Stepping,
                 },
                 "Thread status was not set to correct intermediate state after {} request",
@@ -1080,7 +1115,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
 }
 
 #[gpui::test]
-async fn test_send_breakpoints_when_editor_has_been_saved(
+async fn test_frozen_threads_are_not_resumed_on_continue(
     executor: BackgroundExecutor,
     cx: &mut TestAppContext,
 ) {
@@ -1099,38 +1134,34 @@ async fn test_send_breakpoints_when_editor_has_been_saved(
     let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
     let workspace = init_test_workspace(&project, cx).await;
     let cx = &mut VisualTestContext::from_window(*workspace, cx);
-    let project_path = Path::new(path!("/project"));
-    let worktree = project
-        .update(cx, |project, cx| project.find_worktree(project_path, cx))
-        .expect("This worktree should exist in project")
-        .0;
 
-    let worktree_id = workspace
-        .update(cx, |_, _, cx| worktree.read(cx).id())
-        .unwrap();
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_single_thread_execution_requests: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
 
-    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
     let client = session.update(cx, |session, _| session.adapter_client().unwrap());
 
-    let buffer = project
-        .update(cx, |project, cx| {
-            project.open_buffer((worktree_id, "main.rs"), cx)
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![
+                dap::Thread {
+                    id: 1,
+                    name: "Thread 1".into(),
+                },
+                dap::Thread {
+                    id: 2,
+                    name: "Thread 2".into(),
+                },
+            ],
         })
-        .await
-        .unwrap();
-
-    let (editor, cx) = cx.add_window_view(|window, cx| {
-        Editor::new(
-            EditorMode::full(),
-            MultiBuffer::build_from_buffer(buffer, cx),
-            Some(project.clone()),
-            window,
-            cx,
-        )
     });
 
-    client.on_request::<Launch, _>(move |_, _| Ok(()));
-
     client.on_request::<StackTrace, _>(move |_, _| {
         Ok(dap::StackTraceResponse {
             stack_frames: Vec::default(),
@@ -1138,6 +1169,18 @@ async fn test_send_breakpoints_when_editor_has_been_saved(
         })
     });
 
+    let continued_threads = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Continue, _>({
+        let continued_threads = continued_threads.clone();
+        move |_, args| {
+            assert_eq!(args.single_thread, Some(true));
+            continued_threads.lock().push(args.thread_id);
+            Ok(dap::ContinueResponse {
+                all_threads_continued: Some(false),
+            })
+        }
+    });
+
     client
         .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
             reason: dap::StoppedEventReason::Pause,
@@ -1145,108 +1188,40 @@ async fn test_send_breakpoints_when_editor_has_been_saved(
             thread_id: Some(1),
             preserve_focus_hint: None,
             text: None,
-            all_threads_stopped: None,
+            all_threads_stopped: Some(true),
             hit_breakpoint_ids: None,
         }))
         .await;
 
-    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
-    client.on_request::<SetBreakpoints, _>({
-        let called_set_breakpoints = called_set_breakpoints.clone();
-        move |_, args| {
-            assert_eq!(path!("/project/main.rs"), args.source.path.unwrap());
-            assert_eq!(
-                vec![SourceBreakpoint {
-                    line: 2,
-                    column: None,
-                    condition: None,
-                    hit_condition: None,
-                    log_message: None,
-                    mode: None
-                }],
-                args.breakpoints.unwrap()
-            );
-            assert!(!args.source_modified.unwrap());
-
-            called_set_breakpoints.store(true, Ordering::SeqCst);
-
-            Ok(dap::SetBreakpointsResponse {
-                breakpoints: Vec::default(),
-            })
-        }
-    });
+    cx.run_until_parked();
 
-    editor.update_in(cx, |editor, window, cx| {
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    session.update(cx, |session, cx| {
+        assert_eq!(session.threads(cx).len(), 2);
+        assert!(session.supports_freezing_threads());
+        session.freeze_thread(ThreadId(2), cx);
+        session.continue_thread(ThreadId(1), cx);
     });
 
     cx.run_until_parked();
 
-    assert!(
-        called_set_breakpoints.load(std::sync::atomic::Ordering::SeqCst),
-        "SetBreakpoint request must be called"
-    );
-
-    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
-    client.on_request::<SetBreakpoints, _>({
-        let called_set_breakpoints = called_set_breakpoints.clone();
-        move |_, args| {
-            assert_eq!(path!("/project/main.rs"), args.source.path.unwrap());
-            assert_eq!(
-                vec![SourceBreakpoint {
-                    line: 3,
-                    column: None,
-                    condition: None,
-                    hit_condition: None,
-                    log_message: None,
-                    mode: None
-                }],
-                args.breakpoints.unwrap()
-            );
-            assert!(args.source_modified.unwrap());
-
-            called_set_breakpoints.store(true, Ordering::SeqCst);
-
-            Ok(dap::SetBreakpointsResponse {
-                breakpoints: Vec::default(),
-            })
-        }
+    assert_eq!(*continued_threads.lock(), vec![1]);
+    session.update(cx, |session, _| {
+        assert_eq!(session.thread_status(ThreadId(1)), ThreadStatus::Running);
+        assert_eq!(session.thread_status(ThreadId(2)), ThreadStatus::Stopped);
     });
 
-    editor.update_in(cx, |editor, window, cx| {
-        editor.move_up(&actions::MoveUp, window, cx);
-        editor.insert("new text\n", window, cx);
+    session.update(cx, |session, cx| {
+        session.thaw_thread(ThreadId(2), cx);
+        session.continue_thread(ThreadId(2), cx);
     });
 
-    editor
-        .update_in(cx, |editor, window, cx| {
-            editor.save(
-                SaveOptions {
-                    format: true,
-                    autosave: false,
-                },
-                project.clone(),
-                window,
-                cx,
-            )
-        })
-        .await
-        .unwrap();
-
     cx.run_until_parked();
 
-    assert!(
-        called_set_breakpoints.load(std::sync::atomic::Ordering::SeqCst),
-        "SetBreakpoint request must be called after editor is saved"
-    );
+    assert_eq!(*continued_threads.lock(), vec![1, 2]);
 }
 
 #[gpui::test]
-async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
-    executor: BackgroundExecutor,
-    cx: &mut TestAppContext,
-) {
+async fn test_jump_to_position_sends_goto(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx);
 
     let fs = FakeFs::new(executor.clone());
@@ -1255,8 +1230,6 @@ async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
         path!("/project"),
         json!({
             "main.rs": "First line\nSecond line\nThird line\nFourth line",
-            "second.rs": "First line\nSecond line\nThird line\nFourth line",
-            "no_breakpoints.rs": "Used to ensure that we don't unset breakpoint in files with no breakpoints"
         }),
     )
     .await;
@@ -1264,215 +1237,411 @@ async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
     let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
     let workspace = init_test_workspace(&project, cx).await;
     let cx = &mut VisualTestContext::from_window(*workspace, cx);
-    let project_path = Path::new(path!("/project"));
-    let worktree = project
-        .update(cx, |project, cx| project.find_worktree(project_path, cx))
-        .expect("This worktree should exist in project")
-        .0;
 
-    let worktree_id = workspace
-        .update(cx, |_, _, cx| worktree.read(cx).id())
-        .unwrap();
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_goto_targets_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
 
-    let first = project
-        .update(cx, |project, cx| {
-            project.open_buffer((worktree_id, "main.rs"), cx)
-        })
-        .await
-        .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
 
-    let second = project
-        .update(cx, |project, cx| {
-            project.open_buffer((worktree_id, "second.rs"), cx)
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
         })
-        .await
-        .unwrap();
+    });
 
-    let (first_editor, cx) = cx.add_window_view(|window, cx| {
-        Editor::new(
-            EditorMode::full(),
-            MultiBuffer::build_from_buffer(first, cx),
-            Some(project.clone()),
-            window,
-            cx,
-        )
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
     });
 
-    let (second_editor, cx) = cx.add_window_view(|window, cx| {
-        Editor::new(
-            EditorMode::full(),
-            MultiBuffer::build_from_buffer(second, cx),
-            Some(project.clone()),
-            window,
-            cx,
-        )
+    client.on_request::<GotoTargets, _>(move |_, args| {
+        assert_eq!(args.source.path.as_deref(), Some(path!("/project/main.rs")));
+        Ok(dap::GotoTargetsResponse {
+            targets: vec![dap::GotoTarget {
+                id: args.line * 10,
+                label: format!("line {}", args.line),
+                line: args.line,
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_pointer_reference: None,
+            }],
+        })
     });
 
-    first_editor.update_in(cx, |editor, window, cx| {
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    let gotos = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Goto, _>({
+        let gotos = gotos.clone();
+        move |_, args| {
+            gotos.lock().push((args.thread_id, args.target_id));
+            Ok(())
+        }
     });
 
-    second_editor.update_in(cx, |editor, window, cx| {
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.move_down(&actions::MoveDown, window, cx);
-        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let jump = session.update(cx, |session, cx| {
+        assert_eq!(session.threads(cx).len(), 1);
+        assert!(session.supports_goto());
+        session.jump_to_position(Path::new(path!("/project/main.rs")), 2, ThreadId(1), cx)
     });
+    cx.run_until_parked();
+    jump.await.unwrap();
 
-    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
-    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+    assert_eq!(*gotos.lock(), vec![(1, 30)]);
+}
 
-    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
+#[gpui::test]
+async fn test_stepping_granularity_is_sent_with_steps(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
 
-    client.on_request::<SetBreakpoints, _>({
-        let called_set_breakpoints = called_set_breakpoints.clone();
-        move |_, args| {
-            assert!(
-                args.breakpoints.is_none_or(|bps| bps.is_empty()),
-                "Send empty breakpoint sets to clear them from DAP servers"
-            );
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
 
-            match args
-                .source
-                .path
-                .expect("We should always send a breakpoint's path")
-                .as_str()
-            {
-                path!("/project/main.rs") | path!("/project/second.rs") => {}
-                _ => {
-                    panic!("Unset breakpoints for path that doesn't have any")
-                }
-            }
-
-            called_set_breakpoints.store(true, Ordering::SeqCst);
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
 
-            Ok(dap::SetBreakpointsResponse {
-                breakpoints: Vec::default(),
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_stepping_granularity: Some(true),
+                ..Default::default()
             })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    let granularities = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Next, _>({
+        let granularities = granularities.clone();
+        move |_, args| {
+            granularities.lock().push(args.granularity);
+            Ok(())
+        }
+    });
+    client.on_request::<StepIn, _>({
+        let granularities = granularities.clone();
+        move |_, args| {
+            granularities.lock().push(args.granularity);
+            Ok(())
         }
     });
 
-    cx.dispatch_action(crate::ClearAllBreakpoints);
+    let stopped = dap::StoppedEvent {
+        reason: dap::StoppedEventReason::Pause,
+        description: None,
+        thread_id: Some(1),
+        preserve_focus_hint: None,
+        text: None,
+        all_threads_stopped: None,
+        hit_breakpoint_ids: None,
+    };
+    client
+        .fake_event(dap::messages::Events::Stopped(stopped.clone()))
+        .await;
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .read_with(cx, |item, _| item.running_state().clone());
+
+    running_state.update(cx, |running_state, cx| {
+        assert_eq!(
+            running_state.stepping_granularity(cx),
+            dap::SteppingGranularity::Line
+        );
+        running_state.step_over(cx);
+    });
+    cx.run_until_parked();
+
+    client
+        .fake_event(dap::messages::Events::Stopped(stopped))
+        .await;
+    cx.run_until_parked();
+
+    running_state.update(cx, |running_state, cx| {
+        running_state.cycle_stepping_granularity(cx);
+        assert_eq!(
+            running_state.stepping_granularity(cx),
+            dap::SteppingGranularity::Instruction
+        );
+        running_state.step_in(cx);
+    });
     cx.run_until_parked();
+
+    assert_eq!(
+        *granularities.lock(),
+        vec![
+            Some(dap::SteppingGranularity::Line),
+            Some(dap::SteppingGranularity::Instruction),
+        ]
+    );
 }
 
 #[gpui::test]
-async fn test_debug_session_is_shutdown_when_attach_and_launch_request_fails(
+async fn test_stopped_event_is_kept_until_thread_resumes(
     executor: BackgroundExecutor,
     cx: &mut TestAppContext,
 ) {
     init_test(cx);
 
     let fs = FakeFs::new(executor.clone());
-
-    fs.insert_tree(
-        path!("/project"),
-        json!({
-            "main.rs": "First line\nSecond line\nThird line\nFourth line",
-        }),
-    )
-    .await;
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
 
     let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
     let workspace = init_test_workspace(&project, cx).await;
     let cx = &mut VisualTestContext::from_window(*workspace, cx);
 
-    start_debug_session(&workspace, cx, |client| {
-        client.on_request::<dap::requests::Initialize, _>(|_, _| {
-            Err(ErrorResponse {
-                error: Some(Message {
-                    format: "failed to launch".to_string(),
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![
+                dap::Thread {
                     id: 1,
-                    variables: None,
-                    send_telemetry: None,
-                    show_user: None,
-                    url: None,
-                    url_label: None,
-                }),
-            })
-        });
-    })
-    .ok();
+                    name: "Thread 1".into(),
+                },
+                dap::Thread {
+                    id: 2,
+                    name: "Thread 2".into(),
+                },
+            ],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Continue, _>(move |_, _| {
+        Ok(dap::ContinueResponse {
+            all_threads_continued: Some(false),
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Breakpoint,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        }))
+        .await;
 
     cx.run_until_parked();
 
-    project.update(cx, |project, cx| {
+    session.update(cx, |session, _| {
+        assert_eq!(
+            session
+                .stopped_event(ThreadId(1))
+                .map(|event| event.reason.clone()),
+            Some(dap::StoppedEventReason::Breakpoint)
+        );
         assert!(
-            project.dap_store().read(cx).sessions().count() == 0,
-            "Session wouldn't exist if it was shutdown"
+            session.stopped_event(ThreadId(2)).is_none(),
+            "Threads stopped along with another one have no stop reason of their own"
         );
     });
+
+    session.update(cx, |session, cx| session.continue_thread(ThreadId(1), cx));
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert!(session.stopped_event(ThreadId(1)).is_none());
+    });
 }
 
 #[gpui::test]
-async fn test_we_send_arguments_from_user_config(
+async fn test_restart_session_when_debuggee_exits_unexpectedly(
     executor: BackgroundExecutor,
     cx: &mut TestAppContext,
 ) {
     init_test(cx);
 
     let fs = FakeFs::new(executor.clone());
-
-    fs.insert_tree(
-        path!("/project"),
-        json!({
-            "main.rs": "First line\nSecond line\nThird line\nFourth line",
-        }),
-    )
-    .await;
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
 
     let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
     let workspace = init_test_workspace(&project, cx).await;
     let cx = &mut VisualTestContext::from_window(*workspace, cx);
-    let debug_definition = DebugTaskDefinition {
-        adapter: "fake-adapter".into(),
-        config: json!({
-            "request": "launch",
-            "program": "main.rs".to_owned(),
-            "args": vec!["arg1".to_owned(), "arg2".to_owned()],
-            "cwd": path!("/Random_path"),
-            "env": json!({ "KEY": "VALUE" }),
-        }),
-        label: "test".into(),
-        tcp_connection: None,
-    };
 
-    let launch_handler_called = Arc::new(AtomicBool::new(false));
+    let session = start_debug_session_with(
+        &workspace,
+        cx,
+        DebugTaskDefinition {
+            adapter: "fake-adapter".into(),
+            label: "test".into(),
+            config: json!({
+                "request": "launch",
+                "restartOnExit": true,
+                "maxRestarts": 1,
+            }),
+            tcp_connection: None,
+        },
+        |client| {
+            client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_restart_request: Some(true),
+                    ..Default::default()
+                })
+            });
+        },
+    )
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
 
-    start_debug_session_with(&workspace, cx, debug_definition.clone(), {
-        let debug_definition = debug_definition.clone();
-        let launch_handler_called = launch_handler_called.clone();
+    let restarts = Arc::new(AtomicUsize::new(0));
+    client.on_request::<dap::requests::Restart, _>({
+        let restarts = restarts.clone();
+        move |_, _| {
+            restarts.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    });
 
-        move |client| {
-            let debug_definition = debug_definition.clone();
-            let launch_handler_called = launch_handler_called.clone();
+    let exited = |exit_code| dap::messages::Events::Exited(dap::ExitedEvent { exit_code });
 
-            client.on_request::<dap::requests::Launch, _>(move |_, args| {
-                launch_handler_called.store(true, Ordering::SeqCst);
+    client.fake_event(exited(0)).await;
+    cx.run_until_parked();
+    executor.advance_clock(Duration::from_secs(5));
+    cx.run_until_parked();
+    assert_eq!(
+        restarts.load(Ordering::SeqCst),
+        0,
+        "A clean exit shouldn't restart the session"
+    );
 
-                assert_eq!(args.raw, debug_definition.config);
+    client.fake_event(exited(1)).await;
+    cx.run_until_parked();
+    executor.advance_clock(Duration::from_secs(5));
+    cx.run_until_parked();
+    assert_eq!(restarts.load(Ordering::SeqCst), 1);
+    session.update(cx, |session, _| {
+        assert_eq!(session.auto_restart_count(), 1);
+    });
 
-                Ok(())
-            });
-        }
-    })
-    .ok();
+    client.fake_event(exited(1)).await;
+    cx.run_until_parked();
+    executor.advance_clock(Duration::from_secs(5));
+    cx.run_until_parked();
+    assert_eq!(
+        restarts.load(Ordering::SeqCst),
+        1,
+        "The session shouldn't restart more than `maxRestarts` times"
+    );
+}
+
+#[gpui::test]
+async fn test_session_history_records_exit_code(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+    let debug_panel = workspace
+        .update(cx, |workspace, _, cx| workspace.panel::<DebugPanel>(cx))
+        .unwrap()
+        .unwrap();
+
+    debug_panel.update(cx, |debug_panel, _| {
+        let history = debug_panel.session_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].scenario.label, "test");
+        assert!(history[0].ended_at.is_none());
+    });
+
+    client
+        .fake_event(dap::messages::Events::Exited(dap::ExitedEvent {
+            exit_code: 3,
+        }))
+        .await;
+    cx.run_until_parked();
 
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+    shutdown_session.await.unwrap();
     cx.run_until_parked();
 
-    assert!(
-        launch_handler_called.load(Ordering::SeqCst),
-        "Launch request handler was not called"
-    );
+    debug_panel.update(cx, |debug_panel, _| {
+        let history = debug_panel.session_history();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].exit_code, Some(3));
+        assert!(history[0].ended_at.is_some());
+        assert_eq!(history[0].session_id, None);
+    });
 }
 
 #[gpui::test]
-async fn test_active_debug_line_setting(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+async fn test_send_breakpoints_when_editor_has_been_saved(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
     init_test(cx);
 
     let fs = FakeFs::new(executor.clone());
@@ -1481,7 +1650,6 @@ async fn test_active_debug_line_setting(executor: BackgroundExecutor, cx: &mut T
         path!("/project"),
         json!({
             "main.rs": "First line\nSecond line\nThird line\nFourth line",
-            "second.rs": "First line\nSecond line\nThird line\nFourth line",
         }),
     )
     .await;
@@ -1499,259 +1667,1387 @@ async fn test_active_debug_line_setting(executor: BackgroundExecutor, cx: &mut T
         .update(cx, |_, _, cx| worktree.read(cx).id())
         .unwrap();
 
-    let main_buffer = project
-        .update(cx, |project, cx| {
-            project.open_buffer((worktree_id, "main.rs"), cx)
-        })
-        .await
-        .unwrap();
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
 
-    let second_buffer = project
+    let buffer = project
         .update(cx, |project, cx| {
-            project.open_buffer((worktree_id, "second.rs"), cx)
+            project.open_buffer((worktree_id, "main.rs"), cx)
         })
         .await
         .unwrap();
 
-    let (main_editor, cx) = cx.add_window_view(|window, cx| {
+    let (editor, cx) = cx.add_window_view(|window, cx| {
         Editor::new(
             EditorMode::full(),
-            MultiBuffer::build_from_buffer(main_buffer, cx),
+            MultiBuffer::build_from_buffer(buffer, cx),
             Some(project.clone()),
             window,
             cx,
         )
     });
 
-    let (second_editor, cx) = cx.add_window_view(|window, cx| {
-        Editor::new(
-            EditorMode::full(),
-            MultiBuffer::build_from_buffer(second_buffer, cx),
-            Some(project.clone()),
-            window,
-            cx,
-        )
-    });
+    client.on_request::<Launch, _>(move |_, _| Ok(()));
 
-    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: Vec::default(),
+            total_frames: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
+    client.on_request::<SetBreakpoints, _>({
+        let called_set_breakpoints = called_set_breakpoints.clone();
+        move |_, args| {
+            assert_eq!(path!("/project/main.rs"), args.source.path.unwrap());
+            assert_eq!(
+                vec![SourceBreakpoint {
+                    line: 2,
+                    column: None,
+                    condition: None,
+                    hit_condition: None,
+                    log_message: None,
+                    mode: None
+                }],
+                args.breakpoints.unwrap()
+            );
+            assert!(!args.source_modified.unwrap());
+
+            called_set_breakpoints.store(true, Ordering::SeqCst);
+
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    cx.run_until_parked();
+
+    assert!(
+        called_set_breakpoints.load(std::sync::atomic::Ordering::SeqCst),
+        "SetBreakpoint request must be called"
+    );
+
+    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
+    client.on_request::<SetBreakpoints, _>({
+        let called_set_breakpoints = called_set_breakpoints.clone();
+        move |_, args| {
+            assert_eq!(path!("/project/main.rs"), args.source.path.unwrap());
+            assert_eq!(
+                vec![SourceBreakpoint {
+                    line: 3,
+                    column: None,
+                    condition: None,
+                    hit_condition: None,
+                    log_message: None,
+                    mode: None
+                }],
+                args.breakpoints.unwrap()
+            );
+            assert!(args.source_modified.unwrap());
+
+            called_set_breakpoints.store(true, Ordering::SeqCst);
+
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_up(&actions::MoveUp, window, cx);
+        editor.insert("new text\n", window, cx);
+    });
+
+    editor
+        .update_in(cx, |editor, window, cx| {
+            editor.save(
+                SaveOptions {
+                    format: true,
+                    autosave: false,
+                },
+                project.clone(),
+                window,
+                cx,
+            )
+        })
+        .await
+        .unwrap();
+
+    cx.run_until_parked();
+
+    assert!(
+        called_set_breakpoints.load(std::sync::atomic::Ordering::SeqCst),
+        "SetBreakpoint request must be called after editor is saved"
+    );
+}
+
+#[gpui::test]
+async fn test_breakpoint_verified_state_follows_adapter(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let project_path = Path::new(path!("/project"));
+    let worktree = project
+        .update(cx, |project, cx| project.find_worktree(project_path, cx))
+        .expect("This worktree should exist in project")
+        .0;
+
+    let worktree_id = workspace
+        .update(cx, |_, _, cx| worktree.read(cx).id())
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let session_id = session.read_with(cx, |session, _| session.session_id());
     let client = session.update(cx, |session, _| session.adapter_client().unwrap());
 
-    client.on_request::<dap::requests::Threads, _>(move |_, _| {
-        Ok(dap::ThreadsResponse {
-            threads: vec![dap::Thread {
-                id: 1,
-                name: "Thread 1".into(),
-            }],
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
         })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
     });
 
-    client.on_request::<dap::requests::Scopes, _>(move |_, _| {
-        Ok(dap::ScopesResponse {
-            scopes: Vec::default(),
+    client.on_request::<SetBreakpoints, _>(move |_, _| {
+        Ok(dap::SetBreakpointsResponse {
+            breakpoints: vec![dap::Breakpoint {
+                id: Some(1),
+                verified: false,
+                message: Some("Module not loaded yet".into()),
+                source: None,
+                line: Some(2),
+                column: None,
+                end_line: None,
+                end_column: None,
+                instruction_reference: None,
+                offset: None,
+                reason: None,
+            }],
         })
     });
 
-    client.on_request::<StackTrace, _>(move |_, args| {
-        assert_eq!(args.thread_id, 1);
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
 
-        Ok(dap::StackTraceResponse {
-            stack_frames: vec![dap::StackFrame {
-                id: 1,
-                name: "frame 1".into(),
-                source: Some(dap::Source {
-                    name: Some("main.rs".into()),
-                    path: Some(path!("/project/main.rs").into()),
-                    source_reference: None,
-                    presentation_hint: None,
-                    origin: None,
-                    sources: None,
-                    adapter_data: None,
-                    checksums: None,
-                }),
-                line: 2,
-                column: 0,
+    cx.run_until_parked();
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    let abs_path = Path::new(path!("/project/main.rs"));
+
+    let state = breakpoint_store
+        .read_with(cx, |store, cx| {
+            store.session_state_at_row(abs_path, 1, session_id, cx)
+        })
+        .expect("Breakpoint should have state for the running session");
+    assert!(!state.verified);
+    assert_eq!(state.message.as_deref(), Some("Module not loaded yet"));
+
+    client
+        .fake_event(dap::messages::Events::Breakpoint(dap::BreakpointEvent {
+            reason: dap::BreakpointEventReason::Changed,
+            breakpoint: dap::Breakpoint {
+                id: Some(1),
+                verified: true,
+                message: None,
+                source: None,
+                line: Some(2),
+                column: None,
                 end_line: None,
                 end_column: None,
-                can_restart: None,
-                instruction_pointer_reference: None,
-                module_id: None,
-                presentation_hint: None,
-            }],
-            total_frames: None,
+                instruction_reference: None,
+                offset: None,
+                reason: None,
+            },
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let state = breakpoint_store
+        .read_with(cx, |store, cx| {
+            store.session_state_at_row(abs_path, 1, session_id, cx)
+        })
+        .expect("Breakpoint should have state for the running session");
+    assert!(state.verified);
+    assert_eq!(state.message, None);
+}
+
+#[gpui::test]
+async fn test_breakpoints_snap_to_valid_locations(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {\n\n    // comment\n    let x = 1;\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let project_path = Path::new(path!("/project"));
+    let worktree = project
+        .update(cx, |project, cx| project.find_worktree(project_path, cx))
+        .expect("This worktree should exist in project")
+        .0;
+
+    let worktree_id = workspace
+        .update(cx, |_, _, cx| worktree.read(cx).id())
+        .unwrap();
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_breakpoint_locations_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
         })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer.clone(), cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    client.on_request::<BreakpointLocations, _>(move |_, args| {
+        assert!(args.line <= 4);
+        Ok(dap::BreakpointLocationsResponse {
+            breakpoints: vec![dap::BreakpointLocation {
+                line: 4,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let sent_breakpoints = Arc::new(parking_lot::Mutex::new(None));
+    client.on_request::<SetBreakpoints, _>({
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |_, args| {
+            *sent_breakpoints.lock() = args.breakpoints.map(|breakpoints| {
+                breakpoints
+                    .into_iter()
+                    .map(|breakpoint| (breakpoint.line, breakpoint.log_message))
+                    .collect::<Vec<_>>()
+            });
+
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    cx.run_until_parked();
+
+    assert_eq!(
+        sent_breakpoints.lock().take(),
+        Some(vec![(4, None)]),
+        "Breakpoint on a blank line should be snapped to the next executable line"
+    );
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    let abs_path = Path::new(path!("/project/main.rs"));
+    breakpoint_store.read_with(cx, |store, cx| {
+        assert!(store.breakpoint_at_row(abs_path, 1, cx).is_none());
+        assert!(store.breakpoint_at_row(abs_path, 3, cx).is_some());
+    });
+
+    let add_log_breakpoint = |row: u32, message: &str, cx: &mut VisualTestContext| {
+        breakpoint_store.update(cx, |store, cx| {
+            let position = buffer.read(cx).anchor_before(language::Point::new(row, 0));
+            store.toggle_breakpoint(
+                buffer.clone(),
+                BreakpointWithPosition {
+                    position,
+                    bp: Breakpoint::new_log(message),
+                },
+                BreakpointEditAction::Toggle,
+                cx,
+            );
+        });
+        cx.run_until_parked();
+    };
+
+    // Snapping onto an existing breakpoint carries the settings it doesn't have over to it
+    add_log_breakpoint(2, "hello", cx);
+    assert_eq!(
+        sent_breakpoints.lock().take(),
+        Some(vec![(4, Some("hello".to_string()))]),
+    );
+    breakpoint_store.read_with(cx, |store, cx| {
+        assert!(store.breakpoint_at_row(abs_path, 2, cx).is_none());
+        let (_, breakpoint) = store.breakpoint_at_row(abs_path, 3, cx).unwrap();
+        assert_eq!(breakpoint.bp, Breakpoint::new_log("hello"));
+    });
+
+    // ...and is skipped when that would overwrite one of them
+    add_log_breakpoint(1, "world", cx);
+    breakpoint_store.read_with(cx, |store, cx| {
+        let (_, breakpoint) = store.breakpoint_at_row(abs_path, 1, cx).unwrap();
+        assert_eq!(breakpoint.bp, Breakpoint::new_log("world"));
+        let (_, breakpoint) = store.breakpoint_at_row(abs_path, 3, cx).unwrap();
+        assert_eq!(breakpoint.bp, Breakpoint::new_log("hello"));
+    });
+}
+
+#[gpui::test]
+async fn test_unsetting_breakpoints_on_clear_breakpoint_action(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+            "second.rs": "First line\nSecond line\nThird line\nFourth line",
+            "no_breakpoints.rs": "Used to ensure that we don't unset breakpoint in files with no breakpoints"
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let project_path = Path::new(path!("/project"));
+    let worktree = project
+        .update(cx, |project, cx| project.find_worktree(project_path, cx))
+        .expect("This worktree should exist in project")
+        .0;
+
+    let worktree_id = workspace
+        .update(cx, |_, _, cx| worktree.read(cx).id())
+        .unwrap();
+
+    let first = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let second = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "second.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (first_editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(first, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let (second_editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(second, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    first_editor.update_in(cx, |editor, window, cx| {
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    second_editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.move_down(&actions::MoveDown, window, cx);
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let called_set_breakpoints = Arc::new(AtomicBool::new(false));
+
+    client.on_request::<SetBreakpoints, _>({
+        let called_set_breakpoints = called_set_breakpoints.clone();
+        move |_, args| {
+            assert!(
+                args.breakpoints.is_none_or(|bps| bps.is_empty()),
+                "Send empty breakpoint sets to clear them from DAP servers"
+            );
+
+            match args
+                .source
+                .path
+                .expect("We should always send a breakpoint's path")
+                .as_str()
+            {
+                path!("/project/main.rs") | path!("/project/second.rs") => {}
+                _ => {
+                    panic!("Unset breakpoints for path that doesn't have any")
+                }
+            }
+
+            called_set_breakpoints.store(true, Ordering::SeqCst);
+
+            Ok(dap::SetBreakpointsResponse {
+                breakpoints: Vec::default(),
+            })
+        }
+    });
+
+    cx.dispatch_action(crate::ClearAllBreakpoints);
+    cx.run_until_parked();
+}
+
+#[gpui::test]
+async fn test_debug_session_is_shutdown_when_attach_and_launch_request_fails(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(|_, _| {
+            Err(ErrorResponse {
+                error: Some(Message {
+                    format: "failed to launch".to_string(),
+                    id: 1,
+                    variables: None,
+                    send_telemetry: None,
+                    show_user: None,
+                    url: None,
+                    url_label: None,
+                }),
+            })
+        });
+    })
+    .ok();
+
+    cx.run_until_parked();
+
+    project.update(cx, |project, cx| {
+        assert!(
+            project.dap_store().read(cx).sessions().count() == 0,
+            "Session wouldn't exist if it was shutdown"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_we_send_arguments_from_user_config(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let debug_definition = DebugTaskDefinition {
+        adapter: "fake-adapter".into(),
+        config: json!({
+            "request": "launch",
+            "program": "main.rs".to_owned(),
+            "args": vec!["arg1".to_owned(), "arg2".to_owned()],
+            "cwd": path!("/Random_path"),
+            "env": json!({ "KEY": "VALUE" }),
+        }),
+        label: "test".into(),
+        tcp_connection: None,
+    };
+
+    let launch_handler_called = Arc::new(AtomicBool::new(false));
+
+    start_debug_session_with(&workspace, cx, debug_definition.clone(), {
+        let debug_definition = debug_definition.clone();
+        let launch_handler_called = launch_handler_called.clone();
+
+        move |client| {
+            let debug_definition = debug_definition.clone();
+            let launch_handler_called = launch_handler_called.clone();
+
+            client.on_request::<dap::requests::Launch, _>(move |_, args| {
+                launch_handler_called.store(true, Ordering::SeqCst);
+
+                assert_eq!(args.raw, debug_definition.config);
+
+                Ok(())
+            });
+        }
+    })
+    .ok();
+
+    cx.run_until_parked();
+
+    assert!(
+        launch_handler_called.load(Ordering::SeqCst),
+        "Launch request handler was not called"
+    );
+}
+
+#[gpui::test]
+async fn test_active_debug_line_setting(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+            "second.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let project_path = Path::new(path!("/project"));
+    let worktree = project
+        .update(cx, |project, cx| project.find_worktree(project_path, cx))
+        .expect("This worktree should exist in project")
+        .0;
+
+    let worktree_id = workspace
+        .update(cx, |_, _, cx| worktree.read(cx).id())
+        .unwrap();
+
+    let main_buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let second_buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "second.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (main_editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(main_buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let (second_editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(second_buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<dap::requests::Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: Vec::default(),
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, args| {
+        assert_eq!(args.thread_id, 1);
+
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![dap::StackFrame {
+                id: 1,
+                name: "frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("main.rs".into()),
+                    path: Some(path!("/project/main.rs").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 2,
+                column: 0,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Breakpoint,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    main_editor.update_in(cx, |editor, window, cx| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert_eq!(
+            active_debug_lines.len(),
+            1,
+            "There should be only one active debug line"
+        );
+
+        let point = editor
+            .snapshot(window, cx)
+            .buffer_snapshot
+            .summary_for_anchor::<language::Point>(&active_debug_lines.first().unwrap().0.start);
+
+        assert_eq!(point.row, 1);
+    });
+
+    second_editor.update(cx, |editor, _| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert!(
+            active_debug_lines.is_empty(),
+            "There shouldn't be any active debug lines"
+        );
+    });
+
+    let handled_second_stacktrace = Arc::new(AtomicBool::new(false));
+    client.on_request::<StackTrace, _>({
+        let handled_second_stacktrace = handled_second_stacktrace.clone();
+        move |_, args| {
+            handled_second_stacktrace.store(true, Ordering::SeqCst);
+            assert_eq!(args.thread_id, 1);
+
+            Ok(dap::StackTraceResponse {
+                stack_frames: vec![dap::StackFrame {
+                    id: 2,
+                    name: "frame 2".into(),
+                    source: Some(dap::Source {
+                        name: Some("second.rs".into()),
+                        path: Some(path!("/project/second.rs").into()),
+                        source_reference: None,
+                        presentation_hint: None,
+                        origin: None,
+                        sources: None,
+                        adapter_data: None,
+                        checksums: None,
+                    }),
+                    line: 3,
+                    column: 0,
+                    end_line: None,
+                    end_column: None,
+                    can_restart: None,
+                    instruction_pointer_reference: None,
+                    module_id: None,
+                    presentation_hint: None,
+                }],
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Breakpoint,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    second_editor.update_in(cx, |editor, window, cx| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert_eq!(
+            active_debug_lines.len(),
+            1,
+            "There should be only one active debug line"
+        );
+
+        let point = editor
+            .snapshot(window, cx)
+            .buffer_snapshot
+            .summary_for_anchor::<language::Point>(&active_debug_lines.first().unwrap().0.start);
+
+        assert_eq!(point.row, 2);
+    });
+
+    main_editor.update(cx, |editor, _| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert!(
+            active_debug_lines.is_empty(),
+            "There shouldn't be any active debug lines"
+        );
+    });
+
+    assert!(
+        handled_second_stacktrace.load(Ordering::SeqCst),
+        "Second stacktrace request handler was not called"
+    );
+
+    client
+        .fake_event(dap::messages::Events::Continued(dap::ContinuedEvent {
+            thread_id: 0,
+            all_threads_continued: Some(true),
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    second_editor.update(cx, |editor, _| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert!(
+            active_debug_lines.is_empty(),
+            "There shouldn't be any active debug lines"
+        );
+    });
+
+    main_editor.update(cx, |editor, _| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert!(
+            active_debug_lines.is_empty(),
+            "There shouldn't be any active debug lines"
+        );
+    });
+
+    // Clean up
+    let shutdown_session = project.update(cx, |project, cx| {
+        project.dap_store().update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+    });
+
+    shutdown_session.await.unwrap();
+
+    main_editor.update(cx, |editor, _| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert!(
+            active_debug_lines.is_empty(),
+            "There shouldn't be any active debug lines after session shutdown"
+        );
+    });
+
+    second_editor.update(cx, |editor, _| {
+        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+
+        assert!(
+            active_debug_lines.is_empty(),
+            "There shouldn't be any active debug lines after session shutdown"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_stop_session_leaving_debuggee_running(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                support_terminate_debuggee: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert!(session.supports_stop_mode(StopMode::Terminate));
+        assert!(session.supports_stop_mode(StopMode::Disconnect));
+        assert!(!session.supports_stop_mode(StopMode::Suspend));
+        assert_eq!(session.default_stop_mode(), StopMode::Terminate);
+    });
+
+    let disconnect_arguments = Arc::new(parking_lot::Mutex::new(None));
+    client.on_request::<Disconnect, _>({
+        let disconnect_arguments = disconnect_arguments.clone();
+        move |_, args| {
+            *disconnect_arguments.lock() = Some((args.terminate_debuggee, args.suspend_debuggee));
+            Ok(())
+        }
+    });
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .update(cx, |item, _| item.running_state().clone());
+    running_state.update(cx, |running_state, cx| {
+        running_state.stop_with_mode(StopMode::Disconnect, cx)
+    });
+
+    cx.run_until_parked();
+
+    assert_eq!(
+        *disconnect_arguments.lock(),
+        Some((Some(false), Some(false)))
+    );
+    assert!(session.read_with(cx, |session, _| session.is_terminated()));
+}
+
+#[gpui::test]
+async fn test_hot_reload_sends_adapter_request(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    cx.run_until_parked();
+
+    session.update(cx, |session, cx| {
+        assert_eq!(
+            session.hot_reload_request(HotReloadKind::Reload, cx),
+            Some("hotReload".to_string())
+        );
+        assert_eq!(session.hot_reload_request(HotReloadKind::Restart, cx), None);
+    });
+
+    enum HotReloadRequest {}
+
+    impl dap::requests::Request for HotReloadRequest {
+        type Arguments = Option<serde_json::Value>;
+        type Response = ();
+        const COMMAND: &'static str = "hotReload";
+    }
+
+    let called_hot_reload = Arc::new(AtomicBool::new(false));
+    client.on_request::<HotReloadRequest, _>({
+        let called_hot_reload = called_hot_reload.clone();
+        move |_, _| {
+            called_hot_reload.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    cx.dispatch_action(HotReload);
+    cx.run_until_parked();
+
+    assert!(
+        called_hot_reload.load(Ordering::SeqCst),
+        "Hot reload should send the adapter's hot reload request"
+    );
+}
+
+#[test]
+fn test_launch_groups_in_session_tree() {
+    use crate::session::{LaunchGroup, session_tree};
+
+    let group = LaunchGroup {
+        id: 0,
+        label: "Servers".into(),
+        coupled: false,
+    };
+    let sessions = [
+        (SessionId(1), None),
+        (SessionId(2), None),
+        (SessionId(3), Some(SessionId(2))),
+        (SessionId(4), None),
+    ];
+    let groups =
+        collections::HashMap::from_iter([(SessionId(2), group.clone()), (SessionId(4), group)]);
+
+    assert_eq!(
+        session_tree(&sessions, &groups)
+            .into_iter()
+            .map(|node| (node.depth, node.is_last_child, node.group_label))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, false, None),
+            (1, false, Some("Servers".into())),
+            (2, true, None),
+            (1, true, None),
+        ]
+    );
+}
+
+#[gpui::test]
+async fn test_ended_sessions_are_closed_after_timeout(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<settings::SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<dap::debugger_settings::DebuggerSettings>(
+                cx,
+                |settings| {
+                    settings.close_ended_sessions_after_minutes = Some(5);
+                },
+            );
+        });
     });
 
-    client
-        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
-            reason: dap::StoppedEventReason::Breakpoint,
-            description: None,
-            thread_id: Some(1),
-            preserve_focus_hint: None,
-            text: None,
-            all_threads_stopped: None,
-            hit_breakpoint_ids: None,
-        }))
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
         .await;
 
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+    client.on_request::<Disconnect, _>(move |_, _| Ok(()));
+
     cx.run_until_parked();
 
-    main_editor.update_in(cx, |editor, window, cx| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+    let dap_store = project.update(cx, |project, _| project.dap_store());
+    dap_store
+        .update(cx, |dap_store, cx| {
+            dap_store.shutdown_session(session.read(cx).session_id(), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
 
-        assert_eq!(
-            active_debug_lines.len(),
-            1,
-            "There should be only one active debug line"
-        );
+    let debug_panel = workspace
+        .update(cx, |workspace, _, cx| workspace.panel::<DebugPanel>(cx))
+        .unwrap()
+        .unwrap();
+    let session_count = |cx: &mut VisualTestContext| {
+        debug_panel.read_with(cx, |debug_panel, _| debug_panel.sessions().len())
+    };
+    assert_eq!(session_count(cx), 1, "Ended sessions are kept for a while");
 
-        let point = editor
-            .snapshot(window, cx)
-            .buffer_snapshot
-            .summary_for_anchor::<language::Point>(&active_debug_lines.first().unwrap().0.start);
+    executor.advance_clock(Duration::from_secs(4 * 60));
+    cx.run_until_parked();
+    assert_eq!(session_count(cx), 1);
 
-        assert_eq!(point.row, 1);
-    });
+    executor.advance_clock(Duration::from_secs(60));
+    cx.run_until_parked();
+    assert_eq!(
+        session_count(cx),
+        0,
+        "Ended sessions should be closed once the timeout passes"
+    );
+}
 
-    second_editor.update(cx, |editor, _| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+#[gpui::test]
+async fn test_progress_events_are_tracked_and_cancellable(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
 
-        assert!(
-            active_debug_lines.is_empty(),
-            "There shouldn't be any active debug lines"
-        );
-    });
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "" }))
+        .await;
 
-    let handled_second_stacktrace = Arc::new(AtomicBool::new(false));
-    client.on_request::<StackTrace, _>({
-        let handled_second_stacktrace = handled_second_stacktrace.clone();
-        move |_, args| {
-            handled_second_stacktrace.store(true, Ordering::SeqCst);
-            assert_eq!(args.thread_id, 1);
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
 
-            Ok(dap::StackTraceResponse {
-                stack_frames: vec![dap::StackFrame {
-                    id: 2,
-                    name: "frame 2".into(),
-                    source: Some(dap::Source {
-                        name: Some("second.rs".into()),
-                        path: Some(path!("/project/second.rs").into()),
-                        source_reference: None,
-                        presentation_hint: None,
-                        origin: None,
-                        sources: None,
-                        adapter_data: None,
-                        checksums: None,
-                    }),
-                    line: 3,
-                    column: 0,
-                    end_line: None,
-                    end_column: None,
-                    can_restart: None,
-                    instruction_pointer_reference: None,
-                    module_id: None,
-                    presentation_hint: None,
-                }],
-                total_frames: None,
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_cancel_request: Some(true),
+                ..Default::default()
             })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let cancelled = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Cancel, _>({
+        let cancelled = cancelled.clone();
+        move |_, args| {
+            cancelled.lock().extend(args.progress_id);
+            Ok(())
         }
     });
 
     client
-        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
-            reason: dap::StoppedEventReason::Breakpoint,
-            description: None,
-            thread_id: Some(1),
-            preserve_focus_hint: None,
-            text: None,
-            all_threads_stopped: None,
-            hit_breakpoint_ids: None,
-        }))
+        .fake_event(dap::messages::Events::ProgressStart(
+            dap::ProgressStartEvent {
+                progress_id: "symbols".into(),
+                title: "Loading symbols".into(),
+                request_id: None,
+                cancellable: Some(true),
+                message: None,
+                percentage: None,
+            },
+        ))
+        .await;
+    client
+        .fake_event(dap::messages::Events::ProgressUpdate(
+            dap::ProgressUpdateEvent {
+                progress_id: "symbols".into(),
+                message: Some("libfoo.so".into()),
+                percentage: None,
+            },
+        ))
         .await;
-
     cx.run_until_parked();
 
-    second_editor.update_in(cx, |editor, window, cx| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+    session.update(cx, |session, _| {
+        let progress = session.progress().collect::<Vec<_>>();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].title, "Loading symbols");
+        assert_eq!(progress[0].message.as_deref(), Some("libfoo.so"));
+        assert!(session.can_cancel_progress("symbols"));
+    });
 
-        assert_eq!(
-            active_debug_lines.len(),
-            1,
-            "There should be only one active debug line"
-        );
+    session.update(cx, |session, cx| {
+        session.cancel_progress("symbols".into(), cx)
+    });
+    cx.run_until_parked();
+    assert_eq!(*cancelled.lock(), vec!["symbols".to_string()]);
 
-        let point = editor
-            .snapshot(window, cx)
-            .buffer_snapshot
-            .summary_for_anchor::<language::Point>(&active_debug_lines.first().unwrap().0.start);
+    client
+        .fake_event(dap::messages::Events::ProgressEnd(dap::ProgressEndEvent {
+            progress_id: "symbols".into(),
+            message: None,
+        }))
+        .await;
+    cx.run_until_parked();
 
-        assert_eq!(point.row, 2);
+    session.update(cx, |session, _| {
+        assert_eq!(session.progress().count(), 0);
     });
+}
 
-    main_editor.update(cx, |editor, _| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+#[gpui::test]
+async fn test_coupled_sessions_attach_to_debuggee(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
 
-        assert!(
-            active_debug_lines.is_empty(),
-            "There shouldn't be any active debug lines"
-        );
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.py": "print()" }))
+        .await;
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let attached_with = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let _subscription = project::debugger::test::intercept_debug_sessions(cx, {
+        let attached_with = attached_with.clone();
+        move |client| {
+            let attached_with = attached_with.clone();
+            client.on_request::<dap::requests::Attach, _>(move |_, args| {
+                attached_with.lock().push(args.raw);
+                Ok(())
+            });
+        }
     });
 
-    assert!(
-        handled_second_stacktrace.load(Ordering::SeqCst),
-        "Second stacktrace request handler was not called"
-    );
+    let scenario = task::DebugScenario {
+        adapter: "fake-adapter".into(),
+        label: "Mixed".into(),
+        build: None,
+        config: json!({
+            "request": "launch",
+            "coupled": [
+                {
+                    "adapter": "fake-adapter",
+                    "label": "Native",
+                    "request": "attach",
+                    "processId": "${pid}"
+                }
+            ]
+        }),
+        tcp_connection: None,
+        wait_for: None,
+    };
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.start_debug_session(scenario, task::TaskContext::default(), None, window, cx)
+        })
+        .unwrap();
+    cx.run_until_parked();
+
+    let sessions = |cx: &mut VisualTestContext| {
+        project.read_with(cx, |project, cx| {
+            project
+                .dap_store()
+                .read(cx)
+                .sessions()
+                .map(|session| (session.read(cx).label(), session.clone()))
+                .collect::<collections::HashMap<_, _>>()
+        })
+    };
+    let primary = sessions(cx)
+        .remove("Mixed: fake-adapter")
+        .expect("the scenario's own session should start right away");
+    assert_eq!(sessions(cx).len(), 1);
+    assert!(attached_with.lock().is_empty());
 
+    let client = primary.update(cx, |session, _| session.adapter_client().unwrap());
     client
-        .fake_event(dap::messages::Events::Continued(dap::ContinuedEvent {
-            thread_id: 0,
-            all_threads_continued: Some(true),
+        .fake_event(dap::messages::Events::Process(dap::ProcessEvent {
+            name: "main.py".into(),
+            system_process_id: Some(42),
+            is_local_process: Some(true),
+            start_method: None,
+            pointer_size: None,
         }))
         .await;
-
     cx.run_until_parked();
 
-    second_editor.update(cx, |editor, _| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+    assert_eq!(
+        *attached_with.lock(),
+        vec![json!({ "request": "attach", "processId": 42 })]
+    );
+    let coupled = sessions(cx)
+        .remove("Native")
+        .expect("the coupled session should start once the debuggee is known");
 
-        assert!(
-            active_debug_lines.is_empty(),
-            "There shouldn't be any active debug lines"
-        );
-    });
+    let panel = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.panel::<DebugPanel>(cx).unwrap()
+        })
+        .unwrap();
+    let primary_id = primary.read_with(cx, |session, _| session.session_id());
+    panel.update(cx, |panel, cx| panel.stop_coupled_sessions(primary_id, cx));
+    cx.run_until_parked();
 
-    main_editor.update(cx, |editor, _| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+    assert!(primary.read_with(cx, |session, _| session.is_terminated()));
+    assert!(coupled.read_with(cx, |session, _| session.is_terminated()));
+}
 
-        assert!(
-            active_debug_lines.is_empty(),
-            "There shouldn't be any active debug lines"
-        );
-    });
+#[gpui::test]
+async fn test_adapter_failure_opens_adapter_log(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
 
-    // Clean up
-    let shutdown_session = project.update(cx, |project, cx| {
-        project.dap_store().update(cx, |dap_store, cx| {
-            dap_store.shutdown_session(session.read(cx).session_id(), cx)
-        })
-    });
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({ "main.rs": "fn main() {}" }))
+        .await;
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
 
-    shutdown_session.await.unwrap();
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    cx.run_until_parked();
 
-    main_editor.update(cx, |editor, _| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
+    session.update(cx, |session, cx| {
+        session.push_adapter_log("Traceback (most recent call last):", cx);
+        session.adapter_failed(&anyhow::anyhow!("debug adapter exited with code 1"), cx);
+    });
+    cx.run_until_parked();
 
-        assert!(
-            active_debug_lines.is_empty(),
-            "There shouldn't be any active debug lines after session shutdown"
+    session.read_with(cx, |session, _| {
+        assert_eq!(
+            session
+                .adapter_log()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "Traceback (most recent call last):",
+                "error: debug adapter exited with code 1",
+            ]
         );
     });
-
-    second_editor.update(cx, |editor, _| {
-        let active_debug_lines: Vec<_> = editor.highlighted_rows::<ActiveDebugLine>().collect();
-
-        assert!(
-            active_debug_lines.is_empty(),
-            "There shouldn't be any active debug lines after session shutdown"
+    let running_state = active_debug_session_panel(workspace, cx)
+        .read_with(cx, |session, _| session.running_state().clone());
+    running_state.read_with(cx, |running_state, cx| {
+        assert_eq!(
+            running_state
+                .pane_items_status(cx)
+                .get(&DebuggerPaneItem::AdapterLog),
+            Some(&true)
         );
     });
 }
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_external_terminal_shell_script() {
+    use crate::session::running::external_terminal::shell_script;
+
+    let env = collections::HashMap::from_iter([
+        ("RUST_LOG".to_string(), "debug".to_string()),
+        ("GREETING".to_string(), "hello world".to_string()),
+    ]);
+    let script = shell_script(
+        &["./my app".to_string(), "--flag".to_string()],
+        Some(Path::new("/project dir")),
+        &env,
+    )
+    .unwrap();
+    assert!(script.starts_with("cd '/project dir' && env "));
+    assert!(script.contains("'GREETING=hello world'"));
+    assert!(script.contains("RUST_LOG=debug"));
+    assert!(script.contains(" './my app' --flag; "));
+    assert!(script.ends_with("read _"));
+
+    let script = shell_script(&["node".to_string()], None, &Default::default()).unwrap();
+    assert!(script.starts_with("node; "));
+}
@@ -0,0 +1,469 @@
+use crate::{
+    StepOver,
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    DisassembledInstruction, StackFrame, SteppingGranularity, StoppedEvent,
+    requests::{Disassemble, Next, SetInstructionBreakpoints, StackTrace},
+};
+use gpui::{BackgroundExecutor, Focusable as _, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::sync::Arc;
+use util::path;
+
+#[gpui::test]
+async fn test_disassembly_view_follows_instruction_pointer(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return helper(); }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                supports_stepping_granularity: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x100003f71".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let instruction =
+        |address: &str, symbol: Option<&str>, instruction: &str| DisassembledInstruction {
+            address: address.into(),
+            instruction_bytes: None,
+            instruction: instruction.into(),
+            symbol: symbol.map(Into::into),
+            location: None,
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            presentation_hint: None,
+        };
+    client.on_request::<Disassemble, _>(move |_, args| {
+        assert_eq!(args.memory_reference, "0x100003f71");
+        assert!(args.instruction_offset.is_some_and(|offset| offset < 0));
+        Ok(dap::DisassembleResponse {
+            instructions: vec![
+                instruction("0x0000000100003f70", Some("main"), "push rbp"),
+                instruction("0x0000000100003f71", None, "mov rbp, rsp"),
+                instruction("0x0000000100003f74", Some("main"), "call 0x100003f90"),
+                instruction("0x0000000100003f90", Some("helper"), "ret"),
+            ],
+        })
+    });
+
+    let granularities = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Next, _>({
+        let granularities = granularities.clone();
+        move |_, args| {
+            granularities.lock().push(args.granularity);
+            Ok(())
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.ensure_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    let disassembly_view = running_state.update(cx, |state, _| state.disassembly_view().clone());
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        pretty_assertions::assert_eq!(
+            vec![
+                "main:",
+                "  0x0000000100003f70 push rbp",
+                "> 0x0000000100003f71 mov rbp, rsp",
+                "  0x0000000100003f74 call 0x100003f90",
+                "helper:",
+                "  0x0000000100003f90 ret",
+            ],
+            disassembly_view.visual_entries(cx)
+        );
+    });
+
+    // Steps go by instruction while the disassembly is focused.
+    disassembly_view.update_in(cx, |disassembly_view, window, cx| {
+        window.focus(&disassembly_view.focus_handle(cx));
+    });
+    cx.dispatch_action(StepOver);
+    cx.run_until_parked();
+
+    assert_eq!(
+        *granularities.lock(),
+        vec![Some(SteppingGranularity::Instruction)]
+    );
+}
+
+#[gpui::test]
+async fn test_instruction_breakpoints_toggle_from_disassembly(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                supports_instruction_breakpoints: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x100003f70".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Disassemble, _>(move |_, _| {
+        let instruction = |address: &str, instruction: &str| DisassembledInstruction {
+            address: address.into(),
+            instruction_bytes: None,
+            instruction: instruction.into(),
+            symbol: None,
+            location: None,
+            line: None,
+            column: None,
+            end_line: None,
+            end_column: None,
+            presentation_hint: None,
+        };
+        Ok(dap::DisassembleResponse {
+            instructions: vec![
+                instruction("0x100003f70", "push rbp"),
+                instruction("0x100003f71", "mov rbp, rsp"),
+            ],
+        })
+    });
+
+    let sent_breakpoints = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<SetInstructionBreakpoints, _>({
+        let sent_breakpoints = sent_breakpoints.clone();
+        move |_, args| {
+            let addresses = args
+                .breakpoints
+                .into_iter()
+                .map(|breakpoint| breakpoint.instruction_reference)
+                .collect::<Vec<_>>();
+            sent_breakpoints.lock().push(addresses);
+            Ok(dap::SetInstructionBreakpointsResponse {
+                breakpoints: Vec::new(),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.ensure_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    let disassembly_view = running_state.update(cx, |state, _| state.disassembly_view().clone());
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        disassembly_view.toggle_instruction_breakpoint_at(1, cx);
+    });
+    cx.run_until_parked();
+
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        pretty_assertions::assert_eq!(
+            vec!["> 0x100003f70 push rbp", "* 0x100003f71 mov rbp, rsp"],
+            disassembly_view.visual_entries(cx)
+        );
+    });
+
+    // Ignoring breakpoints clears them in the adapter without forgetting them.
+    session.update(cx, |session, cx| {
+        session.toggle_ignore_breakpoints(cx).detach();
+    });
+    cx.run_until_parked();
+
+    disassembly_view.update(cx, |disassembly_view, cx| {
+        disassembly_view.toggle_instruction_breakpoint_at(1, cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        *sent_breakpoints.lock(),
+        vec![vec!["0x100003f71".to_string()], Vec::new(), Vec::new()]
+    );
+    session.update(cx, |session, _| {
+        assert_eq!(session.instruction_breakpoints().count(), 0);
+    });
+}
+
+#[gpui::test]
+async fn test_open_disassembly_at_frame_in_function(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int helper() {\n  return 1;\n}\nint main() {\n  return helper();\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_disassemble_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    let source = dap::Source {
+        name: Some("main.c".into()),
+        path: Some(path!("/project/main.c").into()),
+        source_reference: None,
+        presentation_hint: None,
+        origin: None,
+        sources: None,
+        adapter_data: None,
+        checksums: None,
+    };
+    let frame = |id: u64, name: &str, line: u64, address: &str| StackFrame {
+        id,
+        name: name.into(),
+        source: Some(source.clone()),
+        line,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference: Some(address.into()),
+        module_id: None,
+        presentation_hint: None,
+    };
+    let stack_frames = vec![
+        frame(1, "helper", 2, "0x100003f90"),
+        frame(2, "main", 5, "0x100003f74"),
+    ];
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: stack_frames.clone(),
+            total_frames: None,
+        })
+    });
+
+    let disassembled = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    client.on_request::<Disassemble, _>({
+        let disassembled = disassembled.clone();
+        move |_, args| {
+            disassembled.lock().push(args.memory_reference);
+            Ok(dap::DisassembleResponse {
+                instructions: Vec::new(),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    // `main` spans rows 3 to 5, and is the caller of the opened frame.
+    running_state
+        .update_in(cx, |state, window, cx| {
+            state.open_disassembly_at(path!("/project/main.c").as_ref(), 3..6, window, cx)
+        })
+        .await
+        .unwrap();
+    running_state.update(cx, |_, cx| cx.refresh_windows());
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        assert_eq!(state.selected_stack_frame_id(cx), Some(2));
+    });
+    assert_eq!(
+        disassembled.lock().last().map(String::as_str),
+        Some("0x100003f74")
+    );
+
+    let error = running_state
+        .update_in(cx, |state, window, cx| {
+            state.open_disassembly_at(path!("/project/other.c").as_ref(), 0..3, window, cx)
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "None of the stack frames are in the function at the cursor"
+    );
+}
@@ -0,0 +1,160 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use dap::{
+    StackFrame,
+    debugger_settings::DebuggerSettings,
+    requests::{Scopes, StackTrace, StepOut, Threads},
+};
+use editor::{Addon as _, Editor};
+use gpui::{BackgroundExecutor, Modifiers, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use settings::SettingsStore;
+use util::path;
+
+use crate::{
+    floating_controls::FloatingControls,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+
+#[gpui::test]
+async fn test_floating_controls(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+    set_floating_controls(true, cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {\n    one();\n}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.rs".into()),
+                    path: Some(path!("/project/main.rs").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 2,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+    let stepped_out = Arc::new(AtomicBool::new(false));
+    client.on_request::<StepOut, _>({
+        let stepped_out = stepped_out.clone();
+        move |_, args| {
+            assert_eq!(args.thread_id, 1);
+            stepped_out.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    // Selecting the stopped thread opens its frame in an editor, which becomes the active item.
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+    cx.run_until_parked();
+
+    let editor = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_item_as::<Editor>(cx).unwrap()
+        })
+        .unwrap();
+    let shows_controls = |cx: &mut VisualTestContext| {
+        cx.update(|window, cx| {
+            editor
+                .read(cx)
+                .addon::<FloatingControls>()
+                .unwrap()
+                .render_overlay(window, cx)
+                .is_some()
+        })
+    };
+    assert!(shows_controls(cx));
+
+    set_floating_controls(false, cx);
+    assert!(!shows_controls(cx));
+    set_floating_controls(true, cx);
+
+    // With the debug panel out of the way, the controls are the only ones drawn.
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.close_all_docks(window, cx);
+        })
+        .unwrap();
+    cx.run_until_parked();
+
+    let step_out = cx.debug_bounds("ICON-ArrowUpRight").unwrap();
+    cx.simulate_click(step_out.center(), Modifiers::none());
+    cx.run_until_parked();
+    assert!(stepped_out.load(Ordering::SeqCst));
+}
+
+fn set_floating_controls(enabled: bool, cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.floating_controls.enabled = enabled;
+            });
+        });
+    });
+}
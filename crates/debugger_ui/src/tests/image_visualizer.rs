@@ -0,0 +1,213 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    session::running::image_visualizer::{ImageSource, PixelFormat, parse_byte, pixels_to_bgra},
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    Scope, StackFrame, StoppedEvent,
+    requests::{Initialize, ReadMemory, Scopes, StackTrace, Variables},
+};
+use gpui::{BackgroundExecutor, Point, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use util::path;
+
+#[gpui::test]
+async fn test_view_memory_as_image(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Locals".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: Vec::new(),
+        })
+    });
+
+    client.on_request::<ReadMemory, _>(move |_, args| {
+        assert_eq!("0x1000", args.memory_reference);
+        // Two RGB pixels: red and green. Anything past them is unreadable.
+        Ok(dap::ReadMemoryResponse {
+            address: "0x1000".into(),
+            unreadable_bytes: Some(args.count.saturating_sub(6)),
+            data: Some("/wAAAP8A".into()),
+        })
+    });
+
+    let stop = || {
+        dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        })
+    };
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            })
+        });
+    let visualizer = variable_list.update_in(cx, |list, window, cx| {
+        list.open_image_visualizer(
+            "pixels".into(),
+            ImageSource::Memory("0x1000".into()),
+            Point::default(),
+            window,
+            cx,
+        );
+        list.image_visualizer().cloned().unwrap()
+    });
+
+    visualizer.update_in(cx, |visualizer, window, cx| {
+        visualizer.set_dimensions(2, 1, PixelFormat::Rgb8, window, cx)
+    });
+    cx.run_until_parked();
+    visualizer.read_with(cx, |visualizer, _| {
+        assert_eq!(Some((2, 1)), visualizer.rendered_size());
+        assert_eq!(None, visualizer.error());
+    });
+
+    visualizer.update_in(cx, |visualizer, window, cx| {
+        visualizer.set_dimensions(2, 2, PixelFormat::Rgb8, window, cx)
+    });
+    cx.run_until_parked();
+    visualizer.read_with(cx, |visualizer, _| {
+        assert_eq!(None, visualizer.rendered_size());
+        assert_eq!(
+            Some("expected 12 bytes for a 2×2 RGB8 image, got 6"),
+            visualizer.error().map(ToString::to_string).as_deref()
+        );
+    });
+
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+    variable_list.read_with(cx, |list, _| {
+        assert!(
+            list.image_visualizer().is_none(),
+            "The visualizer should close once the debuggee stops somewhere else"
+        );
+    });
+}
+
+#[test]
+fn test_pixels_to_bgra() {
+    assert_eq!(
+        vec![3, 2, 1, 4],
+        pixels_to_bgra(&[1, 2, 3, 4], 1, 1, PixelFormat::Rgba8).unwrap()
+    );
+    assert_eq!(
+        vec![1, 2, 3, 4],
+        pixels_to_bgra(&[1, 2, 3, 4, 5], 1, 1, PixelFormat::Bgra8).unwrap()
+    );
+    assert_eq!(
+        vec![3, 2, 1, 255, 6, 5, 4, 255],
+        pixels_to_bgra(&[1, 2, 3, 4, 5, 6], 2, 1, PixelFormat::Rgb8).unwrap()
+    );
+    assert_eq!(
+        vec![7, 7, 7, 255, 9, 9, 9, 255],
+        pixels_to_bgra(&[7, 9], 1, 2, PixelFormat::Gray8).unwrap()
+    );
+    assert!(pixels_to_bgra(&[1, 2, 3], 2, 2, PixelFormat::Gray8).is_err());
+}
+
+#[test]
+fn test_parse_byte() {
+    assert_eq!(Some(255), parse_byte("255"));
+    assert_eq!(Some(0xab), parse_byte("0xAB"));
+    assert_eq!(Some(65), parse_byte("65 'A'"));
+    assert_eq!(Some(255), parse_byte("-1"));
+    assert_eq!(None, parse_byte("256"));
+    assert_eq!(None, parse_byte("'A'"));
+}
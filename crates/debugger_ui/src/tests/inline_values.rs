@@ -1,11 +1,16 @@
 use std::{path::Path, sync::Arc};
 
-use dap::{Scope, StackFrame, Variable, requests::Variables};
+use dap::{
+    Scope, StackFrame, Variable,
+    debugger_settings::{DebuggerSettings, InlineValues},
+    requests::Variables,
+};
 use editor::{Editor, EditorMode, MultiBuffer};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use language::{Language, LanguageConfig, LanguageMatcher, tree_sitter_python, tree_sitter_rust};
 use project::{FakeFs, Project};
 use serde_json::json;
+use settings::SettingsStore;
 use unindent::Unindent as _;
 use util::path;
 
@@ -17,6 +22,7 @@ use crate::{
 #[gpui::test]
 async fn test_rust_inline_values(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx);
+    set_inline_values(InlineValues::NextToVariables, cx);
 
     fn stack_frame_for_line(line: u64) -> dap::StackFrame {
         StackFrame {
@@ -1518,7 +1524,7 @@ fn main() {
     });
 }
 
-fn rust_lang() -> Language {
+pub(super) fn rust_lang() -> Language {
     Language::new(
         LanguageConfig {
             name: "Rust".into(),
@@ -1535,6 +1541,7 @@ fn rust_lang() -> Language {
 #[gpui::test]
 async fn test_python_inline_values(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     init_test(cx);
+    set_inline_values(InlineValues::NextToVariables, cx);
 
     let fs = FakeFs::new(executor.clone());
     let source_code = r#"
@@ -1836,6 +1843,167 @@ def process_data(untyped_param, typed_param: int, another_typed: str):
     });
 }
 
+#[gpui::test]
+async fn test_inline_values_at_end_of_line(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    let source_code = r#"
+def add(a, b):
+    total = a + b
+    return total
+"#
+    .unindent();
+    fs.insert_tree(path!("/project"), json!({ "main.py": source_code.clone() }))
+        .await;
+
+    let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/project/main.py"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_language(Some(Arc::new(python_lang())), cx);
+    });
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project),
+            window,
+            cx,
+        )
+    });
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+    client.on_request::<dap::requests::StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "add".into(),
+                source: Some(dap::Source {
+                    name: Some("main.py".into()),
+                    path: Some(path!("/project/main.py").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 3,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+    client.on_request::<dap::requests::Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Local".into(),
+                presentation_hint: None,
+                variables_reference: 1,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+    client.on_request::<Variables, _>(move |_, args| {
+        let variable = |name: &str, value: &str| Variable {
+            name: name.into(),
+            value: value.into(),
+            type_: Some("int".into()),
+            presentation_hint: None,
+            evaluate_name: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            declaration_location_reference: None,
+            value_location_reference: None,
+        };
+        Ok(dap::VariablesResponse {
+            variables: match args.variables_reference {
+                1 => vec![
+                    variable("a", "1"),
+                    variable("b", "2"),
+                    variable("total", "3"),
+                ],
+                _ => vec![],
+            },
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    editor.update_in(cx, |editor, window, cx| {
+        pretty_assertions::assert_eq!(
+            r#"
+        def add(a, b): a = 1, b = 2
+            total = a + b total = 3
+            return total
+        "#
+            .unindent(),
+            editor.snapshot(window, cx).text()
+        );
+    });
+
+    set_inline_values(InlineValues::Off, cx);
+    cx.run_until_parked();
+    editor.update_in(cx, |editor, window, cx| {
+        pretty_assertions::assert_eq!(source_code, editor.snapshot(window, cx).text());
+    });
+}
+
+fn set_inline_values(inline_values: InlineValues, cx: &mut TestAppContext) {
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.inline_values = inline_values;
+            });
+        });
+    });
+}
+
 fn python_lang() -> Language {
     Language::new(
         LanguageConfig {
@@ -0,0 +1,273 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    session::running::memory_editor::{parse_hex_bytes, pending_runs},
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    Scope, StackFrame, StoppedEvent,
+    requests::{Initialize, ReadMemory, Scopes, StackTrace, Variables, WriteMemory},
+};
+use gpui::{BackgroundExecutor, Point, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use util::path;
+
+#[gpui::test]
+async fn test_edit_and_write_memory(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                supports_write_memory_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Locals".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: Vec::new(),
+        })
+    });
+
+    let written = Arc::new(AtomicBool::new(false));
+    client.on_request::<ReadMemory, _>({
+        let written = written.clone();
+        move |_, args| {
+            assert_eq!("0x1000", args.memory_reference);
+            // `00 01 02 03`, or `00 ff ee aa` once the edits are written.
+            let data = if written.load(Ordering::SeqCst) {
+                "AP/uqg=="
+            } else {
+                "AAECAw=="
+            };
+            Ok(dap::ReadMemoryResponse {
+                address: "0x1000".into(),
+                unreadable_bytes: Some(args.count.saturating_sub(4)),
+                data: Some(data.into()),
+            })
+        }
+    });
+
+    let write_requests = Arc::new(Mutex::new(Vec::new()));
+    client.on_request::<WriteMemory, _>({
+        let written = written.clone();
+        let write_requests = write_requests.clone();
+        move |_, args| {
+            assert_eq!("0x1000", args.memory_reference);
+            write_requests
+                .lock()
+                .unwrap()
+                .push((args.offset, args.data));
+            written.store(true, Ordering::SeqCst);
+            Ok(dap::WriteMemoryResponse {
+                offset: None,
+                bytes_written: None,
+            })
+        }
+    });
+
+    let stop = || {
+        dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        })
+    };
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            })
+        });
+    let editor = variable_list.update_in(cx, |list, window, cx| {
+        list.open_memory_editor(
+            "buffer".into(),
+            "0x1000".into(),
+            Point::default(),
+            window,
+            cx,
+        );
+        list.memory_editor().cloned().unwrap()
+    });
+    cx.run_until_parked();
+    editor.read_with(cx, |editor, _| {
+        assert_eq!(&[0x00, 0x01, 0x02, 0x03], editor.bytes());
+        assert!(editor.pending().is_empty());
+    });
+
+    let edit = |offset: usize, text: &str, cx: &mut VisualTestContext| {
+        editor.update_in(cx, |editor, window, cx| {
+            editor.enter_bytes(offset, text, window, cx);
+            editor.pending().clone()
+        })
+    };
+    assert_eq!(BTreeMap::from([(1, 0xff)]), edit(1, "ff", cx));
+    assert_eq!(BTreeMap::from([(1, 0xff), (3, 0xaa)]), edit(3, "0xAA", cx));
+    // Entering a byte's current value doesn't mark it as edited.
+    assert_eq!(BTreeMap::from([(1, 0xff), (3, 0xaa)]), edit(0, "00", cx));
+
+    let undo = |cx: &mut VisualTestContext| {
+        editor.update_in(cx, |editor, window, cx| {
+            editor.undo(window, cx);
+            editor.pending().clone()
+        })
+    };
+    assert_eq!(BTreeMap::from([(1, 0xff), (3, 0xaa)]), undo(cx));
+    assert_eq!(BTreeMap::from([(1, 0xff)]), undo(cx));
+
+    assert_eq!(
+        BTreeMap::from([(1, 0xff), (2, 0xee), (3, 0xaa)]),
+        edit(2, "ee aa", cx)
+    );
+    assert_eq!(
+        BTreeMap::from([(1, 0xff), (2, 0xee), (3, 0xaa)]),
+        edit(3, "01 02", cx)
+    );
+    editor.read_with(cx, |editor, _| {
+        assert_eq!(
+            Some("The edit runs past the end of the readable memory"),
+            editor.error().map(ToString::to_string).as_deref()
+        );
+    });
+
+    editor.update(cx, |editor, cx| editor.write(cx));
+    // Edits made while the write is in flight would be dropped once it finishes.
+    assert_eq!(
+        BTreeMap::from([(1, 0xff), (2, 0xee), (3, 0xaa)]),
+        edit(0, "11", cx)
+    );
+    cx.run_until_parked();
+    assert_eq!(
+        vec![(Some(1), "/+6q".to_string())],
+        *write_requests.lock().unwrap()
+    );
+    editor.read_with(cx, |editor, _| {
+        assert_eq!(&[0x00, 0xff, 0xee, 0xaa], editor.bytes());
+        assert!(editor.pending().is_empty());
+        assert_eq!(None, editor.error());
+    });
+
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+    variable_list.read_with(cx, |list, _| {
+        assert!(
+            list.memory_editor().is_none(),
+            "The editor should close once the debuggee stops somewhere else"
+        );
+    });
+}
+
+#[test]
+fn test_parse_hex_bytes() {
+    assert_eq!(
+        Some(vec![0xde, 0xad, 0xbe, 0xef]),
+        parse_hex_bytes("de ad 0xBE EF")
+    );
+    assert_eq!(Some(vec![]), parse_hex_bytes("  "));
+    assert_eq!(None, parse_hex_bytes("de adbe"));
+    assert_eq!(None, parse_hex_bytes("zz"));
+}
+
+#[test]
+fn test_pending_runs() {
+    assert_eq!(
+        vec![(1, vec![0xff, 0xee]), (4, vec![0xaa])],
+        pending_runs(&BTreeMap::from([(1, 0xff), (2, 0xee), (4, 0xaa)]))
+    );
+    assert!(pending_runs(&BTreeMap::new()).is_empty());
+}
@@ -1,7 +1,11 @@
-use dap::DapRegistry;
+use dap::{
+    DapRegistry,
+    debugger_settings::{AdapterSettings, DebuggerSettings},
+};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
-use project::{FakeFs, Project};
+use project::{FakeFs, Project, TaskSourceKind};
 use serde_json::json;
+use settings::SettingsStore;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use task::{DebugRequest, DebugScenario, LaunchRequest, TaskContext, VariableName, ZedDebugConfig};
@@ -137,6 +141,7 @@ async fn test_debug_session_substitutes_variables_and_relativizes_paths(
                 "otherField": input_path
             }),
             tcp_connection: None,
+            wait_for: None,
         };
 
         workspace
@@ -152,6 +157,95 @@ async fn test_debug_session_substitutes_variables_and_relativizes_paths(
     }
 }
 
+#[gpui::test]
+async fn test_adapter_defaults_are_merged_into_scenarios(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() {}"
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.adapters.insert(
+                    "fake-adapter".into(),
+                    AdapterSettings {
+                        defaults: json!({
+                            "justMyCode": false,
+                            "stopOnEntry": true,
+                            "env": { "RUST_LOG": "debug", "RUST_BACKTRACE": "0" }
+                        })
+                        .as_object()
+                        .unwrap()
+                        .clone(),
+                    },
+                );
+            });
+        });
+    });
+
+    let called_launch = Arc::new(AtomicBool::new(false));
+    let _subscription = project::debugger::test::intercept_debug_sessions(cx, {
+        let called_launch = called_launch.clone();
+        move |client| {
+            client.on_request::<dap::requests::Launch, _>({
+                let called_launch = called_launch.clone();
+                move |_, args| {
+                    assert_eq!(
+                        args.raw,
+                        json!({
+                            "request": "launch",
+                            "program": path!("/project/main"),
+                            "justMyCode": false,
+                            "stopOnEntry": false,
+                            "env": { "RUST_LOG": "debug", "RUST_BACKTRACE": "1" }
+                        })
+                    );
+                    called_launch.store(true, Ordering::SeqCst);
+                    Ok(())
+                }
+            });
+        }
+    });
+
+    let scenario = DebugScenario {
+        adapter: "fake-adapter".into(),
+        label: "test-debug-session".into(),
+        build: None,
+        config: json!({
+            "request": "launch",
+            "program": path!("/project/main"),
+            "stopOnEntry": false,
+            "env": { "RUST_BACKTRACE": "1" }
+        }),
+        tcp_connection: None,
+        wait_for: None,
+    };
+
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.start_debug_session(scenario, TaskContext::default(), None, window, cx)
+        })
+        .unwrap();
+
+    cx.run_until_parked();
+
+    assert!(called_launch.load(Ordering::SeqCst));
+}
+
 // #[gpui::test]
 // async fn test_save_debug_scenario_to_file(executor: BackgroundExecutor, cx: &mut TestAppContext) {
 //     init_test(cx);
@@ -349,3 +443,87 @@ async fn test_dap_adapter_config_conversion_and_validation(cx: &mut TestAppConte
         expected_adapters
     );
 }
+
+#[test]
+fn test_edits_apply_over_the_rerun_scenario() {
+    let scenario = DebugScenario {
+        adapter: "fake-adapter".into(),
+        label: "Run server".into(),
+        build: None,
+        config: json!({
+            "request": "launch",
+            "program": "server",
+            "args": ["--port", "8080"],
+            "env": { "RUST_LOG": "info" },
+            "sourceMap": { "/build": "/src" },
+        }),
+        tcp_connection: None,
+        wait_for: None,
+    };
+    let edited = DebugScenario {
+        adapter: "fake-adapter".into(),
+        label: "server (fake-adapter)".into(),
+        build: None,
+        config: json!({
+            "request": "launch",
+            "program": "server",
+            "args": ["--port", "9090"],
+        }),
+        tcp_connection: None,
+        wait_for: None,
+    };
+
+    let rerun = crate::new_process_modal::apply_edits_to_scenario(scenario, edited);
+
+    assert_eq!(rerun.label.as_ref(), "Run server");
+    assert_eq!(
+        rerun.config,
+        json!({
+            "request": "launch",
+            "program": "server",
+            "args": ["--port", "9090"],
+            "sourceMap": { "/build": "/src" },
+        }),
+        "Edited fields should replace the original ones, which includes dropping the environment"
+    );
+}
+
+#[test]
+fn test_pinned_scenarios_are_listed_first() {
+    let scenario = |label: &str| DebugScenario {
+        adapter: "fake-adapter".into(),
+        label: label.to_string().into(),
+        build: None,
+        config: json!({ "request": "launch", "program": label }),
+        tcp_connection: None,
+        wait_for: None,
+    };
+    let source = Some(TaskSourceKind::UserInput);
+    let labels = |candidates: &[(Option<TaskSourceKind>, DebugScenario)]| {
+        candidates
+            .iter()
+            .map(|(_, scenario)| scenario.label.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let (candidates, pinned_count, recent_count) = crate::new_process_modal::pin_candidates(
+        &[scenario("tests"), scenario("one-off"), scenario("server")],
+        vec![(None, scenario("server")), (None, scenario("client"))],
+        vec![
+            (source.clone(), scenario("client")),
+            (source.clone(), scenario("server")),
+            (source.clone(), scenario("tests")),
+        ],
+    );
+
+    assert_eq!(
+        labels(&candidates),
+        vec!["tests", "one-off", "server", "client", "client"]
+    );
+    assert_eq!((pinned_count, recent_count), (3, 1));
+    assert_eq!(
+        candidates[0].0, source,
+        "Pinned scenarios should keep the source they are listed with"
+    );
+    assert_eq!(candidates[1].0, None);
+}
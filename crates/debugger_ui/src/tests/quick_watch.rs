@@ -0,0 +1,169 @@
+use crate::{
+    QuickWatch,
+    debugger_panel::DebugPanel,
+    session::running::quick_watch::QuickWatchModal,
+    tests::{init_test, init_test_workspace, start_debug_session, variable},
+};
+use dap::{
+    Scope, StackFrame, StoppedEvent,
+    requests::{Evaluate, Scopes, StackTrace, Variables},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use menu::Cancel;
+use project::{FakeFs, Project};
+use serde_json::json;
+use util::path;
+
+#[gpui::test]
+async fn test_quick_watch_evaluates_in_selected_frame(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Locals".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, args| {
+        let variables = match args.variables_reference {
+            3 => vec![variable("x", "1", 0), variable("y", "2", 0)],
+            _ => Vec::new(),
+        };
+        Ok(dap::VariablesResponse { variables })
+    });
+
+    client.on_request::<Evaluate, _>(move |_, args| {
+        assert_eq!(Some(1), args.frame_id);
+        assert_eq!(Some(dap::EvaluateArgumentsContext::Watch), args.context);
+        assert_eq!("point", args.expression);
+        Ok(dap::EvaluateResponse {
+            result: "{x:1, y:2}".into(),
+            type_: None,
+            presentation_hint: None,
+            variables_reference: 3,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    cx.dispatch_action(QuickWatch);
+    cx.run_until_parked();
+
+    let quick_watch = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_modal::<QuickWatchModal>(cx).unwrap()
+        })
+        .unwrap();
+
+    quick_watch.update_in(cx, |quick_watch, window, cx| {
+        quick_watch.set_expression("point", window, cx)
+    });
+    cx.run_until_parked();
+    quick_watch.update(cx, |quick_watch, cx| {
+        assert_eq!(vec!["> point = {x:1, y:2}"], quick_watch.visual_entries(cx));
+        quick_watch.toggle_path(&["point"], cx);
+    });
+    cx.run_until_parked();
+    quick_watch.update(cx, |quick_watch, cx| {
+        assert_eq!(
+            vec!["v point = {x:1, y:2}", "      x = 1", "      y = 2"],
+            quick_watch.visual_entries(cx)
+        );
+    });
+
+    cx.dispatch_action(Cancel);
+    cx.run_until_parked();
+    workspace
+        .update(cx, |workspace, _, cx| {
+            assert!(workspace.active_modal::<QuickWatchModal>(cx).is_none());
+        })
+        .unwrap();
+}
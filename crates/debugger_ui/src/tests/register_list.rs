@@ -0,0 +1,200 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    Scope, ScopePresentationHint, StackFrame, StoppedEvent, Variable,
+    requests::{Scopes, StackTrace, Variables},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use util::path;
+
+#[gpui::test]
+async fn test_register_list_groups_and_highlights_changes(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Registers".into(),
+                presentation_hint: Some(ScopePresentationHint::Registers),
+                variables_reference: 10,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let register = |name: &str, value: &str, variables_reference: u64| Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+
+    let stepped = Arc::new(AtomicBool::new(false));
+    client.on_request::<Variables, _>({
+        let stepped = stepped.clone();
+        move |_, args| {
+            let variables = match args.variables_reference {
+                10 => vec![register("General Purpose Registers", "", 11)],
+                11 => {
+                    let rax = if stepped.load(Ordering::SeqCst) {
+                        "0x2a"
+                    } else {
+                        "0x0"
+                    };
+                    vec![register("rax", rax, 0), register("rip", "4096", 0)]
+                }
+                _ => unreachable!(),
+            };
+            Ok(dap::VariablesResponse { variables })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.ensure_pane_item(DebuggerPaneItem::Registers, window, cx);
+        this.activate_item(DebuggerPaneItem::Registers, window, cx);
+        cx.refresh_windows();
+    });
+    cx.run_until_parked();
+
+    let register_list = running_state.update(cx, |state, _| state.register_list().clone());
+    register_list.update(cx, |register_list, _| {
+        pretty_assertions::assert_eq!(
+            vec![
+                "General Purpose Registers:",
+                "    rax = 0x0",
+                "    rip = 0x1000",
+            ],
+            register_list.visual_entries()
+        );
+        assert!(register_list.changed_registers().is_empty());
+    });
+
+    stepped.store(true, Ordering::SeqCst);
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Step,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    register_list.update(cx, |register_list, _| {
+        pretty_assertions::assert_eq!(
+            vec![
+                "General Purpose Registers:",
+                "    rax = 0x2a",
+                "    rip = 0x1000",
+            ],
+            register_list.visual_entries()
+        );
+        assert_eq!(vec!["rax"], register_list.changed_registers());
+    });
+}
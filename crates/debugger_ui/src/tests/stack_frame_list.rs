@@ -1,16 +1,22 @@
 use crate::{
+    FrameDown, FrameUp,
     debugger_panel::DebugPanel,
-    session::running::stack_frame_list::StackFrameEntry,
+    session::running::{
+        exception_popover::{exception_detail_lines, exception_summary},
+        stack_frame_list::StackFrameEntry,
+    },
     tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
 };
 use dap::{
     StackFrame,
-    requests::{Scopes, StackTrace, Threads},
+    debugger_settings::{DebuggerSettings, SourcePathMapping},
+    requests::{Continue, ExceptionInfo, Initialize, Scopes, StackTrace, Threads},
 };
 use editor::{Editor, ToPoint as _};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
-use project::{FakeFs, Project};
+use project::{FakeFs, Project, debugger::session::ThreadId};
 use serde_json::json;
+use settings::SettingsStore;
 use std::sync::Arc;
 use unindent::Unindent as _;
 use util::path;
@@ -752,3 +758,1463 @@ async fn test_collapsed_entries(executor: BackgroundExecutor, cx: &mut TestAppCo
         });
     });
 }
+
+fn stack_frame(id: u64, source: dap::Source) -> StackFrame {
+    StackFrame {
+        id,
+        name: format!("Stack Frame {id}"),
+        source: Some(source),
+        line: 1,
+        column: 1,
+        end_line: None,
+        end_column: None,
+        can_restart: None,
+        instruction_pointer_reference: None,
+        module_id: None,
+        presentation_hint: None,
+    }
+}
+
+fn source(path: Option<&str>, origin: Option<&str>) -> dap::Source {
+    dap::Source {
+        name: None,
+        path: path.map(Into::into),
+        source_reference: path.is_none().then_some(1),
+        presentation_hint: None,
+        origin: origin.map(Into::into),
+        sources: None,
+        adapter_data: None,
+        checksums: None,
+    }
+}
+
+#[gpui::test]
+async fn test_library_frames_are_hidden(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "console.log(require('lib'));",
+           },
+           "node_modules": {
+               "lib": {
+                   "index.js": "module.exports = 1;",
+               }
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let stack_frames = vec![
+        stack_frame(1, source(Some(path!("/project/src/test.js")), None)),
+        stack_frame(
+            2,
+            source(Some(path!("/project/node_modules/lib/index.js")), None),
+        ),
+        stack_frame(3, source(None, Some("internal module"))),
+        stack_frame(4, source(Some(path!("/project/src/test.js")), None)),
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: (*stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+    stack_frame_list.update(cx, |stack_frame_list, cx| {
+        stack_frame_list.dap_stack_frames(cx);
+    });
+
+    cx.run_until_parked();
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.build_entries(false, window, cx);
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[0].clone()),
+                StackFrameEntry::Collapsed(vec![stack_frames[1].clone(), stack_frames[2].clone()]),
+                StackFrameEntry::Normal(stack_frames[3].clone()),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.hide_library_frames = false;
+            });
+        });
+    });
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.build_entries(false, window, cx);
+        assert_eq!(
+            &stack_frames
+                .iter()
+                .cloned()
+                .map(StackFrameEntry::Normal)
+                .collect::<Vec<_>>(),
+            stack_frame_list.entries()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_collapse_subtle_frames(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "console.log('hello');",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let with_hint = |stack_frame: StackFrame, hint| StackFrame {
+        presentation_hint: Some(hint),
+        ..stack_frame
+    };
+    let test_js = || source(Some(path!("/project/src/test.js")), None);
+    let stack_frames = vec![
+        stack_frame(1, test_js()),
+        with_hint(
+            stack_frame(2, test_js()),
+            dap::StackFramePresentationHint::Subtle,
+        ),
+        with_hint(
+            stack_frame(3, test_js()),
+            dap::StackFramePresentationHint::Subtle,
+        ),
+        with_hint(
+            stack_frame(4, test_js()),
+            dap::StackFramePresentationHint::Label,
+        ),
+        stack_frame(5, test_js()),
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: (*stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+    stack_frame_list.update(cx, |stack_frame_list, cx| {
+        stack_frame_list.dap_stack_frames(cx);
+    });
+
+    cx.run_until_parked();
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.build_entries(false, window, cx);
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[0].clone()),
+                StackFrameEntry::Collapsed(vec![stack_frames[1].clone(), stack_frames[2].clone()]),
+                StackFrameEntry::Label(stack_frames[3].clone()),
+                StackFrameEntry::Normal(stack_frames[4].clone()),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.collapse_subtle_frames = false;
+            });
+        });
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[0].clone()),
+                StackFrameEntry::Normal(stack_frames[1].clone()),
+                StackFrameEntry::Normal(stack_frames[2].clone()),
+                StackFrameEntry::Label(stack_frames[3].clone()),
+                StackFrameEntry::Normal(stack_frames[4].clone()),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_map_missing_source_path(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "console.log('hello');",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![stack_frame(
+                1,
+                source(Some(path!("/build/src/test.js")), None),
+            )],
+            total_frames: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            Some(std::path::Path::new(path!("/build/src/test.js"))),
+            stack_frame_list
+                .unresolved_source_path()
+                .map(|path| &**path)
+        );
+    });
+
+    cx.update(|_, cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.source_path_mappings = vec![SourcePathMapping {
+                    remote: path!("/build").into(),
+                    local: path!("/project").into(),
+                }];
+            });
+        });
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(None, stack_frame_list.unresolved_source_path());
+    });
+    workspace
+        .update(cx, |workspace, _, cx| {
+            let editors = workspace.items_of_type::<Editor>(cx).collect::<Vec<_>>();
+            assert_eq!(1, editors.len());
+
+            let project_path = editors[0]
+                .update(cx, |editor, cx| editor.project_path(cx))
+                .unwrap();
+            let expected = if cfg!(target_os = "windows") {
+                "src\\test.js"
+            } else {
+                "src/test.js"
+            };
+            assert_eq!(expected, project_path.path.to_string_lossy());
+        })
+        .unwrap();
+}
+
+#[gpui::test]
+async fn test_load_more_stack_frames(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "await main();",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_delayed_stack_trace_loading: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let test_js = || source(Some(path!("/project/src/test.js")), None);
+    let stack_frames = vec![
+        stack_frame(1, test_js()),
+        stack_frame(2, test_js()),
+        StackFrame {
+            presentation_hint: Some(dap::StackFramePresentationHint::Label),
+            ..stack_frame(3, test_js())
+        },
+        stack_frame(4, test_js()),
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, args| {
+            let start_frame = args.start_frame.unwrap_or_default() as usize;
+            Ok(dap::StackTraceResponse {
+                stack_frames: stack_frames[start_frame..]
+                    .iter()
+                    .take(2)
+                    .cloned()
+                    .collect(),
+                total_frames: Some(stack_frames.len() as u64),
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[0].clone()),
+                StackFrameEntry::Normal(stack_frames[1].clone()),
+                StackFrameEntry::LoadMore,
+            ],
+            stack_frame_list.entries()
+        );
+    });
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.load_more_stack_frames(window, cx);
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[0].clone()),
+                StackFrameEntry::Normal(stack_frames[1].clone()),
+                StackFrameEntry::Label(stack_frames[2].clone()),
+                StackFrameEntry::Normal(stack_frames[3].clone()),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_filter_stack_frames(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "console.log('hello');",
+               "module.js": "export const value = 1;",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let stack_frames = vec![
+        stack_frame(1, source(Some(path!("/project/src/test.js")), None)),
+        StackFrame {
+            name: "fibonacci".into(),
+            ..stack_frame(2, source(Some(path!("/project/src/test.js")), None))
+        },
+        stack_frame(3, source(Some(path!("/project/src/module.js")), None)),
+        StackFrame {
+            name: "Fibonacci".into(),
+            presentation_hint: Some(dap::StackFramePresentationHint::Subtle),
+            ..stack_frame(4, source(Some(path!("/project/src/test.js")), None))
+        },
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: (*stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.set_filter("fibo", window, cx);
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[1].clone()),
+                StackFrameEntry::Normal(stack_frames[3].clone()),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.set_filter("module.js", window, cx);
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![StackFrameEntry::Normal(stack_frames[2].clone())],
+            stack_frame_list.entries()
+        );
+    });
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.set_filter("", window, cx);
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Normal(stack_frames[0].clone()),
+                StackFrameEntry::Normal(stack_frames[1].clone()),
+                StackFrameEntry::Normal(stack_frames[2].clone()),
+                StackFrameEntry::Collapsed(vec![stack_frames[3].clone()]),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_walk_stack_with_frame_actions(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "console.log('hello');",
+               "module.js": "export const value = 1;",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let stack_frames = vec![
+        stack_frame(1, source(Some(path!("/project/src/test.js")), None)),
+        stack_frame(2, source(Some(path!("/project/src/module.js")), None)),
+        StackFrame {
+            presentation_hint: Some(dap::StackFramePresentationHint::Label),
+            ..stack_frame(3, source(Some(path!("/project/src/test.js")), None))
+        },
+        stack_frame(4, source(Some(path!("/project/src/test.js")), None)),
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: (*stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+    let opened_stack_frame_id = |cx: &mut VisualTestContext| {
+        stack_frame_list.read_with(cx, |stack_frame_list, _| {
+            stack_frame_list.opened_stack_frame_id()
+        })
+    };
+    assert_eq!(Some(1), opened_stack_frame_id(cx));
+
+    cx.dispatch_action(FrameUp);
+    cx.run_until_parked();
+    assert_eq!(Some(2), opened_stack_frame_id(cx));
+
+    // Label frames are skipped.
+    cx.dispatch_action(FrameUp);
+    cx.run_until_parked();
+    assert_eq!(Some(4), opened_stack_frame_id(cx));
+
+    cx.dispatch_action(FrameUp);
+    cx.run_until_parked();
+    assert_eq!(Some(4), opened_stack_frame_id(cx));
+
+    cx.dispatch_action(FrameDown);
+    cx.run_until_parked();
+    assert_eq!(Some(2), opened_stack_frame_id(cx));
+
+    workspace
+        .update(cx, |workspace, _, cx| {
+            let active_editor = workspace.active_item_as::<Editor>(cx).unwrap();
+            let project_path = active_editor
+                .update(cx, |editor, cx| editor.project_path(cx))
+                .unwrap();
+            let expected = if cfg!(target_os = "windows") {
+                "src\\module.js"
+            } else {
+                "src/module.js"
+            };
+            assert_eq!(expected, project_path.path.to_string_lossy());
+        })
+        .unwrap();
+}
+
+#[gpui::test]
+async fn test_caller_frame_keeps_execution_line_marked(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "function main() {\n    work();\n}\nfunction work() {\n    return 1;\n}\n",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let stack_frames = vec![
+        StackFrame {
+            line: 5,
+            ..stack_frame(1, source(Some(path!("/project/src/test.js")), None))
+        },
+        StackFrame {
+            line: 2,
+            ..stack_frame(2, source(Some(path!("/project/src/test.js")), None))
+        },
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: (*stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let editor = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_item_as::<Editor>(cx).unwrap()
+        })
+        .unwrap();
+    let highlighted_rows = |cx: &mut VisualTestContext| {
+        editor.update_in(cx, |editor, window, cx| {
+            let snapshot = editor.snapshot(window, cx);
+            let row = |(range, _): (std::ops::Range<editor::Anchor>, _)| {
+                range.start.to_point(&snapshot.buffer_snapshot).row
+            };
+            (
+                editor
+                    .highlighted_rows::<editor::ActiveDebugLine>()
+                    .map(row)
+                    .collect::<Vec<_>>(),
+                editor
+                    .highlighted_rows::<editor::TopStackFrameLine>()
+                    .map(row)
+                    .collect::<Vec<_>>(),
+            )
+        })
+    };
+    assert_eq!((vec![4], vec![]), highlighted_rows(cx));
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+
+    // The caller's line is marked, and the line being executed stays marked too.
+    stack_frame_list
+        .update_in(cx, |stack_frame_list, window, cx| {
+            stack_frame_list.go_to_stack_frame(2, window, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    assert_eq!((vec![1], vec![4]), highlighted_rows(cx));
+
+    stack_frame_list
+        .update_in(cx, |stack_frame_list, window, cx| {
+            stack_frame_list.go_to_stack_frame(1, window, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    assert_eq!((vec![4], vec![]), highlighted_rows(cx));
+}
+
+#[gpui::test]
+async fn test_show_all_threads(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "console.log('hello');",
+               "worker.js": "console.log('world');",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![
+                dap::Thread {
+                    id: 1,
+                    name: "Thread 1".into(),
+                },
+                dap::Thread {
+                    id: 2,
+                    name: "Worker".into(),
+                },
+            ],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let main_stack_frames = vec![
+        stack_frame(1, source(Some(path!("/project/src/test.js")), None)),
+        stack_frame(2, source(Some(path!("/project/src/test.js")), None)),
+    ];
+    let worker_stack_frames = vec![
+        stack_frame(11, source(Some(path!("/project/src/worker.js")), None)),
+        stack_frame(12, source(Some(path!("/project/src/worker.js")), None)),
+    ];
+
+    client.on_request::<StackTrace, _>({
+        let main_stack_frames = Arc::new(main_stack_frames.clone());
+        let worker_stack_frames = Arc::new(worker_stack_frames.clone());
+        move |_, args| {
+            let stack_frames = if args.thread_id == 1 {
+                &main_stack_frames
+            } else {
+                &worker_stack_frames
+            };
+            Ok(dap::StackTraceResponse {
+                stack_frames: (**stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: Some(true),
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .update(cx, |item, _| item.running_state().clone());
+    let stack_frame_list = running_state.read_with(cx, |state, _| state.stack_frame_list().clone());
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.toggle_all_threads(window, cx);
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Thread {
+                    thread_id: ThreadId(1),
+                    name: "Thread 1".into(),
+                    is_expanded: true,
+                },
+                StackFrameEntry::Normal(main_stack_frames[0].clone()),
+                StackFrameEntry::Normal(main_stack_frames[1].clone()),
+                StackFrameEntry::Thread {
+                    thread_id: ThreadId(2),
+                    name: "Worker".into(),
+                    is_expanded: false,
+                },
+            ],
+            stack_frame_list.entries()
+        );
+    });
+
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.activate_entry(3, window, cx);
+    });
+    cx.run_until_parked();
+
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(
+            &vec![
+                StackFrameEntry::Thread {
+                    thread_id: ThreadId(1),
+                    name: "Thread 1".into(),
+                    is_expanded: true,
+                },
+                StackFrameEntry::Normal(main_stack_frames[0].clone()),
+                StackFrameEntry::Normal(main_stack_frames[1].clone()),
+                StackFrameEntry::Thread {
+                    thread_id: ThreadId(2),
+                    name: "Worker".into(),
+                    is_expanded: true,
+                },
+                StackFrameEntry::Normal(worker_stack_frames[0].clone()),
+                StackFrameEntry::Normal(worker_stack_frames[1].clone()),
+            ],
+            stack_frame_list.entries()
+        );
+    });
+
+    // Opening a frame of another thread selects that thread.
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.activate_entry(5, window, cx);
+    });
+    cx.run_until_parked();
+
+    assert_eq!(
+        Some(ThreadId(2)),
+        running_state.read_with(cx, |state, _| state.selected_thread_id())
+    );
+    stack_frame_list.update(cx, |stack_frame_list, _| {
+        assert_eq!(Some(12), stack_frame_list.opened_stack_frame_id());
+        assert_eq!(6, stack_frame_list.entries().len());
+    });
+}
+
+#[gpui::test]
+async fn test_open_source_reference(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(path!("/project"), json!({})).await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    let stack_frames = vec![StackFrame {
+        line: 2,
+        ..stack_frame(
+            1,
+            dap::Source {
+                name: Some("<eval>/VM42".into()),
+                ..source(None, None)
+            },
+        )
+    }];
+
+    client.on_request::<StackTrace, _>({
+        let stack_frames = Arc::new(stack_frames.clone());
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: (*stack_frames).clone(),
+                total_frames: None,
+            })
+        }
+    });
+
+    client.on_request::<dap::requests::Source, _>(move |_, args| {
+        assert_eq!(1, args.source_reference);
+        Ok(dap::SourceResponse {
+            content: "let a = 1;\nlet b = 2;\n".into(),
+            mime_type: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // trigger threads to load
+    active_debug_session_panel(workspace, cx).update(cx, |session, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state
+                .session()
+                .update(cx, |session, cx| session.threads(cx));
+        });
+    });
+
+    cx.run_until_parked();
+
+    // select first thread
+    active_debug_session_panel(workspace, cx).update_in(cx, |session, window, cx| {
+        session.running_state().update(cx, |running_state, cx| {
+            running_state.select_current_thread(
+                &running_state
+                    .session()
+                    .update(cx, |session, cx| session.threads(cx)),
+                window,
+                cx,
+            );
+        });
+    });
+
+    cx.run_until_parked();
+
+    let stack_frame_list = active_debug_session_panel(workspace, cx).update(cx, |item, cx| {
+        item.running_state()
+            .update(cx, |state, _| state.stack_frame_list().clone())
+    });
+    stack_frame_list.update_in(cx, |stack_frame_list, window, cx| {
+        stack_frame_list.activate_entry(0, window, cx);
+    });
+    cx.run_until_parked();
+
+    let editor = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_item_as::<Editor>(cx).unwrap()
+        })
+        .unwrap();
+    editor.update(cx, |editor, cx| {
+        assert_eq!("let a = 1;\nlet b = 2;\n", editor.text(cx));
+        assert!(editor.read_only(cx));
+        assert_eq!(
+            1,
+            editor.selections.newest::<language::Point>(cx).head().row
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_exception_info_until_continued(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": "throw new TypeError('x is not a function');",
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_exception_info_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![stack_frame(
+                1,
+                source(Some(path!("/project/src/test.js")), None),
+            )],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<ExceptionInfo, _>(move |_, args| {
+        assert_eq!(1, args.thread_id);
+        Ok(dap::ExceptionInfoResponse {
+            exception_id: "TypeError".into(),
+            description: Some("x is not a function".into()),
+            break_mode: dap::ExceptionBreakMode::Unhandled,
+            details: Some(dap::ExceptionDetails {
+                message: None,
+                type_name: None,
+                full_type_name: None,
+                evaluate_name: None,
+                stack_trace: Some("    at main (test.js:1:7)\n".into()),
+                inner_exception: Some(vec![dap::ExceptionDetails {
+                    message: Some("missing import".into()),
+                    type_name: Some("ReferenceError".into()),
+                    full_type_name: None,
+                    evaluate_name: None,
+                    stack_trace: None,
+                    inner_exception: None,
+                }]),
+            }),
+        })
+    });
+
+    client.on_request::<Continue, _>(move |_, _| {
+        Ok(dap::ContinueResponse {
+            all_threads_continued: Some(true),
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Exception,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        let exception_info = session.exception_info(ThreadId(1)).unwrap();
+        assert_eq!(
+            exception_summary(exception_info),
+            "TypeError: x is not a function"
+        );
+        assert_eq!(
+            exception_detail_lines(exception_info),
+            vec![
+                "    at main (test.js:1:7)",
+                "Caused by ReferenceError: missing import"
+            ],
+        );
+    });
+
+    let editor = workspace
+        .update(cx, |workspace, _, cx| {
+            workspace.active_item_as::<Editor>(cx).unwrap()
+        })
+        .unwrap();
+    let exception_annotation = |cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            editor.exception_annotation(cx).map(|(anchors, message)| {
+                let rows = anchors
+                    .iter()
+                    .map(|anchor| anchor.to_point(&snapshot).row)
+                    .collect::<Vec<_>>();
+                (rows, message)
+            })
+        })
+    };
+    assert_eq!(
+        exception_annotation(cx),
+        Some((vec![0], "TypeError: x is not a function".into()))
+    );
+
+    session.update(cx, |session, cx| session.continue_thread(ThreadId(1), cx));
+    cx.run_until_parked();
+
+    session.update(cx, |session, _| {
+        assert!(session.exception_info(ThreadId(1)).is_none());
+    });
+    assert_eq!(exception_annotation(cx), None);
+}
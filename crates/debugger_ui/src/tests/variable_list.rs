@@ -1,23 +1,37 @@
 use std::sync::{
-    Arc,
+    Arc, Mutex,
     atomic::{AtomicBool, Ordering},
 };
 
 use crate::{
     DebugPanel,
     persistence::DebuggerPaneItem,
-    session::running::variable_list::{CollapseSelectedEntry, ExpandSelectedEntry},
-    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+    session::running::variable_list::{
+        CollapseSelectedEntry, CopyScopeAsJson, EditVariable, EntryPath, ExpandSelectedEntry,
+        ExpandSelectedEntryRecursively, FollowPointer, NumericFormat, RemoveWatch,
+        SelectFirstSibling, SelectLastSibling, SelectParentEntry, TogglePinnedVariable,
+        ToggleTypeColumn, full_text, hex_dump,
+    },
+    tests::{
+        active_debug_session_panel, init_test, init_test_workspace, inline_values::rust_lang,
+        start_debug_session,
+    },
+    watch_expression_at,
 };
 use collections::HashMap;
 use dap::{
-    Scope, StackFrame, Variable,
-    requests::{Initialize, Launch, Scopes, StackTrace, Variables},
+    Scope, StackFrame, Variable, VariablesArgumentsFilter,
+    debugger_settings::DebuggerSettings,
+    requests::{
+        Evaluate, Initialize, Launch, Next, Scopes, SetExpression, StackTrace, StepIn, Variables,
+    },
 };
-use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use gpui::{AppContext as _, BackgroundExecutor, TestAppContext, VisualTestContext};
+use language::Buffer;
 use menu::{SelectFirst, SelectNext, SelectPrevious};
 use project::{FakeFs, Project};
 use serde_json::json;
+use settings::SettingsStore;
 use unindent::Unindent as _;
 use util::path;
 
@@ -1828,3 +1842,2162 @@ async fn test_it_fetches_scopes_variables_when_you_select_a_stack_frame(
         assert_eq!(variables, frame_2_variables,);
     });
 }
+
+#[gpui::test]
+async fn test_edit_variable_falls_back_to_set_expression(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let test_file_content = r#"
+        const point = { x: 1 };
+    "#
+    .unindent();
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": test_file_content,
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_set_expression: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: vec![Variable {
+                name: "x".into(),
+                value: "1".into(),
+                type_: None,
+                presentation_hint: None,
+                evaluate_name: Some("point.x".into()),
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                declaration_location_reference: None,
+                value_location_reference: None,
+            }],
+        })
+    });
+
+    let set_expression_called = Arc::new(AtomicBool::new(false));
+    client.on_request::<SetExpression, _>({
+        let set_expression_called = set_expression_called.clone();
+        move |_, args| {
+            assert_eq!("point.x", args.expression);
+            assert_eq!("42", args.value);
+            assert_eq!(Some(1), args.frame_id);
+            set_expression_called.store(true, Ordering::SeqCst);
+
+            Ok(dap::SetExpressionResponse {
+                value: "42".into(),
+                type_: None,
+                presentation_hint: None,
+                variables_reference: None,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                value_location_reference: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+        cx.focus_self(window);
+        let running = item.running_state().clone();
+
+        let variable_list = running.update(cx, |state, cx| {
+            state.activate_item(DebuggerPaneItem::Variables, window, cx);
+            state.variable_list().clone()
+        });
+        variable_list.update(cx, |_, cx| cx.focus_self(window));
+    });
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.run_until_parked();
+
+    cx.dispatch_action(EditVariable);
+    cx.run_until_parked();
+    cx.simulate_input("42");
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+
+    assert!(
+        set_expression_called.load(Ordering::SeqCst),
+        "Expected the edit to be sent as a setExpression request"
+    );
+}
+
+#[test]
+fn test_entry_path_to_expression() {
+    let path = |names: &[&str]| EntryPath {
+        leaf_name: names.last().map(|name| name.to_string().into()),
+        indices: names.iter().map(|name| name.to_string().into()).collect(),
+        pinned: false,
+    };
+
+    assert_eq!("foo", path(&["Locals", "foo"]).to_expression());
+    assert_eq!(
+        "foo.bar[3].baz",
+        path(&["Locals", "foo", "bar", "3", "baz"]).to_expression()
+    );
+    assert_eq!(
+        "items[0].name",
+        path(&["Locals", "items", "[0]", "name"]).to_expression()
+    );
+}
+
+#[test]
+fn test_full_text_unquotes_and_pretty_prints_json() {
+    assert_eq!(
+        ("line one\nline \"two\"".to_string(), false),
+        full_text(r#""line one\nline \"two\"""#)
+    );
+    assert_eq!(("it's".to_string(), false), full_text(r"'it\'s'"));
+    assert_eq!(
+        (
+            "{\n  \"id\": 1,\n  \"tags\": [\n    \"a\"\n  ]\n}".to_string(),
+            true
+        ),
+        full_text(r#""{\"id\": 1, \"tags\": [\"a\"]}""#)
+    );
+    assert_eq!(("[not json".to_string(), false), full_text("\"[not json\""));
+    assert_eq!(("unquoted".to_string(), false), full_text("unquoted"));
+}
+
+#[test]
+fn test_hex_dump() {
+    assert_eq!("", hex_dump(0x1000, &[]));
+    assert_eq!(
+        "00001000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 ff  |Hello, world!...|\n\
+         00001010  41 42                                             |AB|\n",
+        hex_dump(0x1000, b"Hello, world!\n\0\xffAB")
+    );
+}
+
+#[test]
+fn test_numeric_format() {
+    assert_eq!(NumericFormat::Hex.format("255").as_deref(), Some("0xff"));
+    assert_eq!(NumericFormat::Binary.format("5").as_deref(), Some("0b101"));
+    assert_eq!(NumericFormat::Octal.format("-8").as_deref(), Some("-0o10"));
+    assert_eq!(NumericFormat::Decimal.format("0x1F").as_deref(), Some("31"));
+    assert_eq!(NumericFormat::Hex.format("0b1111").as_deref(), Some("0xf"));
+    assert_eq!(NumericFormat::Hex.format("1.5"), None);
+    assert_eq!(NumericFormat::Hex.format("\"text\""), None);
+    assert_eq!(NumericFormat::Hex.format("0x"), None);
+}
+
+#[gpui::test]
+async fn test_filter_narrows_variables_and_expands_ancestors(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let test_file_content = r#"
+        const point = { x: 1, y: 2 };
+        const count = 3;
+    "#
+    .unindent();
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": test_file_content,
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: &str, value: &str, variables_reference: u64| Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+    let scope_variables = vec![variable("point", "{x, y}", 3), variable("count", "3", 0)];
+    let point_variables = vec![variable("x", "1", 0), variable("y", "2", 0)];
+
+    client.on_request::<Variables, _>(move |_, args| match args.variables_reference {
+        2 => Ok(dap::VariablesResponse {
+            variables: scope_variables.clone(),
+        }),
+        3 => Ok(dap::VariablesResponse {
+            variables: point_variables.clone(),
+        }),
+        _ => unreachable!(),
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            variable_list
+        });
+
+    // load the children of `point`, then collapse it again
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(ExpandSelectedEntry);
+    cx.run_until_parked();
+    cx.dispatch_action(CollapseSelectedEntry);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    > point <=== selected",
+            "    > count",
+        ]);
+    });
+
+    variable_list.update_in(cx, |variable_list, window, cx| {
+        variable_list.set_filter_query("Y", window, cx);
+    });
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v point <=== selected",
+            "        > y",
+        ]);
+    });
+
+    variable_list.update_in(cx, |variable_list, window, cx| {
+        variable_list.set_filter_query("3", window, cx);
+    });
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > count"]);
+    });
+
+    variable_list.update_in(cx, |variable_list, window, cx| {
+        variable_list.set_filter_query("", window, cx);
+    });
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v point <=== selected",
+            "        > x",
+            "        > y",
+            "    > count",
+        ]);
+    });
+}
+
+#[gpui::test]
+async fn test_pinned_variables_survive_stepping(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let test_file_content = r#"
+        const point = { x: 1, y: 2 };
+        const count = 3;
+    "#
+    .unindent();
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": test_file_content,
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: &str, value: &str, variables_reference: u64| Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+    let scope_variables = vec![variable("point", "{x, y}", 3), variable("count", "3", 0)];
+    let point_variables = vec![variable("x", "1", 0), variable("y", "2", 0)];
+
+    client.on_request::<Variables, _>(move |_, args| match args.variables_reference {
+        2 => Ok(dap::VariablesResponse {
+            variables: scope_variables.clone(),
+        }),
+        3 => Ok(dap::VariablesResponse {
+            variables: point_variables.clone(),
+        }),
+        _ => unreachable!(),
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            variable_list
+        });
+
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(TogglePinnedVariable);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Pinned",
+            "    > count",
+            "v Scope 1",
+            "    > point",
+            "    > count <=== selected",
+        ]);
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Step,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Pinned",
+            "    > count",
+            "v Scope 1",
+            "    > point",
+            "    > count",
+        ]);
+    });
+
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(TogglePinnedVariable);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > point", "    > count"]);
+    });
+}
+
+#[gpui::test]
+async fn test_changed_values_are_tracked_between_stops(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let test_file_content = r#"
+        const point = { x: 1, y: 2 };
+        const count = 3;
+    "#
+    .unindent();
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": test_file_content,
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: &str, value: &str, variables_reference: u64| Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+
+    let stepped = Arc::new(AtomicBool::new(false));
+    client.on_request::<Variables, _>({
+        let stepped = stepped.clone();
+        move |_, args| match args.variables_reference {
+            2 => {
+                let count = if stepped.load(Ordering::SeqCst) {
+                    "4"
+                } else {
+                    "3"
+                };
+                Ok(dap::VariablesResponse {
+                    variables: vec![variable("point", "{x, y}", 3), variable("count", count, 0)],
+                })
+            }
+            _ => unreachable!(),
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            variable_list
+        });
+
+    variable_list.update(cx, |variable_list, _| {
+        assert!(variable_list.changed_variable_names().is_empty());
+    });
+
+    stepped.store(true, Ordering::SeqCst);
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Step,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, cx| {
+        assert_eq!(vec!["count"], variable_list.changed_variable_names());
+
+        variable_list.toggle_changed_values_only(cx);
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > count"]);
+
+        variable_list.toggle_changed_values_only(cx);
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > point", "    > count"]);
+    });
+}
+
+#[gpui::test]
+async fn test_follow_pointer_shows_pointee_below_pointer(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { int x = 42; int *ptr = &x; return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: vec![Variable {
+                name: "ptr".into(),
+                value: "0x00007ffd5c3e1a4c".into(),
+                type_: Some("int *".into()),
+                presentation_hint: None,
+                evaluate_name: Some("ptr".into()),
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                declaration_location_reference: None,
+                value_location_reference: None,
+            }],
+        })
+    });
+
+    client.on_request::<Evaluate, _>(move |_, args| {
+        assert_eq!("*ptr", args.expression);
+        Ok(dap::EvaluateResponse {
+            result: "42".into(),
+            type_: Some("int".into()),
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            variable_list
+        });
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.run_until_parked();
+
+    cx.dispatch_action(FollowPointer);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v ptr <=== selected",
+            "        > *ptr",
+        ]);
+    });
+
+    cx.dispatch_action(FollowPointer);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    v ptr <=== selected"]);
+    });
+}
+
+#[gpui::test]
+async fn test_tree_navigation_actions(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: &str, variables_reference: u64| Variable {
+        name: name.into(),
+        value: "value".into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+
+    client.on_request::<Variables, _>(move |_, args| {
+        let variables = match args.variables_reference {
+            2 => vec![variable("a", 3), variable("b", 0), variable("c", 0)],
+            3 => vec![variable("x", 0), variable("inner", 4)],
+            4 => vec![variable("z", 0)],
+            _ => unreachable!(),
+        };
+        Ok(dap::VariablesResponse { variables })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            variable_list
+        });
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(ExpandSelectedEntryRecursively);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v a <=== selected",
+            "        > x",
+            "        v inner",
+            "            > z",
+            "    > b",
+            "    > c",
+        ]);
+    });
+
+    cx.dispatch_action(SelectLastSibling);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v a",
+            "        > x",
+            "        v inner",
+            "            > z",
+            "    > b",
+            "    > c <=== selected",
+        ]);
+    });
+
+    cx.dispatch_action(SelectFirstSibling);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(SelectLastSibling);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v a",
+            "        > x",
+            "        v inner <=== selected",
+            "            > z",
+            "    > b",
+            "    > c",
+        ]);
+    });
+
+    cx.dispatch_action(SelectParentEntry);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v a <=== selected",
+            "        > x",
+            "        v inner",
+            "            > z",
+            "    > b",
+            "    > c",
+        ]);
+    });
+
+    cx.dispatch_action(SelectParentEntry);
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1 <=== selected",
+            "    v a",
+            "        > x",
+            "        v inner",
+            "            > z",
+            "    > b",
+            "    > c",
+        ]);
+    });
+}
+
+#[gpui::test]
+async fn test_watches_are_evaluated_in_selected_frame(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { int count = 3; return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: vec![Variable {
+                name: "count".into(),
+                value: "3".into(),
+                type_: Some("int".into()),
+                presentation_hint: None,
+                evaluate_name: Some("count".into()),
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                declaration_location_reference: None,
+                value_location_reference: None,
+            }],
+        })
+    });
+
+    client.on_request::<Evaluate, _>(move |_, args| {
+        assert_eq!(Some(1), args.frame_id);
+        assert_eq!(Some(dap::EvaluateArgumentsContext::Watch), args.context);
+        Ok(dap::EvaluateResponse {
+            result: "6".into(),
+            type_: Some("int".into()),
+            presentation_hint: None,
+            variables_reference: 0,
+            named_variables: None,
+            indexed_variables: None,
+            memory_reference: None,
+            value_location_reference: None,
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            running.update(cx, |state, cx| {
+                state.add_watch(" count * 2 ".into(), window, cx);
+                state.add_watch("count * 2".into(), window, cx);
+                state.variable_list().clone()
+            })
+        });
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        assert_eq!(vec!["count * 2"], variable_list.watch_expressions());
+        variable_list.assert_visual_entries(vec![
+            "v Watches",
+            "    > count * 2",
+            "v Scope 1",
+            "    > count",
+        ]);
+    });
+
+    variable_list.update_in(cx, |_, window, cx| cx.focus_self(window));
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(RemoveWatch);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        assert!(variable_list.watch_expressions().is_empty());
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > count"]);
+    });
+}
+
+#[gpui::test]
+async fn test_editing_a_watch_reevaluates_it(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { int count = 3; return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_set_expression: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: Vec::new() }));
+
+    let count = Arc::new(Mutex::new("3".to_string()));
+    client.on_request::<Evaluate, _>({
+        let count = count.clone();
+        move |_, args| {
+            assert_eq!("count", args.expression);
+            Ok(dap::EvaluateResponse {
+                result: count.lock().unwrap().clone(),
+                type_: Some("int".into()),
+                presentation_hint: None,
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                value_location_reference: None,
+            })
+        }
+    });
+    client.on_request::<SetExpression, _>({
+        let count = count.clone();
+        move |_, args| {
+            assert_eq!("count", args.expression);
+            *count.lock().unwrap() = args.value.clone();
+            Ok(dap::SetExpressionResponse {
+                value: args.value,
+                type_: None,
+                presentation_hint: None,
+                variables_reference: None,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                value_location_reference: None,
+            })
+        }
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.add_watch("count".into(), window, cx);
+                state.variable_list().clone()
+            })
+        });
+    cx.run_until_parked();
+    variable_list.update(cx, |variable_list, _| {
+        assert_eq!(vec!["3"], variable_list.watch_values());
+    });
+
+    variable_list.update_in(cx, |_, window, cx| cx.focus_self(window));
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(EditVariable);
+    cx.run_until_parked();
+    cx.simulate_input("5");
+    cx.dispatch_action(menu::Confirm);
+    cx.run_until_parked();
+
+    assert_eq!("5", *count.lock().unwrap());
+    variable_list.update(cx, |variable_list, _| {
+        assert_eq!(
+            vec!["5"],
+            variable_list.watch_values(),
+            "The watch should be re-evaluated once its value is set"
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_watch_expression_at(cx: &mut TestAppContext) {
+    let text = "fn main() { let name = self.config.name; }";
+    let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(Arc::new(rust_lang()), cx));
+    cx.run_until_parked();
+
+    buffer.read_with(cx, |buffer, _| {
+        let snapshot = buffer.snapshot();
+        let at = |offset: usize| watch_expression_at(&snapshot, offset);
+
+        assert_eq!(Some("name".into()), at(text.find("name").unwrap() + 1));
+        assert_eq!(Some("self".into()), at(text.find("self").unwrap() + 1));
+        assert_eq!(
+            Some("self.config".into()),
+            at(text.find("config").unwrap() + 1)
+        );
+        assert_eq!(
+            Some("self.config.name".into()),
+            at(text.rfind("name").unwrap() + 1)
+        );
+        assert_eq!(None, at(text.find('{').unwrap()));
+    });
+}
+
+#[gpui::test]
+async fn test_type_column_can_be_toggled(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { int count = 3; char *name = 0; return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: vec![
+                Variable {
+                    name: "count".into(),
+                    value: "3".into(),
+                    type_: Some("int".into()),
+                    presentation_hint: None,
+                    evaluate_name: Some("count".into()),
+                    variables_reference: 0,
+                    named_variables: None,
+                    indexed_variables: None,
+                    memory_reference: None,
+                    declaration_location_reference: None,
+                    value_location_reference: None,
+                },
+                Variable {
+                    name: "untyped".into(),
+                    value: "1".into(),
+                    type_: None,
+                    presentation_hint: None,
+                    evaluate_name: Some("untyped".into()),
+                    variables_reference: 0,
+                    named_variables: None,
+                    indexed_variables: None,
+                    memory_reference: None,
+                    declaration_location_reference: None,
+                    value_location_reference: None,
+                },
+            ],
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            })
+        });
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > count", "    > untyped"]);
+    });
+
+    variable_list.update_in(cx, |_, window, cx| cx.focus_self(window));
+    cx.dispatch_action(ToggleTypeColumn);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > count: int", "    > untyped"]);
+    });
+
+    cx.dispatch_action(ToggleTypeColumn);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Scope 1", "    > count", "    > untyped"]);
+    });
+}
+
+#[gpui::test]
+async fn test_large_collections_are_fetched_in_pages(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { int values[250] = {0}; return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: String, variables_reference, indexed_variables| Variable {
+        name,
+        value: "0".into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+    client.on_request::<Variables, _>(move |_, args| {
+        let variables = match (args.variables_reference, args.filter) {
+            (2, None) => vec![variable("values".into(), 3, Some(250))],
+            (3, Some(VariablesArgumentsFilter::Named)) => vec![variable("len".into(), 0, None)],
+            (3, Some(VariablesArgumentsFilter::Indexed)) => {
+                let start = args.start.unwrap_or_default();
+                let end = start + args.count.unwrap_or_default();
+                (start..end)
+                    .map(|ix| variable(format!("[{ix}]"), 0, None))
+                    .collect()
+            }
+            (reference, filter) => {
+                panic!("unexpected request for the children of {reference} with {filter:?}")
+            }
+        };
+        Ok(dap::VariablesResponse { variables })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            })
+        });
+    cx.run_until_parked();
+
+    variable_list.update_in(cx, |_, window, cx| cx.focus_self(window));
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(SelectNext);
+    cx.dispatch_action(ExpandSelectedEntry);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Scope 1",
+            "    v values <=== selected",
+            "        > len",
+            "        > [0..99]",
+            "        > [100..199]",
+            "        > [200..249]",
+        ]);
+    });
+
+    for _ in 0..4 {
+        cx.dispatch_action(SelectNext);
+    }
+    cx.dispatch_action(ExpandSelectedEntry);
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        let mut expected = vec![
+            "v Scope 1".to_string(),
+            "    v values".to_string(),
+            "        > len".to_string(),
+            "        > [0..99]".to_string(),
+            "        > [100..199]".to_string(),
+            "        v [200..249] <=== selected".to_string(),
+        ];
+        expected.extend((200..250).map(|ix| format!("            > [{ix}]")));
+        variable_list.assert_visual_entries(expected.iter().map(String::as_str).collect());
+    });
+}
+
+#[gpui::test]
+async fn test_copy_scope_as_json(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                settings.copy_as_json_depth = 1;
+            });
+        });
+    });
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() { int count = 3; return 0; }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.c".into()),
+                    path: Some(path!("/project/main.c").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: &str, value: &str, variables_reference, indexed_variables| Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference,
+        named_variables: None,
+        indexed_variables,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+    client.on_request::<Variables, _>(move |_, args| {
+        let variables = match args.variables_reference {
+            2 => vec![
+                variable("count", "3", 0, None),
+                variable("name", "\"zed\"", 0, None),
+                variable("point", "{x, inner}", 3, None),
+                variable("items", "size=2", 4, Some(2)),
+            ],
+            3 => vec![
+                variable("x", "1.5", 0, None),
+                variable("inner", "{deep}", 5, None),
+            ],
+            4 => vec![
+                variable("[0]", "true", 0, None),
+                variable("[1]", "false", 0, None),
+            ],
+            reference => panic!("unexpected request for the children of {reference}"),
+        };
+        Ok(dap::VariablesResponse { variables })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+    let variable_list =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            })
+        });
+    cx.run_until_parked();
+
+    variable_list.update_in(cx, |_, window, cx| cx.focus_self(window));
+    cx.dispatch_action(SelectFirst);
+    cx.dispatch_action(CopyScopeAsJson);
+    cx.run_until_parked();
+
+    let copied = cx
+        .read_from_clipboard()
+        .and_then(|item| item.text())
+        .expect("the scope should have been copied");
+    assert_eq!(
+        json!({
+            "count": 3,
+            "name": "zed",
+            "point": { "x": 1.5, "inner": "{deep}" },
+            "items": [true, false],
+        }),
+        serde_json::from_str::<serde_json::Value>(&copied).unwrap()
+    );
+}
+
+#[gpui::test]
+async fn test_return_value_is_shown_after_stepping_over(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.py": "count = compute()",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "<module>".into(),
+                source: Some(dap::Source {
+                    name: Some("main.py".into()),
+                    path: Some(path!("/project/main.py").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Locals".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let variable = |name: &str, value: &str| Variable {
+        name: name.into(),
+        value: value.into(),
+        type_: None,
+        presentation_hint: None,
+        evaluate_name: None,
+        variables_reference: 0,
+        named_variables: None,
+        indexed_variables: None,
+        memory_reference: None,
+        declaration_location_reference: None,
+        value_location_reference: None,
+    };
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: vec![variable("count", "3"), variable("(return) compute", "6")],
+        })
+    });
+    client.on_request::<Next, _>(move |_, _| Ok(()));
+    client.on_request::<StepIn, _>(move |_, _| Ok(()));
+
+    let stop = || {
+        dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Step,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        })
+    };
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            let running = item.running_state().clone();
+            running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+            });
+            running
+        });
+    let variable_list = running_state.update(cx, |state, _| state.variable_list().clone());
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Locals",
+            "    > count",
+            "    > (return) compute",
+        ]);
+    });
+
+    running_state.update(cx, |state, cx| state.step_over(cx));
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec!["v Locals", "    > Return value", "    > count"]);
+    });
+
+    running_state.update(cx, |state, cx| state.step_in(cx));
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    variable_list.update(cx, |variable_list, _| {
+        variable_list.assert_visual_entries(vec![
+            "v Locals",
+            "    > count",
+            "    > (return) compute",
+        ]);
+    });
+}
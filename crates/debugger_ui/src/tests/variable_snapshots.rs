@@ -0,0 +1,202 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    session::running::{
+        variable_list::VariableListEvent,
+        variable_snapshots::{SnapshotChange, SnapshotDiffEntry, SnapshotValues, diff_snapshots},
+    },
+    tests::{
+        active_debug_session_panel, init_test, init_test_workspace, start_debug_session, variable,
+    },
+};
+use dap::{
+    Scope, StackFrame, StoppedEvent,
+    requests::{Scopes, StackTrace, Variables},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project};
+use serde_json::json;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use util::path;
+
+#[gpui::test]
+async fn test_compare_snapshot_with_current_values(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "fn main() { for i in 0..3 { let sum = i * 2; } }",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let session = start_debug_session(&workspace, cx, |_| {}).unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: Some(dap::Source {
+                    name: Some("main.rs".into()),
+                    path: Some(path!("/project/main.rs").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Locals".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    let stepped = Arc::new(AtomicBool::new(false));
+    client.on_request::<Variables, _>({
+        let stepped = stepped.clone();
+        move |_, _| {
+            let variables = if stepped.load(Ordering::SeqCst) {
+                vec![variable("i", "1", 0), variable("sum", "2", 0)]
+            } else {
+                vec![variable("i", "0", 0), variable("done", "false", 0)]
+            };
+            Ok(dap::VariablesResponse { variables })
+        }
+    });
+
+    let stop = || {
+        dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Step,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        })
+    };
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    let (variable_list, snapshots) =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, _, cx| {
+            let running = item.running_state().read(cx);
+            (
+                running.variable_list().clone(),
+                running.variable_snapshots().clone(),
+            )
+        });
+    variable_list.update(cx, |_, cx| cx.emit(VariableListEvent::CaptureSnapshot));
+    cx.run_until_parked();
+
+    snapshots.read_with(cx, |snapshots, _| {
+        assert!(snapshots.visual_diff().is_empty());
+    });
+
+    stepped.store(true, Ordering::SeqCst);
+    client.fake_event(stop()).await;
+    cx.run_until_parked();
+
+    snapshots.read_with(cx, |snapshots, _| {
+        assert_eq!(
+            vec![
+                "~ Locals › i = 0 → 1",
+                "+ Locals › sum = 2",
+                "- Locals › done = false",
+            ],
+            snapshots.visual_diff()
+        );
+    });
+}
+
+#[test]
+fn test_diff_snapshots() {
+    let values = |values: &[(&str, &str)]| -> SnapshotValues {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string().into(), value.to_string().into()))
+            .collect()
+    };
+    let old = values(&[("a", "1"), ("b", "2"), ("c", "3")]);
+    let new = values(&[("a", "1"), ("c", "4"), ("d", "5")]);
+
+    assert_eq!(
+        vec![
+            SnapshotDiffEntry {
+                key: "a".into(),
+                value: "1".into(),
+                change: SnapshotChange::Unchanged,
+            },
+            SnapshotDiffEntry {
+                key: "c".into(),
+                value: "4".into(),
+                change: SnapshotChange::Changed {
+                    old_value: "3".into(),
+                },
+            },
+            SnapshotDiffEntry {
+                key: "d".into(),
+                value: "5".into(),
+                change: SnapshotChange::Added,
+            },
+            SnapshotDiffEntry {
+                key: "b".into(),
+                value: "2".into(),
+                change: SnapshotChange::Removed,
+            },
+        ],
+        diff_snapshots(&old, &new)
+    );
+}
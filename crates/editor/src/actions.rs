@@ -88,6 +88,8 @@ pub struct ToggleCodeActions {
 pub enum CodeActionSource {
     Indicator(DisplayRow),
     RunMenu(DisplayRow),
+    /// Like `RunMenu`, but only lists the debug scenarios of the row's runnables.
+    DebugMenu(DisplayRow),
     QuickActionBar,
 }
 
@@ -244,7 +246,19 @@ impl_actions!(
     ]
 );
 
-actions!(debugger, [RunToCursor, EvaluateSelectedText]);
+actions!(
+    debugger,
+    [
+        RunToCursor,
+        JumpToCursor,
+        EvaluateSelectedText,
+        EvaluateSelectedTextInline,
+        AddToWatch,
+        DebugTestUnderCursor,
+        OpenDisassembly,
+        ToggleLogpoint
+    ]
+);
 
 actions!(
     editor,
@@ -1376,9 +1376,9 @@ impl CodeActionsMenu {
 
     fn origin(&self) -> ContextMenuOrigin {
         match &self.deployed_from {
-            Some(CodeActionSource::Indicator(row)) | Some(CodeActionSource::RunMenu(row)) => {
-                ContextMenuOrigin::GutterIndicator(*row)
-            }
+            Some(CodeActionSource::Indicator(row))
+            | Some(CodeActionSource::RunMenu(row))
+            | Some(CodeActionSource::DebugMenu(row)) => ContextMenuOrigin::GutterIndicator(*row),
             Some(CodeActionSource::QuickActionBar) => ContextMenuOrigin::QuickActionBar,
             None => ContextMenuOrigin::Cursor,
         }
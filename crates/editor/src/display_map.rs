@@ -517,6 +517,14 @@ impl DisplayMap {
         self.text_highlights.values()
     }
 
+    /// Removes the highlights of the given type whose keys fall within `keys`.
+    pub fn clear_keyed_highlights(&mut self, type_id: TypeId, keys: Range<usize>) {
+        self.text_highlights.remove_range(
+            &HighlightKey::TypePlus(type_id, keys.start),
+            &HighlightKey::TypePlus(type_id, keys.end),
+        );
+    }
+
     pub fn clear_highlights(&mut self, type_id: TypeId) -> bool {
         let mut cleared = self
             .text_highlights
@@ -60,7 +60,10 @@ use client::{Collaborator, ParticipantIndex};
 use clock::{AGENT_REPLICA_ID, ReplicaId};
 use collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use convert_case::{Case, Casing};
-use dap::TelemetrySpawnLocation;
+use dap::{
+    TelemetrySpawnLocation,
+    debugger_settings::{DebuggerSettings, GutterClickAction, InlineValues},
+};
 use display_map::*;
 pub use display_map::{ChunkRenderer, ChunkRendererContext, DisplayPoint, FoldPlaceholder};
 pub use editor_settings::{
@@ -93,10 +96,11 @@ use gpui::{
     AvailableSpace, Background, Bounds, ClickEvent, ClipboardEntry, ClipboardItem, Context,
     DispatchPhase, Edges, Entity, EntityInputHandler, EventEmitter, FocusHandle, FocusOutEvent,
     Focusable, FontId, FontWeight, Global, HighlightStyle, Hsla, KeyContext, Modifiers,
-    MouseButton, MouseDownEvent, PaintQuad, ParentElement, Pixels, Render, ScrollHandle,
-    SharedString, Size, Stateful, Styled, Subscription, Task, TextStyle, TextStyleRefinement,
-    UTF16Selection, UnderlineStyle, UniformListScrollHandle, WeakEntity, WeakFocusHandle, Window,
-    div, impl_actions, point, prelude::*, pulsating_between, px, relative, size,
+    MouseButton, MouseDownEvent, PaintQuad, ParentElement, Pixels, PromptLevel, Render,
+    ScrollHandle, SharedString, Size, Stateful, Styled, Subscription, Task, TextStyle,
+    TextStyleRefinement, UTF16Selection, UnderlineStyle, UniformListScrollHandle, WeakEntity,
+    WeakFocusHandle, Window, div, impl_actions, point, prelude::*, pulsating_between, px, relative,
+    size,
 };
 use highlight_matching_bracket::refresh_matching_bracket_highlights;
 use hover_links::{HoverLink, HoveredLinkState, InlayHighlight, find_file};
@@ -256,8 +260,18 @@ pub type RenderDiffHunkControlsFn = Arc<
     ) -> AnyElement,
 >;
 
+/// The result of evaluating an expression in a debugged program, shown at the end of the line it
+/// was evaluated on until the selections change.
+struct EvaluationAnnotation {
+    position: Anchor,
+    value: SharedString,
+    is_error: bool,
+}
+
 struct InlineValueCache {
     enabled: bool,
+    /// The `debugger.inline_values` setting the inlays were last refreshed with.
+    placement: Option<InlineValues>,
     inlays: Vec<InlayId>,
     refresh_task: Task<Option<()>>,
 }
@@ -266,6 +280,7 @@ impl InlineValueCache {
     fn new(enabled: bool) -> Self {
         Self {
             enabled,
+            placement: None,
             inlays: Vec::new(),
             refresh_task: Task::ready(None),
         }
@@ -293,6 +308,7 @@ impl InlayId {
 }
 
 pub enum ActiveDebugLine {}
+pub enum TopStackFrameLine {}
 pub enum DebugStackFrameLine {}
 enum DocumentHighlightRead {}
 enum DocumentHighlightWrite {}
@@ -849,6 +865,11 @@ pub trait Addon: 'static {
         None
     }
 
+    /// Drawn over the editor's text, in the given corner, e.g. for floating controls.
+    fn render_overlay(&self, _: &Window, _: &App) -> Option<(Corner, AnyElement)> {
+        None
+    }
+
     fn to_any(&self) -> &dyn std::any::Any;
 
     fn to_any_mut(&mut self) -> Option<&mut dyn std::any::Any> {
@@ -1109,6 +1130,8 @@ pub struct Editor {
     tasks_update_task: Option<Task<()>>,
     breakpoint_store: Option<Entity<BreakpointStore>>,
     gutter_breakpoint_indicator: (Option<PhantomBreakpointIndicator>, Option<Task<()>>),
+    /// The row the execution pointer is being dragged to, while it's dragged.
+    execution_pointer_drag: Option<DisplayRow>,
     hovered_diff_hunk_row: Option<DisplayRow>,
     pull_diagnostics_task: Task<()>,
     in_project_search: bool,
@@ -1131,6 +1154,9 @@ pub struct Editor {
     hide_mouse_mode: HideMouseMode,
     pub change_list: ChangeList,
     inline_value_cache: InlineValueCache,
+    /// The exception a debugged thread stopped on, shown at the end of the line that threw it.
+    exception_annotation: Option<(text::Anchor, SharedString)>,
+    evaluation_annotation: Option<EvaluationAnnotation>,
     selection_drag_state: SelectionDragState,
     drag_and_drop_selection_enabled: bool,
     next_color_inlay_id: usize,
@@ -1388,6 +1414,8 @@ impl SelectionHistory {
 pub struct RowHighlightOptions {
     pub autoscroll: bool,
     pub include_gutter: bool,
+    /// Outlines the highlighted rows, e.g. to draw them hollow with a transparent color.
+    pub border: Option<Hsla>,
 }
 
 impl Default for RowHighlightOptions {
@@ -1395,6 +1423,7 @@ impl Default for RowHighlightOptions {
         Self {
             autoscroll: Default::default(),
             include_gutter: true,
+            border: None,
         }
     }
 }
@@ -1849,7 +1878,10 @@ impl Editor {
                     |editor, _, event, window, cx| match event {
                         BreakpointStoreEvent::ClearDebugLines => {
                             editor.clear_row_highlights::<ActiveDebugLine>();
+                            editor.clear_row_highlights::<TopStackFrameLine>();
                             editor.refresh_inline_values(cx);
+                            editor.evaluation_annotation = None;
+                            editor.scrollbar_marker_state.dirty = true;
                         }
                         BreakpointStoreEvent::SetDebugLine => {
                             if editor.go_to_active_debug_line(window, cx) {
@@ -1857,8 +1889,14 @@ impl Editor {
                             }
 
                             editor.refresh_inline_values(cx);
+                            editor.evaluation_annotation = None;
+                            editor.scrollbar_marker_state.dirty = true;
+                        }
+                        BreakpointStoreEvent::BreakpointsUpdated(..)
+                        | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                            editor.scrollbar_marker_state.dirty = true;
+                            cx.notify();
                         }
-                        _ => {}
                     },
                 ));
                 let git_store = project.read(cx).git_store().clone();
@@ -2036,6 +2074,8 @@ impl Editor {
             },
             inline_diagnostics_enabled: mode.is_full(),
             inline_value_cache: InlineValueCache::new(inlay_hint_settings.show_value_hints),
+            exception_annotation: None,
+            evaluation_annotation: None,
             inlay_hint_cache: InlayHintCache::new(inlay_hint_settings),
 
             gutter_hovered: false,
@@ -2072,6 +2112,7 @@ impl Editor {
 
             breakpoint_store,
             gutter_breakpoint_indicator: (None, None),
+            execution_pointer_drag: None,
             hovered_diff_hunk_row: None,
             _subscriptions: vec![
                 cx.observe(&buffer, Self::on_buffer_changed),
@@ -2870,6 +2911,7 @@ impl Editor {
         self.select_next_state = None;
         self.select_prev_state = None;
         self.select_syntax_node_history.try_clear();
+        self.evaluation_annotation = None;
         self.invalidate_autoclose_regions(&self.selections.disjoint_anchors(), buffer);
         self.snippet_stack
             .invalidate(&self.selections.disjoint_anchors(), buffer);
@@ -3742,6 +3784,11 @@ impl Editor {
             return true;
         }
 
+        if self.evaluation_annotation.take().is_some() {
+            cx.notify();
+            return true;
+        }
+
         false
     }
 
@@ -5857,7 +5904,9 @@ impl Editor {
         self.discard_inline_completion(false, cx);
 
         let multibuffer_point = match &action.deployed_from {
-            Some(CodeActionSource::Indicator(row)) | Some(CodeActionSource::RunMenu(row)) => {
+            Some(CodeActionSource::Indicator(row))
+            | Some(CodeActionSource::RunMenu(row))
+            | Some(CodeActionSource::DebugMenu(row)) => {
                 DisplayPoint::new(*row, 0).to_point(&snapshot)
             }
             _ => self.selections.newest::<Point>(cx).head(),
@@ -5886,7 +5935,9 @@ impl Editor {
         let project = self.project.clone();
 
         let code_actions_task = match deployed_from {
-            Some(CodeActionSource::RunMenu(_)) => Task::ready(None),
+            Some(CodeActionSource::RunMenu(_)) | Some(CodeActionSource::DebugMenu(_)) => {
+                Task::ready(None)
+            }
             _ => self.code_actions(buffer_row, window, cx),
         };
 
@@ -5928,16 +5979,23 @@ impl Editor {
         };
 
         cx.spawn_in(window, async move |editor, cx| {
-            let (resolved_tasks, debug_scenarios, task_context) = runnable_task.await?;
+            let (mut resolved_tasks, debug_scenarios, task_context) = runnable_task.await?;
             let code_actions = code_actions_task.await;
-            let spawn_straight_away = quick_launch
-                && resolved_tasks
-                    .as_ref()
-                    .map_or(false, |tasks| tasks.templates.len() == 1)
-                && code_actions
-                    .as_ref()
-                    .map_or(true, |actions| actions.is_empty())
-                && debug_scenarios.is_empty();
+            let spawn_straight_away =
+                if matches!(deployed_from, Some(CodeActionSource::DebugMenu(_))) {
+                    // The tasks were only resolved to find the scenarios that debug them.
+                    resolved_tasks = None;
+                    quick_launch && debug_scenarios.len() == 1
+                } else {
+                    quick_launch
+                        && resolved_tasks
+                            .as_ref()
+                            .map_or(false, |tasks| tasks.templates.len() == 1)
+                        && code_actions
+                            .as_ref()
+                            .map_or(true, |actions| actions.is_empty())
+                        && debug_scenarios.is_empty()
+                };
 
             editor.update_in(cx, |editor, window, cx| {
                 crate::hover_popover::hide_hover(editor, cx);
@@ -7682,52 +7740,12 @@ impl Editor {
             .breakpoint_at_row(row, window, cx)
             .map(|(anchor, bp)| (anchor, Arc::from(bp)));
 
-        let log_breakpoint_msg = if breakpoint.as_ref().is_some_and(|bp| bp.1.message.is_some()) {
-            "Edit Log Breakpoint"
-        } else {
-            "Set Log Breakpoint"
-        };
-
-        let condition_breakpoint_msg = if breakpoint
-            .as_ref()
-            .is_some_and(|bp| bp.1.condition.is_some())
-        {
-            "Edit Condition Breakpoint"
-        } else {
-            "Set Condition Breakpoint"
-        };
-
-        let hit_condition_breakpoint_msg = if breakpoint
-            .as_ref()
-            .is_some_and(|bp| bp.1.hit_condition.is_some())
-        {
-            "Edit Hit Condition Breakpoint"
-        } else {
-            "Set Hit Condition Breakpoint"
-        };
-
-        let set_breakpoint_msg = if breakpoint.as_ref().is_some() {
-            "Unset Breakpoint"
-        } else {
-            "Set Breakpoint"
-        };
-
         let run_to_cursor = window.is_action_available(&RunToCursor, cx);
-
-        let toggle_state_msg = breakpoint.as_ref().map_or(None, |bp| match bp.1.state {
-            BreakpointState::Enabled => Some("Disable"),
-            BreakpointState::Disabled => Some("Enable"),
-        });
-
-        let (anchor, breakpoint) =
-            breakpoint.unwrap_or_else(|| (anchor, Arc::new(Breakpoint::new_standard())));
-
-        ui::ContextMenu::build(window, cx, |menu, _, _cx| {
-            menu.on_blur_subscription(Subscription::new(|| {}))
-                .context(focus_handle)
-                .when(run_to_cursor, |this| {
-                    let weak_editor = weak_editor.clone();
-                    this.entry("Run to cursor", None, move |window, cx| {
+        let run_to_cursor_entry = {
+            let weak_editor = weak_editor.clone();
+            move |menu: ui::ContextMenu| {
+                menu.when(run_to_cursor, |menu| {
+                    menu.entry("Run to cursor", None, move |window, cx| {
                         weak_editor
                             .update(cx, |editor, cx| {
                                 editor.change_selections(None, window, cx, |s| {
@@ -7740,8 +7758,17 @@ impl Editor {
                     })
                     .separator()
                 })
-                .when_some(toggle_state_msg, |this, msg| {
-                    this.entry(msg, None, {
+            }
+        };
+
+        let Some((anchor, breakpoint)) = breakpoint else {
+            let breakpoint = Arc::new(Breakpoint::new_standard());
+
+            return ui::ContextMenu::build(window, cx, |menu, _, _cx| {
+                menu.on_blur_subscription(Subscription::new(|| {}))
+                    .context(focus_handle)
+                    .map(run_to_cursor_entry)
+                    .entry("Set Breakpoint", None, {
                         let weak_editor = weak_editor.clone();
                         let breakpoint = breakpoint.clone();
                         move |_window, cx| {
@@ -7750,24 +7777,112 @@ impl Editor {
                                     this.edit_breakpoint_at_anchor(
                                         anchor,
                                         breakpoint.as_ref().clone(),
-                                        BreakpointEditAction::InvertState,
+                                        BreakpointEditAction::Toggle,
                                         cx,
                                     );
                                 })
                                 .log_err();
                         }
                     })
-                })
-                .entry(set_breakpoint_msg, None, {
+                    .entry("Set Log Breakpoint", None, {
+                        let breakpoint = breakpoint.clone();
+                        let weak_editor = weak_editor.clone();
+                        move |window, cx| {
+                            weak_editor
+                                .update(cx, |this, cx| {
+                                    this.add_edit_breakpoint_block(
+                                        anchor,
+                                        breakpoint.as_ref(),
+                                        BreakpointPromptEditAction::Log,
+                                        window,
+                                        cx,
+                                    );
+                                })
+                                .log_err();
+                        }
+                    })
+                    .entry("Set Condition Breakpoint", None, {
+                        let breakpoint = breakpoint.clone();
+                        let weak_editor = weak_editor.clone();
+                        move |window, cx| {
+                            weak_editor
+                                .update(cx, |this, cx| {
+                                    this.add_edit_breakpoint_block(
+                                        anchor,
+                                        breakpoint.as_ref(),
+                                        BreakpointPromptEditAction::Condition,
+                                        window,
+                                        cx,
+                                    );
+                                })
+                                .log_err();
+                        }
+                    })
+                    .entry("Set Hit Condition Breakpoint", None, move |window, cx| {
+                        weak_editor
+                            .update(cx, |this, cx| {
+                                this.add_edit_breakpoint_block(
+                                    anchor,
+                                    breakpoint.as_ref(),
+                                    BreakpointPromptEditAction::HitCondition,
+                                    window,
+                                    cx,
+                                );
+                            })
+                            .log_err();
+                    })
+            });
+        };
+
+        let log_breakpoint_msg = if breakpoint.message.is_some() {
+            "Edit Log Message"
+        } else {
+            "Convert to Logpoint"
+        };
+
+        let note_msg = if breakpoint.note.is_some() {
+            "Edit Note"
+        } else {
+            "Add Note"
+        };
+
+        let toggle_state_msg = match breakpoint.state {
+            BreakpointState::Enabled => "Disable",
+            BreakpointState::Disabled => "Enable",
+        };
+
+        ui::ContextMenu::build(window, cx, |menu, _, _cx| {
+            menu.on_blur_subscription(Subscription::new(|| {}))
+                .context(focus_handle)
+                .map(run_to_cursor_entry)
+                .entry("Edit Condition", None, {
+                    let breakpoint = breakpoint.clone();
                     let weak_editor = weak_editor.clone();
+                    move |window, cx| {
+                        weak_editor
+                            .update(cx, |this, cx| {
+                                this.add_edit_breakpoint_block(
+                                    anchor,
+                                    breakpoint.as_ref(),
+                                    BreakpointPromptEditAction::Condition,
+                                    window,
+                                    cx,
+                                );
+                            })
+                            .log_err();
+                    }
+                })
+                .entry("Edit Hit Count", None, {
                     let breakpoint = breakpoint.clone();
-                    move |_window, cx| {
+                    let weak_editor = weak_editor.clone();
+                    move |window, cx| {
                         weak_editor
                             .update(cx, |this, cx| {
-                                this.edit_breakpoint_at_anchor(
+                                this.add_edit_breakpoint_block(
                                     anchor,
-                                    breakpoint.as_ref().clone(),
-                                    BreakpointEditAction::Toggle,
+                                    breakpoint.as_ref(),
+                                    BreakpointPromptEditAction::HitCondition,
+                                    window,
                                     cx,
                                 );
                             })
@@ -7791,7 +7906,7 @@ impl Editor {
                             .log_err();
                     }
                 })
-                .entry(condition_breakpoint_msg, None, {
+                .entry(note_msg, None, {
                     let breakpoint = breakpoint.clone();
                     let weak_editor = weak_editor.clone();
                     move |window, cx| {
@@ -7800,7 +7915,7 @@ impl Editor {
                                 this.add_edit_breakpoint_block(
                                     anchor,
                                     breakpoint.as_ref(),
-                                    BreakpointPromptEditAction::Condition,
+                                    BreakpointPromptEditAction::Note,
                                     window,
                                     cx,
                                 );
@@ -7808,14 +7923,30 @@ impl Editor {
                             .log_err();
                     }
                 })
-                .entry(hit_condition_breakpoint_msg, None, move |window, cx| {
+                .separator()
+                .entry(toggle_state_msg, None, {
+                    let weak_editor = weak_editor.clone();
+                    let breakpoint = breakpoint.clone();
+                    move |_window, cx| {
+                        weak_editor
+                            .update(cx, |this, cx| {
+                                this.edit_breakpoint_at_anchor(
+                                    anchor,
+                                    breakpoint.as_ref().clone(),
+                                    BreakpointEditAction::InvertState,
+                                    cx,
+                                );
+                            })
+                            .log_err();
+                    }
+                })
+                .entry("Remove", None, move |_window, cx| {
                     weak_editor
                         .update(cx, |this, cx| {
-                            this.add_edit_breakpoint_block(
+                            this.edit_breakpoint_at_anchor(
                                 anchor,
-                                breakpoint.as_ref(),
-                                BreakpointPromptEditAction::HitCondition,
-                                window,
+                                breakpoint.as_ref().clone(),
+                                BreakpointEditAction::Toggle,
                                 cx,
                             );
                         })
@@ -7832,7 +7963,8 @@ impl Editor {
         state: Option<BreakpointSessionState>,
         cx: &mut Context<Self>,
     ) -> IconButton {
-        let is_rejected = state.is_some_and(|s| !s.verified);
+        let is_rejected = state.as_ref().is_some_and(|s| !s.verified);
+        let adapter_message = state.and_then(|s| s.message);
         // Is it a breakpoint that shows up when hovering over gutter?
         let (is_phantom, collides_with_existing) = self.gutter_breakpoint_indicator.0.map_or(
             (false, false),
@@ -7883,14 +8015,24 @@ impl Editor {
         let focus_handle = self.focus_handle.clone();
 
         let meta = if is_rejected {
-            SharedString::from("No executable code is associated with this line.")
-        } else if collides_with_existing && !breakpoint.is_disabled() {
+            adapter_message.map(SharedString::from).unwrap_or_else(|| {
+                SharedString::from("No executable code is associated with this line.")
+            })
+        } else if collides_with_existing
+            && !breakpoint.is_disabled()
+            && DebuggerSettings::get_global(cx).gutter_click_gestures.secondary
+                == GutterClickAction::ToggleEnabled
+        {
             SharedString::from(format!(
                 "{alt_as_text}-click to disable,\nright-click for more options."
             ))
         } else {
             SharedString::from("Right-click for more options.")
         };
+        let meta = match breakpoint.note.as_ref() {
+            Some(note) if !is_phantom => SharedString::from(format!("{note}\n\n{meta}")),
+            _ => meta,
+        };
         IconButton::new(("breakpoint_indicator", row.0 as usize), icon)
             .icon_size(IconSize::XSmall)
             .size(ui::ButtonSize::None)
@@ -7903,17 +8045,11 @@ impl Editor {
                 let breakpoint = breakpoint.clone();
 
                 move |editor, event: &ClickEvent, window, cx| {
-                    let edit_action = if event.modifiers().platform || breakpoint.is_disabled() {
-                        BreakpointEditAction::InvertState
-                    } else {
-                        BreakpointEditAction::Toggle
-                    };
-
-                    window.focus(&editor.focus_handle(cx));
-                    editor.edit_breakpoint_at_anchor(
+                    editor.click_breakpoint_indicator(
                         position,
-                        breakpoint.as_ref().clone(),
-                        edit_action,
+                        &breakpoint,
+                        event.modifiers(),
+                        window,
                         cx,
                     );
                 }
@@ -7939,6 +8075,164 @@ impl Editor {
             })
     }
 
+    /// Runs the gutter click action bound to the held modifiers on the breakpoint indicator at
+    /// `position`, which may be a phantom breakpoint on a line without one.
+    fn click_breakpoint_indicator(
+        &mut self,
+        position: Anchor,
+        breakpoint: &Breakpoint,
+        modifiers: Modifiers,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let gestures = DebuggerSettings::get_global(cx).gutter_click_gestures;
+        let click_action = if modifiers.secondary() {
+            gestures.secondary
+        } else if modifiers.alt {
+            gestures.alt
+        } else if modifiers.shift {
+            gestures.shift
+        } else {
+            GutterClickAction::Toggle
+        };
+
+        window.focus(&self.focus_handle(cx));
+        let prompt_action = match click_action {
+            GutterClickAction::Toggle | GutterClickAction::ToggleEnabled => {
+                let edit_action = if click_action == GutterClickAction::ToggleEnabled
+                    || breakpoint.is_disabled()
+                {
+                    BreakpointEditAction::InvertState
+                } else {
+                    BreakpointEditAction::Toggle
+                };
+                self.edit_breakpoint_at_anchor(position, breakpoint.clone(), edit_action, cx);
+                return;
+            }
+            GutterClickAction::LogBreakpoint => BreakpointPromptEditAction::Log,
+            GutterClickAction::ConditionBreakpoint => BreakpointPromptEditAction::Condition,
+            GutterClickAction::HitConditionBreakpoint => BreakpointPromptEditAction::HitCondition,
+        };
+        self.add_edit_breakpoint_block(position, breakpoint, prompt_action, window, cx);
+    }
+
+    /// Whether a debug session is stopped, with one of its frames shown in the editor.
+    fn is_stopped_in_debugger(&self, cx: &App) -> bool {
+        self.breakpoint_store
+            .as_ref()
+            .is_some_and(|store| store.read(cx).active_position().is_some())
+    }
+
+    fn render_run_to_cursor_indicator(
+        &self,
+        row: DisplayRow,
+        cx: &mut Context<Self>,
+    ) -> IconButton {
+        let focus_handle = self.focus_handle.clone();
+        IconButton::new(
+            ("run_to_cursor_indicator", row.0 as usize),
+            IconName::DebugContinue,
+        )
+        .icon_size(IconSize::XSmall)
+        .size(ui::ButtonSize::None)
+        .icon_color(Color::Hint)
+        .style(ButtonStyle::Transparent)
+        .on_click(cx.listener(move |editor, _, window, cx| {
+            let snapshot = editor.snapshot(window, cx);
+            let point = DisplayPoint::new(row, 0).to_point(&snapshot.display_snapshot);
+            window.focus(&editor.focus_handle(cx));
+            editor.change_selections(None, window, cx, |s| {
+                s.select_ranges([Point::new(point.row, 0)..Point::new(point.row, 0)])
+            });
+            window.dispatch_action(Box::new(RunToCursor), cx);
+        }))
+        .tooltip(move |window, cx| {
+            Tooltip::for_action_in("Run to Here", &RunToCursor, &focus_handle, window, cx)
+        })
+    }
+
+    /// The display rows of the line a debugged thread is stopped on.
+    fn active_debug_rows(&self, snapshot: &DisplaySnapshot) -> Vec<DisplayRow> {
+        self.highlighted_rows::<ActiveDebugLine>()
+            .map(|(range, _)| range.start.to_display_point(snapshot).row())
+            .collect()
+    }
+
+    /// The arrow drawn in place of the line number of the line a debugged thread is stopped on.
+    /// When `draggable`, it can be dropped on another line to resume execution from there.
+    fn render_execution_pointer(
+        &self,
+        row: DisplayRow,
+        draggable: bool,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        div()
+            .id(("execution_pointer", row.0 as usize))
+            .child(
+                Icon::new(IconName::ArrowRight)
+                    .size(IconSize::XSmall)
+                    .color(Color::Warning),
+            )
+            .when(draggable, |this| {
+                this.cursor_ns_resize()
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |editor, _, _, cx| {
+                            editor.execution_pointer_drag = Some(row);
+                            cx.stop_propagation();
+                            cx.notify();
+                        }),
+                    )
+                    .tooltip(move |window, cx| {
+                        Tooltip::with_meta(
+                            "Current Line",
+                            None,
+                            "Drag to another line to jump there",
+                            window,
+                            cx,
+                        )
+                    })
+            })
+    }
+
+    /// Asks whether to resume execution from the line the execution pointer was dropped on, and
+    /// jumps there with [`JumpToCursor`] if so.
+    fn drop_execution_pointer(
+        &mut self,
+        row: DisplayRow,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.snapshot(window, cx);
+        if self
+            .active_debug_rows(&snapshot.display_snapshot)
+            .contains(&row)
+        {
+            return;
+        }
+        let point = DisplayPoint::new(row, 0).to_point(&snapshot.display_snapshot);
+        let answer = window.prompt(
+            PromptLevel::Info,
+            "Jump to this line?",
+            Some("Execution will resume from here, skipping the lines in between."),
+            &["Jump", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |editor, cx| {
+            if answer.await != Ok(0) {
+                return Ok(());
+            }
+            editor.update_in(cx, |editor, window, cx| {
+                window.focus(&editor.focus_handle(cx));
+                editor.change_selections(None, window, cx, |s| {
+                    s.select_ranges([Point::new(point.row, 0)..Point::new(point.row, 0)])
+                });
+                window.dispatch_action(Box::new(JumpToCursor), cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
     fn build_tasks_context(
         project: &Entity<Project>,
         buffer: &Entity<Buffer>,
@@ -8014,6 +8308,50 @@ impl Editor {
         .detach();
     }
 
+    /// Debugs the runnable enclosing the cursor, e.g. the test it's in, with the scenario the
+    /// language's debug adapter locator builds for it.
+    pub fn debug_test_under_cursor(
+        &mut self,
+        _: &DebugTestUnderCursor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((workspace, _)) = self.workspace.clone() else {
+            return;
+        };
+        let Some(project) = self.project.clone() else {
+            return;
+        };
+        let Some((buffer, buffer_row, tasks)) = self.find_enclosing_node_task(cx) else {
+            return;
+        };
+
+        let task_context = Self::build_tasks_context(&project, &buffer, buffer_row, &tasks, cx);
+        cx.spawn_in(window, async move |editor, cx| {
+            let context = task_context.await?;
+            let resolved_tasks = Some(ResolvedTasks {
+                templates: tasks.resolve(&context).collect(),
+                position: tasks.offset,
+            });
+            let scenario = editor
+                .update(cx, |editor, cx| {
+                    editor.debug_scenarios(&resolved_tasks, &buffer, cx)
+                })
+                .ok()?
+                .await
+                .into_iter()
+                .next()?;
+
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    dap::send_telemetry(&scenario, TelemetrySpawnLocation::TestUnderCursor, cx);
+                    workspace.start_debug_session(scenario, context, Some(buffer), window, cx);
+                })
+                .ok()
+        })
+        .detach();
+    }
+
     fn find_closest_task(
         &mut self,
         cx: &mut Context<Self>,
@@ -8086,12 +8424,18 @@ impl Editor {
             .icon_size(IconSize::XSmall)
             .icon_color(color)
             .toggle_state(is_active)
+            .tooltip(|window, cx| Tooltip::with_meta("Run", None, "Alt-click to debug", window, cx))
             .on_click(cx.listener(move |editor, e: &ClickEvent, window, cx| {
                 let quick_launch = e.down.button == MouseButton::Left;
+                let deployed_from = if e.modifiers().alt {
+                    CodeActionSource::DebugMenu(row)
+                } else {
+                    CodeActionSource::RunMenu(row)
+                };
                 window.focus(&editor.focus_handle(cx));
                 editor.toggle_code_actions(
                     &ToggleCodeActions {
-                        deployed_from: Some(CodeActionSource::RunMenu(row)),
+                        deployed_from: Some(deployed_from),
                         quick_launch,
                     },
                     window,
@@ -10301,6 +10645,7 @@ impl Editor {
                 state: BreakpointState::Enabled,
                 condition: None,
                 hit_condition: None,
+                note: None,
             });
 
             self.add_edit_breakpoint_block(
@@ -10313,6 +10658,36 @@ impl Editor {
         }
     }
 
+    pub fn toggle_logpoint(
+        &mut self,
+        _: &crate::actions::ToggleLogpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        for (anchor, breakpoint) in self.breakpoints_at_cursors(window, cx) {
+            match breakpoint {
+                Some(breakpoint) if breakpoint.message.is_some() => {
+                    self.edit_breakpoint_at_anchor(
+                        anchor,
+                        breakpoint,
+                        BreakpointEditAction::Toggle,
+                        cx,
+                    );
+                }
+                breakpoint => {
+                    let breakpoint = breakpoint.unwrap_or_else(Breakpoint::new_standard);
+                    self.add_edit_breakpoint_block(
+                        anchor,
+                        &breakpoint,
+                        BreakpointPromptEditAction::Log,
+                        window,
+                        cx,
+                    );
+                }
+            }
+        }
+    }
+
     fn breakpoints_at_cursors(
         &self,
         window: &mut Window,
@@ -14798,6 +15173,7 @@ impl Editor {
         &mut self,
         position: Anchor,
         highlight_color: Option<Hsla>,
+        options: RowHighlightOptions,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -14814,7 +15190,7 @@ impl Editor {
             start..end,
             highlight_color
                 .unwrap_or_else(|| cx.theme().colors().editor_highlighted_line_background),
-            Default::default(),
+            options,
             cx,
         );
 
@@ -17908,49 +18284,128 @@ impl Editor {
             let Some(active_stack_frame) = breakpoint_store.read(cx).active_position().cloned()
             else {
                 self.clear_row_highlights::<ActiveDebugLine>();
+                self.clear_row_highlights::<TopStackFrameLine>();
                 return None;
             };
+            let background = cx.theme().colors().editor_debugger_active_line_background;
 
-            let position = active_stack_frame.position;
-            let buffer_id = position.buffer_id?;
-            let snapshot = self
-                .project
-                .as_ref()?
-                .read(cx)
-                .buffer_for_id(buffer_id, cx)?
-                .read(cx)
-                .snapshot();
-
-            let mut handled = false;
-            for (id, ExcerptRange { context, .. }) in
-                self.buffer.read(cx).excerpts_for_buffer(buffer_id, cx)
-            {
-                if context.start.cmp(&position, &snapshot).is_ge()
-                    || context.end.cmp(&position, &snapshot).is_lt()
-                {
-                    continue;
-                }
+            // When a caller's frame is opened, the line the thread is executing keeps a dimmed
+            // marker, and the opened frame's line is drawn hollow.
+            self.clear_row_highlights::<TopStackFrameLine>();
+            if let Some(top_frame_position) = active_stack_frame.top_frame_position {
                 let snapshot = self.buffer.read(cx).snapshot(cx);
-                let multibuffer_anchor = snapshot.anchor_in_excerpt(id, position)?;
+                for anchor in self.debug_line_anchors(top_frame_position, cx) {
+                    let row = anchor.to_point(&snapshot).row;
+                    let start = snapshot.anchor_before(Point::new(row, 0));
+                    let end = snapshot.anchor_before(Point::new(row + 1, 0));
+                    self.highlight_rows::<TopStackFrameLine>(
+                        start..end,
+                        background.opacity(0.5),
+                        Default::default(),
+                        cx,
+                    );
+                }
+            }
+            let (highlight_color, options) = if active_stack_frame.top_frame_position.is_some() {
+                (
+                    gpui::transparent_black(),
+                    RowHighlightOptions {
+                        border: Some(background),
+                        ..Default::default()
+                    },
+                )
+            } else {
+                (background, RowHighlightOptions::default())
+            };
 
-                handled = true;
+            let anchors = self.debug_line_anchors(active_stack_frame.position, cx);
+            if !anchors.is_empty() {
                 self.clear_row_highlights::<ActiveDebugLine>();
-
+            }
+            for anchor in &anchors {
                 self.go_to_line::<ActiveDebugLine>(
-                    multibuffer_anchor,
-                    Some(cx.theme().colors().editor_debugger_active_line_background),
+                    *anchor,
+                    Some(highlight_color),
+                    options,
                     window,
                     cx,
                 );
-
-                cx.notify();
             }
+            cx.notify();
 
-            handled.then_some(())
+            (!anchors.is_empty()).then_some(())
         })
         .is_some()
     }
 
+    /// Shows `message` at the end of the line containing `position`, or clears it when `None`.
+    pub fn set_exception_annotation(
+        &mut self,
+        annotation: Option<(text::Anchor, SharedString)>,
+        cx: &mut Context<Self>,
+    ) {
+        if self.exception_annotation != annotation {
+            self.exception_annotation = annotation;
+            cx.notify();
+        }
+    }
+
+    /// Where the exception annotation is shown in this editor, and its message.
+    pub fn exception_annotation(&self, cx: &App) -> Option<(Vec<Anchor>, SharedString)> {
+        let (position, message) = self.exception_annotation.as_ref()?;
+        Some((self.debug_line_anchors(*position, cx), message.clone()))
+    }
+
+    /// Shows `value`, the result of evaluating an expression, at the end of the line containing
+    /// `position` until the selections change.
+    pub fn show_evaluation_result(
+        &mut self,
+        position: Anchor,
+        value: SharedString,
+        is_error: bool,
+        cx: &mut Context<Self>,
+    ) {
+        self.evaluation_annotation = Some(EvaluationAnnotation {
+            position,
+            value,
+            is_error,
+        });
+        cx.notify();
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn evaluation_result(&self) -> Option<SharedString> {
+        self.evaluation_annotation
+            .as_ref()
+            .map(|annotation| annotation.value.clone())
+    }
+
+    /// Anchors to `position` in each of the editor's excerpts that contains it.
+    fn debug_line_anchors(&self, position: text::Anchor, cx: &App) -> Vec<Anchor> {
+        let Some(buffer_id) = position.buffer_id else {
+            return Vec::new();
+        };
+        let Some(buffer) = self
+            .project
+            .as_ref()
+            .and_then(|project| project.read(cx).buffer_for_id(buffer_id, cx))
+        else {
+            return Vec::new();
+        };
+        let buffer_snapshot = buffer.read(cx).snapshot();
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        self.buffer
+            .read(cx)
+            .excerpts_for_buffer(buffer_id, cx)
+            .into_iter()
+            .filter(|(_, ExcerptRange { context, .. })| {
+                context.start.cmp(&position, &buffer_snapshot).is_lt()
+                    && context.end.cmp(&position, &buffer_snapshot).is_ge()
+            })
+            .filter_map(|(id, _)| snapshot.anchor_in_excerpt(id, position))
+            .collect()
+    }
+
     pub fn copy_file_name_without_extension(
         &mut self,
         _: &CopyFileNameWithoutExtension,
@@ -18534,7 +18989,7 @@ impl Editor {
                                 DisplayRow(row),
                                 LineHighlight {
                                     include_gutter: highlight.options.include_gutter,
-                                    border: None,
+                                    border: highlight.options.border,
                                     background: highlight.color.into(),
                                     type_id: Some(highlight.type_id),
                                 },
@@ -19032,6 +19487,25 @@ impl Editor {
         self.display_map.read(cx).text_highlights(TypeId::of::<T>())
     }
 
+    /// Removes the text and background highlights added with [`Self::highlight_text_key`] and
+    /// [`Self::highlight_background_key`] whose keys fall within `keys`.
+    pub fn clear_highlights_with_keys<T: 'static>(
+        &mut self,
+        keys: Range<usize>,
+        cx: &mut Context<Self>,
+    ) {
+        let type_id = TypeId::of::<T>();
+        self.display_map.update(cx, |map, _| {
+            map.clear_keyed_highlights(type_id, keys.clone())
+        });
+        self.background_highlights.remove_range(
+            &HighlightKey::TypePlus(type_id, keys.start),
+            &HighlightKey::TypePlus(type_id, keys.end),
+        );
+        self.scrollbar_marker_state.dirty = true;
+        cx.notify();
+    }
+
     pub fn clear_highlights<T: 'static>(&mut self, cx: &mut Context<Self>) {
         let cleared = self
             .display_map
@@ -19074,6 +19548,17 @@ impl Editor {
             return;
         };
 
+        let placement = DebuggerSettings::get_global(cx).inline_values;
+        if self.inline_value_cache.placement != Some(placement) {
+            // Turning the setting on or off overrides `ToggleInlineValues` once it changes.
+            let changed = self
+                .inline_value_cache
+                .placement
+                .replace(placement)
+                .is_some();
+            self.inline_value_cache.enabled =
+                placement != InlineValues::Off && (changed || self.inline_value_cache.enabled);
+        }
         if !self.inline_value_cache.enabled {
             let inlays = std::mem::take(&mut self.inline_value_cache.inlays);
             self.splice_inlays(&inlays, Vec::new(), cx);
@@ -19387,6 +19872,14 @@ impl Editor {
             cx,
         );
 
+        if self.project.is_some()
+            && self.inline_value_cache.placement.is_some_and(|placement| {
+                placement != DebuggerSettings::get_global(cx).inline_values
+            })
+        {
+            self.refresh_inline_values(cx);
+        }
+
         let old_cursor_shape = self.cursor_shape;
 
         {
@@ -22634,6 +23127,7 @@ enum BreakpointPromptEditAction {
     Log,
     Condition,
     HitCondition,
+    Note,
 }
 
 struct BreakpointPromptEditor {
@@ -22662,6 +23156,7 @@ impl BreakpointPromptEditor {
             BreakpointPromptEditAction::Log => breakpoint.message.as_ref(),
             BreakpointPromptEditAction::Condition => breakpoint.condition.as_ref(),
             BreakpointPromptEditAction::HitCondition => breakpoint.hit_condition.as_ref(),
+            BreakpointPromptEditAction::Note => breakpoint.note.as_ref(),
         }
         .map(|msg| msg.to_string())
         .unwrap_or_default();
@@ -22687,6 +23182,7 @@ impl BreakpointPromptEditor {
                     BreakpointPromptEditAction::Log => "Message to log when a breakpoint is hit. Expressions within {} are interpolated.",
                     BreakpointPromptEditAction::Condition => "Condition when a breakpoint is hit. Expressions within {} are interpolated.",
                     BreakpointPromptEditAction::HitCondition => "How many breakpoint hits to ignore",
+                    BreakpointPromptEditAction::Note => "Note to keep with this breakpoint, e.g. why it was set",
                 },
                 cx,
             );
@@ -22737,6 +23233,9 @@ impl BreakpointPromptEditor {
                         BreakpointPromptEditAction::HitCondition => {
                             BreakpointEditAction::EditHitCondition(message.into())
                         }
+                        BreakpointPromptEditAction::Note => {
+                            BreakpointEditAction::EditNote(message.into())
+                        }
                     },
                     cx,
                 );
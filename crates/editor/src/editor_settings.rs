@@ -142,6 +142,7 @@ pub struct Scrollbar {
     pub selected_symbol: bool,
     pub search_results: bool,
     pub diagnostics: ScrollbarDiagnostics,
+    pub breakpoints: bool,
     pub cursors: bool,
     pub axes: ScrollbarAxes,
 }
@@ -601,6 +602,10 @@ pub struct ScrollbarContent {
     ///
     /// Default: all
     pub diagnostics: Option<ScrollbarDiagnostics>,
+    /// Whether to show breakpoints and the line a debugged thread is stopped on in the scrollbar.
+    ///
+    /// Default: true
+    pub breakpoints: Option<bool>,
     /// Whether to show cursor positions in the scrollbar.
     ///
     /// Default: true
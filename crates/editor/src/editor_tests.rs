@@ -13,6 +13,7 @@ use crate::{
     },
 };
 use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus, DiffHunkStatus, DiffHunkStatusKind};
+use dap::client::SessionId;
 use futures::StreamExt;
 use gpui::{
     BackgroundExecutor, DismissEvent, Rgba, SemanticVersion, TestAppContext, UpdateGlobal,
@@ -37,7 +38,10 @@ use parking_lot::Mutex;
 use pretty_assertions::{assert_eq, assert_ne};
 use project::{
     FakeFs,
-    debugger::breakpoint_store::{BreakpointState, SourceBreakpoint},
+    debugger::{
+        breakpoint_store::{ActiveStackFrame, BreakpointState, SourceBreakpoint},
+        session::ThreadId,
+    },
     project_settings::{LspSettings, ProjectSettings},
 };
 use serde_json::{self, json};
@@ -19814,6 +19818,7 @@ fn assert_breakpoint(
                         state: breakpoint.state,
                         condition: breakpoint.condition.clone(),
                         hit_condition: breakpoint.hit_condition.clone(),
+                        note: breakpoint.note.clone(),
                     },
                 )
             })
@@ -19861,6 +19866,21 @@ fn add_log_breakpoint_at_cursor(
     );
 }
 
+fn edit_breakpoint_note_at_cursor(
+    editor: &mut Editor,
+    note: &str,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let (anchor, bp) = editor
+        .breakpoints_at_cursors(window, cx)
+        .first()
+        .and_then(|(anchor, bp)| bp.clone().map(|bp| (*anchor, bp)))
+        .expect("a breakpoint at the cursor");
+
+    editor.edit_breakpoint_at_anchor(anchor, bp, BreakpointEditAction::EditNote(note.into()), cx);
+}
+
 #[gpui::test]
 async fn test_breakpoint_toggling(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -20150,6 +20170,298 @@ async fn test_log_breakpoint_editing(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_logpoint_toggling(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let sample_text = "First line\nSecond line\nThird line\nFourth line".to_string();
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": sample_text,
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let worktree_id = workspace.update(cx, |workspace, cx| {
+        workspace.project().update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        })
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let project_path = editor.update(cx, |editor, cx| editor.project_path(cx).unwrap());
+    let abs_path = project.read_with(cx, |project, cx| {
+        project
+            .absolute_path(&project_path, cx)
+            .map(|path_buf| Arc::from(path_buf.to_owned()))
+            .unwrap()
+    });
+    let breakpoints = |cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            editor
+                .breakpoint_store()
+                .as_ref()
+                .unwrap()
+                .read(cx)
+                .all_source_breakpoints(cx)
+                .clone()
+        })
+    };
+
+    // Without a logpoint on the line, the log message prompt is opened, and nothing is added
+    // until it's confirmed
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_logpoint(&actions::ToggleLogpoint, window, cx);
+    });
+    assert_breakpoint(&breakpoints(cx), &abs_path, vec![]);
+
+    editor.update_in(cx, |editor, window, cx| {
+        add_log_breakpoint_at_cursor(editor, "hello world", window, cx);
+    });
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(0, Breakpoint::new_log("hello world"))],
+    );
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_logpoint(&actions::ToggleLogpoint, window, cx);
+    });
+    assert_breakpoint(&breakpoints(cx), &abs_path, vec![]);
+
+    // Standard breakpoints aren't removed
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        editor.toggle_logpoint(&actions::ToggleLogpoint, window, cx);
+    });
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(0, Breakpoint::new_standard())],
+    );
+}
+
+#[gpui::test]
+async fn test_breakpoint_note_editing(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let sample_text = "First line\nSecond line\nThird line\nFourth line".to_string();
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": sample_text,
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let worktree_id = workspace.update(cx, |workspace, cx| {
+        workspace.project().update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        })
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let project_path = editor.update(cx, |editor, cx| editor.project_path(cx).unwrap());
+    let abs_path = project.read_with(cx, |project, cx| {
+        project
+            .absolute_path(&project_path, cx)
+            .map(|path_buf| Arc::from(path_buf.to_owned()))
+            .unwrap()
+    });
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+        edit_breakpoint_note_at_cursor(editor, "race shows up here", window, cx);
+    });
+
+    let breakpoints = editor.update(cx, |editor, cx| {
+        editor
+            .breakpoint_store()
+            .as_ref()
+            .unwrap()
+            .read(cx)
+            .all_source_breakpoints(cx)
+            .clone()
+    });
+
+    assert_breakpoint(
+        &breakpoints,
+        &abs_path,
+        vec![(
+            0,
+            Breakpoint {
+                note: Some("race shows up here".into()),
+                ..Breakpoint::new_standard()
+            },
+        )],
+    );
+
+    // Clearing the note should keep the breakpoint itself
+    editor.update_in(cx, |editor, window, cx| {
+        edit_breakpoint_note_at_cursor(editor, "", window, cx);
+    });
+
+    let breakpoints = editor.update(cx, |editor, cx| {
+        editor
+            .breakpoint_store()
+            .as_ref()
+            .unwrap()
+            .read(cx)
+            .all_source_breakpoints(cx)
+            .clone()
+    });
+
+    assert_breakpoint(
+        &breakpoints,
+        &abs_path,
+        vec![(0, Breakpoint::new_standard())],
+    );
+}
+
+#[gpui::test]
+async fn test_breakpoint_context_menu(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let sample_text = "First line\nSecond line\nThird line\nFourth line".to_string();
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": sample_text,
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let (workspace, cx) =
+        cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+    let worktree_id = workspace.update(cx, |workspace, cx| {
+        workspace.project().update(cx, |project, cx| {
+            project.worktrees(cx).next().unwrap().read(cx).id()
+        })
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let menu_labels = |row: u32, cx: &mut VisualTestContext| {
+        editor.update_in(cx, |editor, window, cx| {
+            let anchor = editor
+                .buffer()
+                .read(cx)
+                .snapshot(cx)
+                .anchor_before(Point::new(row, 0));
+            editor
+                .breakpoint_context_menu(anchor, window, cx)
+                .read(cx)
+                .entry_labels()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    editor.update_in(cx, |editor, window, cx| {
+        editor.toggle_breakpoint(&actions::ToggleBreakpoint, window, cx);
+    });
+
+    assert_eq!(
+        vec![
+            "Set Breakpoint",
+            "Set Log Breakpoint",
+            "Set Condition Breakpoint",
+            "Set Hit Condition Breakpoint",
+        ],
+        menu_labels(1, cx)
+    );
+    assert_eq!(
+        vec![
+            "Edit Condition",
+            "Edit Hit Count",
+            "Convert to Logpoint",
+            "Add Note",
+            "Disable",
+            "Remove",
+        ],
+        menu_labels(0, cx)
+    );
+
+    editor.update_in(cx, |editor, window, cx| {
+        add_log_breakpoint_at_cursor(editor, "hello", window, cx);
+        edit_breakpoint_note_at_cursor(editor, "race shows up here", window, cx);
+        editor.disable_breakpoint(&actions::DisableBreakpoint, window, cx);
+    });
+
+    assert_eq!(
+        vec![
+            "Edit Condition",
+            "Edit Hit Count",
+            "Edit Log Message",
+            "Edit Note",
+            "Enable",
+            "Remove",
+        ],
+        menu_labels(0, cx)
+    );
+}
+
 /// This also tests that Editor::breakpoint_at_cursor_head is working properly
 /// we had some issues where we wouldn't find a breakpoint at Point {row: 0, col: 0}
 /// or when breakpoints were placed out of order. This tests for a regression too
@@ -20313,6 +20625,376 @@ async fn test_breakpoint_enabling_and_disabling(cx: &mut TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_breakpoint_gutter_click_gestures(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let sample_text = "First line\nSecond line\nThird line\nFourth line".to_string();
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/a"),
+        json!({
+            "main.rs": sample_text,
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let workspace = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+    let cx = &mut VisualTestContext::from_window(*workspace.deref(), cx);
+    let worktree_id = workspace
+        .update(cx, |workspace, _window, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees(cx).next().unwrap().read(cx).id()
+            })
+        })
+        .unwrap();
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer, cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+
+    let project_path = editor.update(cx, |editor, cx| editor.project_path(cx).unwrap());
+    let abs_path = project.read_with(cx, |project, cx| {
+        project
+            .absolute_path(&project_path, cx)
+            .map(|path_buf| Arc::from(path_buf.to_owned()))
+            .unwrap()
+    });
+
+    // Clicks the breakpoint indicator on `row`, which is a phantom breakpoint when the row has
+    // none, and returns whether the editor kept focus rather than handing it to a prompt.
+    let click = |row: u32, modifiers: Modifiers, cx: &mut VisualTestContext| {
+        editor.update_in(cx, |editor, window, cx| {
+            let phantom_position = editor
+                .snapshot(window, cx)
+                .display_snapshot
+                .buffer_snapshot
+                .anchor_before(Point::new(row, 0));
+            let (position, breakpoint) = editor
+                .breakpoint_at_row(row, window, cx)
+                .unwrap_or((phantom_position, Breakpoint::new_standard()));
+            editor.click_breakpoint_indicator(position, &breakpoint, modifiers, window, cx);
+            editor.focus_handle(cx).is_focused(window)
+        })
+    };
+    let breakpoints = |cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            editor
+                .breakpoint_store()
+                .as_ref()
+                .unwrap()
+                .read(cx)
+                .all_source_breakpoints(cx)
+                .clone()
+        })
+    };
+    let disabled_breakpoint = {
+        let mut bp = Breakpoint::new_standard();
+        bp.state = BreakpointState::Disabled;
+        bp
+    };
+
+    // On empty lines, shift opens the log prompt without setting a breakpoint, while alt and
+    // the secondary modifier set a disabled one.
+    assert!(!click(0, Modifiers::shift(), cx));
+    assert!(click(1, Modifiers::alt(), cx));
+    assert!(click(2, Modifiers::secondary_key(), cx));
+    assert!(click(3, Modifiers::none(), cx));
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![
+            (1, disabled_breakpoint.clone()),
+            (2, disabled_breakpoint.clone()),
+            (3, Breakpoint::new_standard()),
+        ],
+    );
+
+    // On existing breakpoints, alt and the secondary modifier flip the state, and shift opens
+    // the log prompt without touching the breakpoint.
+    assert!(click(1, Modifiers::alt(), cx));
+    assert!(click(3, Modifiers::secondary_key(), cx));
+    assert!(!click(2, Modifiers::shift(), cx));
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![
+            (1, Breakpoint::new_standard()),
+            (2, disabled_breakpoint.clone()),
+            (3, disabled_breakpoint.clone()),
+        ],
+    );
+
+    // A plain click enables a disabled breakpoint and removes an enabled one.
+    assert!(click(2, Modifiers::none(), cx));
+    assert!(click(1, Modifiers::none(), cx));
+    assert_breakpoint(
+        &breakpoints(cx),
+        &abs_path,
+        vec![(2, Breakpoint::new_standard()), (3, disabled_breakpoint)],
+    );
+}
+
+/// Opens `text` in an editor whose project has a breakpoint store, and focuses the editor.
+async fn debugger_gutter_test_editor<'a>(
+    text: &str,
+    cx: &'a mut TestAppContext,
+) -> (
+    Entity<Editor>,
+    Entity<Buffer>,
+    Arc<Path>,
+    &'a mut VisualTestContext,
+) {
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(path!("/a"), json!({ "main.rs": text }))
+        .await;
+    let project = Project::test(fs, [path!("/a").as_ref()], cx).await;
+    let worktree_id = project.read_with(cx, |project, cx| {
+        project.worktrees(cx).next().unwrap().read(cx).id()
+    });
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, "main.rs"), cx)
+        })
+        .await
+        .unwrap();
+
+    let (editor, cx) = cx.add_window_view(|window, cx| {
+        Editor::new(
+            EditorMode::full(),
+            MultiBuffer::build_from_buffer(buffer.clone(), cx),
+            Some(project.clone()),
+            window,
+            cx,
+        )
+    });
+    editor.update_in(cx, |editor, window, cx| {
+        window.focus(&editor.focus_handle(cx));
+    });
+    cx.run_until_parked();
+
+    let abs_path = Arc::from(Path::new(path!("/a/main.rs")));
+    (editor, buffer, abs_path, cx)
+}
+
+/// Stops a (pretend) debug session on `row`, which the editor highlights as the active debug line.
+fn set_active_debug_line(
+    editor: &Entity<Editor>,
+    buffer: &Entity<Buffer>,
+    path: &Arc<Path>,
+    row: u32,
+    cx: &mut VisualTestContext,
+) {
+    let breakpoint_store = editor.read_with(cx, |editor, _| editor.breakpoint_store().unwrap());
+    breakpoint_store.update(cx, |store, cx| {
+        let position = buffer.read(cx).anchor_before(Point::new(row, 0));
+        store.set_active_position(
+            ActiveStackFrame {
+                session_id: SessionId(1),
+                thread_id: ThreadId(1),
+                stack_frame_id: 1,
+                path: path.clone(),
+                position,
+                top_frame_position: None,
+            },
+            cx,
+        );
+    });
+    cx.run_until_parked();
+}
+
+/// Records the row of the newest cursor whenever `A` is dispatched in `editor`.
+fn record_cursor_row_on<A: Action>(
+    editor: &Entity<Editor>,
+    cx: &mut VisualTestContext,
+) -> Rc<RefCell<Vec<u32>>> {
+    let rows = Rc::new(RefCell::new(Vec::new()));
+    editor.update(cx, |editor, cx| {
+        let handle = cx.weak_entity();
+        let rows = rows.clone();
+        editor
+            .register_action(move |_: &A, _, cx| {
+                let row = handle
+                    .read_with(cx, |editor, cx| {
+                        editor.selections.newest::<Point>(cx).head().row
+                    })
+                    .unwrap();
+                rows.borrow_mut().push(row);
+            })
+            .detach();
+    });
+    // Actions are only registered when the editor is drawn.
+    cx.update(|window, _| window.refresh());
+    cx.run_until_parked();
+    rows
+}
+
+/// The center of the line number of `row`, where the gutter's execution pointer is drawn.
+fn line_number_position(
+    editor: &Entity<Editor>,
+    row: u32,
+    cx: &mut VisualTestContext,
+) -> gpui::Point<Pixels> {
+    editor.read_with(cx, |editor, _| {
+        let position_map = editor.last_position_map.as_ref().unwrap();
+        let gutter = position_map.gutter_hitbox.bounds;
+        gpui::Point::new(
+            gutter.right() - editor.gutter_dimensions.right_padding - px(4.),
+            gutter.top() + position_map.line_height * (row as f32 + 0.5)
+                - position_map.scroll_pixel_position.y,
+        )
+    })
+}
+
+#[gpui::test]
+async fn test_dragging_execution_pointer(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let (editor, buffer, path, cx) =
+        debugger_gutter_test_editor("fn main() {\n    one();\n    two();\n    three();\n}", cx)
+            .await;
+    let jumps = record_cursor_row_on::<JumpToCursor>(&editor, cx);
+    set_active_debug_line(&editor, &buffer, &path, 1, cx);
+
+    // Dragging the pointer follows the mouse, and dropping it asks before jumping.
+    cx.simulate_mouse_down(
+        line_number_position(&editor, 1, cx),
+        MouseButton::Left,
+        Modifiers::none(),
+    );
+    let target = line_number_position(&editor, 3, cx);
+    cx.simulate_mouse_move(target, Some(MouseButton::Left), Modifiers::none());
+    editor.read_with(cx, |editor, _| {
+        assert_eq!(editor.execution_pointer_drag, Some(DisplayRow(3)));
+    });
+    cx.simulate_mouse_up(target, MouseButton::Left, Modifiers::none());
+    editor.read_with(cx, |editor, _| {
+        assert_eq!(editor.execution_pointer_drag, None);
+    });
+    assert!(cx.has_pending_prompt());
+    cx.simulate_prompt_answer("Jump");
+    cx.run_until_parked();
+    assert_eq!(*jumps.borrow(), vec![3]);
+
+    // Cancelling the prompt doesn't jump.
+    cx.simulate_mouse_down(
+        line_number_position(&editor, 1, cx),
+        MouseButton::Left,
+        Modifiers::none(),
+    );
+    let target = line_number_position(&editor, 2, cx);
+    cx.simulate_mouse_move(target, Some(MouseButton::Left), Modifiers::none());
+    cx.simulate_mouse_up(target, MouseButton::Left, Modifiers::none());
+    assert!(cx.has_pending_prompt());
+    cx.simulate_prompt_answer("Cancel");
+    cx.run_until_parked();
+    assert_eq!(*jumps.borrow(), vec![3]);
+
+    // Dropping the pointer back on the line it started from does nothing.
+    let start = line_number_position(&editor, 1, cx);
+    cx.simulate_mouse_down(start, MouseButton::Left, Modifiers::none());
+    cx.simulate_mouse_up(start, MouseButton::Left, Modifiers::none());
+    assert!(!cx.has_pending_prompt());
+    cx.run_until_parked();
+    assert_eq!(*jumps.borrow(), vec![3]);
+}
+
+#[gpui::test]
+async fn test_run_to_cursor_from_gutter(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let (editor, buffer, path, cx) =
+        debugger_gutter_test_editor("fn main() {\n    one();\n    two();\n    three();\n}", cx)
+            .await;
+    let runs = record_cursor_row_on::<RunToCursor>(&editor, cx);
+
+    let hover_gutter = |row: u32, cx: &mut VisualTestContext| {
+        cx.simulate_mouse_move(
+            line_number_position(&editor, row, cx),
+            None,
+            Modifiers::none(),
+        );
+        cx.executor().advance_clock(Duration::from_millis(500));
+        cx.run_until_parked();
+    };
+
+    // Nothing is offered while no session is stopped.
+    hover_gutter(3, cx);
+    assert!(cx.debug_bounds("ICON-DebugContinue").is_none());
+
+    // Once stopped, the hovered line's number turns into a button that runs to it.
+    set_active_debug_line(&editor, &buffer, &path, 1, cx);
+    hover_gutter(3, cx);
+    let indicator = cx.debug_bounds("ICON-DebugContinue").unwrap();
+    cx.simulate_click(indicator.center(), Modifiers::none());
+    cx.run_until_parked();
+    assert_eq!(*runs.borrow(), vec![3]);
+}
+
+#[gpui::test]
+async fn test_alt_clicking_run_indicator_offers_debugging(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let (editor, buffer, _, cx) =
+        debugger_gutter_test_editor("fn main() {\n    one();\n}", cx).await;
+    editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        editor.tasks.insert(
+            (buffer.read(cx).remote_id(), 0),
+            RunnableTasks {
+                templates: vec![],
+                offset: snapshot.anchor_before(0),
+                column: 0,
+                extra_variables: HashMap::default(),
+                context_range: BufferOffset(0)..BufferOffset(23),
+            },
+        );
+        cx.notify();
+    });
+    cx.run_until_parked();
+
+    let code_actions_menu = |cx: &mut VisualTestContext| {
+        editor.read_with(cx, |editor, _| {
+            match editor.context_menu.borrow().as_ref() {
+                Some(CodeContextMenu::CodeActions(menu)) => {
+                    Some((menu.deployed_from.clone(), menu.actions.tasks().is_some()))
+                }
+                _ => None,
+            }
+        })
+    };
+    let run_indicator = cx.debug_bounds("ICON-Play").unwrap();
+
+    // Alt-clicking only offers the runnable's debug scenarios.
+    cx.simulate_click(run_indicator.center(), Modifiers::alt());
+    cx.run_until_parked();
+    assert_eq!(
+        code_actions_menu(cx),
+        Some((Some(CodeActionSource::DebugMenu(DisplayRow(0))), false))
+    );
+
+    // A plain click goes back to the run menu.
+    cx.simulate_click(run_indicator.center(), Modifiers::none());
+    cx.run_until_parked();
+    assert_eq!(
+        code_actions_menu(cx).map(|(deployed_from, _)| deployed_from),
+        Some(Some(CodeActionSource::RunMenu(DisplayRow(0))))
+    );
+}
+
 #[gpui::test]
 async fn test_rename_with_duplicate_edits(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -1,15 +1,15 @@
 use crate::{
-    ActiveDiagnostic, BlockId, CURSORS_VISIBLE_FOR, ChunkRendererContext, ChunkReplacement,
-    CodeActionSource, ColumnarMode, ConflictsOurs, ConflictsOursMarker, ConflictsOuter,
-    ConflictsTheirs, ConflictsTheirsMarker, ContextMenuPlacement, CursorShape, CustomBlockId,
-    DisplayDiffHunk, DisplayPoint, DisplayRow, DocumentHighlightRead, DocumentHighlightWrite,
-    EditDisplayMode, Editor, EditorMode, EditorSettings, EditorSnapshot, EditorStyle,
-    FILE_HEADER_HEIGHT, FocusedBlock, GutterDimensions, HalfPageDown, HalfPageUp, HandleInput,
-    HoveredCursor, InlayHintRefreshReason, InlineCompletion, JumpData, LineDown, LineHighlight,
-    LineUp, MAX_LINE_LEN, MINIMAP_FONT_SIZE, MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts,
-    PageDown, PageUp, PhantomBreakpointIndicator, Point, RowExt, RowRangeExt, SelectPhase,
-    SelectedTextHighlight, Selection, SelectionDragState, SoftWrap, StickyHeaderExcerpt, ToPoint,
-    ToggleFold,
+    ActiveDebugLine, ActiveDiagnostic, BlockId, CURSORS_VISIBLE_FOR, ChunkRendererContext,
+    ChunkReplacement, CodeActionSource, ColumnarMode, ConflictsOurs, ConflictsOursMarker,
+    ConflictsOuter, ConflictsTheirs, ConflictsTheirsMarker, ContextMenuPlacement, CursorShape,
+    CustomBlockId, DisplayDiffHunk, DisplayPoint, DisplayRow, DocumentHighlightRead,
+    DocumentHighlightWrite, EditDisplayMode, Editor, EditorMode, EditorSettings, EditorSnapshot,
+    EditorStyle, FILE_HEADER_HEIGHT, FocusedBlock, GutterDimensions, HalfPageDown, HalfPageUp,
+    HandleInput, HoveredCursor, InlayHintRefreshReason, InlineCompletion, JumpData, JumpToCursor,
+    LineDown, LineHighlight, LineUp, MAX_LINE_LEN, MINIMAP_FONT_SIZE,
+    MULTI_BUFFER_EXCERPT_HEADER_HEIGHT, OpenExcerpts, PageDown, PageUp, PhantomBreakpointIndicator,
+    Point, RowExt, RowRangeExt, SelectPhase, SelectedTextHighlight, Selection, SelectionDragState,
+    SoftWrap, StickyHeaderExcerpt, ToPoint, ToggleFold,
     code_context_menus::{CodeActionsMenu, MENU_ASIDE_MAX_WIDTH, MENU_ASIDE_MIN_WIDTH, MENU_GAP},
     display_map::{
         Block, BlockContext, BlockStyle, DisplaySnapshot, EditorMargins, FoldId, HighlightKey,
@@ -40,14 +40,14 @@ use git::{
 };
 use gpui::{
     Action, Along, AnyElement, App, AppContext, AvailableSpace, Axis as ScrollbarAxis, BorderStyle,
-    Bounds, ClickEvent, ContentMask, Context, Corner, Corners, CursorStyle, DispatchPhase, Edges,
-    Element, ElementInputHandler, Entity, Focusable as _, FontId, GlobalElementId, Hitbox,
-    HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero, Keystroke, Length,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
-    ParentElement, Pixels, ScrollDelta, ScrollHandle, ScrollWheelEvent, ShapedLine, SharedString,
-    Size, StatefulInteractiveElement, Style, Styled, TextRun, TextStyleRefinement, WeakEntity,
-    Window, anchored, deferred, div, fill, linear_color_stop, linear_gradient, outline, point, px,
-    quad, relative, size, solid_background, transparent_black,
+    Bounds, ClickEvent, ClipboardItem, ContentMask, Context, Corner, Corners, CursorStyle,
+    DispatchPhase, Edges, Element, ElementInputHandler, Entity, Focusable as _, FontId,
+    GlobalElementId, Hitbox, HitboxBehavior, Hsla, InteractiveElement, IntoElement, IsZero,
+    Keystroke, Length, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta, ScrollHandle, ScrollWheelEvent,
+    ShapedLine, SharedString, Size, StatefulInteractiveElement, Style, Styled, TextRun,
+    TextStyleRefinement, WeakEntity, Window, anchored, deferred, div, fill, linear_color_stop,
+    linear_gradient, outline, point, px, quad, relative, size, solid_background, transparent_black,
 };
 use itertools::Itertools;
 use language::language_settings::{
@@ -563,11 +563,13 @@ impl EditorElement {
         register_action(editor, window, Editor::open_active_item_in_terminal);
         register_action(editor, window, Editor::reload_file);
         register_action(editor, window, Editor::spawn_nearest_task);
+        register_action(editor, window, Editor::debug_test_under_cursor);
         register_action(editor, window, Editor::insert_uuid_v4);
         register_action(editor, window, Editor::insert_uuid_v7);
         register_action(editor, window, Editor::open_selections_in_multibuffer);
         register_action(editor, window, Editor::toggle_breakpoint);
         register_action(editor, window, Editor::edit_log_breakpoint);
+        register_action(editor, window, Editor::toggle_logpoint);
         register_action(editor, window, Editor::enable_breakpoint);
         register_action(editor, window, Editor::disable_breakpoint);
     }
@@ -838,6 +840,13 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(row) = editor.execution_pointer_drag.take() {
+            editor.drop_execution_pointer(row, window, cx);
+            cx.stop_propagation();
+            cx.notify();
+            return;
+        }
+
         let text_hitbox = &position_map.text_hitbox;
         let end_selection = editor.has_pending_selection();
         let pending_nonempty_selections = editor.has_pending_nonempty_selection();
@@ -952,6 +961,18 @@ impl EditorElement {
         window: &mut Window,
         cx: &mut Context<Editor>,
     ) {
+        if let Some(row) = editor.execution_pointer_drag.as_mut() {
+            let target_row = position_map
+                .point_for_position(event.position)
+                .previous_valid
+                .row();
+            if *row != target_row {
+                *row = target_row;
+                cx.notify();
+            }
+            return;
+        }
+
         if !editor.has_pending_selection()
             && matches!(editor.selection_drag_state, SelectionDragState::None)
         {
@@ -2221,6 +2242,113 @@ impl EditorElement {
         elements
     }
 
+    /// Lays out what the debugger draws like a diagnostic at the end of lines: the message of the
+    /// exception a thread stopped on, on the line that threw it, and the result of evaluating the
+    /// selection, with a button to copy it.
+    fn layout_debugger_annotations(
+        &self,
+        snapshot: &EditorSnapshot,
+        line_layouts: &[LineWithInvisibles],
+        crease_trailers: &[Option<CreaseTrailerLayout>],
+        row_block_types: &HashMap<DisplayRow, bool>,
+        content_origin: gpui::Point<Pixels>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        start_row: DisplayRow,
+        end_row: DisplayRow,
+        line_height: Pixels,
+        em_width: Pixels,
+        style: &EditorStyle,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<(DisplayRow, AnyElement)> {
+        let editor = self.editor.read(cx);
+        if editor.mode().is_minimap() {
+            return Vec::new();
+        }
+
+        let mut annotations = Vec::new();
+        if let Some((anchors, message)) = editor.exception_annotation(cx) {
+            annotations.push((
+                "exception-annotation",
+                anchors,
+                message,
+                Color::Error,
+                false,
+            ));
+        }
+        if let Some(evaluation) = &editor.evaluation_annotation {
+            let color = if evaluation.is_error {
+                Color::Error
+            } else {
+                Color::Info
+            };
+            annotations.push((
+                "evaluation-annotation",
+                vec![evaluation.position],
+                evaluation.value.clone(),
+                color,
+                !evaluation.is_error,
+            ));
+        }
+
+        let padding = ProjectSettings::get_global(cx).diagnostics.inline.padding as f32 * em_width;
+        annotations
+            .into_iter()
+            .filter_map(|(id, anchors, message, color, copyable)| {
+                let row = anchors
+                    .iter()
+                    .map(|anchor| anchor.to_display_point(snapshot).row())
+                    .find(|row| {
+                        (start_row..end_row).contains(row) && !row_block_types.contains_key(row)
+                    })?;
+
+                let pos_y = content_origin.y
+                    + line_height * (row.0 as f32 - scroll_pixel_position.y / line_height);
+                let window_ix = row.0.saturating_sub(start_row.0) as usize;
+                let line_end = if let Some(crease_trailer) = &crease_trailers[window_ix] {
+                    crease_trailer.bounds.right()
+                } else {
+                    content_origin.x - scroll_pixel_position.x + line_layouts[window_ix].width
+                };
+
+                let mut element = h_flex()
+                    .id((id, row.0))
+                    .h(line_height)
+                    .w_full()
+                    .gap_1()
+                    .px_1()
+                    .rounded_xs()
+                    .bg(color.color(cx).opacity(0.05))
+                    .text_color(color.color(cx))
+                    .text_sm()
+                    .font_family(style.text.font().family)
+                    .child(message.clone())
+                    .when(copyable, |this| {
+                        this.child(
+                            IconButton::new("copy-evaluation-result", IconName::Copy)
+                                .icon_size(IconSize::XSmall)
+                                .icon_color(Color::Muted)
+                                .tooltip(Tooltip::text("Copy Result"))
+                                .on_click(move |_, _, cx| {
+                                    cx.write_to_clipboard(ClipboardItem::new_string(
+                                        message.to_string(),
+                                    ))
+                                }),
+                        )
+                    })
+                    .into_any();
+                element.prepaint_as_root(
+                    point(line_end + padding, pos_y),
+                    AvailableSpace::min_size(),
+                    window,
+                    cx,
+                );
+
+                Some((row, element))
+            })
+            .collect()
+    }
+
     fn layout_inline_code_actions(
         &self,
         display_point: DisplayPoint,
@@ -2774,6 +2902,137 @@ impl EditorElement {
         })
     }
 
+    /// Lays out what the editor's addons draw over its text, in the corners they ask for.
+    fn layout_addon_overlays(
+        &self,
+        text_hitbox: &Hitbox,
+        right_margin: Pixels,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Vec<AnyElement> {
+        let overlays = self
+            .editor
+            .read(cx)
+            .addons
+            .values()
+            .filter_map(|addon| addon.render_overlay(window, cx))
+            .collect::<Vec<_>>();
+        if overlays.is_empty() {
+            return Vec::new();
+        }
+
+        let mut bounds = text_hitbox.bounds;
+        bounds.size.width -= right_margin;
+        let bounds = bounds.inset(px(8.));
+        overlays
+            .into_iter()
+            .map(|(corner, mut overlay)| {
+                let size = overlay.layout_as_root(AvailableSpace::min_size(), window, cx);
+                let origin =
+                    Bounds::from_corner_and_size(corner, bounds.corner(corner), size).origin;
+                overlay.prepaint_as_root(origin, AvailableSpace::min_size(), window, cx);
+                overlay
+            })
+            .collect()
+    }
+
+    fn layout_run_to_cursor_indicator(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        snapshot: &EditorSnapshot,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<(MultiBufferRow, AnyElement)> {
+        self.editor.update(cx, |editor, cx| {
+            let display_row = editor
+                .gutter_breakpoint_indicator
+                .0
+                .filter(|indicator| indicator.is_active)?
+                .display_row;
+            if !range.contains(&display_row) || !editor.is_stopped_in_debugger(cx) {
+                return None;
+            }
+
+            let mut button = editor
+                .render_run_to_cursor_indicator(display_row, cx)
+                .into_any_element();
+            let available_space = size(
+                AvailableSpace::MinContent,
+                AvailableSpace::Definite(line_height),
+            );
+            let indicator_size = button.layout_as_root(available_space, window, cx);
+
+            // It's drawn in place of the line's number.
+            let x =
+                gutter_hitbox.size.width - indicator_size.width - gutter_dimensions.right_padding;
+            let y = display_row.as_f32() * line_height - scroll_pixel_position.y
+                + (line_height - indicator_size.height) / 2.;
+            button.prepaint_as_root(
+                gutter_hitbox.origin + point(x, y),
+                available_space,
+                window,
+                cx,
+            );
+
+            let row = MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+            Some((row, button))
+        })
+    }
+
+    /// Lays out the arrow drawn in place of the line number of the line a debugged thread is
+    /// stopped on, or of the line it's being dragged to.
+    fn layout_execution_pointer(
+        &self,
+        line_height: Pixels,
+        range: Range<DisplayRow>,
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        snapshot: &EditorSnapshot,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<(MultiBufferRow, AnyElement)> {
+        let draggable = window.is_action_available(&JumpToCursor, cx);
+        self.editor.update(cx, |editor, cx| {
+            let display_row = editor.execution_pointer_drag.or_else(|| {
+                editor
+                    .active_debug_rows(&snapshot.display_snapshot)
+                    .into_iter()
+                    .find(|row| range.contains(row))
+            })?;
+            if !range.contains(&display_row) {
+                return None;
+            }
+
+            let mut pointer = editor
+                .render_execution_pointer(display_row, draggable, cx)
+                .into_any_element();
+            let available_space = size(
+                AvailableSpace::MinContent,
+                AvailableSpace::Definite(line_height),
+            );
+            let pointer_size = pointer.layout_as_root(available_space, window, cx);
+
+            // Like the run to cursor indicator, it's drawn in place of the line's number.
+            let x = gutter_hitbox.size.width - pointer_size.width - gutter_dimensions.right_padding;
+            let y = display_row.as_f32() * line_height - scroll_pixel_position.y
+                + (line_height - pointer_size.height) / 2.;
+            pointer.prepaint_as_root(
+                gutter_hitbox.origin + point(x, y),
+                available_space,
+                window,
+                cx,
+            );
+
+            let row = MultiBufferRow(DisplayPoint::new(display_row, 0).to_point(snapshot).row);
+            Some((row, pointer))
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn layout_run_indicators(
         &self,
@@ -2801,7 +3060,8 @@ impl EditorElement {
                         .tasks()
                         .map(|tasks| tasks.position.to_display_point(snapshot).row())
                         .or_else(|| match deployed_from {
-                            Some(CodeActionSource::Indicator(row)) => Some(*row),
+                            Some(CodeActionSource::Indicator(row))
+                            | Some(CodeActionSource::DebugMenu(row)) => Some(*row),
                             _ => None,
                         })
                 } else {
@@ -5367,14 +5627,25 @@ impl EditorElement {
         let line_height = layout.position_map.line_height;
         window.set_cursor_style(CursorStyle::Arrow, &layout.gutter_hitbox);
 
-        for LineNumberLayout {
-            shaped_line,
-            hitbox,
-        } in layout.line_numbers.values()
+        for (
+            row,
+            LineNumberLayout {
+                shaped_line,
+                hitbox,
+            },
+        ) in layout.line_numbers.iter()
         {
             let Some(hitbox) = hitbox else {
                 continue;
             };
+            if layout
+                .run_to_cursor_indicator
+                .iter()
+                .chain(&layout.execution_pointer)
+                .any(|(indicator_row, _)| indicator_row == row)
+            {
+                continue;
+            }
 
             let Some(()) = (if !is_singleton && hitbox.is_hovered(window) {
                 let color = cx.theme().colors().editor_hover_line_number;
@@ -5591,6 +5862,14 @@ impl EditorElement {
                 breakpoint.paint(window, cx);
             }
 
+            if let Some((_, run_to_cursor_indicator)) = layout.run_to_cursor_indicator.as_mut() {
+                run_to_cursor_indicator.paint(window, cx);
+            }
+
+            if let Some((_, execution_pointer)) = layout.execution_pointer.as_mut() {
+                execution_pointer.paint(window, cx);
+            }
+
             for test_indicator in layout.test_indicators.iter_mut() {
                 test_indicator.paint(window, cx);
             }
@@ -6149,6 +6428,45 @@ impl EditorElement {
             let snapshot = layout.position_map.snapshot.clone();
             let theme = cx.theme().clone();
             let scrollbar_settings = EditorSettings::get_global(cx).scrollbar;
+            let debugger_marker_rows = if scrollbar_settings.breakpoints {
+                let mut breakpoints = editor
+                    .active_breakpoints(
+                        DisplayRow(0)..snapshot.max_point().row().next_row(),
+                        window,
+                        cx,
+                    )
+                    .into_iter()
+                    .map(|(row, (_, breakpoint, _))| {
+                        let mut color = theme.colors().debugger_accent;
+                        if breakpoint.is_disabled() {
+                            color.fade_out(0.5);
+                        }
+                        ColoredRange {
+                            start: row,
+                            end: row,
+                            color,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                // Paint breakpoints in order, with the line the thread is stopped on above them.
+                breakpoints.sort_by_key(|range| range.start);
+                breakpoints.extend(editor.highlighted_rows::<ActiveDebugLine>().map(
+                    |(range, _)| {
+                        let row = range
+                            .start
+                            .to_display_point(&snapshot.display_snapshot)
+                            .row();
+                        ColoredRange {
+                            start: row,
+                            end: row,
+                            color: theme.status().warning,
+                        }
+                    },
+                ));
+                breakpoints
+            } else {
+                Vec::new()
+            };
 
             editor.scrollbar_marker_state.dirty = false;
             editor.scrollbar_marker_state.pending_refresh =
@@ -6297,6 +6615,11 @@ impl EditorElement {
                                 );
                             }
 
+                            marker_quads.extend(
+                                scrollbar_layout
+                                    .marker_quads_for_ranges(debugger_marker_rows, None),
+                            );
+
                             Arc::from(marker_quads)
                         })
                         .await;
@@ -8198,7 +8521,7 @@ impl Element for EditorElement {
                         editor.active_breakpoints(start_row..end_row, window, cx)
                     });
                     for (display_row, (_, bp, state)) in &breakpoint_rows {
-                        if bp.is_enabled() && state.is_none_or(|s| s.verified) {
+                        if bp.is_enabled() && state.as_ref().is_none_or(|s| s.verified) {
                             active_rows.entry(*display_row).or_default().breakpoint = true;
                         }
                     }
@@ -8570,6 +8893,29 @@ impl Element for EditorElement {
                         }
                     }
 
+                    // What the debugger shows at the end of a line overrides both blame and
+                    // diagnostics on it.
+                    for (row, element) in self.layout_debugger_annotations(
+                        &snapshot,
+                        &line_layouts,
+                        &crease_trailers,
+                        &row_block_types,
+                        content_origin,
+                        scroll_pixel_position,
+                        start_row,
+                        end_row,
+                        line_height,
+                        em_width,
+                        &style,
+                        window,
+                        cx,
+                    ) {
+                        if newest_selection_head.is_some_and(|head| head.row() == row) {
+                            inline_blame_layout = None;
+                        }
+                        inline_diagnostics.insert(row, element);
+                    }
+
                     let blamed_display_rows = self.layout_blame_entries(
                         &row_infos,
                         em_width,
@@ -8738,6 +9084,31 @@ impl Element for EditorElement {
                         Vec::new()
                     };
 
+                    let mut run_to_cursor_indicator = self.layout_run_to_cursor_indicator(
+                        line_height,
+                        start_row..end_row,
+                        scroll_pixel_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &snapshot,
+                        window,
+                        cx,
+                    );
+                    let execution_pointer = self.layout_execution_pointer(
+                        line_height,
+                        start_row..end_row,
+                        scroll_pixel_position,
+                        &gutter_dimensions,
+                        &gutter_hitbox,
+                        &snapshot,
+                        window,
+                        cx,
+                    );
+                    if let Some((pointer_row, _)) = &execution_pointer {
+                        run_to_cursor_indicator
+                            .take_if(|(indicator_row, _)| indicator_row == pointer_row);
+                    }
+
                     self.layout_signature_help(
                         &hitbox,
                         content_origin,
@@ -8776,6 +9147,9 @@ impl Element for EditorElement {
                         cx,
                     );
 
+                    let addon_overlays =
+                        self.layout_addon_overlays(&text_hitbox, right_margin, window, cx);
+
                     window.with_element_namespace("crease_toggles", |window| {
                         self.prepaint_crease_toggles(
                             &mut crease_toggles,
@@ -8906,8 +9280,11 @@ impl Element for EditorElement {
                         inline_completion_popover,
                         diff_hunk_controls,
                         mouse_context_menu,
+                        addon_overlays,
                         test_indicators,
                         breakpoints,
+                        run_to_cursor_indicator,
+                        execution_pointer,
                         crease_toggles,
                         crease_trailers,
                         tab_invisible,
@@ -8983,6 +9360,9 @@ impl Element for EditorElement {
 
                     self.paint_minimap(layout, window, cx);
                     self.paint_scrollbars(layout, window, cx);
+                    for overlay in layout.addon_overlays.iter_mut() {
+                        overlay.paint(window, cx);
+                    }
                     self.paint_inline_completion_popover(layout, window, cx);
                     self.paint_mouse_context_menu(layout, window, cx);
                 });
@@ -9089,12 +9469,16 @@ pub struct EditorLayout {
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     test_indicators: Vec<AnyElement>,
     breakpoints: Vec<AnyElement>,
+    /// The "run to here" button of the hovered line, and the row whose number it replaces.
+    run_to_cursor_indicator: Option<(MultiBufferRow, AnyElement)>,
+    execution_pointer: Option<(MultiBufferRow, AnyElement)>,
     crease_toggles: Vec<Option<AnyElement>>,
     expand_toggles: Vec<Option<(AnyElement, gpui::Point<Pixels>)>>,
     diff_hunk_controls: Vec<AnyElement>,
     crease_trailers: Vec<Option<CreaseTrailerLayout>>,
     inline_completion_popover: Option<AnyElement>,
     mouse_context_menu: Option<AnyElement>,
+    addon_overlays: Vec<AnyElement>,
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
@@ -1,9 +1,9 @@
 use crate::{
-    Copy, CopyAndTrim, CopyPermalinkToLine, Cut, DisplayPoint, DisplaySnapshot, Editor,
-    EvaluateSelectedText, FindAllReferences, GoToDeclaration, GoToDefinition, GoToImplementation,
-    GoToTypeDefinition, Paste, Rename, RevealInFileManager, SelectMode, SelectionExt,
-    ToDisplayPoint, ToggleCodeActions,
-    actions::{Format, FormatSelections},
+    AddToWatch, Copy, CopyAndTrim, CopyPermalinkToLine, Cut, DisplayPoint, DisplaySnapshot, Editor,
+    EvaluateSelectedText, EvaluateSelectedTextInline, FindAllReferences, GoToDeclaration,
+    GoToDefinition, GoToImplementation, GoToTypeDefinition, Paste, Rename, RevealInFileManager,
+    SelectMode, SelectionExt, ToDisplayPoint, ToggleCodeActions,
+    actions::{Format, FormatSelections, JumpToCursor, RunToCursor},
     selections_collection::SelectionsCollection,
 };
 use gpui::prelude::FluentBuilder;
@@ -200,15 +200,37 @@ pub fn deploy_context_menu(
         });
 
         let evaluate_selection = window.is_action_available(&EvaluateSelectedText, cx);
+        let evaluate_inline = window.is_action_available(&EvaluateSelectedTextInline, cx);
+        let add_to_watch = window.is_action_available(&AddToWatch, cx);
+        let is_stopped = editor.is_stopped_in_debugger(cx);
+        let run_to_cursor = is_stopped && window.is_action_available(&RunToCursor, cx);
+        let jump_to_cursor = is_stopped && window.is_action_available(&JumpToCursor, cx);
 
         ui::ContextMenu::build(window, cx, |menu, _window, _cx| {
             let builder = menu
                 .on_blur_subscription(Subscription::new(|| {}))
                 .when(evaluate_selection && has_selections, |builder| {
-                    builder
-                        .action("Evaluate Selection", Box::new(EvaluateSelectedText))
-                        .separator()
+                    builder.action("Evaluate Selection", Box::new(EvaluateSelectedText))
                 })
+                .when(evaluate_inline && has_selections, |builder| {
+                    builder.action("Evaluate Inline", Box::new(EvaluateSelectedTextInline))
+                })
+                .when(add_to_watch, |builder| {
+                    builder.action("Add to Watch", Box::new(AddToWatch))
+                })
+                .when(run_to_cursor, |builder| {
+                    builder.action("Continue to Here", Box::new(RunToCursor))
+                })
+                .when(jump_to_cursor, |builder| {
+                    builder.action("Jump to Here", Box::new(JumpToCursor))
+                })
+                .when(
+                    (evaluate_selection && has_selections)
+                        || add_to_watch
+                        || run_to_cursor
+                        || jump_to_cursor,
+                    |builder| builder.separator(),
+                )
                 .action("Go to Definition", Box::new(GoToDefinition))
                 .action("Go to Declaration", Box::new(GoToDeclaration))
                 .action("Go to Type Definition", Box::new(GoToTypeDefinition))
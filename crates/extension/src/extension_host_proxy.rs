@@ -8,7 +8,7 @@ use language::{BinaryStatus, LanguageMatcher, LanguageName, LoadedLanguage};
 use lsp::LanguageServerName;
 use parking_lot::RwLock;
 
-use crate::{Extension, SlashCommand};
+use crate::{DebugAdapterManifestEntry, DebugVisualizerManifestEntry, Extension, SlashCommand};
 
 #[derive(Default)]
 struct GlobalExtensionHostProxy(Arc<ExtensionHostProxy>);
@@ -416,10 +416,17 @@ pub trait ExtensionDebugAdapterProviderProxy: Send + Sync + 'static {
         extension: Arc<dyn Extension>,
         debug_adapter_name: Arc<str>,
         schema_path: &Path,
+        manifest_entry: &DebugAdapterManifestEntry,
     );
     fn register_debug_locator(&self, extension: Arc<dyn Extension>, locator_name: Arc<str>);
+    fn register_debug_visualizer(
+        &self,
+        visualizer_name: Arc<str>,
+        visualizer: &DebugVisualizerManifestEntry,
+    );
     fn unregister_debug_adapter(&self, debug_adapter_name: Arc<str>);
     fn unregister_debug_locator(&self, locator_name: Arc<str>);
+    fn unregister_debug_visualizer(&self, visualizer_name: Arc<str>);
 }
 
 impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
@@ -428,12 +435,13 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
         extension: Arc<dyn Extension>,
         debug_adapter_name: Arc<str>,
         schema_path: &Path,
+        manifest_entry: &DebugAdapterManifestEntry,
     ) {
         let Some(proxy) = self.debug_adapter_provider_proxy.read().clone() else {
             return;
         };
 
-        proxy.register_debug_adapter(extension, debug_adapter_name, schema_path)
+        proxy.register_debug_adapter(extension, debug_adapter_name, schema_path, manifest_entry)
     }
 
     fn register_debug_locator(&self, extension: Arc<dyn Extension>, locator_name: Arc<str>) {
@@ -443,6 +451,18 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
 
         proxy.register_debug_locator(extension, locator_name)
     }
+
+    fn register_debug_visualizer(
+        &self,
+        visualizer_name: Arc<str>,
+        visualizer: &DebugVisualizerManifestEntry,
+    ) {
+        let Some(proxy) = self.debug_adapter_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.register_debug_visualizer(visualizer_name, visualizer)
+    }
     fn unregister_debug_adapter(&self, debug_adapter_name: Arc<str>) {
         let Some(proxy) = self.debug_adapter_provider_proxy.read().clone() else {
             return;
@@ -457,4 +477,11 @@ impl ExtensionDebugAdapterProviderProxy for ExtensionHostProxy {
 
         proxy.unregister_debug_locator(locator_name)
     }
+    fn unregister_debug_visualizer(&self, visualizer_name: Arc<str>) {
+        let Some(proxy) = self.debug_adapter_provider_proxy.read().clone() else {
+            return;
+        };
+
+        proxy.unregister_debug_visualizer(visualizer_name)
+    }
 }
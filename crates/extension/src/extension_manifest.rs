@@ -91,6 +91,8 @@ pub struct ExtensionManifest {
     pub debug_adapters: BTreeMap<Arc<str>, DebugAdapterManifestEntry>,
     #[serde(default)]
     pub debug_locators: BTreeMap<Arc<str>, DebugLocatorManifestEntry>,
+    #[serde(default)]
+    pub debug_visualizers: BTreeMap<Arc<str>, DebugVisualizerManifestEntry>,
 }
 
 impl ExtensionManifest {
@@ -213,11 +215,30 @@ pub struct IndexedDocsProviderEntry {}
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct DebugAdapterManifestEntry {
     pub schema_path: Option<PathBuf>,
+    /// The custom request the adapter applies code changes to the running debuggee with.
+    #[serde(default)]
+    pub hot_reload_request: Option<String>,
+    /// The custom request the adapter restarts the debuggee in place with.
+    #[serde(default)]
+    pub hot_restart_request: Option<String>,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct DebugLocatorManifestEntry {}
 
+/// Replaces the displayed value of debugger variables whose type matches one of `types`.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct DebugVisualizerManifestEntry {
+    /// Debug adapters this visualizer applies to; all of them when empty.
+    #[serde(default)]
+    pub adapters: Vec<Arc<str>>,
+    /// Type names to match exactly, or by prefix when ending with `*`.
+    pub types: Vec<String>,
+    /// Text to display, e.g. `{secs}s {nanos}ns`. Placeholders refer to `name`, `value` and `type`
+    /// of the variable, or to the value of one of its members.
+    pub template: String,
+}
+
 impl ExtensionManifest {
     pub async fn load(fs: Arc<dyn Fs>, extension_dir: &Path) -> Result<Self> {
         let extension_name = extension_dir
@@ -288,6 +309,7 @@ fn manifest_from_old_manifest(
         capabilities: Vec::new(),
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        debug_visualizers: Default::default(),
     }
 }
 
@@ -317,6 +339,7 @@ mod tests {
             capabilities: vec![],
             debug_adapters: Default::default(),
             debug_locators: Default::default(),
+            debug_visualizers: Default::default(),
         }
     }
 
@@ -140,6 +140,7 @@ fn manifest() -> ExtensionManifest {
         }],
         debug_adapters: Default::default(),
         debug_locators: Default::default(),
+        debug_visualizers: Default::default(),
     }
 }
 
@@ -1158,6 +1158,9 @@ impl ExtensionStore {
             for (locator, _) in extension.manifest.debug_locators.iter() {
                 self.proxy.unregister_debug_locator(locator.clone());
             }
+            for (visualizer, _) in extension.manifest.debug_visualizers.iter() {
+                self.proxy.unregister_debug_visualizer(visualizer.clone());
+            }
         }
 
         self.wasm_extensions
@@ -1367,6 +1370,7 @@ impl ExtensionStore {
                             extension.clone(),
                             debug_adapter.clone(),
                             &path,
+                            meta,
                         );
                     }
 
@@ -1374,6 +1378,11 @@ impl ExtensionStore {
                         this.proxy
                             .register_debug_locator(extension.clone(), debug_adapter.clone());
                     }
+
+                    for (visualizer_name, visualizer) in &manifest.debug_visualizers {
+                        this.proxy
+                            .register_debug_visualizer(visualizer_name.clone(), visualizer);
+                    }
                 }
 
                 this.wasm_extensions.extend(wasm_extensions);
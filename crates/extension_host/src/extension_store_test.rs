@@ -164,6 +164,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        debug_visualizers: Default::default(),
                     }),
                     dev: false,
                 },
@@ -195,6 +196,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                         capabilities: Vec::new(),
                         debug_adapters: Default::default(),
                         debug_locators: Default::default(),
+                        debug_visualizers: Default::default(),
                     }),
                     dev: false,
                 },
@@ -371,6 +373,7 @@ async fn test_extension_store(cx: &mut TestAppContext) {
                 capabilities: Vec::new(),
                 debug_adapters: Default::default(),
                 debug_locators: Default::default(),
+                debug_visualizers: Default::default(),
             }),
             dev: false,
         },
@@ -14,8 +14,13 @@
 pub mod breakpoint_store;
 pub mod dap_command;
 pub mod dap_store;
+pub mod docker;
+pub mod launch_targets;
 pub mod locators;
+pub mod process_tree;
+pub mod recording;
 pub mod session;
+pub mod wsl;
 
 #[cfg(any(feature = "test-support", test))]
 pub mod test;
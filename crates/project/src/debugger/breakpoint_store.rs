@@ -5,14 +5,17 @@ use anyhow::{Context as _, Result};
 pub use breakpoints_in_file::{BreakpointSessionState, BreakpointWithPosition};
 use breakpoints_in_file::{BreakpointsInFile, StatefulBreakpoint};
 use collections::{BTreeMap, HashMap};
-use dap::{StackFrameId, client::SessionId};
-use gpui::{App, AppContext, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
+use dap::{StackFrameId, client::SessionId, debugger_settings::DebuggerSettings};
+use gpui::{
+    App, AppContext, AsyncApp, Context, Entity, EventEmitter, SharedString, Subscription, Task,
+};
 use itertools::Itertools;
 use language::{Buffer, BufferSnapshot, proto::serialize_anchor as serialize_text_anchor};
 use rpc::{
     AnyProtoClient, TypedEnvelope,
     proto::{self},
 };
+use settings::Settings as _;
 use std::{hash::Hash, ops::Range, path::Path, sync::Arc, u32};
 use text::{Point, PointUtf16};
 use util::maybe;
@@ -52,11 +55,13 @@ mod breakpoints_in_file {
         }
     }
 
-    #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
     pub struct BreakpointSessionState {
         /// Session-specific identifier for the breakpoint, as assigned by Debug Adapter.
         pub id: u64,
         pub verified: bool,
+        /// Explanation provided by the Debug Adapter, e.g. why the breakpoint could not be verified.
+        pub message: Option<Arc<str>>,
     }
     #[derive(Clone)]
     pub(super) struct BreakpointsInFile {
@@ -152,12 +157,19 @@ pub struct ActiveStackFrame {
     pub stack_frame_id: StackFrameId,
     pub path: Arc<Path>,
     pub position: text::Anchor,
+    /// The line the thread is executing, when the active frame is one of its callers.
+    pub top_frame_position: Option<text::Anchor>,
 }
 
+/// Breakpoints that are not currently loaded, keyed by the git branch they were set on.
+pub type BranchBreakpoints = BTreeMap<SharedString, BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>>;
+
 pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
+    active_branch: Option<SharedString>,
+    branch_breakpoints: BranchBreakpoints,
     // E.g ssh
     mode: BreakpointStoreMode,
 }
@@ -176,6 +188,8 @@ impl BreakpointStore {
             }),
             downstream_client: None,
             active_stack_frame: Default::default(),
+            active_branch: None,
+            branch_breakpoints: Default::default(),
         }
     }
 
@@ -188,6 +202,8 @@ impl BreakpointStore {
             }),
             downstream_client: None,
             active_stack_frame: Default::default(),
+            active_branch: None,
+            branch_breakpoints: Default::default(),
         }
     }
 
@@ -242,6 +258,7 @@ impl BreakpointStore {
                             let state = BreakpointSessionState {
                                 id: state.id,
                                 verified: state.verified,
+                                message: state.message.clone().map(Into::into),
                             };
                             (SessionId::from_proto(*session_id), state)
                         })
@@ -333,6 +350,7 @@ impl BreakpointStore {
         session_id: SessionId,
         _: dap::BreakpointEventReason,
         breakpoint: dap::Breakpoint,
+        cx: &mut Context<Self>,
     ) {
         maybe!({
             let event_id = breakpoint.id?;
@@ -356,6 +374,8 @@ impl BreakpointStore {
                 })?;
 
             state.verified = breakpoint.verified;
+            state.message = breakpoint.message.map(Into::into);
+            cx.notify();
             Some(())
         });
     }
@@ -364,8 +384,8 @@ impl BreakpointStore {
         &mut self,
         session_id: SessionId,
         abs_path: &Path,
-
         it: impl Iterator<Item = (BreakpointWithPosition, BreakpointSessionState)>,
+        cx: &mut Context<Self>,
     ) {
         maybe!({
             let breakpoints = self.breakpoints.get_mut(abs_path)?;
@@ -381,6 +401,7 @@ impl BreakpointStore {
                         .insert_entry(state);
                 }
             }
+            cx.notify();
             Some(())
         });
     }
@@ -535,6 +556,25 @@ impl BreakpointStore {
                     }
                 }
             }
+            BreakpointEditAction::EditNote(note) => {
+                let note = (!note.is_empty()).then_some(note);
+                let found_bp = breakpoint_set.breakpoints.iter_mut().find_map(|other| {
+                    if breakpoint.position == *other.position() {
+                        Some(&mut other.bp.bp)
+                    } else {
+                        None
+                    }
+                });
+
+                if let Some(found_bp) = found_bp {
+                    found_bp.note = note;
+                } else if note.is_some() {
+                    breakpoint.bp.note = note;
+                    breakpoint_set
+                        .breakpoints
+                        .push(StatefulBreakpoint::new(breakpoint.clone()));
+                }
+            }
         }
 
         if breakpoint_set.breakpoints.is_empty() {
@@ -584,6 +624,69 @@ impl BreakpointStore {
         cx.notify();
     }
 
+    /// Re-anchors the breakpoint at `from_row` to `to_row`, e.g. after the Debug Adapter reported
+    /// that execution can't stop at `from_row`.
+    pub(crate) fn move_breakpoint(
+        &mut self,
+        path: Arc<Path>,
+        from_row: u32,
+        to_row: u32,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(breakpoints) = self.breakpoints.get_mut(&path) else {
+            return;
+        };
+        let snapshot = breakpoints.buffer.read(cx).snapshot();
+        if to_row > snapshot.max_point().row {
+            return;
+        }
+        let row_of = |bp: &StatefulBreakpoint| bp.position().summary::<Point>(&snapshot).row;
+
+        if let Some(target_ix) = breakpoints
+            .breakpoints
+            .iter()
+            .position(|bp| row_of(bp) == to_row)
+        {
+            let Some(source_ix) = breakpoints
+                .breakpoints
+                .iter()
+                .position(|bp| row_of(bp) == from_row)
+            else {
+                return;
+            };
+            // Leave the breakpoint where it is rather than drop settings that clash with the
+            // ones of the breakpoint already on `to_row`.
+            let Some(merged) = breakpoints.breakpoints[target_ix]
+                .bp
+                .bp
+                .merged_with(&breakpoints.breakpoints[source_ix].bp.bp)
+            else {
+                return;
+            };
+            let target = &mut breakpoints.breakpoints[target_ix];
+            if target.bp.bp != merged {
+                target.bp.bp = merged;
+                target.session_state.clear();
+            }
+            breakpoints.breakpoints.remove(source_ix);
+        } else if let Some(bp) = breakpoints
+            .breakpoints
+            .iter_mut()
+            .find(|bp| row_of(bp) == from_row)
+        {
+            bp.bp.position = snapshot.anchor_after(Point::new(to_row, 0));
+            bp.session_state.clear();
+        } else {
+            return;
+        }
+
+        cx.emit(BreakpointStoreEvent::BreakpointsUpdated(
+            path,
+            BreakpointUpdatedReason::Toggled,
+        ));
+        cx.notify();
+    }
+
     pub fn on_file_rename(
         &mut self,
         old_path: Arc<Path>,
@@ -632,7 +735,7 @@ impl BreakpointStore {
                         }
                         let session_state = active_session_id
                             .and_then(|id| bp.session_state.get(&id))
-                            .copied();
+                            .cloned();
                         Some((&bp.bp, session_state))
                     }
                 })
@@ -696,6 +799,23 @@ impl BreakpointStore {
         })
     }
 
+    pub fn session_state_at_row(
+        &self,
+        path: &Path,
+        row: u32,
+        session_id: SessionId,
+        cx: &App,
+    ) -> Option<BreakpointSessionState> {
+        let breakpoints = self.breakpoints.get(path)?;
+        let snapshot = breakpoints.buffer.read(cx).text_snapshot();
+
+        breakpoints
+            .breakpoints
+            .iter()
+            .find(|bp| bp.position().summary::<Point>(&snapshot).row == row)
+            .and_then(|bp| bp.session_state.get(&session_id).cloned())
+    }
+
     pub fn breakpoints_from_path(&self, path: &Arc<Path>) -> Vec<BreakpointWithPosition> {
         self.breakpoints
             .get(path)
@@ -724,6 +844,7 @@ impl BreakpointStore {
                             message: bp.bp.message.clone(),
                             condition: bp.bp.condition.clone(),
                             hit_condition: bp.bp.hit_condition.clone(),
+                            note: bp.bp.note.clone(),
                         }
                     })
                     .collect()
@@ -763,6 +884,7 @@ impl BreakpointStore {
                                 state: breakpoint.bp.state,
                                 hit_condition: breakpoint.bp.hit_condition.clone(),
                                 condition: breakpoint.bp.condition.clone(),
+                                note: breakpoint.bp.note.clone(),
                             }
                         })
                         .collect(),
@@ -826,6 +948,7 @@ impl BreakpointStore {
                                     state: bp.state,
                                     condition: bp.condition,
                                     hit_condition: bp.hit_condition,
+                                    note: bp.note,
                                 },
                             }))
                     }
@@ -856,6 +979,72 @@ impl BreakpointStore {
         }
     }
 
+    /// Returns the breakpoints of every branch that should be persisted, including the ones
+    /// that are currently loaded.
+    pub fn branch_breakpoints(&self, cx: &App) -> BranchBreakpoints {
+        let mut branch_breakpoints = self.branch_breakpoints.clone();
+        if DebuggerSettings::get_global(cx).breakpoints_per_branch {
+            if let Some(branch) = self.active_branch.clone() {
+                branch_breakpoints.insert(branch, self.all_source_breakpoints(cx));
+            }
+        }
+        branch_breakpoints
+    }
+
+    pub fn with_serialized_branch_breakpoints(&mut self, branch_breakpoints: BranchBreakpoints) {
+        self.branch_breakpoints = branch_breakpoints;
+    }
+
+    /// Swaps in the breakpoints that were set the last time `branch` was checked out, stashing
+    /// the current ones under the previously active branch.
+    ///
+    /// A detached HEAD keeps the breakpoints of the last checked out branch, and edits made
+    /// there are stashed under that branch once another one is checked out.
+    pub fn set_active_branch(
+        &mut self,
+        branch: Option<SharedString>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(branch) = branch else {
+            return Task::ready(Ok(()));
+        };
+        if !DebuggerSettings::get_global(cx).breakpoints_per_branch
+            || self.active_branch.as_ref() == Some(&branch)
+        {
+            return Task::ready(Ok(()));
+        }
+        let previous_branch = self.active_branch.replace(branch.clone());
+
+        let current_breakpoints = self.all_source_breakpoints(cx);
+        let next_breakpoints = match previous_branch {
+            Some(previous_branch) => {
+                self.branch_breakpoints
+                    .insert(previous_branch, current_breakpoints);
+                self.branch_breakpoints.remove(&branch).unwrap_or_default()
+            }
+            // The first branch we observe owns whatever was restored from the database, unless
+            // we've stored a set for it during a previous run.
+            None => match self.branch_breakpoints.remove(&branch) {
+                Some(stashed) if stashed != current_breakpoints => stashed,
+                _ => return Task::ready(Ok(())),
+            },
+        };
+
+        self.clear_breakpoints(cx);
+        let load = self.with_serialized_breakpoints(next_breakpoints, cx);
+        cx.spawn(async move |this, cx| {
+            load.await?;
+            this.update(cx, |this, cx| {
+                for path in this.breakpoints.keys().cloned().collect::<Vec<_>>() {
+                    cx.emit(BreakpointStoreEvent::BreakpointsUpdated(
+                        path,
+                        BreakpointUpdatedReason::Toggled,
+                    ));
+                }
+            })
+        })
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn breakpoint_paths(&self) -> Vec<Arc<Path>> {
         self.breakpoints.keys().cloned().collect()
@@ -886,6 +1075,7 @@ pub enum BreakpointEditAction {
     EditLogMessage(BreakpointMessage),
     EditCondition(BreakpointMessage),
     EditHitCondition(BreakpointMessage),
+    EditNote(BreakpointMessage),
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
@@ -920,6 +1110,8 @@ pub struct Breakpoint {
     /// How many times do we hit the breakpoint until we actually stop at it e.g. (2 = 2 times of the breakpoint action)
     pub hit_condition: Option<Arc<str>>,
     pub condition: Option<BreakpointMessage>,
+    /// Free-form annotation left by the user; never sent to the debug adapter.
+    pub note: Option<BreakpointMessage>,
     pub state: BreakpointState,
 }
 
@@ -930,6 +1122,7 @@ impl Breakpoint {
             hit_condition: None,
             condition: None,
             message: None,
+            note: None,
         }
     }
 
@@ -939,6 +1132,7 @@ impl Breakpoint {
             condition: None,
             hit_condition: Some(hit_condition.into()),
             message: None,
+            note: None,
         }
     }
 
@@ -948,9 +1142,30 @@ impl Breakpoint {
             hit_condition: None,
             condition: None,
             message: Some(log_message.into()),
+            note: None,
         }
     }
 
+    /// `self` with the settings of `other` that it doesn't set itself, or `None` if both set the
+    /// same setting to different values.
+    fn merged_with(&self, other: &Breakpoint) -> Option<Breakpoint> {
+        fn merge<T: Clone + PartialEq>(this: &Option<T>, other: &Option<T>) -> Option<Option<T>> {
+            match (this, other) {
+                (Some(this), Some(other)) if this != other => None,
+                (Some(value), _) | (None, Some(value)) => Some(Some(value.clone())),
+                (None, None) => Some(None),
+            }
+        }
+
+        Some(Breakpoint {
+            message: merge(&self.message, &other.message)?,
+            hit_condition: merge(&self.hit_condition, &other.hit_condition)?,
+            condition: merge(&self.condition, &other.condition)?,
+            note: merge(&self.note, &other.note)?,
+            state: self.state,
+        })
+    }
+
     fn to_proto(
         &self,
         _path: &Path,
@@ -969,6 +1184,7 @@ impl Breakpoint {
                 .hit_condition
                 .as_ref()
                 .map(|s| String::from(s.as_ref())),
+            note: self.note.as_ref().map(|s| String::from(s.as_ref())),
             session_state: session_states
                 .iter()
                 .map(|(session_id, state)| {
@@ -977,6 +1193,7 @@ impl Breakpoint {
                         proto::BreakpointSessionState {
                             id: state.id,
                             verified: state.verified,
+                            message: state.message.as_ref().map(|s| String::from(s.as_ref())),
                         },
                     )
                 })
@@ -993,6 +1210,7 @@ impl Breakpoint {
             message: breakpoint.message.map(Into::into),
             condition: breakpoint.condition.map(Into::into),
             hit_condition: breakpoint.hit_condition.map(Into::into),
+            note: breakpoint.note.map(Into::into),
         })
     }
 
@@ -1015,6 +1233,7 @@ pub struct SourceBreakpoint {
     pub message: Option<Arc<str>>,
     pub condition: Option<Arc<str>>,
     pub hit_condition: Option<Arc<str>>,
+    pub note: Option<Arc<str>>,
     pub state: BreakpointState,
 }
 
@@ -1,14 +1,15 @@
 use std::sync::Arc;
 
 use anyhow::{Context as _, Ok, Result};
+use base64::Engine as _;
 use dap::{
     Capabilities, ContinueArguments, ExceptionFilterOptions, InitializeRequestArguments,
-    InitializeRequestArgumentsPathFormat, NextArguments, SetVariableResponse, SourceBreakpoint,
-    StepInArguments, StepOutArguments, SteppingGranularity, ValueFormat, Variable,
-    VariablesArgumentsFilter,
+    InitializeRequestArgumentsPathFormat, NextArguments, ReverseContinueArguments,
+    SetVariableResponse, SourceBreakpoint, StepInArguments, StepOutArguments, SteppingGranularity,
+    ValueFormat, Variable, VariablesArgumentsFilter,
     client::SessionId,
     proto_conversions::ProtoConversion,
-    requests::{Continue, Next},
+    requests::{Continue, Next, ReverseContinue},
 };
 use rpc::proto;
 use serde_json::Value;
@@ -31,26 +32,24 @@ pub trait LocalDapCommand: 'static + Send + Sync + std::fmt::Debug {
 }
 
 pub trait DapCommand: LocalDapCommand {
-    type ProtoRequest: 'static + Send;
+    type ProtoRequest: 'static
+        + Send
+        + proto::RequestMessage<Response = Self::ProtoResponse>
+        + proto::EntityMessage;
     type ProtoResponse: 'static + Send;
     const CACHEABLE: bool = false;
 
-    #[allow(dead_code)]
     fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId;
 
-    #[allow(dead_code)]
     fn from_proto(request: &Self::ProtoRequest) -> Self;
 
-    #[allow(unused)]
     fn to_proto(&self, debug_client_id: SessionId, upstream_project_id: u64) -> Self::ProtoRequest;
 
-    #[allow(dead_code)]
     fn response_to_proto(
         debug_client_id: SessionId,
         message: Self::Response,
     ) -> Self::ProtoResponse;
 
-    #[allow(unused)]
     fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response>;
 }
 
@@ -478,6 +477,73 @@ impl DapCommand for ContinueCommand {
     }
 }
 
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub(crate) struct ReverseContinueCommand {
+    pub args: ReverseContinueArguments,
+}
+
+impl LocalDapCommand for ReverseContinueCommand {
+    type Response = <ReverseContinue as dap::requests::Request>::Response;
+    type DapRequest = ReverseContinue;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_step_back.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        self.args.clone()
+    }
+
+    fn response_from_dap(
+        &self,
+        _message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(())
+    }
+}
+
+impl DapCommand for ReverseContinueCommand {
+    type ProtoRequest = proto::DapReverseContinueRequest;
+    type ProtoResponse = proto::Ack;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn to_proto(
+        &self,
+        debug_client_id: SessionId,
+        upstream_project_id: u64,
+    ) -> proto::DapReverseContinueRequest {
+        proto::DapReverseContinueRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            thread_id: self.args.thread_id,
+            single_thread: self.args.single_thread,
+        }
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            args: ReverseContinueArguments {
+                thread_id: request.thread_id,
+                single_thread: request.single_thread,
+            },
+        }
+    }
+
+    fn response_from_proto(&self, _message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(())
+    }
+
+    fn response_to_proto(
+        _debug_client_id: SessionId,
+        _message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::Ack {}
+    }
+}
+
 #[derive(Debug, Hash, PartialEq, Eq)]
 pub(crate) struct PauseCommand {
     pub thread_id: u64,
@@ -856,7 +922,7 @@ impl DapCommand for VariablesCommand {
             project_id: upstream_project_id,
             client_id: debug_client_id.to_proto(),
             variables_reference: self.variables_reference,
-            filter: None,
+            filter: self.filter.map(|filter| filter.to_proto() as i32),
             start: self.start,
             count: self.count,
             format: None,
@@ -866,7 +932,10 @@ impl DapCommand for VariablesCommand {
     fn from_proto(request: &Self::ProtoRequest) -> Self {
         Self {
             variables_reference: request.variables_reference,
-            filter: None,
+            filter: request
+                .filter
+                .and_then(proto::VariablesArgumentsFilter::from_i32)
+                .map(VariablesArgumentsFilter::from_proto),
             start: request.start,
             count: request.count,
             format: None,
@@ -970,6 +1039,93 @@ impl DapCommand for SetVariableValueCommand {
     }
 }
 
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(crate) struct SetExpressionCommand {
+    pub expression: String,
+    pub value: String,
+    pub frame_id: Option<u64>,
+}
+
+impl LocalDapCommand for SetExpressionCommand {
+    type Response = dap::SetExpressionResponse;
+    type DapRequest = dap::requests::SetExpression;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_set_expression.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetExpressionArguments {
+            expression: self.expression.clone(),
+            value: self.value.clone(),
+            frame_id: self.frame_id,
+            format: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+impl DapCommand for SetExpressionCommand {
+    type ProtoRequest = proto::DapSetExpressionRequest;
+    type ProtoResponse = proto::DapSetExpressionResponse;
+
+    fn client_id_from_proto(request: &Self::ProtoRequest) -> SessionId {
+        SessionId::from_proto(request.client_id)
+    }
+
+    fn to_proto(&self, debug_client_id: SessionId, upstream_project_id: u64) -> Self::ProtoRequest {
+        proto::DapSetExpressionRequest {
+            project_id: upstream_project_id,
+            client_id: debug_client_id.to_proto(),
+            expression: self.expression.clone(),
+            value: self.value.clone(),
+            frame_id: self.frame_id,
+        }
+    }
+
+    fn from_proto(request: &Self::ProtoRequest) -> Self {
+        Self {
+            expression: request.expression.clone(),
+            value: request.value.clone(),
+            frame_id: request.frame_id,
+        }
+    }
+
+    fn response_to_proto(
+        debug_client_id: SessionId,
+        message: Self::Response,
+    ) -> Self::ProtoResponse {
+        proto::DapSetExpressionResponse {
+            client_id: debug_client_id.to_proto(),
+            value: message.value,
+            variable_type: message.type_,
+            variables_reference: message.variables_reference,
+            named_variables: message.named_variables,
+            indexed_variables: message.indexed_variables,
+            memory_reference: message.memory_reference,
+        }
+    }
+
+    fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
+        Ok(dap::SetExpressionResponse {
+            value: message.value,
+            type_: message.variable_type,
+            presentation_hint: None,
+            variables_reference: message.variables_reference,
+            named_variables: message.named_variables,
+            indexed_variables: message.indexed_variables,
+            memory_reference: message.memory_reference,
+            value_location_reference: None,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub(crate) struct RestartStackFrameCommand {
     pub stack_frame_id: u64,
@@ -1185,7 +1341,7 @@ pub(crate) struct StackTraceCommand {
 }
 
 impl LocalDapCommand for StackTraceCommand {
-    type Response = Vec<dap::StackFrame>;
+    type Response = dap::StackTraceResponse;
     type DapRequest = dap::requests::StackTrace;
 
     fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
@@ -1201,7 +1357,7 @@ impl LocalDapCommand for StackTraceCommand {
         &self,
         message: <Self::DapRequest as dap::requests::Request>::Response,
     ) -> Result<Self::Response> {
-        Ok(message.stack_frames)
+        Ok(message)
     }
 }
 
@@ -1233,11 +1389,14 @@ impl DapCommand for StackTraceCommand {
     }
 
     fn response_from_proto(&self, message: Self::ProtoResponse) -> Result<Self::Response> {
-        Ok(message
-            .frames
-            .into_iter()
-            .map(dap::StackFrame::from_proto)
-            .collect())
+        Ok(dap::StackTraceResponse {
+            stack_frames: message
+                .frames
+                .into_iter()
+                .map(dap::StackFrame::from_proto)
+                .collect(),
+            total_frames: message.total_frames,
+        })
     }
 
     fn response_to_proto(
@@ -1245,7 +1404,8 @@ impl DapCommand for StackTraceCommand {
         message: Self::Response,
     ) -> Self::ProtoResponse {
         proto::DapStackTraceResponse {
-            frames: message.to_proto(),
+            frames: message.stack_frames.to_proto(),
+            total_frames: message.total_frames,
         }
     }
 }
@@ -1389,6 +1549,7 @@ pub(crate) struct EvaluateCommand {
     pub frame_id: Option<u64>,
     pub context: Option<dap::EvaluateArgumentsContext>,
     pub source: Option<dap::Source>,
+    pub format: Option<ValueFormat>,
 }
 
 impl LocalDapCommand for EvaluateCommand {
@@ -1402,7 +1563,7 @@ impl LocalDapCommand for EvaluateCommand {
             source: self.source.clone(),
             line: None,
             column: None,
-            format: None,
+            format: self.format.clone(),
         }
     }
 
@@ -1440,6 +1601,7 @@ impl DapCommand for EvaluateCommand {
             frame_id: request.frame_id,
             context: Some(dap::EvaluateArgumentsContext::from_proto(request.context())),
             source: None,
+            format: None,
         }
     }
 
@@ -1529,7 +1691,7 @@ pub(super) struct Initialize {
     pub(super) adapter_id: String,
 }
 
-fn dap_client_capabilities(adapter_id: String) -> InitializeRequestArguments {
+pub(super) fn dap_client_capabilities(adapter_id: String) -> InitializeRequestArguments {
     InitializeRequestArguments {
         client_id: Some("zed".to_owned()),
         client_name: Some("Zed".to_owned()),
@@ -1537,10 +1699,10 @@ fn dap_client_capabilities(adapter_id: String) -> InitializeRequestArguments {
         locale: Some("en-US".to_owned()),
         path_format: Some(InitializeRequestArgumentsPathFormat::Path),
         supports_variable_type: Some(true),
-        supports_variable_paging: Some(false),
+        supports_variable_paging: Some(true),
         supports_run_in_terminal_request: Some(true),
         supports_memory_references: Some(true),
-        supports_progress_reporting: Some(false),
+        supports_progress_reporting: Some(true),
         supports_invalidated_event: Some(false),
         lines_start_at1: Some(true),
         columns_start_at1: Some(true),
@@ -1638,6 +1800,259 @@ impl LocalDapCommand for Attach {
     }
 }
 
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct ReadMemoryCommand {
+    pub(super) memory_reference: String,
+    pub(super) offset: Option<i64>,
+    pub(super) count: u64,
+}
+
+impl LocalDapCommand for ReadMemoryCommand {
+    type Response = Vec<u8>;
+    type DapRequest = dap::requests::ReadMemory;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_read_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ReadMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            count: self.count,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        // Adapters leave out `data` when none of the requested bytes could be read.
+        let Some(data) = message.data else {
+            return Ok(Vec::new());
+        };
+        base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .context("decoding memory contents")
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct WriteMemoryCommand {
+    pub(super) memory_reference: String,
+    pub(super) offset: Option<i64>,
+    pub(super) data: Vec<u8>,
+}
+
+impl LocalDapCommand for WriteMemoryCommand {
+    type Response = ();
+    type DapRequest = dap::requests::WriteMemory;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_write_memory_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::WriteMemoryArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: self.offset,
+            allow_partial: None,
+            data: base64::engine::general_purpose::STANDARD.encode(&self.data),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        _message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct CancelCommand {
+    pub(super) progress_id: String,
+}
+
+impl LocalDapCommand for CancelCommand {
+    type Response = ();
+    type DapRequest = dap::requests::Cancel;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities.supports_cancel_request.unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::CancelArguments {
+            request_id: None,
+            progress_id: Some(self.progress_id.clone()),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct ExceptionInfoCommand {
+    pub(super) thread_id: u64,
+}
+
+impl LocalDapCommand for ExceptionInfoCommand {
+    type Response = dap::ExceptionInfoResponse;
+    type DapRequest = dap::requests::ExceptionInfo;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_exception_info_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::ExceptionInfoArguments {
+            thread_id: self.thread_id,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct DisassembleCommand {
+    pub(super) memory_reference: String,
+    pub(super) instruction_offset: i64,
+    pub(super) instruction_count: u64,
+}
+
+impl LocalDapCommand for DisassembleCommand {
+    type Response = Vec<dap::DisassembledInstruction>;
+    type DapRequest = dap::requests::Disassemble;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_disassemble_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::DisassembleArguments {
+            memory_reference: self.memory_reference.clone(),
+            offset: None,
+            instruction_offset: Some(self.instruction_offset),
+            instruction_count: self.instruction_count,
+            resolve_symbols: Some(true),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.instructions)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct GotoTargetsCommand {
+    pub(super) source: dap::Source,
+    pub(super) line: u64,
+}
+
+impl LocalDapCommand for GotoTargetsCommand {
+    type Response = Vec<dap::GotoTarget>;
+    type DapRequest = dap::requests::GotoTargets;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::GotoTargetsArguments {
+            source: self.source.clone(),
+            line: self.line,
+            column: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.targets)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct GotoCommand {
+    pub(super) thread_id: u64,
+    pub(super) target_id: u64,
+}
+
+impl LocalDapCommand for GotoCommand {
+    type Response = ();
+    type DapRequest = dap::requests::Goto;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_goto_targets_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::GotoArguments {
+            thread_id: self.thread_id,
+            target_id: self.target_id,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct SourceCommand {
+    pub(super) source: dap::Source,
+    pub(super) source_reference: u64,
+}
+
+impl LocalDapCommand for SourceCommand {
+    type Response = dap::SourceResponse;
+    type DapRequest = dap::requests::Source;
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SourceArguments {
+            source: Some(self.source.clone()),
+            source_reference: self.source_reference,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub(super) struct SetBreakpoints {
     pub(super) source: dap::Source,
@@ -1665,6 +2080,41 @@ impl LocalDapCommand for SetBreakpoints {
         Ok(message.breakpoints)
     }
 }
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub(super) struct BreakpointLocationsCommand {
+    pub(super) source: dap::Source,
+    pub(super) line: u64,
+    pub(super) end_line: Option<u64>,
+}
+
+impl LocalDapCommand for BreakpointLocationsCommand {
+    type Response = Vec<dap::BreakpointLocation>;
+    type DapRequest = dap::requests::BreakpointLocations;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_breakpoint_locations_request
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::BreakpointLocationsArguments {
+            source: self.source.clone(),
+            line: self.line,
+            column: None,
+            end_line: self.end_line,
+            end_column: None,
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq)]
 pub(super) enum SetExceptionBreakpoints {
     Plain {
@@ -1704,6 +2154,35 @@ impl LocalDapCommand for SetExceptionBreakpoints {
     }
 }
 
+#[derive(Clone, Debug)]
+pub(super) struct SetInstructionBreakpoints {
+    pub(super) breakpoints: Vec<dap::InstructionBreakpoint>,
+}
+
+impl LocalDapCommand for SetInstructionBreakpoints {
+    type Response = Vec<dap::Breakpoint>;
+    type DapRequest = dap::requests::SetInstructionBreakpoints;
+
+    fn is_supported(capabilities: &Capabilities) -> bool {
+        capabilities
+            .supports_instruction_breakpoints
+            .unwrap_or_default()
+    }
+
+    fn to_dap(&self) -> <Self::DapRequest as dap::requests::Request>::Arguments {
+        dap::SetInstructionBreakpointsArguments {
+            breakpoints: self.breakpoints.clone(),
+        }
+    }
+
+    fn response_from_dap(
+        &self,
+        message: <Self::DapRequest as dap::requests::Request>::Response,
+    ) -> Result<Self::Response> {
+        Ok(message.breakpoints)
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub(super) struct LocationsCommand {
     pub(super) reference: u64,
@@ -1,8 +1,17 @@
 use super::{
     breakpoint_store::BreakpointStore,
-    dap_command::EvaluateCommand,
-    locators,
-    session::{self, Session, SessionStateEvent},
+    dap_command::{
+        ContinueCommand, DapCommand, DisconnectCommand, EvaluateCommand, LoadedSourcesCommand,
+        LocationsCommand, ModulesCommand, NextCommand, PauseCommand, RestartCommand,
+        RestartStackFrameCommand, ReverseContinueCommand, ScopesCommand, SetExpressionCommand,
+        SetVariableValueCommand, StackTraceCommand, StepBackCommand, StepInCommand, StepOutCommand,
+        TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+        dap_client_capabilities,
+    },
+    docker, locators,
+    recording::DapRecording,
+    session::{self, CompletionsQuery, Session, SessionEvent, SessionStateEvent},
+    wsl,
 };
 use crate::{
     InlayHint, InlayHintLabel, ProjectEnvironment, ResolveState,
@@ -19,10 +28,11 @@ use dap::{
     adapters::{
         DapDelegate, DebugAdapterBinary, DebugAdapterName, DebugTaskDefinition, TcpArguments,
     },
-    client::SessionId,
+    client::{DebugAdapterClient, SessionId},
+    debugger_settings::{DebuggerSettings, InlineValues},
     inline_value::VariableLookupKind,
-    messages::Message,
-    requests::{Completions, Evaluate},
+    messages::{Events, Message},
+    requests::{Completions, Evaluate, Initialize},
 };
 use fs::Fs;
 use futures::{
@@ -48,8 +58,11 @@ use std::{
     net::Ipv4Addr,
     path::{Path, PathBuf},
     sync::{Arc, Once},
+    time::Duration,
+};
+use task::{
+    DebugScenario, LaunchRequest, SpawnInTerminal, TaskContext, TaskTemplate, ZedDebugConfig,
 };
-use task::{DebugScenario, SpawnInTerminal, TaskContext, TaskTemplate};
 use util::ResultExt as _;
 use worktree::Worktree;
 
@@ -64,12 +77,46 @@ pub enum DapStoreEvent {
     },
     Notification(String),
     RemoteHasInitialized,
+    /// The host of a shared project started a debug session that this guest now mirrors.
+    RemoteSessionAdded(Entity<Session>),
+}
+
+/// How long a debug adapter is given to answer the `initialize` request when it's checked.
+const ADAPTER_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The step of starting a debug adapter that [`DapStore::check_adapter`] failed at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdapterCheckStage {
+    /// Finding or downloading the adapter's binary.
+    Locate,
+    /// Starting the adapter's process and connecting to it.
+    Launch,
+    /// Exchanging the `initialize` request and response.
+    Initialize,
+}
+
+/// The outcome of checking that a debug adapter can be started.
+#[derive(Clone, Debug)]
+pub enum AdapterCheck {
+    Passed {
+        /// The command the adapter was started with, if it was started locally.
+        command: Option<String>,
+    },
+    Failed {
+        stage: AdapterCheckStage,
+        error: String,
+    },
 }
 
 enum DapStoreMode {
     Local(LocalDapStore),
     Ssh(SshDapStore),
-    Collab,
+    Collab(CollabDapStore),
+}
+
+pub struct CollabDapStore {
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
 }
 
 pub struct LocalDapStore {
@@ -110,6 +157,32 @@ impl DapStore {
         client.add_entity_request_handler(Self::handle_run_debug_locator);
         client.add_entity_request_handler(Self::handle_get_debug_adapter_binary);
         client.add_entity_message_handler(Self::handle_log_to_debug_console);
+        client.add_entity_message_handler(Self::handle_update_debug_session);
+        client.add_entity_message_handler(Self::handle_debug_session_event);
+        client.add_entity_message_handler(Self::handle_debug_session_shutdown);
+        client.add_entity_request_handler(Self::handle_dap_command::<NextCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<StepInCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<StepOutCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<StepBackCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<ContinueCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<ReverseContinueCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<PauseCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<DisconnectCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<TerminateThreadsCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<TerminateCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<RestartCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<VariablesCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<SetVariableValueCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<SetExpressionCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<RestartStackFrameCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<ModulesCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<LoadedSourcesCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<StackTraceCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<ScopesCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<CompletionsQuery>);
+        client.add_entity_request_handler(Self::handle_dap_command::<EvaluateCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<ThreadsCommand>);
+        client.add_entity_request_handler(Self::handle_dap_command::<LocationsCommand>);
     }
 
     #[expect(clippy::too_many_arguments)]
@@ -151,13 +224,18 @@ impl DapStore {
     }
 
     pub fn new_collab(
-        _project_id: u64,
-        _upstream_client: AnyProtoClient,
+        project_id: u64,
+        upstream_client: AnyProtoClient,
         breakpoint_store: Entity<BreakpointStore>,
         worktree_store: Entity<WorktreeStore>,
         cx: &mut Context<Self>,
     ) -> Self {
-        Self::new(DapStoreMode::Collab, breakpoint_store, worktree_store, cx)
+        let mode = DapStoreMode::Collab(CollabDapStore {
+            upstream_client,
+            upstream_project_id: project_id,
+        });
+
+        Self::new(mode, breakpoint_store, worktree_store, cx)
     }
 
     fn new(
@@ -197,12 +275,22 @@ impl DapStore {
 
                 let delegate = self.delegate(&worktree, console, cx);
                 let cwd: Arc<Path> = worktree.read(cx).abs_path().as_ref().into();
+                let container = docker::container_for_config(&definition.config).map(String::from);
+                let wsl_distro = wsl::wsl_path(&cwd).map(|(distro, _)| distro);
 
                 cx.spawn(async move |this, cx| {
                     let mut binary = adapter
                         .get_binary(&delegate, &definition, user_installed_path, cx)
                         .await?;
 
+                    // The host's environment doesn't apply inside of a container.
+                    if let Some(container) = container {
+                        return docker::wrap_for_docker(&container, binary).await;
+                    }
+                    if let Some(wsl_distro) = wsl_distro {
+                        return Ok(wsl::wrap_for_wsl(&wsl_distro, binary));
+                    }
+
                     let env = this
                         .update(cx, |this, cx| {
                             this.as_local()
@@ -275,9 +363,9 @@ impl DapStore {
                     })
                 })
             }
-            DapStoreMode::Collab => {
-                Task::ready(Err(anyhow!("Debugging is not yet supported via collab")))
-            }
+            DapStoreMode::Collab(_) => Task::ready(Err(anyhow!(
+                "Only the host of a shared project can start debug sessions"
+            ))),
         }
     }
 
@@ -345,9 +433,9 @@ impl DapStore {
                     DebugRequest::from_proto(response)
                 })
             }
-            DapStoreMode::Collab => {
-                Task::ready(Err(anyhow!("Debugging is not yet supported via collab")))
-            }
+            DapStoreMode::Collab(_) => Task::ready(Err(anyhow!(
+                "Only the host of a shared project can start debug sessions"
+            ))),
         }
     }
 
@@ -385,8 +473,20 @@ impl DapStore {
         );
 
         self.sessions.insert(session_id, session.clone());
+        if let Some(downstream_client) = self.downstream_client.clone() {
+            session.update(cx, |session, _| {
+                session.set_downstream_client(Some(downstream_client))
+            });
+            self.send_session_update(&session, cx);
+        }
         cx.notify();
 
+        cx.subscribe(&session, |this, session, event: &SessionEvent, cx| {
+            if let SessionEvent::CapabilitiesLoaded = event {
+                this.send_session_update(&session, cx);
+            }
+        })
+        .detach();
         cx.subscribe(&session, {
             move |this: &mut DapStore, _, event: &SessionStateEvent, cx| match event {
                 SessionStateEvent::Shutdown => {
@@ -403,6 +503,23 @@ impl DapStore {
         session
     }
 
+    /// Creates a read-only session that plays back a recording of another session's DAP traffic.
+    pub fn replay_session(
+        &mut self,
+        recording: DapRecording,
+        cx: &mut Context<Self>,
+    ) -> Entity<Session> {
+        let session = self.new_session(
+            format!("{} (replay)", recording.label).into(),
+            DebugAdapterName(recording.adapter.clone().into()),
+            TaskContext::default(),
+            None,
+            cx,
+        );
+        session.update(cx, |session, cx| session.start_replay(recording, cx));
+        session
+    }
+
     pub fn boot_session(
         &self,
         session: Entity<Session>,
@@ -417,22 +534,31 @@ impl DapStore {
         cx.spawn({
             let session = session.clone();
             async move |this, cx| {
-                let binary = this
-                    .update(cx, |this, cx| {
-                        this.get_debug_adapter_binary(
-                            definition.clone(),
-                            session_id,
-                            &worktree,
-                            console,
-                            cx,
-                        )
-                    })?
-                    .await?;
-                session
-                    .update(cx, |session, cx| {
-                        session.boot(binary, worktree, dap_store, cx)
-                    })?
-                    .await
+                let result = async {
+                    let binary = this
+                        .update(cx, |this, cx| {
+                            this.get_debug_adapter_binary(
+                                definition.clone(),
+                                session_id,
+                                &worktree,
+                                console,
+                                cx,
+                            )
+                        })?
+                        .await?;
+                    session
+                        .update(cx, |session, cx| {
+                            session.boot(binary, worktree, dap_store, cx)
+                        })?
+                        .await
+                }
+                .await;
+                if let Err(error) = &result {
+                    session
+                        .update(cx, |session, cx| session.adapter_failed(error, cx))
+                        .ok();
+                }
+                result
             }
         })
     }
@@ -469,6 +595,90 @@ impl DapStore {
         &self.worktree_store
     }
 
+    /// Checks that an adapter can be located, launched and completes the `initialize` handshake,
+    /// without starting a debug session. The adapter is shut down afterwards.
+    pub fn check_adapter(
+        &mut self,
+        adapter_name: DebugAdapterName,
+        worktree: &Entity<Worktree>,
+        cx: &mut Context<Self>,
+    ) -> Task<AdapterCheck> {
+        let failed = |stage, error: anyhow::Error| AdapterCheck::Failed {
+            stage,
+            error: format!("{error:#}"),
+        };
+        let Some(adapter) = DapRegistry::global(cx).adapter(&adapter_name) else {
+            return Task::ready(failed(
+                AdapterCheckStage::Locate,
+                anyhow!("no debug adapter named {adapter_name} is registered"),
+            ));
+        };
+
+        let session_id = SessionId(util::post_inc(&mut self.next_session_id));
+        let worktree = worktree.clone();
+        let (console, _console_rx) = mpsc::unbounded();
+        cx.spawn(async move |this, cx| {
+            let _console_rx = _console_rx;
+            let binary = async {
+                let config = adapter
+                    .config_from_zed_format(ZedDebugConfig {
+                        label: format!("Check {adapter_name}").into(),
+                        adapter: adapter_name.0.clone(),
+                        request: DebugRequest::Launch(LaunchRequest {
+                            program: String::new(),
+                            cwd: None,
+                            args: Vec::new(),
+                            env: Default::default(),
+                        }),
+                        stop_on_entry: None,
+                    })
+                    .await?;
+                let definition = DebugTaskDefinition {
+                    label: config.label,
+                    adapter: adapter_name.clone(),
+                    config: config.config,
+                    tcp_connection: config.tcp_connection,
+                };
+                this.update(cx, |this, cx| {
+                    this.get_debug_adapter_binary(definition, session_id, &worktree, console, cx)
+                })?
+                .await
+            }
+            .await;
+            let binary = match binary {
+                Ok(binary) => binary,
+                Err(error) => return failed(AdapterCheckStage::Locate, error),
+            };
+
+            let command = binary.command.clone();
+            let client =
+                match DebugAdapterClient::start(session_id, binary, Box::new(|_| {}), None, cx)
+                    .await
+                {
+                    Ok(client) => client,
+                    Err(error) => return failed(AdapterCheckStage::Launch, error),
+                };
+
+            let timeout = cx.background_executor().timer(ADAPTER_CHECK_TIMEOUT);
+            let initialize =
+                client.request::<Initialize>(dap_client_capabilities(adapter_name.to_string()));
+            let result = smol::future::or(initialize, async move {
+                timeout.await;
+                Err(anyhow!(
+                    "the adapter didn't respond within {} seconds",
+                    ADAPTER_CHECK_TIMEOUT.as_secs()
+                ))
+            })
+            .await;
+            client.shutdown().await.log_err();
+
+            match result {
+                Ok(_) => AdapterCheck::Passed { command },
+                Err(error) => failed(AdapterCheckStage::Initialize, error),
+            }
+        })
+    }
+
     #[allow(dead_code)]
     async fn handle_ignore_breakpoint_state(
         this: Entity<Self>,
@@ -583,6 +793,7 @@ impl DapStore {
     ) -> Task<Result<Vec<InlayHint>>> {
         let snapshot = buffer_handle.read(cx).snapshot();
         let all_variables = session.read(cx).variables_by_stack_frame_id(stack_frame_id);
+        let inline_values = DebuggerSettings::get_global(cx).inline_values;
 
         fn format_value(mut value: String) -> String {
             const LIMIT: usize = 100;
@@ -597,18 +808,24 @@ impl DapStore {
                 value.push_str("...");
             }
 
-            format!(": {}", value)
+            value
+        }
+
+        fn inlay_hint(position: language::Anchor, label: String) -> InlayHint {
+            InlayHint {
+                position,
+                label: InlayHintLabel::String(label),
+                kind: Some(InlayHintKind::Type),
+                padding_left: false,
+                padding_right: false,
+                tooltip: None,
+                resolve_state: ResolveState::Resolved,
+            }
         }
 
         cx.spawn(async move |_, cx| {
-            let mut inlay_hints = Vec::with_capacity(inline_value_locations.len());
+            let mut values = Vec::with_capacity(inline_value_locations.len());
             for inline_value_location in inline_value_locations.iter() {
-                let point = snapshot.point_to_point_utf16(language::Point::new(
-                    inline_value_location.row as u32,
-                    inline_value_location.column as u32,
-                ));
-                let position = snapshot.anchor_after(point);
-
                 match inline_value_location.lookup {
                     VariableLookupKind::Variable => {
                         let Some(variable) = all_variables
@@ -618,15 +835,7 @@ impl DapStore {
                             continue;
                         };
 
-                        inlay_hints.push(InlayHint {
-                            position,
-                            label: InlayHintLabel::String(format_value(variable.value.clone())),
-                            kind: Some(InlayHintKind::Type),
-                            padding_left: false,
-                            padding_right: false,
-                            tooltip: None,
-                            resolve_state: ResolveState::Resolved,
-                        });
+                        values.push((inline_value_location, format_value(variable.value.clone())));
                     }
                     VariableLookupKind::Expression => {
                         let Ok(eval_task) = session.read_with(cx, |session, _| {
@@ -635,26 +844,54 @@ impl DapStore {
                                 frame_id: Some(stack_frame_id),
                                 source: None,
                                 context: Some(EvaluateArgumentsContext::Variables),
+                                format: None,
                             })
                         }) else {
                             continue;
                         };
 
                         if let Some(response) = eval_task.await.log_err() {
-                            inlay_hints.push(InlayHint {
-                                position,
-                                label: InlayHintLabel::String(format_value(response.result)),
-                                kind: Some(InlayHintKind::Type),
-                                padding_left: false,
-                                padding_right: false,
-                                tooltip: None,
-                                resolve_state: ResolveState::Resolved,
-                            });
+                            values.push((inline_value_location, format_value(response.result)));
                         };
                     }
                 };
             }
 
+            let inlay_hints = match inline_values {
+                InlineValues::Off => Vec::new(),
+                InlineValues::NextToVariables => values
+                    .into_iter()
+                    .map(|(location, value)| {
+                        let point = snapshot.point_to_point_utf16(language::Point::new(
+                            location.row as u32,
+                            location.column as u32,
+                        ));
+                        inlay_hint(snapshot.anchor_after(point), format!(": {value}"))
+                    })
+                    .collect(),
+                InlineValues::EndOfLine => {
+                    let mut rows = BTreeMap::<u32, Vec<(&str, String)>>::new();
+                    for (location, value) in values {
+                        let row = rows.entry(location.row as u32).or_default();
+                        // A variable used several times on a line is only listed once.
+                        if !row.iter().any(|(name, _)| *name == location.variable_name) {
+                            row.push((&location.variable_name, value));
+                        }
+                    }
+                    rows.into_iter()
+                        .map(|(row, values)| {
+                            let values = values
+                                .into_iter()
+                                .map(|(name, value)| format!("{name} = {value}"))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            let end = language::Point::new(row, snapshot.line_len(row));
+                            inlay_hint(snapshot.anchor_after(end), format!(" {values}"))
+                        })
+                        .collect()
+                }
+            };
+
             Ok(inlay_hints)
         })
     }
@@ -708,6 +945,14 @@ impl DapStore {
 
         let shutdown_task = session.update(cx, |this, cx| this.shutdown(cx));
 
+        if let Some((client, project_id)) = &self.downstream_client {
+            client
+                .send(proto::DebugSessionShutdown {
+                    project_id: *project_id,
+                    session_id: session_id.to_proto(),
+                })
+                .log_err();
+        }
         cx.emit(DapStoreEvent::DebugClientShutdown(session_id));
 
         cx.background_spawn(async move {
@@ -729,17 +974,158 @@ impl DapStore {
         &mut self,
         project_id: u64,
         downstream_client: AnyProtoClient,
-        _: &mut Context<Self>,
+        cx: &mut Context<Self>,
     ) {
         self.downstream_client = Some((downstream_client.clone(), project_id));
+
+        let sessions = self.sessions.values().cloned().collect::<Vec<_>>();
+        for session in sessions {
+            session.update(cx, |session, _| {
+                session.set_downstream_client(Some((downstream_client.clone(), project_id)))
+            });
+        }
+        self.broadcast(cx);
+    }
+
+    /// Sends every session and the state of its threads to the guests, e.g. when one joins after
+    /// the sessions were started.
+    pub(crate) fn broadcast(&self, cx: &App) {
+        for session in self.sessions.values() {
+            self.send_session_update(session, cx);
+            session.read(cx).send_thread_states();
+        }
     }
 
     pub fn unshared(&mut self, cx: &mut Context<Self>) {
         self.downstream_client.take();
+        for session in self.sessions.values() {
+            session.update(cx, |session, _| session.set_downstream_client(None));
+        }
 
         cx.notify();
     }
 
+    /// Tells the guests of a shared project about a session, creating it on their side if needed.
+    fn send_session_update(&self, session: &Entity<Session>, cx: &App) {
+        let Some((client, project_id)) = &self.downstream_client else {
+            return;
+        };
+        let session = session.read(cx);
+        let Some(capabilities) = serde_json::to_string(session.capabilities()).log_err() else {
+            return;
+        };
+        client
+            .send(proto::UpdateDebugSession {
+                project_id: *project_id,
+                session_id: session.session_id().to_proto(),
+                parent_session_id: session.parent_id(cx).map(|id| id.to_proto()),
+                label: session.label().to_string(),
+                adapter: session.adapter().to_string(),
+                capabilities,
+            })
+            .log_err();
+    }
+
+    async fn handle_dap_command<T: DapCommand>(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<T::ProtoRequest>,
+        mut cx: AsyncApp,
+    ) -> Result<T::ProtoResponse> {
+        let session_id = T::client_id_from_proto(&envelope.payload);
+        let request = T::from_proto(&envelope.payload);
+        let session = this
+            .read_with(&mut cx, |this, _| this.session_by_id(session_id))?
+            .with_context(|| format!("Could not find session: {session_id:?}"))?;
+        let response = session
+            .update(&mut cx, |session, _| session.request_for_remote(request))?
+            .await?;
+
+        Ok(T::response_to_proto(session_id, response))
+    }
+
+    async fn handle_update_debug_session(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::UpdateDebugSession>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let payload = envelope.payload;
+        let session_id = SessionId::from_proto(payload.session_id);
+        let capabilities = serde_json::from_str::<Capabilities>(&payload.capabilities)
+            .context("Deserializing debug adapter capabilities")?;
+        this.update(&mut cx, |this, cx| {
+            if let Some(session) = this.sessions.get(&session_id) {
+                session.update(cx, |session, cx| {
+                    session.capabilities = capabilities;
+                    cx.emit(SessionEvent::CapabilitiesLoaded);
+                    cx.notify();
+                });
+                return;
+            }
+
+            let DapStoreMode::Collab(collab) = &this.mode else {
+                return;
+            };
+            let parent_session = payload
+                .parent_session_id
+                .and_then(|id| this.sessions.get(&SessionId::from_proto(id)).cloned());
+            let session = Session::remote(
+                this.breakpoint_store.clone(),
+                session_id,
+                parent_session,
+                payload.label.into(),
+                DebugAdapterName(payload.adapter.into()),
+                capabilities,
+                collab.upstream_client.clone(),
+                collab.upstream_project_id,
+                cx,
+            );
+            this.sessions.insert(session_id, session.clone());
+            cx.subscribe(&session, move |this, _, event: &SessionStateEvent, cx| {
+                if let SessionStateEvent::Shutdown = event {
+                    this.sessions.remove(&session_id);
+                    cx.emit(DapStoreEvent::DebugClientShutdown(session_id));
+                }
+            })
+            .detach();
+            cx.emit(DapStoreEvent::RemoteSessionAdded(session));
+            cx.notify();
+        })
+    }
+
+    async fn handle_debug_session_event(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::DebugSessionEvent>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let session_id = SessionId::from_proto(envelope.payload.session_id);
+        let event = serde_json::from_str::<Events>(&envelope.payload.event)
+            .context("Deserializing debug adapter event")?;
+        this.update(&mut cx, |this, cx| {
+            let Some(session) = this.sessions.get(&session_id) else {
+                return;
+            };
+            session.update(cx, |session, cx| {
+                session.handle_dap_event(Box::new(event), cx);
+            });
+        })
+    }
+
+    async fn handle_debug_session_shutdown(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::DebugSessionShutdown>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        let session_id = SessionId::from_proto(envelope.payload.session_id);
+        this.update(&mut cx, |this, cx| {
+            let Some(session) = this.sessions.get(&session_id).cloned() else {
+                return;
+            };
+            session
+                .update(cx, |session, cx| session.shutdown(cx))
+                .detach();
+        })
+    }
+
     async fn handle_run_debug_locator(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::RunDebugLocators>,
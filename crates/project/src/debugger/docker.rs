@@ -0,0 +1,312 @@
+//! Support for debugging processes that run inside Docker containers.
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use dap::{
+    adapters::{DebugAdapterBinary, TcpArguments},
+    debugger_settings::SourcePathMapping,
+};
+use gpui::SharedString;
+use serde::Deserialize;
+use std::{
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+};
+use util::command::new_smol_command;
+
+/// The key in a scenario's config naming the container its adapter should run in.
+pub const CONTAINER_CONFIG_KEY: &str = "container";
+
+/// Returns the container a debug scenario's config asks to run its adapter in, if any.
+pub fn container_for_config(config: &serde_json::Value) -> Option<&str> {
+    config
+        .get(CONTAINER_CONFIG_KEY)
+        .and_then(serde_json::Value::as_str)
+        .filter(|container| !container.is_empty())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DockerContainer {
+    pub id: String,
+    pub name: SharedString,
+    pub image: SharedString,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainerProcess {
+    /// The pid of the process within the container's pid namespace.
+    pub pid: u32,
+    pub user: String,
+    pub command: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct ContainerMount {
+    #[serde(rename = "Type")]
+    pub kind: String,
+    /// The path on the host.
+    #[serde(rename = "Source")]
+    pub source: PathBuf,
+    /// The path inside the container.
+    #[serde(rename = "Destination")]
+    pub destination: PathBuf,
+}
+
+async fn docker(args: &[&str]) -> Result<String> {
+    let output = new_smol_command("docker")
+        .args(args)
+        .output()
+        .await
+        .context("Failed to run docker, is it installed?")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "docker {} failed: {}",
+        args.first().copied().unwrap_or_default(),
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+pub async fn running_containers() -> Result<Vec<DockerContainer>> {
+    let output = docker(&["ps", "--format", "{{.ID}}\t{{.Names}}\t{{.Image}}"]).await?;
+    Ok(parse_containers(&output))
+}
+
+fn parse_containers(output: &str) -> Vec<DockerContainer> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let id = fields.next()?.trim();
+            let name = fields.next()?.trim();
+            let image = fields.next().unwrap_or_default().trim();
+            (!id.is_empty()).then(|| DockerContainer {
+                id: id.to_string(),
+                name: name.to_string().into(),
+                image: image.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+/// Lists the processes running inside a container, using the container's own `ps`.
+pub async fn container_processes(container: &str) -> Result<Vec<ContainerProcess>> {
+    let output = docker(&["exec", container, "ps", "-eo", "pid=,user=,args="]).await?;
+    Ok(parse_processes(&output))
+}
+
+fn parse_processes(output: &str) -> Vec<ContainerProcess> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let pid = fields.next()?.parse().ok()?;
+            let user = fields.next()?.to_string();
+            let command = fields.map(ToString::to_string).collect::<Vec<_>>();
+            Some(ContainerProcess { pid, user, command })
+        })
+        .collect()
+}
+
+pub async fn container_mounts(container: &str) -> Result<Vec<ContainerMount>> {
+    let output = docker(&["inspect", "--format", "{{json .Mounts}}", container]).await?;
+    serde_json::from_str(output.trim()).context("Parsing container mounts")
+}
+
+async fn container_address(container: &str) -> Result<Ipv4Addr> {
+    let output = docker(&[
+        "inspect",
+        "--format",
+        "{{range .NetworkSettings.Networks}}{{.IPAddress}} {{end}}",
+        container,
+    ])
+    .await?;
+    output
+        .split_whitespace()
+        .find_map(|address| address.parse().ok())
+        .with_context(|| format!("Container {container} has no IPv4 address"))
+}
+
+/// Maps the container side of bind mounts onto the worktrees they expose, so that sources
+/// reported by an adapter inside the container open the local files.
+pub fn source_path_mappings(
+    mounts: &[ContainerMount],
+    worktree_roots: &[&Path],
+) -> Vec<SourcePathMapping> {
+    let mut mappings = Vec::new();
+    for mount in mounts.iter().filter(|mount| mount.kind == "bind") {
+        for root in worktree_roots {
+            let (remote, local) = if let Ok(relative) = root.strip_prefix(&mount.source) {
+                (mount.destination.join(relative), root.to_path_buf())
+            } else if mount.source.starts_with(root) {
+                (mount.destination.clone(), mount.source.clone())
+            } else {
+                continue;
+            };
+            mappings.push(SourcePathMapping {
+                remote: remote.to_string_lossy().into_owned(),
+                local: local.to_string_lossy().into_owned(),
+            });
+        }
+    }
+    mappings
+}
+
+/// Rewrites an adapter binary to run inside `container`.
+pub(crate) async fn wrap_for_docker(
+    container: &str,
+    binary: DebugAdapterBinary,
+) -> Result<DebugAdapterBinary> {
+    let command = binary
+        .command
+        .context("Debug adapters that Zed doesn't start can't be run in a container")?;
+    let mut arguments = binary.arguments;
+
+    let connection = if let Some(connection) = binary.connection {
+        // The adapter has to accept connections from outside of the container.
+        let local_host = connection.host.to_string();
+        for argument in &mut arguments {
+            *argument = argument.replace(&local_host, "0.0.0.0");
+        }
+        Some(TcpArguments {
+            host: container_address(container).await?,
+            ..connection
+        })
+    } else {
+        None
+    };
+
+    let cwd = match binary.cwd {
+        Some(cwd) => container_mounts(container).await?.iter().find_map(|mount| {
+            let relative = cwd.strip_prefix(&mount.source).ok()?;
+            Some(mount.destination.join(relative))
+        }),
+        None => None,
+    };
+
+    Ok(DebugAdapterBinary {
+        command: Some("docker".into()),
+        arguments: docker_exec_arguments(container, cwd.as_deref(), &binary.envs)
+            .into_iter()
+            .chain([command])
+            .chain(arguments)
+            .collect(),
+        envs: HashMap::default(),
+        cwd: None,
+        connection,
+        request_args: binary.request_args,
+    })
+}
+
+fn docker_exec_arguments(
+    container: &str,
+    cwd: Option<&Path>,
+    envs: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut arguments = vec!["exec".to_string(), "-i".to_string()];
+    if let Some(cwd) = cwd {
+        arguments.push("-w".to_string());
+        arguments.push(cwd.to_string_lossy().into_owned());
+    }
+    let mut envs = envs.iter().collect::<Vec<_>>();
+    envs.sort();
+    for (key, value) in envs {
+        arguments.push("-e".to_string());
+        arguments.push(format!("{key}={value}"));
+    }
+    arguments.push(container.to_string());
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_docker_output() {
+        assert_eq!(
+            parse_containers("3f2a1c\tweb\tnode:20\n\n9b8e7d\tdb\tpostgres:16\n"),
+            vec![
+                DockerContainer {
+                    id: "3f2a1c".into(),
+                    name: "web".into(),
+                    image: "node:20".into(),
+                },
+                DockerContainer {
+                    id: "9b8e7d".into(),
+                    name: "db".into(),
+                    image: "postgres:16".into(),
+                },
+            ]
+        );
+
+        assert_eq!(
+            parse_processes("    1 root     node server.js --port 80\n   42 app      sleep 1\n"),
+            vec![
+                ContainerProcess {
+                    pid: 1,
+                    user: "root".into(),
+                    command: vec![
+                        "node".into(),
+                        "server.js".into(),
+                        "--port".into(),
+                        "80".into()
+                    ],
+                },
+                ContainerProcess {
+                    pid: 42,
+                    user: "app".into(),
+                    command: vec!["sleep".into(), "1".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_path_mappings_for_bind_mounts() {
+        let mounts = vec![
+            ContainerMount {
+                kind: "bind".into(),
+                source: PathBuf::from("/home/me"),
+                destination: PathBuf::from("/src"),
+            },
+            ContainerMount {
+                kind: "volume".into(),
+                source: PathBuf::from("/home/me/project/node_modules"),
+                destination: PathBuf::from("/app/node_modules"),
+            },
+            ContainerMount {
+                kind: "bind".into(),
+                source: PathBuf::from("/home/me/project/assets"),
+                destination: PathBuf::from("/assets"),
+            },
+        ];
+
+        assert_eq!(
+            source_path_mappings(&mounts, &[Path::new("/home/me/project")]),
+            vec![
+                SourcePathMapping {
+                    remote: "/src/project".into(),
+                    local: "/home/me/project".into(),
+                },
+                SourcePathMapping {
+                    remote: "/assets".into(),
+                    local: "/home/me/project/assets".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_docker_exec_arguments() {
+        let envs = HashMap::from_iter([("RUST_LOG".to_string(), "debug".to_string())]);
+        assert_eq!(
+            docker_exec_arguments("web", Some(Path::new("/src")), &envs),
+            vec!["exec", "-i", "-w", "/src", "-e", "RUST_LOG=debug", "web"]
+        );
+        assert_eq!(
+            container_for_config(&serde_json::json!({ "container": "web", "processId": 1 })),
+            Some("web")
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Scenarios that launch several programs at once.
+
+use anyhow::{Context as _, Result};
+use fs::Fs;
+use futures::StreamExt as _;
+use std::path::{Path, PathBuf};
+use task::DebugScenario;
+use util::paths::PathMatcher;
+
+/// The key in a launch scenario's config listing globs of the programs to launch.
+pub const PROGRAMS_CONFIG_KEY: &str = "programs";
+
+/// The key in a scenario's config listing configurations for other adapters that debug the same
+/// program.
+pub const COUPLED_CONFIG_KEY: &str = "coupled";
+
+/// Returns the globs a scenario's config lists its programs with, if it has several targets.
+pub fn program_globs(config: &serde_json::Value) -> Option<Vec<&str>> {
+    match config.get(PROGRAMS_CONFIG_KEY)? {
+        serde_json::Value::String(glob) => Some(vec![glob.as_str()]),
+        serde_json::Value::Array(globs) => {
+            Some(globs.iter().filter_map(serde_json::Value::as_str).collect())
+        }
+        _ => None,
+    }
+}
+
+/// Expands a scenario with several targets into one scenario per program matching its globs,
+/// which are relative to `root` unless they're absolute.
+pub async fn expand_targets(
+    scenario: &DebugScenario,
+    root: &Path,
+    fs: &dyn Fs,
+) -> Result<Vec<DebugScenario>> {
+    let globs = program_globs(&scenario.config)
+        .with_context(|| format!("`{PROGRAMS_CONFIG_KEY}` has to be a glob or a list of globs"))?;
+    // The programs are looked up before any session starts, so there's no build to wait for.
+    anyhow::ensure!(
+        scenario.build.is_none(),
+        "Scenarios with `{PROGRAMS_CONFIG_KEY}` can't have a `build` task, build the programs beforehand"
+    );
+
+    let globs = globs
+        .into_iter()
+        .map(|glob| root.join(glob))
+        .collect::<Vec<_>>();
+    let matcher = PathMatcher::new(globs.iter().map(|glob| glob.to_string_lossy()))?;
+    // Without `**`, no program is nested deeper than its glob.
+    let max_depth = globs
+        .iter()
+        .map(|glob| {
+            if glob.to_string_lossy().contains("**") {
+                usize::MAX
+            } else {
+                glob.components().count()
+            }
+        })
+        .max()
+        .unwrap_or_default();
+
+    let mut programs = Vec::new();
+    let mut dirs = globs
+        .iter()
+        .map(|glob| literal_prefix(glob))
+        .collect::<Vec<_>>();
+    dirs.sort();
+    dirs.dedup_by(|dir, parent| dir.starts_with(parent));
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs.read_dir(&dir).await?;
+        while let Some(path) = entries.next().await {
+            let path = path?;
+            let Some(metadata) = fs.metadata(&path).await? else {
+                continue;
+            };
+            if metadata.is_dir {
+                if !metadata.is_symlink && path.components().count() < max_depth {
+                    dirs.push(path);
+                }
+            } else if matcher.is_match(&path) {
+                programs.push(path);
+            }
+        }
+    }
+    anyhow::ensure!(!programs.is_empty(), "No programs match {globs:?}");
+    programs.sort();
+
+    Ok(programs
+        .into_iter()
+        .map(|program| target_scenario(scenario, &program))
+        .collect())
+}
+
+/// Splits a mixed-mode scenario into its own scenario and the scenarios coupled to it, each of
+/// which names its `adapter` and optionally its `label` alongside its configuration.
+pub fn split_coupled(scenario: &DebugScenario) -> Result<(DebugScenario, Vec<DebugScenario>)> {
+    let mut config = scenario.config.clone();
+    let coupled = config
+        .as_object_mut()
+        .and_then(|config| config.remove(COUPLED_CONFIG_KEY));
+    let Some(serde_json::Value::Array(coupled)) = coupled else {
+        anyhow::bail!("`{COUPLED_CONFIG_KEY}` has to be a list of configurations");
+    };
+    anyhow::ensure!(
+        !coupled.is_empty(),
+        "`{COUPLED_CONFIG_KEY}` has to list at least one configuration"
+    );
+
+    let coupled = coupled
+        .into_iter()
+        .map(|config| {
+            let serde_json::Value::Object(mut config) = config else {
+                anyhow::bail!("Each of `{COUPLED_CONFIG_KEY}`'s entries has to be a configuration");
+            };
+            let Some(serde_json::Value::String(adapter)) = config.remove("adapter") else {
+                anyhow::bail!(
+                    "Each of `{COUPLED_CONFIG_KEY}`'s configurations has to name an `adapter`"
+                );
+            };
+            let label = match config.remove("label") {
+                Some(serde_json::Value::String(label)) => label,
+                _ => format!("{}: {adapter}", scenario.label),
+            };
+            Ok(DebugScenario {
+                adapter: adapter.into(),
+                label: label.into(),
+                build: None,
+                config: serde_json::Value::Object(config),
+                tcp_connection: None,
+                wait_for: None,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let primary = DebugScenario {
+        label: format!("{}: {}", scenario.label, scenario.adapter).into(),
+        config,
+        ..scenario.clone()
+    };
+    Ok((primary, coupled))
+}
+
+/// The directory a glob's matches are all in.
+fn literal_prefix(glob: &Path) -> PathBuf {
+    let prefix = glob
+        .components()
+        .take_while(|component| {
+            !component
+                .as_os_str()
+                .to_string_lossy()
+                .contains(['*', '?', '[', '{'])
+        })
+        .collect::<PathBuf>();
+    // A glob without wildcards matches a single file, which is looked up in its directory.
+    if prefix == glob {
+        prefix.parent().map(Path::to_path_buf).unwrap_or(prefix)
+    } else {
+        prefix
+    }
+}
+
+fn target_scenario(scenario: &DebugScenario, program: &Path) -> DebugScenario {
+    let mut config = scenario.config.clone();
+    if let Some(config) = config.as_object_mut() {
+        config.remove(PROGRAMS_CONFIG_KEY);
+        config.insert(
+            "program".into(),
+            program.to_string_lossy().into_owned().into(),
+        );
+    }
+    let name = program
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_else(|| program.to_string_lossy());
+    DebugScenario {
+        label: format!("{}: {name}", scenario.label).into(),
+        config,
+        ..scenario.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use util::path;
+
+    #[gpui::test]
+    async fn test_expand_targets(cx: &mut TestAppContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "target": {
+                    "debug": {
+                        "api-server": "",
+                        "api-server.d": "",
+                        "jobs-server": "",
+                        "cli": "",
+                        "deps": {
+                            "old-server": "",
+                        },
+                    },
+                },
+            }),
+        )
+        .await;
+
+        let scenario = DebugScenario {
+            adapter: "CodeLLDB".into(),
+            label: "Servers".into(),
+            build: None,
+            config: json!({
+                "request": "launch",
+                "programs": "target/debug/*-server",
+                "args": ["--verbose"],
+            }),
+            tcp_connection: None,
+            wait_for: None,
+        };
+        let targets = expand_targets(&scenario, Path::new(path!("/project")), fs.as_ref())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            targets
+                .iter()
+                .map(|target| (target.label.to_string(), target.config.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "Servers: api-server".to_string(),
+                    json!({
+                        "request": "launch",
+                        "program": path!("/project/target/debug/api-server"),
+                        "args": ["--verbose"],
+                    })
+                ),
+                (
+                    "Servers: jobs-server".to_string(),
+                    json!({
+                        "request": "launch",
+                        "program": path!("/project/target/debug/jobs-server"),
+                        "args": ["--verbose"],
+                    })
+                ),
+            ]
+        );
+
+        let nothing = DebugScenario {
+            config: json!({ "request": "launch", "programs": ["target/release/*"] }),
+            ..scenario
+        };
+        assert!(
+            expand_targets(&nothing, Path::new(path!("/project")), fs.as_ref())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_split_coupled() {
+        let scenario = DebugScenario {
+            adapter: "Debugpy".into(),
+            label: "Python + native".into(),
+            build: None,
+            config: json!({
+                "request": "launch",
+                "program": "main.py",
+                "coupled": [
+                    { "adapter": "CodeLLDB", "request": "attach", "pid": "${pid}" },
+                    { "adapter": "GDB", "label": "GDB", "request": "attach" },
+                ],
+            }),
+            tcp_connection: None,
+            wait_for: None,
+        };
+        let (primary, coupled) = split_coupled(&scenario).unwrap();
+
+        assert_eq!(primary.label.as_ref(), "Python + native: Debugpy");
+        assert_eq!(
+            primary.config,
+            json!({ "request": "launch", "program": "main.py" })
+        );
+        assert_eq!(
+            coupled
+                .iter()
+                .map(|scenario| (
+                    scenario.adapter.to_string(),
+                    scenario.label.to_string(),
+                    scenario.config.clone()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "CodeLLDB".to_string(),
+                    "Python + native: CodeLLDB".to_string(),
+                    json!({ "request": "attach", "pid": "${pid}" })
+                ),
+                (
+                    "GDB".to_string(),
+                    "GDB".to_string(),
+                    json!({ "request": "attach" })
+                ),
+            ]
+        );
+
+        let without_adapter = DebugScenario {
+            config: json!({ "request": "launch", "coupled": [{ "request": "attach" }] }),
+            ..scenario
+        };
+        assert!(split_coupled(&without_adapter).is_err());
+    }
+}
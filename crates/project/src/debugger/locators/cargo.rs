@@ -36,6 +36,37 @@ async fn find_best_executable(executables: &[String], test_name: &str) -> Option
     }
     None
 }
+
+/// Nextest runs each test in a process of its own, which can't be debugged through it, so
+/// `cargo nextest run` is turned into the `cargo test` that builds the same test binaries.
+fn cargo_test_args_for_nextest(args: &[String]) -> Option<Vec<String>> {
+    let mut args = args.iter().skip(1);
+    if !matches!(args.next()?.as_str(), "run" | "r") {
+        return None;
+    }
+
+    let mut test_args = vec!["test".to_owned()];
+    let mut skip_value = false;
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            test_args.push(arg.clone());
+            test_args.extend(args.cloned());
+            break;
+        }
+        if std::mem::take(&mut skip_value) {
+            continue;
+        }
+        let flag = arg.split_once('=').map_or(arg.as_str(), |(flag, _)| flag);
+        match flag {
+            "--no-capture" | "--nocapture" => {}
+            "-P" | "--profile" | "--retries" | "-E" | "--filterset" | "--filter-expr" | "-j"
+            | "--test-threads" => skip_value = !arg.contains('='),
+            _ => test_args.push(arg.clone()),
+        }
+    }
+    Some(test_args)
+}
+
 #[async_trait]
 impl DapLocator for CargoLocator {
     fn name(&self) -> SharedString {
@@ -51,6 +82,13 @@ impl DapLocator for CargoLocator {
             return None;
         }
         let mut task_template = build_config.clone();
+        if task_template
+            .args
+            .first()
+            .is_some_and(|arg| arg == "nextest")
+        {
+            task_template.args = cargo_test_args_for_nextest(&task_template.args)?;
+        }
         let cargo_action = task_template.args.first_mut()?;
         if cargo_action == "check" || cargo_action == "clean" {
             return None;
@@ -85,6 +123,7 @@ impl DapLocator for CargoLocator {
             }),
             config: serde_json::Value::Null,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -177,3 +216,60 @@ impl DapLocator for CargoLocator {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[test]
+    fn test_cargo_test_args_for_nextest() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            cargo_test_args_for_nextest(&args(&[
+                "nextest",
+                "run",
+                "-p",
+                "editor",
+                "--profile",
+                "ci",
+                "--no-capture",
+                "-E=test(foo)",
+                "tests::bar",
+                "--",
+                "--exact",
+            ])),
+            Some(args(&[
+                "test",
+                "-p",
+                "editor",
+                "tests::bar",
+                "--",
+                "--exact"
+            ]))
+        );
+        assert_eq!(
+            cargo_test_args_for_nextest(&args(&["nextest", "list"])),
+            None
+        );
+    }
+
+    #[gpui::test]
+    async fn test_nextest_scenario_builds_with_cargo_test(_: &mut TestAppContext) {
+        let task = TaskTemplate {
+            label: "nextest".into(),
+            command: "cargo".into(),
+            args: vec!["nextest".into(), "run".into(), "tests::bar".into()],
+            ..TaskTemplate::default()
+        };
+        let scenario = CargoLocator
+            .create_scenario(&task, "nextest", &DebugAdapterName("CodeLLDB".into()))
+            .await
+            .unwrap();
+        let Some(BuildTaskDefinition::Template { task_template, .. }) = scenario.build else {
+            panic!("expected a build task");
+        };
+        assert_eq!(task_template.args, ["test", "tests::bar", "--no-run"]);
+    }
+}
@@ -174,6 +174,7 @@ impl DapLocator for GoLocator {
                     build: None,
                     config: config,
                     tcp_connection: None,
+                    wait_for: None,
                 })
             }
             "run" => {
@@ -218,6 +219,7 @@ impl DapLocator for GoLocator {
                     build: None,
                     config,
                     tcp_connection: None,
+                    wait_for: None,
                 })
             }
             _ => None,
@@ -35,10 +35,22 @@ impl DapLocator for NodeLocator {
             return None;
         }
 
+        // Task arguments are quoted for a shell, like jest's `--testNamePattern "name"`, but
+        // the adapter passes them to the runtime as they are.
+        let args = build_config
+            .args
+            .iter()
+            .map(|arg| {
+                arg.strip_prefix('"')
+                    .and_then(|arg| arg.strip_suffix('"'))
+                    .unwrap_or(arg)
+                    .to_owned()
+            })
+            .collect::<Vec<_>>();
         let config = serde_json::json!({
             "request": "launch",
             "type": "pwa-node",
-            "args": build_config.args.clone(),
+            "args": args,
             "cwd": build_config.cwd.clone(),
             "runtimeExecutable": build_config.command.clone(),
             "env": build_config.env.clone(),
@@ -52,6 +64,7 @@ impl DapLocator for NodeLocator {
             build: None,
             config,
             tcp_connection: None,
+            wait_for: None,
         })
     }
 
@@ -59,3 +72,43 @@ impl DapLocator for NodeLocator {
         bail!("JavaScript locator should not require DapLocator::run to be ran");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    async fn test_jest_test_name_is_unquoted(_: &mut TestAppContext) {
+        let task = TaskTemplate {
+            label: "jest test".into(),
+            command: TYPESCRIPT_RUNNER_VARIABLE.template_value(),
+            args: vec![
+                "exec".into(),
+                "--".into(),
+                "jest".into(),
+                "--runInBand".into(),
+                "--testNamePattern".into(),
+                "\"adds numbers\"".into(),
+                "sum.test.ts".into(),
+            ],
+            ..TaskTemplate::default()
+        };
+        let scenario = NodeLocator
+            .create_scenario(&task, "jest test", &DebugAdapterName("JavaScript".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            scenario.config["args"],
+            serde_json::json!([
+                "exec",
+                "--",
+                "jest",
+                "--runInBand",
+                "--testNamePattern",
+                "adds numbers",
+                "sum.test.ts"
+            ])
+        );
+    }
+}
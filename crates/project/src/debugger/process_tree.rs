@@ -0,0 +1,128 @@
+//! Cleanup of the processes a debuggee spawned, for launch scenarios that ask for it.
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use util::command::new_smol_command;
+
+/// The key in a launch scenario's config asking for the debuggee's children to be killed with it.
+pub const KILL_PROCESS_TREE_CONFIG_KEY: &str = "killProcessTree";
+
+pub fn kills_process_tree(config: &serde_json::Value) -> bool {
+    config
+        .get(KILL_PROCESS_TREE_CONFIG_KEY)
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or_default()
+}
+
+/// Lists every process descending from `pid`, children before their own children.
+pub(crate) async fn descendants(pid: u32) -> Result<Vec<u32>> {
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = new_smol_command("powershell");
+        command.args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Process | ForEach-Object { \"$($_.ProcessId) $($_.ParentProcessId)\" }",
+        ]);
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = new_smol_command("ps");
+        command.args(["-A", "-o", "pid=,ppid="]);
+        command
+    };
+
+    let output = command.output().await.context("Listing processes")?;
+    anyhow::ensure!(
+        output.status.success(),
+        "Listing processes failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    Ok(descendants_in(
+        &parse_parents(&String::from_utf8_lossy(&output.stdout)),
+        pid,
+    ))
+}
+
+/// Parses lines of `<pid> <parent pid>` into a map from parents to their children.
+fn parse_parents(output: &str) -> HashMap<u32, Vec<u32>> {
+    let mut children = HashMap::<u32, Vec<u32>>::default();
+    for line in output.lines() {
+        let mut fields = line.split_whitespace();
+        let (Some(Ok(pid)), Some(Ok(parent))) = (
+            fields.next().map(str::parse::<u32>),
+            fields.next().map(str::parse::<u32>),
+        ) else {
+            continue;
+        };
+        if pid != parent {
+            children.entry(parent).or_default().push(pid);
+        }
+    }
+    children
+}
+
+fn descendants_in(children: &HashMap<u32, Vec<u32>>, pid: u32) -> Vec<u32> {
+    let mut descendants = children.get(&pid).cloned().unwrap_or_default();
+    let mut ix = 0;
+    while let Some(pid) = descendants.get(ix).copied() {
+        for grandchild in children.get(&pid).into_iter().flatten() {
+            if !descendants.contains(grandchild) {
+                descendants.push(*grandchild);
+            }
+        }
+        ix += 1;
+    }
+    descendants
+}
+
+/// Kills the given processes. Processes that already exited are skipped.
+pub(crate) async fn kill(pids: &[u32]) -> Result<()> {
+    if pids.is_empty() {
+        return Ok(());
+    }
+
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = new_smol_command("taskkill");
+        command.arg("/F");
+        for pid in pids {
+            command.args(["/PID", &pid.to_string()]);
+        }
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = new_smol_command("kill");
+        command
+            .arg("-KILL")
+            .args(pids.iter().map(ToString::to_string));
+        command
+    };
+
+    // Some of the processes may have exited with the debuggee, so failures aren't errors.
+    command.output().await.context("Killing processes")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_descendants_of_process() {
+        let children = parse_parents(
+            "    1     0\n  100     1\n  101   100\n  102   100\n  103   101\n  200     1\n  bad line\n",
+        );
+
+        assert_eq!(descendants_in(&children, 100), vec![101, 102, 103]);
+        assert_eq!(descendants_in(&children, 103), Vec::<u32>::new());
+        assert!(kills_process_tree(
+            &serde_json::json!({ "program": "./server", "killProcessTree": true })
+        ));
+        assert!(!kills_process_tree(
+            &serde_json::json!({ "program": "./server" })
+        ));
+    }
+}
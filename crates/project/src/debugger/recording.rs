@@ -0,0 +1,325 @@
+//! Recording a debug session's DAP traffic to a file, and replaying it as a read-only session.
+
+use super::dap_command::LocalDapCommand;
+use anyhow::{Context as _, Result, anyhow};
+use dap::{Capabilities, messages::Events, requests::Request};
+use futures::{
+    AsyncWriteExt as _, StreamExt as _,
+    channel::mpsc::{self, UnboundedSender},
+};
+use gpui::{App, BackgroundExecutor, Task};
+use serde::{Deserialize, Serialize};
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use util::ResultExt as _;
+
+/// Requests that don't change the state of the debuggee, which are answered from the recording
+/// when replaying it.
+const REPLAYABLE_COMMANDS: &[&str] = &[
+    "threads",
+    "stackTrace",
+    "scopes",
+    "variables",
+    "modules",
+    "loadedSources",
+    "source",
+    "evaluate",
+    "completions",
+    "exceptionInfo",
+    "readMemory",
+    "disassemble",
+    "breakpointLocations",
+    "locations",
+];
+
+/// The longest pause between two replayed events, so that idle time isn't replayed verbatim.
+const MAX_REPLAY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordedMessage {
+    Session {
+        label: String,
+        adapter: String,
+        capabilities: Capabilities,
+    },
+    Request {
+        elapsed_ms: u64,
+        command: String,
+        arguments: serde_json::Value,
+        success: bool,
+        /// The response's body, or its error message if it failed.
+        body: serde_json::Value,
+    },
+    Event {
+        elapsed_ms: u64,
+        event: Events,
+    },
+}
+
+/// Appends the DAP traffic of a session to a recording file.
+#[derive(Clone)]
+pub struct DapRecorder {
+    path: PathBuf,
+    started_at: Instant,
+    tx: UnboundedSender<RecordedMessage>,
+}
+
+impl DapRecorder {
+    /// Starts writing a recording to `path`. The file is complete once every clone of the
+    /// recorder is dropped.
+    pub(crate) fn new(path: PathBuf, header: RecordedMessage, cx: &App) -> Self {
+        let (tx, mut rx) = mpsc::unbounded::<RecordedMessage>();
+        tx.unbounded_send(header).ok();
+        cx.background_spawn({
+            let path = path.clone();
+            async move {
+                let Some(mut file) = smol::fs::File::create(&path).await.log_err() else {
+                    return;
+                };
+                while let Some(message) = rx.next().await {
+                    let Some(mut line) = serde_json::to_vec(&message).log_err() else {
+                        continue;
+                    };
+                    line.push(b'\n');
+                    if file.write_all(&line).await.log_err().is_none() {
+                        return;
+                    }
+                }
+                file.flush().await.log_err();
+            }
+        })
+        .detach();
+
+        Self {
+            path,
+            started_at: Instant::now(),
+            tx,
+        }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started_at.elapsed().as_millis() as u64
+    }
+
+    pub(crate) fn record_response(
+        &self,
+        command: &str,
+        arguments: serde_json::Value,
+        response: &Result<serde_json::Value>,
+    ) {
+        let (success, body) = match response {
+            Ok(body) => (true, body.clone()),
+            Err(error) => (false, error.to_string().into()),
+        };
+        self.tx
+            .unbounded_send(RecordedMessage::Request {
+                elapsed_ms: self.elapsed_ms(),
+                command: command.to_string(),
+                arguments,
+                success,
+                body,
+            })
+            .ok();
+    }
+
+    pub(crate) fn record_event(&self, event: &Events) {
+        self.tx
+            .unbounded_send(RecordedMessage::Event {
+                elapsed_ms: self.elapsed_ms(),
+                event: event.clone(),
+            })
+            .ok();
+    }
+}
+
+pub struct DapRecording {
+    pub label: String,
+    pub adapter: String,
+    pub capabilities: Capabilities,
+    messages: Vec<RecordedMessage>,
+}
+
+impl DapRecording {
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut messages = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str::<RecordedMessage>);
+        let Some(RecordedMessage::Session {
+            label,
+            adapter,
+            capabilities,
+        }) = messages.next().transpose()?
+        else {
+            anyhow::bail!("Debug session recordings have to start with a session header");
+        };
+
+        Ok(Self {
+            label,
+            adapter,
+            capabilities,
+            messages: messages
+                .collect::<Result<_, _>>()
+                .context("Parsing debug session recording")?,
+        })
+    }
+
+    /// The recorded events, with the time they were received at.
+    pub(crate) fn events(&self) -> impl Iterator<Item = (u64, &Events)> {
+        self.messages.iter().filter_map(|message| match message {
+            RecordedMessage::Event { elapsed_ms, event } => Some((*elapsed_ms, event)),
+            _ => None,
+        })
+    }
+
+    /// The response to a request with the same arguments. Prefers the first one recorded between
+    /// `position` and the next event, as it reflects the state the replay is in, then the latest
+    /// one recorded before `position`.
+    fn response(
+        &self,
+        command: &str,
+        arguments: &serde_json::Value,
+        position: u64,
+    ) -> Option<(bool, &serde_json::Value)> {
+        let next_event_ms = self
+            .events()
+            .map(|(elapsed_ms, _)| elapsed_ms)
+            .find(|elapsed_ms| *elapsed_ms > position)
+            .unwrap_or(u64::MAX);
+        let matching = self
+            .messages
+            .iter()
+            .filter_map(|message| match message {
+                RecordedMessage::Request {
+                    elapsed_ms,
+                    command: recorded_command,
+                    arguments: recorded_arguments,
+                    success,
+                    body,
+                } if recorded_command == command && recorded_arguments == arguments => {
+                    Some((*elapsed_ms, *success, body))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let (_, success, body) = matching
+            .iter()
+            .find(|(elapsed_ms, _, _)| (position..next_event_ms).contains(elapsed_ms))
+            .or_else(|| {
+                matching
+                    .iter()
+                    .rev()
+                    .find(|(elapsed_ms, _, _)| *elapsed_ms < position)
+            })
+            .or_else(|| matching.first())?;
+        Some((*success, body))
+    }
+}
+
+/// A session that is replayed from a recording rather than backed by a debug adapter.
+#[derive(Clone)]
+pub struct ReplayMode {
+    recording: Arc<DapRecording>,
+    /// How far into the recording the replay is, in milliseconds.
+    position: Arc<AtomicU64>,
+    executor: BackgroundExecutor,
+}
+
+impl ReplayMode {
+    pub(crate) fn new(recording: Arc<DapRecording>, executor: BackgroundExecutor) -> Self {
+        Self {
+            recording,
+            position: Default::default(),
+            executor,
+        }
+    }
+
+    pub(crate) fn recording(&self) -> &Arc<DapRecording> {
+        &self.recording
+    }
+
+    pub(crate) fn set_position(&self, elapsed_ms: u64) {
+        self.position.store(elapsed_ms, Ordering::Relaxed);
+    }
+
+    pub(crate) fn request<R: LocalDapCommand>(&self, request: R) -> Task<Result<R::Response>> {
+        let command = R::DapRequest::COMMAND;
+        if !REPLAYABLE_COMMANDS.contains(&command) {
+            return Task::ready(Err(anyhow!("Replayed debug sessions are read-only")));
+        }
+        let recording = self.recording.clone();
+        let position = self.position.load(Ordering::Relaxed);
+        self.executor.spawn(async move {
+            let arguments = serde_json::to_value(request.to_dap())?;
+            let (success, body) = recording
+                .response(command, &arguments, position)
+                .with_context(|| format!("The recording has no response to `{command}`"))?;
+            anyhow::ensure!(success, "{}", body.as_str().unwrap_or("Request failed"));
+            let response = serde_json::from_value(body.clone())?;
+            request.response_from_dap(response)
+        })
+    }
+}
+
+/// How long to wait before replaying an event recorded `elapsed_ms` into the session, after the
+/// previous one recorded at `previous_ms`.
+pub(crate) fn replay_delay(previous_ms: u64, elapsed_ms: u64) -> Duration {
+    Duration::from_millis(elapsed_ms.saturating_sub(previous_ms)).min(MAX_REPLAY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_responses_follow_the_replay() {
+        let recording = DapRecording::parse(
+            r#"
+{"kind":"session","label":"Debug main","adapter":"CodeLLDB","capabilities":{}}
+{"kind":"request","elapsed_ms":10,"command":"threads","arguments":null,"success":true,"body":{"threads":[{"id":1,"name":"main"}]}}
+{"kind":"event","elapsed_ms":20,"event":{"event":"stopped","body":{"reason":"breakpoint","threadId":1}}}
+{"kind":"request","elapsed_ms":30,"command":"threads","arguments":null,"success":true,"body":{"threads":[{"id":1,"name":"main"},{"id":2,"name":"worker"}]}}
+{"kind":"request","elapsed_ms":40,"command":"scopes","arguments":{"frameId":7},"success":false,"body":"No such frame"}
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(recording.label, "Debug main");
+        assert_eq!(recording.events().count(), 1);
+
+        let thread_count = |position| {
+            recording
+                .response("threads", &serde_json::Value::Null, position)
+                .and_then(|(_, body)| body["threads"].as_array().map(Vec::len))
+        };
+        assert_eq!(thread_count(0), Some(1));
+        assert_eq!(thread_count(20), Some(2));
+        assert_eq!(thread_count(25), Some(2));
+        assert_eq!(thread_count(35), Some(2));
+
+        let (success, body) = recording
+            .response("scopes", &serde_json::json!({ "frameId": 7 }), 50)
+            .unwrap();
+        assert!(!success);
+        assert_eq!(body, "No such frame");
+        assert!(
+            recording
+                .response("scopes", &serde_json::json!({ "frameId": 8 }), 50)
+                .is_none()
+        );
+
+        assert_eq!(replay_delay(20, 30), Duration::from_millis(10));
+        assert_eq!(replay_delay(0, 60_000), MAX_REPLAY_DELAY);
+    }
+}
@@ -4,27 +4,36 @@ use super::breakpoint_store::{
     BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
-    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
-    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, CancelCommand, ConfigurationDone, ContinueCommand, DapCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, ExceptionInfoCommand, GotoCommand,
+    GotoTargetsCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand,
+    LocationsCommand, ModulesCommand, NextCommand, PauseCommand, ReadMemoryCommand, RestartCommand,
+    RestartStackFrameCommand, ReverseContinueCommand, ScopesCommand, SetExceptionBreakpoints,
+    SetExpressionCommand, SetInstructionBreakpoints, SetVariableValueCommand, SourceCommand,
+    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
+    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    WriteMemoryCommand,
 };
 use super::dap_store::DapStore;
+use super::process_tree;
+use super::recording::{DapRecorder, DapRecording, RecordedMessage, ReplayMode, replay_delay};
+use super::wsl;
 use anyhow::{Context as _, Result, anyhow};
 use collections::{HashMap, HashSet, IndexMap};
 use dap::adapters::{DebugAdapterBinary, DebugAdapterName};
+use dap::debugger_settings::{DebuggerSettings, SourcePathMapping};
 use dap::messages::Response;
 use dap::requests::{Request, RunInTerminal, StartDebugging};
 use dap::{
-    Capabilities, ContinueArguments, EvaluateArgumentsContext, Module, Source, StackFrameId,
-    SteppingGranularity, StoppedEvent, VariableReference,
+    Capabilities, ContinueArguments, EvaluateArgumentsContext, Module, ReverseContinueArguments,
+    Source, StackFrameId, SteppingGranularity, StoppedEvent, VariableReference,
+    VariablesArgumentsFilter,
     client::{DebugAdapterClient, SessionId},
     messages::{Events, Message},
+    transport::IoHandler,
 };
 use dap::{
-    ExceptionBreakpointsFilter, ExceptionFilterOptions, OutputEvent, OutputEventCategory,
+    DapRegistry, ExceptionBreakpointsFilter, ExceptionFilterOptions, OutputEvent,
     RunInTerminalRequestArguments, StackFramePresentationHint, StartDebuggingRequestArguments,
     StartDebuggingRequestArgumentsRequest,
 };
@@ -36,9 +45,11 @@ use gpui::{
     App, AppContext, AsyncApp, BackgroundExecutor, Context, Entity, EventEmitter, SharedString,
     Task, WeakEntity,
 };
+use parking_lot::Mutex;
 
-use rpc::ErrorExt;
+use rpc::{AnyProtoClient, ErrorExt, proto};
 use serde_json::Value;
+use settings::{Settings as _, SettingsStore};
 use smol::stream::StreamExt;
 use std::any::TypeId;
 use std::collections::BTreeMap;
@@ -47,8 +58,10 @@ use std::{
     any::Any,
     collections::hash_map::Entry,
     hash::{Hash, Hasher},
-    path::Path,
+    ops::Range,
+    path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 use task::TaskContext;
 use text::{PointUtf16, ToPointUtf16};
@@ -112,6 +125,8 @@ pub struct Thread {
     dap: dap::Thread,
     stack_frames: Vec<StackFrame>,
     stack_frames_error: Option<anyhow::Error>,
+    /// Whether the adapter may have frames past the ones we've fetched, see [`Session::load_more_stack_frames`].
+    has_more_stack_frames: bool,
     _has_stopped: bool,
 }
 
@@ -121,6 +136,7 @@ impl From<dap::Thread> for Thread {
             dap,
             stack_frames: Default::default(),
             stack_frames_error: None,
+            has_more_stack_frames: false,
             _has_stopped: false,
         }
     }
@@ -129,6 +145,29 @@ impl From<dap::Thread> for Thread {
 pub enum Mode {
     Building,
     Running(RunningMode),
+    /// A session running on the host of a shared project, driven over the collab protocol.
+    Remote(RemoteMode),
+    /// A read-only session replayed from a recording of another session's DAP traffic.
+    Replay(ReplayMode),
+}
+
+#[derive(Clone)]
+pub struct RemoteMode {
+    session_id: SessionId,
+    upstream_client: AnyProtoClient,
+    upstream_project_id: u64,
+    executor: BackgroundExecutor,
+}
+
+impl RemoteMode {
+    fn request<R: DapCommand>(&self, request: R) -> Task<Result<R::Response>> {
+        let message = request.to_proto(self.session_id, self.upstream_project_id);
+        let response = self.upstream_client.request(message);
+        self.executor.spawn(async move {
+            let response = response.await?;
+            request.response_from_proto(response)
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -141,14 +180,23 @@ pub struct RunningMode {
     is_started: bool,
     has_ever_stopped: bool,
     messages_tx: UnboundedSender<Message>,
+    /// The WSL distro the adapter runs in, when debugging a worktree inside of one.
+    wsl_distro: Option<String>,
+    recorder: Arc<Mutex<Option<DapRecorder>>>,
 }
 
-fn client_source(abs_path: &Path) -> dap::Source {
+fn client_source(abs_path: &Path, wsl_distro: Option<&str>, cx: &App) -> dap::Source {
+    let mut path = DebuggerSettings::get_global(cx).remote_source_path(abs_path);
+    if wsl_distro.is_some() {
+        if let Some((_, linux_path)) = wsl::wsl_path(&path) {
+            path = PathBuf::from(linux_path);
+        }
+    }
     dap::Source {
         name: abs_path
             .file_name()
             .map(|filename| filename.to_string_lossy().to_string()),
-        path: Some(abs_path.to_string_lossy().to_string()),
+        path: Some(path.to_string_lossy().to_string()),
         source_reference: None,
         presentation_hint: None,
         origin: None,
@@ -165,6 +213,7 @@ impl RunningMode {
         worktree: WeakEntity<Worktree>,
         binary: DebugAdapterBinary,
         messages_tx: futures::channel::mpsc::UnboundedSender<Message>,
+        adapter_log_handler: IoHandler,
         cx: &mut AsyncApp,
     ) -> Result<Self> {
         let message_handler = Box::new({
@@ -179,15 +228,35 @@ impl RunningMode {
             .flatten()
         {
             client
-                .create_child_connection(session_id, binary.clone(), message_handler, cx)
+                .create_child_connection(
+                    session_id,
+                    binary.clone(),
+                    message_handler,
+                    Some(adapter_log_handler),
+                    cx,
+                )
                 .await?
         } else {
-            DebugAdapterClient::start(session_id, binary.clone(), message_handler, cx).await?
+            DebugAdapterClient::start(
+                session_id,
+                binary.clone(),
+                message_handler,
+                Some(adapter_log_handler),
+                cx,
+            )
+            .await?
         };
+        let wsl_distro = worktree
+            .read_with(cx, |worktree, _| wsl::wsl_path(&worktree.abs_path()))
+            .ok()
+            .flatten()
+            .map(|(distro, _)| distro);
 
         Ok(Self {
             client: Arc::new(client),
             worktree,
+            wsl_distro,
+            recorder: Default::default(),
             tmp_breakpoint: None,
             binary,
             executor: cx.background_executor().clone(),
@@ -206,7 +275,7 @@ impl RunningMode {
             .into_iter()
             .map(|path| {
                 self.request(dap_command::SetBreakpoints {
-                    source: client_source(path),
+                    source: client_source(path, self.wsl_distro.as_deref(), cx),
                     source_modified: None,
                     breakpoints: vec![],
                 })
@@ -231,56 +300,136 @@ impl RunningMode {
         abs_path: Arc<Path>,
         reason: BreakpointUpdatedReason,
         breakpoint_store: &Entity<BreakpointStore>,
+        snap_to_valid_locations: bool,
         cx: &mut App,
     ) -> Task<()> {
-        let breakpoints =
-            breakpoint_store
-                .read(cx)
-                .source_breakpoints_from_path(&abs_path, cx)
-                .into_iter()
-                .filter(|bp| bp.state.is_enabled())
-                .chain(self.tmp_breakpoint.iter().filter_map(|breakpoint| {
-                    breakpoint.path.eq(&abs_path).then(|| breakpoint.clone())
-                }))
-                .map(Into::into)
-                .collect();
-
-        let raw_breakpoints = breakpoint_store
-            .read(cx)
-            .breakpoints_from_path(&abs_path)
-            .into_iter()
-            .filter(|bp| bp.bp.state.is_enabled())
-            .collect::<Vec<_>>();
-
-        let task = self.request(dap_command::SetBreakpoints {
-            source: client_source(&abs_path),
-            source_modified: Some(matches!(reason, BreakpointUpdatedReason::FileSaved)),
-            breakpoints,
+        let snap_task = snap_to_valid_locations.then(|| {
+            self.snap_breakpoints_to_valid_locations([abs_path.clone()], breakpoint_store, cx)
         });
+        let this = self.clone();
         let session_id = self.client.id();
-        let breakpoint_store = breakpoint_store.downgrade();
-        cx.spawn(async move |cx| match cx.background_spawn(task).await {
-            Ok(breakpoints) => {
-                let breakpoints =
-                    breakpoints
-                        .into_iter()
-                        .zip(raw_breakpoints)
-                        .filter_map(|(dap_bp, zed_bp)| {
+        let breakpoint_store = breakpoint_store.clone();
+        cx.spawn(async move |cx| {
+            if let Some(snap_task) = snap_task {
+                snap_task.await;
+            }
+
+            let Ok((breakpoints, raw_breakpoints)) = breakpoint_store.read_with(cx, |store, cx| {
+                let breakpoints = store
+                    .source_breakpoints_from_path(&abs_path, cx)
+                    .into_iter()
+                    .filter(|bp| bp.state.is_enabled())
+                    .chain(this.tmp_breakpoint.iter().filter_map(|breakpoint| {
+                        breakpoint.path.eq(&abs_path).then(|| breakpoint.clone())
+                    }))
+                    .map(Into::into)
+                    .collect();
+
+                let raw_breakpoints = store
+                    .breakpoints_from_path(&abs_path)
+                    .into_iter()
+                    .filter(|bp| bp.bp.state.is_enabled())
+                    .collect::<Vec<_>>();
+
+                (breakpoints, raw_breakpoints)
+            }) else {
+                return;
+            };
+
+            let Ok(source) =
+                cx.update(|cx| client_source(&abs_path, this.wsl_distro.as_deref(), cx))
+            else {
+                return;
+            };
+            let task = this.request(dap_command::SetBreakpoints {
+                source,
+                source_modified: Some(matches!(reason, BreakpointUpdatedReason::FileSaved)),
+                breakpoints,
+            });
+            match cx.background_spawn(task).await {
+                Ok(breakpoints) => {
+                    let breakpoints = breakpoints.into_iter().zip(raw_breakpoints).filter_map(
+                        |(dap_bp, zed_bp)| {
                             Some((
                                 zed_bp,
                                 BreakpointSessionState {
                                     id: dap_bp.id?,
                                     verified: dap_bp.verified,
+                                    message: dap_bp.message.map(Into::into),
                                 },
                             ))
-                        });
-                breakpoint_store
-                    .update(cx, |this, _| {
-                        this.mark_breakpoints_verified(session_id, &abs_path, breakpoints);
-                    })
-                    .ok();
+                        },
+                    );
+                    breakpoint_store
+                        .update(cx, |this, cx| {
+                            this.mark_breakpoints_verified(session_id, &abs_path, breakpoints, cx);
+                        })
+                        .ok();
+                }
+                Err(err) => log::warn!("Set breakpoints request failed for path: {}", err),
+            }
+        })
+    }
+
+    /// Moves breakpoints that were placed on lines without executable code (e.g. blank lines or
+    /// comments) to the nearest following location reported by `breakpointLocations`.
+    fn snap_breakpoints_to_valid_locations(
+        &self,
+        paths: impl IntoIterator<Item = Arc<Path>>,
+        breakpoint_store: &Entity<BreakpointStore>,
+        cx: &App,
+    ) -> Task<()> {
+        const SNAP_LOOKAHEAD_LINES: u64 = 32;
+
+        let mut rows = Vec::new();
+        let mut requests = Vec::new();
+        for path in paths {
+            for breakpoint in breakpoint_store
+                .read(cx)
+                .source_breakpoints_from_path(&path, cx)
+            {
+                if breakpoint.state.is_disabled() {
+                    continue;
+                }
+                let line = breakpoint.row as u64 + 1;
+                requests.push(self.request(dap_command::BreakpointLocationsCommand {
+                    source: client_source(&path, self.wsl_distro.as_deref(), cx),
+                    line,
+                    end_line: Some(line + SNAP_LOOKAHEAD_LINES),
+                }));
+                rows.push((path.clone(), breakpoint.row));
+            }
+        }
+
+        let breakpoint_store = breakpoint_store.downgrade();
+        cx.spawn(async move |cx| {
+            let locations = futures::future::join_all(requests).await;
+            let moves = rows
+                .into_iter()
+                .zip(locations)
+                .filter_map(|((path, row), locations)| {
+                    let line = row as u64 + 1;
+                    let snapped_line = locations
+                        .log_err()?
+                        .into_iter()
+                        .map(|location| location.line)
+                        .filter(|location_line| *location_line >= line)
+                        .min()?;
+                    (snapped_line != line).then(|| (path, row, (snapped_line - 1) as u32))
+                })
+                .collect::<Vec<_>>();
+
+            if moves.is_empty() {
+                return;
             }
-            Err(err) => log::warn!("Set breakpoints request failed for path: {}", err),
+
+            breakpoint_store
+                .update(cx, |store, cx| {
+                    for (path, from_row, to_row) in moves {
+                        store.move_breakpoint(path, from_row, to_row, cx);
+                    }
+                })
+                .ok();
         })
     }
 
@@ -308,6 +457,13 @@ impl RunningMode {
         self.request(arg)
     }
 
+    fn send_instruction_breakpoints(
+        &self,
+        breakpoints: Vec<dap::InstructionBreakpoint>,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        self.request(SetInstructionBreakpoints { breakpoints })
+    }
+
     fn send_source_breakpoints(
         &self,
         ignore_breakpoints: bool,
@@ -338,7 +494,7 @@ impl RunningMode {
             let error_path = path.clone();
             let send_request = self
                 .request(dap_command::SetBreakpoints {
-                    source: client_source(&path),
+                    source: client_source(&path, self.wsl_distro.as_deref(), cx),
                     source_modified: Some(false),
                     breakpoints,
                 })
@@ -356,13 +512,14 @@ impl RunningMode {
                                 BreakpointSessionState {
                                     id: dap_bp.id?,
                                     verified: dap_bp.verified,
+                                    message: dap_bp.message.map(Into::into),
                                 },
                             ))
                         },
                     );
                     breakpoint_store
-                        .update(cx, |this, _| {
-                            this.mark_breakpoints_verified(session_id, &path, breakpoints);
+                        .update(cx, |this, cx| {
+                            this.mark_breakpoints_verified(session_id, &path, breakpoints, cx);
                         })
                         .ok();
 
@@ -401,6 +558,8 @@ impl RunningMode {
         };
 
         let configuration_done_supported = ConfigurationDone::is_supported(capabilities);
+        let supports_breakpoint_locations =
+            dap_command::BreakpointLocationsCommand::is_supported(capabilities);
         let exception_filters = capabilities
             .exception_breakpoint_filters
             .as_ref()
@@ -422,6 +581,17 @@ impl RunningMode {
                 let breakpoint_store =
                     dap_store.read_with(cx, |dap_store, _| dap_store.breakpoint_store().clone())?;
                 initialized_rx.await?;
+                if supports_breakpoint_locations {
+                    cx.update(|cx| {
+                        let paths = breakpoint_store
+                            .read(cx)
+                            .all_source_breakpoints(cx)
+                            .into_keys()
+                            .collect::<Vec<_>>();
+                        this.snap_breakpoints_to_valid_locations(paths, &breakpoint_store, cx)
+                    })?
+                    .await;
+                }
                 let errors_by_path = cx
                     .update(|cx| this.send_source_breakpoints(false, &breakpoint_store, cx))?
                     .await;
@@ -509,10 +679,25 @@ impl RunningMode {
 
         let request_clone = request.clone();
         let connection = self.client.clone();
+        let recorder = self.recorder.lock().clone();
         self.executor.spawn(async move {
             let args = request_clone.to_dap();
-            let response = connection.request::<R::DapRequest>(args).await?;
-            request.response_from_dap(response)
+            let Some(recorder) = recorder else {
+                let response = connection.request::<R::DapRequest>(args).await?;
+                return request.response_from_dap(response);
+            };
+
+            let arguments = serde_json::to_value(&args).unwrap_or_default();
+            let response = connection.request::<R::DapRequest>(args).await;
+            recorder.record_response(
+                R::DapRequest::COMMAND,
+                arguments,
+                &response
+                    .as_ref()
+                    .map(|response| serde_json::to_value(response).unwrap_or_default())
+                    .map_err(|error| anyhow!("{error}")),
+            );
+            request.response_from_dap(response?)
         })
     }
 }
@@ -525,6 +710,8 @@ impl Mode {
     {
         match self {
             Mode::Running(debug_adapter_client) => debug_adapter_client.request(request),
+            Mode::Remote(remote) => remote.request(request),
+            Mode::Replay(replay) => replay.request(request),
             Mode::Building => Task::ready(Err(anyhow!(
                 "no adapter running to send request: {request:?}"
             ))),
@@ -534,7 +721,7 @@ impl Mode {
     /// Did this debug session stop at least once?
     pub(crate) fn has_ever_stopped(&self) -> bool {
         match self {
-            Mode::Building => false,
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => false,
             Mode::Running(running_mode) => running_mode.has_ever_stopped,
         }
     }
@@ -550,22 +737,27 @@ impl Mode {
 struct ThreadStates {
     global_state: Option<ThreadStatus>,
     known_thread_states: IndexMap<ThreadId, ThreadStatus>,
+    /// The events that stopped threads, for the threads that haven't resumed since.
+    stopped_events: HashMap<ThreadId, StoppedEvent>,
 }
 
 impl ThreadStates {
     fn stop_all_threads(&mut self) {
         self.global_state = Some(ThreadStatus::Stopped);
         self.known_thread_states.clear();
+        self.stopped_events.clear();
     }
 
     fn exit_all_threads(&mut self) {
         self.global_state = Some(ThreadStatus::Exited);
         self.known_thread_states.clear();
+        self.stopped_events.clear();
     }
 
     fn continue_all_threads(&mut self) {
         self.global_state = Some(ThreadStatus::Running);
         self.known_thread_states.clear();
+        self.stopped_events.clear();
     }
 
     fn stop_thread(&mut self, thread_id: ThreadId) {
@@ -576,11 +768,13 @@ impl ThreadStates {
     fn continue_thread(&mut self, thread_id: ThreadId) {
         self.known_thread_states
             .insert(thread_id, ThreadStatus::Running);
+        self.stopped_events.remove(&thread_id);
     }
 
     fn process_step(&mut self, thread_id: ThreadId) {
         self.known_thread_states
             .insert(thread_id, ThreadStatus::Stepping);
+        self.stopped_events.remove(&thread_id);
     }
 
     fn thread_status(&self, thread_id: ThreadId) -> ThreadStatus {
@@ -598,6 +792,7 @@ impl ThreadStates {
     fn exit_thread(&mut self, thread_id: ThreadId) {
         self.known_thread_states
             .insert(thread_id, ThreadStatus::Exited);
+        self.stopped_events.remove(&thread_id);
     }
 
     fn any_stopped_thread(&self) -> bool {
@@ -610,6 +805,9 @@ impl ThreadStates {
     }
 }
 const MAX_TRACKED_OUTPUT_EVENTS: usize = 5000;
+const MAX_ADAPTER_LOG_LINES: usize = 2000;
+/// How many stack frames are requested at a time from adapters that support delayed stack trace loading.
+const STACK_FRAMES_PAGE_SIZE: u64 = 200;
 
 type IsEnabled = bool;
 
@@ -627,19 +825,48 @@ pub struct Session {
     modules: Vec<dap::Module>,
     loaded_sources: Vec<dap::Source>,
     output_token: OutputToken,
-    output: Box<circular_buffer::CircularBuffer<MAX_TRACKED_OUTPUT_EVENTS, dap::OutputEvent>>,
+    /// Output events, along with the time they were received at.
+    output: Box<
+        circular_buffer::CircularBuffer<MAX_TRACKED_OUTPUT_EVENTS, (SystemTime, dap::OutputEvent)>,
+    >,
+    started_at: SystemTime,
+    /// What the debug adapter wrote to stderr, along with why it failed to start, if it did.
+    adapter_log: Box<circular_buffer::CircularBuffer<MAX_ADAPTER_LOG_LINES, SharedString>>,
     threads: IndexMap<ThreadId, Thread>,
     thread_states: ThreadStates,
+    /// Threads left suspended when the session is continued, see [`Session::freeze_thread`].
+    frozen_threads: HashSet<ThreadId>,
+    /// How many times in a row the session was restarted after its debuggee exited unexpectedly.
+    auto_restart_count: u32,
     variables: HashMap<VariableReference, Vec<dap::Variable>>,
+    /// Children of collections fetched a page at a time, keyed by the range of indexed children
+    /// they cover, or by `None` for their named children.
+    variable_pages: HashMap<(VariableReference, Option<Range<u64>>), Vec<dap::Variable>>,
     stack_frames: IndexMap<StackFrameId, StackFrame>,
+    /// Whether the debuggee last resumed by stepping over or out of a call, after which some
+    /// adapters report the value the call returned among the locals.
+    stepped_over_call: bool,
     locations: HashMap<u64, dap::LocationsResponse>,
     is_session_terminated: bool,
     requests: HashMap<TypeId, HashMap<RequestSlot, Shared<Task<Option<()>>>>>,
     pub(crate) breakpoint_store: Entity<BreakpointStore>,
+    /// The mappings breakpoints were last sent with, to resend them when the setting changes.
+    source_path_mappings: Vec<SourcePathMapping>,
     ignore_breakpoints: bool,
+    hex_values: bool,
     exception_breakpoints: BTreeMap<String, (ExceptionBreakpointsFilter, IsEnabled)>,
+    /// Breakpoints on the addresses of instructions, in the order they were set.
+    instruction_breakpoints: IndexMap<String, IsEnabled>,
+    /// Details of the exception a thread stopped on, until it's resumed or they're dismissed.
+    exception_info: Option<(ThreadId, dap::ExceptionInfoResponse)>,
+    /// Long-running operations the adapter reported progress for, by their progress ID.
+    progress: IndexMap<String, dap::ProgressStartEvent>,
     background_tasks: Vec<Task<()>>,
     task_context: TaskContext,
+    /// The guests of a shared project that the adapter's events are forwarded to.
+    downstream_client: Option<(AnyProtoClient, u64)>,
+    /// The system's id for the debuggee, as reported by the adapter's `process` event.
+    debuggee_pid: Option<u32>,
 }
 
 trait CacheableCommand: Any + Send + Sync {
@@ -707,13 +934,53 @@ impl CompletionsQuery {
         let PointUtf16 { row, column } = cursor_position.to_point_utf16(&buffer.snapshot());
         Self {
             query: buffer.text(),
-            column: column as u64,
+            // Columns start at 1, as announced when initializing the adapter.
+            column: column as u64 + 1,
             frame_id,
             line: Some(row as u64),
         }
     }
 }
 
+/// The ways an adapter can reload the debuggee's code without ending the session.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HotReloadKind {
+    /// Apply code changes to the running program, keeping its state.
+    Reload,
+    /// Restart the program in place, with its state reset.
+    Restart,
+}
+
+/// What happens to the debuggee when a session is stopped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StopMode {
+    /// End the debuggee along with the session.
+    Terminate,
+    /// Disconnect from the debuggee and leave it running.
+    Disconnect,
+    /// Disconnect from the debuggee and leave it suspended, so another debugger can attach to it.
+    Suspend,
+}
+
+impl StopMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StopMode::Terminate => "Terminate Debuggee",
+            StopMode::Disconnect => "Disconnect and Leave Running",
+            StopMode::Suspend => "Disconnect and Suspend",
+        }
+    }
+
+    /// How the mode is listed in the menus of the stop and detach buttons.
+    pub fn menu_label(&self) -> &'static str {
+        match self {
+            StopMode::Terminate => "Terminate Debuggee",
+            StopMode::Disconnect => "Leave Running",
+            StopMode::Suspend => "Suspend Debuggee",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum SessionEvent {
     Modules,
@@ -726,9 +993,25 @@ pub enum SessionEvent {
     CapabilitiesLoaded,
     RunInTerminal {
         request: RunInTerminalRequestArguments,
-        sender: mpsc::Sender<Result<u32>>,
+        sender: mpsc::Sender<Result<Option<u32>>>,
     },
     ConsoleOutput,
+    /// Instruction breakpoints were set, removed, enabled or disabled.
+    InstructionBreakpoints,
+    /// Details of the exception a thread stopped on were fetched or cleared.
+    ExceptionInfo,
+    /// The adapter started, updated or ended reporting the progress of an operation.
+    Progress,
+    /// The debug adapter was asked to restart the debuggee within this session.
+    Restarted,
+    /// The debuggee exited.
+    Exited(dap::ExitedEvent),
+    /// A line was added to the adapter's log.
+    AdapterLog,
+    /// The debug adapter couldn't be started; why is in the adapter's log.
+    AdapterFailed,
+    /// The debug adapter reported the process it's debugging.
+    DebuggeeProcess,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -760,12 +1043,20 @@ impl Session {
         cx.new::<Self>(|cx| {
             cx.subscribe(&breakpoint_store, |this, store, event, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(path, reason) => {
+                    let snap_to_valid_locations =
+                        dap_command::BreakpointLocationsCommand::is_supported(&this.capabilities);
                     if let Some(local) = (!this.ignore_breakpoints)
                         .then(|| this.as_running_mut())
                         .flatten()
                     {
                         local
-                            .send_breakpoints_from_path(path.clone(), *reason, &store, cx)
+                            .send_breakpoints_from_path(
+                                path.clone(),
+                                *reason,
+                                &store,
+                                snap_to_valid_locations,
+                                cx,
+                            )
                             .detach();
                     };
                 }
@@ -780,6 +1071,25 @@ impl Session {
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
             })
             .detach();
+            cx.observe_global::<SettingsStore>(|this, cx| {
+                let source_path_mappings = DebuggerSettings::get_global(cx)
+                    .source_path_mappings
+                    .clone();
+                if source_path_mappings == this.source_path_mappings {
+                    return;
+                }
+                this.source_path_mappings = source_path_mappings;
+                if let Some(local) = this.as_running() {
+                    local
+                        .send_source_breakpoints(
+                            this.ignore_breakpoints,
+                            &this.breakpoint_store,
+                            cx,
+                        )
+                        .detach();
+                }
+            })
+            .detach();
             cx.on_app_quit(Self::on_app_quit).detach();
 
             let this = Self {
@@ -789,10 +1099,16 @@ impl Session {
                 parent_session,
                 capabilities: Capabilities::default(),
                 variables: Default::default(),
+                variable_pages: Default::default(),
+                stepped_over_call: false,
                 stack_frames: Default::default(),
                 thread_states: ThreadStates::default(),
+                frozen_threads: HashSet::default(),
+                auto_restart_count: 0,
                 output_token: OutputToken(0),
                 output: circular_buffer::CircularBuffer::boxed(),
+                started_at: SystemTime::now(),
+                adapter_log: circular_buffer::CircularBuffer::boxed(),
                 requests: HashMap::default(),
                 modules: Vec::default(),
                 loaded_sources: Vec::default(),
@@ -801,24 +1117,74 @@ impl Session {
                 locations: Default::default(),
                 is_session_terminated: false,
                 ignore_breakpoints: false,
+                hex_values: false,
                 breakpoint_store,
+                source_path_mappings: DebuggerSettings::get_global(cx)
+                    .source_path_mappings
+                    .clone(),
                 exception_breakpoints: Default::default(),
+                instruction_breakpoints: IndexMap::default(),
+                exception_info: None,
+                progress: IndexMap::default(),
                 label,
                 adapter,
                 task_context,
+                downstream_client: None,
+                debuggee_pid: None,
             };
 
             this
         })
     }
 
+    #[expect(clippy::too_many_arguments)]
+    pub(crate) fn remote(
+        breakpoint_store: Entity<BreakpointStore>,
+        session_id: SessionId,
+        parent_session: Option<Entity<Session>>,
+        label: SharedString,
+        adapter: DebugAdapterName,
+        capabilities: Capabilities,
+        upstream_client: AnyProtoClient,
+        upstream_project_id: u64,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        let session = Self::new(
+            breakpoint_store,
+            session_id,
+            parent_session,
+            label,
+            adapter,
+            TaskContext::default(),
+            cx,
+        );
+        let executor = cx.background_executor().clone();
+        session.update(cx, |session, _| {
+            session.capabilities = capabilities;
+            session.mode = Mode::Remote(RemoteMode {
+                session_id,
+                upstream_client,
+                upstream_project_id,
+                executor,
+            });
+        });
+        session
+    }
+
+    pub(crate) fn set_downstream_client(
+        &mut self,
+        downstream_client: Option<(AnyProtoClient, u64)>,
+    ) {
+        self.downstream_client = downstream_client;
+    }
+
     pub fn task_context(&self) -> &TaskContext {
         &self.task_context
     }
 
     pub fn worktree(&self) -> Option<Entity<Worktree>> {
         match &self.mode {
-            Mode::Building => None,
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => None,
             Mode::Running(local_mode) => local_mode.worktree.upgrade(),
         }
     }
@@ -832,8 +1198,14 @@ impl Session {
     ) -> Task<Result<()>> {
         let (message_tx, mut message_rx) = futures::channel::mpsc::unbounded();
         let (initialized_tx, initialized_rx) = futures::channel::oneshot::channel();
+        let (adapter_log_tx, mut adapter_log_rx) = futures::channel::mpsc::unbounded::<String>();
+        let adapter_log_handler: IoHandler = Box::new(move |_, _, line| {
+            adapter_log_tx
+                .unbounded_send(line.trim_end().to_string())
+                .ok();
+        });
 
-        let background_tasks = vec![cx.spawn(async move |this: WeakEntity<Session>, cx| {
+        let mut background_tasks = vec![cx.spawn(async move |this: WeakEntity<Session>, cx| {
             let mut initialized_tx = Some(initialized_tx);
             while let Some(message) = message_rx.next().await {
                 if let Message::Event(event) = message {
@@ -863,6 +1235,14 @@ impl Session {
                 }
             }
         })];
+        background_tasks.push(cx.spawn(async move |this: WeakEntity<Session>, cx| {
+            while let Some(line) = adapter_log_rx.next().await {
+                let Ok(_) = this.update(cx, |session, cx| session.push_adapter_log(line, cx))
+                else {
+                    break;
+                };
+            }
+        }));
         self.background_tasks = background_tasks;
         let id = self.id;
         let parent_session = self.parent_session.clone();
@@ -874,6 +1254,7 @@ impl Session {
                 worktree.downgrade(),
                 binary.clone(),
                 message_tx,
+                adapter_log_handler,
                 cx,
             )
             .await?;
@@ -912,6 +1293,28 @@ impl Session {
         self.id
     }
 
+    /// The id of the process being debugged, once the debug adapter has reported it.
+    pub fn debuggee_pid(&self) -> Option<u32> {
+        self.debuggee_pid
+    }
+
+    /// What the debug adapter wrote to stderr, along with why it failed to start, if it did.
+    pub fn adapter_log(&self) -> impl Iterator<Item = &SharedString> {
+        self.adapter_log.iter()
+    }
+
+    pub fn push_adapter_log(&mut self, line: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.adapter_log.push_back(line.into());
+        cx.emit(SessionEvent::AdapterLog);
+        cx.notify();
+    }
+
+    /// Records why the debug adapter couldn't be started in its log.
+    pub fn adapter_failed(&mut self, error: &anyhow::Error, cx: &mut Context<Self>) {
+        self.push_adapter_log(format!("error: {error:#}"), cx);
+        cx.emit(SessionEvent::AdapterFailed);
+    }
+
     pub fn child_session_ids(&self) -> HashSet<SessionId> {
         self.child_session_ids.clone()
     }
@@ -940,11 +1343,19 @@ impl Session {
 
     pub fn binary(&self) -> Option<&DebugAdapterBinary> {
         match &self.mode {
-            Mode::Building => None,
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => None,
             Mode::Running(running_mode) => Some(&running_mode.binary),
         }
     }
 
+    pub fn auto_restart_count(&self) -> u32 {
+        self.auto_restart_count
+    }
+
+    pub fn set_auto_restart_count(&mut self, auto_restart_count: u32) {
+        self.auto_restart_count = auto_restart_count;
+    }
+
     pub fn adapter(&self) -> DebugAdapterName {
         self.adapter.clone()
     }
@@ -988,6 +1399,7 @@ impl Session {
         match &self.mode {
             Mode::Building => false,
             Mode::Running(running) => running.is_started,
+            Mode::Remote(_) | Mode::Replay(_) => true,
         }
     }
 
@@ -996,20 +1408,109 @@ impl Session {
     }
 
     pub fn is_running(&self) -> bool {
-        matches!(self.mode, Mode::Running(_))
+        matches!(
+            self.mode,
+            Mode::Running(_) | Mode::Remote(_) | Mode::Replay(_)
+        )
+    }
+
+    /// Whether this session mirrors one running on the host of a shared project.
+    pub fn is_remote(&self) -> bool {
+        matches!(self.mode, Mode::Remote(_))
     }
 
     pub fn as_running_mut(&mut self) -> Option<&mut RunningMode> {
         match &mut self.mode {
             Mode::Running(local_mode) => Some(local_mode),
-            Mode::Building => None,
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => None,
         }
     }
 
     pub fn as_running(&self) -> Option<&RunningMode> {
         match &self.mode {
             Mode::Running(local_mode) => Some(local_mode),
-            Mode::Building => None,
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => None,
+        }
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(self.mode, Mode::Replay(_))
+    }
+
+    fn recorder(&self) -> Option<DapRecorder> {
+        self.as_running()?.recorder.lock().clone()
+    }
+
+    /// Where the session's DAP traffic is being recorded to, if it is.
+    pub fn recording_path(&self) -> Option<PathBuf> {
+        self.recorder().map(|recorder| recorder.path().clone())
+    }
+
+    /// Starts recording the DAP traffic of the session to `path`, replacing any recording that's
+    /// in progress.
+    pub fn start_recording(&mut self, path: PathBuf, cx: &mut Context<Self>) -> Result<()> {
+        let header = RecordedMessage::Session {
+            label: self.label.to_string(),
+            adapter: self.adapter.to_string(),
+            capabilities: self.capabilities.clone(),
+        };
+        let running = self
+            .as_running()
+            .context("Only sessions with a running debug adapter can be recorded")?;
+        *running.recorder.lock() = Some(DapRecorder::new(path, header, cx));
+        cx.notify();
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self, cx: &mut Context<Self>) {
+        if let Some(running) = self.as_running() {
+            running.recorder.lock().take();
+            cx.notify();
+        }
+    }
+
+    /// Turns this session into a read-only replay of `recording`, whose events are played back
+    /// with their original timing.
+    pub(crate) fn start_replay(&mut self, recording: DapRecording, cx: &mut Context<Self>) {
+        let recording = Arc::new(recording);
+        let replay = ReplayMode::new(recording.clone(), cx.background_executor().clone());
+        self.capabilities = recording.capabilities.clone();
+        self.mode = Mode::Replay(replay.clone());
+        cx.emit(SessionEvent::CapabilitiesLoaded);
+        cx.emit(SessionStateEvent::Running);
+
+        self.background_tasks.push(cx.spawn(async move |this, cx| {
+            let mut previous_ms = 0;
+            for (elapsed_ms, event) in replay.recording().events() {
+                cx.background_executor()
+                    .timer(replay_delay(previous_ms, elapsed_ms))
+                    .await;
+                previous_ms = elapsed_ms;
+                replay.set_position(elapsed_ms);
+                let event = Box::new(event.clone());
+                if this
+                    .update(cx, |session, cx| session.handle_dap_event(event, cx))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }));
+        cx.notify();
+    }
+
+    /// Resolves a source path reported by the debug adapter to a local path, applying the
+    /// `source_path_mappings` setting and translating paths inside of a WSL distro.
+    pub fn local_source_path(&self, path: &str, cx: &App) -> PathBuf {
+        let local_path = DebuggerSettings::get_global(cx).local_source_path(path);
+        match self
+            .as_running()
+            .and_then(|running| running.wsl_distro.as_deref())
+        {
+            Some(distro) if local_path.to_string_lossy().starts_with('/') => {
+                wsl::windows_path(distro, &local_path.to_string_lossy())
+            }
+            _ => local_path,
         }
     }
 
@@ -1093,7 +1594,7 @@ impl Session {
 
         let seq = request.seq;
 
-        let (tx, mut rx) = mpsc::channel::<Result<u32>>(1);
+        let (tx, mut rx) = mpsc::channel::<Result<Option<u32>>>(1);
         cx.emit(SessionEvent::RunInTerminal {
             request: request_args,
             sender: tx,
@@ -1112,7 +1613,7 @@ impl Session {
                     true,
                     serde_json::to_value(dap::RunInTerminalResponse {
                         process_id: None,
-                        shell_process_id: Some(pid as u64),
+                        shell_process_id: pid.map(|pid| pid as u64),
                     })
                     .ok(),
                 ),
@@ -1215,6 +1716,8 @@ impl Session {
                 local_mode.initialize_sequence(&self.capabilities, initialize_rx, dap_store, cx)
             }
             Mode::Building => Task::ready(Err(anyhow!("cannot initialize, still building"))),
+            Mode::Remote(_) => Task::ready(Err(anyhow!("cannot initialize a remote session"))),
+            Mode::Replay(_) => Task::ready(Err(anyhow!("cannot initialize a replayed session"))),
         }
     }
 
@@ -1238,6 +1741,7 @@ impl Session {
                     path,
                     BreakpointUpdatedReason::Toggled,
                     &self.breakpoint_store,
+                    false,
                     cx,
                 );
 
@@ -1249,8 +1753,57 @@ impl Session {
                 })
                 .detach();
             }
-            Mode::Building => {}
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => {}
+        }
+    }
+
+    /// Whether the adapter can move where a stopped thread resumes execution.
+    pub fn supports_goto(&self) -> bool {
+        GotoTargetsCommand::is_supported(&self.capabilities)
+    }
+
+    /// Moves where the stopped thread resumes execution to `row` of `abs_path`, without running
+    /// the code in between.
+    pub fn jump_to_position(
+        &mut self,
+        abs_path: &Path,
+        row: u32,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !self.supports_goto() {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support jumping to a line"
+            )));
         }
+        if self.thread_states.thread_status(thread_id) != ThreadStatus::Stopped {
+            return Task::ready(Err(anyhow!("The thread isn't stopped")));
+        }
+        let Mode::Running(running) = &self.mode else {
+            return Task::ready(Err(anyhow!("no adapter running to jump with")));
+        };
+        let targets = running.request(GotoTargetsCommand {
+            source: client_source(abs_path, running.wsl_distro.as_deref(), cx),
+            line: row as u64 + 1,
+        });
+
+        cx.spawn(async move |this, cx| {
+            let target = targets
+                .await?
+                .into_iter()
+                .next()
+                .context("The debug adapter can't jump to this line")?;
+            let goto = this.update(cx, |this, _| match &this.mode {
+                Mode::Running(running) => Ok(running.request(GotoCommand {
+                    thread_id: thread_id.0,
+                    target_id: target.id,
+                })),
+                Mode::Building | Mode::Remote(_) | Mode::Replay(_) => {
+                    Err(anyhow!("no adapter running to jump with"))
+                }
+            })??;
+            goto.await
+        })
     }
 
     pub fn has_new_output(&self, last_update: OutputToken) -> bool {
@@ -1260,21 +1813,25 @@ impl Session {
     pub fn output(
         &self,
         since: OutputToken,
-    ) -> (impl Iterator<Item = &dap::OutputEvent>, OutputToken) {
-        if self.output_token.0 == 0 {
-            return (self.output.range(0..0), OutputToken(0));
-        };
-
+    ) -> (
+        impl Iterator<Item = (SystemTime, &dap::OutputEvent)>,
+        OutputToken,
+    ) {
         let events_since = self.output_token.0.checked_sub(since.0).unwrap_or(0);
 
         let clamped_events_since = events_since.clamp(0, self.output.len());
         (
             self.output
-                .range(self.output.len() - clamped_events_since..),
+                .range(self.output.len() - clamped_events_since..)
+                .map(|(received_at, event)| (*received_at, event)),
             self.output_token,
         )
     }
 
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
     pub fn respond_to_client(
         &self,
         request_seq: u64,
@@ -1316,6 +1873,7 @@ impl Session {
                     path,
                     BreakpointUpdatedReason::Toggled,
                     &breakpoint_store,
+                    false,
                     cx,
                 )
                 .detach();
@@ -1331,6 +1889,9 @@ impl Session {
         // to our own data
         if let Some(thread_id) = event.thread_id {
             self.thread_states.stop_thread(ThreadId(thread_id));
+            self.thread_states
+                .stopped_events
+                .insert(ThreadId(thread_id), event.clone());
 
             self.invalidate_state(
                 &StackTraceCommand {
@@ -1345,6 +1906,14 @@ impl Session {
         self.invalidate_generic();
         self.threads.clear();
         self.variables.clear();
+        self.variable_pages.clear();
+        self.clear_exception_info(cx);
+        if let Some(thread_id) = event
+            .thread_id
+            .filter(|_| matches!(event.reason, dap::StoppedEventReason::Exception))
+        {
+            self.fetch_exception_info(ThreadId(thread_id), cx);
+        }
         cx.emit(SessionEvent::Stopped(
             event
                 .thread_id
@@ -1355,7 +1924,61 @@ impl Session {
         cx.notify();
     }
 
+    /// Replays the events that stopped the threads which are still stopped to the guests of a
+    /// shared project.
+    pub(crate) fn send_thread_states(&self) {
+        let Some((client, project_id)) = &self.downstream_client else {
+            return;
+        };
+        let mut stopped_events = self
+            .thread_states
+            .stopped_events
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        // Stopping all threads resets the state of the others, so it has to be replayed first.
+        stopped_events.sort_by_key(|event| !event.all_threads_stopped.unwrap_or_default());
+        if stopped_events.is_empty() && self.thread_states.any_stopped_thread() {
+            stopped_events.push(StoppedEvent {
+                reason: dap::StoppedEventReason::Pause,
+                description: None,
+                thread_id: None,
+                preserve_focus_hint: None,
+                text: None,
+                all_threads_stopped: Some(true),
+                hit_breakpoint_ids: None,
+            });
+        }
+        for event in stopped_events {
+            let Some(event) = serde_json::to_string(&Events::Stopped(event)).log_err() else {
+                continue;
+            };
+            client
+                .send(proto::DebugSessionEvent {
+                    project_id: *project_id,
+                    session_id: self.id.to_proto(),
+                    event,
+                })
+                .log_err();
+        }
+    }
+
     pub(crate) fn handle_dap_event(&mut self, event: Box<Events>, cx: &mut Context<Self>) {
+        if let Some((client, project_id)) = &self.downstream_client {
+            if let Some(event) = serde_json::to_string(&event).log_err() {
+                client
+                    .send(proto::DebugSessionEvent {
+                        project_id: *project_id,
+                        session_id: self.id.to_proto(),
+                        event,
+                    })
+                    .log_err();
+            }
+        }
+        if let Some(recorder) = self.recorder() {
+            recorder.record_event(&event);
+        }
+
         match *event {
             Events::Initialized(_) => {
                 debug_assert!(
@@ -1376,9 +1999,11 @@ impl Session {
                 }
                 // todo(debugger): We should be able to get away with only invalidating generic if all threads were continued
                 self.invalidate_generic();
+                self.clear_exception_info(cx);
             }
-            Events::Exited(_event) => {
+            Events::Exited(event) => {
                 self.clear_active_debug_line(cx);
+                cx.emit(SessionEvent::Exited(event));
             }
             Events::Terminated(_) => {
                 self.shutdown(cx).detach();
@@ -1392,6 +2017,7 @@ impl Session {
                     }
                     dap::ThreadEventReason::Exited => {
                         self.thread_states.exit_thread(thread_id);
+                        self.frozen_threads.remove(&thread_id);
                     }
                     reason => {
                         log::error!("Unhandled thread event reason {:?}", reason);
@@ -1401,19 +2027,16 @@ impl Session {
                 cx.notify();
             }
             Events::Output(event) => {
-                if event
-                    .category
-                    .as_ref()
-                    .is_some_and(|category| *category == OutputEventCategory::Telemetry)
-                {
-                    return;
-                }
-
                 self.push_output(event, cx);
                 cx.notify();
             }
-            Events::Breakpoint(event) => self.breakpoint_store.update(cx, |store, _| {
-                store.update_session_breakpoint(self.session_id(), event.reason, event.breakpoint);
+            Events::Breakpoint(event) => self.breakpoint_store.update(cx, |store, cx| {
+                store.update_session_breakpoint(
+                    self.session_id(),
+                    event.reason,
+                    event.breakpoint,
+                    cx,
+                );
             }),
             Events::Module(event) => {
                 match event.reason {
@@ -1445,10 +2068,35 @@ impl Session {
                 cx.notify();
             }
             Events::Memory(_) => {}
-            Events::Process(_) => {}
-            Events::ProgressEnd(_) => {}
-            Events::ProgressStart(_) => {}
-            Events::ProgressUpdate(_) => {}
+            Events::Process(event) => {
+                self.debuggee_pid = event
+                    .system_process_id
+                    .and_then(|pid| u32::try_from(pid).ok());
+                cx.emit(SessionEvent::DebuggeeProcess);
+            }
+            Events::ProgressEnd(event) => {
+                if self.progress.shift_remove(&event.progress_id).is_some() {
+                    cx.emit(SessionEvent::Progress);
+                    cx.notify();
+                }
+            }
+            Events::ProgressStart(event) => {
+                self.progress.insert(event.progress_id.clone(), event);
+                cx.emit(SessionEvent::Progress);
+                cx.notify();
+            }
+            Events::ProgressUpdate(event) => {
+                if let Some(progress) = self.progress.get_mut(&event.progress_id) {
+                    if event.message.is_some() {
+                        progress.message = event.message;
+                    }
+                    if event.percentage.is_some() {
+                        progress.percentage = event.percentage;
+                    }
+                    cx.emit(SessionEvent::Progress);
+                    cx.notify();
+                }
+            }
             Events::Invalidated(_) => {}
             Events::Other(_) => {}
         }
@@ -1556,6 +2204,19 @@ impl Session {
         Self::request_inner(&self.capabilities, &self.mode, request, process_result, cx)
     }
 
+    /// Sends a request on behalf of a collaborator, bypassing this session's caches.
+    pub(crate) fn request_for_remote<T: DapCommand>(
+        &self,
+        request: T,
+    ) -> Task<Result<T::Response>> {
+        if !T::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "Debug adapter does not support request: {request:?}"
+            )));
+        }
+        self.mode.request_dap(request)
+    }
+
     fn invalidate_command_type<Command: DapCommand>(&mut self) {
         self.requests.remove(&std::any::TypeId::of::<Command>());
     }
@@ -1575,7 +2236,7 @@ impl Session {
     }
 
     fn push_output(&mut self, event: OutputEvent, cx: &mut Context<Self>) {
-        self.output.push_back(event);
+        self.output.push_back((SystemTime::now(), event));
         self.output_token.0 += 1;
         cx.emit(SessionEvent::ConsoleOutput);
     }
@@ -1584,6 +2245,12 @@ impl Session {
         self.thread_states.any_stopped_thread()
     }
 
+    /// The event that reported why the thread stopped, if it stopped on its own account rather
+    /// than because another thread stopped all of them.
+    pub fn stopped_event(&self, thread_id: ThreadId) -> Option<&StoppedEvent> {
+        self.thread_states.stopped_events.get(&thread_id)
+    }
+
     pub fn thread_status(&self, thread_id: ThreadId) -> ThreadStatus {
         self.thread_states.thread_status(thread_id)
     }
@@ -1641,14 +2308,44 @@ impl Session {
         self.ignore_breakpoints
     }
 
-    pub fn toggle_ignore_breakpoints(
-        &mut self,
-        cx: &mut App,
-    ) -> Task<HashMap<Arc<Path>, anyhow::Error>> {
-        self.set_ignore_breakpoints(!self.ignore_breakpoints, cx)
+    pub fn hex_values(&self) -> bool {
+        self.hex_values
     }
 
-    pub(crate) fn set_ignore_breakpoints(
+    /// Asks the adapter to format variable and evaluation results as hexadecimal, provided it
+    /// supports value formatting options.
+    pub fn set_hex_values(&mut self, hex_values: bool, cx: &mut Context<Self>) {
+        if self.hex_values == hex_values {
+            return;
+        }
+        self.hex_values = hex_values;
+        if self
+            .capabilities
+            .supports_value_formatting_options
+            .unwrap_or_default()
+        {
+            self.invalidate_command_type::<VariablesCommand>();
+            cx.emit(SessionEvent::Variables);
+        }
+        cx.notify();
+    }
+
+    fn value_format(&self) -> Option<dap::ValueFormat> {
+        let supported = self
+            .capabilities
+            .supports_value_formatting_options
+            .unwrap_or_default();
+        (self.hex_values && supported).then_some(dap::ValueFormat { hex: Some(true) })
+    }
+
+    pub fn toggle_ignore_breakpoints(
+        &mut self,
+        cx: &mut App,
+    ) -> Task<HashMap<Arc<Path>, anyhow::Error>> {
+        self.set_ignore_breakpoints(!self.ignore_breakpoints, cx)
+    }
+
+    pub(crate) fn set_ignore_breakpoints(
         &mut self,
         ignore: bool,
         cx: &mut App,
@@ -1660,6 +2357,11 @@ impl Session {
         self.ignore_breakpoints = ignore;
 
         if let Some(local) = self.as_running() {
+            if !self.instruction_breakpoints.is_empty() {
+                local
+                    .send_instruction_breakpoints(self.enabled_instruction_breakpoints())
+                    .detach_and_log_err(cx);
+            }
             local.send_source_breakpoints(ignore, &self.breakpoint_store, cx)
         } else {
             // todo(debugger): We need to propagate this change to downstream sessions and send a message to upstream sessions
@@ -1700,6 +2402,60 @@ impl Session {
         }
     }
 
+    pub fn supports_instruction_breakpoints(&self) -> bool {
+        SetInstructionBreakpoints::is_supported(&self.capabilities)
+    }
+
+    pub fn instruction_breakpoints(&self) -> impl Iterator<Item = (&String, &IsEnabled)> {
+        self.instruction_breakpoints.iter()
+    }
+
+    /// Sets a breakpoint on the instruction at `address`, or removes the one that's set there.
+    pub fn toggle_instruction_breakpoint(&mut self, address: String, cx: &mut Context<Self>) {
+        if self
+            .instruction_breakpoints
+            .shift_remove(&address)
+            .is_none()
+        {
+            self.instruction_breakpoints.insert(address, true);
+        }
+        self.send_instruction_breakpoints(cx);
+    }
+
+    pub fn toggle_instruction_breakpoint_enabled(&mut self, address: &str, cx: &mut Context<Self>) {
+        if let Some(is_enabled) = self.instruction_breakpoints.get_mut(address) {
+            *is_enabled = !*is_enabled;
+            self.send_instruction_breakpoints(cx);
+        }
+    }
+
+    fn send_instruction_breakpoints(&mut self, cx: &mut Context<Self>) {
+        cx.emit(SessionEvent::InstructionBreakpoints);
+        cx.notify();
+        if let Some(local) = self.as_running() {
+            local
+                .send_instruction_breakpoints(self.enabled_instruction_breakpoints())
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn enabled_instruction_breakpoints(&self) -> Vec<dap::InstructionBreakpoint> {
+        if self.ignore_breakpoints {
+            return Vec::new();
+        }
+        self.instruction_breakpoints
+            .iter()
+            .filter(|(_, is_enabled)| **is_enabled)
+            .map(|(address, _)| dap::InstructionBreakpoint {
+                instruction_reference: address.clone(),
+                offset: None,
+                condition: None,
+                hit_condition: None,
+                mode: None,
+            })
+            .collect()
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -1747,6 +2503,7 @@ impl Session {
                 this.breakpoint_store.update(cx, |store, cx| {
                     store.remove_active_position(Some(this.session_id()), cx)
                 });
+                this.clear_exception_info(cx);
                 Some(response)
             }
             None => {
@@ -1803,11 +2560,52 @@ impl Session {
                 cx,
             )
             .detach();
+            cx.emit(SessionEvent::Restarted);
         } else {
             cx.emit(SessionStateEvent::Restart);
         }
     }
 
+    /// The custom request the session's adapter performs `kind` with, if it supports it.
+    pub fn hot_reload_request(&self, kind: HotReloadKind, cx: &App) -> Option<String> {
+        if self.is_terminated() || !matches!(self.mode, Mode::Running(_)) {
+            return None;
+        }
+        let requests = DapRegistry::global(cx)
+            .adapter(&self.adapter)?
+            .hot_reload_requests();
+        match kind {
+            HotReloadKind::Reload => requests.reload,
+            HotReloadKind::Restart => requests.restart,
+        }
+    }
+
+    /// Reloads the debuggee's code through its adapter, keeping the session running.
+    pub fn hot_reload(&mut self, kind: HotReloadKind, cx: &mut Context<Self>) -> Task<Result<()>> {
+        let (Some(command), Some(client)) =
+            (self.hot_reload_request(kind, cx), self.adapter_client())
+        else {
+            return Task::ready(Err(anyhow!(
+                "{} doesn't support hot reloading",
+                self.adapter
+            )));
+        };
+
+        cx.spawn(async move |this, cx| {
+            client.custom_request(&command, None).await?;
+            this.update(cx, |this, cx| {
+                // Frames and variables may refer to code that was just replaced.
+                this.invalidate_generic();
+                this.invalidate_command_type::<StackTraceCommand>();
+                this.invalidate_command_type::<ScopesCommand>();
+                this.invalidate_command_type::<VariablesCommand>();
+                cx.emit(SessionEvent::StackTrace);
+                cx.emit(SessionEvent::Variables);
+                cx.notify();
+            })
+        })
+    }
+
     fn on_app_quit(&mut self, cx: &mut Context<Self>) -> Task<()> {
         let debug_adapter = self.adapter_client();
 
@@ -1818,15 +2616,75 @@ impl Session {
         })
     }
 
+    /// Whether the debug adapter lets the user choose what happens to the debuggee with `mode`.
+    pub fn supports_stop_mode(&self, mode: StopMode) -> bool {
+        if self.is_remote() || self.is_replay() {
+            return mode == StopMode::Terminate;
+        }
+        match mode {
+            // Adapters always end the programs they launched.
+            StopMode::Terminate => {
+                !self.is_attached()
+                    || self
+                        .capabilities
+                        .support_terminate_debuggee
+                        .unwrap_or_default()
+            }
+            StopMode::Disconnect => {
+                self.is_attached()
+                    || self
+                        .capabilities
+                        .support_terminate_debuggee
+                        .unwrap_or_default()
+            }
+            StopMode::Suspend => self
+                .capabilities
+                .support_suspend_debuggee
+                .unwrap_or_default(),
+        }
+    }
+
+    /// What stopping the session does to the debuggee when the user isn't asked.
+    pub fn default_stop_mode(&self) -> StopMode {
+        if self.is_attached() {
+            StopMode::Disconnect
+        } else {
+            StopMode::Terminate
+        }
+    }
+
     pub fn shutdown(&mut self, cx: &mut Context<Self>) -> Task<()> {
+        self.stop(StopMode::Terminate, cx)
+    }
+
+    /// Ends the session, leaving the debuggee as `mode` asks for.
+    pub fn stop(&mut self, mode: StopMode, cx: &mut Context<Self>) -> Task<()> {
         self.is_session_terminated = true;
         self.thread_states.exit_all_threads();
         cx.notify();
 
-        let task = if self
-            .capabilities
-            .supports_terminate_request
-            .unwrap_or_default()
+        // Children of the debuggee are reparented once it exits, so look them up beforehand.
+        let process_tree = match self.debuggee_pid {
+            Some(pid)
+                if mode == StopMode::Terminate
+                    && !self.is_attached()
+                    && self.binary().is_some_and(|binary| {
+                        process_tree::kills_process_tree(&binary.request_args.configuration)
+                    }) =>
+            {
+                Some(cx.background_spawn(process_tree::descendants(pid)))
+            }
+            _ => None,
+        };
+
+        let task = if self.is_remote() {
+            // The debuggee belongs to the host, so only stop mirroring the session here.
+            Task::ready(None)
+        } else if mode == StopMode::Terminate
+            && self
+                .capabilities
+                .supports_terminate_request
+                .unwrap_or_default()
         {
             self.request(
                 TerminateCommand {
@@ -1839,8 +2697,8 @@ impl Session {
             self.request(
                 DisconnectCommand {
                     restart: Some(false),
-                    terminate_debuggee: Some(true),
-                    suspend_debuggee: Some(false),
+                    terminate_debuggee: Some(mode == StopMode::Terminate),
+                    suspend_debuggee: Some(mode == StopMode::Suspend),
                 },
                 Self::clear_active_debug_line_response,
                 cx,
@@ -1852,11 +2710,18 @@ impl Session {
         let debug_client = self.adapter_client();
 
         cx.background_spawn(async move {
+            let descendants = match process_tree {
+                Some(process_tree) => process_tree.await.log_err(),
+                None => None,
+            };
             let _ = task.await;
 
             if let Some(client) = debug_client {
                 client.shutdown().await.log_err();
             }
+            if let Some(descendants) = descendants {
+                process_tree::kill(&descendants).await.log_err();
+            }
         })
     }
 
@@ -1877,6 +2742,29 @@ impl Session {
     }
 
     pub fn continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        self.stepped_over_call = false;
+
+        if self.frozen_threads.is_empty() {
+            self.continue_single_thread(thread_id, cx);
+            return;
+        }
+
+        // Frozen threads must stay suspended, so resume every other stopped thread on its own.
+        let thread_ids = self
+            .threads
+            .keys()
+            .copied()
+            .filter(|thread_id| {
+                !self.frozen_threads.contains(thread_id)
+                    && self.thread_states.thread_status(*thread_id) == ThreadStatus::Stopped
+            })
+            .collect::<Vec<_>>();
+        for thread_id in thread_ids {
+            self.continue_single_thread(thread_id, cx);
+        }
+    }
+
+    fn continue_single_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
         self.thread_states.continue_thread(thread_id);
         self.request(
             ContinueCommand {
@@ -1891,16 +2779,46 @@ impl Session {
         .detach();
     }
 
+    /// Whether threads can be frozen, which relies on the adapter resuming only the requested
+    /// thread on `continue`.
+    pub fn supports_freezing_threads(&self) -> bool {
+        self.capabilities
+            .supports_single_thread_execution_requests
+            .unwrap_or_default()
+    }
+
+    pub fn is_thread_frozen(&self, thread_id: ThreadId) -> bool {
+        self.frozen_threads.contains(&thread_id)
+    }
+
+    /// Keeps the thread suspended when the session is continued, until it's thawed.
+    pub fn freeze_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if self.supports_freezing_threads() && self.frozen_threads.insert(thread_id) {
+            cx.notify();
+        }
+    }
+
+    pub fn thaw_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if self.frozen_threads.remove(&thread_id) {
+            cx.notify();
+        }
+    }
+
     pub fn adapter_client(&self) -> Option<Arc<DebugAdapterClient>> {
         match self.mode {
             Mode::Running(ref local) => Some(local.client.clone()),
-            Mode::Building => None,
+            Mode::Building | Mode::Remote(_) | Mode::Replay(_) => None,
         }
     }
 
     pub fn has_ever_stopped(&self) -> bool {
         self.mode.has_ever_stopped()
     }
+
+    pub fn stepped_over_call(&self) -> bool {
+        self.stepped_over_call
+    }
+
     pub fn step_over(
         &mut self,
         thread_id: ThreadId,
@@ -1923,6 +2841,7 @@ impl Session {
         };
 
         self.thread_states.process_step(thread_id);
+        self.stepped_over_call = true;
         self.request(
             command,
             Self::on_step_response::<NextCommand>(thread_id),
@@ -1953,6 +2872,7 @@ impl Session {
         };
 
         self.thread_states.process_step(thread_id);
+        self.stepped_over_call = false;
         self.request(
             command,
             Self::on_step_response::<StepInCommand>(thread_id),
@@ -1983,6 +2903,7 @@ impl Session {
         };
 
         self.thread_states.process_step(thread_id);
+        self.stepped_over_call = true;
         self.request(
             command,
             Self::on_step_response::<StepOutCommand>(thread_id),
@@ -2013,6 +2934,7 @@ impl Session {
         };
 
         self.thread_states.process_step(thread_id);
+        self.stepped_over_call = false;
 
         self.request(
             command,
@@ -2022,6 +2944,26 @@ impl Session {
         .detach();
     }
 
+    /// Runs the thread backwards until it hits a breakpoint or the start of the recording.
+    pub fn reverse_continue(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        let supports_single_thread_execution_requests =
+            self.capabilities.supports_single_thread_execution_requests;
+
+        self.thread_states.continue_thread(thread_id);
+        self.stepped_over_call = false;
+        self.request(
+            ReverseContinueCommand {
+                args: ReverseContinueArguments {
+                    thread_id: thread_id.0,
+                    single_thread: supports_single_thread_execution_requests,
+                },
+            },
+            Self::on_step_response::<ReverseContinueCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
     pub fn stack_frames(
         &mut self,
         thread_id: ThreadId,
@@ -2035,49 +2977,48 @@ impl Session {
         // This isn't the biggest concern right now because it hasn't caused any issues outside of tests
         // But it very well could cause a minor bug in the future that is hard to track down
         {
+            let levels = self
+                .capabilities
+                .supports_delayed_stack_trace_loading
+                .unwrap_or_default()
+                .then_some(STACK_FRAMES_PAGE_SIZE);
             self.fetch(
                 super::dap_command::StackTraceCommand {
                     thread_id: thread_id.0,
                     start_frame: None,
-                    levels: None,
+                    levels,
                 },
-                move |this, stack_frames, cx| {
+                move |this, response, cx| {
                     let entry =
                         this.threads
                             .entry(thread_id)
-                            .and_modify(|thread| match &stack_frames {
-                                Ok(stack_frames) => {
-                                    thread.stack_frames = stack_frames
+                            .and_modify(|thread| match &response {
+                                Ok(response) => {
+                                    thread.stack_frames = response
+                                        .stack_frames
                                         .iter()
                                         .cloned()
                                         .map(StackFrame::from)
                                         .collect();
                                     thread.stack_frames_error = None;
+                                    thread.has_more_stack_frames = Self::stack_trace_is_partial(
+                                        levels,
+                                        response,
+                                        thread.stack_frames.len(),
+                                    );
                                 }
                                 Err(error) => {
                                     thread.stack_frames.clear();
                                     thread.stack_frames_error = Some(error.cloned());
+                                    thread.has_more_stack_frames = false;
                                 }
                             });
                     debug_assert!(
                         matches!(entry, indexmap::map::Entry::Occupied(_)),
                         "Sent request for thread_id that doesn't exist"
                     );
-                    if let Ok(stack_frames) = stack_frames {
-                        this.stack_frames.extend(
-                            stack_frames
-                                .into_iter()
-                                .filter(|frame| {
-                                    // Workaround for JavaScript debug adapter sending out "fake" stack frames for delineating await points. This is fine,
-                                    // except that they always use an id of 0 for it, which collides with other (valid) stack frames.
-                                    !(frame.id == 0
-                                        && frame.line == 0
-                                        && frame.column == 0
-                                        && frame.presentation_hint
-                                            == Some(StackFramePresentationHint::Label))
-                                })
-                                .map(|frame| (frame.id, StackFrame::from(frame))),
-                        );
+                    if let Ok(response) = response {
+                        this.insert_stack_frames(response.stack_frames);
                     }
 
                     this.invalidate_command_type::<ScopesCommand>();
@@ -2101,6 +3042,90 @@ impl Session {
         }
     }
 
+    fn insert_stack_frames(&mut self, stack_frames: Vec<dap::StackFrame>) {
+        self.stack_frames.extend(
+            stack_frames
+                .into_iter()
+                .filter(|frame| {
+                    // Workaround for JavaScript debug adapter sending out "fake" stack frames for delineating await points. This is fine,
+                    // except that they always use an id of 0 for it, which collides with other (valid) stack frames.
+                    !(frame.id == 0
+                        && frame.line == 0
+                        && frame.column == 0
+                        && frame.presentation_hint == Some(StackFramePresentationHint::Label))
+                })
+                .map(|frame| (frame.id, StackFrame::from(frame))),
+        );
+    }
+
+    /// Per the DAP spec, a missing `totalFrames` (or one larger than what the adapter returns) means
+    /// that the client should keep requesting frames until it gets fewer than it asked for.
+    fn stack_trace_is_partial(
+        levels: Option<u64>,
+        response: &dap::StackTraceResponse,
+        loaded_frames: usize,
+    ) -> bool {
+        let Some(levels) = levels else {
+            return false;
+        };
+        response.stack_frames.len() as u64 >= levels
+            || response
+                .total_frames
+                .is_some_and(|total_frames| total_frames > loaded_frames as u64)
+    }
+
+    /// Whether the thread's stack was only partially loaded and [`Self::load_more_stack_frames`] can fetch the rest.
+    pub fn has_more_stack_frames(&self, thread_id: ThreadId) -> bool {
+        self.threads
+            .get(&thread_id)
+            .is_some_and(|thread| thread.has_more_stack_frames)
+    }
+
+    /// Fetch the next page of frames for a thread whose stack was only partially loaded, e.g. the
+    /// continuation chain of an async stack trace.
+    pub fn load_more_stack_frames(
+        &mut self,
+        thread_id: ThreadId,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        let Some(start_frame) = self
+            .threads
+            .get(&thread_id)
+            .filter(|thread| thread.has_more_stack_frames)
+            .map(|thread| thread.stack_frames.len() as u64)
+        else {
+            return Task::ready(());
+        };
+        let levels = Some(STACK_FRAMES_PAGE_SIZE);
+        let task = self.request(
+            StackTraceCommand {
+                thread_id: thread_id.0,
+                start_frame: Some(start_frame),
+                levels,
+            },
+            move |this, response, cx| {
+                let response = response.log_err()?;
+                let thread = this.threads.get_mut(&thread_id)?;
+                // The stack was refetched while this page was in flight.
+                if thread.stack_frames.len() as u64 != start_frame {
+                    return None;
+                }
+                thread
+                    .stack_frames
+                    .extend(response.stack_frames.iter().cloned().map(StackFrame::from));
+                thread.has_more_stack_frames = !response.stack_frames.is_empty()
+                    && Self::stack_trace_is_partial(levels, &response, thread.stack_frames.len());
+                this.insert_stack_frames(response.stack_frames);
+                cx.notify();
+                None
+            },
+            cx,
+        );
+        cx.background_spawn(async move {
+            task.await;
+        })
+    }
+
     pub fn scopes(&mut self, stack_frame_id: u64, cx: &mut Context<Self>) -> &[dap::Scope] {
         if self.requests.contains_key(&TypeId::of::<ThreadsCommand>())
             && self
@@ -2156,6 +3181,15 @@ impl Session {
             .collect()
     }
 
+    /// Returns the children of `variables_reference` that have already been fetched, without
+    /// issuing a request for them.
+    pub fn loaded_variables(&self, variables_reference: VariableReference) -> &[dap::Variable] {
+        self.variables
+            .get(&variables_reference)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     pub fn variables(
         &mut self,
         variables_reference: VariableReference,
@@ -2166,7 +3200,7 @@ impl Session {
             filter: None,
             start: None,
             count: None,
-            format: None,
+            format: self.value_format(),
         };
 
         self.fetch(
@@ -2190,30 +3224,119 @@ impl Session {
             .unwrap_or_default()
     }
 
+    /// Returns part of the children of a collection too large to load at once: its named children
+    /// when `range` is `None`, or else its indexed children within `range`.
+    pub fn variables_page(
+        &mut self,
+        variables_reference: VariableReference,
+        range: Option<Range<u64>>,
+        cx: &mut Context<Self>,
+    ) -> Vec<dap::Variable> {
+        let command = VariablesCommand {
+            variables_reference,
+            filter: Some(if range.is_some() {
+                VariablesArgumentsFilter::Indexed
+            } else {
+                VariablesArgumentsFilter::Named
+            }),
+            start: range.as_ref().map(|range| range.start),
+            count: range
+                .as_ref()
+                .map(|range| range.end.saturating_sub(range.start)),
+            format: self.value_format(),
+        };
+        let key = (variables_reference, range);
+
+        self.fetch(
+            command,
+            {
+                let key = key.clone();
+                move |this, variables, cx| {
+                    let Some(variables) = variables.log_err() else {
+                        return;
+                    };
+
+                    this.variable_pages.insert(key, variables);
+                    cx.emit(SessionEvent::Variables);
+                }
+            },
+            cx,
+        );
+
+        self.variable_pages.get(&key).cloned().unwrap_or_default()
+    }
+
+    /// Fetches the children of `variables_reference` without caching them. With a `range`, only
+    /// the indexed children within it are requested, so views can page through collections too
+    /// large to load at once.
+    pub fn fetch_variables(
+        &self,
+        variables_reference: VariableReference,
+        range: Option<Range<u64>>,
+    ) -> Task<Result<Vec<dap::Variable>>> {
+        self.mode.request_dap(VariablesCommand {
+            variables_reference,
+            filter: range.is_some().then_some(VariablesArgumentsFilter::Indexed),
+            start: range.as_ref().map(|range| range.start),
+            count: range.map(|range| range.end.saturating_sub(range.start)),
+            format: self.value_format(),
+        })
+    }
+
     pub fn set_variable_value(
         &mut self,
         variables_reference: u64,
         name: String,
         value: String,
         cx: &mut Context<Self>,
-    ) {
-        if self.capabilities.supports_set_variable.unwrap_or_default() {
-            self.request(
-                SetVariableValueCommand {
-                    name,
-                    value,
-                    variables_reference,
-                },
-                move |this, response, cx| {
-                    let response = response.log_err()?;
-                    this.invalidate_command_type::<VariablesCommand>();
-                    cx.notify();
-                    Some(response)
-                },
-                cx,
-            )
-            .detach()
+    ) -> Task<Option<dap::SetVariableResponse>> {
+        if !self.capabilities.supports_set_variable.unwrap_or_default() {
+            return Task::ready(None);
+        }
+        self.request(
+            SetVariableValueCommand {
+                name,
+                value,
+                variables_reference,
+            },
+            move |this, response, cx| {
+                let response = response.log_err()?;
+                this.invalidate_command_type::<VariablesCommand>();
+                cx.notify();
+                Some(response)
+            },
+            cx,
+        )
+    }
+
+    pub fn set_expression(
+        &mut self,
+        expression: String,
+        value: String,
+        frame_id: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Option<dap::SetExpressionResponse>> {
+        if !self
+            .capabilities
+            .supports_set_expression
+            .unwrap_or_default()
+        {
+            return Task::ready(None);
         }
+        self.request(
+            SetExpressionCommand {
+                expression,
+                value,
+                frame_id,
+            },
+            move |this, response, cx| {
+                let response = response.log_err()?;
+                this.invalidate_command_type::<VariablesCommand>();
+                cx.notify();
+                Some(response)
+            },
+            cx,
+        )
     }
 
     pub fn evaluate(
@@ -2241,6 +3364,7 @@ impl Session {
             context,
             frame_id,
             source,
+            format: self.value_format(),
         });
         cx.spawn(async move |this, cx| {
             let response = request.await;
@@ -2282,6 +3406,209 @@ impl Session {
         })
     }
 
+    /// Evaluates `expression` without echoing it or its result to the console.
+    pub fn evaluate_silently(
+        &self,
+        expression: String,
+        context: Option<EvaluateArgumentsContext>,
+        frame_id: Option<u64>,
+    ) -> Task<Result<dap::EvaluateResponse>> {
+        self.mode.request_dap(EvaluateCommand {
+            expression,
+            context,
+            frame_id,
+            source: None,
+            format: self.value_format(),
+        })
+    }
+
+    /// Reads up to `count` bytes starting `offset` bytes past the location `memory_reference`
+    /// points to. Fewer bytes are returned when part of the range is unreadable.
+    pub fn read_memory(
+        &self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+    ) -> Task<Result<Vec<u8>>> {
+        if !ReadMemoryCommand::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support reading memory"
+            )));
+        }
+        let request = ReadMemoryCommand {
+            memory_reference,
+            offset: Some(offset),
+            count,
+        };
+        match &self.mode {
+            Mode::Running(running) => running.request(request),
+            Mode::Replay(replay) => replay.request(request),
+            Mode::Building | Mode::Remote(_) => {
+                Task::ready(Err(anyhow!("no adapter running to read memory")))
+            }
+        }
+    }
+
+    pub fn supports_write_memory(&self) -> bool {
+        WriteMemoryCommand::is_supported(&self.capabilities)
+    }
+
+    /// Overwrites memory starting `offset` bytes past the location `memory_reference` points to
+    /// with `data`, failing rather than writing only part of it.
+    pub fn write_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        data: Vec<u8>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if !self.supports_write_memory() {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support writing memory"
+            )));
+        }
+        let request = WriteMemoryCommand {
+            memory_reference,
+            offset: Some(offset),
+            data,
+        };
+        let task = match &self.mode {
+            Mode::Running(running) => running.request(request),
+            Mode::Replay(replay) => replay.request(request),
+            Mode::Building | Mode::Remote(_) => {
+                return Task::ready(Err(anyhow!("no adapter running to write memory")));
+            }
+        };
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                // Variables stored in the written range now show stale values.
+                this.invalidate_command_type::<VariablesCommand>();
+                cx.notify();
+            })
+        })
+    }
+
+    /// Operations the adapter is reporting progress for, oldest first.
+    pub fn progress(&self) -> impl Iterator<Item = &dap::ProgressStartEvent> {
+        self.progress.values()
+    }
+
+    /// Whether the operation can be cancelled, which relies on the adapter supporting `cancel`.
+    pub fn can_cancel_progress(&self, progress_id: &str) -> bool {
+        CancelCommand::is_supported(&self.capabilities)
+            && self
+                .progress
+                .get(progress_id)
+                .is_some_and(|progress| progress.cancellable.unwrap_or_default())
+    }
+
+    /// Asks the adapter to cancel the operation; it's removed once the adapter ends its progress.
+    pub fn cancel_progress(&mut self, progress_id: String, cx: &mut Context<Self>) {
+        if !self.can_cancel_progress(&progress_id) {
+            return;
+        }
+        let request = CancelCommand { progress_id };
+        let task = match &self.mode {
+            Mode::Running(running) => running.request(request),
+            Mode::Replay(replay) => replay.request(request),
+            Mode::Building | Mode::Remote(_) => return,
+        };
+        task.detach_and_log_err(cx);
+    }
+
+    /// The details of the exception `thread_id` stopped on, for adapters that provide them.
+    pub fn exception_info(&self, thread_id: ThreadId) -> Option<&dap::ExceptionInfoResponse> {
+        self.exception_info
+            .as_ref()
+            .filter(|(exception_thread_id, _)| *exception_thread_id == thread_id)
+            .map(|(_, exception_info)| exception_info)
+    }
+
+    pub fn dismiss_exception_info(&mut self, cx: &mut Context<Self>) {
+        self.clear_exception_info(cx);
+    }
+
+    fn clear_exception_info(&mut self, cx: &mut Context<Self>) {
+        if self.exception_info.take().is_some() {
+            cx.emit(SessionEvent::ExceptionInfo);
+            cx.notify();
+        }
+    }
+
+    fn fetch_exception_info(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if !ExceptionInfoCommand::is_supported(&self.capabilities) {
+            return;
+        }
+        let request = ExceptionInfoCommand {
+            thread_id: thread_id.0,
+        };
+        let task = match &self.mode {
+            Mode::Running(running) => running.request(request),
+            Mode::Replay(replay) => replay.request(request),
+            Mode::Building | Mode::Remote(_) => return,
+        };
+        cx.spawn(async move |this, cx| {
+            let exception_info = task.await?;
+            this.update(cx, |this, cx| {
+                // The thread may have been resumed while the details were requested.
+                if this.thread_states.thread_status(thread_id) == ThreadStatus::Stopped {
+                    this.exception_info = Some((thread_id, exception_info));
+                    cx.emit(SessionEvent::ExceptionInfo);
+                    cx.notify();
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// away from the one `memory_reference` points to.
+    pub fn disassemble(
+        &self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+    ) -> Task<Result<Vec<dap::DisassembledInstruction>>> {
+        if !DisassembleCommand::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "The debug adapter doesn't support disassembling"
+            )));
+        }
+        let request = DisassembleCommand {
+            memory_reference,
+            instruction_offset,
+            instruction_count,
+        };
+        match &self.mode {
+            Mode::Running(running) => running.request(request),
+            Mode::Replay(replay) => replay.request(request),
+            Mode::Building | Mode::Remote(_) => {
+                Task::ready(Err(anyhow!("no adapter running to disassemble")))
+            }
+        }
+    }
+
+    /// Fetches the contents of a source that only the adapter can provide, e.g. decompiled or
+    /// generated code, through its `sourceReference`.
+    pub fn source_content(&self, source: dap::Source) -> Task<Result<dap::SourceResponse>> {
+        let Some(source_reference) = source.source_reference.filter(|reference| *reference > 0)
+        else {
+            return Task::ready(Err(anyhow!("Source has no source reference")));
+        };
+        let request = SourceCommand {
+            source,
+            source_reference,
+        };
+        match &self.mode {
+            Mode::Running(running) => running.request(request),
+            Mode::Replay(replay) => replay.request(request),
+            Mode::Building | Mode::Remote(_) => {
+                Task::ready(Err(anyhow!("no adapter running to fetch source")))
+            }
+        }
+    }
+
     pub fn location(
         &mut self,
         reference: u64,
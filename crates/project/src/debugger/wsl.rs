@@ -0,0 +1,157 @@
+//! Support for debugging programs in worktrees that live inside a WSL distro.
+
+use collections::HashMap;
+use dap::adapters::DebugAdapterBinary;
+use std::path::{Path, PathBuf};
+
+const WSL_HOSTS: [&str; 2] = ["wsl.localhost", "wsl$"];
+
+/// Splits a Windows path into a `\\wsl.localhost\<distro>\...` share into the distro's name and
+/// the path inside of the distro.
+pub fn wsl_path(path: &Path) -> Option<(String, String)> {
+    let path = path.to_string_lossy();
+    let rest = path
+        .strip_prefix(r"\\")
+        .or_else(|| path.strip_prefix("//"))?;
+    let mut components = rest.split(['\\', '/']);
+    let host = components.next()?;
+    if !WSL_HOSTS
+        .iter()
+        .any(|wsl_host| host.eq_ignore_ascii_case(wsl_host))
+    {
+        return None;
+    }
+    let distro = components.next().filter(|distro| !distro.is_empty())?;
+    let linux_path = components
+        .filter(|component| !component.is_empty())
+        .fold(String::new(), |path, component| path + "/" + component);
+    let linux_path = if linux_path.is_empty() {
+        "/".to_string()
+    } else {
+        linux_path
+    };
+    Some((distro.to_string(), linux_path))
+}
+
+/// The inverse of [`wsl_path`].
+pub fn windows_path(distro: &str, linux_path: &str) -> PathBuf {
+    let mut path = format!(r"\\wsl.localhost\{distro}");
+    for component in linux_path
+        .split('/')
+        .filter(|component| !component.is_empty())
+    {
+        path.push('\\');
+        path.push_str(component);
+    }
+    PathBuf::from(path)
+}
+
+/// Translates a Windows path for use inside of `distro`, e.g. `C:\Users` to `/mnt/c/Users`.
+/// Anything that isn't a path is returned unchanged.
+fn to_linux_path(distro: &str, path: &str) -> String {
+    if let Some((path_distro, linux_path)) = wsl_path(Path::new(path)) {
+        if path_distro.eq_ignore_ascii_case(distro) {
+            return linux_path;
+        }
+    }
+    let mut chars = path.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(drive), Some(':'), Some('\\' | '/')) if drive.is_ascii_alphabetic() => {
+            format!(
+                "/mnt/{}/{}",
+                drive.to_ascii_lowercase(),
+                path[3..].replace('\\', "/")
+            )
+        }
+        _ => path.to_string(),
+    }
+}
+
+fn translate_config(distro: &str, value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(string) => *string = to_linux_path(distro, string),
+        serde_json::Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| translate_config(distro, value)),
+        serde_json::Value::Object(values) => values
+            .values_mut()
+            .for_each(|value| translate_config(distro, value)),
+        _ => {}
+    }
+}
+
+/// Rewrites an adapter binary to run inside `distro`, translating the paths it's given.
+pub(crate) fn wrap_for_wsl(distro: &str, binary: DebugAdapterBinary) -> DebugAdapterBinary {
+    let mut arguments = vec!["--distribution".to_string(), distro.to_string()];
+    if let Some(cwd) = &binary.cwd {
+        arguments.push("--cd".to_string());
+        arguments.push(to_linux_path(distro, &cwd.to_string_lossy()));
+    }
+    arguments.push("--exec".to_string());
+    if !binary.envs.is_empty() {
+        let mut envs = binary.envs.iter().collect::<Vec<_>>();
+        envs.sort();
+        arguments.push("env".to_string());
+        arguments.extend(
+            envs.into_iter()
+                .map(|(key, value)| format!("{key}={value}")),
+        );
+    }
+    arguments.extend(
+        binary
+            .command
+            .iter()
+            .chain(&binary.arguments)
+            .map(|argument| to_linux_path(distro, argument)),
+    );
+
+    let mut request_args = binary.request_args;
+    translate_config(distro, &mut request_args.configuration);
+
+    DebugAdapterBinary {
+        command: binary.command.is_some().then(|| "wsl.exe".to_string()),
+        arguments: if binary.command.is_some() {
+            arguments
+        } else {
+            binary.arguments
+        },
+        envs: HashMap::default(),
+        cwd: None,
+        connection: binary.connection,
+        request_args,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wsl_path_translation() {
+        assert_eq!(
+            wsl_path(Path::new(r"\\wsl.localhost\Ubuntu\home\me\project")),
+            Some(("Ubuntu".to_string(), "/home/me/project".to_string()))
+        );
+        assert_eq!(
+            wsl_path(Path::new(r"\\wsl$\Debian\")),
+            Some(("Debian".to_string(), "/".to_string()))
+        );
+        assert_eq!(wsl_path(Path::new(r"\\server\share\project")), None);
+        assert_eq!(wsl_path(Path::new(r"C:\Users\me")), None);
+
+        assert_eq!(
+            windows_path("Ubuntu", "/home/me/project/main.rs"),
+            PathBuf::from(r"\\wsl.localhost\Ubuntu\home\me\project\main.rs")
+        );
+
+        assert_eq!(
+            to_linux_path("Ubuntu", r"\\wsl.localhost\Ubuntu\home\me\a.out"),
+            "/home/me/a.out"
+        );
+        assert_eq!(
+            to_linux_path("Ubuntu", r"C:\Users\me\adapter.js"),
+            "/mnt/c/Users/me/adapter.js"
+        );
+        assert_eq!(to_linux_path("Ubuntu", "--port=1234"), "--port=1234");
+    }
+}
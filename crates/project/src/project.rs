@@ -27,7 +27,7 @@ use buffer_diff::BufferDiff;
 use context_server_store::ContextServerStore;
 pub use environment::{EnvironmentErrorMessage, ProjectEnvironmentEvent};
 use git::repository::get_git_committer;
-use git_store::{Repository, RepositoryId};
+use git_store::{GitStoreEvent, Repository, RepositoryEvent, RepositoryId};
 pub mod search_history;
 mod yarn;
 
@@ -45,7 +45,7 @@ use client::{
 };
 use clock::ReplicaId;
 
-use dap::{DapRegistry, client::DebugAdapterClient};
+use dap::{DapRegistry, client::DebugAdapterClient, debugger_settings::DebuggerSettings};
 
 use collections::{BTreeSet, HashMap, HashSet};
 use debounced_delay::DebouncedDelay;
@@ -909,6 +909,8 @@ impl Project {
     pub fn init_settings(cx: &mut App) {
         WorktreeSettings::register(cx);
         ProjectSettings::register(cx);
+        DebuggerSettings::register(cx);
+        dap::custom_adapter::init(cx);
     }
 
     pub fn init(client: &Arc<Client>, cx: &mut App) {
@@ -1059,6 +1061,7 @@ impl Project {
             });
 
             cx.subscribe(&lsp_store, Self::on_lsp_store_event).detach();
+            cx.subscribe(&git_store, Self::on_git_store_event).detach();
 
             Self {
                 buffer_ordered_messages_tx: tx,
@@ -2941,6 +2944,35 @@ impl Project {
         }
     }
 
+    fn on_git_store_event(
+        &mut self,
+        git_store: Entity<GitStore>,
+        event: &GitStoreEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            GitStoreEvent::ActiveRepositoryChanged(_)
+            | GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::Updated { .. }, true) => {
+                let branch = git_store
+                    .read(cx)
+                    .active_repository()
+                    .and_then(|repository| {
+                        repository
+                            .read(cx)
+                            .branch
+                            .as_ref()
+                            .map(|branch| SharedString::from(branch.name().to_string()))
+                    });
+                self.breakpoint_store
+                    .update(cx, |breakpoint_store, cx| {
+                        breakpoint_store.set_active_branch(branch, cx)
+                    })
+                    .detach_and_log_err(cx);
+            }
+            _ => {}
+        }
+    }
+
     fn on_worktree_store_event(
         &mut self,
         _: Entity<WorktreeStore>,
@@ -4397,6 +4429,7 @@ impl Project {
                 buffer_store.forget_shared_buffers_for(&collaborator.peer_id);
             });
             this.breakpoint_store.read(cx).broadcast();
+            this.dap_store.read(cx).broadcast(cx);
             cx.emit(Event::CollaboratorJoined(collaborator.peer_id));
             this.collaborators
                 .insert(collaborator.peer_id, collaborator);
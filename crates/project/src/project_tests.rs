@@ -9225,3 +9225,121 @@ async fn test_find_project_path_abs(
         );
     });
 }
+
+#[gpui::test]
+async fn test_breakpoints_per_branch(cx: &mut gpui::TestAppContext) {
+    use crate::debugger::breakpoint_store::{
+        Breakpoint, BreakpointEditAction, BreakpointWithPosition,
+    };
+    use dap::debugger_settings::DebuggerSettings;
+
+    init_test(cx);
+    let set_breakpoints_per_branch = |enabled: bool, cx: &mut gpui::TestAppContext| {
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|store, cx| {
+                store.update_user_settings::<DebuggerSettings>(cx, |settings| {
+                    settings.breakpoints_per_branch = enabled;
+                });
+            });
+        });
+    };
+    set_breakpoints_per_branch(true, cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/main.rs"), cx)
+        })
+        .await
+        .unwrap();
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+
+    let toggle_breakpoint = |row: u32, cx: &mut gpui::TestAppContext| {
+        breakpoint_store.update(cx, |store, cx| {
+            let position = buffer.read(cx).anchor_before(Point::new(row, 0));
+            store.toggle_breakpoint(
+                buffer.clone(),
+                BreakpointWithPosition {
+                    position,
+                    bp: Breakpoint::new_standard(),
+                },
+                BreakpointEditAction::Toggle,
+                cx,
+            );
+        });
+    };
+    let set_active_branch = |branch: Option<&'static str>, cx: &mut gpui::TestAppContext| {
+        breakpoint_store
+            .update(cx, |store, cx| {
+                store.set_active_branch(branch.map(SharedString::from), cx)
+            })
+            .detach();
+        cx.run_until_parked();
+    };
+    let breakpoint_rows = |cx: &mut gpui::TestAppContext| {
+        breakpoint_store.read_with(cx, |store, cx| {
+            store
+                .all_source_breakpoints(cx)
+                .into_values()
+                .flatten()
+                .map(|breakpoint| breakpoint.row)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    // The first branch observed takes ownership of the breakpoints restored from the database.
+    toggle_breakpoint(0, cx);
+    set_active_branch(Some("main"), cx);
+    assert_eq!(breakpoint_rows(cx), vec![0]);
+
+    set_active_branch(Some("feature"), cx);
+    assert_eq!(breakpoint_rows(cx), Vec::<u32>::new());
+    toggle_breakpoint(2, cx);
+
+    set_active_branch(Some("main"), cx);
+    assert_eq!(breakpoint_rows(cx), vec![0]);
+    let branch_rows = breakpoint_store.read_with(cx, |store, cx| {
+        store
+            .branch_breakpoints(cx)
+            .into_iter()
+            .map(|(branch, breakpoints)| {
+                let rows = breakpoints
+                    .into_values()
+                    .flatten()
+                    .map(|breakpoint| breakpoint.row)
+                    .collect::<Vec<_>>();
+                (branch, rows)
+            })
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(
+        branch_rows,
+        vec![("feature".into(), vec![2]), ("main".into(), vec![0])]
+    );
+
+    // A detached HEAD keeps the last branch's breakpoints, and they stay stashed under it.
+    set_active_branch(None, cx);
+    assert_eq!(breakpoint_rows(cx), vec![0]);
+    toggle_breakpoint(1, cx);
+    set_active_branch(Some("feature"), cx);
+    assert_eq!(breakpoint_rows(cx), vec![2]);
+    set_active_branch(Some("main"), cx);
+    assert_eq!(breakpoint_rows(cx), vec![0, 1]);
+
+    // With the setting off, switching branches leaves the breakpoints alone, and they're still
+    // owned by the branch that was active when it was turned off.
+    set_breakpoints_per_branch(false, cx);
+    set_active_branch(Some("feature"), cx);
+    assert_eq!(breakpoint_rows(cx), vec![0, 1]);
+    set_breakpoints_per_branch(true, cx);
+    set_active_branch(Some("feature"), cx);
+    assert_eq!(breakpoint_rows(cx), vec![2]);
+}
@@ -86,6 +86,11 @@ impl SearchHistory {
         Some(&self.history[next_index])
     }
 
+    /// The queries in the history, from oldest to newest.
+    pub fn queries(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
     pub fn current(&self, cursor: &SearchHistoryCursor) -> Option<&str> {
         cursor
             .selection
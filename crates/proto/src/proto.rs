@@ -313,7 +313,45 @@ messages!(
     (LogToDebugConsole, Background),
     (GetDocumentDiagnostics, Background),
     (GetDocumentDiagnosticsResponse, Background),
-    (PullWorkspaceDiagnostics, Background)
+    (PullWorkspaceDiagnostics, Background),
+    (UpdateDebugSession, Background),
+    (DebugSessionEvent, Background),
+    (DebugSessionShutdown, Background),
+    (DapNextRequest, Background),
+    (DapStepInRequest, Background),
+    (DapStepOutRequest, Background),
+    (DapStepBackRequest, Background),
+    (DapContinueRequest, Background),
+    (DapContinueResponse, Background),
+    (DapReverseContinueRequest, Background),
+    (DapPauseRequest, Background),
+    (DapDisconnectRequest, Background),
+    (DapTerminateThreadsRequest, Background),
+    (DapTerminateRequest, Background),
+    (DapRestartRequest, Background),
+    (VariablesRequest, Background),
+    (DapVariables, Background),
+    (DapSetVariableValueRequest, Background),
+    (DapSetVariableValueResponse, Background),
+    (DapSetExpressionRequest, Background),
+    (DapSetExpressionResponse, Background),
+    (DapRestartStackFrameRequest, Background),
+    (DapModulesRequest, Background),
+    (DapModulesResponse, Background),
+    (DapLoadedSourcesRequest, Background),
+    (DapLoadedSourcesResponse, Background),
+    (DapStackTraceRequest, Background),
+    (DapStackTraceResponse, Background),
+    (DapScopesRequest, Background),
+    (DapScopesResponse, Background),
+    (DapCompletionRequest, Background),
+    (DapCompletionResponse, Background),
+    (DapEvaluateRequest, Background),
+    (DapEvaluateResponse, Background),
+    (DapThreadsRequest, Background),
+    (DapThreadsResponse, Background),
+    (DapLocationsRequest, Background),
+    (DapLocationsResponse, Background)
 );
 
 request_messages!(
@@ -479,7 +517,30 @@ request_messages!(
     (GetDebugAdapterBinary, DebugAdapterBinary),
     (RunDebugLocators, DebugRequest),
     (GetDocumentDiagnostics, GetDocumentDiagnosticsResponse),
-    (PullWorkspaceDiagnostics, Ack)
+    (PullWorkspaceDiagnostics, Ack),
+    (DapNextRequest, Ack),
+    (DapStepInRequest, Ack),
+    (DapStepOutRequest, Ack),
+    (DapStepBackRequest, Ack),
+    (DapContinueRequest, DapContinueResponse),
+    (DapReverseContinueRequest, Ack),
+    (DapPauseRequest, Ack),
+    (DapDisconnectRequest, Ack),
+    (DapTerminateThreadsRequest, Ack),
+    (DapTerminateRequest, Ack),
+    (DapRestartRequest, Ack),
+    (VariablesRequest, DapVariables),
+    (DapSetVariableValueRequest, DapSetVariableValueResponse),
+    (DapSetExpressionRequest, DapSetExpressionResponse),
+    (DapRestartStackFrameRequest, Ack),
+    (DapModulesRequest, DapModulesResponse),
+    (DapLoadedSourcesRequest, DapLoadedSourcesResponse),
+    (DapStackTraceRequest, DapStackTraceResponse),
+    (DapScopesRequest, DapScopesResponse),
+    (DapCompletionRequest, DapCompletionResponse),
+    (DapEvaluateRequest, DapEvaluateResponse),
+    (DapThreadsRequest, DapThreadsResponse),
+    (DapLocationsRequest, DapLocationsResponse)
 );
 
 entity_messages!(
@@ -609,7 +670,33 @@ entity_messages!(
     GetDebugAdapterBinary,
     LogToDebugConsole,
     GetDocumentDiagnostics,
-    PullWorkspaceDiagnostics
+    PullWorkspaceDiagnostics,
+    UpdateDebugSession,
+    DebugSessionEvent,
+    DebugSessionShutdown,
+    DapNextRequest,
+    DapStepInRequest,
+    DapStepOutRequest,
+    DapStepBackRequest,
+    DapContinueRequest,
+    DapReverseContinueRequest,
+    DapPauseRequest,
+    DapDisconnectRequest,
+    DapTerminateThreadsRequest,
+    DapTerminateRequest,
+    DapRestartRequest,
+    VariablesRequest,
+    DapSetVariableValueRequest,
+    DapSetExpressionRequest,
+    DapRestartStackFrameRequest,
+    DapModulesRequest,
+    DapLoadedSourcesRequest,
+    DapStackTraceRequest,
+    DapScopesRequest,
+    DapCompletionRequest,
+    DapEvaluateRequest,
+    DapThreadsRequest,
+    DapLocationsRequest
 );
 
 entity_messages!(
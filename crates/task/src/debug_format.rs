@@ -258,6 +258,32 @@ pub struct ZedDebugConfig {
     pub stop_on_entry: Option<bool>,
 }
 
+/// What to wait for before attaching to a program
+#[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitForTarget {
+    /// A TCP port on 127.0.0.1 that the program accepts connections on
+    Port(u16),
+    /// A running process with this name, whose id replaces `${pid}` in the configuration
+    Process(String),
+    /// A file that the program writes its process id to, which replaces `${pid}` in the
+    /// configuration
+    PidFile(PathBuf),
+}
+
+/// Something to wait for before starting a debug session, so that a program started with e.g.
+/// `--wait-for-debugger` is attached to as soon as it's ready
+#[derive(Deserialize, Serialize, PartialEq, Eq, JsonSchema, Clone, Debug)]
+pub struct WaitFor {
+    #[serde(flatten)]
+    pub target: WaitForTarget,
+    /// The max amount of time in milliseconds to wait before giving up
+    ///
+    /// Default: 30000ms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
 /// This struct represent a user created debug task
 #[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -278,6 +304,9 @@ pub struct DebugScenario {
     /// that is already running or is started by another process.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tcp_connection: Option<TcpArgumentsTemplate>,
+    /// Optional port, process or pid file to wait for before the session is started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait_for: Option<WaitFor>,
 }
 
 /// A group of Debug Tasks defined in a JSON file.
@@ -370,6 +399,33 @@ impl DebugTaskFile {
                                 "description": "The max amount of time in milliseconds to connect to a tcp DAP before returning an error (default: 2000ms)"
                             }
                         }
+                    },
+                    "wait_for": {
+                        "type": "object",
+                        "description": "Wait for a port, process or pid file before starting the session, e.g. to attach to a program started with `--wait-for-debugger`",
+                        "properties": {
+                            "port": {
+                                "type": "integer",
+                                "description": "Wait until a TCP port on 127.0.0.1 accepts connections"
+                            },
+                            "process": {
+                                "type": "string",
+                                "description": "Wait for a process with this name, and use its id for `${pid}`"
+                            },
+                            "pid_file": {
+                                "type": "string",
+                                "description": "Wait for a file containing a process id, and use it for `${pid}`"
+                            },
+                            "timeout": {
+                                "type": "integer",
+                                "description": "The max amount of time in milliseconds to wait before giving up (default: 30000ms)"
+                            }
+                        },
+                        "oneOf": [
+                            { "required": ["port"] },
+                            { "required": ["process"] },
+                            { "required": ["pid_file"] }
+                        ]
                     }
                 },
                 "allOf": adapter_conditions
@@ -464,6 +520,46 @@ mod tests {
         assert_eq!("Attach to process", deserialized.label.as_ref());
     }
 
+    #[test]
+    fn test_attach_scenario_with_wait_for() {
+        let json = r#"{
+            "label": "Attach when ready",
+            "adapter": "CodeLLDB",
+            "request": "attach",
+            "pid": "${pid}",
+            "wait_for": { "process": "server", "timeout": 60000 }
+        }"#;
+
+        let deserialized: DebugScenario = serde_json::from_str(json).unwrap();
+
+        assert_eq!(
+            json!({ "request": "attach", "pid": "${pid}" }),
+            deserialized.config
+        );
+        assert_eq!(
+            Some(crate::WaitFor {
+                target: crate::WaitForTarget::Process("server".into()),
+                timeout: Some(60000),
+            }),
+            deserialized.wait_for
+        );
+
+        let json = r#"{
+            "label": "Attach to port",
+            "adapter": "CodeLLDB",
+            "request": "attach",
+            "wait_for": { "port": 9229 }
+        }"#;
+        let deserialized: DebugScenario = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            Some(crate::WaitFor {
+                target: crate::WaitForTarget::Port(9229),
+                timeout: None,
+            }),
+            deserialized.wait_for
+        );
+    }
+
     #[test]
     fn test_build_task_definition_without_label() {
         use crate::BuildTaskDefinition;
@@ -48,6 +48,7 @@ impl VsCodeDebugTaskDefinition {
                 timeout: None,
             }),
             config,
+            wait_for: None,
         };
         Ok(definition)
     }
@@ -155,7 +156,8 @@ mod tests {
                     "port": 17,
                 }),
                 tcp_connection: None,
-                build: None
+                build: None,
+                wait_for: None,
             }])
         );
     }
@@ -585,6 +585,14 @@ impl ContextMenu {
         self
     }
 
+    /// Returns the labels of the menu's entries, in order.
+    pub fn entry_labels(&self) -> impl Iterator<Item = &SharedString> {
+        self.items.iter().filter_map(|item| match item {
+            ContextMenuItem::Entry(entry) => Some(&entry.label),
+            _ => None,
+        })
+    }
+
     pub fn confirm(&mut self, _: &menu::Confirm, window: &mut Window, cx: &mut Context<Self>) {
         let context = self.action_context.as_ref();
         if let Some(
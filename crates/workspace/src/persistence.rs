@@ -13,7 +13,7 @@ use client::DevServerProjectId;
 use db::{define_connection, query, sqlez::connection::Connection, sqlez_macros::sql};
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use itertools::Itertools;
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::debugger::breakpoint_store::{BranchBreakpoints, BreakpointState, SourceBreakpoint};
 
 use language::{LanguageName, Toolchain};
 use project::WorktreeId;
@@ -151,6 +151,7 @@ pub struct Breakpoint {
     pub message: Option<Arc<str>>,
     pub condition: Option<Arc<str>>,
     pub hit_condition: Option<Arc<str>>,
+    pub note: Option<Arc<str>>,
     pub state: BreakpointState,
 }
 
@@ -193,8 +194,8 @@ struct Breakpoints(Vec<Breakpoint>);
 
 impl sqlez::bindable::StaticColumnCount for Breakpoint {
     fn column_count() -> usize {
-        // Position, log message, condition message, and hit condition message
-        4 + BreakpointStateWrapper::column_count()
+        // Position, log message, condition message, hit condition message, and note
+        5 + BreakpointStateWrapper::column_count()
     }
 }
 
@@ -208,6 +209,7 @@ impl sqlez::bindable::Bind for Breakpoint {
         let next_index = statement.bind(&self.message, next_index)?;
         let next_index = statement.bind(&self.condition, next_index)?;
         let next_index = statement.bind(&self.hit_condition, next_index)?;
+        let next_index = statement.bind(&self.note, next_index)?;
         statement.bind(
             &BreakpointStateWrapper(Cow::Borrowed(&self.state)),
             next_index,
@@ -224,6 +226,7 @@ impl Column for Breakpoint {
         let (message, next_index) = Option::<String>::column(statement, start_index + 1)?;
         let (condition, next_index) = Option::<String>::column(statement, next_index)?;
         let (hit_condition, next_index) = Option::<String>::column(statement, next_index)?;
+        let (note, next_index) = Option::<String>::column(statement, next_index)?;
         let (state, next_index) = BreakpointStateWrapper::column(statement, next_index)?;
 
         Ok((
@@ -232,6 +235,7 @@ impl Column for Breakpoint {
                 message: message.map(Arc::from),
                 condition: condition.map(Arc::from),
                 hit_condition: hit_condition.map(Arc::from),
+                note: note.map(Arc::from),
                 state: state.0.into_owned(),
             },
             next_index,
@@ -542,6 +546,25 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        CREATE TABLE branch_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            branch TEXT NOT NULL,
+            path TEXT NOT NULL,
+            breakpoint_location INTEGER NOT NULL,
+            log_message TEXT,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER DEFAULT(0) NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
+    sql!(
+        ALTER TABLE breakpoints ADD COLUMN note TEXT;
+        ALTER TABLE branch_breakpoints ADD COLUMN note TEXT;
+    ),
     ];
 }
 
@@ -630,6 +653,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            branch_breakpoints: self.branch_breakpoints(workspace_id),
             window_id,
         })
     }
@@ -684,6 +708,7 @@ impl WorkspaceDb {
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
+            branch_breakpoints: self.branch_breakpoints(workspace_id),
             display,
             docks,
             session_id: None,
@@ -694,7 +719,7 @@ impl WorkspaceDb {
     fn breakpoints(&self, workspace_id: WorkspaceId) -> BTreeMap<Arc<Path>, Vec<SourceBreakpoint>> {
         let breakpoints: Result<Vec<(PathBuf, Breakpoint)>> = self
             .select_bound(sql! {
-                SELECT path, breakpoint_location, log_message, condition, hit_condition, state
+                SELECT path, breakpoint_location, log_message, condition, hit_condition, note, state
                 FROM breakpoints
                 WHERE workspace_id = ?
             })
@@ -716,6 +741,7 @@ impl WorkspaceDb {
                         message: breakpoint.message,
                         condition: breakpoint.condition,
                         hit_condition: breakpoint.hit_condition,
+                        note: breakpoint.note,
                         state: breakpoint.state,
                     });
                 }
@@ -737,6 +763,45 @@ impl WorkspaceDb {
         }
     }
 
+    fn branch_breakpoints(&self, workspace_id: WorkspaceId) -> BranchBreakpoints {
+        let breakpoints: Result<Vec<(String, PathBuf, Breakpoint)>> = self
+            .select_bound(sql! {
+                SELECT branch, path, breakpoint_location, log_message, condition, hit_condition, note, state
+                FROM branch_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match breakpoints {
+            Ok(bp) => {
+                let mut map = BranchBreakpoints::default();
+
+                for (branch, path, breakpoint) in bp {
+                    let path: Arc<Path> = path.into();
+                    map.entry(branch.into())
+                        .or_default()
+                        .entry(path.clone())
+                        .or_default()
+                        .push(SourceBreakpoint {
+                            row: breakpoint.position,
+                            path,
+                            message: breakpoint.message,
+                            condition: breakpoint.condition,
+                            hit_condition: breakpoint.hit_condition,
+                            note: breakpoint.note,
+                            state: breakpoint.state,
+                        });
+                }
+
+                map
+            }
+            Err(msg) => {
+                log::error!("Branch breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -755,8 +820,8 @@ impl WorkspaceDb {
                     for bp in breakpoints {
                         let state = BreakpointStateWrapper::from(bp.state);
                         match conn.exec_bound(sql!(
-                            INSERT INTO breakpoints (workspace_id, path, breakpoint_location,  log_message, condition, hit_condition, state)
-                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+                            INSERT INTO breakpoints (workspace_id, path, breakpoint_location,  log_message, condition, hit_condition, note, state)
+                            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8);))?
 
                         ((
                             workspace.id,
@@ -765,6 +830,7 @@ impl WorkspaceDb {
                             bp.message,
                             bp.condition,
                             bp.hit_condition,
+                            bp.note,
                             state,
                         )) {
                             Ok(_) => {
@@ -779,6 +845,32 @@ impl WorkspaceDb {
 
                 }
 
+                conn.exec_bound(sql!(DELETE FROM branch_breakpoints WHERE workspace_id = ?1))?(workspace.id).context("Clearing old branch breakpoints")?;
+
+                for (branch, breakpoints) in workspace.branch_breakpoints {
+                    for (path, breakpoints) in breakpoints {
+                        for bp in breakpoints {
+                            let state = BreakpointStateWrapper::from(bp.state);
+                            if let Err(err) = conn.exec_bound(sql!(
+                                INSERT INTO branch_breakpoints (workspace_id, branch, path, breakpoint_location, log_message, condition, hit_condition, note, state)
+                                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9);))?
+
+                            ((
+                                workspace.id,
+                                branch.as_ref(),
+                                path.as_ref(),
+                                bp.row,
+                                bp.message,
+                                bp.condition,
+                                bp.hit_condition,
+                                bp.note,
+                                state,
+                            )) {
+                                log::error!("{err}");
+                            }
+                        }
+                    }
+                }
 
                 match workspace.location {
                     SerializedWorkspaceLocation::Local(local_paths, local_paths_order) => {
@@ -1479,6 +1571,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: None,
+            note: None,
         };
 
         let log_breakpoint = Breakpoint {
@@ -1487,6 +1580,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: None,
+            note: None,
         };
 
         let disable_breakpoint = Breakpoint {
@@ -1495,6 +1589,7 @@ mod tests {
             state: BreakpointState::Disabled,
             condition: None,
             hit_condition: None,
+            note: None,
         };
 
         let condition_breakpoint = Breakpoint {
@@ -1503,6 +1598,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: Some("x > 5".into()),
             hit_condition: None,
+            note: Some("this is where the race shows up".into()),
         };
 
         let hit_condition_breakpoint = Breakpoint {
@@ -1511,6 +1607,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: Some(">= 3".into()),
+            note: None,
         };
 
         let workspace = SerializedWorkspace {
@@ -1521,6 +1618,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -1533,6 +1631,7 @@ mod tests {
                             state: breakpoint.state,
                             condition: breakpoint.condition.clone(),
                             hit_condition: breakpoint.hit_condition.clone(),
+                            note: breakpoint.note.clone(),
                         },
                         SourceBreakpoint {
                             row: log_breakpoint.position,
@@ -1541,6 +1640,7 @@ mod tests {
                             state: log_breakpoint.state,
                             condition: log_breakpoint.condition.clone(),
                             hit_condition: log_breakpoint.hit_condition.clone(),
+                            note: log_breakpoint.note.clone(),
                         },
                         SourceBreakpoint {
                             row: disable_breakpoint.position,
@@ -1549,6 +1649,7 @@ mod tests {
                             state: disable_breakpoint.state,
                             condition: disable_breakpoint.condition.clone(),
                             hit_condition: disable_breakpoint.hit_condition.clone(),
+                            note: disable_breakpoint.note.clone(),
                         },
                         SourceBreakpoint {
                             row: condition_breakpoint.position,
@@ -1557,6 +1658,7 @@ mod tests {
                             state: condition_breakpoint.state,
                             condition: condition_breakpoint.condition.clone(),
                             hit_condition: condition_breakpoint.hit_condition.clone(),
+                            note: condition_breakpoint.note.clone(),
                         },
                         SourceBreakpoint {
                             row: hit_condition_breakpoint.position,
@@ -1565,6 +1667,7 @@ mod tests {
                             state: hit_condition_breakpoint.state,
                             condition: hit_condition_breakpoint.condition.clone(),
                             hit_condition: hit_condition_breakpoint.hit_condition.clone(),
+                            note: hit_condition_breakpoint.note.clone(),
                         },
                     ],
                 );
@@ -1628,6 +1731,7 @@ mod tests {
             loaded_breakpoints[3].hit_condition,
             condition_breakpoint.hit_condition
         );
+        assert_eq!(loaded_breakpoints[3].note, condition_breakpoint.note);
         assert_eq!(loaded_breakpoints[3].state, condition_breakpoint.state);
         assert_eq!(loaded_breakpoints[3].path, Arc::from(path));
 
@@ -1664,6 +1768,7 @@ mod tests {
             state: BreakpointState::Enabled,
             condition: None,
             hit_condition: None,
+            note: None,
         };
 
         let workspace = SerializedWorkspace {
@@ -1674,6 +1779,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: {
                 let mut map = collections::BTreeMap::default();
                 map.insert(
@@ -1685,6 +1791,7 @@ mod tests {
                         state: BreakpointState::Enabled,
                         condition: None,
                         hit_condition: None,
+                        note: None,
                     }],
                 );
                 map
@@ -1720,6 +1827,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: collections::BTreeMap::default(),
             session_id: None,
             window_id: None,
@@ -1736,6 +1844,60 @@ mod tests {
         assert!(empty_breakpoints.is_none());
     }
 
+    #[gpui::test]
+    async fn test_branch_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_branch_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let path = Path::new("/tmp/test_branch_breakpoints.rs");
+        let stashed_breakpoint = SourceBreakpoint {
+            row: 42,
+            path: Arc::from(path),
+            message: Some("on feature branch".into()),
+            state: BreakpointState::Disabled,
+            condition: None,
+            hit_condition: None,
+            note: None,
+        };
+
+        let workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            branch_breakpoints: {
+                let mut map = BranchBreakpoints::default();
+                map.entry("feature".into())
+                    .or_default()
+                    .insert(Arc::from(path), vec![stashed_breakpoint.clone()]);
+                map
+            },
+            breakpoints: Default::default(),
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.breakpoints.is_empty());
+        assert_eq!(loaded.branch_breakpoints, workspace.branch_breakpoints);
+
+        let workspace_without_stash = SerializedWorkspace {
+            branch_breakpoints: Default::default(),
+            ..workspace
+        };
+        db.save_workspace(workspace_without_stash).await;
+
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.branch_breakpoints.is_empty());
+    }
+
     #[gpui::test]
     async fn test_next_id_stability() {
         zlog::init_test();
@@ -1814,6 +1976,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -1827,6 +1990,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -1932,6 +2096,7 @@ mod tests {
             ),
             center_group,
             window_bounds: Default::default(),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -1967,6 +2132,7 @@ mod tests {
             ),
             center_group: Default::default(),
             window_bounds: Default::default(),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -1983,6 +2149,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
@@ -2023,6 +2190,7 @@ mod tests {
             ),
             center_group: Default::default(),
             window_bounds: Default::default(),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -2063,6 +2231,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
@@ -2076,6 +2245,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
@@ -2089,6 +2259,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
@@ -2102,6 +2273,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: None,
             window_id: None,
@@ -2120,6 +2292,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
@@ -2133,6 +2306,7 @@ mod tests {
             ),
             center_group: Default::default(),
             window_bounds: Default::default(),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
@@ -2191,6 +2365,7 @@ mod tests {
             window_bounds: Default::default(),
             display: Default::default(),
             docks: Default::default(),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
@@ -2239,6 +2414,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id),
         })
@@ -2332,6 +2508,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
+            branch_breakpoints: Default::default(),
             breakpoints: Default::default(),
             window_id: Some(window_id),
         })
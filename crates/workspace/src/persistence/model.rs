@@ -10,7 +10,10 @@ use db::sqlez::{
 };
 use gpui::{AsyncWindowContext, Entity, WeakEntity};
 use itertools::Itertools as _;
-use project::{Project, debugger::breakpoint_store::SourceBreakpoint};
+use project::{
+    Project,
+    debugger::breakpoint_store::{BranchBreakpoints, SourceBreakpoint},
+};
 use remote::ssh_session::SshProjectId;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -265,6 +268,7 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) docks: DockStructure,
     pub(crate) session_id: Option<String>,
     pub(crate) breakpoints: BTreeMap<Arc<Path>, Vec<SourceBreakpoint>>,
+    pub(crate) branch_breakpoints: BranchBreakpoints,
     pub(crate) window_id: Option<u64>,
 }
 
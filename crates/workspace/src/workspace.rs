@@ -5051,11 +5051,12 @@ impl Workspace {
         }
 
         if let Some(location) = self.serialize_workspace_location(cx) {
-            let breakpoints = self.project.update(cx, |project, cx| {
-                project
-                    .breakpoint_store()
-                    .read(cx)
-                    .all_source_breakpoints(cx)
+            let (breakpoints, branch_breakpoints) = self.project.update(cx, |project, cx| {
+                let breakpoint_store = project.breakpoint_store().read(cx);
+                (
+                    breakpoint_store.all_source_breakpoints(cx),
+                    breakpoint_store.branch_breakpoints(cx),
+                )
             });
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
@@ -5071,6 +5072,7 @@ impl Workspace {
                 centered_layout: self.centered_layout,
                 session_id: self.session_id.clone(),
                 breakpoints,
+                branch_breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
             };
 
@@ -5242,6 +5244,9 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_branch_breakpoints(
+                                serialized_workspace.branch_breakpoints,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })